@@ -3,7 +3,6 @@
 #[global_allocator]
 static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::new_allocator();
 
-use clap::Parser;
 use reth::{args::RessArgs, cli::Cli, ress::install_ress_subprotocol};
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
 use reth_node_builder::NodeHandle;
@@ -18,8 +17,8 @@ fn main() {
         unsafe { std::env::set_var("RUST_BACKTRACE", "1") };
     }
 
-    if let Err(err) =
-        Cli::<EthereumChainSpecParser, RessArgs>::parse().run(async move |builder, ress_args| {
+    if let Err(err) = Cli::<EthereumChainSpecParser, RessArgs>::parse_with_config().run(
+        async move |builder, ress_args| {
             info!(target: "reth::cli", "Launching node");
             let NodeHandle { node, node_exit_future } =
                 builder.node(EthereumNode::default()).launch_with_debug_capabilities().await?;
@@ -37,8 +36,8 @@ fn main() {
             }
 
             node_exit_future.await
-        })
-    {
+        },
+    ) {
         eprintln!("Error: {err:?}");
         std::process::exit(1);
     }
@@ -1,4 +1,4 @@
-use std::{fs, path::PathBuf, sync::Arc};
+use std::{fs, io, path::PathBuf, sync::Arc};
 
 use clap::builder::TypedValueParser;
 
@@ -71,7 +71,13 @@ pub trait ChainSpecParser: Clone + Send + Sync + 'static {
 }
 
 /// A helper to parse a [`Genesis`](alloy_genesis::Genesis) as argument or from disk.
+///
+/// The genesis is read from stdin if the argument is `-`.
 pub fn parse_genesis(s: &str) -> eyre::Result<alloy_genesis::Genesis> {
+    if s == "-" {
+        return Ok(serde_json::from_reader(io::stdin().lock())?)
+    }
+
     // try to read json from path first
     let raw = match fs::read_to_string(PathBuf::from(shellexpand::full(s)?.into_owned())) {
         Ok(raw) => raw,
@@ -0,0 +1,260 @@
+//! Defaults for CLI arguments loaded from the configuration file.
+//!
+//! The `[cli]` table of the configuration file holds values keyed by the long name of an argument:
+//!
+//! ```toml
+//! [cli]
+//! datadir = "/data/reth"
+//! "log.file.filter" = "debug,reth_stages=trace"
+//! verbosity = 4
+//! ```
+//!
+//! These values replace the built-in defaults of the arguments, so an argument passed on the
+//! command line takes precedence over its environment variable, which takes precedence over the
+//! configuration file, which takes precedence over the built-in default.
+
+use clap::{parser::ValueSource, ArgAction, ArgMatches, Command};
+use eyre::{bail, eyre};
+use std::{collections::BTreeMap, fmt, path::Path};
+
+/// The table of the configuration file that holds the CLI defaults.
+pub const CLI_DEFAULTS_TABLE: &str = "cli";
+
+/// Defaults for CLI arguments, keyed by the long name of the argument.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CliDefaults(BTreeMap<String, String>);
+
+impl CliDefaults {
+    /// Loads the defaults from the `[cli]` table of the TOML file at the given path.
+    ///
+    /// Returns empty defaults if the file does not exist.
+    pub fn from_path(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Self::from_toml(&contents)
+                .map_err(|err| eyre!("Invalid config file {}: {err}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(eyre!("Failed to read config file {}: {err}", path.display())),
+        }
+    }
+
+    /// Parses the defaults from the `[cli]` table of a TOML document.
+    pub fn from_toml(contents: &str) -> eyre::Result<Self> {
+        let mut config: toml::Table =
+            contents.parse().map_err(|err| eyre!("Failed to parse TOML: {err}"))?;
+        let Some(table) = config.remove(CLI_DEFAULTS_TABLE) else { return Ok(Self::default()) };
+        let toml::Value::Table(table) = table else {
+            bail!("`{CLI_DEFAULTS_TABLE}` must be a table")
+        };
+
+        table
+            .into_iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    toml::Value::String(value) => value,
+                    toml::Value::Integer(value) => value.to_string(),
+                    toml::Value::Float(value) => value.to_string(),
+                    toml::Value::Boolean(value) => value.to_string(),
+                    value => bail!("Unsupported value for `{name}`: {value}"),
+                };
+                Ok((name, value))
+            })
+            .collect::<eyre::Result<_>>()
+            .map(Self)
+    }
+
+    /// Returns `true` if there are no defaults.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the default for the argument with the given long name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Sets the defaults of the arguments of `command` and all of its subcommands.
+    pub fn apply(&self, mut command: Command) -> Command {
+        if self.is_empty() {
+            return command
+        }
+
+        let args = command
+            .get_arguments()
+            .filter_map(|arg| Some((arg.get_id().clone(), self.get(arg.get_long()?)?.to_string())))
+            .collect::<Vec<_>>();
+        for (id, value) in args {
+            command = command.mut_arg(id, |arg| arg.default_value(value));
+        }
+
+        let subcommands =
+            command.get_subcommands().map(|sub| sub.get_name().to_string()).collect::<Vec<_>>();
+        for name in subcommands {
+            command = command.mut_subcommand(name, |sub| self.apply(sub));
+        }
+        command
+    }
+
+    /// Returns the effective values of the arguments of `command`, followed by the defaults that
+    /// are not arguments of `command`.
+    ///
+    /// `command` must be the command `matches` were parsed with, after applying these defaults.
+    pub fn effective_values(&self, command: &Command, matches: &ArgMatches) -> Vec<EffectiveValue> {
+        let mut values = Vec::new();
+        for arg in command.get_arguments() {
+            let Some(name) = arg.get_long() else { continue };
+            let id = arg.get_id().as_str();
+            let value = match arg.get_action() {
+                ArgAction::Count => matches.get_count(id).to_string(),
+                ArgAction::Help |
+                ArgAction::HelpShort |
+                ArgAction::HelpLong |
+                ArgAction::Version => continue,
+                _ => match matches.get_raw(id) {
+                    Some(raw) => {
+                        raw.map(|value| value.to_string_lossy()).collect::<Vec<_>>().join(",")
+                    }
+                    None => continue,
+                },
+            };
+            let origin = match matches.value_source(id) {
+                Some(ValueSource::CommandLine) => ValueOrigin::CommandLine,
+                Some(ValueSource::EnvVariable) => ValueOrigin::Environment,
+                _ if self.get(name).is_some() => ValueOrigin::ConfigFile,
+                _ => ValueOrigin::Default,
+            };
+            values.push(EffectiveValue { name: name.to_string(), value, origin });
+        }
+
+        for (name, value) in &self.0 {
+            if !values.iter().any(|effective| effective.name == *name) {
+                values.push(EffectiveValue {
+                    name: name.clone(),
+                    value: value.clone(),
+                    origin: ValueOrigin::ConfigFile,
+                });
+            }
+        }
+        values
+    }
+}
+
+/// The effective value of a CLI argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectiveValue {
+    /// The long name of the argument.
+    pub name: String,
+    /// The value of the argument.
+    pub value: String,
+    /// Where the value comes from.
+    pub origin: ValueOrigin,
+}
+
+impl fmt::Display for EffectiveValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} = {:?} # {}", self.name, self.value, self.origin)
+    }
+}
+
+/// Where the effective value of a CLI argument comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueOrigin {
+    /// Passed on the command line.
+    CommandLine,
+    /// Read from an environment variable.
+    Environment,
+    /// Read from the `[cli]` table of the configuration file.
+    ConfigFile,
+    /// The built-in default.
+    Default,
+}
+
+impl fmt::Display for ValueOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CommandLine => write!(f, "cli"),
+            Self::Environment => write!(f, "env"),
+            Self::ConfigFile => write!(f, "config file"),
+            Self::Default => write!(f, "default"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, FromArgMatches, Parser};
+
+    #[derive(Debug, Parser)]
+    struct Args {
+        #[arg(long = "log.filter", default_value = "info")]
+        filter: String,
+        /// Read from a variable that cargo sets for every test, so that the test doesn't have to
+        /// modify the environment.
+        #[arg(long = "package", env = "CARGO_PKG_NAME", default_value = "reth")]
+        package: String,
+        #[arg(long, default_value = "reth.log")]
+        name: String,
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 3)]
+        verbosity: u8,
+    }
+
+    fn parse(defaults: &CliDefaults, args: &[&str]) -> (Args, Vec<EffectiveValue>) {
+        let command = defaults.apply(Args::command());
+        let matches = command.clone().try_get_matches_from(args).unwrap();
+        let effective = defaults.effective_values(&command, &matches);
+        (Args::from_arg_matches(&matches).unwrap(), effective)
+    }
+
+    #[test]
+    fn precedence() {
+        let defaults = CliDefaults::from_toml(
+            "[cli]\n\"log.filter\" = \"debug\"\npackage = \"config\"\nverbosity = 4\n",
+        )
+        .unwrap();
+
+        let (args, effective) = parse(&defaults, &["reth"]);
+        assert_eq!(args.filter, "debug");
+        assert_eq!(args.package, env!("CARGO_PKG_NAME"));
+        assert_eq!(args.name, "reth.log");
+        assert_eq!(args.verbosity, 4);
+        assert_eq!(
+            effective.iter().map(|value| value.origin).collect::<Vec<_>>(),
+            [
+                ValueOrigin::ConfigFile,
+                ValueOrigin::Environment,
+                ValueOrigin::Default,
+                ValueOrigin::ConfigFile
+            ]
+        );
+
+        let args = ["reth", "--log.filter", "trace", "--package", "cli", "-vv"];
+        let (args, effective) = parse(&defaults, &args);
+        assert_eq!(args.filter, "trace");
+        assert_eq!(args.package, "cli");
+        assert_eq!(args.verbosity, 2);
+        assert_eq!(effective[0].origin, ValueOrigin::CommandLine);
+        assert_eq!(effective[1].origin, ValueOrigin::CommandLine);
+        assert_eq!(effective[3].origin, ValueOrigin::CommandLine);
+    }
+
+    #[test]
+    fn unknown_defaults_are_reported() {
+        let defaults = CliDefaults::from_toml("[cli]\ndatadir = \"/data\"\n").unwrap();
+        let (_, effective) = parse(&defaults, &["reth"]);
+        assert_eq!(
+            effective.last(),
+            Some(&EffectiveValue {
+                name: "datadir".to_string(),
+                value: "/data".to_string(),
+                origin: ValueOrigin::ConfigFile,
+            })
+        );
+    }
+
+    #[test]
+    fn missing_table() {
+        assert!(CliDefaults::from_toml("[stages]\n").unwrap().is_empty());
+        assert!(CliDefaults::from_toml("cli = 1\n").is_err());
+    }
+}
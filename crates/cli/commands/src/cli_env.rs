@@ -0,0 +1,140 @@
+//! Environment variables for CLI arguments.
+//!
+//! Every argument with a long name can be set with an environment variable, named after the long
+//! name in upper case with [`CLI_ENV_PREFIX`], and `.` and `-` replaced by `_`:
+//!
+//! ```text
+//! --datadir              RETH_DATADIR
+//! --log.stdout.filter    RETH_LOG_STDOUT_FILTER
+//! --http.api             RETH_HTTP_API
+//! ```
+//!
+//! An argument passed on the command line takes precedence over its environment variable.
+//!
+//! The environment variables are added to the command by [`with_env`] instead of being declared
+//! on every argument, so that new arguments can't miss them.
+
+use clap::{Arg, ArgAction, Command};
+use std::collections::BTreeMap;
+
+/// The prefix of the environment variables of the CLI arguments.
+pub const CLI_ENV_PREFIX: &str = "RETH_";
+
+/// Returns the name of the environment variable of the argument with the given long name.
+pub fn env_name(long: &str) -> String {
+    let name = long.to_ascii_uppercase().replace(['.', '-'], "_");
+    format!("{CLI_ENV_PREFIX}{name}")
+}
+
+/// Returns `true` if the argument can't be set with an environment variable.
+///
+/// These are the arguments without a long name, the help and version flags, and the counted flags
+/// like `-vvv`, which can't be read from a single value.
+pub fn is_exempt(arg: &Arg) -> bool {
+    arg.get_long().is_none() ||
+        matches!(
+            arg.get_action(),
+            ArgAction::Help |
+                ArgAction::HelpShort |
+                ArgAction::HelpLong |
+                ArgAction::Version |
+                ArgAction::Count
+        )
+}
+
+/// Adds the environment variables to the arguments of `command` and all of its subcommands.
+///
+/// Arguments that already read a standard environment variable keep it, like the `OTEL_*`
+/// variables of the tracing arguments. The variables are hidden from the help, which would
+/// otherwise list one for every argument.
+pub fn with_env(command: Command) -> Command {
+    let mut command = command.mut_args(|arg| {
+        if is_exempt(&arg) || arg.get_env().is_some() {
+            return arg
+        }
+        let name = env_name(arg.get_long().expect("not exempt"));
+        arg.env(name).hide_env(true)
+    });
+
+    let subcommands =
+        command.get_subcommands().map(|sub| sub.get_name().to_string()).collect::<Vec<_>>();
+    for name in subcommands {
+        command = command.mut_subcommand(name, with_env);
+    }
+    command
+}
+
+/// Returns the environment variables of the arguments of `command` and all of its subcommands,
+/// keyed by the long name of the argument.
+///
+/// `command` must have gone through [`with_env`].
+pub fn env_vars(command: &Command) -> BTreeMap<String, String> {
+    let mut vars = BTreeMap::new();
+    for arg in command.get_arguments() {
+        if let (Some(long), Some(env)) = (arg.get_long(), arg.get_env()) {
+            vars.insert(long.to_string(), env.to_string_lossy().into_owned());
+        }
+    }
+    for sub in command.get_subcommands() {
+        vars.extend(env_vars(sub));
+    }
+    vars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, FromArgMatches, Parser};
+
+    #[derive(Debug, Parser)]
+    struct Args {
+        #[arg(long = "cli-env.test-name", default_value = "reth")]
+        name: String,
+        #[arg(long = "cli-env.test-flag")]
+        flag: bool,
+        #[arg(short, long, action = ArgAction::Count)]
+        verbosity: u8,
+    }
+
+    fn parse(args: &[&str]) -> Args {
+        let matches = with_env(Args::command()).try_get_matches_from(args).unwrap();
+        Args::from_arg_matches(&matches).unwrap()
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(env_name("datadir"), "RETH_DATADIR");
+        assert_eq!(env_name("log.stdout.filter"), "RETH_LOG_STDOUT_FILTER");
+        assert_eq!(env_name("datadir.static-files"), "RETH_DATADIR_STATIC_FILES");
+
+        let vars = env_vars(&with_env(Args::command()));
+        assert_eq!(
+            vars.into_iter().collect::<Vec<_>>(),
+            [
+                ("cli-env.test-flag".to_string(), "RETH_CLI_ENV_TEST_FLAG".to_string()),
+                ("cli-env.test-name".to_string(), "RETH_CLI_ENV_TEST_NAME".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn precedence() {
+        unsafe {
+            std::env::set_var("RETH_CLI_ENV_TEST_NAME", "env");
+            std::env::set_var("RETH_CLI_ENV_TEST_FLAG", "true");
+        }
+        let args = parse(&["reth"]);
+        assert_eq!(args.name, "env");
+        assert!(args.flag);
+
+        let args = parse(&["reth", "--cli-env.test-name", "cli", "-vv"]);
+        assert_eq!(args.name, "cli");
+        assert_eq!(args.verbosity, 2);
+        unsafe {
+            std::env::remove_var("RETH_CLI_ENV_TEST_NAME");
+            std::env::remove_var("RETH_CLI_ENV_TEST_FLAG");
+        }
+
+        assert_eq!(parse(&["reth"]).name, "reth");
+    }
+}
@@ -9,7 +9,7 @@ use reth_cli::chainspec::ChainSpecParser;
 use reth_config::{config::EtlConfig, Config};
 use reth_consensus::noop::NoopConsensus;
 use reth_db::{init_db, open_db_read_only, DatabaseEnv};
-use reth_db_common::init::init_genesis_with_settings;
+use reth_db_common::{consistency::StorageHeights, init::init_genesis_with_settings};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_eth_wire::NetPrimitivesFor;
 use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
@@ -162,6 +162,11 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
         )?
         .with_prune_modes(prune_modes.clone());
 
+        // Heights before healing, to log what the consistency check changed
+        let heights = (!access.is_read_only_inconsistent())
+            .then(|| factory.provider().and_then(|provider| StorageHeights::read(&provider)))
+            .transpose()?;
+
         // Check for consistency between database and static files.
         if !access.is_read_only_inconsistent() &&
             let Some(unwind_target) =
@@ -204,6 +209,10 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
             pipeline.unwind(unwind_target.unwind_target().expect("should exist"), None)?;
         }
 
+        if let Some(heights) = heights {
+            heights.log_changes(&StorageHeights::read(&factory.provider()?)?);
+        }
+
         Ok(factory)
     }
 }
@@ -265,9 +274,11 @@ where
     type NetworkPrimitives = <<<N::ComponentsBuilder as NodeComponentsBuilder<FullTypesAdapter<Self>>>::Components as NodeComponents<FullTypesAdapter<Self>>>::Network as NetworkEventListenerProvider>::Primitives;
 }
 
-type EvmFor<N> = <<<N as Node<FullTypesAdapter<N>>>::ComponentsBuilder as NodeComponentsBuilder<
-    FullTypesAdapter<N>,
->>::Components as NodeComponents<FullTypesAdapter<N>>>::Evm;
+/// The EVM configuration of the components of the node type `N`.
+pub type EvmFor<N> =
+    <<<N as Node<FullTypesAdapter<N>>>::ComponentsBuilder as NodeComponentsBuilder<
+        FullTypesAdapter<N>,
+    >>::Components as NodeComponents<FullTypesAdapter<N>>>::Evm;
 
 type ConsensusFor<N> =
     <<<N as Node<FullTypesAdapter<N>>>::ComponentsBuilder as NodeComponentsBuilder<
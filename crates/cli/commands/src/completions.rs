@@ -0,0 +1,35 @@
+//! Command that generates shell completions.
+
+use clap::{CommandFactory, Parser};
+use std::io::Write;
+
+pub use clap_complete::Shell;
+
+/// `reth completions` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The shell to generate completions for.
+    #[arg(value_enum)]
+    shell: Shell,
+}
+
+impl Command {
+    /// Creates a new command generating completions for the given shell.
+    pub const fn new(shell: Shell) -> Self {
+        Self { shell }
+    }
+
+    /// Execute `completions` command, writing the completions for the command tree of `T` to
+    /// stdout.
+    pub fn execute<T: CommandFactory>(&self) -> eyre::Result<()> {
+        self.generate::<T>(&mut std::io::stdout());
+        Ok(())
+    }
+
+    /// Writes the completions for the command tree of `T` to `out`.
+    pub fn generate<T: CommandFactory>(&self, out: &mut impl Write) {
+        let mut command = T::command();
+        let name = command.get_name().to_string();
+        clap_complete::generate(self.shell, &mut command, name, out);
+    }
+}
@@ -1,5 +1,6 @@
 //! CLI command to show configs.
 
+use crate::cli_defaults::EffectiveValue;
 use clap::Parser;
 use eyre::{bail, WrapErr};
 use reth_config::Config;
@@ -14,11 +15,40 @@ pub struct Command {
     /// Show the default config
     #[arg(long, verbatim_doc_comment, conflicts_with = "config")]
     default: bool,
+
+    /// Show the effective values of the CLI arguments, merged from the command line, the
+    /// environment and the `[cli]` table of the config file, annotated with their source.
+    #[arg(long, conflicts_with = "default")]
+    show_effective: bool,
+
+    /// The effective values of the CLI arguments, see [`Self::set_effective_values`].
+    #[arg(skip)]
+    effective_values: Option<Vec<EffectiveValue>>,
 }
 
 impl Command {
+    /// Returns `true` if the effective values of the CLI arguments should be shown.
+    pub const fn show_effective(&self) -> bool {
+        self.show_effective
+    }
+
+    /// Sets the effective values of the CLI arguments shown with `--show-effective`.
+    pub fn set_effective_values(&mut self, values: Vec<EffectiveValue>) {
+        self.effective_values = Some(values);
+    }
+
     /// Execute `config` command
     pub async fn execute(&self) -> eyre::Result<()> {
+        if self.show_effective {
+            let Some(values) = &self.effective_values else {
+                bail!("Showing the effective CLI arguments is not supported by this CLI")
+            };
+            for value in values {
+                println!("{value}");
+            }
+            return Ok(())
+        }
+
         let config = if self.default {
             Config::default()
         } else {
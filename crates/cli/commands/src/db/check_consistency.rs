@@ -0,0 +1,36 @@
+use clap::Parser;
+use reth_db_common::{
+    consistency::{StorageHeights, INCONSISTENCY_INSTRUCTIONS},
+    DbTool,
+};
+use reth_provider::providers::ProviderNodeTypes;
+
+/// The arguments for the `reth db check-consistency` command
+#[derive(Parser, Debug)]
+pub struct Command;
+
+impl Command {
+    /// Execute `db check-consistency` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let heights = StorageHeights::read(&tool.provider_factory.provider()?)?;
+        println!("{heights}");
+
+        let inconsistencies = heights.inconsistencies();
+        if inconsistencies.is_empty() {
+            println!("\nThe stage checkpoints, static files and canonical hashes are consistent.");
+            return Ok(())
+        }
+
+        println!();
+        for inconsistency in &inconsistencies {
+            let kind = if inconsistency.healable { "healable" } else { "not healable" };
+            println!("[{kind}] {inconsistency}");
+        }
+
+        if inconsistencies.iter().any(|inconsistency| !inconsistency.healable) {
+            eyre::bail!("the node refuses to start: {INCONSISTENCY_INSTRUCTIONS}")
+        }
+        println!("\nThe inconsistencies are healed when the node starts.");
+        Ok(())
+    }
+}
@@ -7,14 +7,18 @@ use clap::Parser;
 use reth_chainspec::EthereumHardforks;
 use reth_db::DatabaseEnv;
 use reth_db_api::{
-    cursor::DbCursorRO, table::Table, transaction::DbTx, RawKey, RawTable, RawValue, TableViewer,
-    Tables,
+    cursor::DbCursorRO,
+    table::{Key, Table},
+    transaction::DbTx,
+    RawKey, RawTable, RawValue, TableViewer, Tables,
 };
 use reth_db_common::DbTool;
 use reth_node_builder::{NodeTypesWithDB, NodeTypesWithDBAdapter};
-use reth_provider::{providers::ProviderNodeTypes, DBProvider};
+use reth_provider::{providers::ProviderNodeTypes, DBProvider, StaticFileProviderFactory};
+use reth_static_file_types::StaticFileSegment;
 use std::{
     hash::{BuildHasher, Hasher},
+    ops::{Bound, RangeInclusive},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -24,7 +28,12 @@ use tracing::{info, warn};
 /// The arguments for the `reth db checksum` command
 pub struct Command {
     /// The table name
-    table: Tables,
+    #[arg(required_unless_present = "static_file")]
+    table: Option<Tables>,
+
+    /// Checksum the given static file segment instead of a table.
+    #[arg(long, conflicts_with_all = ["table", "start_key", "end_key"])]
+    static_file: Option<StaticFileSegment>,
 
     /// The start of the range to checksum.
     #[arg(long, value_parser = maybe_json_value_parser)]
@@ -34,6 +43,15 @@ pub struct Command {
     #[arg(long, value_parser = maybe_json_value_parser)]
     end_key: Option<String>,
 
+    /// The range of block numbers to checksum, e.g. `100..200` or `100..=199`.
+    ///
+    /// For tables, this only includes the keys that start with a block number in the range, so it
+    /// is rejected for tables that are not keyed by block number. For static file segments, this
+    /// is the range of block numbers for block-based segments and of transaction numbers for
+    /// transaction-based segments.
+    #[arg(long, value_parser = parse_range, conflicts_with_all = ["start_key", "end_key"])]
+    range: Option<RangeInclusive<u64>>,
+
     /// The maximum number of records that are queried and used to compute the
     /// checksum.
     #[arg(long)]
@@ -47,10 +65,20 @@ impl Command {
         tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
     ) -> eyre::Result<()> {
         warn!("This command should be run without the node running!");
-        self.table.view(&ChecksumViewer {
+        if let Some(segment) = self.static_file {
+            checksum_static_file(tool, segment, self.range, self.limit)?;
+            return Ok(())
+        }
+
+        let table = self.table.expect("required unless a static file segment is given");
+        if self.range.is_some() && !is_block_keyed(table) {
+            eyre::bail!("`--range` is only supported for tables keyed by block number, not {table}")
+        }
+        table.view(&ChecksumViewer {
             tool,
             start_key: self.start_key,
             end_key: self.end_key,
+            range: self.range,
             limit: self.limit,
         })?;
         Ok(())
@@ -61,12 +89,13 @@ pub(crate) struct ChecksumViewer<'a, N: NodeTypesWithDB> {
     tool: &'a DbTool<N>,
     start_key: Option<String>,
     end_key: Option<String>,
+    range: Option<RangeInclusive<u64>>,
     limit: Option<usize>,
 }
 
 impl<N: NodeTypesWithDB> ChecksumViewer<'_, N> {
     pub(crate) const fn new(tool: &'_ DbTool<N>) -> ChecksumViewer<'_, N> {
-        ChecksumViewer { tool, start_key: None, end_key: None, limit: None }
+        ChecksumViewer { tool, start_key: None, end_key: None, range: None, limit: None }
     }
 }
 
@@ -78,31 +107,25 @@ impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N
             self.tool.provider_factory.provider()?.disable_long_read_transaction_safety();
         let tx = provider.tx_ref();
         info!(
-            "Start computing checksum, start={:?}, end={:?}, limit={:?}",
-            self.start_key, self.end_key, self.limit
+            "Start computing checksum, start={:?}, end={:?}, range={:?}, limit={:?}",
+            self.start_key, self.end_key, self.range, self.limit
         );
 
-        let mut cursor = tx.cursor_read::<RawTable<T>>()?;
-        let walker = match (self.start_key.as_deref(), self.end_key.as_deref()) {
-            (Some(start), Some(end)) => {
-                let start_key = table_key::<T>(start).map(RawKey::new)?;
-                let end_key = table_key::<T>(end).map(RawKey::new)?;
-                cursor.walk_range(start_key..=end_key)?
+        let bounds = match &self.range {
+            Some(range) => block_prefix_bounds(range),
+            None => {
+                let bound = |key: Option<&str>| match key {
+                    Some(key) => table_key::<T>(key).map(|key| Bound::Included(RawKey::new(key))),
+                    None => Ok(Bound::Unbounded),
+                };
+                (bound(self.start_key.as_deref())?, bound(self.end_key.as_deref())?)
             }
-            (None, Some(end)) => {
-                let end_key = table_key::<T>(end).map(RawKey::new)?;
-
-                cursor.walk_range(..=end_key)?
-            }
-            (Some(start), None) => {
-                let start_key = table_key::<T>(start).map(RawKey::new)?;
-                cursor.walk_range(start_key..)?
-            }
-            (None, None) => cursor.walk_range(..)?,
         };
+        let mut cursor = tx.cursor_read::<RawTable<T>>()?;
+        let walker = cursor.walk_range(bounds)?;
 
         let start_time = Instant::now();
-        let mut hasher = FixedState::with_seed(u64::from_be_bytes(*b"RETHRETH")).build_hasher();
+        let mut hasher = checksum_hasher();
         let mut total = 0;
 
         let limit = self.limit.unwrap_or(usize::MAX);
@@ -138,8 +161,186 @@ impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N
         let checksum = hasher.finish();
         let elapsed = start_time.elapsed();
 
-        info!("Checksum for table `{}`: {:#x} (elapsed: {:?})", T::NAME, checksum, elapsed);
+        info!(
+            "Checksum for table `{}`: {:#x} (entries: {}, elapsed: {:?})",
+            T::NAME,
+            checksum,
+            total,
+            elapsed
+        );
 
         Ok((checksum, elapsed))
     }
 }
+
+/// Computes the checksum of a static file segment by walking the rows of all its static files.
+///
+/// Every column of a row is hashed in order, so the checksum of a segment does not depend on how
+/// its rows are split into static files.
+fn checksum_static_file<N: ProviderNodeTypes>(
+    tool: &DbTool<N>,
+    segment: StaticFileSegment,
+    range: Option<RangeInclusive<u64>>,
+    limit: Option<usize>,
+) -> eyre::Result<(u64, Duration)> {
+    let static_file_provider = tool.provider_factory.static_file_provider();
+    let highest = if segment.is_block_based() {
+        static_file_provider.get_highest_static_file_block(segment)
+    } else {
+        static_file_provider.get_highest_static_file_tx(segment)
+    };
+    info!("Start computing checksum, segment={segment}, range={range:?}, limit={limit:?}");
+
+    let start_time = Instant::now();
+    let mut hasher = checksum_hasher();
+    let mut total = 0;
+
+    let limit = limit.unwrap_or(usize::MAX);
+    let (mut number, end) = range.map_or((0, u64::MAX), RangeInclusive::into_inner);
+    let mut enumerate_start_number = None;
+    let mut enumerate_end_number = None;
+    'files: while let Some(highest) = highest &&
+        number <= end.min(highest) &&
+        total < limit
+    {
+        let Some(jar) = static_file_provider.get_maybe_segment_provider(segment, number)? else {
+            break
+        };
+        let Some(start) = jar.user_header().start() else { break };
+        let mut cursor = jar.cursor()?;
+
+        let mut row = cursor.row_by_number((number - start) as usize)?;
+        if row.is_none() {
+            break
+        }
+        while let Some(columns) = row {
+            if total.is_multiple_of(100_000) {
+                info!("Hashed {total} entries.");
+            }
+
+            for column in columns {
+                hasher.write(column);
+            }
+
+            enumerate_start_number.get_or_insert(number);
+            enumerate_end_number = Some(number);
+
+            total += 1;
+            if number >= end.min(highest) || total >= limit {
+                break 'files
+            }
+            number += 1;
+            row = cursor.next_row()?;
+        }
+    }
+
+    info!("Hashed {total} entries.");
+    if let (Some(s), Some(e)) = (enumerate_start_number, enumerate_end_number) {
+        info!("start-number: {s}");
+        info!("end-number: {e}");
+    }
+
+    let checksum = hasher.finish();
+    let elapsed = start_time.elapsed();
+
+    info!(
+        "Checksum for static file segment `{segment}`: {checksum:#x} (entries: {total}, elapsed: \
+         {elapsed:?})"
+    );
+
+    Ok((checksum, elapsed))
+}
+
+/// Returns the hasher used for checksums, seeded so that checksums are comparable across runs.
+fn checksum_hasher() -> impl Hasher {
+    FixedState::with_seed(u64::from_be_bytes(*b"RETHRETH")).build_hasher()
+}
+
+/// Returns `true` if the keys of the table start with a block number.
+const fn is_block_keyed(table: Tables) -> bool {
+    matches!(
+        table,
+        Tables::CanonicalHeaders |
+            Tables::HeaderTerminalDifficulties |
+            Tables::Headers |
+            Tables::BlockBodyIndices |
+            Tables::BlockOmmers |
+            Tables::BlockWithdrawals |
+            Tables::AccountChangeSets |
+            Tables::StorageChangeSets |
+            Tables::AccountsTrieChangeSets |
+            Tables::StoragesTrieChangeSets
+    )
+}
+
+/// Returns the bounds of the raw keys that start with a big-endian block number in the given
+/// range.
+fn block_prefix_bounds<K: Key>(
+    range: &RangeInclusive<u64>,
+) -> (Bound<RawKey<K>>, Bound<RawKey<K>>) {
+    let start = Bound::Included(RawKey::from_vec(range.start().to_be_bytes().to_vec()));
+    let end = match range.end().checked_add(1) {
+        Some(end) => Bound::Excluded(RawKey::from_vec(end.to_be_bytes().to_vec())),
+        None => Bound::Unbounded,
+    };
+    (start, end)
+}
+
+/// Parses a range of numbers, e.g. `100..200`, `100..=199`, `100..` or `..200`.
+fn parse_range(value: &str) -> eyre::Result<RangeInclusive<u64>> {
+    let (start, end) =
+        value.split_once("..").ok_or_else(|| eyre::eyre!("expected a range like `100..200`"))?;
+    let start = if start.is_empty() { 0 } else { start.parse()? };
+    let end = match end.strip_prefix('=') {
+        Some(end) => end.parse()?,
+        None if end.is_empty() => u64::MAX,
+        None => end
+            .parse::<u64>()?
+            .checked_sub(1)
+            .ok_or_else(|| eyre::eyre!("range `{value}` is empty"))?,
+    };
+    if start > end {
+        eyre::bail!("range `{value}` is empty")
+    }
+    Ok(start..=end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db_api::models::BlockNumberAddress;
+    use std::ops::RangeBounds;
+
+    #[test]
+    fn parse_ranges() {
+        assert_eq!(parse_range("100..200").unwrap(), 100..=199);
+        assert_eq!(parse_range("100..=199").unwrap(), 100..=199);
+        assert_eq!(parse_range("100..").unwrap(), 100..=u64::MAX);
+        assert_eq!(parse_range("..200").unwrap(), 0..=199);
+        assert!(parse_range("100").is_err());
+        assert!(parse_range("100..100").is_err());
+        assert!(parse_range("200..=100").is_err());
+    }
+
+    #[test]
+    fn block_keyed_tables() {
+        assert!(is_block_keyed(Tables::StorageChangeSets));
+        assert!(!is_block_keyed(Tables::Transactions));
+        assert!(!is_block_keyed(Tables::PlainAccountState));
+    }
+
+    #[test]
+    fn block_prefix() {
+        let bounds = block_prefix_bounds::<u64>(&(100..=199));
+        assert!(bounds.contains(&RawKey::new(100)));
+        assert!(bounds.contains(&RawKey::new(199)));
+        assert!(!bounds.contains(&RawKey::new(99)));
+        assert!(!bounds.contains(&RawKey::new(200)));
+
+        let bounds = block_prefix_bounds::<BlockNumberAddress>(&(100..=199));
+        let key = |block| RawKey::new(BlockNumberAddress((block, Default::default())));
+        assert!(bounds.contains(&key(100)));
+        assert!(bounds.contains(&key(199)));
+        assert!(!bounds.contains(&key(200)));
+    }
+}
@@ -1,31 +1,56 @@
 use clap::{Parser, Subcommand};
+use itertools::Itertools;
+use reth_chainspec::EthChainSpec;
 use reth_db::static_file::iter_static_files;
 use reth_db_api::{
     database::Database,
     table::Table,
+    tables,
     transaction::{DbTx, DbTxMut},
     TableViewer, Tables,
 };
-use reth_db_common::DbTool;
-use reth_node_builder::NodeTypesWithDB;
-use reth_provider::StaticFileProviderFactory;
+use reth_db_common::{
+    init::{insert_genesis_header, insert_genesis_history, insert_genesis_state},
+    DbTool,
+};
+use reth_provider::{
+    providers::ProviderNodeTypes, ChainSpecProvider, DBProvider, DatabaseProvider,
+    DatabaseProviderFactory, StaticFileProviderFactory, StaticFileWriter,
+};
+use reth_prune::PruneSegment;
+use reth_stages::StageId;
 use reth_static_file_types::StaticFileSegment;
+use std::io::{self, Write};
 
 /// The arguments for the `reth db clear` command
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Command {
+    /// The table to clear
+    #[arg(required = true)]
+    table: Option<Tables>,
+
+    /// Resets the stages that write the table and all later stages without prompting, so that
+    /// they regenerate their tables from genesis on the next run
+    #[arg(long)]
+    reset_checkpoints: bool,
+
     #[command(subcommand)]
-    subcommand: Subcommands,
+    subcommand: Option<Subcommands>,
 }
 
 impl Command {
     /// Execute `db clear` command
-    pub fn execute<N: NodeTypesWithDB>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
         match self.subcommand {
-            Subcommands::Mdbx { table } => {
-                table.view(&ClearViewer { db: tool.provider_factory.db_ref() })?
+            None => {
+                let table = self.table.expect("required without a subcommand");
+                clear_table(tool, table, self.reset_checkpoints)?
+            }
+            Some(Subcommands::Mdbx { table, reset_checkpoints }) => {
+                clear_table(tool, table, reset_checkpoints)?
             }
-            Subcommands::StaticFile { segment } => {
+            Some(Subcommands::StaticFile { segment }) => {
                 let static_file_provider = tool.provider_factory.static_file_provider();
                 let static_files = iter_static_files(static_file_provider.directory())?;
 
@@ -43,23 +68,323 @@ impl Command {
 
 #[derive(Subcommand, Debug)]
 enum Subcommands {
-    /// Deletes all database table entries
-    Mdbx { table: Tables },
+    /// Deletes all database table entries, same as `db clear <TABLE>`
+    Mdbx {
+        table: Tables,
+        /// Resets the stages that write the table and all later stages without prompting
+        #[arg(long)]
+        reset_checkpoints: bool,
+    },
     /// Deletes all static file segment entries
     StaticFile { segment: StaticFileSegment },
 }
 
-struct ClearViewer<'a, DB: Database> {
-    db: &'a DB,
+/// Clears the table, and resets the stages that write it and all later stages if
+/// `reset_checkpoints` is set or the user confirms it, in the same transaction.
+///
+/// Fails if the table is stored in static files, where clearing the database table would leave
+/// the data in place, or if it's part of the state and the stages are not reset, which would leave
+/// the state inconsistent with the stages that already ran on top of it.
+fn clear_table<N: ProviderNodeTypes>(
+    tool: &DbTool<N>,
+    table: Tables,
+    reset_checkpoints: bool,
+) -> eyre::Result<()> {
+    if let Some(segment) = static_file_segment(table) &&
+        tool.provider_factory
+            .static_file_provider()
+            .get_highest_static_file_block(segment)
+            .is_some()
+    {
+        eyre::bail!(
+            "The {table} table is stored in the {segment} static files, clear them with `reth db \
+             clear static-file {}` instead, or re-run the stage with `reth stage drop`",
+            segment.as_str()
+        )
+    }
+
+    let stages = stages_to_reset(table);
+    let reset = !stages.is_empty() &&
+        (reset_checkpoints ||
+            confirm(&format!(
+                "The stages {} assume that the {table} table is complete. Reset them, clearing \
+                 the tables and static files they write, so that they regenerate them from \
+                 genesis on the next run? (y/N): ",
+                stages.iter().join(", ")
+            )));
+    if !reset && is_state_table(table) {
+        eyre::bail!(
+            "The {table} table is part of the state, which can't be cleared without resetting the \
+             stages {}",
+            stages.iter().join(", ")
+        )
+    }
+
+    let provider_rw = tool.provider_factory.database_provider_rw()?;
+    let entries = table.view(&ClearViewer { tx: provider_rw.tx_ref() })?;
+    if reset {
+        reset_stages(&provider_rw, &stages)?;
+    }
+    provider_rw.commit()?;
+
+    println!("Removed {entries} entries from the {table} table");
+    if reset {
+        println!("Reset the stages {}", stages.iter().join(", "));
+    } else if !stages.is_empty() {
+        println!(
+            "Kept the checkpoints of the stages {}, which won't regenerate the table",
+            stages.iter().join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Resets the stages to genesis, like `reth stage drop` does for each of them.
+///
+/// This clears the tables they write and prunes their static files down to the genesis block,
+/// which is written again along with the genesis state and history. The static files are only
+/// pruned once the provider is committed.
+fn reset_stages<N: ProviderNodeTypes>(
+    provider_rw: &DatabaseProvider<<N::DB as Database>::TXMut, N>,
+    stages: &[StageId],
+) -> eyre::Result<()> {
+    let tx = provider_rw.tx_ref();
+    for table in Tables::ALL {
+        if dependent_stages(*table).iter().any(|stage| stages.contains(stage)) {
+            table.view(&ClearViewer { tx })?;
+        }
+    }
+
+    let static_file_provider = provider_rw.static_file_provider();
+    for stage in stages {
+        let Some(segment) = stage_static_file_segment(*stage) else { continue };
+        let Some(highest_block) = static_file_provider.get_highest_static_file_block(segment)
+        else {
+            continue
+        };
+        let mut writer = static_file_provider.latest_writer(segment)?;
+        let to_delete = static_file_provider
+            .get_highest_static_file_tx(segment)
+            .map(|tx_num| tx_num + 1)
+            .unwrap_or_default();
+        match segment {
+            // Prune all headers leaving genesis intact
+            StaticFileSegment::Headers => writer.prune_headers(highest_block)?,
+            StaticFileSegment::Transactions => writer.prune_transactions(to_delete, 0)?,
+            StaticFileSegment::Receipts => writer.prune_receipts(to_delete, 0)?,
+            StaticFileSegment::TransactionSenders => {
+                writer.prune_transaction_senders(to_delete, 0)?
+            }
+        }
+    }
+
+    for segment in stages.iter().flat_map(|stage| stage_prune_segments(*stage)) {
+        // Pruned numbers would otherwise count towards the progress of the next run
+        if let Some(mut checkpoint) = tx.get::<tables::PruneCheckpoints>(*segment)? {
+            checkpoint.block_number = None;
+            checkpoint.tx_number = None;
+            tx.put::<tables::PruneCheckpoints>(*segment, checkpoint)?;
+        }
+    }
+    for stage in stages {
+        tx.put::<tables::StageCheckpoints>(stage.to_string(), Default::default())?;
+        tx.delete::<tables::StageCheckpointProgresses>(stage.to_string(), None)?;
+    }
+
+    let chain = provider_rw.chain_spec();
+    if stages.iter().any(|stage| {
+        matches!(stage, StageId::Headers | StageId::Bodies | StageId::TransactionLookup)
+    }) {
+        insert_genesis_header(provider_rw, &chain)?;
+    }
+    if stages.contains(&StageId::Execution) {
+        insert_genesis_state(provider_rw, chain.genesis().alloc.iter())?;
+    }
+    if stages.contains(&StageId::IndexAccountHistory) {
+        insert_genesis_history(provider_rw, chain.genesis().alloc.iter())?;
+    }
+
+    Ok(())
+}
+
+/// Returns the stages that write the table and all stages after them, which have to be reset for
+/// the pipeline to regenerate it, since they only process new blocks otherwise.
+fn stages_to_reset(table: Tables) -> Vec<StageId> {
+    let dependent = dependent_stages(table);
+    StageId::ALL
+        .iter()
+        .position(|stage| dependent.contains(stage))
+        .map(|first| StageId::ALL[first..].to_vec())
+        .unwrap_or_default()
+}
+
+/// Returns `true` if the table is part of the state, or derived from it by the hashing and merkle
+/// stages.
+const fn is_state_table(table: Tables) -> bool {
+    matches!(
+        table,
+        Tables::Bytecodes |
+            Tables::PlainAccountState |
+            Tables::PlainStorageState |
+            Tables::AccountChangeSets |
+            Tables::StorageChangeSets |
+            Tables::HashedAccounts |
+            Tables::HashedStorages |
+            Tables::AccountsTrie |
+            Tables::StoragesTrie |
+            Tables::AccountsTrieChangeSets |
+            Tables::StoragesTrieChangeSets
+    )
+}
+
+/// Returns the static file segment that the stage writes, if any.
+const fn stage_static_file_segment(stage: StageId) -> Option<StaticFileSegment> {
+    match stage {
+        StageId::Headers => Some(StaticFileSegment::Headers),
+        StageId::Bodies => Some(StaticFileSegment::Transactions),
+        StageId::SenderRecovery => Some(StaticFileSegment::TransactionSenders),
+        StageId::Execution => Some(StaticFileSegment::Receipts),
+        _ => None,
+    }
+}
+
+/// Returns the prune segments whose checkpoints track the tables that the stage writes.
+const fn stage_prune_segments(stage: StageId) -> &'static [PruneSegment] {
+    match stage {
+        StageId::SenderRecovery => &[PruneSegment::SenderRecovery],
+        StageId::Execution => &[PruneSegment::Receipts, PruneSegment::ContractLogs],
+        StageId::TransactionLookup => &[PruneSegment::TransactionLookup],
+        _ => &[],
+    }
+}
+
+/// Returns the static file segment that the table is moved to, if any.
+const fn static_file_segment(table: Tables) -> Option<StaticFileSegment> {
+    match table {
+        Tables::CanonicalHeaders | Tables::Headers | Tables::HeaderTerminalDifficulties => {
+            Some(StaticFileSegment::Headers)
+        }
+        Tables::Transactions => Some(StaticFileSegment::Transactions),
+        Tables::Receipts => Some(StaticFileSegment::Receipts),
+        Tables::TransactionSenders => Some(StaticFileSegment::TransactionSenders),
+        _ => None,
+    }
+}
+
+/// Returns the stages that write the table, whose checkpoints have to be reset for the pipeline
+/// to regenerate it.
+const fn dependent_stages(table: Tables) -> &'static [StageId] {
+    match table {
+        Tables::CanonicalHeaders |
+        Tables::Headers |
+        Tables::HeaderTerminalDifficulties |
+        Tables::HeaderNumbers => &[StageId::Headers],
+        Tables::BlockBodyIndices |
+        Tables::BlockOmmers |
+        Tables::BlockWithdrawals |
+        Tables::Transactions |
+        Tables::TransactionBlocks => &[StageId::Bodies],
+        Tables::TransactionSenders => &[StageId::SenderRecovery],
+        Tables::Bytecodes |
+        Tables::PlainAccountState |
+        Tables::PlainStorageState |
+        Tables::AccountChangeSets |
+        Tables::StorageChangeSets |
+        Tables::Receipts => &[StageId::Execution],
+        Tables::HashedAccounts => &[StageId::AccountHashing],
+        Tables::HashedStorages => &[StageId::StorageHashing],
+        Tables::AccountsTrie | Tables::StoragesTrie => {
+            &[StageId::MerkleExecute, StageId::MerkleUnwind]
+        }
+        Tables::AccountsTrieChangeSets | Tables::StoragesTrieChangeSets => {
+            &[StageId::MerkleChangeSets]
+        }
+        Tables::AccountsHistory => &[StageId::IndexAccountHistory],
+        Tables::StoragesHistory => &[StageId::IndexStorageHistory],
+        Tables::TransactionHashNumbers => &[StageId::TransactionLookup],
+        Tables::StageCheckpoints |
+        Tables::StageCheckpointProgresses |
+        Tables::PruneCheckpoints |
+        Tables::VersionHistory |
+        Tables::ChainState |
+        Tables::Metadata => &[],
+    }
 }
 
-impl<DB: Database> TableViewer<()> for ClearViewer<'_, DB> {
+/// Asks the user a yes or no question, which defaults to no.
+fn confirm(question: &str) -> bool {
+    print!("{question}");
+    // Flush the buffer to ensure the question is printed immediately
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Failed to read line");
+    input.trim().eq_ignore_ascii_case("y")
+}
+
+/// Deletes all entries of a table, including all values of every key of dupsort tables, and
+/// returns how many were deleted.
+struct ClearViewer<'a, TX> {
+    tx: &'a TX,
+}
+
+impl<TX: DbTxMut + DbTx> TableViewer<usize> for ClearViewer<'_, TX> {
     type Error = eyre::Report;
 
-    fn view<T: Table>(&self) -> Result<(), Self::Error> {
-        let tx = self.db.tx_mut()?;
-        tx.clear::<T>()?;
-        tx.commit()?;
-        Ok(())
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        let entries = self.tx.entries::<T>()?;
+        self.tx.clear::<T>()?;
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Parser, Debug)]
+    struct Cli {
+        #[command(flatten)]
+        command: Command,
+    }
+
+    #[test]
+    fn parse_table_or_subcommand() {
+        let cli = Cli::try_parse_from(["reth", "AccountsHistory", "--reset-checkpoints"]).unwrap();
+        assert_eq!(cli.command.table, Some(Tables::AccountsHistory));
+        assert!(cli.command.reset_checkpoints);
+        assert!(cli.command.subcommand.is_none());
+
+        let cli = Cli::try_parse_from(["reth", "mdbx", "AccountsHistory"]).unwrap();
+        assert!(cli.command.table.is_none());
+        assert!(matches!(
+            cli.command.subcommand,
+            Some(Subcommands::Mdbx { table: Tables::AccountsHistory, reset_checkpoints: false })
+        ));
+
+        assert!(Cli::try_parse_from(["reth"]).is_err());
+    }
+
+    #[test]
+    fn later_stages_are_reset() {
+        assert_eq!(stages_to_reset(Tables::Metadata), Vec::new());
+        let stages = stages_to_reset(Tables::PlainAccountState);
+        assert_eq!(stages.first(), Some(&StageId::Execution));
+        assert!(stages.contains(&StageId::MerkleExecute));
+        assert_eq!(stages.last(), Some(&StageId::Finish));
+        assert!(!stages.contains(&StageId::SenderRecovery));
+
+        // The earliest of the stages that write the table
+        assert_eq!(stages_to_reset(Tables::AccountsTrie).first(), Some(&StageId::MerkleUnwind));
+    }
+
+    #[test]
+    fn tables_of_static_files_have_stages() {
+        for table in Tables::ALL {
+            if static_file_segment(*table).is_some() {
+                assert!(!dependent_stages(*table).is_empty(), "{table}");
+            }
+        }
     }
 }
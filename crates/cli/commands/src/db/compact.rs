@@ -0,0 +1,205 @@
+//! `reth db compact` command for writing a compacted copy of the database
+
+use crate::common::AccessRights;
+use clap::Parser;
+use eyre::WrapErr;
+use human_bytes::human_bytes;
+use itertools::Itertools;
+use reth_db::{
+    lockfile::StorageLock,
+    mdbx::{self, CopyFlags, DatabaseArguments},
+    open_db_read_only,
+    version::create_db_version_file,
+    ClientVersion, DatabaseEnv,
+};
+use reth_db_api::{database::Database, transaction::DbTx, Tables};
+use reth_db_common::DbTool;
+use reth_fs_util as fs;
+use reth_node_builder::NodeTypesWithDB;
+use std::{
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+    thread,
+    time::Duration,
+};
+use sysinfo::Disks;
+use tracing::info;
+
+/// The name of the data file of the database in its directory.
+const DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// The name of the lock file of the database in its directory, which MDBX recreates on open.
+const LOCK_FILE_NAME: &str = "mdbx.lck";
+
+/// Interval between the progress logs of the copy.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The arguments for the `reth db compact` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to write the compacted database to, which must not contain a database
+    #[arg(long, value_name = "PATH")]
+    output: PathBuf,
+
+    /// Replaces the database with the compacted copy, which requires that the node is not running.
+    ///
+    /// The output directory must be on the same volume as the database, and is removed after the
+    /// swap if the command created it.
+    #[arg(long)]
+    in_place: bool,
+}
+
+impl Command {
+    /// Returns database access rights required for the command.
+    pub const fn access_rights(&self) -> AccessRights {
+        if self.in_place {
+            AccessRights::RW
+        } else {
+            AccessRights::RO
+        }
+    }
+
+    /// Execute `db compact` command
+    pub fn execute<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>>(
+        self,
+        tool: DbTool<N>,
+        db_path: &Path,
+    ) -> eyre::Result<()> {
+        let output_file = self.output.join(DATA_FILE_NAME);
+        eyre::ensure!(
+            !output_file.exists(),
+            "Database already exists at {}",
+            self.output.display()
+        );
+        let created_output = !self.output.exists();
+        fs::create_dir_all(&self.output)?;
+
+        let env = tool.provider_factory.db_ref();
+        let page_size = env.stat()?.page_size() as usize;
+        let used_size = (env.info()?.last_pgno() + 1 - env.freelist()?) * page_size;
+        let database_size = fs::metadata(db_path.join(DATA_FILE_NAME))?.len();
+
+        // The copy is at most as large as the pages in use
+        let output_path = self.output.canonicalize()?;
+        if let Some(available) = available_space(&output_path) &&
+            available < used_size as u64
+        {
+            eyre::bail!(
+                "Not enough free space on the volume of {}: {} available, the copy needs up to {}",
+                output_path.display(),
+                human_bytes(available as f64),
+                human_bytes(used_size as f64)
+            )
+        }
+
+        let mut tx = env.tx()?;
+        tx.disable_long_read_transaction_safety();
+        let entries = table_entries(&tx.inner)?;
+
+        info!(target: "reth::cli", output = ?output_file, size = %human_bytes(used_size as f64), "Copying the database");
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        let progress = thread::spawn({
+            let output_file = output_file.clone();
+            move || {
+                while let Err(mpsc::RecvTimeoutError::Timeout) =
+                    done_rx.recv_timeout(PROGRESS_INTERVAL)
+                {
+                    let copied = fs::metadata(&output_file).map(|m| m.len()).unwrap_or_default();
+                    info!(
+                        target: "reth::cli",
+                        copied = %human_bytes(copied as f64),
+                        progress = %format!("{:.2}%", copied as f64 / used_size as f64 * 100.0),
+                        "Copying the database"
+                    );
+                }
+            }
+        });
+        let result = tx.inner.copy_to_path(&output_file, CopyFlags::COMPACT);
+        drop(done_tx);
+        let _ = progress.join();
+        result.wrap_err("Could not copy the database")?;
+        drop(tx);
+        create_db_version_file(&self.output)?;
+
+        info!(target: "reth::cli", "Verifying the table entry counts of the copy");
+        let copy =
+            open_db_read_only(&self.output, DatabaseArguments::new(ClientVersion::default()))?;
+        let copy_entries = table_entries(&copy.begin_ro_txn()?)?;
+        drop(copy);
+        let mismatches = entries
+            .iter()
+            .zip(&copy_entries)
+            .filter(|(expected, actual)| expected != actual)
+            .map(|((table, expected), (_, actual))| format!("{table}: {expected} != {actual}"))
+            .collect::<Vec<_>>();
+        if !mismatches.is_empty() {
+            eyre::bail!(
+                "The entry counts of the copy at {} differ: {}",
+                self.output.display(),
+                mismatches.iter().join(", ")
+            )
+        }
+
+        let compacted_size = fs::metadata(&output_file)?.len();
+        println!(
+            "Compacted the database from {} to {}",
+            human_bytes(database_size as f64),
+            human_bytes(compacted_size as f64)
+        );
+
+        if !self.in_place {
+            println!("Wrote the compacted database to {}", self.output.display());
+            return Ok(())
+        }
+
+        // Close the database, and keep other processes from opening it until it's swapped
+        drop(tool);
+        let _lock = StorageLock::try_acquire(db_path)?;
+        fs::rename(&output_file, db_path.join(DATA_FILE_NAME)).wrap_err(
+            "Could not move the compacted database into place, the output directory has to be on \
+             the same volume as the database",
+        )?;
+        // The lock file describes the reader table and geometry of the replaced data file
+        remove_lock_file(db_path)?;
+        if created_output {
+            fs::remove_dir_all(&self.output)?;
+        } else {
+            remove_lock_file(&self.output)?;
+        }
+        println!("Replaced the database at {} with the compacted copy", db_path.display());
+
+        Ok(())
+    }
+}
+
+/// Removes the MDBX lock file in the database directory, if there is one.
+fn remove_lock_file(dir: &Path) -> eyre::Result<()> {
+    let lock_file = dir.join(LOCK_FILE_NAME);
+    if lock_file.exists() {
+        fs::remove_file(&lock_file)?;
+    }
+    Ok(())
+}
+
+/// Returns the number of entries of every table in the snapshot that the transaction reads.
+fn table_entries<K: mdbx::TransactionKind>(
+    tx: &mdbx::Transaction<K>,
+) -> eyre::Result<Vec<(&'static str, usize)>> {
+    Tables::ALL
+        .iter()
+        .map(|table| {
+            let db = tx.open_db(Some(table.name())).wrap_err("Could not open db.")?;
+            Ok((table.name(), tx.db_stat(&db)?.entries()))
+        })
+        .collect()
+}
+
+/// Returns the available space of the volume that contains `path`, which is the one with the
+/// longest mount point that `path` starts with.
+fn available_space(path: &Path) -> Option<u64> {
+    Disks::new_with_refreshed_list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().components().count())
+        .map(|disk| disk.available_space())
+}
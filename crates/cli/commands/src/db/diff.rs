@@ -1,7 +1,12 @@
+use crate::db::get::{maybe_json_value_parser, table_key};
 use clap::Parser;
-use reth_db::{open_db_read_only, tables_to_generic, DatabaseEnv};
+use reth_db::{open_db_read_only, DatabaseEnv};
 use reth_db_api::{
-    cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, Tables,
+    cursor::DbCursorRO,
+    database::Database,
+    table::{Compress, DupSort, Table},
+    transaction::DbTx,
+    TableViewer, Tables,
 };
 use reth_db_common::DbTool;
 use reth_node_builder::{NodeTypes, NodeTypesWithDBAdapter};
@@ -9,12 +14,13 @@ use reth_node_core::{
     args::DatabaseArgs,
     dirs::{DataDirPath, PlatformPath},
 };
+use reth_primitives_traits::ValueWithSubKey;
 use std::{
-    collections::BTreeMap,
+    cmp::Ordering,
     fmt::Debug,
     fs::{self, File},
-    hash::Hash,
-    io::Write,
+    io::{BufWriter, Write},
+    ops::Bound,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -35,6 +41,22 @@ pub struct Command {
     #[arg(long, verbatim_doc_comment)]
     table: Option<Tables>,
 
+    /// Diff all tables, which is the default if no table is specified.
+    #[arg(long, conflicts_with = "table")]
+    all: bool,
+
+    /// The start of the range to diff, only available when a single table is diffed.
+    #[arg(long, requires = "table", value_parser = maybe_json_value_parser)]
+    start_key: Option<String>,
+
+    /// The end of the range to diff, only available when a single table is diffed.
+    #[arg(long, requires = "table", value_parser = maybe_json_value_parser)]
+    end_key: Option<String>,
+
+    /// The maximum number of differences reported per table.
+    #[arg(long)]
+    max_diffs: Option<usize>,
+
     /// The output directory for the diff report.
     #[arg(long, verbatim_doc_comment)]
     output: PlatformPath<PathBuf>,
@@ -46,14 +68,13 @@ impl Command {
     /// This first opens the `db/` folder from the secondary datadir, where the second database is
     /// opened read-only.
     ///
-    /// The tool will then iterate through all key-value pairs for the primary and secondary
-    /// databases. The value for each key will be compared with its corresponding value in the
-    /// other database. If the values are different, a discrepancy will be recorded in-memory. If
-    /// one key is present in one database but not the other, this will be recorded as an "extra
-    /// element" for that database.
+    /// The tool will then walk the key-value pairs of the primary and secondary databases in
+    /// lockstep. If the values for a key are different, a discrepancy is recorded. If one key is
+    /// present in one database but not the other, this will be recorded as an "extra element" for
+    /// that database. The entries of `DUPSORT` tables are matched by their key and subkey.
     ///
-    /// The discrepancies and extra elements, along with a brief summary of the diff results are
-    /// then written to a file in the output directory.
+    /// The discrepancies and extra elements are written to a file in the output directory as they
+    /// are found, followed by a brief summary of the diff results.
     pub fn execute<T: NodeTypes>(
         self,
         tool: &DbTool<NodeTypesWithDBAdapter<T, Arc<DatabaseEnv>>>,
@@ -64,186 +85,284 @@ impl Command {
         let second_db = open_db_read_only(&second_db_path, self.second_db.database_args())?;
 
         let tables = match &self.table {
-            Some(table) => std::slice::from_ref(table),
-            None => Tables::ALL,
+            Some(table) if !self.all => std::slice::from_ref(table),
+            _ => Tables::ALL,
         };
 
+        let viewer = DiffViewer {
+            primary: tool.provider_factory.db_ref(),
+            secondary: &second_db,
+            command: &self,
+        };
         for table in tables {
-            let mut primary_tx = tool.provider_factory.db_ref().tx()?;
-            let mut secondary_tx = second_db.tx()?;
-
-            // disable long read transaction safety, since this will run for a while and it's
-            // expected that the node is not running
-            primary_tx.disable_long_read_transaction_safety();
-            secondary_tx.disable_long_read_transaction_safety();
-
-            let output_dir = self.output.clone();
-            tables_to_generic!(table, |Table| find_diffs::<Table>(
-                primary_tx,
-                secondary_tx,
-                output_dir
-            ))?;
+            table.view(&viewer)?;
         }
 
         Ok(())
     }
 }
 
-/// Find diffs for a table, then analyzing the result
-fn find_diffs<T: Table>(
+/// Diffs a single table of both databases, see [`find_diffs`].
+struct DiffViewer<'a, P, S> {
+    /// The primary database
+    primary: &'a P,
+
+    /// The secondary database
+    secondary: &'a S,
+
+    /// The diff arguments
+    command: &'a Command,
+}
+
+impl<P: Database, S: Database> DiffViewer<'_, P, S> {
+    /// Diffs a table, ordering the entries of a key by the given subkey.
+    fn diff<T: Table, K: Ord>(&self, subkey: impl Fn(&T::Value) -> K) -> eyre::Result<()> {
+        let mut primary_tx = self.primary.tx()?;
+        let mut secondary_tx = self.secondary.tx()?;
+
+        // disable long read transaction safety, since this will run for a while and it's
+        // expected that the node is not running
+        primary_tx.disable_long_read_transaction_safety();
+        secondary_tx.disable_long_read_transaction_safety();
+
+        find_diffs::<T, K>(
+            primary_tx,
+            secondary_tx,
+            self.command.start_key.as_deref(),
+            self.command.end_key.as_deref(),
+            self.command.max_diffs,
+            &self.command.output,
+            subkey,
+        )
+    }
+}
+
+impl<P: Database, S: Database> TableViewer<()> for DiffViewer<'_, P, S> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        self.diff::<T, ()>(|_| ())
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<(), Self::Error>
+    where
+        T::Value: ValueWithSubKey<SubKey = T::SubKey>,
+    {
+        self.diff::<T, T::SubKey>(ValueWithSubKey::get_subkey)
+    }
+}
+
+/// Find diffs for a table by walking both tables in lockstep, writing them to the output
+/// directory as they are found.
+///
+/// The entries are walked in the order of their key and then the given subkey, which is the
+/// order of the entries of a `DUPSORT` table, so that the entries of a key are matched by their
+/// subkey. Values are compared in their stored form.
+fn find_diffs<T: Table, K: Ord>(
     primary_tx: impl DbTx,
     secondary_tx: impl DbTx,
+    start_key: Option<&str>,
+    end_key: Option<&str>,
+    max_diffs: Option<usize>,
     output_dir: impl AsRef<Path>,
-) -> eyre::Result<()>
-where
-    T::Key: Hash,
-    T::Value: PartialEq,
-{
+    subkey: impl Fn(&T::Value) -> K,
+) -> eyre::Result<()> {
     let table = T::NAME;
+    let start = match start_key {
+        Some(key) => Bound::Included(table_key::<T>(key)?),
+        None => Bound::Unbounded,
+    };
+    let end = match end_key {
+        Some(key) => Bound::Included(table_key::<T>(key)?),
+        None => Bound::Unbounded,
+    };
 
     info!("Analyzing table {table}...");
-    let result = find_diffs_advanced::<T>(&primary_tx, &secondary_tx)?;
-    info!("Done analyzing table {table}!");
+    let mut primary_cursor = primary_tx.cursor_read::<T>()?;
+    let mut primary_walker = primary_cursor.walk_range((start.clone(), end.clone()))?;
+    let mut secondary_cursor = secondary_tx.cursor_read::<T>()?;
+    let mut secondary_walker = secondary_cursor.walk_range((start, end))?;
+
+    let mut writer = DiffWriter::new(table, output_dir.as_ref());
+    let mut primary = primary_walker.next().transpose()?;
+    let mut secondary = secondary_walker.next().transpose()?;
+
+    // both walkers are sorted by key, so the walker with the smaller key is advanced until the keys
+    // match again, everything it passes over is missing from the other table
+    loop {
+        if primary.is_none() && secondary.is_none() {
+            break
+        }
+        if max_diffs.is_some_and(|max| writer.diffs() >= max) {
+            writer.truncated = true;
+            break
+        }
 
-    // Pretty info summary header: newline then header
-    info!("");
-    info!("Diff results for {table}:");
+        match (primary.take(), secondary.take()) {
+            (Some((key, value)), None) => {
+                writer.write_extra_element(ExtraTableElement::<T>::First { key, value })?;
+                primary = primary_walker.next().transpose()?;
+            }
+            (None, Some((key, value))) => {
+                writer.write_extra_element(ExtraTableElement::<T>::Second { key, value })?;
+                secondary = secondary_walker.next().transpose()?;
+            }
+            (Some(first), Some(second)) => {
+                match first.0.cmp(&second.0).then_with(|| subkey(&first.1).cmp(&subkey(&second.1)))
+                {
+                    Ordering::Less => {
+                        let (key, value) = first;
+                        writer.write_extra_element(ExtraTableElement::<T>::First { key, value })?;
+                        primary = primary_walker.next().transpose()?;
+                        secondary = Some(second);
+                    }
+                    Ordering::Greater => {
+                        let (key, value) = second;
+                        writer
+                            .write_extra_element(ExtraTableElement::<T>::Second { key, value })?;
+                        primary = Some(first);
+                        secondary = secondary_walker.next().transpose()?;
+                    }
+                    Ordering::Equal => {
+                        if !same_value(&first.1, &second.1) {
+                            writer.write_discrepancy(TableDiffElement::<T> {
+                                key: first.0,
+                                first: first.1,
+                                second: second.1,
+                            })?;
+                        }
+                        primary = primary_walker.next().transpose()?;
+                        secondary = secondary_walker.next().transpose()?;
+                    }
+                }
+            }
+            (None, None) => unreachable!("checked above"),
+        }
+    }
+    info!("Done analyzing table {table}!");
 
-    // analyze the result and print some stats
-    let discrepancies = result.discrepancies.len();
-    let extra_elements = result.extra_elements.len();
+    writer.finish()
+}
 
-    if discrepancies == 0 && extra_elements == 0 {
-        info!("No discrepancies or extra elements found in table {table}");
-        return Ok(());
-    }
+/// Returns `true` if both values are stored the same way.
+fn same_value<V: Compress>(first: &V, second: &V) -> bool {
+    let (mut first_buf, mut second_buf) = (Vec::new(), Vec::new());
+    first.compress_to_buf(&mut first_buf);
+    second.compress_to_buf(&mut second_buf);
+    first_buf == second_buf
+}
 
-    // create directory and open file
-    fs::create_dir_all(output_dir.as_ref())?;
-    let file_name = format!("{table}.txt");
-    let mut file = File::create(output_dir.as_ref().join(file_name.clone()))?;
+/// Writes the diff results of a table to a file in the output directory as they are found.
+///
+/// The file is only created once the first difference is found.
+struct DiffWriter<'a> {
+    /// The name of the table
+    table: &'static str,
 
-    // Make a pretty summary header for the table
-    writeln!(file, "Diff results for {table}")?;
+    /// The output directory
+    output_dir: &'a Path,
 
-    if discrepancies > 0 {
-        // write to file
-        writeln!(file, "Found {discrepancies} discrepancies in table {table}")?;
+    /// The output file, if any difference was found
+    file: Option<BufWriter<File>>,
 
-        // also print to info
-        info!("Found {discrepancies} discrepancies in table {table}");
-    } else {
-        // write to file
-        writeln!(file, "No discrepancies found in table {table}")?;
+    /// The number of discrepancies written so far
+    discrepancies: usize,
 
-        // also print to info
-        info!("No discrepancies found in table {table}");
-    }
+    /// The number of extra elements written so far
+    extra_elements: usize,
 
-    if extra_elements > 0 {
-        // write to file
-        writeln!(file, "Found {extra_elements} extra elements in table {table}")?;
+    /// Whether the diff stopped early because the maximum number of differences was reached
+    truncated: bool,
+}
 
-        // also print to info
-        info!("Found {extra_elements} extra elements in table {table}");
-    } else {
-        writeln!(file, "No extra elements found in table {table}")?;
+impl<'a> DiffWriter<'a> {
+    const fn new(table: &'static str, output_dir: &'a Path) -> Self {
+        Self {
+            table,
+            output_dir,
+            file: None,
+            discrepancies: 0,
+            extra_elements: 0,
+            truncated: false,
+        }
+    }
 
-        // also print to info
-        info!("No extra elements found in table {table}");
+    /// Returns the number of differences written so far.
+    const fn diffs(&self) -> usize {
+        self.discrepancies + self.extra_elements
     }
 
-    info!("Writing diff results for {table} to {file_name}...");
+    /// Returns the output file, creating it on first use.
+    fn file(&mut self) -> eyre::Result<&mut BufWriter<File>> {
+        if self.file.is_none() {
+            fs::create_dir_all(self.output_dir)?;
+            let path = self.output_dir.join(format!("{}.txt", self.table));
+            info!("Writing diff results for {} to {}...", self.table, path.display());
 
-    if discrepancies > 0 {
-        writeln!(file, "Discrepancies:")?;
+            let mut file = BufWriter::new(File::create(path)?);
+            writeln!(file, "Diff results for {}", self.table)?;
+            self.file = Some(file);
+        }
+        Ok(self.file.as_mut().expect("file was just created"))
     }
 
-    for discrepancy in result.discrepancies.values() {
-        writeln!(file, "{discrepancy:#?}")?;
+    /// Write a key present in both tables with different values.
+    fn write_discrepancy(&mut self, discrepancy: impl Debug) -> eyre::Result<()> {
+        writeln!(self.file()?, "Discrepancy: {discrepancy:#?}")?;
+        self.discrepancies += 1;
+        Ok(())
     }
 
-    if extra_elements > 0 {
-        writeln!(file, "Extra elements:")?;
+    /// Write a key present in only one of the tables.
+    fn write_extra_element(&mut self, element: impl Debug) -> eyre::Result<()> {
+        writeln!(self.file()?, "Extra element: {element:#?}")?;
+        self.extra_elements += 1;
+        Ok(())
     }
 
-    for extra_element in result.extra_elements.values() {
-        writeln!(file, "{extra_element:#?}")?;
-    }
+    /// Write a brief summary of the diff results and flush the output file.
+    fn finish(mut self) -> eyre::Result<()> {
+        let table = self.table;
 
-    let full_file_name = output_dir.as_ref().join(file_name);
-    info!("Done writing diff results for {table} to {}", full_file_name.display());
-    Ok(())
-}
+        // Pretty info summary header: newline then header
+        info!("");
+        info!("Diff results for {table}:");
 
-/// This diff algorithm is slightly different, it will walk _each_ table, cross-checking for the
-/// element in the other table.
-fn find_diffs_advanced<T: Table>(
-    primary_tx: &impl DbTx,
-    secondary_tx: &impl DbTx,
-) -> eyre::Result<TableDiffResult<T>>
-where
-    T::Value: PartialEq,
-    T::Key: Hash,
-{
-    // initialize the zipped walker
-    let mut primary_zip_cursor =
-        primary_tx.cursor_read::<T>().expect("Was not able to obtain a cursor.");
-    let primary_walker = primary_zip_cursor.walk(None)?;
-
-    let mut secondary_zip_cursor =
-        secondary_tx.cursor_read::<T>().expect("Was not able to obtain a cursor.");
-    let secondary_walker = secondary_zip_cursor.walk(None)?;
-    let zipped_cursor = primary_walker.zip(secondary_walker);
-
-    // initialize the cursors for seeking when we are cross checking elements
-    let mut primary_cursor =
-        primary_tx.cursor_read::<T>().expect("Was not able to obtain a cursor.");
-
-    let mut secondary_cursor =
-        secondary_tx.cursor_read::<T>().expect("Was not able to obtain a cursor.");
-
-    let mut result = TableDiffResult::<T>::default();
-
-    // this loop will walk both tables, cross-checking for the element in the other table.
-    // it basically just loops through both tables at the same time. if the keys are different, it
-    // will check each key in the other table. if the keys are the same, it will compare the
-    // values
-    for (primary_entry, secondary_entry) in zipped_cursor {
-        let (primary_key, primary_value) = primary_entry?;
-        let (secondary_key, secondary_value) = secondary_entry?;
-
-        if primary_key != secondary_key {
-            // if the keys are different, we need to check if the key is in the other table
-            let crossed_secondary =
-                secondary_cursor.seek_exact(primary_key.clone())?.map(|(_, value)| value);
-            result.try_push_discrepancy(
-                primary_key.clone(),
-                Some(primary_value),
-                crossed_secondary,
-            );
+        let Some(mut file) = self.file.take() else {
+            info!("No discrepancies or extra elements found in table {table}");
+            return Ok(())
+        };
 
-            // now do the same for the primary table
-            let crossed_primary =
-                primary_cursor.seek_exact(secondary_key.clone())?.map(|(_, value)| value);
-            result.try_push_discrepancy(
-                secondary_key.clone(),
-                crossed_primary,
-                Some(secondary_value),
+        let summary = [
+            format!("Found {} discrepancies in table {table}", self.discrepancies),
+            format!("Found {} extra elements in table {table}", self.extra_elements),
+        ];
+        for line in summary {
+            writeln!(file, "{line}")?;
+            info!("{line}");
+        }
+        if self.truncated {
+            let line = format!(
+                "Stopped after reaching the maximum number of differences in table {table}"
             );
-        } else {
-            // the keys are the same, so we need to compare the values
-            result.try_push_discrepancy(primary_key, Some(primary_value), Some(secondary_value));
+            writeln!(file, "{line}")?;
+            warn!("{line}");
         }
-    }
+        file.flush()?;
 
-    Ok(result)
+        info!(
+            "Done writing diff results for {table} to {}",
+            self.output_dir.join(format!("{table}.txt")).display()
+        );
+        Ok(())
+    }
 }
 
 /// Includes a table element between two databases with the same key, but different values
 #[derive(Debug)]
 struct TableDiffElement<T: Table> {
     /// The key for the element
+    #[expect(dead_code)]
     key: T::Key,
 
     /// The element from the first table
@@ -255,85 +374,6 @@ struct TableDiffElement<T: Table> {
     second: T::Value,
 }
 
-/// The diff result for an entire table. If the tables had the same number of elements, there will
-/// be no extra elements.
-struct TableDiffResult<T: Table>
-where
-    T::Key: Hash,
-{
-    /// All elements of the database that are different
-    discrepancies: BTreeMap<T::Key, TableDiffElement<T>>,
-
-    /// Any extra elements, and the table they are in
-    extra_elements: BTreeMap<T::Key, ExtraTableElement<T>>,
-}
-
-impl<T> Default for TableDiffResult<T>
-where
-    T: Table,
-    T::Key: Hash,
-{
-    fn default() -> Self {
-        Self { discrepancies: BTreeMap::default(), extra_elements: BTreeMap::default() }
-    }
-}
-
-impl<T: Table> TableDiffResult<T>
-where
-    T::Key: Hash,
-{
-    /// Push a diff result into the discrepancies set.
-    fn push_discrepancy(&mut self, discrepancy: TableDiffElement<T>) {
-        self.discrepancies.insert(discrepancy.key.clone(), discrepancy);
-    }
-
-    /// Push an extra element into the extra elements set.
-    fn push_extra_element(&mut self, element: ExtraTableElement<T>) {
-        self.extra_elements.insert(element.key().clone(), element);
-    }
-}
-
-impl<T> TableDiffResult<T>
-where
-    T: Table,
-    T::Key: Hash,
-    T::Value: PartialEq,
-{
-    /// Try to push a diff result into the discrepancy set, only pushing if the given elements are
-    /// different, and the discrepancy does not exist anywhere already.
-    fn try_push_discrepancy(
-        &mut self,
-        key: T::Key,
-        first: Option<T::Value>,
-        second: Option<T::Value>,
-    ) {
-        // do not bother comparing if the key is already in the discrepancies map
-        if self.discrepancies.contains_key(&key) {
-            return
-        }
-
-        // do not bother comparing if the key is already in the extra elements map
-        if self.extra_elements.contains_key(&key) {
-            return
-        }
-
-        match (first, second) {
-            (Some(first), Some(second)) => {
-                if first != second {
-                    self.push_discrepancy(TableDiffElement { key, first, second });
-                }
-            }
-            (Some(first), None) => {
-                self.push_extra_element(ExtraTableElement::First { key, value: first });
-            }
-            (None, Some(second)) => {
-                self.push_extra_element(ExtraTableElement::Second { key, value: second });
-            }
-            (None, None) => {}
-        }
-    }
-}
-
 /// A single extra element from a table
 #[derive(Debug)]
 enum ExtraTableElement<T: Table> {
@@ -346,11 +386,100 @@ enum ExtraTableElement<T: Table> {
     Second { key: T::Key, value: T::Value },
 }
 
-impl<T: Table> ExtraTableElement<T> {
-    /// Return the key for the extra element
-    const fn key(&self) -> &T::Key {
-        match self {
-            Self::First { key, .. } | Self::Second { key, .. } => key,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, B256, U256};
+    use reth_db::test_utils::create_test_rw_db;
+    use reth_db_api::{tables, transaction::DbTxMut};
+    use reth_primitives_traits::StorageEntry;
+
+    #[test]
+    fn lockstep_diff() {
+        let primary = create_test_rw_db();
+        let secondary = create_test_rw_db();
+
+        let tx = primary.tx_mut().unwrap();
+        for number in [1, 2, 3, 5] {
+            tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8)).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let tx = secondary.tx_mut().unwrap();
+        for number in [2, 3, 4, 5, 6] {
+            let hash = if number == 3 { B256::ZERO } else { B256::with_last_byte(number as u8) };
+            tx.put::<tables::CanonicalHeaders>(number, hash).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let output = tempfile::tempdir().unwrap();
+        let report = |start_key, end_key, max_diffs| {
+            find_diffs::<tables::CanonicalHeaders, ()>(
+                primary.tx().unwrap(),
+                secondary.tx().unwrap(),
+                start_key,
+                end_key,
+                max_diffs,
+                output.path(),
+                |_| (),
+            )
+            .unwrap();
+            let report = fs::read_to_string(output.path().join("CanonicalHeaders.txt")).unwrap();
+            fs::remove_dir_all(output.path()).unwrap();
+            report
+        };
+
+        let full = report(None, None, None);
+        assert!(full.contains("Found 1 discrepancies"));
+        assert!(full.contains("Found 3 extra elements"));
+        assert!(!full.contains("Stopped after"));
+
+        let range = report(Some("2"), Some("4"), None);
+        assert!(range.contains("Found 1 discrepancies"));
+        assert!(range.contains("Found 1 extra elements"));
+
+        let truncated = report(None, None, Some(2));
+        assert!(truncated.contains("Found 1 discrepancies"));
+        assert!(truncated.contains("Found 1 extra elements"));
+        assert!(truncated.contains("Stopped after"));
+    }
+
+    #[test]
+    fn dupsort_diff_by_subkey() {
+        let primary = create_test_rw_db();
+        let secondary = create_test_rw_db();
+        let address = Address::with_last_byte(1);
+        let entry = |slot: u8, value: u64| StorageEntry {
+            key: B256::with_last_byte(slot),
+            value: U256::from(value),
+        };
+
+        let tx = primary.tx_mut().unwrap();
+        for entry in [entry(1, 1), entry(2, 2), entry(3, 3)] {
+            tx.put::<tables::PlainStorageState>(address, entry).unwrap();
+        }
+        tx.commit().unwrap();
+
+        // The first slot is missing and the last one differs
+        let tx = secondary.tx_mut().unwrap();
+        for entry in [entry(2, 2), entry(3, 4)] {
+            tx.put::<tables::PlainStorageState>(address, entry).unwrap();
         }
+        tx.commit().unwrap();
+
+        let output = tempfile::tempdir().unwrap();
+        find_diffs::<tables::PlainStorageState, B256>(
+            primary.tx().unwrap(),
+            secondary.tx().unwrap(),
+            None,
+            None,
+            None,
+            output.path(),
+            ValueWithSubKey::get_subkey,
+        )
+        .unwrap();
+        let report = fs::read_to_string(output.path().join("PlainStorageState.txt")).unwrap();
+        assert!(report.contains("Found 1 discrepancies"));
+        assert!(report.contains("Found 1 extra elements"));
     }
 }
@@ -0,0 +1,467 @@
+//! `reth db export-segment` command for exporting a range of blocks into a new datadir
+
+use crate::db::checksum::ChecksumViewer;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use clap::Parser;
+use reth_chainspec::EthChainSpec;
+use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
+use reth_db_api::{
+    models::ClientVersion, table::TableImporter, tables, transaction::DbTxMut, Tables,
+};
+use reth_db_common::{migration::SCHEMA_VERSION, DbTool};
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_node_api::{HeaderTy, NodeTypesWithDBAdapter, ReceiptTy, TxTy};
+use reth_node_core::{
+    args::DatadirArgs,
+    dirs::{ChainPath, DataDirPath, PlatformPath},
+};
+use reth_primitives_traits::{Account, Bytecode, StorageEntry};
+use reth_provider::{
+    providers::{ProviderNodeTypes, RocksDBProvider, StaticFileProvider},
+    BlockBodyIndicesProvider, BlockReader, ChainSpecProvider, DBProvider, DatabaseProviderFactory,
+    HeaderProvider, MetadataWriter, ProviderFactory, PruneCheckpointReader, ReceiptProvider,
+    StageCheckpointReader, StageCheckpointWriter, StateProviderFactory, StorageSettings,
+    TransactionsProvider,
+};
+use reth_prune::PruneSegment;
+use reth_revm::database::StateProviderDatabase;
+use reth_stages::{StageCheckpoint, StageId};
+use revm::{bytecode::Bytecode as RevmBytecode, state::AccountInfo, Database};
+use serde::Serialize;
+use std::{collections::BTreeMap, ops::RangeInclusive, sync::Arc};
+use tracing::info;
+
+/// The number of ancestors of the first block whose headers are exported, which is how far back
+/// the `BLOCKHASH` opcode can read.
+const BLOCKHASH_HISTORY: u64 = 256;
+
+/// The file name of the [`SegmentManifest`] in the exported datadir.
+const MANIFEST_FILE_NAME: &str = "segment-manifest.json";
+
+/// The tables that are exported, whose checksums are written to the manifest.
+const EXPORTED_TABLES: [Tables; 13] = [
+    Tables::CanonicalHeaders,
+    Tables::Headers,
+    Tables::HeaderNumbers,
+    Tables::BlockBodyIndices,
+    Tables::BlockOmmers,
+    Tables::BlockWithdrawals,
+    Tables::Transactions,
+    Tables::TransactionBlocks,
+    Tables::TransactionSenders,
+    Tables::Receipts,
+    Tables::PlainAccountState,
+    Tables::PlainStorageState,
+    Tables::Bytecodes,
+];
+
+/// The node types of the exported datadir, which are the ones of the source datadir.
+type SegmentNode<N> = NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>;
+
+/// The arguments for the `reth db export-segment` command
+///
+/// The exported datadir holds the headers, bodies, senders and receipts of the segment, and the
+/// state before the segment of the accounts, storage slots and bytecodes that the segment reads or
+/// changes, which are recorded by executing the segment on top of the historical state before it.
+/// Its execution checkpoint is the block before the segment, so the segment is executed by
+/// `reth stage run execution --from <FROM - 1> --to <TO>`, which first unwinds the receipts.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The first block of the segment.
+    #[arg(long)]
+    from: BlockNumber,
+
+    /// The last block of the segment.
+    #[arg(long)]
+    to: BlockNumber,
+
+    /// The path to the new datadir, which must not have a database yet.
+    ///
+    /// A `segment-manifest.json` file with the source chain, the range, the block hashes and the
+    /// checksums of the exported tables is written to it.
+    #[arg(long, value_name = "OUTPUT_PATH", verbatim_doc_comment)]
+    output: PlatformPath<DataDirPath>,
+}
+
+/// Manifest written to the root of the exported datadir, to check its integrity when loading it.
+///
+/// The checksums are computed like `reth db checksum <TABLE>` computes them on the exported
+/// datadir.
+#[derive(Debug, Serialize)]
+struct SegmentManifest {
+    /// The chain of the source datadir.
+    chain: String,
+    /// The hash of the genesis block of the chain.
+    genesis_hash: B256,
+    /// The first block of the segment.
+    from: BlockNumber,
+    /// The last block of the segment.
+    to: BlockNumber,
+    /// The hashes of the blocks from the parent of the first block to the last block.
+    block_hashes: BTreeMap<BlockNumber, B256>,
+    /// The number of exported transactions.
+    transactions: u64,
+    /// The number of exported accounts.
+    accounts: usize,
+    /// The number of exported storage slots.
+    storage_slots: usize,
+    /// The checksums of the exported tables.
+    checksums: BTreeMap<&'static str, String>,
+    /// The arguments of the command that executes the segment.
+    replay: String,
+}
+
+impl Command {
+    /// Execute `db export-segment` command
+    pub fn execute<N, E>(self, tool: &DbTool<N>, evm_config: &E) -> eyre::Result<()>
+    where
+        N: ProviderNodeTypes,
+        E: ConfigureEvm<Primitives = N::Primitives>,
+    {
+        let Self { from, to, output } = self;
+        let output = ChainPath::new(output, tool.chain().chain(), DatadirArgs::default());
+        export_segment(&tool.provider_factory, evm_config, from..=to, &output)
+    }
+}
+
+/// Exports the segment from the datadir of the provider factory into the new `output` datadir,
+/// see [`Command`].
+fn export_segment<N, E>(
+    factory: &ProviderFactory<N>,
+    evm_config: &E,
+    segment: RangeInclusive<BlockNumber>,
+    output: &ChainPath<DataDirPath>,
+) -> eyre::Result<()>
+where
+    N: ProviderNodeTypes,
+    E: ConfigureEvm<Primitives = N::Primitives>,
+{
+    let (from, to) = segment.into_inner();
+    eyre::ensure!(
+        from > 0 && from <= to,
+        "Invalid segment {from}..={to}, it must start after the genesis block"
+    );
+
+    let chain = factory.chain_spec();
+    eyre::ensure!(!output.db().exists(), "Database already exists: {:?}", output.db());
+
+    let provider = factory.provider()?.disable_long_read_transaction_safety();
+
+    // The receipts of the segment are exported, and the state before it is read from history
+    for stage in [StageId::Execution, StageId::IndexAccountHistory, StageId::IndexStorageHistory] {
+        let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default();
+        eyre::ensure!(
+            checkpoint.block_number >= to,
+            "The {stage} stage only reached block {}, before block {to}",
+            checkpoint.block_number
+        );
+    }
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        if let Some(pruned) =
+            provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number) &&
+            pruned >= from
+        {
+            eyre::bail!("The {segment:?} changesets are pruned up to block {pruned}")
+        }
+    }
+
+    let indices = provider.block_body_indices_range(from - 1..=to)?;
+    eyre::ensure!(indices.len() as u64 == to - from + 2, "Missing block bodies of the segment");
+    let tx_range = indices[1].first_tx_num..indices[indices.len() - 1].next_tx_num();
+    eyre::ensure!(!tx_range.is_empty(), "The segment has no transactions to execute");
+
+    // The genesis header is exported too, since opening a datadir checks it against the chain
+    let first_header = from.saturating_sub(BLOCKHASH_HISTORY);
+    let mut headers = provider.sealed_headers_range(first_header..=to)?;
+    if first_header > 0 {
+        headers.insert(
+            0,
+            provider.sealed_header(0)?.ok_or_else(|| eyre::eyre!("Missing genesis header"))?,
+        );
+    }
+    eyre::ensure!(
+        headers.len() as u64 == to - first_header + 1 + u64::from(first_header > 0),
+        "Missing headers of the segment"
+    );
+
+    let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+    let senders = provider.senders_by_tx_range(tx_range.clone())?;
+    let receipts = provider.receipts_by_tx_range(tx_range.clone())?;
+    let tx_count = tx_range.end - tx_range.start;
+    for (name, len) in [
+        ("transactions", transactions.len()),
+        ("senders", senders.len()),
+        ("receipts", receipts.len()),
+    ] {
+        eyre::ensure!(len as u64 == tx_count, "Missing {name} of the segment, are they pruned?");
+    }
+
+    let state = SegmentState::before(factory, evm_config, from..=to)?;
+    info!(
+        target: "reth::cli",
+        transactions = tx_count,
+        accounts = state.accounts.len(),
+        storage_slots = state.storages.len(),
+        "Read segment"
+    );
+
+    info!(target: "reth::cli", path = ?output.db(), "Creating segment datadir");
+    let output_db = init_db(output.db(), DatabaseArguments::new(ClientVersion::default()))?;
+    let output_factory = ProviderFactory::<SegmentNode<N>>::new(
+        Arc::new(output_db),
+        chain.clone(),
+        StaticFileProvider::read_write(output.static_files())?,
+        RocksDBProvider::builder(output.rocksdb()).build()?,
+    )?;
+
+    // Everything is written to the database, which is read when the static files don't have
+    // the data
+    let provider_rw = output_factory.database_provider_rw()?;
+    let tx = provider_rw.tx_ref();
+    let mut block_hashes = BTreeMap::new();
+    for header in headers {
+        let (number, hash) = (header.number(), header.hash());
+        tx.put::<tables::CanonicalHeaders>(number, hash)?;
+        tx.put::<tables::HeaderNumbers>(hash, number)?;
+        tx.put::<tables::Headers<HeaderTy<N>>>(number, header.into_header())?;
+        if number >= from - 1 {
+            block_hashes.insert(number, hash);
+        }
+    }
+    for (number, indices) in (from - 1..=to).zip(indices) {
+        tx.put::<tables::BlockBodyIndices>(number, indices)?;
+    }
+    tx.import_table_with_range::<tables::BlockOmmers<HeaderTy<N>>, _>(
+        provider.tx_ref(),
+        Some(from),
+        to,
+    )?;
+    tx.import_table_with_range::<tables::BlockWithdrawals, _>(provider.tx_ref(), Some(from), to)?;
+    tx.import_table_with_range::<tables::TransactionBlocks, _>(
+        provider.tx_ref(),
+        Some(tx_range.start),
+        tx_range.end - 1,
+    )?;
+    for (tx_num, ((transaction, sender), receipt)) in
+        tx_range.zip(transactions.into_iter().zip(senders).zip(receipts))
+    {
+        tx.put::<tables::Transactions<TxTy<N>>>(tx_num, transaction)?;
+        tx.put::<tables::TransactionSenders>(tx_num, sender)?;
+        tx.put::<tables::Receipts<ReceiptTy<N>>>(tx_num, receipt)?;
+    }
+    for (address, account) in &state.accounts {
+        tx.put::<tables::PlainAccountState>(*address, *account)?;
+    }
+    for ((address, key), value) in &state.storages {
+        tx.put::<tables::PlainStorageState>(*address, StorageEntry { key: *key, value: *value })?;
+    }
+    for (hash, bytecode) in &state.bytecodes {
+        tx.put::<tables::Bytecodes>(*hash, bytecode.clone())?;
+    }
+
+    for stage in [StageId::Headers, StageId::Bodies, StageId::SenderRecovery] {
+        provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(to))?;
+    }
+    provider_rw.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(from - 1))?;
+    provider_rw.write_storage_settings(StorageSettings::legacy())?;
+    provider_rw.write_schema_version(SCHEMA_VERSION)?;
+    provider_rw.commit()?;
+
+    let output_tool = DbTool::new(output_factory)?;
+    let mut checksums = BTreeMap::new();
+    for table in EXPORTED_TABLES {
+        let (checksum, _) = table.view(&ChecksumViewer::new(&output_tool))?;
+        checksums.insert(table.name(), format!("{checksum:#x}"));
+    }
+
+    let manifest = SegmentManifest {
+        chain: chain.chain().to_string(),
+        genesis_hash: chain.genesis_hash(),
+        from,
+        to,
+        block_hashes,
+        transactions: tx_count,
+        accounts: state.accounts.len(),
+        storage_slots: state.storages.len(),
+        checksums,
+        replay: format!("stage run execution --from {} --to {to}", from - 1),
+    };
+    let manifest_path = output.data_dir().join(MANIFEST_FILE_NAME);
+    reth_fs_util::write_json_file(&manifest_path, &manifest)?;
+    info!(target: "reth::cli", path = ?manifest_path, "Wrote segment manifest");
+
+    Ok(())
+}
+
+/// The state before a segment of the accounts, storage slots and bytecodes that the segment
+/// accesses.
+#[derive(Debug, Default)]
+struct SegmentState {
+    accounts: BTreeMap<Address, Account>,
+    storages: BTreeMap<(Address, B256), U256>,
+    bytecodes: BTreeMap<B256, Bytecode>,
+}
+
+impl SegmentState {
+    /// Records the state that the range accesses by executing it on top of the historical state
+    /// before it.
+    ///
+    /// Accounts that don't exist and storage slots that are zero before the range are left out.
+    fn before<N, E>(
+        factory: &ProviderFactory<N>,
+        evm_config: &E,
+        range: RangeInclusive<BlockNumber>,
+    ) -> eyre::Result<Self>
+    where
+        N: ProviderNodeTypes,
+        E: ConfigureEvm<Primitives = N::Primitives>,
+    {
+        let history = factory.history_by_block_number(range.start() - 1)?;
+        let blocks = factory.recovered_block_range(range)?;
+
+        let mut executor =
+            evm_config.batch_executor(RecordingDatabase::new(StateProviderDatabase::new(history)));
+        for block in &blocks {
+            executor.execute_one(block)?;
+        }
+        Ok(executor.into_state().database.into_state()?)
+    }
+}
+
+/// A database that records the state that is read from it.
+///
+/// The executor caches everything it reads, so every account, storage slot and bytecode is read
+/// from the database once, before the executed blocks change it.
+#[derive(Debug)]
+struct RecordingDatabase<DB> {
+    inner: DB,
+    state: SegmentState,
+}
+
+impl<DB: Database> RecordingDatabase<DB> {
+    fn new(inner: DB) -> Self {
+        Self { inner, state: SegmentState::default() }
+    }
+
+    /// Returns the recorded state, with the account and bytecode of every address whose storage
+    /// was accessed, which an executor needs to access the storage again.
+    fn into_state(mut self) -> Result<SegmentState, DB::Error> {
+        let addresses = self.state.storages.keys().map(|(address, _)| *address).collect::<Vec<_>>();
+        for address in addresses {
+            if !self.state.accounts.contains_key(&address) {
+                self.basic(address)?;
+            }
+        }
+        let hashes = self
+            .state
+            .accounts
+            .values()
+            .filter_map(|account| account.bytecode_hash)
+            .collect::<Vec<_>>();
+        for hash in hashes {
+            if !self.state.bytecodes.contains_key(&hash) {
+                self.code_by_hash(hash)?;
+            }
+        }
+        Ok(self.state)
+    }
+}
+
+impl<DB: Database> Database for RecordingDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.inner.basic(address)?;
+        if let Some(info) = &info {
+            self.state.accounts.entry(address).or_insert_with(|| info.into());
+        }
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<RevmBytecode, Self::Error> {
+        let code = self.inner.code_by_hash(code_hash)?;
+        self.state.bytecodes.entry(code_hash).or_insert_with(|| Bytecode(code.clone()));
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let value = self.inner.storage(address, index)?;
+        if !value.is_zero() {
+            self.state.storages.entry((address, index.into())).or_insert(value);
+        }
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.inner.block_hash(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_consensus::noop::NoopConsensus;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::test_utils::MockNodeTypesWithDB;
+    use reth_stages::{
+        stages::{ExecutionStage, IndexAccountHistoryStage, IndexStorageHistoryStage},
+        test_utils::{ExecutableChain, TestStageDB},
+        ExecInput, Stage,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn exported_segment_re_executes() {
+        let chain = ExecutableChain::generate(6, 4);
+        let db = TestStageDB::default();
+        chain.insert(&db).unwrap();
+        let evm_config =
+            EthEvmConfig::new(Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build()));
+
+        // The export reads the receipts and the history that the pipeline writes
+        let provider_rw = db.factory.database_provider_rw().unwrap();
+        let input = ExecInput { target: Some(6), checkpoint: None };
+        ExecutionStage::new_with_executor(evm_config.clone(), NoopConsensus::arc())
+            .execute(&provider_rw, input)
+            .unwrap();
+        IndexAccountHistoryStage::default().execute(&provider_rw, input).unwrap();
+        IndexStorageHistoryStage::default().execute(&provider_rw, input).unwrap();
+        for stage in
+            [StageId::Execution, StageId::IndexAccountHistory, StageId::IndexStorageHistory]
+        {
+            provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(6)).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let output = ChainPath::new(
+            PlatformPath::from_str(dir.path().to_str().unwrap()).unwrap(),
+            db.factory.chain_spec().chain(),
+            DatadirArgs::default(),
+        );
+        export_segment(&db.factory, &evm_config, 4..=6, &output).unwrap();
+
+        // The counter contract and its storage slot are only read before they're changed, so the
+        // segment executes like it did on the full state only if both were exported
+        let exported = ProviderFactory::<SegmentNode<MockNodeTypesWithDB>>::new(
+            Arc::new(
+                init_db(output.db(), DatabaseArguments::new(ClientVersion::default())).unwrap(),
+            ),
+            db.factory.chain_spec(),
+            StaticFileProvider::read_write(output.static_files()).unwrap(),
+            RocksDBProvider::builder(output.rocksdb()).build().unwrap(),
+        )
+        .unwrap();
+        let mut executor =
+            evm_config.batch_executor(StateProviderDatabase::new(exported.latest().unwrap()));
+        let receipts = exported
+            .recovered_block_range(4..=6)
+            .unwrap()
+            .iter()
+            .map(|block| executor.execute_one(block).unwrap().receipts)
+            .collect::<Vec<_>>();
+        assert_eq!(receipts, db.factory.receipts_by_block_range(4..=6).unwrap());
+    }
+}
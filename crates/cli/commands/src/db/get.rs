@@ -44,6 +44,15 @@ enum Subcommand {
         #[arg(value_parser = maybe_json_value_parser)]
         subkey: Option<String>,
 
+        /// The subkey to get content for, only available for DUPSORT tables
+        #[arg(
+            long = "subkey",
+            value_name = "SUBKEY",
+            value_parser = maybe_json_value_parser,
+            conflicts_with = "subkey"
+        )]
+        subkey_flag: Option<String>,
+
         /// Optional end key for range query (exclusive upper bound)
         #[arg(value_parser = maybe_json_value_parser)]
         end_key: Option<String>,
@@ -74,7 +83,11 @@ impl Command {
     /// Execute `db get` command
     pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
         match self.subcommand {
-            Subcommand::Mdbx { table, key, subkey, end_key, end_subkey, raw } => {
+            Subcommand::Mdbx { table, key, subkey, subkey_flag, end_key, end_subkey, raw } => {
+                if subkey_flag.is_some() && !table.is_dupsort() {
+                    eyre::bail!("Only DUPSORT tables have subkeys")
+                }
+                let subkey = subkey.or(subkey_flag);
                 table.view(&GetValueViewer { tool, key, subkey, end_key, end_subkey, raw })?
             }
             Subcommand::StaticFile { segment, key, raw } => {
@@ -218,13 +231,11 @@ impl<N: ProviderNodeTypes> TableViewer<()> for GetValueViewer<'_, N> {
             })??;
         } else {
             // Single key lookup
-            let content = if self.raw {
-                self.tool
-                    .get::<RawTable<T>>(RawKey::from(key))?
-                    .map(|content| hex::encode_prefixed(content.raw_value()))
-            } else {
-                self.tool.get::<T>(key)?.as_ref().map(serde_json::to_string_pretty).transpose()?
-            };
+            let content = self
+                .tool
+                .get::<RawTable<T>>(RawKey::from(key))?
+                .map(|content| format_value::<T>(content.raw_value(), self.raw))
+                .transpose()?;
 
             match content {
                 Some(content) => {
@@ -309,17 +320,11 @@ impl<N: ProviderNodeTypes> TableViewer<()> for GetValueViewer<'_, N> {
             // Single key/subkey lookup
             let subkey = table_subkey::<T>(self.subkey.as_deref())?;
 
-            let content = if self.raw {
-                self.tool
-                    .get_dup::<RawDupSort<T>>(RawKey::from(key), RawKey::from(subkey))?
-                    .map(|content| hex::encode_prefixed(content.raw_value()))
-            } else {
-                self.tool
-                    .get_dup::<T>(key, subkey)?
-                    .as_ref()
-                    .map(serde_json::to_string_pretty)
-                    .transpose()?
-            };
+            let content = self
+                .tool
+                .get_dup::<RawDupSort<T>>(RawKey::from(key), RawKey::from(subkey))?
+                .map(|content| format_value::<T>(content.raw_value(), self.raw))
+                .transpose()?;
 
             match content {
                 Some(content) => {
@@ -334,6 +339,14 @@ impl<N: ProviderNodeTypes> TableViewer<()> for GetValueViewer<'_, N> {
     }
 }
 
+/// Formats a compressed value of the given table, either as hex or decoded into pretty JSON.
+fn format_value<T: Table>(value: &[u8], raw: bool) -> eyre::Result<String> {
+    if raw {
+        return Ok(hex::encode_prefixed(value))
+    }
+    Ok(serde_json::to_string_pretty(&T::Value::decompress(value)?)?)
+}
+
 /// Map the user input value to json
 pub(crate) fn maybe_json_value_parser(value: &str) -> Result<String, eyre::Error> {
     if serde_json::from_str::<serde::de::IgnoredAny>(value).is_ok() {
@@ -346,12 +359,16 @@ pub(crate) fn maybe_json_value_parser(value: &str) -> Result<String, eyre::Error
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::{address, B256};
+    use alloy_consensus::Header;
+    use alloy_primitives::{address, b256, B256, U256};
     use clap::{Args, Parser};
     use reth_db_api::{
         models::{storage_sharded_key::StorageShardedKey, ShardedKey},
-        AccountsHistory, HashedAccounts, Headers, StageCheckpoints, StoragesHistory,
+        AccountsHistory, HashedAccounts, Headers, PlainAccountState, StageCheckpoints,
+        StoragesHistory,
     };
+    use reth_primitives_traits::Account;
+    use serde::de::DeserializeOwned;
     use std::str::FromStr;
 
     /// A helper type to parse Args more easily
@@ -407,4 +424,82 @@ mod tests {
             )
         );
     }
+
+    /// Asserts that the value survives being compressed, formatted and parsed back from JSON.
+    fn assert_round_trip<T: Table>(value: T::Value)
+    where
+        T::Value: DeserializeOwned + PartialEq + std::fmt::Debug + Clone,
+    {
+        let compressed = value.clone().compress();
+        let raw = format_value::<T>(compressed.as_ref(), true).unwrap();
+        assert_eq!(raw, hex::encode_prefixed(compressed.as_ref()));
+
+        let json = format_value::<T>(compressed.as_ref(), false).unwrap();
+        assert_eq!(serde_json::from_str::<T::Value>(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn round_trip_values() {
+        assert_round_trip::<Headers>(Header {
+            number: 100,
+            gas_limit: 30_000_000,
+            gas_used: 21_000,
+            timestamp: 1_700_000_000,
+            base_fee_per_gas: Some(7),
+            ..Default::default()
+        });
+        assert_round_trip::<PlainAccountState>(Account {
+            nonce: 1,
+            balance: U256::from(10).pow(U256::from(18)),
+            bytecode_hash: Some(b256!(
+                "0x0ac361fe774b78f8fc4e86c1916930d150865c3fc2e21dca2e58833557608bac"
+            )),
+        });
+
+        let mut receipt = <<Receipts as Table>::Value>::default();
+        receipt.success = true;
+        receipt.cumulative_gas_used = 21_000;
+        assert_round_trip::<Receipts>(receipt);
+    }
+
+    #[test]
+    fn parse_friendly_keys() {
+        let key = maybe_json_value_parser("100").unwrap();
+        assert_eq!(table_key::<Headers>(&key).unwrap(), 100);
+
+        let key = maybe_json_value_parser("0x4448e1273fd5a8bfdb9ed111e96889c960eee145").unwrap();
+        assert_eq!(
+            table_key::<PlainAccountState>(&key).unwrap(),
+            address!("0x4448e1273fd5a8bfdb9ed111e96889c960eee145")
+        );
+    }
+
+    #[test]
+    fn parse_subkey_flag() {
+        let args = CommandParser::<Command>::try_parse_from([
+            "reth",
+            "mdbx",
+            "PlainStorageState",
+            "0x4448e1273fd5a8bfdb9ed111e96889c960eee145",
+            "--subkey",
+            "0x0000000000000000000000000000000000000000000000000000000000000003",
+        ])
+        .unwrap()
+        .args;
+        assert!(matches!(
+            args.subcommand,
+            Subcommand::Mdbx { subkey: None, subkey_flag: Some(_), .. }
+        ));
+
+        assert!(CommandParser::<Command>::try_parse_from([
+            "reth",
+            "mdbx",
+            "PlainStorageState",
+            "0x01",
+            "0x02",
+            "--subkey",
+            "0x03",
+        ])
+        .is_err());
+    }
 }
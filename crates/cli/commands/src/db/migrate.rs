@@ -0,0 +1,52 @@
+//! `reth db migrate` command for applying the pending database migrations
+
+use crate::common::AccessRights;
+use clap::Parser;
+use reth_db_common::{
+    migration::{migrate, pending_migrations, schema_version, SCHEMA_VERSION},
+    DbTool,
+};
+use reth_provider::{providers::ProviderNodeTypes, DatabaseProviderFactory};
+
+/// The arguments for the `reth db migrate` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only list the pending migrations without applying them
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Command {
+    /// Returns database access rights required for the command.
+    pub const fn access_rights(&self) -> AccessRights {
+        if self.dry_run {
+            AccessRights::RO
+        } else {
+            AccessRights::RW
+        }
+    }
+
+    /// Execute `db migrate` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let factory = &tool.provider_factory;
+        let version = schema_version(&factory.database_provider_ro()?)?;
+        println!("Database schema version: {version}, latest version: {SCHEMA_VERSION}");
+
+        let pending = pending_migrations(factory)?;
+        if pending.is_empty() {
+            println!("No pending migrations");
+            return Ok(())
+        }
+        for migration in &pending {
+            let kind = if migration.is_destructive() { " (destructive)" } else { "" };
+            println!("Pending migration {}{kind}: {}", migration.id(), migration.description());
+        }
+        if self.dry_run {
+            return Ok(())
+        }
+
+        let applied = migrate(factory, true)?;
+        println!("Applied {applied} migrations, database schema version: {SCHEMA_VERSION}");
+        Ok(())
+    }
+}
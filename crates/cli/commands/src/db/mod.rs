@@ -1,4 +1,4 @@
-use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use crate::common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment, EnvironmentArgs};
 use clap::{Parser, Subcommand};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
@@ -10,17 +10,24 @@ use std::{
     sync::Arc,
 };
 mod account_storage;
+mod check_consistency;
 mod checksum;
 mod clear;
+mod compact;
 mod diff;
+mod export_segment;
 mod get;
 mod list;
+mod migrate;
 mod repair_trie;
 mod settings;
 mod static_file_header;
+mod static_files;
 mod stats;
 /// DB List TUI
 mod tui;
+mod verify_chain;
+mod verify_trie;
 
 /// `reth db` command
 #[derive(Debug, Parser)]
@@ -45,6 +52,8 @@ pub enum Subcommands {
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Exports a range of blocks and the state they access into a new datadir
+    ExportSegment(export_segment::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
@@ -53,18 +62,30 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Writes a compacted copy of the database without its free pages
+    Compact(compact::Command),
     /// Verifies trie consistency and outputs any inconsistencies
     RepairTrie(repair_trie::Command),
+    /// Verifies the trie tables against the hashed state and the state root of the latest block
+    VerifyTrie(verify_trie::Command),
     /// Reads and displays the static file segment header
     StaticFileHeader(static_file_header::Command),
+    /// Lists the static file segments and optionally checks them against the database
+    StaticFiles(static_files::Command),
     /// Lists current and local database versions
     Version,
+    /// Applies the pending database migrations
+    Migrate(migrate::Command),
     /// Returns the full database path
     Path,
     /// Manage storage settings
     Settings(settings::Command),
     /// Gets storage size information for an account
     AccountStorage(account_storage::Command),
+    /// Verifies the hash chain of the headers against the hash indices
+    VerifyChain(verify_chain::Command),
+    /// Compares the stage checkpoints, static files and canonical hashes
+    CheckConsistency(check_consistency::Command),
 }
 
 /// Initializes a provider factory with specified access rights, and then execute with the provided
@@ -80,10 +101,15 @@ macro_rules! db_exec {
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
     /// Execute `db` command
-    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(
+    pub async fn execute<N, Comp>(
         self,
         ctx: CliContext,
-    ) -> eyre::Result<()> {
+        components: impl FnOnce(Arc<C::ChainSpec>) -> Comp,
+    ) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        Comp: CliNodeComponents<N>,
+    {
         let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
         let db_path = data_dir.db();
         let static_files_path = data_dir.static_files();
@@ -131,6 +157,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::ExportSegment(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RO, {
+                    let components = components(tool.chain());
+                    command.execute(&tool, components.evm_config())?;
+                });
+            }
             Subcommands::Drop { force } => {
                 if !force {
                     // Ask for confirmation
@@ -158,6 +190,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::Compact(command) => {
+                db_exec!(self.env, tool, N, command.access_rights(), {
+                    command.execute(tool, &db_path)?;
+                });
+            }
             Subcommands::RepairTrie(command) => {
                 let access_rights =
                     if command.dry_run { AccessRights::RO } else { AccessRights::RW };
@@ -165,11 +202,21 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool, ctx.task_executor.clone())?;
                 });
             }
+            Subcommands::VerifyTrie(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RO, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::StaticFileHeader(command) => {
                 db_exec!(self.env, tool, N, AccessRights::RoInconsistent, {
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::StaticFiles(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RoInconsistent, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -185,6 +232,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     println!("Local database is uninitialized");
                 }
             }
+            Subcommands::Migrate(command) => {
+                db_exec!(self.env, tool, N, command.access_rights(), {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Path => {
                 println!("{}", db_path.display());
             }
@@ -198,6 +250,16 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::VerifyChain(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RoInconsistent, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::CheckConsistency(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RoInconsistent, {
+                    command.execute(&tool)?;
+                });
+            }
         }
 
         Ok(())
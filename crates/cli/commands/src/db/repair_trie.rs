@@ -127,7 +127,7 @@ fn verify_only<N: ProviderNodeTypes>(tool: &DbTool<N>) -> eyre::Result<()> {
 
         if let Output::Progress(path) = output {
             if last_progress_time.elapsed() > PROGRESS_PERIOD {
-                output_progress(path, start_time, inconsistent_nodes);
+                output_progress("Repairing", path, start_time, inconsistent_nodes);
                 last_progress_time = Instant::now();
             }
         } else {
@@ -157,7 +157,7 @@ fn verify_only<N: ProviderNodeTypes>(tool: &DbTool<N>) -> eyre::Result<()> {
 }
 
 /// Checks that the merkle stage has completed running up to the account and storage hashing stages.
-fn verify_checkpoints(provider: impl StageCheckpointReader) -> eyre::Result<()> {
+pub(crate) fn verify_checkpoints(provider: impl StageCheckpointReader) -> eyre::Result<()> {
     let account_hashing_checkpoint =
         provider.get_stage_checkpoint(StageId::AccountHashing)?.unwrap_or_default();
     let storage_hashing_checkpoint =
@@ -291,7 +291,7 @@ fn verify_and_repair<N: ProviderNodeTypes>(tool: &DbTool<N>) -> eyre::Result<()>
             }
             Output::Progress(path) => {
                 if last_progress_time.elapsed() > PROGRESS_PERIOD {
-                    output_progress(path, start_time, inconsistent_nodes);
+                    output_progress("Repairing", path, start_time, inconsistent_nodes);
                     last_progress_time = Instant::now();
                 }
             }
@@ -308,8 +308,14 @@ fn verify_and_repair<N: ProviderNodeTypes>(tool: &DbTool<N>) -> eyre::Result<()>
     Ok(())
 }
 
-/// Output progress information based on the last seen account path.
-fn output_progress(last_account: Nibbles, start_time: Instant, inconsistent_nodes: u64) {
+/// Output progress information of the `action` on the trie tables based on the last seen account
+/// path.
+pub(crate) fn output_progress(
+    action: &str,
+    last_account: Nibbles,
+    start_time: Instant,
+    inconsistent_nodes: u64,
+) {
     // Calculate percentage based on position in the trie path space
     // For progress estimation, we'll use the first few nibbles as an approximation
 
@@ -341,7 +347,7 @@ fn output_progress(last_account: Nibbles, start_time: Instant, inconsistent_node
         progress_percent = progress_percent_str,
         eta = %humantime::format_duration(eta_duration),
         inconsistent_nodes,
-        "Repairing trie tables",
+        "{action} trie tables",
     );
 }
 
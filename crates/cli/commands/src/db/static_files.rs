@@ -0,0 +1,232 @@
+use crate::db::stats::{static_files_stats, StaticFileStats};
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use itertools::Itertools;
+use reth_db_common::DbTool;
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockBodyIndicesProvider, StageCheckpointReader,
+    StaticFileProviderFactory,
+};
+use reth_stages::StageId;
+use reth_static_file_types::{SegmentRangeInclusive, StaticFileSegment};
+
+/// The arguments for the `reth db static-files` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Cross-verify the segment ranges against the stage checkpoints and the block body indices,
+    /// and report any gaps or overlaps between static files.
+    #[arg(long)]
+    check: bool,
+}
+
+impl Command {
+    /// Execute `db static-files` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let static_file_provider = tool.provider_factory.static_file_provider();
+        let stats = static_files_stats(&static_file_provider)?;
+        println!("{}", segments_table(&stats));
+
+        if !self.check {
+            return Ok(())
+        }
+
+        let provider = tool.provider_factory.provider()?;
+        let mut issues = Vec::new();
+        for files in stats.chunk_by(|a, b| a.segment == b.segment) {
+            issues.extend(check_ranges(files));
+            issues.extend(check_checkpoints(&provider, files)?);
+        }
+
+        if issues.is_empty() {
+            println!("No inconsistencies found.");
+            return Ok(())
+        }
+
+        for issue in &issues {
+            println!("{issue}");
+        }
+        eyre::bail!("Found {} inconsistencies in static files", issues.len())
+    }
+}
+
+/// Renders a table with one row per static file segment.
+fn segments_table(stats: &[StaticFileStats]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Segment",
+        "Highest Block",
+        "Highest Transaction",
+        "Files",
+        "Compression",
+        "Size",
+    ]);
+
+    for files in stats.chunk_by(|a, b| a.segment == b.segment) {
+        let last = files.last().expect("not empty list of files");
+        let highest_tx = files.iter().rev().find_map(|file| file.tx_range);
+        let compression = files
+            .iter()
+            .map(|file| file.compression.unwrap_or("none"))
+            .counts()
+            .into_iter()
+            .sorted()
+            .map(|(compression, count)| format!("{compression} ({count})"))
+            .join(", ");
+
+        let mut row = Row::new();
+        row.add_cell(Cell::new(last.segment))
+            .add_cell(Cell::new(last.block_range.end()))
+            .add_cell(Cell::new(
+                highest_tx.map_or("N/A".to_string(), |range| range.end().to_string()),
+            ))
+            .add_cell(Cell::new(files.len()))
+            .add_cell(Cell::new(compression))
+            .add_cell(Cell::new(human_bytes(
+                files.iter().map(StaticFileStats::total_size).sum::<u64>() as f64,
+            )));
+        table.add_row(row);
+    }
+
+    table
+}
+
+/// Reports gaps and overlaps between the block and transaction ranges of consecutive static files
+/// of a segment.
+fn check_ranges(files: &[StaticFileStats]) -> Vec<String> {
+    let mut issues = Vec::new();
+    for (previous, next) in files.iter().tuple_windows() {
+        issues.extend(range_issue(next.segment, "block", previous.block_range, next.block_range));
+    }
+
+    // Transaction ranges can be empty, so only the static files with transactions are compared.
+    for (previous, next) in files.iter().filter_map(|file| file.tx_range).tuple_windows() {
+        issues.extend(range_issue(files[0].segment, "transaction", previous, next));
+    }
+
+    issues
+}
+
+/// Returns the issue, if any, between two consecutive ranges of a segment.
+fn range_issue(
+    segment: StaticFileSegment,
+    kind: &str,
+    previous: SegmentRangeInclusive,
+    next: SegmentRangeInclusive,
+) -> Option<String> {
+    let expected = previous.end() + 1;
+    if next.start() > expected {
+        Some(format!(
+            "{segment}: gap between {kind} ranges {previous} and {next}, missing {kind}s \
+             {expected}..={}",
+            next.start() - 1
+        ))
+    } else if next.start() < expected {
+        Some(format!("{segment}: {kind} ranges {previous} and {next} overlap"))
+    } else {
+        None
+    }
+}
+
+/// Reports a mismatch between the highest block of a segment and the checkpoint of the stage
+/// writing it, and between the highest transaction of a segment and the block body indices.
+fn check_checkpoints(
+    provider: &(impl StageCheckpointReader + BlockBodyIndicesProvider),
+    files: &[StaticFileStats],
+) -> eyre::Result<Vec<String>> {
+    let last = files.last().expect("not empty list of files");
+    let segment = last.segment;
+    let highest_block = last.block_range.end();
+    let mut issues = Vec::new();
+
+    // Receipts and senders can be written to the database instead of static files, so static
+    // files being behind the checkpoint is only an issue for headers and transactions.
+    let (stage, always_in_static_files) = match segment {
+        StaticFileSegment::Headers => (StageId::Headers, true),
+        StaticFileSegment::Transactions => (StageId::Bodies, true),
+        StaticFileSegment::Receipts => (StageId::Execution, false),
+        StaticFileSegment::TransactionSenders => (StageId::SenderRecovery, false),
+    };
+    let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number;
+    if highest_block > checkpoint {
+        issues.push(format!(
+            "{segment}: highest block {highest_block} is ahead of the {stage} stage checkpoint \
+             {checkpoint}"
+        ));
+    } else if highest_block < checkpoint && always_in_static_files {
+        issues.push(format!(
+            "{segment}: highest block {highest_block} is behind the {stage} stage checkpoint \
+             {checkpoint}"
+        ));
+    }
+
+    if segment.is_tx_based() {
+        let highest_tx = files.iter().rev().find_map(|file| file.tx_range).map(|range| range.end());
+        match provider.block_body_indices(highest_block)? {
+            Some(indices) if indices.tx_count() > 0 || highest_tx.is_some() => {
+                let expected = indices.last_tx_num();
+                if highest_tx != Some(expected) {
+                    issues.push(format!(
+                        "{segment}: highest transaction {highest_tx:?} does not match the last \
+                         transaction {expected} of block {highest_block}"
+                    ));
+                }
+            }
+            Some(_) => {}
+            None => issues.push(format!(
+                "{segment}: missing block body indices for highest block {highest_block}"
+            )),
+        }
+    }
+
+    Ok(issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(
+        block_range: (u64, u64),
+        tx_range: Option<(u64, u64)>,
+        compression: Option<&'static str>,
+    ) -> StaticFileStats {
+        StaticFileStats {
+            segment: StaticFileSegment::Transactions,
+            block_range: SegmentRangeInclusive::new(block_range.0, block_range.1),
+            tx_range: tx_range.map(|(start, end)| SegmentRangeInclusive::new(start, end)),
+            columns: 1,
+            rows: 0,
+            compression,
+            data_size: 1,
+            index_size: 0,
+            offsets_size: 0,
+            config_size: 0,
+        }
+    }
+
+    #[test]
+    fn contiguous_ranges() {
+        let files = [
+            file((0, 499_999), Some((0, 9)), Some("lz4")),
+            file((500_000, 999_999), None, Some("lz4")),
+            file((1_000_000, 1_000_010), Some((10, 20)), None),
+        ];
+        assert!(check_ranges(&files).is_empty());
+
+        let table = segments_table(&files).to_string();
+        assert!(table.contains("1000010"));
+        assert!(table.contains("lz4 (2), none (1)"));
+    }
+
+    #[test]
+    fn gaps_and_overlaps() {
+        let files =
+            [file((0, 499_999), Some((0, 9)), None), file((500_010, 999_999), Some((5, 20)), None)];
+        let issues = check_ranges(&files);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].contains("missing blocks 500000..=500009"));
+        assert!(issues[1].contains("transaction ranges 0..=9 and 5..=20 overlap"));
+    }
+}
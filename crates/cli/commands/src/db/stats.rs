@@ -9,11 +9,16 @@ use reth_db::{mdbx, static_file::iter_static_files, DatabaseEnv};
 use reth_db_api::{database::Database, TableViewer, Tables};
 use reth_db_common::DbTool;
 use reth_fs_util as fs;
+use reth_nippy_jar::compression::Compressors;
 use reth_node_builder::{NodePrimitives, NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_node_core::dirs::{ChainPath, DataDirPath};
 use reth_provider::providers::{ProviderNodeTypes, StaticFileProvider};
-use reth_static_file_types::SegmentRangeInclusive;
-use std::{sync::Arc, time::Duration};
+use reth_static_file_types::{SegmentRangeInclusive, StaticFileSegment};
+use serde::Serialize;
+use std::{path::Path, sync::Arc, time::Duration};
+
+/// The number of entries sampled per table to estimate the average key and value sizes.
+const SIZE_SAMPLE_ENTRIES: usize = 10_000;
 
 #[derive(Parser, Debug)]
 /// The arguments for the `reth db stats` command
@@ -38,6 +43,106 @@ pub struct Command {
     /// For individual table checksums, use the `reth db checksum` command.
     #[arg(long, default_value_t = false)]
     checksum: bool,
+
+    /// Also show the average key and value sizes of each table, sampled from its first entries.
+    #[arg(long, default_value_t = false)]
+    detailed: bool,
+
+    /// Print the statistics as JSON.
+    #[arg(long, default_value_t = false, conflicts_with = "checksum")]
+    json: bool,
+}
+
+/// Statistics of a database table.
+#[derive(Debug, Serialize)]
+struct TableStats {
+    name: &'static str,
+    entries: usize,
+    branch_pages: usize,
+    leaf_pages: usize,
+    overflow_pages: usize,
+    size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avg_key_size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avg_value_size: Option<f64>,
+}
+
+/// Statistics of the database.
+#[derive(Debug, Serialize)]
+struct DbStats {
+    /// The tables, sorted by size in descending order.
+    tables: Vec<TableStats>,
+    freelist_pages: usize,
+    freelist_size: usize,
+}
+
+impl DbStats {
+    fn tables_size(&self) -> usize {
+        self.tables.iter().map(|table| table.size).sum()
+    }
+}
+
+/// Statistics of a single static file.
+#[derive(Debug, Serialize)]
+pub(crate) struct StaticFileStats {
+    pub(crate) segment: StaticFileSegment,
+    pub(crate) block_range: SegmentRangeInclusive,
+    pub(crate) tx_range: Option<SegmentRangeInclusive>,
+    pub(crate) columns: usize,
+    pub(crate) rows: usize,
+    pub(crate) compression: Option<&'static str>,
+    pub(crate) data_size: u64,
+    pub(crate) index_size: u64,
+    pub(crate) offsets_size: u64,
+    pub(crate) config_size: u64,
+}
+
+impl StaticFileStats {
+    pub(crate) const fn total_size(&self) -> u64 {
+        self.data_size + self.index_size + self.offsets_size + self.config_size
+    }
+}
+
+/// Statistics of a static file segment, aggregated over all of its static files.
+#[derive(Debug, Serialize)]
+struct SegmentStats {
+    segment: StaticFileSegment,
+    block_range: SegmentRangeInclusive,
+    tx_range: Option<SegmentRangeInclusive>,
+    columns: usize,
+    rows: usize,
+    size: u64,
+}
+
+impl SegmentStats {
+    /// Aggregates the statistics of the static files of a segment, in ascending block order.
+    fn new(files: &[&StaticFileStats]) -> Option<Self> {
+        let (first, last) = (files.first()?, files.last()?);
+        // Transaction ranges can be empty, so we need to find the first and last which are not.
+        let tx_range = files.iter().rev().find_map(|file| file.tx_range).map(|last_range| {
+            let start = files.iter().find_map(|file| file.tx_range).unwrap_or(last_range).start();
+            SegmentRangeInclusive::new(start, last_range.end())
+        });
+        Some(Self {
+            segment: first.segment,
+            block_range: SegmentRangeInclusive::new(
+                first.block_range.start(),
+                last.block_range.end(),
+            ),
+            tx_range,
+            columns: first.columns,
+            rows: files.iter().map(|file| file.rows).sum(),
+            size: files.iter().map(|file| file.total_size()).sum(),
+        })
+    }
+}
+
+/// The statistics printed with `--json`.
+#[derive(Debug, Serialize)]
+struct StatsReport {
+    static_files: Vec<SegmentStats>,
+    db: DbStats,
 }
 
 impl Command {
@@ -47,43 +152,45 @@ impl Command {
         data_dir: ChainPath<DataDirPath>,
         tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
     ) -> eyre::Result<()> {
+        let static_file_provider =
+            StaticFileProvider::<N::Primitives>::read_only(data_dir.static_files(), false)?;
+        let static_files = static_files_stats(&static_file_provider)?;
+        let db = self.db_stats(tool)?;
+
+        if self.json {
+            let static_files = static_files
+                .chunk_by(|a, b| a.segment == b.segment)
+                .filter_map(|files| SegmentStats::new(&files.iter().collect::<Vec<_>>()))
+                .collect();
+            let report = StatsReport { static_files, db };
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            return Ok(())
+        }
+
         if self.checksum {
             let checksum_report = self.checksum_report(tool)?;
             println!("{checksum_report}");
             println!("\n");
         }
 
-        let static_files_stats_table = self.static_files_stats_table::<N::Primitives>(data_dir)?;
+        let static_files_stats_table = self.static_files_stats_table(&static_files);
         println!("{static_files_stats_table}");
 
         println!("\n");
 
-        let db_stats_table = self.db_stats_table(tool)?;
+        let db_stats_table = self.db_stats_table(&db);
         println!("{db_stats_table}");
 
         Ok(())
     }
 
-    fn db_stats_table<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>>(
+    fn db_stats<N: NodeTypesWithDB<DB = Arc<DatabaseEnv>>>(
         &self,
         tool: &DbTool<N>,
-    ) -> eyre::Result<ComfyTable> {
-        let mut table = ComfyTable::new();
-        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
-        table.set_header([
-            "Table Name",
-            "# Entries",
-            "Branch Pages",
-            "Leaf Pages",
-            "Overflow Pages",
-            "Total Size",
-        ]);
-
+    ) -> eyre::Result<DbStats> {
         tool.provider_factory.db_ref().view(|tx| {
-            let mut db_tables = Tables::ALL.iter().map(|table| table.name()).collect::<Vec<_>>();
-            db_tables.sort();
-            let mut total_size = 0;
-            for db_table in db_tables {
+            let mut tables = Vec::with_capacity(Tables::ALL.len());
+            for db_table in Tables::ALL.iter().map(|table| table.name()) {
                 let table_db = tx.inner.open_db(Some(db_table)).wrap_err("Could not open db.")?;
 
                 let stats = tx
@@ -99,58 +206,111 @@ impl Command {
                 let branch_pages = stats.branch_pages();
                 let overflow_pages = stats.overflow_pages();
                 let num_pages = leaf_pages + branch_pages + overflow_pages;
-                let table_size = page_size * num_pages;
 
-                total_size += table_size;
-                let mut row = Row::new();
-                row.add_cell(Cell::new(db_table))
-                    .add_cell(Cell::new(stats.entries()))
-                    .add_cell(Cell::new(branch_pages))
-                    .add_cell(Cell::new(leaf_pages))
-                    .add_cell(Cell::new(overflow_pages))
-                    .add_cell(Cell::new(human_bytes(table_size as f64)));
-                table.add_row(row);
-            }
+                let (avg_key_size, avg_value_size) = if self.detailed {
+                    let mut cursor = tx.inner.cursor(&table_db)?;
+                    let (mut sampled, mut key_size, mut value_size) = (0usize, 0usize, 0usize);
+                    for entry in cursor
+                        .iter_start::<mdbx::ObjectLength, mdbx::ObjectLength>()
+                        .take(SIZE_SAMPLE_ENTRIES)
+                    {
+                        let (key, value) = entry?;
+                        sampled += 1;
+                        key_size += *key;
+                        value_size += *value;
+                    }
+                    let avg = |size: usize| (sampled > 0).then(|| size as f64 / sampled as f64);
+                    (avg(key_size), avg(value_size))
+                } else {
+                    (None, None)
+                };
 
-            let max_widths = table.column_max_content_widths();
-            let mut separator = Row::new();
-            for width in max_widths {
-                separator.add_cell(Cell::new("-".repeat(width as usize)));
+                tables.push(TableStats {
+                    name: db_table,
+                    entries: stats.entries(),
+                    branch_pages,
+                    leaf_pages,
+                    overflow_pages,
+                    size: page_size * num_pages,
+                    avg_key_size,
+                    avg_value_size,
+                });
             }
-            table.add_row(separator);
-
-            let mut row = Row::new();
-            row.add_cell(Cell::new("Tables"))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(human_bytes(total_size as f64)));
-            table.add_row(row);
+            tables.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(b.name)));
 
-            let freelist = tx.inner.env().freelist()?;
+            let freelist_pages = tx.inner.env().freelist()?;
             let pagesize = tx.inner.db_stat(&mdbx::Database::freelist_db())?.page_size() as usize;
-            let freelist_size = freelist * pagesize;
 
+            Ok::<_, eyre::Report>(DbStats {
+                tables,
+                freelist_pages,
+                freelist_size: freelist_pages * pagesize,
+            })
+        })?
+    }
+
+    fn db_stats_table(&self, stats: &DbStats) -> ComfyTable {
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        let mut header = vec![
+            "Table Name",
+            "# Entries",
+            "Branch Pages",
+            "Leaf Pages",
+            "Overflow Pages",
+            "Total Size",
+        ];
+        if self.detailed {
+            header.extend(["Avg Key Size", "Avg Value Size"]);
+        }
+        table.set_header(header);
+
+        let format_avg = |size: Option<f64>| size.map_or("N/A".to_string(), human_bytes);
+        for table_stats in &stats.tables {
             let mut row = Row::new();
-            row.add_cell(Cell::new("Freelist"))
-                .add_cell(Cell::new(freelist))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(""))
-                .add_cell(Cell::new(human_bytes(freelist_size as f64)));
+            row.add_cell(Cell::new(table_stats.name))
+                .add_cell(Cell::new(table_stats.entries))
+                .add_cell(Cell::new(table_stats.branch_pages))
+                .add_cell(Cell::new(table_stats.leaf_pages))
+                .add_cell(Cell::new(table_stats.overflow_pages))
+                .add_cell(Cell::new(human_bytes(table_stats.size as f64)));
+            if self.detailed {
+                row.add_cell(Cell::new(format_avg(table_stats.avg_key_size)))
+                    .add_cell(Cell::new(format_avg(table_stats.avg_value_size)));
+            }
             table.add_row(row);
+        }
+
+        let max_widths = table.column_max_content_widths();
+        let mut separator = Row::new();
+        for width in max_widths {
+            separator.add_cell(Cell::new("-".repeat(width as usize)));
+        }
+        table.add_row(separator);
 
-            Ok::<(), eyre::Report>(())
-        })??;
+        let mut row = Row::new();
+        row.add_cell(Cell::new("Tables"))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(human_bytes(stats.tables_size() as f64)));
+        table.add_row(row);
 
-        Ok(table)
+        let mut row = Row::new();
+        row.add_cell(Cell::new("Freelist"))
+            .add_cell(Cell::new(stats.freelist_pages))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(human_bytes(stats.freelist_size as f64)));
+        table.add_row(row);
+
+        table
     }
 
-    fn static_files_stats_table<N: NodePrimitives>(
-        &self,
-        data_dir: ChainPath<DataDirPath>,
-    ) -> eyre::Result<ComfyTable> {
+    /// Returns the statistics of all static files, sorted by segment and block range.
+    fn static_files_stats_table(&self, stats: &[StaticFileStats]) -> ComfyTable {
         let mut table = ComfyTable::new();
         table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
 
@@ -176,133 +336,49 @@ impl Command {
             ]);
         }
 
-        let static_files = iter_static_files(&data_dir.static_files())?;
-        let static_file_provider =
-            StaticFileProvider::<N>::read_only(data_dir.static_files(), false)?;
-
-        let mut total_data_size = 0;
-        let mut total_index_size = 0;
-        let mut total_offsets_size = 0;
-        let mut total_config_size = 0;
-
-        for (segment, ranges) in static_files.into_iter().sorted_by_key(|(segment, _)| *segment) {
-            let (
-                mut segment_columns,
-                mut segment_rows,
-                mut segment_data_size,
-                mut segment_index_size,
-                mut segment_offsets_size,
-                mut segment_config_size,
-            ) = (0, 0, 0, 0, 0, 0);
-
-            for (block_range, header) in &ranges {
-                let fixed_block_range =
-                    static_file_provider.find_fixed_range(segment, block_range.start());
-                let jar_provider = static_file_provider
-                    .get_segment_provider_for_range(segment, || Some(fixed_block_range), None)?
-                    .ok_or_else(|| {
-                        eyre::eyre!("Failed to get segment provider for segment: {}", segment)
-                    })?;
-
-                let columns = jar_provider.columns();
-                let rows = jar_provider.rows();
-
-                let data_size = fs::metadata(jar_provider.data_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let index_size = fs::metadata(jar_provider.index_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let offsets_size = fs::metadata(jar_provider.offsets_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let config_size = fs::metadata(jar_provider.config_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-
-                if self.detailed_segments {
+        let format_range = |range: Option<SegmentRangeInclusive>| {
+            range.map_or("N/A".to_string(), |range| format!("{range}"))
+        };
+
+        for files in stats.chunk_by(|a, b| a.segment == b.segment) {
+            if self.detailed_segments {
+                for file in files {
                     let mut row = Row::new();
-                    row.add_cell(Cell::new(segment))
-                        .add_cell(Cell::new(format!("{block_range}")))
-                        .add_cell(Cell::new(
-                            header.tx_range().map_or("N/A".to_string(), |range| format!("{range}")),
-                        ))
-                        .add_cell(Cell::new(format!("{columns} x {rows}")));
+                    row.add_cell(Cell::new(file.segment))
+                        .add_cell(Cell::new(format!("{}", file.block_range)))
+                        .add_cell(Cell::new(format_range(file.tx_range)))
+                        .add_cell(Cell::new(format!("{} x {}", file.columns, file.rows)));
                     if self.detailed_sizes {
-                        row.add_cell(Cell::new(human_bytes(data_size as f64)))
-                            .add_cell(Cell::new(human_bytes(index_size as f64)))
-                            .add_cell(Cell::new(human_bytes(offsets_size as f64)))
-                            .add_cell(Cell::new(human_bytes(config_size as f64)));
+                        row.add_cell(Cell::new(human_bytes(file.data_size as f64)))
+                            .add_cell(Cell::new(human_bytes(file.index_size as f64)))
+                            .add_cell(Cell::new(human_bytes(file.offsets_size as f64)))
+                            .add_cell(Cell::new(human_bytes(file.config_size as f64)));
                     }
-                    row.add_cell(Cell::new(human_bytes(
-                        (data_size + index_size + offsets_size + config_size) as f64,
-                    )));
+                    row.add_cell(Cell::new(human_bytes(file.total_size() as f64)));
                     table.add_row(row);
-                } else {
-                    if segment_columns > 0 {
-                        assert_eq!(segment_columns, columns);
-                    } else {
-                        segment_columns = columns;
-                    }
-                    segment_rows += rows;
-                    segment_data_size += data_size;
-                    segment_index_size += index_size;
-                    segment_offsets_size += offsets_size;
-                    segment_config_size += config_size;
                 }
-
-                total_data_size += data_size;
-                total_index_size += index_size;
-                total_offsets_size += offsets_size;
-                total_config_size += config_size;
-
-                // Manually drop provider, otherwise removal from cache will deadlock.
-                drop(jar_provider);
-
-                // Removes from cache, since if we have many files, it may hit ulimit limits
-                static_file_provider.remove_cached_provider(segment, fixed_block_range.end());
-            }
-
-            if !self.detailed_segments {
-                let first_ranges = ranges.first().expect("not empty list of ranges");
-                let last_ranges = ranges.last().expect("not empty list of ranges");
-
-                let block_range =
-                    SegmentRangeInclusive::new(first_ranges.0.start(), last_ranges.0.end());
-
-                // Transaction ranges can be empty, so we need to find the first and last which are
-                // not.
-                let tx_range = {
-                    let start = ranges
-                        .iter()
-                        .find_map(|(_, header)| header.tx_range().map(|range| range.start()))
-                        .unwrap_or_default();
-                    let end = ranges
-                        .iter()
-                        .rev()
-                        .find_map(|(_, header)| header.tx_range().map(|range| range.end()));
-                    end.map(|end| SegmentRangeInclusive::new(start, end))
-                };
+            } else {
+                for file in files {
+                    assert_eq!(files[0].columns, file.columns);
+                }
+                let segment = SegmentStats::new(&files.iter().collect::<Vec<_>>())
+                    .expect("not empty list of files");
 
                 let mut row = Row::new();
-                row.add_cell(Cell::new(segment))
-                    .add_cell(Cell::new(format!("{block_range}")))
-                    .add_cell(Cell::new(
-                        tx_range.map_or("N/A".to_string(), |tx_range| format!("{tx_range}")),
-                    ))
-                    .add_cell(Cell::new(format!("{segment_columns} x {segment_rows}")));
+                row.add_cell(Cell::new(segment.segment))
+                    .add_cell(Cell::new(format!("{}", segment.block_range)))
+                    .add_cell(Cell::new(format_range(segment.tx_range)))
+                    .add_cell(Cell::new(format!("{} x {}", segment.columns, segment.rows)));
                 if self.detailed_sizes {
-                    row.add_cell(Cell::new(human_bytes(segment_data_size as f64)))
-                        .add_cell(Cell::new(human_bytes(segment_index_size as f64)))
-                        .add_cell(Cell::new(human_bytes(segment_offsets_size as f64)))
-                        .add_cell(Cell::new(human_bytes(segment_config_size as f64)));
+                    let sum = |size: fn(&StaticFileStats) -> u64| {
+                        human_bytes(files.iter().map(size).sum::<u64>() as f64)
+                    };
+                    row.add_cell(Cell::new(sum(|file| file.data_size)))
+                        .add_cell(Cell::new(sum(|file| file.index_size)))
+                        .add_cell(Cell::new(sum(|file| file.offsets_size)))
+                        .add_cell(Cell::new(sum(|file| file.config_size)));
                 }
-                row.add_cell(Cell::new(human_bytes(
-                    (segment_data_size +
-                        segment_index_size +
-                        segment_offsets_size +
-                        segment_config_size) as f64,
-                )));
+                row.add_cell(Cell::new(human_bytes(segment.size as f64)));
                 table.add_row(row);
             }
         }
@@ -314,23 +390,22 @@ impl Command {
         }
         table.add_row(separator);
 
+        let total = |size: fn(&StaticFileStats) -> u64| stats.iter().map(size).sum::<u64>();
         let mut row = Row::new();
         row.add_cell(Cell::new("Total"))
             .add_cell(Cell::new(""))
             .add_cell(Cell::new(""))
             .add_cell(Cell::new(""));
         if self.detailed_sizes {
-            row.add_cell(Cell::new(human_bytes(total_data_size as f64)))
-                .add_cell(Cell::new(human_bytes(total_index_size as f64)))
-                .add_cell(Cell::new(human_bytes(total_offsets_size as f64)))
-                .add_cell(Cell::new(human_bytes(total_config_size as f64)));
+            row.add_cell(Cell::new(human_bytes(total(|file| file.data_size) as f64)))
+                .add_cell(Cell::new(human_bytes(total(|file| file.index_size) as f64)))
+                .add_cell(Cell::new(human_bytes(total(|file| file.offsets_size) as f64)))
+                .add_cell(Cell::new(human_bytes(total(|file| file.config_size) as f64)));
         }
-        row.add_cell(Cell::new(human_bytes(
-            (total_data_size + total_index_size + total_offsets_size + total_config_size) as f64,
-        )));
+        row.add_cell(Cell::new(human_bytes(total(StaticFileStats::total_size) as f64)));
         table.add_row(row);
 
-        Ok(table)
+        table
     }
 
     fn checksum_report<N: ProviderNodeTypes>(&self, tool: &DbTool<N>) -> eyre::Result<ComfyTable> {
@@ -373,3 +448,49 @@ impl Command {
         Ok(table)
     }
 }
+
+/// Collects the statistics of all static files, sorted by segment and block range.
+pub(crate) fn static_files_stats<N: NodePrimitives>(
+    static_file_provider: &StaticFileProvider<N>,
+) -> eyre::Result<Vec<StaticFileStats>> {
+    let static_files = iter_static_files(static_file_provider.directory())?;
+
+    let mut stats = Vec::new();
+    for (segment, ranges) in static_files.into_iter().sorted_by_key(|(segment, _)| *segment) {
+        for (block_range, header) in &ranges {
+            let fixed_block_range =
+                static_file_provider.find_fixed_range(segment, block_range.start());
+            let jar_provider = static_file_provider
+                .get_segment_provider_for_range(segment, || Some(fixed_block_range), None)?
+                .ok_or_else(|| {
+                    eyre::eyre!("Failed to get segment provider for segment: {}", segment)
+                })?;
+
+            let file_size =
+                |path: &Path| fs::metadata(path).map(|metadata| metadata.len()).unwrap_or_default();
+            stats.push(StaticFileStats {
+                segment,
+                block_range: *block_range,
+                tx_range: header.tx_range(),
+                columns: jar_provider.columns(),
+                rows: jar_provider.rows(),
+                compression: jar_provider.compressor().map(|compressor| match compressor {
+                    Compressors::Zstd(_) => "zstd",
+                    Compressors::Lz4(_) => "lz4",
+                }),
+                data_size: file_size(jar_provider.data_path()),
+                index_size: file_size(&jar_provider.index_path()),
+                offsets_size: file_size(&jar_provider.offsets_path()),
+                config_size: file_size(&jar_provider.config_path()),
+            });
+
+            // Manually drop provider, otherwise removal from cache will deadlock.
+            drop(jar_provider);
+
+            // Removes from cache, since if we have many files, it may hit ulimit limits
+            static_file_provider.remove_cached_provider(segment, fixed_block_range.end());
+        }
+    }
+
+    Ok(stats)
+}
@@ -0,0 +1,317 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, Sealable, B256};
+use clap::Parser;
+use reth_db_api::{cursor::DbCursorRO, tables, transaction::DbTx};
+use reth_db_common::DbTool;
+use reth_primitives_traits::SealedHeader;
+use reth_provider::{
+    providers::ProviderNodeTypes, DBProvider, HeaderProvider, StaticFileProviderFactory,
+};
+use reth_static_file_types::StaticFileSegment;
+use serde::Serialize;
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+/// Number of headers that are read from the static files at once.
+const BATCH_SIZE: u64 = 10_000;
+
+/// Interval between progress reports.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The arguments for the `reth db verify-chain` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The first block to verify.
+    #[arg(long, default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to verify, the highest header in the static files by default.
+    #[arg(long)]
+    to: Option<BlockNumber>,
+
+    /// Stop once this many inconsistencies were found, 0 to verify the whole range.
+    #[arg(long, default_value_t = 1)]
+    max_findings: usize,
+
+    /// Print the result as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+/// Where an inconsistency was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+enum Source {
+    /// The header in the headers static files.
+    #[serde(rename = "Headers")]
+    Header,
+    /// The block hash stored next to the header in the headers static files.
+    #[serde(rename = "Headers.hash")]
+    HeaderHash,
+    /// The [`tables::HeaderNumbers`] table.
+    HeaderNumbers,
+    /// The [`tables::CanonicalHeaders`] table.
+    CanonicalHeaders,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Header => "Headers",
+            Self::HeaderHash => "Headers.hash",
+            Self::HeaderNumbers => "HeaderNumbers",
+            Self::CanonicalHeaders => "CanonicalHeaders",
+        })
+    }
+}
+
+/// An inconsistency of a block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Finding {
+    block_number: BlockNumber,
+    source: Source,
+    message: String,
+    /// The hash that the header hashes to, or the hash of the parent for a broken link.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_hash: Option<B256>,
+    /// The hash that is stored instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stored_hash: Option<B256>,
+}
+
+impl Finding {
+    const fn new(block_number: BlockNumber, source: Source, message: String) -> Self {
+        Self { block_number, source, message, expected_hash: None, stored_hash: None }
+    }
+
+    const fn with_hashes(mut self, expected: B256, stored: Option<B256>) -> Self {
+        self.expected_hash = Some(expected);
+        self.stored_hash = stored;
+        self
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "block #{} ({}): {}", self.block_number, self.source, self.message)?;
+        if let Some(expected) = self.expected_hash {
+            write!(f, ", expected {expected}")?;
+        }
+        if let Some(stored) = self.stored_hash {
+            write!(f, ", stored {stored}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of the command, as printed with `--json`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Report {
+    from: BlockNumber,
+    to: BlockNumber,
+    /// The last block that was checked before stopping.
+    verified_to: Option<BlockNumber>,
+    findings: Vec<Finding>,
+}
+
+impl Command {
+    /// Execute `db verify-chain` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let static_file_provider = tool.provider_factory.static_file_provider();
+        let highest = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .ok_or_else(|| eyre::eyre!("there are no headers in the static files"))?;
+        let to = self.to.unwrap_or(highest);
+        eyre::ensure!(self.from <= to, "--from {} is after --to {to}", self.from);
+
+        let provider = tool.provider_factory.provider()?;
+        let mut header_numbers = provider.tx_ref().cursor_read::<tables::HeaderNumbers>()?;
+        let mut canonical_headers = provider.tx_ref().cursor_read::<tables::CanonicalHeaders>()?;
+
+        // The first block is linked to the header before the range
+        let mut parent = match self.from.checked_sub(1) {
+            Some(number) => static_file_provider
+                .header_by_number(number)?
+                .map(|header| (number, header.hash_slow())),
+            None => None,
+        };
+
+        let mut findings = Vec::new();
+        let started = Instant::now();
+        let mut last_report = started;
+        let mut verified_to = None;
+        let mut batch_start = self.from;
+        'batches: while batch_start <= to {
+            let batch_end = batch_start.saturating_add(BATCH_SIZE - 1).min(to);
+            let headers = static_file_provider.sealed_headers_range(batch_start..=batch_end)?;
+
+            let mut expected_number = batch_start;
+            for header in headers {
+                let number = expected_number;
+                expected_number += 1;
+
+                let hash = header.header().hash_slow();
+                let indexed = header_numbers.seek_exact(hash)?.map(|(_, number)| number);
+                let canonical = canonical_headers.seek_exact(number)?.map(|(_, hash)| hash);
+                let found = check_header(number, &header, hash, parent, indexed, canonical);
+
+                parent = Some((number, hash));
+                verified_to = Some(number);
+                let failed = !found.is_empty();
+                findings.extend(found);
+                if failed && self.max_findings != 0 && findings.len() >= self.max_findings {
+                    break 'batches
+                }
+            }
+
+            if expected_number <= batch_end {
+                // A missing header makes the links of the following headers meaningless
+                findings.push(Finding::new(
+                    expected_number,
+                    Source::Header,
+                    "header is missing from the static files".to_string(),
+                ));
+                break
+            }
+
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                let blocks = batch_end - self.from + 1;
+                info!(
+                    block = batch_end,
+                    to,
+                    blocks_per_second = (blocks as f64 / started.elapsed().as_secs_f64()) as u64,
+                    "Verifying headers"
+                );
+                last_report = Instant::now();
+            }
+            batch_start = batch_end + 1;
+        }
+
+        let report = Report { from: self.from, to, verified_to, findings };
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else if report.findings.is_empty() {
+            println!("Verified blocks {}..={to}, no inconsistencies found.", self.from);
+        } else {
+            for finding in &report.findings {
+                println!("{finding}");
+            }
+        }
+
+        if !report.findings.is_empty() {
+            eyre::bail!("Found {} inconsistencies in the chain", report.findings.len())
+        }
+        Ok(())
+    }
+}
+
+/// Checks the header of block `number` that hashes to `hash` against the hash of its parent, and
+/// the block number and canonical hash that the database stores for it.
+fn check_header<H: BlockHeader>(
+    number: BlockNumber,
+    header: &SealedHeader<H>,
+    hash: B256,
+    parent: Option<(BlockNumber, B256)>,
+    indexed_number: Option<BlockNumber>,
+    canonical_hash: Option<B256>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    if header.number() != number {
+        let message = format!("header has number {}", header.number());
+        findings.push(Finding::new(number, Source::Header, message));
+    }
+
+    if header.hash() != hash {
+        let message = "stored hash doesn't match the header".to_string();
+        findings.push(
+            Finding::new(number, Source::HeaderHash, message)
+                .with_hashes(hash, Some(header.hash())),
+        );
+    }
+
+    if let Some((parent_number, parent_hash)) = parent &&
+        header.parent_hash() != parent_hash
+    {
+        let message = format!("parent hash doesn't match the hash of block #{parent_number}");
+        findings.push(
+            Finding::new(number, Source::Header, message)
+                .with_hashes(parent_hash, Some(header.parent_hash())),
+        );
+    }
+
+    let message = match indexed_number {
+        Some(indexed) if indexed == number => None,
+        Some(indexed) => Some(format!("hash of the header is indexed as block #{indexed}")),
+        None => Some("hash of the header is not indexed".to_string()),
+    };
+    if let Some(message) = message {
+        findings.push(Finding::new(number, Source::HeaderNumbers, message).with_hashes(hash, None));
+    }
+
+    // Canonical hashes are stored in the static files, but may also be left in the database
+    if let Some(canonical) = canonical_hash &&
+        canonical != hash
+    {
+        let message = "canonical hash doesn't match the header".to_string();
+        findings.push(
+            Finding::new(number, Source::CanonicalHeaders, message)
+                .with_hashes(hash, Some(canonical)),
+        );
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+
+    #[test]
+    fn finds_inconsistencies() {
+        let parent = Header { number: 9, ..Default::default() };
+        let parent_hash = parent.hash_slow();
+        let header = Header { number: 10, parent_hash, ..Default::default() };
+        let hash = header.hash_slow();
+        let sealed = SealedHeader::new(header.clone(), hash);
+
+        let consistent =
+            check_header(10, &sealed, hash, Some((9, parent_hash)), Some(10), Some(hash));
+        assert!(consistent.is_empty());
+        // The database doesn't need to have the canonical hash
+        assert!(check_header(10, &sealed, hash, Some((9, parent_hash)), Some(10), None).is_empty());
+
+        let findings = check_header(
+            11,
+            &SealedHeader::new(header, B256::ZERO),
+            hash,
+            Some((10, B256::ZERO)),
+            None,
+            Some(B256::ZERO),
+        );
+        let sources = findings.iter().map(|finding| finding.source).collect::<Vec<_>>();
+        assert_eq!(
+            sources,
+            [
+                Source::Header,
+                Source::HeaderHash,
+                Source::Header,
+                Source::HeaderNumbers,
+                Source::CanonicalHeaders
+            ]
+        );
+        assert_eq!(
+            findings[2].to_string(),
+            format!(
+                "block #11 (Headers): parent hash doesn't match the hash of block #10, expected \
+                 {}, stored {parent_hash}",
+                B256::ZERO
+            )
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! `reth db verify-trie` command for checking the trie tables against the hashed state
+
+use crate::db::repair_trie::{output_progress, verify_checkpoints};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{keccak256, Address, B256};
+use clap::Parser;
+use reth_db_api::{database::Database, transaction::DbTx};
+use reth_db_common::DbTool;
+use reth_provider::{
+    providers::ProviderNodeTypes, HeaderProvider, ProviderError, StageCheckpointReader,
+};
+use reth_stages::StageId;
+use reth_trie::{
+    proof::Proof,
+    verify::{verify_storage_trie, Output, Verifier},
+    StateRoot,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseStateRoot, DatabaseTrieCursorFactory};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+const PROGRESS_PERIOD: Duration = Duration::from_secs(5);
+
+/// The arguments for the `reth db verify-trie` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only verify the storage trie of the account, and its Merkle proof against the state root
+    #[arg(long, value_name = "ADDRESS")]
+    account: Option<Address>,
+
+    /// The number of inconsistencies to report before stopping
+    #[arg(long, default_value_t = 100)]
+    limit: usize,
+}
+
+impl Command {
+    /// Execute `db verify-trie` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        // The trie is only complete once the merkle stage caught up with the hashing stages
+        let provider = tool.provider_factory.provider()?;
+        verify_checkpoints(&provider)?;
+        let block =
+            provider.get_stage_checkpoint(StageId::MerkleExecute)?.unwrap_or_default().block_number;
+        let state_root = provider
+            .sealed_header(block)?
+            .ok_or(ProviderError::HeaderNotFound(block.into()))?
+            .state_root();
+        drop(provider);
+        info!(target: "reth::cli", block, %state_root, "Verifying the trie against the state root");
+
+        let mut tx = tool.provider_factory.db_ref().tx()?;
+        tx.disable_long_read_transaction_safety();
+
+        let mut report = Report { limit: self.limit, found: 0 };
+        if let Some(address) = self.account {
+            verify_account(&tx, address, state_root, &mut report)?;
+        } else {
+            verify_state(&tx, block, state_root, &mut report)?;
+        }
+
+        if report.found == 0 {
+            info!(target: "reth::cli", "The trie is consistent with the hashed state");
+            return Ok(())
+        }
+        eyre::bail!(
+            "Found {} inconsistencies{}, heal the trie nodes with `reth db repair-trie` or rebuild \
+             the trie with `reth stage drop merkle`",
+            report.found,
+            if report.is_full() { " and stopped at the limit" } else { "" }
+        )
+    }
+}
+
+/// Walks the whole trie, recomputing every branch node from the hashed state and comparing it
+/// with the stored one, and checks the state root of the stored trie.
+fn verify_state<TX: DbTx>(
+    tx: &TX,
+    block: u64,
+    state_root: B256,
+    report: &mut Report,
+) -> eyre::Result<()> {
+    let root = StateRoot::from_tx(tx).root()?;
+    if root != state_root {
+        report.record(format_args!(
+            "the stored trie has root {root}, but block {block} has state root {state_root}"
+        ));
+    }
+
+    let trie_cursor_factory = DatabaseTrieCursorFactory::new(tx);
+    let verifier = Verifier::new(&trie_cursor_factory, DatabaseHashedCursorFactory::new(tx))?;
+    let start_time = Instant::now();
+    let mut last_progress_time = Instant::now();
+    for output in verifier {
+        match output? {
+            Output::Progress(path) => {
+                if last_progress_time.elapsed() > PROGRESS_PERIOD {
+                    output_progress("Verifying", path, start_time, report.found as u64);
+                    last_progress_time = Instant::now();
+                }
+            }
+            output => {
+                report.record(output);
+                if report.is_full() {
+                    break
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the storage trie of a single account against its hashed storage, and verifies the
+/// Merkle proof of the account against the state root.
+fn verify_account<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    state_root: B256,
+    report: &mut Report,
+) -> eyre::Result<()> {
+    let hashed_address = keccak256(address);
+    let trie_cursor_factory = DatabaseTrieCursorFactory::new(tx);
+    let hashed_cursor_factory = DatabaseHashedCursorFactory::new(tx);
+
+    for output in
+        verify_storage_trie(&trie_cursor_factory, hashed_cursor_factory.clone(), hashed_address)?
+    {
+        report.record(output);
+        if report.is_full() {
+            return Ok(())
+        }
+    }
+
+    let proof =
+        Proof::new(trie_cursor_factory, hashed_cursor_factory).account_proof(address, &[])?;
+    info!(
+        target: "reth::cli",
+        %address,
+        %hashed_address,
+        account = ?proof.info,
+        storage_root = %proof.storage_root,
+        proof_nodes = proof.proof.len(),
+        "Generated the proof of the account"
+    );
+    if let Err(err) = proof.verify(state_root) {
+        report.record(format_args!(
+            "the proof of account {address} doesn't verify against state root {state_root}: {err}"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Counts and logs the inconsistencies, the walk stops once the limit is reached.
+#[derive(Debug)]
+struct Report {
+    limit: usize,
+    found: usize,
+}
+
+impl Report {
+    fn record(&mut self, inconsistency: impl fmt::Display) {
+        self.found += 1;
+        warn!(target: "reth::cli", "Inconsistency found: {inconsistency}");
+    }
+
+    const fn is_full(&self) -> bool {
+        self.found >= self.limit
+    }
+}
@@ -0,0 +1,304 @@
+//! Command that builds a block on top of a parent block without writing it to the database.
+
+use crate::common::{
+    AccessRights, CliComponentsBuilder, CliNodeComponents, CliNodeTypes, Environment,
+    EnvironmentArgs, EvmFor,
+};
+use alloy_consensus::{transaction::TxHashRef, BlockHeader};
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use alloy_primitives::{hex, Address, Bytes, B256};
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_ethereum_primitives::{PooledTransactionVariant, TransactionSigned};
+use reth_evm::{execute::BlockBuilder, ConfigureEvm, NextBlockEnvAttributes};
+use reth_node_api::TxTy;
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::{BlockHashReader, BlockNumReader, ChainSpecProvider, HeaderProvider};
+use reth_revm::{database::StateProviderDatabase, db::State};
+use serde::Serialize;
+use std::{path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// The default number of seconds between the parent block and the built block.
+const DEFAULT_BLOCK_TIME: u64 = 12;
+
+/// `reth debug build-block` command
+///
+/// Assembles a block from the given raw transactions on top of the parent block, executes it and
+/// prints the resulting block. Nothing is written to the database.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The hash of the parent block, defaults to the highest block in the database.
+    #[arg(long, value_name = "HASH")]
+    parent: Option<B256>,
+
+    /// File with the raw transactions to include, one hex encoded EIP-2718 transaction per line.
+    ///
+    /// Empty lines are ignored.
+    #[arg(long, value_name = "FILE")]
+    transactions: PathBuf,
+
+    /// The fee recipient of the block.
+    #[arg(long, value_name = "ADDRESS", default_value_t = Address::ZERO)]
+    fee_recipient: Address,
+
+    /// The timestamp of the block, defaults to 12 seconds after the parent block.
+    #[arg(long)]
+    timestamp: Option<u64>,
+
+    /// The randomness value of the block.
+    #[arg(long, value_name = "HASH", default_value_t = B256::ZERO)]
+    prev_randao: B256,
+
+    /// The parent beacon block root of the block, used once Cancun is active.
+    #[arg(long, value_name = "HASH", default_value_t = B256::ZERO)]
+    parent_beacon_block_root: B256,
+
+    /// The gas limit of the block, defaults to the gas limit of the parent block.
+    #[arg(long)]
+    gas_limit: Option<u64>,
+
+    /// The extra data of the block.
+    #[arg(long, default_value_t = Bytes::new())]
+    extra_data: Bytes,
+
+    /// Decode the transactions in their network encoding, with the blob sidecars of EIP-4844
+    /// transactions.
+    ///
+    /// The versioned hashes of each blob transaction are checked against its sidecar, which is
+    /// then stripped before execution.
+    #[arg(long)]
+    with_blob_sidecars: bool,
+
+    /// Also write the RLP encoded block to this file, which can be passed to `reth import`.
+    #[arg(long, value_name = "PATH")]
+    rlp: Option<PathBuf>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `debug build-block` command
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        EvmFor<N>: ConfigureEvm<NextBlockEnvCtx: From<NextBlockEnvAttributes>>,
+    {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let chain_spec = provider_factory.chain_spec();
+        let components = components(chain_spec.clone());
+        let provider = provider_factory.provider()?;
+
+        let parent_hash = match self.parent {
+            Some(hash) => hash,
+            None => {
+                let best_block = provider.best_block_number()?;
+                provider
+                    .block_hash(best_block)?
+                    .ok_or_else(|| eyre::eyre!("Hash of block {best_block} not found"))?
+            }
+        };
+        let parent = provider
+            .sealed_header_by_hash(parent_hash)?
+            .ok_or_else(|| eyre::eyre!("Parent block {parent_hash} not found"))?;
+
+        let contents = reth_fs_util::read_to_string(&self.transactions)?;
+        let transactions = parse_raw_transactions(&contents)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, raw)| {
+                let tx = if self.with_blob_sidecars {
+                    decode_pooled_transaction(&raw)
+                } else {
+                    <TxTy<N>>::decode_2718_exact(&raw).map_err(Into::into)
+                }
+                .wrap_err_with(|| format!("Failed to decode transaction {index}"))?;
+                let hash = *tx.tx_hash();
+                tx.try_into_recovered().map_err(|_| {
+                    eyre::eyre!("Transaction {index} ({hash}) has an invalid signature")
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        let timestamp = self.timestamp.unwrap_or(parent.timestamp() + DEFAULT_BLOCK_TIME);
+        let attributes = NextBlockEnvAttributes {
+            timestamp,
+            suggested_fee_recipient: self.fee_recipient,
+            prev_randao: self.prev_randao,
+            gas_limit: self.gas_limit.unwrap_or(parent.gas_limit()),
+            parent_beacon_block_root: chain_spec
+                .is_cancun_active_at_timestamp(timestamp)
+                .then_some(self.parent_beacon_block_root),
+            withdrawals: chain_spec
+                .is_shanghai_active_at_timestamp(timestamp)
+                .then(Default::default),
+            extra_data: self.extra_data,
+        };
+
+        info!(
+            target: "reth::cli",
+            parent = %parent_hash,
+            number = parent.number() + 1,
+            timestamp,
+            transactions = transactions.len(),
+            "Building block"
+        );
+
+        let state = provider_factory.history_by_block_hash(parent_hash)?;
+        let mut db = State::builder()
+            .with_bundle_update()
+            .with_database(StateProviderDatabase::new(&state))
+            .build();
+        let mut builder =
+            components.evm_config().builder_for_next_block(&mut db, &parent, attributes.into())?;
+        builder.apply_pre_execution_changes()?;
+
+        let mut executed = Vec::with_capacity(transactions.len());
+        for (index, tx) in transactions.into_iter().enumerate() {
+            let hash = *tx.tx_hash();
+            let gas_used = builder
+                .execute_transaction(tx)
+                .wrap_err_with(|| format!("Transaction {index} ({hash}) failed"))?;
+            executed.push(BuiltTransaction { hash, gas_used });
+        }
+        let outcome = builder.finish(&state)?;
+
+        let block = outcome.block.into_sealed_block();
+        let header = block.header();
+        let built = BuiltBlock {
+            hash: block.hash(),
+            number: header.number(),
+            parent_hash: header.parent_hash(),
+            timestamp: header.timestamp(),
+            fee_recipient: header.beneficiary(),
+            gas_limit: header.gas_limit(),
+            gas_used: header.gas_used(),
+            base_fee_per_gas: header.base_fee_per_gas(),
+            blob_gas_used: header.blob_gas_used(),
+            excess_blob_gas: header.excess_blob_gas(),
+            state_root: header.state_root(),
+            transactions_root: header.transactions_root(),
+            receipts_root: header.receipts_root(),
+            transactions: executed,
+        };
+        println!("{}", serde_json::to_string_pretty(&built)?);
+
+        if let Some(path) = &self.rlp {
+            reth_fs_util::write(path, alloy_rlp::encode(block.into_block()))?;
+            info!(target: "reth::cli", path = %path.display(), "Wrote RLP encoded block");
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// The block built by the `debug build-block` command.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BuiltBlock {
+    hash: B256,
+    number: u64,
+    parent_hash: B256,
+    timestamp: u64,
+    fee_recipient: Address,
+    gas_limit: u64,
+    gas_used: u64,
+    base_fee_per_gas: Option<u64>,
+    blob_gas_used: Option<u64>,
+    excess_blob_gas: Option<u64>,
+    state_root: B256,
+    transactions_root: B256,
+    receipts_root: B256,
+    transactions: Vec<BuiltTransaction>,
+}
+
+/// A transaction executed in the built block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BuiltTransaction {
+    hash: B256,
+    gas_used: u64,
+}
+
+/// Parses one hex encoded transaction per non-empty line.
+fn parse_raw_transactions(contents: &str) -> eyre::Result<Vec<Bytes>> {
+    contents
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            hex::decode(line)
+                .map(Bytes::from)
+                .wrap_err_with(|| format!("Invalid hex transaction on line {}", index + 1))
+        })
+        .collect()
+}
+
+/// Decodes a transaction in its network encoding and converts it to the consensus encoding,
+/// checking that the sidecar of a blob transaction matches its versioned hashes.
+fn decode_pooled_transaction<T: Decodable2718>(raw: &[u8]) -> eyre::Result<T> {
+    let pooled = PooledTransactionVariant::decode_2718_exact(raw)?;
+    if let Some(blob_tx) = pooled.as_eip4844() {
+        let blob_tx = blob_tx.tx();
+        let sidecar_hashes = blob_tx.sidecar.versioned_hashes().collect::<Vec<_>>();
+        if sidecar_hashes != blob_tx.tx.blob_versioned_hashes {
+            eyre::bail!(
+                "Versioned hashes {:?} of blob transaction {} do not match its sidecar {:?}",
+                blob_tx.tx.blob_versioned_hashes,
+                pooled.tx_hash(),
+                sidecar_hashes
+            )
+        }
+    }
+
+    let tx = TransactionSigned::from(pooled);
+    Ok(T::decode_2718_exact(&tx.encoded_2718())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_build_block_command() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--transactions",
+            "txs.txt",
+            "--fee-recipient",
+            "0x0000000000000000000000000000000000000001",
+            "--timestamp",
+            "1700000000",
+            "--with-blob-sidecars",
+            "--rlp",
+            "block.rlp",
+        ])
+        .unwrap();
+        assert_eq!(cmd.parent, None);
+        assert_eq!(cmd.fee_recipient, Address::with_last_byte(1));
+        assert_eq!(cmd.timestamp, Some(1700000000));
+        assert!(cmd.with_blob_sidecars);
+        assert_eq!(cmd.rlp, Some(PathBuf::from("block.rlp")));
+    }
+
+    #[test]
+    fn raw_transactions() {
+        let txs = parse_raw_transactions("0x01\n\n  0203  \n").unwrap();
+        assert_eq!(txs, [Bytes::from_static(&[1]), Bytes::from_static(&[2, 3])]);
+
+        let err = parse_raw_transactions("0x01\nzz\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+}
@@ -0,0 +1,399 @@
+//! Command that executes a range of blocks and compares the receipts with the stored or remote
+//! ones.
+
+use crate::common::{
+    AccessRights, CliComponentsBuilder, CliNodeComponents, CliNodeTypes, Environment,
+    EnvironmentArgs,
+};
+use alloy_consensus::{transaction::TxHashRef, TxReceipt};
+use alloy_primitives::{Address, Bloom, Bytes, Log, B256, U64};
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_primitives_traits::BlockBody;
+use reth_provider::{
+    BlockNumReader, BlockReader, ChainSpecProvider, ReceiptProvider, TransactionVariant,
+};
+use reth_revm::database::StateProviderDatabase;
+use serde::Deserialize;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::time::{Interval, MissedTickBehavior};
+use tracing::info;
+
+/// `reth debug execution-range` command
+///
+/// Executes a range of blocks on top of the state of the database and compares the receipts
+/// field by field with the stored receipts, or with the receipts of a remote RPC endpoint.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to execute.
+    #[arg(long, value_name = "BLOCK", default_value_t = 1)]
+    from: u64,
+
+    /// The last block to execute, defaults to the highest block in the database.
+    #[arg(long, value_name = "BLOCK")]
+    to: Option<u64>,
+
+    /// Compare the receipts with the ones returned by `eth_getBlockReceipts` of `--rpc-url`
+    /// instead of the stored receipts.
+    #[arg(long, requires = "rpc_url")]
+    compare_remote: bool,
+
+    /// The JSON-RPC endpoint to fetch the receipts from.
+    #[arg(long, value_name = "URL")]
+    rpc_url: Option<String>,
+
+    /// The maximum number of requests per second sent to the remote endpoint.
+    #[arg(
+        long,
+        value_name = "COUNT",
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    rpc_requests_per_second: u32,
+
+    /// File recording the last compared block.
+    ///
+    /// If the file exists, the comparison resumes after the recorded block.
+    #[arg(long, value_name = "PATH")]
+    progress_file: Option<PathBuf>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `debug execution-range` command
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let components = components(provider_factory.chain_spec());
+        let provider = provider_factory.provider()?;
+
+        let to = match self.to {
+            Some(to) => to,
+            None => provider.best_block_number()?,
+        };
+        let mut from = self.from.max(1);
+        if let Some(path) = &self.progress_file &&
+            path.exists()
+        {
+            let last = read_progress(path)?;
+            info!(target: "reth::cli", last, path = %path.display(), "Resuming comparison");
+            from = from.max(last + 1);
+        }
+        if from > to {
+            info!(target: "reth::cli", from, to, "No blocks left to compare");
+            return Ok(())
+        }
+
+        let mut remote = match &self.rpc_url {
+            Some(url) if self.compare_remote => {
+                Some(RemoteReceipts::new(url.clone(), self.rpc_requests_per_second))
+            }
+            _ => None,
+        };
+
+        info!(target: "reth::cli", from, to, remote = remote.is_some(), "Comparing receipts");
+
+        let mut executor = components.evm_config().batch_executor(StateProviderDatabase::new(
+            provider_factory.history_by_block_number(from - 1)?,
+        ));
+        let mut mismatched_blocks = 0u64;
+        for number in from..=to {
+            let block = provider
+                .recovered_block(number.into(), TransactionVariant::WithHash)?
+                .ok_or_else(|| eyre::eyre!("Block {number} not found"))?;
+            let result = executor
+                .execute_one(&block)
+                .wrap_err_with(|| format!("Failed to execute block {number}"))?;
+            let ours = receipt_fields(&result.receipts);
+
+            let expected = match &mut remote {
+                Some(remote) => remote.block_receipts(number).await?,
+                None => receipt_fields(
+                    &provider
+                        .receipts_by_block(number.into())?
+                        .ok_or_else(|| eyre::eyre!("Receipts of block {number} not found"))?,
+                ),
+            };
+
+            let tx_hashes =
+                block.body().transactions().iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>();
+            let diff = diff_block(&tx_hashes, &ours, &expected);
+            if !diff.is_empty() {
+                mismatched_blocks += 1;
+                println!("Block {number}:");
+                for line in diff {
+                    println!("  {line}");
+                }
+            }
+
+            if let Some(path) = &self.progress_file {
+                reth_fs_util::write(path, number.to_string())?;
+            }
+        }
+
+        if mismatched_blocks > 0 {
+            eyre::bail!("Receipts of {mismatched_blocks} blocks in {from}..={to} do not match")
+        }
+        info!(target: "reth::cli", from, to, "All receipts match");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Reads the last compared block from the progress file.
+fn read_progress(path: &Path) -> eyre::Result<u64> {
+    reth_fs_util::read_to_string(path)?
+        .trim()
+        .parse()
+        .wrap_err_with(|| format!("Invalid progress file {}", path.display()))
+}
+
+/// The compared fields of a receipt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReceiptFields {
+    /// The status of the transaction, unknown for remote receipts before Byzantium.
+    status: Option<bool>,
+    gas_used: u64,
+    logs: Vec<Log>,
+    bloom: Bloom,
+}
+
+/// Returns the compared fields of the receipts of a block.
+fn receipt_fields<R: TxReceipt<Log = Log>>(receipts: &[R]) -> Vec<ReceiptFields> {
+    let mut cumulative_gas_used = 0;
+    receipts
+        .iter()
+        .map(|receipt| {
+            let gas_used = receipt.cumulative_gas_used() - cumulative_gas_used;
+            cumulative_gas_used = receipt.cumulative_gas_used();
+            ReceiptFields {
+                status: Some(receipt.status()),
+                gas_used,
+                logs: receipt.logs().to_vec(),
+                bloom: receipt.bloom(),
+            }
+        })
+        .collect()
+}
+
+/// Returns one line per differing field of the receipts of a block.
+fn diff_block(
+    tx_hashes: &[B256],
+    ours: &[ReceiptFields],
+    expected: &[ReceiptFields],
+) -> Vec<String> {
+    let mut diff = Vec::new();
+    if ours.len() != expected.len() {
+        diff.push(format!("receipts: ours {}, expected {}", ours.len(), expected.len()));
+    }
+
+    for (index, (ours, expected)) in ours.iter().zip(expected).enumerate() {
+        let tx = format!("transaction {index} ({})", tx_hashes.get(index).unwrap_or(&B256::ZERO));
+        if let (Some(ours), Some(expected)) = (ours.status, expected.status) &&
+            ours != expected
+        {
+            diff.push(format!("{tx} status: ours {ours}, expected {expected}"));
+        }
+        if ours.gas_used != expected.gas_used {
+            diff.push(format!(
+                "{tx} gas used: ours {}, expected {}",
+                ours.gas_used, expected.gas_used
+            ));
+        }
+        if ours.logs != expected.logs {
+            match ours.logs.iter().zip(&expected.logs).position(|(ours, expected)| ours != expected)
+            {
+                Some(log) => diff.push(format!(
+                    "{tx} log {log}: ours {:?}, expected {:?}",
+                    ours.logs[log], expected.logs[log]
+                )),
+                None => diff.push(format!(
+                    "{tx} logs: ours {}, expected {}",
+                    ours.logs.len(),
+                    expected.logs.len()
+                )),
+            }
+        }
+        if ours.bloom != expected.bloom {
+            diff.push(format!("{tx} bloom: ours {}, expected {}", ours.bloom, expected.bloom));
+        }
+    }
+
+    diff
+}
+
+/// Fetches block receipts from a JSON-RPC endpoint, limiting the rate of requests.
+#[derive(Debug)]
+struct RemoteReceipts {
+    client: reqwest::Client,
+    url: String,
+    interval: Interval,
+    request_id: u64,
+}
+
+impl RemoteReceipts {
+    fn new(url: String, requests_per_second: u32) -> Self {
+        let mut interval = tokio::time::interval(Duration::from_secs(1) / requests_per_second);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self { client: reqwest::Client::new(), url, interval, request_id: 0 }
+    }
+
+    /// Returns the receipts of the block with the given number.
+    async fn block_receipts(&mut self, number: u64) -> eyre::Result<Vec<ReceiptFields>> {
+        self.interval.tick().await;
+        self.request_id += 1;
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": self.request_id,
+            "method": "eth_getBlockReceipts",
+            "params": [format!("{number:#x}")],
+        });
+
+        let response = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(&request)?)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        let response: RpcResponse = serde_json::from_slice(&response)
+            .wrap_err_with(|| format!("Invalid response for the receipts of block {number}"))?;
+        if let Some(error) = response.error {
+            eyre::bail!("Failed to fetch the receipts of block {number}: {error}")
+        }
+        let receipts = response
+            .result
+            .ok_or_else(|| eyre::eyre!("Receipts of block {number} not found remotely"))?;
+
+        Ok(receipts.into_iter().map(RemoteReceipt::into_fields).collect())
+    }
+}
+
+/// A JSON-RPC response to `eth_getBlockReceipts`.
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    result: Option<Vec<RemoteReceipt>>,
+    error: Option<serde_json::Value>,
+}
+
+/// The fields of a receipt returned by `eth_getBlockReceipts` that are compared.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RemoteReceipt {
+    status: Option<U64>,
+    gas_used: U64,
+    logs: Vec<RemoteLog>,
+    logs_bloom: Bloom,
+}
+
+/// A log of a receipt returned by `eth_getBlockReceipts`.
+#[derive(Debug, Deserialize)]
+struct RemoteLog {
+    address: Address,
+    topics: Vec<B256>,
+    data: Bytes,
+}
+
+impl RemoteReceipt {
+    fn into_fields(self) -> ReceiptFields {
+        ReceiptFields {
+            status: self.status.map(|status| status == U64::from(1)),
+            gas_used: self.gas_used.to(),
+            logs: self
+                .logs
+                .into_iter()
+                .map(|log| Log::new_unchecked(log.address, log.topics, log.data))
+                .collect(),
+            bloom: self.logs_bloom,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    fn fields(status: bool, gas_used: u64) -> ReceiptFields {
+        ReceiptFields { status: Some(status), gas_used, logs: Vec::new(), bloom: Bloom::ZERO }
+    }
+
+    #[test]
+    fn parse_execution_range_command() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--from",
+            "10",
+            "--to",
+            "20",
+            "--compare-remote",
+            "--rpc-url",
+            "http://localhost:8545",
+            "--progress-file",
+            "progress",
+        ])
+        .unwrap();
+        assert_eq!((cmd.from, cmd.to), (10, Some(20)));
+        assert!(cmd.compare_remote);
+        assert_eq!(cmd.rpc_requests_per_second, 10);
+
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from(["reth", "--compare-remote"])
+            .is_err());
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--rpc-requests-per-second",
+            "0"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn remote_receipt() {
+        let receipt: RemoteReceipt = serde_json::from_value(serde_json::json!({
+            "status": "0x0",
+            "gasUsed": "0x5208",
+            "logs": [],
+            "logsBloom": Bloom::ZERO,
+            "transactionHash": B256::ZERO,
+        }))
+        .unwrap();
+        assert_eq!(receipt.into_fields(), fields(false, 21_000));
+    }
+
+    #[test]
+    fn receipt_diff() {
+        let ours = [fields(true, 21_000), fields(true, 50_000)];
+        assert!(diff_block(&[B256::ZERO; 2], &ours, &ours).is_empty());
+
+        let mut expected = ours.to_vec();
+        expected[1].gas_used = 40_000;
+        expected[1].status = None;
+        let diff = diff_block(&[B256::ZERO; 2], &ours, &expected);
+        assert_eq!(diff.len(), 1);
+        assert!(diff[0].contains("transaction 1") && diff[0].contains("gas used"));
+
+        let diff = diff_block(&[B256::ZERO; 2], &ours, &expected[..1]);
+        assert_eq!(diff, ["receipts: ours 2, expected 1"]);
+    }
+}
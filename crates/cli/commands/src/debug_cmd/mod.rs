@@ -0,0 +1,64 @@
+//! `reth debug` command.
+
+use crate::common::{CliComponentsBuilder, CliNodeTypes, EvmFor};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_evm::{ConfigureEvm, NextBlockEnvAttributes};
+use std::sync::Arc;
+
+mod build_block;
+mod execution_range;
+mod replay_block;
+mod unwind_to;
+
+/// `reth debug` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    command: Subcommands<C>,
+}
+
+/// `reth debug` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Build and execute a block on top of a parent block from a list of raw transactions,
+    /// without writing anything to the database.
+    BuildBlock(build_block::Command<C>),
+    /// Re-execute a block from the database and report the first point where it diverges from
+    /// the stored block.
+    ReplayBlock(replay_block::Command<C>),
+    /// Execute a range of blocks and compare the receipts with the stored or remote ones.
+    ExecutionRange(execution_range::Command<C>),
+    /// Unwind all stages of the pipeline to a block, and check the consistency of the storage
+    /// afterwards.
+    UnwindTo(unwind_to::Command<C>),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `debug` command
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        EvmFor<N>: ConfigureEvm<NextBlockEnvCtx: From<NextBlockEnvAttributes>>,
+    {
+        match self.command {
+            Subcommands::BuildBlock(command) => command.execute::<N>(components).await,
+            Subcommands::ReplayBlock(command) => command.execute::<N>(components).await,
+            Subcommands::ExecutionRange(command) => command.execute::<N>(components).await,
+            Subcommands::UnwindTo(command) => command.execute::<N>(components).await,
+        }
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        match &self.command {
+            Subcommands::BuildBlock(command) => command.chain_spec(),
+            Subcommands::ReplayBlock(command) => command.chain_spec(),
+            Subcommands::ExecutionRange(command) => command.chain_spec(),
+            Subcommands::UnwindTo(command) => command.chain_spec(),
+        }
+    }
+}
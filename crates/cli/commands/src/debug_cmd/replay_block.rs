@@ -0,0 +1,261 @@
+//! Command that re-executes a block from the database and compares the outcome with its header.
+
+use crate::common::{
+    AccessRights, CliComponentsBuilder, CliNodeComponents, CliNodeTypes, Environment,
+    EnvironmentArgs,
+};
+use alloy_consensus::{transaction::TxHashRef, BlockHeader};
+use alloy_eips::BlockHashOrNumber;
+use alloy_primitives::B256;
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_consensus::FullConsensus;
+use reth_evm::{execute::BlockExecutor, ConfigureEvm, Evm};
+use reth_primitives_traits::{BlockBody, GotExpected};
+use reth_provider::{
+    BlockReader, ChainSpecProvider, HashedPostStateProvider, ReceiptProvider, StateRootProvider,
+    TransactionVariant,
+};
+use reth_revm::{database::StateProviderDatabase, db::State};
+use revm::{database::states::bundle_state::BundleRetention, inspector::inspectors::TracerEip3155};
+use std::{
+    io, mem,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use tracing::{error, info, warn};
+
+/// `reth debug replay-block` command
+///
+/// Re-executes a block on top of its pre-state from the database and compares the gas used,
+/// receipts and state root with the stored block, reporting the first point of divergence.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The number or hash of the block to replay.
+    #[arg(long, value_name = "BLOCK")]
+    block: BlockHashOrNumber,
+
+    /// Write an EIP-3155 trace of every executed transaction to this directory, as
+    /// `block_<number>_tx_<index>.jsonl`.
+    #[arg(long, value_name = "DIR")]
+    trace: Option<PathBuf>,
+
+    /// Only execute the transactions of the block up to and including this one.
+    ///
+    /// Only the receipts of the executed transactions are compared, since the gas used and the
+    /// state root of the block are not known for a prefix of it.
+    #[arg(long, value_name = "HASH")]
+    tx: Option<B256>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `debug replay-block` command
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let components = components(provider_factory.chain_spec());
+        let provider = provider_factory.provider()?;
+
+        let block = provider
+            .recovered_block(self.block, TransactionVariant::WithHash)?
+            .ok_or_else(|| eyre::eyre!("Block {:?} not found", self.block))?;
+        let number = block.number();
+        if number == 0 {
+            eyre::bail!("The genesis block is not executed and can't be replayed")
+        }
+
+        let transactions = block.body().transactions();
+        let tx_count = match self.tx {
+            Some(hash) => {
+                transactions.iter().position(|tx| *tx.tx_hash() == hash).ok_or_else(|| {
+                    eyre::eyre!("Transaction {hash} is not part of block {number}")
+                })? + 1
+            }
+            None => transactions.len(),
+        };
+
+        if let Some(dir) = &self.trace {
+            reth_fs_util::create_dir_all(dir)?;
+        }
+
+        info!(
+            target: "reth::cli",
+            number,
+            hash = %block.hash(),
+            transactions = tx_count,
+            "Replaying block"
+        );
+
+        let state = provider_factory.history_by_block_number(number - 1)?;
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(&state))
+            .with_bundle_update()
+            .build();
+        let evm_config = components.evm_config();
+        let buffer = SharedBuffer::default();
+        let mut evm = evm_config.evm_with_env_and_inspector(
+            &mut db,
+            evm_config.evm_env(block.header())?,
+            TracerEip3155::new(Box::new(buffer.clone())),
+        );
+        evm.set_inspector_enabled(self.trace.is_some());
+        let ctx = evm_config.context_for_block(block.sealed_block())?;
+        let mut executor = evm_config.create_executor(evm, ctx);
+
+        executor.apply_pre_execution_changes()?;
+        // System calls are not part of any transaction trace.
+        buffer.take();
+
+        for (index, tx) in block.transactions_recovered().take(tx_count).enumerate() {
+            let hash = *tx.tx_hash();
+            let result = executor.execute_transaction(tx);
+            if let Some(dir) = &self.trace {
+                let path = dir.join(format!("block_{number}_tx_{index}.jsonl"));
+                reth_fs_util::write(path, buffer.take())?;
+            }
+            result.wrap_err_with(|| {
+                format!("Transaction {index} ({hash}) failed, the block diverges here")
+            })?;
+        }
+        let (_, result) = executor.finish()?;
+
+        let mut divergences = Vec::new();
+        match provider.receipts_by_block(number.into())? {
+            Some(stored) => {
+                if let Some(index) = first_divergence(&result.receipts, &stored) {
+                    let tx_hash = transactions[index].tx_hash();
+                    error!(
+                        target: "reth::cli",
+                        index,
+                        %tx_hash,
+                        executed = ?result.receipts[index],
+                        stored = ?stored.get(index),
+                        "First diverging receipt"
+                    );
+                    divergences.push(format!("receipt of transaction {index} ({tx_hash})"));
+                } else {
+                    info!(target: "reth::cli", receipts = result.receipts.len(), "Receipts match");
+                }
+            }
+            None => warn!(target: "reth::cli", "Receipts are not stored, skipping comparison"),
+        }
+
+        if self.tx.is_none() {
+            let header = block.header();
+            let gas_used = GotExpected { got: result.gas_used, expected: header.gas_used() };
+            if gas_used.got == gas_used.expected {
+                info!(target: "reth::cli", gas_used = gas_used.got, "Gas used matches");
+            } else {
+                error!(target: "reth::cli", %gas_used, "Gas used mismatch");
+                divergences.push("gas used".to_string());
+            }
+
+            // Compares the receipts root, logs bloom and requests hash with the header.
+            if let Err(err) = components.consensus().validate_block_post_execution(&block, &result)
+            {
+                error!(target: "reth::cli", %err, "Post-execution validation failed");
+                divergences.push(err.to_string());
+            }
+
+            db.merge_transitions(BundleRetention::Reverts);
+            let hashed_state = state.hashed_post_state(&db.bundle_state);
+            let (state_root, _) = state.state_root_with_updates(hashed_state)?;
+            let state_root = GotExpected { got: state_root, expected: header.state_root() };
+            if state_root.got == state_root.expected {
+                info!(target: "reth::cli", state_root = %state_root.got, "State root matches");
+            } else {
+                error!(target: "reth::cli", %state_root, "State root mismatch");
+                divergences.push("state root".to_string());
+            }
+        }
+
+        if !divergences.is_empty() {
+            eyre::bail!("Block {number} diverges in {}", divergences.join(", "))
+        }
+        info!(target: "reth::cli", number, "Replayed block matches the stored block");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Returns the index of the first executed receipt that doesn't match the stored one.
+fn first_divergence<R: PartialEq>(executed: &[R], stored: &[R]) -> Option<usize> {
+    executed
+        .iter()
+        .zip(stored)
+        .position(|(executed, stored)| executed != stored)
+        .or_else(|| (stored.len() < executed.len()).then_some(stored.len()))
+}
+
+/// An in-memory writer shared with the tracer, so that its output can be split per transaction.
+#[derive(Debug, Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    /// Takes the contents of the buffer, leaving it empty.
+    fn take(&self) -> Vec<u8> {
+        mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_replay_block_command() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth", "--block", "100", "--trace", "traces",
+        ])
+        .unwrap();
+        assert_eq!(cmd.block, BlockHashOrNumber::Number(100));
+        assert_eq!(cmd.trace, Some(PathBuf::from("traces")));
+        assert_eq!(cmd.tx, None);
+
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--block",
+            &B256::with_last_byte(1).to_string(),
+            "--tx",
+            &B256::with_last_byte(2).to_string(),
+        ])
+        .unwrap();
+        assert_eq!(cmd.block, BlockHashOrNumber::Hash(B256::with_last_byte(1)));
+        assert_eq!(cmd.tx, Some(B256::with_last_byte(2)));
+    }
+
+    #[test]
+    fn receipt_divergence() {
+        assert_eq!(first_divergence(&[1, 2, 3], &[1, 2, 3]), None);
+        assert_eq!(first_divergence(&[1, 5, 3], &[1, 2, 4]), Some(1));
+        // A prefix of the block is compared with the matching stored receipts.
+        assert_eq!(first_divergence(&[1, 2], &[1, 2, 3]), None);
+        assert_eq!(first_divergence(&[1, 2, 3], &[1, 2]), Some(2));
+    }
+}
@@ -0,0 +1,220 @@
+//! Command that unwinds the whole pipeline to a block.
+
+use crate::{
+    common::{
+        AccessRights, CliComponentsBuilder, CliNodeComponents, CliNodeTypes, Environment,
+        EnvironmentArgs,
+    },
+    stage::unwind::build_pipeline,
+};
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use itertools::Itertools;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db_common::consistency::{StorageHeights, INCONSISTENCY_INSTRUCTIONS};
+use reth_provider::{
+    BlockNumReader, ChainSpecProvider, PruneCheckpointReader, StageCheckpointReader,
+    StaticFileProviderFactory,
+};
+use reth_prune::{PruneCheckpoint, PruneSegment};
+use reth_stages::StageId;
+use std::{fmt, sync::Arc};
+use tracing::{info, warn};
+
+/// `reth debug unwind-to` command
+///
+/// Unwinds every stage in reverse order to the block, which truncates the static files and resets
+/// the stage checkpoints to it, and then runs the storage consistency check of the node startup.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The block to unwind to. The block stays in the database.
+    #[arg(long, value_name = "BLOCK")]
+    block: BlockNumber,
+
+    /// Confirm the unwind, which removes all data above the block and can't be undone.
+    #[arg(long)]
+    confirm: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `debug unwind-to` command
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
+        let components = components(provider_factory.chain_spec());
+        let target = self.block;
+
+        let provider = provider_factory.provider()?;
+        let latest = provider.last_block_number()?;
+        eyre::ensure!(target < latest, "Block {target} is not below the latest block {latest}");
+
+        let earliest = provider_factory.static_file_provider().earliest_history_height();
+        if let Some(horizon) = unwind_horizon(&provider.get_prune_checkpoints()?, earliest) &&
+            target < horizon.block
+        {
+            eyre::bail!("Can't unwind to block {target}: {horizon}")
+        }
+
+        let mut stages = Vec::new();
+        for stage in StageId::ALL {
+            if provider.get_stage_checkpoint(stage)?.is_some_and(|c| c.block_number > target) {
+                stages.push(stage);
+            }
+        }
+        drop(provider);
+
+        if !self.confirm {
+            eyre::bail!(
+                "Unwinding from block {latest} to block {target} removes the data of the stages {} \
+                 above block {target} and can't be undone, pass `--confirm` to unwind",
+                stages.iter().join(", ")
+            )
+        }
+
+        info!(target: "reth::cli", from = latest, to = target, stages = %stages.iter().join(", "), "Unwinding the pipeline");
+        let mut pipeline = build_pipeline(
+            config,
+            provider_factory.clone(),
+            components.evm_config().clone(),
+            false,
+        )?;
+
+        // Move all applicable data from database to static files.
+        pipeline.move_to_static_files()?;
+
+        pipeline.unwind(target, None)?;
+
+        // The same check as on startup, which would otherwise heal the storage on the next start
+        info!(target: "reth::cli", "Checking the storage consistency");
+        if let Some(unwind_target) = provider_factory
+            .static_file_provider()
+            .check_consistency(&provider_factory.provider()?)?
+        {
+            eyre::bail!(
+                "The storage is inconsistent after the unwind, the node would unwind to \
+                 {unwind_target} on startup. {INCONSISTENCY_INSTRUCTIONS}"
+            )
+        }
+        let heights = StorageHeights::read(&provider_factory.provider()?)?;
+        for inconsistency in heights.inconsistencies() {
+            if !inconsistency.healable {
+                eyre::bail!(
+                    "Inconsistent storage after the unwind: {inconsistency}.\n{heights}\n{INCONSISTENCY_INSTRUCTIONS}"
+                )
+            }
+            warn!(target: "reth::cli", "Inconsistent storage that is healed on startup: {inconsistency}");
+        }
+
+        info!(target: "reth::cli", block = target, "Unwound the pipeline");
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// The lowest block that the storage can be unwound to, because data that unwinding needs is
+/// missing for the blocks up to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct UnwindHorizon {
+    block: BlockNumber,
+    reason: String,
+}
+
+impl fmt::Display for UnwindHorizon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, the lowest block to unwind to is {}", self.reason, self.block)
+    }
+}
+
+/// Returns the data of the segment that unwinding the stages needs, if any.
+///
+/// The execution, hashing and merkle stages unwind the state with the changesets, and the
+/// transaction lookup stage removes the hashes of the unwound transactions.
+const fn required_data(segment: PruneSegment) -> Option<&'static str> {
+    match segment {
+        PruneSegment::AccountHistory => Some("account changesets"),
+        PruneSegment::StorageHistory => Some("storage changesets"),
+        PruneSegment::Transactions | PruneSegment::Bodies => Some("transactions"),
+        _ => None,
+    }
+}
+
+/// Returns the lowest block that the storage can be unwound to, from the prune checkpoints and
+/// the earliest block whose history hasn't expired.
+fn unwind_horizon(
+    checkpoints: &[(PruneSegment, PruneCheckpoint)],
+    earliest_history_height: BlockNumber,
+) -> Option<UnwindHorizon> {
+    let mut horizons = checkpoints
+        .iter()
+        .filter_map(|(segment, checkpoint)| {
+            let data = required_data(*segment)?;
+            let block = checkpoint.block_number?;
+            Some(UnwindHorizon {
+                block,
+                reason: format!(
+                    "the {data} are pruned up to block {block} by the {segment} segment"
+                ),
+            })
+        })
+        .collect::<Vec<_>>();
+    if earliest_history_height > 0 {
+        horizons.push(UnwindHorizon {
+            block: earliest_history_height - 1,
+            reason: format!("the history before block {earliest_history_height} has expired"),
+        });
+    }
+    horizons.into_iter().max_by_key(|horizon| horizon.block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_prune::PruneMode;
+
+    fn checkpoint(
+        segment: PruneSegment,
+        block_number: Option<u64>,
+    ) -> (PruneSegment, PruneCheckpoint) {
+        (segment, PruneCheckpoint { block_number, tx_number: None, prune_mode: PruneMode::Full })
+    }
+
+    #[test]
+    fn horizon() {
+        assert_eq!(unwind_horizon(&[], 0), None);
+
+        // Receipts aren't needed to unwind, and unfinished pruning has no horizon
+        let checkpoints = [
+            checkpoint(PruneSegment::Receipts, Some(900)),
+            checkpoint(PruneSegment::StorageHistory, None),
+        ];
+        assert_eq!(unwind_horizon(&checkpoints, 0), None);
+
+        let checkpoints = [
+            checkpoint(PruneSegment::AccountHistory, Some(100)),
+            checkpoint(PruneSegment::StorageHistory, Some(200)),
+        ];
+        let horizon = unwind_horizon(&checkpoints, 0).unwrap();
+        assert_eq!(horizon.block, 200);
+        assert_eq!(
+            horizon.to_string(),
+            "the storage changesets are pruned up to block 200 by the StorageHistory segment, the \
+             lowest block to unwind to is 200"
+        );
+
+        // Expired history is the limit if it's above the pruned data
+        assert_eq!(unwind_horizon(&checkpoints, 301).unwrap().block, 300);
+        assert_eq!(unwind_horizon(&checkpoints, 150).unwrap().block, 200);
+    }
+}
@@ -1,11 +1,14 @@
 //! Command that dumps genesis block JSON configuration to stdout
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
+use alloy_genesis::Genesis;
 use clap::Parser;
-use reth_chainspec::EthChainSpec;
+use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition, Hardfork};
 use reth_cli::chainspec::ChainSpecParser;
 
 /// Dumps genesis block JSON configuration to stdout
+///
+/// The output can be passed back to `reth init --chain`, or piped to `reth init --chain -`.
 #[derive(Debug, Parser)]
 pub struct DumpGenesisCommand<C: ChainSpecParser> {
     /// The chain this node is running.
@@ -21,10 +24,10 @@ pub struct DumpGenesisCommand<C: ChainSpecParser> {
     chain: Arc<C::ChainSpec>,
 }
 
-impl<C: ChainSpecParser<ChainSpec: EthChainSpec>> DumpGenesisCommand<C> {
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> DumpGenesisCommand<C> {
     /// Execute the `dump-genesis` command
     pub async fn execute(self) -> eyre::Result<()> {
-        println!("{}", serde_json::to_string_pretty(self.chain.genesis())?);
+        println!("{}", serde_json::to_string_pretty(&genesis_with_hardforks(&*self.chain))?);
         Ok(())
     }
 }
@@ -36,9 +39,93 @@ impl<C: ChainSpecParser> DumpGenesisCommand<C> {
     }
 }
 
+/// Returns the genesis of the chain with the chain id, deposit contract, blob schedule and the
+/// activation of every Ethereum hardfork filled into its config.
+///
+/// The built-in chain specs keep their hardforks outside of the genesis config, so without this a
+/// chain spec parsed from the dumped genesis would not have any hardforks. Values that are already
+/// set in the config are kept.
+fn genesis_with_hardforks<C: EthChainSpec + EthereumHardforks>(chain: &C) -> Genesis {
+    let block = |fork| chain.ethereum_fork_activation(fork).block_number();
+    let timestamp = |fork| match chain.ethereum_fork_activation(fork) {
+        ForkCondition::Timestamp(timestamp) => Some(timestamp),
+        _ => None,
+    };
+
+    let mut genesis = chain.genesis().clone();
+    let config = &mut genesis.config;
+    if config.chain_id == 0 {
+        config.chain_id = chain.chain_id();
+    }
+
+    config.homestead_block = config.homestead_block.or(block(EthereumHardfork::Homestead));
+    config.dao_fork_block = config.dao_fork_block.or(block(EthereumHardfork::Dao));
+    config.dao_fork_support |= config.dao_fork_block.is_some();
+    config.eip150_block = config.eip150_block.or(block(EthereumHardfork::Tangerine));
+    config.eip155_block = config.eip155_block.or(block(EthereumHardfork::SpuriousDragon));
+    config.eip158_block = config.eip158_block.or(block(EthereumHardfork::SpuriousDragon));
+    config.byzantium_block = config.byzantium_block.or(block(EthereumHardfork::Byzantium));
+    config.constantinople_block =
+        config.constantinople_block.or(block(EthereumHardfork::Constantinople));
+    config.petersburg_block = config.petersburg_block.or(block(EthereumHardfork::Petersburg));
+    config.istanbul_block = config.istanbul_block.or(block(EthereumHardfork::Istanbul));
+    config.muir_glacier_block = config.muir_glacier_block.or(block(EthereumHardfork::MuirGlacier));
+    config.berlin_block = config.berlin_block.or(block(EthereumHardfork::Berlin));
+    config.london_block = config.london_block.or(block(EthereumHardfork::London));
+    config.arrow_glacier_block =
+        config.arrow_glacier_block.or(block(EthereumHardfork::ArrowGlacier));
+    config.gray_glacier_block = config.gray_glacier_block.or(block(EthereumHardfork::GrayGlacier));
+
+    if let ForkCondition::TTD { total_difficulty, fork_block, .. } =
+        chain.ethereum_fork_activation(EthereumHardfork::Paris) &&
+        config.terminal_total_difficulty.is_none()
+    {
+        config.terminal_total_difficulty = Some(total_difficulty);
+        config.terminal_total_difficulty_passed = true;
+        config.merge_netsplit_block = config.merge_netsplit_block.or(fork_block);
+    }
+
+    config.shanghai_time = config.shanghai_time.or(timestamp(EthereumHardfork::Shanghai));
+    config.cancun_time = config.cancun_time.or(timestamp(EthereumHardfork::Cancun));
+    config.prague_time = config.prague_time.or(timestamp(EthereumHardfork::Prague));
+    config.osaka_time = config.osaka_time.or(timestamp(EthereumHardfork::Osaka));
+    config.bpo1_time = config.bpo1_time.or(timestamp(EthereumHardfork::Bpo1));
+    config.bpo2_time = config.bpo2_time.or(timestamp(EthereumHardfork::Bpo2));
+    config.bpo3_time = config.bpo3_time.or(timestamp(EthereumHardfork::Bpo3));
+    config.bpo4_time = config.bpo4_time.or(timestamp(EthereumHardfork::Bpo4));
+    config.bpo5_time = config.bpo5_time.or(timestamp(EthereumHardfork::Bpo5));
+
+    config.deposit_contract_address = config
+        .deposit_contract_address
+        .or_else(|| chain.deposit_contract().map(|contract| contract.address));
+
+    if config.blob_schedule.is_empty() {
+        config.blob_schedule = [
+            EthereumHardfork::Cancun,
+            EthereumHardfork::Prague,
+            EthereumHardfork::Osaka,
+            EthereumHardfork::Bpo1,
+            EthereumHardfork::Bpo2,
+            EthereumHardfork::Bpo3,
+            EthereumHardfork::Bpo4,
+            EthereumHardfork::Bpo5,
+        ]
+        .into_iter()
+        .filter_map(|fork| {
+            let params = chain.blob_params_at_timestamp(timestamp(fork)?)?;
+            Some((fork.name().to_lowercase(), params))
+        })
+        .collect::<BTreeMap<_, _>>();
+    }
+
+    genesis
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_consensus::constants::MAINNET_GENESIS_HASH;
+    use reth_chainspec::{ChainSpec, MAINNET};
     use reth_ethereum_cli::chainspec::{EthereumChainSpecParser, SUPPORTED_CHAINS};
 
     #[test]
@@ -53,4 +140,19 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn dump_mainnet_genesis_round_trip() {
+        let json = serde_json::to_string_pretty(&genesis_with_hardforks(&*MAINNET)).unwrap();
+        let genesis: Genesis = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(genesis.config.chain_id, 1);
+        assert_eq!(genesis.config.london_block, Some(12_965_000));
+        assert_eq!(genesis.config.shanghai_time, Some(1_681_338_455));
+        assert!(genesis.config.blob_schedule.contains_key("bpo1"));
+
+        let chain = ChainSpec::from(genesis);
+        assert_eq!(chain.genesis_hash(), MAINNET_GENESIS_HASH);
+        assert_eq!(chain.latest_fork_id(), MAINNET.latest_fork_id());
+    }
 }
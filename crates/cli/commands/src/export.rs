@@ -0,0 +1,336 @@
+//! Command that exports canonical blocks to RLP files readable by `reth import`.
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_primitives_traits::{Block, SealedHeader};
+use reth_provider::{BlockHashReader, BlockNumReader, BlockReader};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+/// The number of blocks read from the database at once.
+const EXPORT_BATCH_SIZE: u64 = 1_000;
+
+/// How often the export progress is logged.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Exports canonical blocks as consecutive RLP encoded blocks to a file or files.
+#[derive(Debug, Parser)]
+pub struct ExportCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to export.
+    #[arg(long, value_name = "BLOCK_NUMBER", default_value_t = 0)]
+    from: u64,
+
+    /// The last block to export, defaults to the highest block in the database.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    to: Option<u64>,
+
+    /// Maximum size in bytes of each exported file.
+    ///
+    /// If set, the blocks are split into files named `<STEM>-<INDEX>.<EXTENSION>` after the
+    /// export path, each of which can be passed to `reth import`.
+    #[arg(long, value_name = "BYTES")]
+    max_file_size: Option<u64>,
+
+    /// Also export the senders of the transactions of each block.
+    ///
+    /// The senders are written next to each exported file, to a file with the `.senders`
+    /// extension appended, as one RLP list of addresses per block.
+    #[arg(long)]
+    with_senders: bool,
+
+    /// The path to export the blocks to.
+    #[arg(value_name = "EXPORT_PATH")]
+    path: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportCommand<C> {
+    /// Execute `export` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+
+        let to = match self.to {
+            Some(to) => to,
+            None => provider.best_block_number()?,
+        };
+        if self.from > to {
+            eyre::bail!("Invalid block range {}..={to}", self.from)
+        }
+
+        info!(target: "reth::cli", from = self.from, to, path = %self.path.display(), "Exporting blocks");
+
+        let mut writer = ChunkedWriter::new(self.path.clone(), self.max_file_size);
+        let mut senders_file = None;
+        let mut parent_hash =
+            if self.from == 0 { None } else { provider.block_hash(self.from - 1)? };
+
+        let start = Instant::now();
+        let mut last_log = start;
+        let mut exported = 0u64;
+        for batch_start in (self.from..=to).step_by(EXPORT_BATCH_SIZE as usize) {
+            let batch = batch_start..=to.min(batch_start + EXPORT_BATCH_SIZE - 1);
+            let blocks = if self.with_senders {
+                provider
+                    .block_with_senders_range(batch.clone())?
+                    .into_iter()
+                    .map(|block| {
+                        let (block, senders) = block.split();
+                        (block, Some(senders))
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                provider
+                    .block_range(batch.clone())?
+                    .into_iter()
+                    .map(|block| (block, None))
+                    .collect()
+            };
+            if blocks.len() as u64 != batch.end() - batch.start() + 1 {
+                eyre::bail!("Missing blocks in range {batch:?}, the database is incomplete")
+            }
+
+            for (number, (block, senders)) in batch.zip(blocks) {
+                let header = SealedHeader::seal_slow(block.header().clone());
+                let canonical = provider.block_hash(number)?;
+                verify_block(number, &header, parent_hash, canonical)?;
+                parent_hash = Some(header.hash());
+
+                // A new senders file is started together with each blocks file, so that every
+                // senders file matches the blocks file it is named after.
+                let rotated = writer.write(&alloy_rlp::encode(&block))?;
+                if let Some(senders) = senders {
+                    if rotated {
+                        if let Some(mut file) = senders_file.take() {
+                            file.flush()?;
+                        }
+                        let path = senders_path(writer.current_path());
+                        senders_file = Some(BufWriter::new(File::create(path)?));
+                    }
+                    let file = senders_file.as_mut().expect("created with the first block");
+                    file.write_all(&alloy_rlp::encode(&senders))?;
+                }
+                exported += 1;
+            }
+
+            if last_log.elapsed() >= PROGRESS_INTERVAL {
+                last_log = Instant::now();
+                info!(
+                    target: "reth::cli",
+                    exported,
+                    total = to - self.from + 1,
+                    blocks_per_sec = exported as f64 / start.elapsed().as_secs_f64(),
+                    "Exporting blocks"
+                );
+            }
+        }
+
+        let files = writer.finish()?;
+        if let Some(mut file) = senders_file {
+            file.flush()?;
+        }
+
+        info!(
+            target: "reth::cli",
+            exported,
+            files = files.len(),
+            elapsed = ?start.elapsed(),
+            "Exported blocks"
+        );
+        for file in files {
+            info!(target: "reth::cli", path = %file.display(), "Exported file");
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> ExportCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Verifies that an exported block is canonical and links to the previously exported one.
+fn verify_block<H: BlockHeader>(
+    number: u64,
+    header: &SealedHeader<H>,
+    parent_hash: Option<B256>,
+    canonical_hash: Option<B256>,
+) -> eyre::Result<()> {
+    if header.number() != number {
+        eyre::bail!("Block {number} has unexpected header number {}", header.number())
+    }
+    if canonical_hash != Some(header.hash()) {
+        eyre::bail!(
+            "Block {number} hash {} does not match the canonical hash {canonical_hash:?}",
+            header.hash()
+        )
+    }
+    if let Some(parent_hash) = parent_hash &&
+        header.parent_hash() != parent_hash
+    {
+        eyre::bail!(
+            "Block {number} parent hash {} does not match the hash {parent_hash} of block {}",
+            header.parent_hash(),
+            number - 1
+        )
+    }
+    Ok(())
+}
+
+/// Returns the path of the senders file exported next to a blocks file.
+fn senders_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".senders");
+    path.into()
+}
+
+/// Returns the path of the chunk with the given index, e.g. `blocks-00001.rlp` for `blocks.rlp`.
+fn chunk_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{stem}-{index:05}.{}", extension.to_string_lossy()),
+        None => format!("{stem}-{index:05}"),
+    };
+    path.with_file_name(name)
+}
+
+/// Writes encoded items to a file, moving on to the next file once the maximum file size would be
+/// exceeded.
+#[derive(Debug)]
+struct ChunkedWriter {
+    /// The export path, which chunk paths are derived from.
+    path: PathBuf,
+    /// The maximum size of each file, if the output is chunked.
+    max_file_size: Option<u64>,
+    /// The file currently written to.
+    file: Option<BufWriter<File>>,
+    /// The number of bytes written to the current file.
+    written: u64,
+    /// The paths of all files created so far.
+    files: Vec<PathBuf>,
+}
+
+impl ChunkedWriter {
+    const fn new(path: PathBuf, max_file_size: Option<u64>) -> Self {
+        Self { path, max_file_size, file: None, written: 0, files: Vec::new() }
+    }
+
+    /// Returns the path of the file currently written to.
+    fn current_path(&self) -> &Path {
+        self.files.last().expect("a file is open")
+    }
+
+    /// Writes an item, returning `true` if a new file was started for it.
+    fn write(&mut self, item: &[u8]) -> eyre::Result<bool> {
+        let full = self
+            .max_file_size
+            .is_some_and(|max| self.written > 0 && self.written + item.len() as u64 > max);
+        let rotated = self.file.is_none() || full;
+        if rotated {
+            self.rotate()?;
+        }
+
+        self.file.as_mut().expect("file is open").write_all(item)?;
+        self.written += item.len() as u64;
+        Ok(rotated)
+    }
+
+    /// Closes the current file and starts the next one.
+    fn rotate(&mut self) -> eyre::Result<()> {
+        if let Some(mut file) = self.file.take() {
+            file.flush()?;
+        }
+        let path = match self.max_file_size {
+            Some(_) => chunk_path(&self.path, self.files.len()),
+            None => self.path.clone(),
+        };
+        self.file = Some(BufWriter::new(File::create(&path)?));
+        self.files.push(path);
+        self.written = 0;
+        Ok(())
+    }
+
+    /// Flushes the current file and returns the paths of all written files.
+    fn finish(mut self) -> eyre::Result<Vec<PathBuf>> {
+        if let Some(mut file) = self.file.take() {
+            file.flush()?;
+        }
+        Ok(self.files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_export_command() {
+        let args: ExportCommand<EthereumChainSpecParser> = ExportCommand::parse_from([
+            "reth",
+            "--from",
+            "10",
+            "--to",
+            "20",
+            "--max-file-size",
+            "1000",
+            "--with-senders",
+            "blocks.rlp",
+        ]);
+        assert_eq!(args.from, 10);
+        assert_eq!(args.to, Some(20));
+        assert_eq!(args.max_file_size, Some(1000));
+        assert!(args.with_senders);
+        assert_eq!(args.path, PathBuf::from("blocks.rlp"));
+    }
+
+    #[test]
+    fn chunked_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = ChunkedWriter::new(dir.path().join("blocks.rlp"), Some(10));
+        assert!(writer.write(&[0; 6]).unwrap());
+        assert!(!writer.write(&[1; 4]).unwrap());
+        assert!(writer.write(&[2; 4]).unwrap());
+        // Items larger than the maximum size get a file of their own.
+        assert!(writer.write(&[3; 20]).unwrap());
+        assert_eq!(writer.current_path(), dir.path().join("blocks-00002.rlp"));
+
+        let files = writer.finish().unwrap();
+        assert_eq!(
+            files.iter().map(|file| std::fs::read(file).unwrap()).collect::<Vec<_>>(),
+            [[[0; 6].as_slice(), &[1; 4]].concat(), vec![2; 4], vec![3; 20]]
+        );
+        assert_eq!(senders_path(&files[0]), dir.path().join("blocks-00000.rlp.senders"));
+    }
+
+    #[test]
+    fn hash_chain() {
+        let parent = SealedHeader::seal_slow(Header { number: 1, ..Default::default() });
+        let child = SealedHeader::seal_slow(Header {
+            number: 2,
+            parent_hash: parent.hash(),
+            ..Default::default()
+        });
+
+        assert!(verify_block(2, &child, Some(parent.hash()), Some(child.hash())).is_ok());
+        assert!(verify_block(2, &child, None, Some(child.hash())).is_ok());
+        assert!(verify_block(2, &child, Some(B256::ZERO), Some(child.hash())).is_err());
+        assert!(verify_block(2, &child, Some(parent.hash()), Some(parent.hash())).is_err());
+        assert!(verify_block(3, &child, Some(parent.hash()), Some(child.hash())).is_err());
+    }
+}
@@ -3,10 +3,12 @@ use crate::{
     common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment, EnvironmentArgs},
     import_core::{import_blocks_from_file, ImportConfig},
 };
+use alloy_primitives::B256;
 use clap::Parser;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_node_core::version::version_metadata;
+use reth_provider::{BlockHashReader, BlockNumReader};
 use std::{path::PathBuf, sync::Arc};
 use tracing::info;
 
@@ -26,6 +28,20 @@ pub struct ImportCommand<C: ChainSpecParser> {
     #[arg(long, value_name = "CHUNK_LEN", verbatim_doc_comment)]
     chunk_len: Option<u64>,
 
+    /// Skips blocks that are already in the database.
+    ///
+    /// The skipped blocks must match the local chain by hash, so that an interrupted import can be
+    /// resumed with the same file(s).
+    #[arg(long, verbatim_doc_comment)]
+    resume: bool,
+
+    /// The hash the chain tip is expected to have after the import.
+    ///
+    /// The command fails if the tip differs, e.g. because the file(s) diverge from the expected
+    /// chain.
+    #[arg(long, value_name = "HASH", verbatim_doc_comment)]
+    expected_tip: Option<B256>,
+
     /// The path(s) to block file(s) for import.
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
@@ -52,7 +68,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportComm
 
         info!(target: "reth::cli", "Starting import of {} file(s)", self.paths.len());
 
-        let import_config = ImportConfig { no_state: self.no_state, chunk_len: self.chunk_len };
+        let import_config = ImportConfig {
+            no_state: self.no_state,
+            chunk_len: self.chunk_len,
+            resume: self.resume,
+        };
 
         let executor = components.evm_config().clone();
         let consensus = Arc::new(components.consensus().clone());
@@ -61,6 +81,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportComm
         let mut total_imported_txns = 0;
         let mut total_decoded_blocks = 0;
         let mut total_decoded_txns = 0;
+        let mut total_skipped_blocks = 0;
 
         // Import each file sequentially
         for (index, path) in self.paths.iter().enumerate() {
@@ -80,26 +101,41 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportComm
             total_imported_txns += result.total_imported_txns;
             total_decoded_blocks += result.total_decoded_blocks;
             total_decoded_txns += result.total_decoded_txns;
+            total_skipped_blocks += result.total_skipped_blocks;
 
             if !result.is_complete() {
                 return Err(eyre::eyre!(
-                    "Chain was partially imported from file: {}. Imported {}/{} blocks, {}/{} transactions",
+                    "Chain was partially imported from file: {}. Imported {}/{} blocks, {}/{} transactions, skipped {} blocks",
                     path.display(),
                     result.total_imported_blocks,
                     result.total_decoded_blocks,
                     result.total_imported_txns,
-                    result.total_decoded_txns
+                    result.total_decoded_txns,
+                    result.total_skipped_blocks
                 ));
             }
 
             info!(target: "reth::cli",
-                  "Successfully imported file {}: {} blocks, {} transactions",
-                  path.display(), result.total_imported_blocks, result.total_imported_txns);
+                  "Successfully imported file {}: {} blocks, {} transactions, {} blocks skipped",
+                  path.display(), result.total_imported_blocks, result.total_imported_txns,
+                  result.total_skipped_blocks);
         }
 
         info!(target: "reth::cli",
-              "All files imported successfully. Total: {}/{} blocks, {}/{} transactions",
-              total_imported_blocks, total_decoded_blocks, total_imported_txns, total_decoded_txns);
+              "All files imported successfully. Total: {}/{} blocks, {}/{} transactions, {} blocks skipped",
+              total_imported_blocks, total_decoded_blocks, total_imported_txns, total_decoded_txns,
+              total_skipped_blocks);
+
+        if let Some(expected_tip) = self.expected_tip {
+            let tip = provider_factory.last_block_number()?;
+            let tip_hash = provider_factory.block_hash(tip)?;
+            if tip_hash != Some(expected_tip) {
+                return Err(eyre::eyre!(
+                    "Chain tip {tip} has hash {tip_hash:?}, expected {expected_tip}"
+                ));
+            }
+            info!(target: "reth::cli", tip, %expected_tip, "Chain tip matches the expected hash");
+        }
 
         Ok(())
     }
@@ -130,6 +166,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_import_command_with_resume() {
+        let args: ImportCommand<EthereumChainSpecParser> = ImportCommand::parse_from([
+            "reth",
+            "--resume",
+            "--expected-tip",
+            "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+            "file.rlp",
+        ]);
+        assert!(args.resume);
+        assert_eq!(
+            args.expected_tip,
+            Some(alloy_primitives::b256!(
+                "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+            ))
+        );
+    }
+
     #[test]
     fn parse_import_command_with_multiple_paths() {
         let args: ImportCommand<EthereumChainSpecParser> =
@@ -1,6 +1,7 @@
 //! Core import functionality without CLI dependencies.
 
-use alloy_primitives::B256;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Sealable, B256};
 use futures::StreamExt;
 use reth_config::Config;
 use reth_consensus::FullConsensus;
@@ -17,9 +18,10 @@ use reth_network_p2p::{
 };
 use reth_node_api::BlockTy;
 use reth_node_events::node::NodeEvent;
+use reth_primitives_traits::{FullBlock, SealedHeader};
 use reth_provider::{
-    providers::ProviderNodeTypes, BlockNumReader, HeaderProvider, ProviderError, ProviderFactory,
-    StageCheckpointReader,
+    providers::ProviderNodeTypes, BlockHashReader, BlockNumReader, HeaderProvider, ProviderError,
+    ProviderFactory, StageCheckpointReader,
 };
 use reth_prune::PruneModes;
 use reth_stages::{prelude::*, Pipeline, StageId, StageSet};
@@ -35,6 +37,9 @@ pub struct ImportConfig {
     pub no_state: bool,
     /// Chunk byte length to read from file.
     pub chunk_len: Option<u64>,
+    /// Skips blocks of the file that are already in the database, after checking that their
+    /// hashes match the local chain.
+    pub resume: bool,
 }
 
 /// Result of an import operation.
@@ -48,13 +53,17 @@ pub struct ImportResult {
     pub total_imported_blocks: usize,
     /// Total number of transactions imported into the database.
     pub total_imported_txns: usize,
+    /// Total number of blocks skipped because they were already in the database.
+    pub total_skipped_blocks: usize,
+    /// Total number of transactions skipped because they were already in the database.
+    pub total_skipped_txns: usize,
 }
 
 impl ImportResult {
-    /// Returns true if all blocks and transactions were imported successfully.
+    /// Returns true if all blocks and transactions were either imported successfully or skipped.
     pub fn is_complete(&self) -> bool {
-        self.total_decoded_blocks == self.total_imported_blocks &&
-            self.total_decoded_txns == self.total_imported_txns
+        self.total_decoded_blocks == self.total_imported_blocks + self.total_skipped_blocks &&
+            self.total_decoded_txns == self.total_imported_txns + self.total_skipped_txns
     }
 }
 
@@ -97,27 +106,64 @@ where
 
     let mut total_decoded_blocks = 0;
     let mut total_decoded_txns = 0;
-
-    let mut sealed_header = provider_factory
-        .sealed_header(provider_factory.last_block_number()?)?
-        .expect("should have genesis");
+    let mut total_skipped_blocks = 0;
+    let mut total_skipped_txns = 0;
+
+    // When resuming, the local head is the last block that went through all stages, so that the
+    // blocks of an interrupted import are run through the remaining stages again.
+    let local_head = || -> eyre::Result<_> {
+        let number = if import_config.resume {
+            provider_factory
+                .get_stage_checkpoint(StageId::Finish)?
+                .map(|checkpoint| checkpoint.block_number)
+                .unwrap_or_default()
+        } else {
+            provider_factory.last_block_number()?
+        };
+        Ok(provider_factory.sealed_header(number)?.expect("should have genesis"))
+    };
+    let mut sealed_header = local_head()?;
 
     let static_file_producer =
         StaticFileProducer::new(provider_factory.clone(), PruneModes::default());
 
-    while let Some(file_client) =
-        reader.next_chunk::<BlockTy<N>>(consensus.clone(), Some(sealed_header)).await?
+    // When resuming, the file may start with blocks below the local head, so the first block isn't
+    // validated against the local head. Every following chunk is validated against the tip of the
+    // previous one.
+    let mut parent_header = (!import_config.resume).then(|| sealed_header.clone());
+    while let Some(mut file_client) =
+        reader.next_chunk::<BlockTy<N>>(consensus.clone(), parent_header.take()).await?
     {
         // create a new FileClient from chunk read from file
         info!(target: "reth::import",
             "Importing chain file chunk"
         );
 
-        let tip = file_client.tip().ok_or(eyre::eyre!("file client has no tip"))?;
         info!(target: "reth::import", "Chain file chunk read");
 
         total_decoded_blocks += file_client.headers_len();
         total_decoded_txns += file_client.total_transactions();
+        parent_header = file_client.tip_header();
+
+        if import_config.resume {
+            let (skipped_blocks, skipped_txns) =
+                skip_imported_blocks(&provider_factory, &mut file_client, &sealed_header)?;
+            total_skipped_blocks += skipped_blocks;
+            total_skipped_txns += skipped_txns;
+            if skipped_blocks > 0 {
+                info!(target: "reth::import",
+                    skipped_blocks,
+                    remaining_blocks = file_client.headers_len(),
+                    "Skipped blocks already in the database"
+                );
+            }
+            // All blocks of the chunk went through all stages already
+            if file_client.headers_len() == 0 {
+                continue
+            }
+        }
+
+        let tip = file_client.tip().ok_or(eyre::eyre!("file client has no tip"))?;
 
         let (mut pipeline, events) = build_import_pipeline_impl(
             config,
@@ -147,9 +193,7 @@ where
             },
         }
 
-        sealed_header = provider_factory
-            .sealed_header(provider_factory.last_block_number()?)?
-            .expect("should have genesis");
+        sealed_header = local_head()?;
     }
 
     let provider = provider_factory.provider()?;
@@ -162,6 +206,8 @@ where
         total_decoded_txns,
         total_imported_blocks,
         total_imported_txns,
+        total_skipped_blocks,
+        total_skipped_txns,
     };
 
     if !result.is_complete() {
@@ -170,12 +216,16 @@ where
             total_imported_blocks,
             total_decoded_txns,
             total_imported_txns,
+            total_skipped_blocks,
+            total_skipped_txns,
             "Chain was partially imported"
         );
     } else {
         info!(target: "reth::import",
             total_imported_blocks,
             total_imported_txns,
+            total_skipped_blocks,
+            total_skipped_txns,
             "Chain was fully imported"
         );
     }
@@ -183,6 +233,56 @@ where
     Ok(result)
 }
 
+/// Removes the blocks up to the local head from the file client, returning the number of removed
+/// blocks and transactions.
+///
+/// Fails if a removed block doesn't match the local chain, or if the remaining blocks don't extend
+/// the local head.
+fn skip_imported_blocks<N, B>(
+    provider_factory: &ProviderFactory<N>,
+    file_client: &mut FileClient<B>,
+    local_head: &SealedHeader<B::Header>,
+) -> eyre::Result<(usize, usize)>
+where
+    N: ProviderNodeTypes,
+    B: FullBlock,
+{
+    let head = local_head.number();
+    for header in file_client.headers_iter().filter(|header| header.number() <= head) {
+        let number = header.number();
+        let hash = header.hash_slow();
+        let local_hash = provider_factory.block_hash(number)?;
+        if local_hash != Some(hash) {
+            eyre::bail!(
+                "Block {number} of the file has hash {hash}, which does not match the local chain \
+                 block {local_hash:?}"
+            )
+        }
+    }
+
+    let (blocks, txns) = (file_client.headers_len(), file_client.total_transactions());
+    file_client.remove_blocks_up_to(head);
+    let skipped = (blocks - file_client.headers_len(), txns - file_client.total_transactions());
+
+    if let Some(first) = file_client.min_block() {
+        let parent_hash = file_client
+            .headers_iter()
+            .find(|header| header.number() == first)
+            .map(|header| header.parent_hash());
+        if first != head + 1 {
+            eyre::bail!("The file resumes at block {first}, but the local head is block {head}")
+        }
+        if parent_hash != Some(local_head.hash()) {
+            eyre::bail!(
+                "Block {first} of the file does not extend the local head {head} ({})",
+                local_head.hash()
+            )
+        }
+    }
+
+    Ok(skipped)
+}
+
 /// Builds import pipeline.
 ///
 /// If configured to execute, all stages will run. Otherwise, only stages that don't require state
@@ -8,11 +8,16 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+pub mod cli_defaults;
+pub mod cli_env;
 pub mod common;
+pub mod completions;
 pub mod config_cmd;
 pub mod db;
+pub mod debug_cmd;
 pub mod download;
 pub mod dump_genesis;
+pub mod export;
 pub mod export_era;
 pub mod import;
 pub mod import_core;
@@ -24,8 +29,10 @@ pub mod node;
 pub mod p2p;
 pub mod prune;
 pub mod re_execute;
+pub mod recover;
 pub mod stage;
 #[cfg(feature = "arbitrary")]
 pub mod test_vectors;
+pub mod version_cmd;
 
 pub use node::NodeCommand;
@@ -2,20 +2,22 @@
 
 use crate::launcher::Launcher;
 use clap::{value_parser, Args, Parser};
-use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_chainspec::{ChainSpec, EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 use reth_db::init_db;
 use reth_node_builder::NodeBuilder;
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, MetricArgs,
-        NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, StaticFilesArgs, TxPoolArgs,
+        BlockingTaskArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs,
+        HardforkOverrideArgs, HealthArgs, MetricArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs,
+        RpcServerArgs, StaticFilesArgs, TxPoolArgs,
     },
     node_config::NodeConfig,
     version,
 };
-use std::{ffi::OsString, fmt, path::PathBuf, sync::Arc};
+use reth_tasks::lanes::{BlockingTaskLaneLimits, BlockingTaskLanes};
+use std::{ffi::OsString, fmt, path::PathBuf, sync::Arc, time::Duration};
 
 /// Start the node
 #[derive(Debug, Parser)]
@@ -66,6 +68,31 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[arg(long, conflicts_with = "instance", global = true)]
     pub with_unused_ports: bool,
 
+    /// How long to wait for the node to shut down gracefully after the first Ctrl-C or SIGTERM
+    /// before the process is aborted.
+    ///
+    /// Without a timeout, a second Ctrl-C or SIGTERM reports the tasks that are still shutting
+    /// down and a third one aborts the process.
+    #[arg(long, value_name = "DURATION", value_parser = humantime::parse_duration)]
+    pub shutdown_timeout: Option<Duration>,
+
+    /// Shut down gracefully and exit once the node has finished its initial sync.
+    ///
+    /// The node exits once the pipeline has synced to its target, e.g. `--debug.tip`, or, if no
+    /// pipeline sync is needed, once the engine has caught up with the chain tip.
+    #[arg(long)]
+    pub exit_after_sync: bool,
+
+    /// Serve RPC from the existing datadir without syncing.
+    ///
+    /// The database and static files are opened read-only, and neither networking, the pipeline
+    /// nor the engine API are started. Only the namespaces that serve existing chain data are
+    /// available, and methods that require new chain data, e.g. `eth_sendRawTransaction` and
+    /// subscriptions, return an error. The datadir is not locked, so another node may keep syncing
+    /// into it, in which case only the blocks it persisted are served, behind its tip.
+    #[arg(long, conflicts_with_all = ["dev", "exit_after_sync"])]
+    pub read_only: bool,
+
     /// All datadir related arguments
     #[command(flatten)]
     pub datadir: DatadirArgs,
@@ -114,6 +141,18 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten, next_help_heading = "Static Files")]
     pub static_files: StaticFilesArgs,
 
+    /// All health check server related arguments with --health prefix
+    #[command(flatten, next_help_heading = "Health")]
+    pub health: HealthArgs,
+
+    /// Limits of the blocking tasks of each kind of work
+    #[command(flatten, next_help_heading = "Blocking Tasks")]
+    pub blocking_tasks: BlockingTaskArgs,
+
+    /// Hardfork activation overrides with --override prefix
+    #[command(flatten, next_help_heading = "Overrides")]
+    pub overrides: HardforkOverrideArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -158,6 +197,9 @@ where
             metrics,
             instance,
             with_unused_ports,
+            shutdown_timeout: _,
+            exit_after_sync,
+            read_only,
             network,
             rpc,
             txpool,
@@ -169,9 +211,20 @@ where
             engine,
             era,
             static_files,
+            health,
+            blocking_tasks,
+            overrides,
             ext,
         } = self;
 
+        if read_only {
+            eyre::bail!("`--read-only` is not supported by this node")
+        }
+
+        for (fork, timestamp) in overrides.overrides() {
+            tracing::warn!(target: "reth::cli", ?fork, timestamp, "Overriding hardfork activation, this should only be used for testnets");
+        }
+
         // set up node config
         let mut node_config = NodeConfig {
             datadir,
@@ -190,6 +243,8 @@ where
             engine,
             era,
             static_files,
+            health,
+            exit_after_sync,
         };
 
         let data_dir = node_config.datadir();
@@ -202,9 +257,15 @@ where
             node_config = node_config.with_unused_ports();
         }
 
+        // The blocking RPC requests are already limited by their own flag
+        let lanes = BlockingTaskLanes::new(BlockingTaskLaneLimits {
+            sync: blocking_tasks.sync_max_blocking_threads,
+            rpc: node_config.rpc.rpc_max_blocking_io_requests,
+            maintenance: blocking_tasks.maintenance_max_blocking_threads,
+        });
         let builder = NodeBuilder::new(node_config)
             .with_database(database)
-            .with_launch_context(ctx.task_executor);
+            .with_launch_context(ctx.task_executor.with_blocking_task_lanes(lanes));
 
         launcher.entrypoint(builder, ext).await
     }
@@ -217,6 +278,22 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> NodeCommand<C, Ext> {
     }
 }
 
+impl<C, Ext> NodeCommand<C, Ext>
+where
+    C: ChainSpecParser<ChainSpec = ChainSpec>,
+    Ext: clap::Args + fmt::Debug,
+{
+    /// Applies the `--override.*` hardfork activations to the chain spec of this command.
+    ///
+    /// Returns an error if the overrides would activate hardforks out of order.
+    pub fn apply_hardfork_overrides(&mut self) -> eyre::Result<()> {
+        if !self.overrides.is_empty() {
+            self.overrides.apply(Arc::make_mut(&mut self.chain))?;
+        }
+        Ok(())
+    }
+}
+
 /// No Additional arguments
 #[derive(Debug, Clone, Copy, Default, Args)]
 #[non_exhaustive]
@@ -381,6 +458,15 @@ mod tests {
         assert_eq!(cmd.network.port, 30305);
     }
 
+    #[test]
+    fn parse_hardfork_overrides() {
+        let mut cmd: NodeCommand<EthereumChainSpecParser> =
+            NodeCommand::try_parse_args_from(["reth", "--chain", "dev", "--override.osaka", "42"])
+                .unwrap();
+        cmd.apply_hardfork_overrides().unwrap();
+        assert!(cmd.chain.is_osaka_active_at_timestamp(42));
+    }
+
     #[test]
     fn parse_with_unused_ports() {
         let cmd: NodeCommand<EthereumChainSpecParser> =
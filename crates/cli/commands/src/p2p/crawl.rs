@@ -0,0 +1,396 @@
+//! Crawl subcommand of P2P Debugging tool.
+
+use super::probe::handshake;
+use alloy_primitives::hex;
+use clap::Parser;
+use futures::stream::FuturesUnordered;
+use reth_chainspec::{EthChainSpec, ForkId, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_util::parse_duration_from_secs;
+use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config};
+use reth_network::config::rng_secret_key;
+use reth_network_peers::{NodeRecord, PeerId};
+use serde::Serialize;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    io::{BufWriter, Write},
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio_stream::StreamExt;
+use tracing::{debug, info};
+
+/// The interval between two lookups of the discovery service.
+const LOOKUP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// `reth p2p crawl` command
+///
+/// Runs discv4 with frequent random lookups and writes every node that was discovered to a file.
+/// Nodes are identified by their enode.
+#[derive(Parser, Debug)]
+pub struct Command<C: ChainSpecParser> {
+    /// How long to crawl for, in seconds.
+    #[arg(long, value_name = "SECS", value_parser = parse_duration_from_secs, default_value = "60")]
+    duration: Duration,
+
+    /// The file to write the discovered nodes to, one JSON object per line.
+    #[arg(long, value_name = "FILE")]
+    output: PathBuf,
+
+    /// Stop the crawl once this many unique nodes were discovered.
+    #[arg(long, default_value_t = 10_000)]
+    max_nodes: usize,
+
+    /// Perform the `RLPx` and eth handshakes with every discovered node whose fork id is not
+    /// known from its ENR.
+    #[arg(long)]
+    handshake: bool,
+
+    /// The maximum number of concurrent handshakes.
+    #[arg(long, default_value_t = 16, value_parser = clap::value_parser!(u64).range(1..))]
+    max_dials: u64,
+
+    /// How long to wait for each handshake.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "5s")]
+    handshake_timeout: Duration,
+
+    /// Listen address for discovery, a random port is used by default.
+    #[arg(long, default_value = "0.0.0.0:0")]
+    addr: SocketAddr,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::default_value(),
+        value_parser = C::parser()
+    )]
+    chain: Arc<C::ChainSpec>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks>> Command<C> {
+    /// Execute `p2p crawl` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let key = rng_secret_key();
+        let local_enr = NodeRecord::from_secret_key(self.addr, &key);
+        let config = Discv4Config::builder()
+            .add_boot_nodes(self.chain.bootnodes().unwrap_or_default())
+            .enable_eip868(true)
+            .lookup_interval(LOOKUP_INTERVAL)
+            .build();
+        let (discv4, mut discv4_service) = Discv4::bind(self.addr, local_enr, key, config).await?;
+        let mut updates = discv4_service.update_stream();
+        discv4_service.spawn();
+
+        info!(
+            target: "reth::cli",
+            duration = %humantime::format_duration(self.duration),
+            max_nodes = self.max_nodes,
+            "Crawling the network"
+        );
+
+        let mut crawler = Crawler::new(self.max_nodes);
+        let mut pending = VecDeque::new();
+        let mut dials = FuturesUnordered::new();
+        let (chain_spec, timeout) = (&*self.chain, self.handshake_timeout);
+        let dial = move |record: NodeRecord| async move {
+            let result = tokio::time::timeout(timeout, handshake(&key, record, chain_spec)).await;
+            (record.id, result)
+        };
+
+        let deadline = tokio::time::sleep(self.duration);
+        tokio::pin!(deadline);
+        loop {
+            tokio::select! {
+                _ = &mut deadline => break,
+                update = updates.next() => {
+                    let Some(update) = update else { break };
+                    let mut discovered = Vec::new();
+                    crawler.on_update(update, unix_timestamp(), &mut discovered);
+                    if self.handshake {
+                        pending.extend(discovered);
+                    }
+                }
+                Some((peer_id, result)) = dials.next(), if !dials.is_empty() => {
+                    match result {
+                        Ok(Ok((hello, status))) => {
+                            crawler.on_handshake(peer_id, hello.client_version, status.forkid)
+                        }
+                        Ok(Err(err)) => {
+                            debug!(target: "reth::cli", %peer_id, %err, "Handshake failed")
+                        }
+                        Err(_) => debug!(target: "reth::cli", %peer_id, "Handshake timed out"),
+                    }
+                }
+            }
+
+            while dials.len() < self.max_dials as usize &&
+                let Some(record) = pending.pop_front()
+            {
+                if crawler.needs_handshake(&record.id) {
+                    dials.push(dial(record));
+                }
+            }
+
+            if crawler.is_full() && dials.is_empty() {
+                info!(target: "reth::cli", "Reached the maximum number of nodes");
+                break
+            }
+        }
+        discv4.terminate();
+
+        let mut nodes = crawler.nodes.into_values().collect::<Vec<_>>();
+        nodes.sort_by_key(|node| node.first_seen);
+
+        let mut file = BufWriter::new(reth_fs_util::create_file(&self.output)?);
+        for node in &nodes {
+            serde_json::to_writer(&mut file, node)?;
+            writeln!(file)?;
+        }
+        file.flush()?;
+        info!(target: "reth::cli", path = %self.output.display(), "Wrote discovered nodes");
+
+        print_summary(&nodes);
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.chain)
+    }
+}
+
+/// Keeps track of the nodes discovered during a crawl.
+#[derive(Debug)]
+struct Crawler {
+    nodes: HashMap<PeerId, CrawledNode>,
+    max_nodes: usize,
+}
+
+impl Crawler {
+    fn new(max_nodes: usize) -> Self {
+        Self { nodes: HashMap::new(), max_nodes }
+    }
+
+    /// Returns true once the maximum number of nodes was discovered.
+    fn is_full(&self) -> bool {
+        self.nodes.len() >= self.max_nodes
+    }
+
+    /// Records a discovery update, adding the newly discovered nodes to `discovered`.
+    fn on_update(&mut self, update: DiscoveryUpdate, now: u64, discovered: &mut Vec<NodeRecord>) {
+        match update {
+            DiscoveryUpdate::Added(record) | DiscoveryUpdate::DiscoveredAtCapacity(record) => {
+                self.on_node(record, now, discovered);
+            }
+            DiscoveryUpdate::EnrForkId(record, fork_id) => {
+                self.on_node(record, now, discovered);
+                if let Some(node) = self.nodes.get_mut(&record.id) {
+                    node.fork_id = Some(CrawledForkId::new(fork_id, ForkIdSource::Enr));
+                }
+            }
+            DiscoveryUpdate::Removed(_) => {}
+            DiscoveryUpdate::Batch(updates) => {
+                for update in updates {
+                    self.on_update(update, now, discovered);
+                }
+            }
+        }
+    }
+
+    fn on_node(&mut self, record: NodeRecord, now: u64, discovered: &mut Vec<NodeRecord>) {
+        if let Some(node) = self.nodes.get_mut(&record.id) {
+            node.last_seen = now;
+        } else if !self.is_full() {
+            self.nodes.insert(record.id, CrawledNode::new(record, now));
+            discovered.push(record);
+        }
+    }
+
+    /// Returns true if the fork id of the node is not known yet.
+    fn needs_handshake(&self, peer_id: &PeerId) -> bool {
+        self.nodes.get(peer_id).is_some_and(|node| node.fork_id.is_none())
+    }
+
+    /// Records the outcome of a successful handshake with a node.
+    fn on_handshake(&mut self, peer_id: PeerId, client_version: String, fork_id: ForkId) {
+        if let Some(node) = self.nodes.get_mut(&peer_id) {
+            node.last_seen = unix_timestamp();
+            node.client_version = Some(client_version);
+            node.fork_id.get_or_insert(CrawledForkId::new(fork_id, ForkIdSource::Handshake));
+        }
+    }
+}
+
+/// A node discovered during a crawl.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CrawledNode {
+    id: PeerId,
+    enode: String,
+    ip: IpAddr,
+    tcp_port: u16,
+    udp_port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fork_id: Option<CrawledForkId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_version: Option<String>,
+    first_seen: u64,
+    last_seen: u64,
+}
+
+impl CrawledNode {
+    fn new(record: NodeRecord, now: u64) -> Self {
+        Self {
+            id: record.id,
+            enode: record.to_string(),
+            ip: record.address,
+            tcp_port: record.tcp_port,
+            udp_port: record.udp_port,
+            fork_id: None,
+            client_version: None,
+            first_seen: now,
+            last_seen: now,
+        }
+    }
+}
+
+/// The fork id of a discovered node.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CrawledForkId {
+    hash: String,
+    next: u64,
+    source: ForkIdSource,
+}
+
+impl CrawledForkId {
+    fn new(fork_id: ForkId, source: ForkIdSource) -> Self {
+        Self { hash: hex::encode_prefixed(fork_id.hash.0), next: fork_id.next, source }
+    }
+}
+
+/// Where the fork id of a node was obtained from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ForkIdSource {
+    /// The `eth` entry of the ENR of the node.
+    Enr,
+    /// The status message of the node.
+    Handshake,
+}
+
+/// Returns the number of nodes per fork id, most common first. Nodes without a known fork id are
+/// counted under `None`, which comes last among equally common fork ids.
+fn fork_distribution<'a>(
+    nodes: impl IntoIterator<Item = &'a CrawledNode>,
+) -> Vec<(Option<(&'a str, u64)>, usize)> {
+    let mut counts = HashMap::<_, usize>::new();
+    for node in nodes {
+        let fork_id = node.fork_id.as_ref().map(|fork_id| (fork_id.hash.as_str(), fork_id.next));
+        *counts.entry(fork_id).or_default() += 1;
+    }
+    let mut counts = counts.into_iter().collect::<Vec<_>>();
+    counts.sort_by_key(|(fork_id, count)| (Reverse(*count), fork_id.is_none(), *fork_id));
+    counts
+}
+
+fn print_summary(nodes: &[CrawledNode]) {
+    let count = |source| {
+        nodes.iter().filter(|node| node.fork_id.as_ref().map(|f| f.source) == Some(source)).count()
+    };
+    println!("Unique nodes:        {}", nodes.len());
+    println!("Fork id from ENR:    {}", count(ForkIdSource::Enr));
+    println!("Fork id from status: {}", count(ForkIdSource::Handshake));
+    println!("Fork distribution:");
+    for (fork_id, count) in fork_distribution(nodes) {
+        match fork_id {
+            Some((hash, next)) => println!("  {hash} (next: {next}): {count}"),
+            None => println!("  unknown: {count}"),
+        }
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::ForkHash;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    fn record(port: u16) -> NodeRecord {
+        NodeRecord::new(([10, 0, 0, 1], port).into(), PeerId::random())
+    }
+
+    #[test]
+    fn parse_crawl_command() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--duration",
+            "120",
+            "--output",
+            "peers.jsonl",
+            "--handshake",
+        ])
+        .unwrap();
+        assert_eq!(cmd.duration, Duration::from_secs(120));
+        assert_eq!(cmd.output, PathBuf::from("peers.jsonl"));
+        assert!(cmd.handshake);
+        assert_eq!(cmd.max_dials, 16);
+    }
+
+    #[test]
+    fn crawler_records_unique_nodes() {
+        let mut crawler = Crawler::new(2);
+        let (a, b, c) = (record(1), record(2), record(3));
+        let fork_id = ForkId { hash: ForkHash([1, 2, 3, 4]), next: 0 };
+
+        let mut discovered = Vec::new();
+        crawler.on_update(
+            DiscoveryUpdate::Batch(vec![
+                DiscoveryUpdate::Added(a),
+                DiscoveryUpdate::DiscoveredAtCapacity(a),
+                DiscoveryUpdate::EnrForkId(b, fork_id),
+            ]),
+            10,
+            &mut discovered,
+        );
+        assert_eq!(discovered, [a, b]);
+        assert!(crawler.is_full());
+
+        // The cap is reached, new nodes are ignored but known ones are still updated.
+        let mut discovered = Vec::new();
+        crawler.on_update(DiscoveryUpdate::Added(c), 20, &mut discovered);
+        crawler.on_update(DiscoveryUpdate::Added(a), 20, &mut discovered);
+        assert!(discovered.is_empty());
+        assert_eq!(crawler.nodes.len(), 2);
+        assert_eq!(crawler.nodes[&a.id].first_seen, 10);
+        assert_eq!(crawler.nodes[&a.id].last_seen, 20);
+
+        assert!(crawler.needs_handshake(&a.id));
+        assert!(!crawler.needs_handshake(&b.id));
+
+        // A fork id from the ENR takes precedence over the one from the handshake.
+        crawler.on_handshake(b.id, "reth".to_string(), ForkId { hash: ForkHash([0; 4]), next: 1 });
+        let node = &crawler.nodes[&b.id];
+        assert_eq!(node.client_version.as_deref(), Some("reth"));
+        let fork_id = node.fork_id.as_ref().unwrap();
+        assert_eq!((fork_id.hash.as_str(), fork_id.next), ("0x01020304", 0));
+        assert_eq!(fork_id.source, ForkIdSource::Enr);
+
+        let nodes = crawler.nodes.values().collect::<Vec<_>>();
+        assert_eq!(fork_distribution(nodes), [(Some(("0x01020304", 0)), 1), (None, 1)]);
+    }
+}
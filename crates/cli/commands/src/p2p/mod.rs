@@ -1,23 +1,47 @@
 //! P2P Debugging tool
 
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::common::CliNodeTypes;
+use alloy_consensus::BlockHeader;
 use alloy_eips::BlockHashOrNumber;
+use alloy_primitives::B256;
 use backon::{ConstantBuilder, Retryable};
 use clap::{Parser, Subcommand};
+use eyre::WrapErr;
+use futures::StreamExt;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_util::hash_or_num_value_parser;
 use reth_config::Config;
-use reth_network::{BlockDownloaderProvider, NetworkConfigBuilder};
-use reth_network_p2p::bodies::client::BodiesClient;
+use reth_consensus_common::validation::validate_body_against_header;
+use reth_network::{
+    events::PeerEvent,
+    types::{
+        EthVersion, GetBlockBodies, GetBlockHeaders, GetReceipts, GetReceipts70, HeadersDirection,
+        NetworkPrimitives,
+    },
+    BlockDownloaderProvider, NetworkConfigBuilder, NetworkEvent, NetworkEventListenerProvider,
+    PeerRequest, PeerRequestSender,
+};
+use reth_network_p2p::{bodies::client::BodiesClient, error::RequestResult};
+use reth_network_peers::{PeerId, TrustedPeer};
 use reth_node_core::{
     args::{DatadirArgs, NetworkArgs},
     utils::get_single_header,
 };
+use reth_primitives_traits::SealedHeader;
+use serde::Serialize;
+use tokio::sync::oneshot;
 
 pub mod bootnode;
+pub mod crawl;
+pub mod peers;
+pub mod probe;
 pub mod rlpx;
 
 /// `reth p2p` command
@@ -43,7 +67,10 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                 tracing::info!(target: "reth::cli", ?header, "Successfully downloaded header");
             }
 
-            Subcommands::Body { args, id } => {
+            Subcommands::Body { args, id, peer: Some(peer), receipts, timeout } => {
+                args.fetch_block_from_peer::<N>(peer, id, receipts, timeout).await?;
+            }
+            Subcommands::Body { args, id, peer: None, .. } => {
                 let handle = args.launch_network::<N>().await?;
                 let fetch_client = handle.fetch_client().await?;
                 let backoff = args.backoff();
@@ -85,6 +112,18 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             Subcommands::Bootnode(command) => {
                 command.execute().await?;
             }
+            Subcommands::Probe(command) => {
+                command.execute().await?;
+            }
+            Subcommands::Crawl(command) => {
+                command.execute().await?;
+            }
+            Subcommands::ExportPeers(command) => {
+                command.execute()?;
+            }
+            Subcommands::ImportPeers(command) => {
+                command.execute()?;
+            }
         }
 
         Ok(())
@@ -99,6 +138,10 @@ impl<C: ChainSpecParser> Command<C> {
             Subcommands::Body { args, .. } => Some(&args.chain),
             Subcommands::Rlpx(_) => None,
             Subcommands::Bootnode(_) => None,
+            Subcommands::Probe(command) => command.chain_spec(),
+            Subcommands::Crawl(command) => command.chain_spec(),
+            Subcommands::ExportPeers(command) => command.chain_spec(),
+            Subcommands::ImportPeers(command) => command.chain_spec(),
         }
     }
 }
@@ -121,11 +164,31 @@ pub enum Subcommands<C: ChainSpecParser> {
         /// The block number or hash
         #[arg(value_parser = hash_or_num_value_parser)]
         id: BlockHashOrNumber,
+        /// Only dial this peer and request the block from it directly.
+        ///
+        /// The header and body returned by the peer are checked against each other and printed
+        /// as JSON, together with the response times and the eth version of the peer.
+        #[arg(long, value_name = "ENODE")]
+        peer: Option<TrustedPeer>,
+        /// Also request the receipts of the block from the peer.
+        #[arg(long, requires = "peer")]
+        receipts: bool,
+        /// How long to wait for the session with the peer and for each of its responses.
+        #[arg(long, value_parser = humantime::parse_duration, default_value = "30s")]
+        timeout: Duration,
     },
     // RLPx utilities
     Rlpx(rlpx::Command),
     /// Bootnode command
     Bootnode(bootnode::Command),
+    /// Ping a node over discv4 and report what it advertises in the `RLPx` handshake
+    Probe(probe::Command<C>),
+    /// Crawl the network over discv4 and write the discovered nodes to a file
+    Crawl(crawl::Command<C>),
+    /// Export the known and banned peers of the node to a file
+    ExportPeers(peers::ExportCommand<C>),
+    /// Import peers from a file into the peers file of the node
+    ImportPeers(peers::ImportCommand<C>),
 }
 
 #[derive(Debug, Clone, Parser)]
@@ -207,6 +270,222 @@ impl<C: ChainSpecParser> DownloadArgs<C> {
     pub fn backoff(&self) -> ConstantBuilder {
         ConstantBuilder::default().with_max_times(self.retries.max(1))
     }
+
+    /// Dials only the given peer and requests the header, body and optionally the receipts of the
+    /// block from its session, then prints them as JSON.
+    async fn fetch_block_from_peer<N>(
+        &self,
+        peer: TrustedPeer,
+        id: BlockHashOrNumber,
+        with_receipts: bool,
+        timeout: Duration,
+    ) -> eyre::Result<()>
+    where
+        C::ChainSpec: EthChainSpec + Hardforks + EthereumHardforks + Send + Sync + 'static,
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let peer_id = peer.id;
+        let mut args = self.clone();
+        args.network.trusted_peers = vec![peer];
+        args.network.trusted_only = true;
+        args.network.discovery.disable_discovery = true;
+
+        let handle = args.launch_network::<N>().await?;
+        let mut events = handle.event_listener();
+
+        tracing::info!(target: "reth::cli", %peer_id, "Waiting for a session with the peer");
+        let (info, messages) = tokio::time::timeout(timeout, async {
+            while let Some(event) = events.next().await {
+                match event {
+                    NetworkEvent::ActivePeerSession { info, messages }
+                        if info.peer_id == peer_id =>
+                    {
+                        return Ok((info, messages))
+                    }
+                    NetworkEvent::Peer(PeerEvent::SessionClosed { peer_id: closed, reason })
+                        if closed == peer_id =>
+                    {
+                        eyre::bail!("Peer {peer_id} closed the session: {reason:?}")
+                    }
+                    _ => {}
+                }
+            }
+            eyre::bail!("Network stopped before a session with peer {peer_id} was established")
+        })
+        .await
+        .map_err(|_| {
+            eyre::eyre!(
+                "Timed out after {} waiting for a session with peer {peer_id}",
+                humantime::format_duration(timeout)
+            )
+        })??;
+        tracing::info!(
+            target: "reth::cli",
+            client_version = %info.client_version,
+            version = %info.version,
+            "Established session with the peer"
+        );
+
+        let (headers, header_time) = request_from_peer(&messages, "header", timeout, |response| {
+            PeerRequest::GetBlockHeaders {
+                request: GetBlockHeaders {
+                    start_block: id,
+                    limit: 1,
+                    skip: 0,
+                    direction: HeadersDirection::Rising,
+                },
+                response,
+            }
+        })
+        .await?;
+        let header = SealedHeader::seal_slow(
+            headers.0.into_iter().next().ok_or_else(|| eyre::eyre!("Peer returned no header"))?,
+        );
+        let requested = match id {
+            BlockHashOrNumber::Hash(hash) => header.hash() == hash,
+            BlockHashOrNumber::Number(number) => header.number() == number,
+        };
+        if !requested {
+            eyre::bail!(
+                "Peer returned header of block {} ({}) instead of {id}",
+                header.number(),
+                header.hash()
+            )
+        }
+
+        let hash = header.hash();
+        let (bodies, body_time) = request_from_peer(&messages, "body", timeout, |response| {
+            PeerRequest::GetBlockBodies { request: GetBlockBodies(vec![hash]), response }
+        })
+        .await?;
+        let body =
+            bodies.0.into_iter().next().ok_or_else(|| eyre::eyre!("Peer returned no body"))?;
+        validate_body_against_header(&body, header.header())
+            .wrap_err_with(|| format!("Peer returned a body that doesn't match block {hash}"))?;
+
+        let (receipts, receipts_time) = if with_receipts {
+            let (receipts, elapsed) =
+                request_receipts(&messages, info.version, hash, timeout).await?;
+            (Some(receipts), Some(elapsed))
+        } else {
+            (None, None)
+        };
+
+        let block = PeerBlock {
+            peer_id,
+            client_version: info.client_version.to_string(),
+            eth_version: info.version as u8,
+            hash,
+            number: header.number(),
+            header_time_ms: header_time.as_millis(),
+            body_time_ms: body_time.as_millis(),
+            receipts_time_ms: receipts_time.map(|elapsed| elapsed.as_millis()),
+            header: header.header(),
+            body,
+            receipts,
+        };
+        println!("{}", serde_json::to_string_pretty(&block)?);
+
+        Ok(())
+    }
+}
+
+/// A block fetched from a single peer by `reth p2p body --peer`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PeerBlock<'a, H, B, R> {
+    peer_id: PeerId,
+    client_version: String,
+    eth_version: u8,
+    hash: B256,
+    number: u64,
+    header_time_ms: u128,
+    body_time_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receipts_time_ms: Option<u128>,
+    header: &'a H,
+    body: B,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receipts: Option<Vec<R>>,
+}
+
+/// Sends a request to the session of a peer and waits for the response, returning it together
+/// with the time it took the peer to respond.
+async fn request_from_peer<N: NetworkPrimitives, T>(
+    messages: &PeerRequestSender<PeerRequest<N>>,
+    kind: &str,
+    timeout: Duration,
+    request: impl FnOnce(oneshot::Sender<RequestResult<T>>) -> PeerRequest<N>,
+) -> eyre::Result<(T, Duration)> {
+    let peer_id = messages.peer_id;
+    let (tx, rx) = oneshot::channel();
+    let start = Instant::now();
+    messages
+        .try_send(request(tx))
+        .map_err(|_| eyre::eyre!("Failed to send the {kind} request to peer {peer_id}"))?;
+    let response = tokio::time::timeout(timeout, rx)
+        .await
+        .map_err(|_| {
+            eyre::eyre!(
+                "Peer {peer_id} did not respond to the {kind} request within {}",
+                humantime::format_duration(timeout)
+            )
+        })?
+        .map_err(|_| eyre::eyre!("Session with peer {peer_id} closed during the {kind} request"))?
+        .wrap_err_with(|| format!("The {kind} request to peer {peer_id} failed"))?;
+    Ok((response, start.elapsed()))
+}
+
+/// Requests the receipts of a block with the message matching the eth version of the session.
+async fn request_receipts<N: NetworkPrimitives>(
+    messages: &PeerRequestSender<PeerRequest<N>>,
+    version: EthVersion,
+    hash: B256,
+    timeout: Duration,
+) -> eyre::Result<(Vec<N::Receipt>, Duration)> {
+    let (receipts, elapsed) = match version {
+        EthVersion::Eth66 | EthVersion::Eth67 | EthVersion::Eth68 => {
+            let (receipts, elapsed) =
+                request_from_peer(messages, "receipts", timeout, |response| {
+                    PeerRequest::GetReceipts { request: GetReceipts(vec![hash]), response }
+                })
+                .await?;
+            let receipts = receipts
+                .0
+                .into_iter()
+                .map(|block| block.into_iter().map(|receipt| receipt.receipt).collect())
+                .collect::<Vec<Vec<_>>>();
+            (receipts, elapsed)
+        }
+        EthVersion::Eth69 => {
+            let (receipts, elapsed) =
+                request_from_peer(messages, "receipts", timeout, |response| {
+                    PeerRequest::GetReceipts69 { request: GetReceipts(vec![hash]), response }
+                })
+                .await?;
+            (receipts.0, elapsed)
+        }
+        EthVersion::Eth70 => {
+            let (receipts, elapsed) =
+                request_from_peer(messages, "receipts", timeout, |response| {
+                    PeerRequest::GetReceipts70 {
+                        request: GetReceipts70 {
+                            first_block_receipt_index: 0,
+                            block_hashes: vec![hash],
+                        },
+                        response,
+                    }
+                })
+                .await?;
+            if receipts.last_block_incomplete {
+                tracing::warn!(target: "reth::cli", "Peer returned an incomplete list of receipts");
+            }
+            (receipts.receipts, elapsed)
+        }
+    };
+    let receipts =
+        receipts.into_iter().next().ok_or_else(|| eyre::eyre!("Peer returned no receipts"))?;
+    Ok((receipts, elapsed))
 }
 
 #[cfg(test)]
@@ -225,4 +504,33 @@ mod tests {
         let _args: Command<EthereumChainSpecParser> =
             Command::parse_from(["reth", "body", "--chain", "mainnet", "1000"]);
     }
+
+    #[test]
+    fn parse_body_from_peer_cmd() {
+        let enode = "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303";
+        let args: Command<EthereumChainSpecParser> = Command::parse_from([
+            "reth",
+            "body",
+            "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+            "--peer",
+            enode,
+            "--receipts",
+            "--timeout",
+            "5s",
+        ]);
+        let Subcommands::Body { peer, receipts, timeout, .. } = args.command else {
+            panic!("expected body command")
+        };
+        assert_eq!(peer, Some(enode.parse().unwrap()));
+        assert!(receipts);
+        assert_eq!(timeout, Duration::from_secs(5));
+
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "body",
+            "1000",
+            "--receipts"
+        ])
+        .is_err());
+    }
 }
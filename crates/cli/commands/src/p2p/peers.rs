@@ -0,0 +1,147 @@
+//! Export and import subcommands of P2P Debugging tool, for moving the peers file of a node.
+
+use clap::Parser;
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::lockfile::StorageLock;
+use reth_network::{
+    peers::write_peers_file,
+    types::{peers::persisted::unix_timestamp, PersistedPeers},
+};
+use reth_node_core::args::DatadirArgs;
+use std::{path::PathBuf, sync::Arc, time::SystemTime};
+
+/// The arguments that locate the peers file of a node.
+#[derive(Debug, Parser)]
+pub struct PeersFileArgs<C: ChainSpecParser> {
+    #[command(flatten)]
+    datadir: DatadirArgs,
+
+    /// The path to the known peers file, instead of the one in the data dir.
+    #[arg(long, value_name = "FILE")]
+    peers_file: Option<PathBuf>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::default_value(),
+        value_parser = C::parser()
+    )]
+    chain: Arc<C::ChainSpec>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec>> PeersFileArgs<C> {
+    fn peers_file(&self) -> PathBuf {
+        self.peers_file.clone().unwrap_or_else(|| {
+            self.datadir.clone().resolve_datadir(self.chain.chain()).known_peers()
+        })
+    }
+}
+
+/// `reth p2p export-peers` command
+///
+/// Writes the known peers of the node, with their reputation, and the bans that didn't expire to a
+/// file, which `reth p2p import-peers` reads on another machine.
+#[derive(Debug, Parser)]
+pub struct ExportCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    args: PeersFileArgs<C>,
+
+    /// The file to write the peers to.
+    #[arg(long, value_name = "FILE")]
+    output: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec>> ExportCommand<C> {
+    /// Execute `p2p export-peers` command
+    pub fn execute(self) -> eyre::Result<()> {
+        let peers_file = self.args.peers_file();
+        let Some(mut peers) = PersistedPeers::read_from_file(&peers_file)? else {
+            eyre::bail!("No peers file at {}", peers_file.display())
+        };
+        peers.remove_expired_bans(unix_timestamp(SystemTime::now()));
+        write_peers_file(&self.output, &peers)?;
+
+        println!(
+            "Exported {} peers and {} bans from {} to {}",
+            peers.peers.len(),
+            peers.banned.len(),
+            peers_file.display(),
+            self.output.display()
+        );
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> ExportCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.args.chain)
+    }
+}
+
+/// `reth p2p import-peers` command
+///
+/// Merges the peers of a file written by `reth p2p export-peers`, or a list of enodes, into the
+/// peers file of the node, which seeds the peer set on the next start.
+///
+/// The node must not be running, since it overwrites the peers file when it saves its peers.
+#[derive(Debug, Parser)]
+pub struct ImportCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    args: PeersFileArgs<C>,
+
+    /// The file to read the peers from.
+    path: PathBuf,
+
+    /// Replace the peers file instead of merging the peers into it.
+    ///
+    /// When merging, the entry that was seen last is kept for peers that are in both files, and
+    /// bans are kept until the later expiry.
+    #[arg(long)]
+    replace: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec>> ImportCommand<C> {
+    /// Execute `p2p import-peers` command
+    pub fn execute(self) -> eyre::Result<()> {
+        let Some(imported) = PersistedPeers::read_from_file(&self.path)? else {
+            eyre::bail!("No peers file at {}", self.path.display())
+        };
+
+        // Keep the node from starting, and fail if it's running
+        let db_path = self.args.datadir.clone().resolve_datadir(self.args.chain.chain()).db();
+        let _lock = db_path.exists().then(|| StorageLock::try_acquire(&db_path)).transpose()?;
+
+        let peers_file = self.args.peers_file();
+        let mut peers = if self.replace {
+            PersistedPeers::default()
+        } else {
+            PersistedPeers::read_from_file(&peers_file)?.unwrap_or_default()
+        };
+        let (num_peers, num_banned) = (imported.peers.len(), imported.banned.len());
+        peers.merge(imported);
+        peers.remove_expired_bans(unix_timestamp(SystemTime::now()));
+        write_peers_file(&peers_file, &peers)?;
+
+        println!(
+            "Imported {num_peers} peers and {num_banned} bans into {}, which has {} peers and {} \
+             bans",
+            peers_file.display(),
+            peers.peers.len(),
+            peers.banned.len()
+        );
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> ImportCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.args.chain)
+    }
+}
@@ -0,0 +1,342 @@
+//! Probe subcommand of P2P Debugging tool.
+
+use alloy_primitives::{hex, B256, U256};
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::{EthChainSpec, Hardforks, Head};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_discv4::proto::{Message, NodeEndpoint, Ping};
+use reth_ecies::stream::ECIESStream;
+use reth_eth_wire::{
+    CanDisconnect, DisconnectReason, EthNetworkPrimitives, HelloMessage, UnauthedEthStream,
+    UnauthedP2PStream, UnifiedStatus,
+};
+use reth_network::config::rng_secret_key;
+use reth_network_peers::{pk2id, AnyNode, NodeRecord, PeerId};
+use secp256k1::{SecretKey, SECP256K1};
+use serde::Serialize;
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::net::{TcpStream, UdpSocket};
+
+/// The maximum size of a discv4 packet.
+const MAX_PACKET_SIZE: usize = 1280;
+
+/// How long the discv4 ping sent to the node stays valid.
+const PING_EXPIRATION: Duration = Duration::from_secs(20);
+
+/// `reth p2p probe` command
+///
+/// Pings a node over discv4, then performs the `RLPx` and eth handshakes with it and reports what
+/// the node advertised.
+#[derive(Parser, Debug)]
+pub struct Command<C: ChainSpecParser> {
+    /// The enode or ENR of the node to probe.
+    node: AnyNode,
+
+    /// The number of times to probe the node.
+    ///
+    /// The minimum, average and maximum latencies of all probes are reported.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    count: u64,
+
+    /// How long to wait for the discv4 pong and for the `RLPx` handshake.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "5s")]
+    timeout: Duration,
+
+    /// Print the result as JSON.
+    #[arg(long)]
+    json: bool,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::default_value(),
+        value_parser = C::parser()
+    )]
+    chain: Arc<C::ChainSpec>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks>> Command<C> {
+    /// Execute `p2p probe` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let record = self
+            .node
+            .node_record()
+            .ok_or_else(|| eyre::eyre!("failed to parse node {}", self.node))?;
+        let key = rng_secret_key();
+
+        let mut pings = Vec::new();
+        let mut handshakes = Vec::new();
+        let mut node = None;
+        for _ in 0..self.count {
+            match ping(&key, record, self.timeout).await {
+                Ok(latency) => pings.push(latency),
+                Err(err) => tracing::warn!(target: "reth::cli", %err, "Discv4 ping failed"),
+            }
+
+            let start = Instant::now();
+            let (hello, status) =
+                tokio::time::timeout(self.timeout, handshake(&key, record, &*self.chain))
+                    .await
+                    .map_err(|_| {
+                        eyre::eyre!(
+                            "Timed out after {} during the RLPx handshake with {}",
+                            humantime::format_duration(self.timeout),
+                            record.id
+                        )
+                    })?
+                    .wrap_err_with(|| format!("RLPx handshake with {} failed", record.id))?;
+            handshakes.push(start.elapsed());
+            node = Some(NodeInfo::new(record.id, hello, status));
+        }
+
+        let report = ProbeReport {
+            node: node.expect("node is probed at least once"),
+            probes: self.count,
+            pings_lost: self.count - pings.len() as u64,
+            ping_latency: Latency::from_samples(&pings),
+            handshake_latency: Latency::from_samples(&handshakes),
+        };
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            report.print();
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.chain)
+    }
+}
+
+/// Sends a discv4 ping to the node and returns the time it took to receive the matching pong.
+async fn ping(key: &SecretKey, record: NodeRecord, timeout: Duration) -> eyre::Result<Duration> {
+    let local_ip = if record.address.is_ipv4() {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+    };
+    let socket = UdpSocket::bind(SocketAddr::new(local_ip, 0)).await?;
+    let local_addr = socket.local_addr()?;
+
+    let ping = Ping {
+        from: NodeEndpoint {
+            address: local_addr.ip(),
+            udp_port: local_addr.port(),
+            tcp_port: local_addr.port(),
+        },
+        to: record.into(),
+        expire: (SystemTime::now().duration_since(UNIX_EPOCH)? + PING_EXPIRATION).as_secs(),
+        enr_sq: None,
+    };
+    let (packet, hash) = Message::Ping(ping).encode(key);
+
+    let start = Instant::now();
+    socket.send_to(&packet, record.udp_addr()).await?;
+    tokio::time::timeout(timeout, async {
+        let mut buf = [0; MAX_PACKET_SIZE];
+        loop {
+            let (len, _) = socket.recv_from(&mut buf).await?;
+            // The node may also ping back to verify our endpoint, which is ignored.
+            if let Ok(packet) = Message::decode(&buf[..len]) &&
+                packet.node_id == record.id &&
+                let Message::Pong(pong) = packet.msg &&
+                pong.echo == hash
+            {
+                return Ok(start.elapsed())
+            }
+        }
+    })
+    .await
+    .map_err(|_| eyre::eyre!("No pong within {}", humantime::format_duration(timeout)))?
+}
+
+/// Performs the `RLPx` and eth handshakes with the node and returns its hello and status messages.
+pub(super) async fn handshake<Spec: EthChainSpec + Hardforks>(
+    key: &SecretKey,
+    record: NodeRecord,
+    chain_spec: &Spec,
+) -> eyre::Result<(HelloMessage, UnifiedStatus)> {
+    let outgoing = TcpStream::connect(record.tcp_addr()).await?;
+    let ecies_stream = ECIESStream::connect(outgoing, *key, record.id).await?;
+
+    let hello = HelloMessage::builder(pk2id(&key.public_key(SECP256K1))).build();
+    let (p2p_stream, their_hello) = UnauthedP2PStream::new(ecies_stream).handshake(hello).await?;
+    let eth_version = p2p_stream.shared_capabilities().eth_version()?;
+
+    // Advertise the genesis block as our head, which any synced node of the chain accepts.
+    let genesis = chain_spec.genesis();
+    let head = Head {
+        hash: chain_spec.genesis_hash(),
+        number: 0,
+        timestamp: genesis.timestamp,
+        difficulty: genesis.difficulty,
+        total_difficulty: genesis.difficulty,
+    };
+    let mut status = UnifiedStatus::spec_builder(chain_spec, &head);
+    status.set_eth_version(eth_version);
+
+    let (mut eth_stream, their_status) = UnauthedEthStream::new(p2p_stream)
+        .handshake::<EthNetworkPrimitives>(status, chain_spec.fork_filter(head))
+        .await?;
+    let _ = eth_stream.disconnect(DisconnectReason::ClientQuitting).await;
+
+    Ok((their_hello, their_status))
+}
+
+/// The result of probing a node.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProbeReport {
+    #[serde(flatten)]
+    node: NodeInfo,
+    probes: u64,
+    pings_lost: u64,
+    ping_latency: Option<Latency>,
+    handshake_latency: Option<Latency>,
+}
+
+impl ProbeReport {
+    /// Prints the report in a human-readable form.
+    fn print(&self) {
+        let node = &self.node;
+        println!("Node:               {}", node.id);
+        println!("Client:             {}", node.client_version);
+        println!("Capabilities:       {}", node.capabilities.join(", "));
+        println!("Eth version:        eth/{}", node.eth_version);
+        println!("Network id:         {}", node.network_id);
+        println!("Genesis:            {}", node.genesis);
+        println!("Fork id:            {} (next: {})", node.fork_hash, node.fork_next);
+        println!("Head:               {}", node.head);
+        if let Some(total_difficulty) = node.total_difficulty {
+            println!("Total difficulty:   {total_difficulty}");
+        }
+        if let Some(latest_block) = node.latest_block {
+            println!("Latest block:       {latest_block}");
+        }
+        match &self.ping_latency {
+            Some(latency) => println!(
+                "Ping latency:       {latency} ({} of {} lost)",
+                self.pings_lost, self.probes
+            ),
+            None => println!("Ping latency:       no pong received"),
+        }
+        if let Some(latency) = &self.handshake_latency {
+            println!("Handshake latency:  {latency}");
+        }
+    }
+}
+
+/// What a node advertised in its `RLPx` hello and eth status messages.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NodeInfo {
+    id: PeerId,
+    client_version: String,
+    capabilities: Vec<String>,
+    eth_version: u8,
+    network_id: u64,
+    genesis: B256,
+    fork_hash: String,
+    fork_next: u64,
+    head: B256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_difficulty: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latest_block: Option<u64>,
+}
+
+impl NodeInfo {
+    fn new(id: PeerId, hello: HelloMessage, status: UnifiedStatus) -> Self {
+        Self {
+            id,
+            client_version: hello.client_version,
+            capabilities: hello.capabilities.iter().map(ToString::to_string).collect(),
+            eth_version: status.version as u8,
+            network_id: status.chain.id(),
+            genesis: status.genesis,
+            fork_hash: hex::encode_prefixed(status.forkid.hash.0),
+            fork_next: status.forkid.next,
+            head: status.blockhash,
+            total_difficulty: status.total_difficulty,
+            latest_block: status.latest_block,
+        }
+    }
+}
+
+/// Minimum, average and maximum of a set of latencies, in milliseconds.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Latency {
+    min_ms: f64,
+    avg_ms: f64,
+    max_ms: f64,
+}
+
+impl Latency {
+    /// Returns the latency statistics of the samples, or `None` if there are none.
+    fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let min = samples.iter().min()?;
+        let max = samples.iter().max()?;
+        let avg = samples.iter().sum::<Duration>() / samples.len() as u32;
+        Some(Self { min_ms: millis(*min), avg_ms: millis(avg), max_ms: millis(*max) })
+    }
+}
+
+impl std::fmt::Display for Latency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "min {:.2}ms, avg {:.2}ms, max {:.2}ms", self.min_ms, self.avg_ms, self.max_ms)
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_probe_command() {
+        let enode = "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303";
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth", enode, "--count", "3", "--json",
+        ])
+        .unwrap();
+        assert_eq!(cmd.count, 3);
+        assert!(cmd.json);
+        assert_eq!(cmd.timeout, Duration::from_secs(5));
+
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth", enode, "--count", "0"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn latency_stats() {
+        assert_eq!(Latency::from_samples(&[]), None);
+
+        let samples = [10, 30, 20].map(Duration::from_millis);
+        assert_eq!(
+            Latency::from_samples(&samples),
+            Some(Latency { min_ms: 10.0, avg_ms: 20.0, max_ms: 30.0 })
+        );
+    }
+}
@@ -3,7 +3,10 @@ use crate::common::{AccessRights, CliNodeTypes, EnvironmentArgs};
 use clap::Parser;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
-use reth_prune::PrunerBuilder;
+use reth_node_core::args::PruningArgs;
+use reth_provider::{PruneCheckpointReader, StageCheckpointReader};
+use reth_prune::{PruneMode, PruneModes, PrunePurpose, PruneSegment, PrunerBuilder};
+use reth_stages::StageId;
 use reth_static_file::StaticFileProducer;
 use std::sync::Arc;
 use tracing::info;
@@ -13,14 +16,61 @@ use tracing::info;
 pub struct PruneCommand<C: ChainSpecParser> {
     #[command(flatten)]
     env: EnvironmentArgs<C>,
+
+    /// Prune configuration, taking precedence over the configuration file.
+    #[command(flatten)]
+    pruning: PruningArgs,
+
+    /// Report the block ranges that would be pruned per segment without deleting any data.
+    #[arg(long)]
+    dry_run: bool,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneCommand<C> {
     /// Execute the `prune` command
     pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
-        let env = self.env.init::<N>(AccessRights::RW)?;
+        // Opening the database read-write acquires the storage lock, so this fails if another
+        // process is using the datadir.
+        let access_rights = if self.dry_run { AccessRights::RO } else { AccessRights::RW };
+        let env = self.env.init::<N>(access_rights)?;
         let provider_factory = env.provider_factory;
-        let config = env.config.prune;
+        let config = match self.pruning.prune_config(&*self.env.chain) {
+            Some(mut config) => {
+                config.merge(env.config.prune);
+                config
+            }
+            None => env.config.prune,
+        };
+
+        if self.dry_run {
+            let provider = provider_factory.provider()?;
+            let prune_tip =
+                provider.get_stage_checkpoint(StageId::Execution)?.map(|c| c.block_number);
+            info!(target: "reth::cli", ?prune_tip, ?config, "Dry run, no data will be deleted");
+            let Some(prune_tip) = prune_tip else { return Ok(()) };
+
+            for (segment, mode) in prune_segments(&config.segments) {
+                let Some((to_block, _)) =
+                    mode.prune_target_block(prune_tip, segment, PrunePurpose::User)?
+                else {
+                    info!(target: "reth::cli", ?segment, ?mode, "Nothing to prune");
+                    continue
+                };
+                let from_block = provider
+                    .get_prune_checkpoint(segment)?
+                    .and_then(|checkpoint| checkpoint.block_number)
+                    .map_or(0, |block| block + 1);
+                if from_block > to_block {
+                    info!(target: "reth::cli", ?segment, ?mode, "Nothing to prune");
+                } else {
+                    info!(target: "reth::cli", ?segment, ?mode, from_block, to_block, "Would prune blocks");
+                }
+            }
+            if !config.segments.receipts_log_filter.is_empty() {
+                info!(target: "reth::cli", segment = ?PruneSegment::ContractLogs, "Receipts are pruned by the log filter, not estimated");
+            }
+            return Ok(())
+        }
 
         // Copy data from database to static files
         info!(target: "reth::cli", "Copying data from database to static files...");
@@ -38,7 +88,21 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneComma
                 .delete_limit(usize::MAX)
                 .build_with_provider_factory(provider_factory);
 
-            pruner.run(prune_tip)?;
+            loop {
+                let output = pruner.run(prune_tip)?;
+                for (segment, segment_output) in &output.segments {
+                    info!(
+                        target: "reth::cli",
+                        ?segment,
+                        pruned = segment_output.pruned,
+                        progress = ?segment_output.progress,
+                        "Pruned segment"
+                    );
+                }
+                if output.progress.is_finished() {
+                    break
+                }
+            }
             info!(target: "reth::cli", "Pruned data from database");
         }
 
@@ -52,3 +116,49 @@ impl<C: ChainSpecParser> PruneCommand<C> {
         Some(&self.env.chain)
     }
 }
+
+/// Returns the configured prune mode of every segment, in the order the pruner runs them.
+///
+/// Receipts pruned by the log filter are not included.
+fn prune_segments(modes: &PruneModes) -> Vec<(PruneSegment, PruneMode)> {
+    [
+        (PruneSegment::Bodies, modes.bodies_history),
+        (PruneSegment::MerkleChangeSets, Some(modes.merkle_changesets)),
+        (PruneSegment::AccountHistory, modes.account_history),
+        (PruneSegment::StorageHistory, modes.storage_history),
+        (PruneSegment::Receipts, modes.receipts),
+        (PruneSegment::TransactionLookup, modes.transaction_lookup),
+        (PruneSegment::SenderRecovery, modes.sender_recovery),
+    ]
+    .into_iter()
+    .filter_map(|(segment, mode)| Some((segment, mode?)))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_prune_args() {
+        let cmd = PruneCommand::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--prune.receipts.before",
+            "100",
+            "--dry-run",
+        ])
+        .unwrap();
+        assert!(cmd.dry_run);
+
+        let config = cmd.pruning.prune_config(&*cmd.env.chain).unwrap();
+        assert_eq!(config.segments.receipts, Some(PruneMode::Before(100)));
+        assert_eq!(
+            prune_segments(&config.segments),
+            [
+                (PruneSegment::MerkleChangeSets, config.segments.merkle_changesets),
+                (PruneSegment::Receipts, PruneMode::Before(100)),
+            ]
+        );
+    }
+}
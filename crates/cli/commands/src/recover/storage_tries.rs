@@ -0,0 +1,136 @@
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::BlockHeader;
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_provider::{
+    DBProvider, DatabaseProviderFactory, HeaderProvider, StageCheckpointReader,
+    StageCheckpointWriter,
+};
+use reth_stages::{
+    stages::{AccountHashingStage, MerkleStage, StorageHashingStage},
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageId,
+};
+use reth_trie::StateRoot;
+use reth_trie_db::DatabaseStateRoot;
+use std::sync::Arc;
+use tracing::info;
+
+/// `reth recover storage-tries` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// Re-run the hashing and merkle stages from this block instead of rebuilding the hashed
+    /// state and trie tables from scratch.
+    ///
+    /// The tables are expected to be valid up to this block.
+    #[arg(long, value_name = "BLOCK")]
+    from_block: Option<u64>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `recover storage-tries` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
+
+        let mut provider_rw = provider_factory.database_provider_rw()?;
+        let tip = provider_rw.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default();
+        let tip = tip.block_number;
+
+        let from_block = match self.from_block {
+            Some(from_block) if from_block > tip => {
+                eyre::bail!("Block {from_block} is above the execution checkpoint {tip}")
+            }
+            Some(from_block) => from_block,
+            None => {
+                info!(target: "reth::cli", "Clearing hashed state and trie tables");
+                let tx = provider_rw.tx_ref();
+                tx.clear::<tables::HashedAccounts>()?;
+                tx.clear::<tables::HashedStorages>()?;
+                tx.clear::<tables::AccountsTrie>()?;
+                tx.clear::<tables::StoragesTrie>()?;
+                tx.delete::<tables::StageCheckpointProgresses>(
+                    StageId::MerkleExecute.to_string(),
+                    None,
+                )?;
+                0
+            }
+        };
+
+        // The unwind checkpoint is reset as well, otherwise the pipeline would consider the
+        // merkle stage to be in the middle of an unwind.
+        for stage_id in [
+            StageId::AccountHashing,
+            StageId::StorageHashing,
+            StageId::MerkleExecute,
+            StageId::MerkleUnwind,
+        ] {
+            provider_rw.save_stage_checkpoint(stage_id, StageCheckpoint::new(from_block))?;
+        }
+        provider_rw.commit()?;
+        provider_rw = provider_factory.database_provider_rw()?;
+
+        let etl_config = config.stages.etl.clone();
+        let stages: [Box<dyn Stage<_>>; 3] = [
+            Box::new(AccountHashingStage::new(config.stages.account_hashing, etl_config.clone())),
+            Box::new(StorageHashingStage::new(config.stages.storage_hashing, etl_config)),
+            Box::new(MerkleStage::new_execution(
+                config.stages.merkle.rebuild_threshold,
+                config.stages.merkle.incremental_threshold,
+            )),
+        ];
+
+        for mut stage in stages {
+            let stage_id = stage.id();
+            info!(target: "reth::cli", stage = %stage_id, from_block, to_block = tip, "Running stage");
+
+            let mut input =
+                ExecInput { target: Some(tip), checkpoint: Some(StageCheckpoint::new(from_block)) };
+            loop {
+                let ExecOutput { checkpoint, done } = stage.execute(&provider_rw, input)?;
+                provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
+                provider_rw.commit()?;
+                provider_rw = provider_factory.database_provider_rw()?;
+
+                if let Some(entities) = checkpoint.entities() {
+                    info!(
+                        target: "reth::cli",
+                        stage = %stage_id,
+                        processed = entities.processed,
+                        total = entities.total,
+                        "Processed chunk"
+                    );
+                }
+
+                input.checkpoint = Some(checkpoint);
+                if done {
+                    break
+                }
+            }
+        }
+
+        let header = provider_rw
+            .header_by_number(tip)?
+            .ok_or_else(|| eyre::eyre!("Header for block {tip} not found"))?;
+        let state_root = StateRoot::from_tx(provider_rw.tx_ref()).root()?;
+        if state_root != header.state_root() {
+            eyre::bail!(
+                "State root mismatch at block {tip}: computed {state_root}, expected {}",
+                header.state_root()
+            )
+        }
+        info!(target: "reth::cli", block = tip, %state_root, "Recovered hashed state and tries");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
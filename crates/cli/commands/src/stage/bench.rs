@@ -0,0 +1,503 @@
+//! `reth stage bench` command
+//!
+//! Benchmarks a stage over a block range, to compare the performance of different builds.
+
+use crate::common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::BlockHeader;
+use clap::Parser;
+use eyre::{bail, WrapErr};
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_config::{
+    config::{HashingConfig, SenderRecoveryConfig, TransactionLookupConfig},
+    Config,
+};
+use reth_db::DatabaseEnv;
+use reth_db_api::Database;
+use reth_exex::ExExManagerHandle;
+use reth_node_builder::NodeTypesWithDBAdapter;
+use reth_node_core::{
+    args::{DatadirArgs, StageEnum},
+    version::version_metadata,
+};
+use reth_provider::{
+    ChainSpecProvider, DBProvider, DatabaseProvider, DatabaseProviderFactory, HeaderProvider,
+    StageCheckpointReader, StageCheckpointWriter,
+};
+use reth_stages::{
+    stages::{
+        AccountHashingStage, ExecutionStage, IndexAccountHistoryStage, IndexStorageHistoryStage,
+        MerkleStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
+    },
+    ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt, UnwindInput, UnwindOutput,
+};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::*;
+
+/// The file in a snapshot directory that marks it as created by `reth stage bench`, so that an
+/// existing directory is only replaced if it's a snapshot.
+const SNAPSHOT_MARKER: &str = ".stage-bench-snapshot";
+
+/// The read-write provider that the benchmarked stages run with.
+type BenchProvider<N> = DatabaseProvider<
+    <Arc<DatabaseEnv> as Database>::TXMut,
+    NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>,
+>;
+
+/// A stage that runs with [`BenchProvider`].
+type BenchStage<N> = Box<dyn Stage<BenchProvider<N>>>;
+
+/// `reth stage bench` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The name of the stage to benchmark
+    #[arg(value_enum)]
+    stage: StageEnum,
+
+    /// The block that the stage starts executing after
+    #[arg(long)]
+    from: u64,
+
+    /// The last block that the stage executes
+    #[arg(long, short)]
+    to: u64,
+
+    /// The number of timed runs of the stage
+    #[arg(long, default_value_t = 5)]
+    iterations: usize,
+
+    /// The number of untimed runs before the timed runs, to warm up the page cache
+    #[arg(long, default_value_t = 1)]
+    warmup: usize,
+
+    /// Drop the page cache of the OS before every run, so that every run starts cold.
+    ///
+    /// Only supported on Linux, and requires root.
+    #[arg(long)]
+    drop_caches: bool,
+
+    /// The directory of the copy of the datadir that the stage runs against.
+    ///
+    /// The datadir is copied with `cp --reflink=auto`, which is instant on copy-on-write
+    /// filesystems and a full copy otherwise. Defaults to `<DATADIR>/stage-bench`.
+    #[arg(long, value_name = "DIR")]
+    snapshot_dir: Option<PathBuf>,
+
+    /// The JSON file that the result is added to, keyed by the stage and the range.
+    #[arg(long, value_name = "FILE", default_value = "stage-bench.json")]
+    output: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
+    /// Execute `stage bench` command
+    pub async fn execute<N, Comp, F>(self, components: F) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        Comp: CliNodeComponents<N>,
+        F: FnOnce(Arc<C::ChainSpec>) -> Comp,
+    {
+        match self.stage {
+            StageEnum::Headers | StageEnum::Bodies => {
+                bail!(
+                    "the {} stage downloads from the network and can't be benchmarked",
+                    self.stage
+                )
+            }
+            StageEnum::Hashing | StageEnum::MerkleChangeSets => {
+                bail!("the {} stage can't be benchmarked", self.stage)
+            }
+            _ => {}
+        }
+        if self.iterations == 0 {
+            bail!("`--iterations` must be at least 1")
+        }
+        if self.from >= self.to {
+            bail!("`--from` must be below `--to`")
+        }
+        if self.drop_caches && !cfg!(target_os = "linux") {
+            bail!("`--drop-caches` is only supported on Linux")
+        }
+
+        let _ = fdlimit::raise_fd_limit();
+
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
+        let snapshot =
+            self.snapshot_dir.clone().unwrap_or_else(|| data_dir.data_dir().join("stage-bench"));
+        create_snapshot(&data_dir.db(), &data_dir.static_files(), &data_dir.rocksdb(), &snapshot)?;
+
+        // The config of the datadir is used if there is one, instead of creating one in the
+        // snapshot
+        let config = self
+            .env
+            .config
+            .clone()
+            .or_else(|| Some(data_dir.config()).filter(|path| path.exists()));
+        let env = EnvironmentArgs::<C> {
+            datadir: DatadirArgs { datadir: snapshot.clone().into(), ..Default::default() },
+            config,
+            chain: self.env.chain.clone(),
+            db: self.env.db,
+            static_files: self.env.static_files,
+        };
+
+        let result = self.run::<N, _, _>(&env, components).await;
+        if let Err(err) = reth_fs_util::remove_dir_all(&snapshot) {
+            warn!(target: "reth::cli", %err, ?snapshot, "Failed to remove the snapshot");
+        }
+        let result = result?;
+
+        info!(
+            target: "reth::cli",
+            stage = %self.stage,
+            min = ?Duration::from_secs_f64(result.wall_time.min_secs),
+            median = ?Duration::from_secs_f64(result.wall_time.median_secs),
+            stddev = ?Duration::from_secs_f64(result.wall_time.stddev_secs),
+            blocks_per_second = result.blocks_per_second,
+            rows_per_second = ?result.rows_per_second,
+            gas_per_second = ?result.gas_per_second,
+            "Finished benchmark"
+        );
+
+        let mut results: BTreeMap<String, serde_json::Value> = if self.output.exists() {
+            serde_json::from_str(&reth_fs_util::read_to_string(&self.output)?)
+                .wrap_err_with(|| format!("failed to parse {}", self.output.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        results.insert(
+            format!("{}:{}-{}", self.stage, self.from, self.to),
+            serde_json::to_value(&result)?,
+        );
+        reth_fs_util::write(&self.output, serde_json::to_string_pretty(&results)?)?;
+        info!(target: "reth::cli", output = ?self.output, "Wrote benchmark result");
+
+        Ok(())
+    }
+
+    /// Unwinds the stage in the snapshot to `--from`, and runs it to `--to` in transactions that
+    /// are never committed.
+    async fn run<N, Comp, F>(
+        &self,
+        env: &EnvironmentArgs<C>,
+        components: F,
+    ) -> eyre::Result<BenchResult>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        Comp: CliNodeComponents<N>,
+        F: FnOnce(Arc<C::ChainSpec>) -> Comp,
+    {
+        let Environment { provider_factory, config, .. } = env.init::<N>(AccessRights::RW)?;
+        let components = components(provider_factory.chain_spec());
+
+        // The unwind is committed to the snapshot, so that every run starts from the same state
+        let provider_rw = provider_factory.database_provider_rw()?;
+        let (mut stage, mut unwind_stage) = self.build_stage::<N, _>(&components, &config);
+        let stage_id = stage.id();
+        let checkpoint = provider_rw.get_stage_checkpoint(stage.id())?.unwrap_or_default();
+        if checkpoint.block_number < self.from {
+            bail!(
+                "the {} stage checkpoint {} is below `--from`, run the stage up to block {} first",
+                self.stage,
+                checkpoint.block_number,
+                self.from
+            )
+        }
+        let unwind_stage = unwind_stage.as_mut().unwrap_or(&mut stage);
+        let mut unwind = UnwindInput { checkpoint, unwind_to: self.from, bad_block: None };
+        while unwind.checkpoint.block_number > self.from {
+            let UnwindOutput { checkpoint } = unwind_stage.unwind(&provider_rw, unwind)?;
+            unwind.checkpoint = checkpoint;
+        }
+        provider_rw.save_stage_checkpoint(stage_id, unwind.checkpoint)?;
+        let gas = provider_rw
+            .headers_range(self.from + 1..=self.to)?
+            .iter()
+            .map(|header| header.gas_used())
+            .sum::<u64>();
+        provider_rw.commit()?;
+        drop(provider_factory);
+
+        let mut samples = Vec::with_capacity(self.iterations);
+        let mut rows = None;
+        for iteration in 0..self.warmup + self.iterations {
+            if self.drop_caches {
+                drop_page_cache()?;
+            }
+
+            // The storage is opened for every run, to drop the uncommitted writes to static
+            // files of the previous run
+            let Environment { provider_factory, config, .. } = env.init::<N>(AccessRights::RW)?;
+            let provider_rw = provider_factory.database_provider_rw()?;
+            let (mut stage, _) = self.build_stage::<N, _>(&components, &config);
+            let start_checkpoint = provider_rw.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+            let mut input = ExecInput { target: Some(self.to), checkpoint: Some(start_checkpoint) };
+
+            let start = Instant::now();
+            loop {
+                stage.execute_ready(input).await?;
+                let ExecOutput { checkpoint, done } = stage.execute(&provider_rw, input)?;
+                input.checkpoint = Some(checkpoint);
+                if done {
+                    break
+                }
+            }
+            let elapsed = start.elapsed();
+            // Dropping the provider aborts the transaction
+            drop(provider_rw);
+
+            let warmup = iteration < self.warmup;
+            info!(target: "reth::cli", stage = %self.stage, iteration, warmup, ?elapsed, "Finished run");
+            if !warmup {
+                samples.push(elapsed.as_secs_f64());
+                rows = input.checkpoint().entities().map(|entities| {
+                    let start =
+                        start_checkpoint.entities().map_or(0, |entities| entities.processed);
+                    entities.processed.saturating_sub(start)
+                });
+            }
+        }
+
+        let wall_time = WallTimeStats::new(samples);
+        let per_second = |count: u64| count as f64 / wall_time.median_secs.max(f64::EPSILON);
+        Ok(BenchResult {
+            stage: self.stage.to_string(),
+            from: self.from,
+            to: self.to,
+            iterations: self.iterations,
+            warmup: self.warmup,
+            drop_caches: self.drop_caches,
+            version: version_metadata().short_version.to_string(),
+            git_sha: version_metadata().vergen_git_sha.to_string(),
+            blocks_per_second: per_second(self.to - self.from),
+            rows_per_second: rows.map(per_second),
+            gas_per_second: matches!(self.stage, StageEnum::Execution).then(|| per_second(gas)),
+            wall_time,
+        })
+    }
+
+    /// Returns the stage, and the stage to unwind it with if it's a different one.
+    fn build_stage<N, Comp>(
+        &self,
+        components: &Comp,
+        config: &Config,
+    ) -> (BenchStage<N>, Option<BenchStage<N>>)
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        Comp: CliNodeComponents<N>,
+    {
+        // The whole range is executed in one transaction
+        let batch_size = self.to - self.from + 1;
+        let etl_config = config.stages.etl.clone();
+        let prune_modes = config.prune.segments.clone();
+
+        match self.stage {
+            StageEnum::Senders => (
+                Box::new(SenderRecoveryStage::new(SenderRecoveryConfig {
+                    commit_threshold: batch_size,
+                })),
+                None,
+            ),
+            StageEnum::Execution => (
+                Box::new(ExecutionStage::new(
+                    components.evm_config().clone(),
+                    Arc::new(components.consensus().clone()),
+                    ExecutionStageThresholds {
+                        max_blocks: Some(batch_size),
+                        max_changes: None,
+                        max_cumulative_gas: None,
+                        max_duration: None,
+                    },
+                    config.stages.merkle.incremental_threshold,
+                    ExExManagerHandle::empty(),
+                )),
+                None,
+            ),
+            StageEnum::TxLookup => (
+                Box::new(TransactionLookupStage::new(
+                    TransactionLookupConfig { chunk_size: batch_size },
+                    etl_config,
+                    prune_modes.transaction_lookup,
+                )),
+                None,
+            ),
+            StageEnum::AccountHashing => (
+                Box::new(AccountHashingStage::new(
+                    HashingConfig { clean_threshold: 1, commit_threshold: batch_size },
+                    etl_config,
+                )),
+                None,
+            ),
+            StageEnum::StorageHashing => (
+                Box::new(StorageHashingStage::new(
+                    HashingConfig { clean_threshold: 1, commit_threshold: batch_size },
+                    etl_config,
+                )),
+                None,
+            ),
+            StageEnum::Merkle => (
+                Box::new(MerkleStage::new_execution(
+                    config.stages.merkle.rebuild_threshold,
+                    config.stages.merkle.incremental_threshold,
+                )),
+                Some(Box::new(MerkleStage::default_unwind())),
+            ),
+            StageEnum::AccountHistory => (
+                Box::new(IndexAccountHistoryStage::new(
+                    config.stages.index_account_history,
+                    etl_config,
+                    prune_modes.account_history,
+                )),
+                None,
+            ),
+            StageEnum::StorageHistory => (
+                Box::new(IndexStorageHistoryStage::new(
+                    config.stages.index_storage_history,
+                    etl_config,
+                    prune_modes.storage_history,
+                )),
+                None,
+            ),
+            StageEnum::Headers |
+            StageEnum::Bodies |
+            StageEnum::Hashing |
+            StageEnum::MerkleChangeSets => {
+                unreachable!("unsupported stages are rejected before opening the storage")
+            }
+        }
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// The result of a benchmark, as written to the output file.
+#[derive(Debug, Serialize)]
+struct BenchResult {
+    stage: String,
+    from: u64,
+    to: u64,
+    iterations: usize,
+    warmup: usize,
+    drop_caches: bool,
+    /// The version of the build that ran the benchmark.
+    version: String,
+    git_sha: String,
+    wall_time: WallTimeStats,
+    /// Blocks per second of the median run.
+    blocks_per_second: f64,
+    /// Entities processed per second of the median run, e.g. accounts for account hashing.
+    rows_per_second: Option<f64>,
+    /// Gas per second of the median run of the execution stage.
+    gas_per_second: Option<f64>,
+}
+
+/// Statistics of the wall time of the timed runs, in seconds.
+#[derive(Debug, Serialize, PartialEq)]
+struct WallTimeStats {
+    min_secs: f64,
+    median_secs: f64,
+    mean_secs: f64,
+    stddev_secs: f64,
+    samples_secs: Vec<f64>,
+}
+
+impl WallTimeStats {
+    /// Computes the statistics of at least one sample.
+    fn new(samples: Vec<f64>) -> Self {
+        let mut sorted = samples.clone();
+        sorted.sort_by(f64::total_cmp);
+        let len = sorted.len();
+        let median = if len.is_multiple_of(2) {
+            (sorted[len / 2 - 1] + sorted[len / 2]) / 2.0
+        } else {
+            sorted[len / 2]
+        };
+        let mean = sorted.iter().sum::<f64>() / len as f64;
+        let variance = if len > 1 {
+            sorted.iter().map(|sample| (sample - mean).powi(2)).sum::<f64>() / (len - 1) as f64
+        } else {
+            0.0
+        };
+        Self {
+            min_secs: sorted[0],
+            median_secs: median,
+            mean_secs: mean,
+            stddev_secs: variance.sqrt(),
+            samples_secs: samples,
+        }
+    }
+}
+
+/// Copies the storage directories into `snapshot`, replacing a previous snapshot.
+fn create_snapshot(
+    db: &Path,
+    static_files: &Path,
+    rocksdb: &Path,
+    snapshot: &Path,
+) -> eyre::Result<()> {
+    if snapshot.exists() {
+        if !snapshot.join(SNAPSHOT_MARKER).exists() {
+            bail!("{} exists and is not a snapshot of `reth stage bench`", snapshot.display())
+        }
+        reth_fs_util::remove_dir_all(snapshot)?;
+    }
+    reth_fs_util::create_dir_all(snapshot)?;
+    reth_fs_util::write(snapshot.join(SNAPSHOT_MARKER), b"")?;
+
+    let start = Instant::now();
+    for (source, name) in [(db, "db"), (static_files, "static_files"), (rocksdb, "rocksdb")] {
+        if !source.exists() {
+            continue
+        }
+        let mut cp = process::Command::new("cp");
+        cp.arg("-R");
+        #[cfg(target_os = "linux")]
+        cp.arg("--reflink=auto");
+        let status = cp.arg(source).arg(snapshot.join(name)).status()?;
+        if !status.success() {
+            bail!("failed to copy {} to the snapshot: {status}", source.display())
+        }
+    }
+    info!(target: "reth::cli", ?snapshot, elapsed = ?start.elapsed(), "Created snapshot of the datadir");
+    Ok(())
+}
+
+/// Writes dirty pages to disk and drops the page cache of the OS.
+fn drop_page_cache() -> eyre::Result<()> {
+    process::Command::new("sync").status()?;
+    reth_fs_util::write("/proc/sys/vm/drop_caches", "3")
+        .wrap_err("failed to drop the page cache, `--drop-caches` requires root")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wall_time_stats() {
+        let stats = WallTimeStats::new(vec![3.0, 1.0, 2.0, 6.0]);
+        assert_eq!(stats.min_secs, 1.0);
+        assert_eq!(stats.median_secs, 2.5);
+        assert_eq!(stats.mean_secs, 3.0);
+        assert!((stats.stddev_secs - (14.0f64 / 3.0).sqrt()).abs() < 1e-9);
+        assert_eq!(stats.samples_secs, [3.0, 1.0, 2.0, 6.0]);
+
+        let single = WallTimeStats::new(vec![2.0]);
+        assert_eq!((single.median_secs, single.stddev_secs), (2.0, 0.0));
+    }
+}
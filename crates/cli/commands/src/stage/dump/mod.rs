@@ -3,17 +3,24 @@ use crate::common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment,
 use clap::Parser;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
-use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
+use reth_db::{init_db, mdbx::DatabaseArguments, static_file::iter_static_files, DatabaseEnv};
 use reth_db_api::{
-    cursor::DbCursorRO, database::Database, models::ClientVersion, table::TableImporter, tables,
+    cursor::DbCursorRO,
+    database::Database,
+    models::ClientVersion,
+    table::{Table, TableImporter},
+    tables,
     transaction::DbTx,
 };
 use reth_db_common::DbTool;
 use reth_node_builder::NodeTypesWithDB;
 use reth_node_core::{
     args::DatadirArgs,
-    dirs::{DataDirPath, PlatformPath},
+    dirs::{ChainPath, DataDirPath, PlatformPath},
 };
+use reth_provider::StaticFileProviderFactory;
+use reth_static_file_types::StaticFileSegment;
+use serde::Serialize;
 use std::{path::PathBuf, sync::Arc};
 use tracing::info;
 
@@ -52,11 +59,85 @@ pub enum Stages {
     Merkle(StageCommand),
 }
 
+impl Stages {
+    /// Returns the name of the stage.
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Execution(_) => "execution",
+            Self::StorageHashing(_) => "storage-hashing",
+            Self::AccountHashing(_) => "account-hashing",
+            Self::Merkle(_) => "merkle",
+        }
+    }
+
+    /// Returns the arguments of the stage command.
+    const fn command(&self) -> &StageCommand {
+        match self {
+            Self::Execution(cmd) |
+            Self::StorageHashing(cmd) |
+            Self::AccountHashing(cmd) |
+            Self::Merkle(cmd) => cmd,
+        }
+    }
+
+    /// Returns the database tables that are copied to the dump.
+    fn tables(&self) -> Vec<&'static str> {
+        match self {
+            Self::Execution(_) => vec![
+                tables::BlockBodyIndices::NAME,
+                tables::CanonicalHeaders::NAME,
+                tables::Headers::NAME,
+                tables::BlockOmmers::NAME,
+                tables::Transactions::NAME,
+                tables::TransactionSenders::NAME,
+                tables::PlainAccountState::NAME,
+                tables::PlainStorageState::NAME,
+                tables::Bytecodes::NAME,
+            ],
+            Self::StorageHashing(_) => vec![
+                tables::BlockBodyIndices::NAME,
+                tables::PlainStorageState::NAME,
+                tables::StorageChangeSets::NAME,
+            ],
+            Self::AccountHashing(_) => vec![
+                tables::BlockBodyIndices::NAME,
+                tables::AccountChangeSets::NAME,
+                tables::PlainAccountState::NAME,
+            ],
+            Self::Merkle(_) => vec![
+                tables::BlockBodyIndices::NAME,
+                tables::Headers::NAME,
+                tables::AccountChangeSets::NAME,
+                tables::StorageChangeSets::NAME,
+                tables::HashedAccounts::NAME,
+                tables::HashedStorages::NAME,
+                tables::AccountsTrie::NAME,
+                tables::StoragesTrie::NAME,
+            ],
+        }
+    }
+
+    /// Returns the static file segments the stage reads, which are copied to the dump.
+    const fn static_file_segments(&self) -> &'static [StaticFileSegment] {
+        match self {
+            Self::Execution(_) => &[
+                StaticFileSegment::Headers,
+                StaticFileSegment::Transactions,
+                StaticFileSegment::TransactionSenders,
+            ],
+            Self::StorageHashing(_) | Self::AccountHashing(_) => &[],
+            Self::Merkle(_) => &[StaticFileSegment::Headers],
+        }
+    }
+}
+
 /// Stage command that takes a range
 #[derive(Debug, Clone, Parser)]
 pub struct StageCommand {
     /// The path to the new datadir folder.
-    #[arg(long, value_name = "OUTPUT_PATH", verbatim_doc_comment)]
+    ///
+    /// A `dump-manifest.json` file listing the copied tables and static files is written to it.
+    #[arg(long, alias = "output", value_name = "OUTPUT_PATH", verbatim_doc_comment)]
     output_datadir: PlatformPath<DataDirPath>,
 
     /// From which block.
@@ -71,19 +152,40 @@ pub struct StageCommand {
     dry_run: bool,
 }
 
+/// Manifest written to the root of the dumped datadir, describing what was copied into it.
+#[derive(Debug, Serialize)]
+struct DumpManifest {
+    /// The dumped stage.
+    stage: &'static str,
+    /// The first block of the dumped range.
+    from: u64,
+    /// The last block of the dumped range.
+    to: u64,
+    /// The database tables copied to the dump.
+    tables: Vec<&'static str>,
+    /// The file names of the static files copied to the dump.
+    static_files: Vec<String>,
+}
+
+/// The file name of the [`DumpManifest`] in the dumped datadir.
+const MANIFEST_FILE_NAME: &str = "dump-manifest.json";
+
 macro_rules! handle_stage {
-    ($stage_fn:ident, $tool:expr, $command:expr) => {{
-        let StageCommand { output_datadir, from, to, dry_run, .. } = $command;
-        let output_datadir =
-            output_datadir.with_chain($tool.chain().chain(), DatadirArgs::default());
-        $stage_fn($tool, *from, *to, output_datadir, *dry_run).await?
+    ($stage_fn:ident, $tool:expr, $command:expr, $output_datadir:expr) => {{
+        let StageCommand { from, to, dry_run, .. } = $command;
+        $stage_fn($tool, *from, *to, $output_datadir, *dry_run).await?
     }};
 
-    ($stage_fn:ident, $tool:expr, $command:expr, $executor:expr, $consensus:expr) => {{
-        let StageCommand { output_datadir, from, to, dry_run, .. } = $command;
-        let output_datadir =
-            output_datadir.with_chain($tool.chain().chain(), DatadirArgs::default());
-        $stage_fn($tool, *from, *to, output_datadir, *dry_run, $executor, $consensus).await?
+    (
+        $stage_fn:ident,
+        $tool:expr,
+        $command:expr,
+        $output_datadir:expr,
+        $executor:expr,
+        $consensus:expr
+    ) => {{
+        let StageCommand { from, to, dry_run, .. } = $command;
+        $stage_fn($tool, *from, *to, $output_datadir, *dry_run, $executor, $consensus).await?
     }};
 }
 
@@ -101,17 +203,47 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         let evm_config = components.evm_config().clone();
         let consensus = components.consensus().clone();
 
+        let StageCommand { output_datadir, from, to, .. } = self.command.command();
+        let output_datadir =
+            output_datadir.clone().with_chain(tool.chain().chain(), DatadirArgs::default());
+
+        // Static files are copied first, so that they are available if the stage is run right
+        // after dumping.
+        let static_files = copy_static_files(
+            &tool,
+            self.command.static_file_segments(),
+            *from,
+            *to,
+            &output_datadir,
+        )?;
+
+        let datadir = output_datadir.clone();
         match &self.command {
             Stages::Execution(cmd) => {
-                handle_stage!(dump_execution_stage, &tool, cmd, evm_config, consensus)
+                handle_stage!(dump_execution_stage, &tool, cmd, datadir, evm_config, consensus)
+            }
+            Stages::StorageHashing(cmd) => {
+                handle_stage!(dump_hashing_storage_stage, &tool, cmd, datadir)
+            }
+            Stages::AccountHashing(cmd) => {
+                handle_stage!(dump_hashing_account_stage, &tool, cmd, datadir)
             }
-            Stages::StorageHashing(cmd) => handle_stage!(dump_hashing_storage_stage, &tool, cmd),
-            Stages::AccountHashing(cmd) => handle_stage!(dump_hashing_account_stage, &tool, cmd),
             Stages::Merkle(cmd) => {
-                handle_stage!(dump_merkle_stage, &tool, cmd, evm_config, consensus)
+                handle_stage!(dump_merkle_stage, &tool, cmd, datadir, evm_config, consensus)
             }
         }
 
+        let manifest = DumpManifest {
+            stage: self.command.name(),
+            from: *from,
+            to: *to,
+            tables: self.command.tables(),
+            static_files,
+        };
+        let manifest_path = output_datadir.as_ref().join(MANIFEST_FILE_NAME);
+        reth_fs_util::write_json_file(&manifest_path, &manifest)?;
+        info!(target: "reth::cli", path = ?manifest_path, "Wrote dump manifest");
+
         Ok(())
     }
 }
@@ -153,3 +285,68 @@ pub(crate) fn setup<N: NodeTypesWithDB>(
 
     Ok((output_datadir, tip_block_number))
 }
+
+/// Copies the static files of the given segments that contain any block of the range to the
+/// static files directory of the output datadir, returning the names of the copied files.
+///
+/// Static files are copied whole, so the dump may contain blocks outside of the range.
+fn copy_static_files<N: NodeTypesWithDB>(
+    db_tool: &DbTool<N>,
+    segments: &[StaticFileSegment],
+    from: u64,
+    to: u64,
+    output_datadir: &ChainPath<DataDirPath>,
+) -> eyre::Result<Vec<String>> {
+    let static_file_provider = db_tool.provider_factory.static_file_provider();
+    let source = static_file_provider.directory();
+    let destination = output_datadir.static_files();
+    reth_fs_util::create_dir_all(&destination)?;
+
+    let static_files = iter_static_files(source)?;
+    let mut copied = Vec::new();
+    for segment in segments {
+        for (block_range, header) in static_files.get(segment).into_iter().flatten() {
+            if block_range.end() < from || block_range.start() > to {
+                continue
+            }
+
+            // A static file consists of the data file and its index, offsets and config files,
+            // which share its name as a prefix.
+            let name = segment.filename(&header.expected_block_range());
+            for entry in reth_fs_util::read_dir(source)? {
+                let file_name = entry?.file_name().to_string_lossy().into_owned();
+                if file_name == name || file_name.starts_with(&format!("{name}.")) {
+                    std::fs::copy(source.join(&file_name), destination.join(&file_name))?;
+                    copied.push(file_name);
+                }
+            }
+            info!(target: "reth::cli", %segment, %block_range, "Copied static file");
+        }
+    }
+
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_stage_dump_command() {
+        let cmd = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "execution",
+            "--output",
+            "dump",
+            "--from",
+            "10",
+            "--to",
+            "20",
+        ]);
+        assert_eq!(cmd.command.name(), "execution");
+        assert_eq!(cmd.command.command().from, 10);
+        assert_eq!(cmd.command.command().to, 20);
+        assert!(cmd.command.static_file_segments().contains(&StaticFileSegment::Headers));
+    }
+}
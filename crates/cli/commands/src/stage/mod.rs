@@ -8,6 +8,7 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 
+pub mod bench;
 pub mod drop;
 pub mod dump;
 pub mod run;
@@ -36,6 +37,11 @@ pub enum Subcommands<C: ChainSpecParser> {
     Dump(dump::Command<C>),
     /// Unwinds a certain block range, deleting it from the database.
     Unwind(unwind::Command<C>),
+    /// Benchmarks a stage over a block range against a snapshot of the datadir.
+    ///
+    /// The stage is run repeatedly in transactions that are never committed, and the wall time
+    /// and throughput of the runs are added to a JSON file, to compare different builds.
+    Bench(Box<bench::Command<C>>),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
@@ -54,6 +60,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             Subcommands::Drop(command) => command.execute::<N>().await,
             Subcommands::Dump(command) => command.execute::<N, _, _>(components).await,
             Subcommands::Unwind(command) => command.execute::<N, _, _>(components).await,
+            Subcommands::Bench(command) => command.execute::<N, _, _>(components).await,
         }
     }
 }
@@ -66,6 +73,7 @@ impl<C: ChainSpecParser> Command<C> {
             Subcommands::Drop(ref command) => command.chain_spec(),
             Subcommands::Dump(ref command) => command.chain_spec(),
             Subcommands::Unwind(ref command) => command.chain_spec(),
+            Subcommands::Bench(ref command) => command.chain_spec(),
         }
     }
 }
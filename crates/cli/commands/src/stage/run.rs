@@ -6,6 +6,7 @@ use crate::common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment,
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::Sealable;
 use clap::Parser;
+use metrics::Gauge;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
@@ -39,12 +40,22 @@ use reth_stages::{
         IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
         TransactionLookupStage,
     },
-    ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt, UnwindInput, UnwindOutput,
+    ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageCheckpoint, StageExt, StageId,
+    UnwindInput, UnwindOutput,
+};
+use std::{
+    any::Any,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use std::{any::Any, net::SocketAddr, sync::Arc, time::Instant};
 use tokio::sync::watch;
 use tracing::*;
 
+/// The number of blocks after which the size of the transaction is checked against
+/// `--commit-every-bytes`, if `--commit-every` is not set.
+const COMMIT_EVERY_BYTES_CHECK_INTERVAL: u64 = 100;
+
 /// `reth stage` command
 #[derive(Debug, Parser)]
 pub struct Command<C: ChainSpecParser> {
@@ -53,7 +64,8 @@ pub struct Command<C: ChainSpecParser> {
 
     /// Enable Prometheus metrics.
     ///
-    /// The metrics will be served at the given interface and port.
+    /// The metrics will be served at the given interface and port. Besides the database metrics,
+    /// the checkpoint, processed entities and elapsed time of the stage are reported.
     #[arg(long, value_name = "SOCKET")]
     metrics: Option<SocketAddr>,
 
@@ -92,10 +104,34 @@ pub struct Command<C: ChainSpecParser> {
     #[arg(long, short)]
     commit: bool,
 
+    /// Commit every this many blocks and continue from the committed checkpoint in a new
+    /// transaction, instead of executing the whole range in one transaction.
+    ///
+    /// Implies `--commit`.
+    #[arg(long, value_name = "BLOCKS")]
+    commit_every: Option<u64>,
+
+    /// Commit once the database pages written by the transaction exceed this many bytes, and
+    /// continue from the committed checkpoint in a new transaction.
+    ///
+    /// The size is checked every `--commit-every` blocks if set, or every 100 blocks otherwise.
+    /// Implies `--commit`.
+    #[arg(long, value_name = "BYTES")]
+    commit_every_bytes: Option<u64>,
+
     /// Save stage checkpoints
     #[arg(long)]
     checkpoints: bool,
 
+    /// Write and commit every batch of the execution stage on a dedicated thread while the next
+    /// batch executes, instead of alternating between them.
+    ///
+    /// The stage checkpoint is saved with every batch. Only supported for the execution stage, and
+    /// requires `--commit`. The node's pipeline always alternates between executing and
+    /// committing.
+    #[arg(long, requires = "commit")]
+    pipeline_commits: bool,
+
     #[command(flatten)]
     network: NetworkArgs,
 }
@@ -109,12 +145,18 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
         F: FnOnce(Arc<C::ChainSpec>) -> Comp,
     {
         // Quit early if the stages requires a commit and `--commit` is not provided.
-        if self.requires_commit() && !self.commit {
+        if self.requires_commit() && !self.commits() {
             return Err(eyre::eyre!(
                 "The stage {} requires overwriting existing static files and must commit, but `--commit` was not provided. Please pass `--commit` and try again.",
                 self.stage.to_string()
             ));
         }
+        if self.pipeline_commits && self.stage != StageEnum::Execution {
+            return Err(eyre::eyre!(
+                "`--pipeline-commits` is only supported for the execution stage, not {}",
+                self.stage
+            ));
+        }
 
         // Raise the fd limit of the process.
         // Does not do anything on windows.
@@ -162,6 +204,21 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
 
         let etl_config = config.stages.etl.clone();
         let prune_modes = config.prune.segments.clone();
+        let incremental_threshold = config.stages.merkle.incremental_threshold;
+        let execution_stage = || {
+            ExecutionStage::new(
+                components.evm_config().clone(),
+                Arc::new(components.consensus().clone()),
+                ExecutionStageThresholds {
+                    max_blocks: Some(batch_size),
+                    max_changes: None,
+                    max_cumulative_gas: None,
+                    max_duration: None,
+                },
+                incremental_threshold,
+                ExExManagerHandle::empty(),
+            )
+        };
 
         let (mut exec_stage, mut unwind_stage): (Box<dyn Stage<_>>, Option<Box<dyn Stage<_>>>) =
             match self.stage {
@@ -266,21 +323,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     })),
                     None,
                 ),
-                StageEnum::Execution => (
-                    Box::new(ExecutionStage::new(
-                        components.evm_config().clone(),
-                        Arc::new(components.consensus().clone()),
-                        ExecutionStageThresholds {
-                            max_blocks: Some(batch_size),
-                            max_changes: None,
-                            max_cumulative_gas: None,
-                            max_duration: None,
-                        },
-                        config.stages.merkle.incremental_threshold,
-                        ExExManagerHandle::empty(),
-                    )),
-                    None,
-                ),
+                StageEnum::Execution => (Box::new(execution_stage()), None),
                 StageEnum::TxLookup => (
                     Box::new(TransactionLookupStage::new(
                         TransactionLookupConfig { chunk_size: batch_size },
@@ -351,7 +394,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     provider_rw.save_stage_checkpoint(unwind_stage.id(), checkpoint)?;
                 }
 
-                if self.commit {
+                if self.commits() {
                     provider_rw.commit()?;
                     provider_rw = provider_factory.database_provider_rw()?;
                 }
@@ -363,27 +406,86 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             checkpoint: Some(checkpoint.with_block_number(self.from)),
         };
 
+        let stage_metrics = StageRunMetrics::new(exec_stage.id());
+        let initial_processed = checkpoint.entities().map_or(0, |entities| entities.processed);
+
+        // When committing in batches, the stage is executed with targets bounded by the batch
+        // size, so that the batch can be committed once it is complete.
+        let batched = self.commit_every.is_some() || self.commit_every_bytes.is_some();
+        let step = self.commit_every.unwrap_or(COMMIT_EVERY_BYTES_CHECK_INTERVAL);
+        let mut batches = 0;
+        let mut batch_from = self.from;
+        let mut batch_start = Instant::now();
+
         let start = Instant::now();
+        if self.pipeline_commits {
+            // The batches are committed in their own transactions.
+            provider_rw.commit()?;
+
+            info!(target: "reth::cli", stage = %self.stage, "Executing stage with pipelined commits");
+            let checkpoint = execution_stage().execute_pipelined(&provider_factory, input)?;
+            info!(target: "reth::cli", stage = %self.stage, block = checkpoint.block_number, time = ?start.elapsed(), "Finished stage");
+
+            return Ok(())
+        }
+
         info!(target: "reth::cli", stage = %self.stage, "Executing stage");
         loop {
+            if batched {
+                let block = input.checkpoint().block_number;
+                input.target = Some(self.to.min(block.saturating_add(step)));
+            }
+
             exec_stage.execute_ready(input).await?;
             let ExecOutput { checkpoint, done } = exec_stage.execute(&provider_rw, input)?;
+            let finished = done && (!batched || checkpoint.block_number >= self.to);
 
             input.checkpoint = Some(checkpoint);
+            stage_metrics.record(checkpoint, initial_processed, start.elapsed());
 
             if self.checkpoints {
                 provider_rw.save_stage_checkpoint(exec_stage.id(), checkpoint)?;
             }
-            if self.commit {
+
+            let batch_complete = !batched ||
+                finished ||
+                self.commit_every
+                    .is_some_and(|blocks| checkpoint.block_number >= batch_from + blocks) ||
+                match self.commit_every_bytes {
+                    Some(bytes) => provider_rw.tx_ref().inner.space_dirty()? >= bytes,
+                    None => false,
+                };
+            if self.commits() && batch_complete {
                 provider_rw.commit()?;
                 provider_rw = provider_factory.database_provider_rw()?;
+
+                if batched {
+                    batches += 1;
+                    info!(
+                        target: "reth::cli",
+                        stage = %self.stage,
+                        from = batch_from,
+                        to = checkpoint.block_number,
+                        time = ?batch_start.elapsed(),
+                        "Committed batch"
+                    );
+                    batch_from = checkpoint.block_number;
+                    batch_start = Instant::now();
+                }
             }
 
-            if done {
+            if finished {
                 break
             }
         }
-        info!(target: "reth::cli", stage = %self.stage, time = ?start.elapsed(), "Finished stage");
+        info!(
+            target: "reth::cli",
+            stage = %self.stage,
+            blocks = self.to.saturating_sub(self.from),
+            batches,
+            time = ?start.elapsed(),
+            "Finished stage"
+        );
 
         Ok(())
     }
@@ -403,4 +505,58 @@ impl<C: ChainSpecParser> Command<C> {
     pub fn requires_commit(&self) -> bool {
         matches!(self.stage, StageEnum::Headers | StageEnum::Bodies | StageEnum::Execution)
     }
+
+    /// Returns whether the changes are committed, which is implied by committing in batches.
+    const fn commits(&self) -> bool {
+        self.commit || self.commit_every.is_some() || self.commit_every_bytes.is_some()
+    }
+}
+
+/// Metrics of a stage run, served on the `--metrics` endpoint.
+#[derive(Debug)]
+struct StageRunMetrics {
+    /// The block number of the last checkpoint of the stage.
+    checkpoint: Gauge,
+    /// The number of entities processed by the stage, e.g. gas for the execution stage.
+    entities_processed: Gauge,
+    /// The number of entities the stage has to process.
+    entities_total: Gauge,
+    /// The number of entities processed per second since the stage started.
+    entities_per_second: Gauge,
+    /// The number of seconds since the stage started.
+    elapsed: Gauge,
+}
+
+impl StageRunMetrics {
+    fn new(stage: StageId) -> Self {
+        let stage = stage.to_string();
+        Self {
+            checkpoint: metrics::gauge!("stage.run.checkpoint", "stage" => stage.clone()),
+            entities_processed: metrics::gauge!(
+                "stage.run.entities_processed",
+                "stage" => stage.clone()
+            ),
+            entities_total: metrics::gauge!("stage.run.entities_total", "stage" => stage.clone()),
+            entities_per_second: metrics::gauge!(
+                "stage.run.entities_per_second",
+                "stage" => stage.clone()
+            ),
+            elapsed: metrics::gauge!("stage.run.elapsed_seconds", "stage" => stage),
+        }
+    }
+
+    /// Records the checkpoint returned by the stage, `elapsed` after the stage started with
+    /// `initial_processed` entities already processed.
+    fn record(&self, checkpoint: StageCheckpoint, initial_processed: u64, elapsed: Duration) {
+        self.checkpoint.set(checkpoint.block_number as f64);
+        self.elapsed.set(elapsed.as_secs_f64());
+        if let Some(entities) = checkpoint.entities() {
+            self.entities_processed.set(entities.processed as f64);
+            self.entities_total.set(entities.total as f64);
+            self.entities_per_second.set(
+                entities.processed.saturating_sub(initial_processed) as f64 /
+                    elapsed.as_secs_f64().max(f64::EPSILON),
+            );
+        }
+    }
 }
@@ -12,19 +12,27 @@ use reth_cli::chainspec::ChainSpecParser;
 use reth_config::Config;
 use reth_consensus::noop::NoopConsensus;
 use reth_db::DatabaseEnv;
+use reth_db_api::{table::Table, tables};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_evm::ConfigureEvm;
 use reth_exex::ExExManagerHandle;
-use reth_provider::{providers::ProviderNodeTypes, BlockNumReader, ProviderFactory};
+use reth_node_core::args::StageEnum;
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockNumReader, DBProvider, DatabaseProviderFactory,
+    ProviderFactory, StageCheckpointReader, StageCheckpointWriter, StatsReader,
+};
 use reth_stages::{
     sets::{DefaultStages, OfflineStages},
-    stages::ExecutionStage,
-    ExecutionStageThresholds, Pipeline, StageSet,
+    stages::{
+        AccountHashingStage, ExecutionStage, IndexAccountHistoryStage, IndexStorageHistoryStage,
+        MerkleStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
+    },
+    ExecutionStageThresholds, Pipeline, Stage, StageId, StageSet, UnwindInput, UnwindOutput,
 };
 use reth_static_file::StaticFileProducer;
 use std::sync::Arc;
 use tokio::sync::watch;
-use tracing::info;
+use tracing::{info, warn};
 
 /// `reth stage unwind` command
 #[derive(Debug, Parser)]
@@ -33,7 +41,20 @@ pub struct Command<C: ChainSpecParser> {
     env: EnvironmentArgs<C>,
 
     #[command(subcommand)]
-    command: Subcommands,
+    command: Option<Subcommands>,
+
+    /// Unwind only this stage instead of the whole pipeline, leaving the checkpoints of all other
+    /// stages as they are.
+    #[arg(value_enum, requires = "to")]
+    stage: Option<StageEnum>,
+
+    /// The block to unwind the stage to. The block stays in the database.
+    #[arg(long, value_name = "BLOCK", requires = "stage")]
+    to: Option<u64>,
+
+    /// Unwind the stage even if a later stage would be left checkpointed beyond it.
+    #[arg(long, requires = "stage")]
+    force: bool,
 
     /// If this is enabled, then all stages except headers, bodies, and sender recovery will be
     /// unwound.
@@ -52,10 +73,25 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         F: FnOnce(Arc<C::ChainSpec>) -> Comp,
     {
         let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
+        let components = components(provider_factory.chain_spec());
 
-        let target = self.command.unwind_target(provider_factory.clone())?;
+        if let (Some(stage), Some(to)) = (self.stage, self.to) {
+            return unwind_single_stage(
+                stage,
+                to,
+                self.force,
+                &config,
+                provider_factory,
+                components.evm_config().clone(),
+            )
+        }
 
-        let components = components(provider_factory.chain_spec());
+        let Some(command) = &self.command else {
+            eyre::bail!(
+                "Either a stage or one of the `to-block` and `num-blocks` subcommands is required"
+            )
+        };
+        let target = command.unwind_target(provider_factory.clone())?;
 
         if self.offline {
             info!(target: "reth::cli", "Performing an unwind for offline-only data!");
@@ -65,8 +101,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         info!(target: "reth::cli", ?target, ?highest_static_file_block, prune_config=?config.prune,  "Executing a pipeline unwind.");
 
         // This will build an offline-only pipeline if the `offline` flag is enabled
-        let mut pipeline =
-            self.build_pipeline(config, provider_factory, components.evm_config().clone())?;
+        let mut pipeline = build_pipeline(
+            config,
+            provider_factory,
+            components.evm_config().clone(),
+            self.offline,
+        )?;
 
         // Move all applicable data from database to static files.
         pipeline.move_to_static_files()?;
@@ -77,63 +117,66 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
 
         Ok(())
     }
+}
 
-    fn build_pipeline<N: ProviderNodeTypes<ChainSpec = C::ChainSpec>>(
-        self,
-        config: Config,
-        provider_factory: ProviderFactory<N>,
-        evm_config: impl ConfigureEvm<Primitives = N::Primitives> + 'static,
-    ) -> Result<Pipeline<N>, eyre::Error> {
-        let stage_conf = &config.stages;
-        let prune_modes = config.prune.segments.clone();
-
-        let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
-
-        let builder = if self.offline {
-            Pipeline::<N>::builder().add_stages(
-                OfflineStages::new(
-                    evm_config,
-                    NoopConsensus::arc(),
-                    config.stages,
-                    prune_modes.clone(),
-                )
-                .builder()
-                .disable(reth_stages::StageId::SenderRecovery),
+/// Builds the pipeline that unwinds all stages, or only the stages of offline data if `offline` is
+/// set.
+pub(crate) fn build_pipeline<N>(
+    config: Config,
+    provider_factory: ProviderFactory<N>,
+    evm_config: impl ConfigureEvm<Primitives = N::Primitives> + 'static,
+    offline: bool,
+) -> Result<Pipeline<N>, eyre::Error>
+where
+    N: ProviderNodeTypes<ChainSpec: EthChainSpec + EthereumHardforks>,
+{
+    let stage_conf = &config.stages;
+    let prune_modes = config.prune.segments.clone();
+
+    let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
+
+    let builder = if offline {
+        Pipeline::<N>::builder().add_stages(
+            OfflineStages::new(
+                evm_config,
+                NoopConsensus::arc(),
+                config.stages,
+                prune_modes.clone(),
             )
-        } else {
-            Pipeline::<N>::builder().with_tip_sender(tip_tx).add_stages(
-                DefaultStages::new(
-                    provider_factory.clone(),
-                    tip_rx,
-                    Arc::new(NoopConsensus::default()),
-                    NoopHeaderDownloader::default(),
-                    NoopBodiesDownloader::default(),
-                    evm_config.clone(),
-                    stage_conf.clone(),
-                    prune_modes.clone(),
-                    None,
-                )
-                .set(ExecutionStage::new(
-                    evm_config,
-                    Arc::new(NoopConsensus::default()),
-                    ExecutionStageThresholds {
-                        max_blocks: None,
-                        max_changes: None,
-                        max_cumulative_gas: None,
-                        max_duration: None,
-                    },
-                    stage_conf.execution_external_clean_threshold(),
-                    ExExManagerHandle::empty(),
-                )),
+            .builder()
+            .disable(reth_stages::StageId::SenderRecovery),
+        )
+    } else {
+        Pipeline::<N>::builder().with_tip_sender(tip_tx).add_stages(
+            DefaultStages::new(
+                provider_factory.clone(),
+                tip_rx,
+                Arc::new(NoopConsensus::default()),
+                NoopHeaderDownloader::default(),
+                NoopBodiesDownloader::default(),
+                evm_config.clone(),
+                stage_conf.clone(),
+                prune_modes.clone(),
+                None,
             )
-        };
+            .set(ExecutionStage::new(
+                evm_config,
+                Arc::new(NoopConsensus::default()),
+                ExecutionStageThresholds {
+                    max_blocks: None,
+                    max_changes: None,
+                    max_cumulative_gas: None,
+                    max_duration: None,
+                },
+                stage_conf.execution_external_clean_threshold(),
+                ExExManagerHandle::empty(),
+            )),
+        )
+    };
 
-        let pipeline = builder.build(
-            provider_factory.clone(),
-            StaticFileProducer::new(provider_factory, prune_modes),
-        );
-        Ok(pipeline)
-    }
+    let pipeline = builder
+        .build(provider_factory.clone(), StaticFileProducer::new(provider_factory, prune_modes));
+    Ok(pipeline)
 }
 
 impl<C: ChainSpecParser> Command<C> {
@@ -143,6 +186,177 @@ impl<C: ChainSpecParser> Command<C> {
     }
 }
 
+/// Unwinds a single stage to the given block, and updates only the checkpoint of that stage.
+///
+/// Refuses to unwind if a later stage would be left checkpointed beyond an earlier one, unless
+/// `force` is set.
+fn unwind_single_stage<N: ProviderNodeTypes>(
+    stage: StageEnum,
+    to: u64,
+    force: bool,
+    config: &Config,
+    provider_factory: ProviderFactory<N>,
+    evm_config: impl ConfigureEvm<Primitives = N::Primitives> + 'static,
+) -> eyre::Result<()> {
+    let etl_config = config.stages.etl.clone();
+    let prune_modes = &config.prune.segments;
+    let mut unwind_stage: Box<dyn Stage<_>> = match stage {
+        StageEnum::Senders => Box::new(SenderRecoveryStage::new(config.stages.sender_recovery)),
+        StageEnum::Execution => {
+            Box::new(ExecutionStage::new_with_executor(evm_config, NoopConsensus::arc()))
+        }
+        StageEnum::AccountHashing => {
+            Box::new(AccountHashingStage::new(config.stages.account_hashing, etl_config))
+        }
+        StageEnum::StorageHashing => {
+            Box::new(StorageHashingStage::new(config.stages.storage_hashing, etl_config))
+        }
+        StageEnum::Merkle => Box::new(MerkleStage::default_unwind()),
+        StageEnum::TxLookup => Box::new(TransactionLookupStage::new(
+            config.stages.transaction_lookup,
+            etl_config,
+            prune_modes.transaction_lookup,
+        )),
+        StageEnum::AccountHistory => Box::new(IndexAccountHistoryStage::new(
+            config.stages.index_account_history,
+            etl_config,
+            prune_modes.account_history,
+        )),
+        StageEnum::StorageHistory => Box::new(IndexStorageHistoryStage::new(
+            config.stages.index_storage_history,
+            etl_config,
+            prune_modes.storage_history,
+        )),
+        _ => eyre::bail!(
+            "Unwinding the {stage} stage on its own is not supported, use `stage unwind to-block`"
+        ),
+    };
+
+    // The merkle stage is unwound by its unwind variant, but both variants are checkpointed.
+    let stage_id = unwind_stage.id();
+    let stage_ids: &[StageId] = if stage_id == StageId::MerkleUnwind {
+        &[StageId::MerkleUnwind, StageId::MerkleExecute]
+    } else {
+        &[stage_id]
+    };
+
+    let provider = provider_factory.provider()?;
+    let checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+    if checkpoint.block_number <= to {
+        eyre::bail!(
+            "The {stage_id} stage checkpoint {} is not above block {to}",
+            checkpoint.block_number
+        )
+    }
+
+    // The era stage is only run for the first blocks of a sync, so it is not compared.
+    let mut checkpoints = Vec::new();
+    for id in StageId::ALL.into_iter().filter(|id| *id != StageId::Era) {
+        if let Some(checkpoint) = provider.get_stage_checkpoint(id)? {
+            let block = if stage_ids.contains(&id) { to } else { checkpoint.block_number };
+            checkpoints.push((id, block));
+        }
+    }
+    drop(provider);
+
+    let issues = checkpoint_ordering_issues(&checkpoints);
+    if !issues.is_empty() {
+        if !force {
+            eyre::bail!(
+                "Unwinding the {stage_id} stage to block {to} leaves the stage checkpoints out of \
+                 order, pass `--force` to unwind anyway:\n{}",
+                issues.join("\n")
+            )
+        }
+        for issue in &issues {
+            warn!(target: "reth::cli", %issue, "Unwinding with inconsistent stage checkpoints");
+        }
+    }
+
+    let mut provider_rw = provider_factory.database_provider_rw()?;
+    let entries_before = stage_table_entries(&provider_rw, stage)?;
+
+    info!(target: "reth::cli", stage = %stage_id, from = checkpoint.block_number, to, "Unwinding stage");
+    let mut input = UnwindInput { checkpoint, unwind_to: to, bad_block: None };
+    while input.checkpoint.block_number > to {
+        let UnwindOutput { checkpoint } = unwind_stage.unwind(&provider_rw, input)?;
+        for id in stage_ids {
+            provider_rw.save_stage_checkpoint(*id, checkpoint)?;
+        }
+        provider_rw.commit()?;
+        provider_rw = provider_factory.database_provider_rw()?;
+
+        info!(target: "reth::cli", stage = %stage_id, checkpoint = checkpoint.block_number, to, "Unwound chunk");
+        input.checkpoint = checkpoint;
+    }
+
+    let entries_after = stage_table_entries(&provider_rw, stage)?;
+    for ((table, before), (_, after)) in entries_before.into_iter().zip(entries_after) {
+        info!(
+            target: "reth::cli",
+            table,
+            before,
+            after,
+            removed = before.saturating_sub(after),
+            "Unwound table"
+        );
+    }
+    info!(target: "reth::cli", stage = %stage_id, to, "Unwound stage");
+
+    Ok(())
+}
+
+/// Returns the number of entries of each table the stage removes entries from on unwind.
+///
+/// Entries are counted in both the database and the static files, since tables like receipts and
+/// transaction senders may be kept in static files.
+fn stage_table_entries(
+    provider: &impl StatsReader,
+    stage: StageEnum,
+) -> eyre::Result<Vec<(&'static str, usize)>> {
+    macro_rules! entries {
+        ($($table:ident),*) => {
+            vec![$((tables::$table::NAME, provider.count_entries::<tables::$table>()?)),*]
+        };
+    }
+
+    Ok(match stage {
+        StageEnum::Senders => entries!(TransactionSenders),
+        StageEnum::Execution => entries!(
+            PlainAccountState,
+            PlainStorageState,
+            AccountChangeSets,
+            StorageChangeSets,
+            Receipts
+        ),
+        StageEnum::AccountHashing => entries!(HashedAccounts),
+        StageEnum::StorageHashing => entries!(HashedStorages),
+        StageEnum::Merkle => entries!(AccountsTrie, StoragesTrie),
+        StageEnum::TxLookup => entries!(TransactionHashNumbers),
+        StageEnum::AccountHistory => entries!(AccountsHistory),
+        StageEnum::StorageHistory => entries!(StoragesHistory),
+        _ => Vec::new(),
+    })
+}
+
+/// Returns an issue for every stage that is checkpointed beyond a stage that runs before it.
+///
+/// The checkpoints are expected in the order the stages run in the pipeline.
+fn checkpoint_ordering_issues(checkpoints: &[(StageId, u64)]) -> Vec<String> {
+    let mut issues = Vec::new();
+    for (index, (earlier, earlier_block)) in checkpoints.iter().enumerate() {
+        for (later, later_block) in &checkpoints[index + 1..] {
+            if later_block > earlier_block {
+                issues.push(format!(
+                    "{later} stage checkpoint {later_block} is beyond {earlier} stage checkpoint \
+                     {earlier_block}"
+                ));
+            }
+        }
+    }
+    issues
+}
+
 /// `reth stage unwind` subcommand
 #[derive(Subcommand, Debug, Eq, PartialEq)]
 enum Subcommands {
@@ -197,7 +411,10 @@ mod tests {
             "to-block",
             "100",
         ]);
-        assert_eq!(cmd.command, Subcommands::ToBlock { target: BlockHashOrNumber::Number(100) });
+        assert_eq!(
+            cmd.command,
+            Some(Subcommands::ToBlock { target: BlockHashOrNumber::Number(100) })
+        );
 
         let cmd = Command::<EthereumChainSpecParser>::parse_from([
             "reth",
@@ -206,7 +423,42 @@ mod tests {
             "num-blocks",
             "100",
         ]);
-        assert_eq!(cmd.command, Subcommands::NumBlocks { amount: 100 });
+        assert_eq!(cmd.command, Some(Subcommands::NumBlocks { amount: 100 }));
+    }
+
+    #[test]
+    fn parse_unwind_stage() {
+        let cmd = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "account-hashing",
+            "--to",
+            "100",
+            "--force",
+        ]);
+        assert_eq!(cmd.command, None);
+        assert_eq!(cmd.stage, Some(StageEnum::AccountHashing));
+        assert_eq!(cmd.to, Some(100));
+        assert!(cmd.force);
+
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from(["reth", "merkle"]).is_err());
+    }
+
+    #[test]
+    fn checkpoint_ordering() {
+        let mut checkpoints = vec![
+            (StageId::Headers, 100),
+            (StageId::Execution, 100),
+            (StageId::AccountHashing, 100),
+            (StageId::MerkleExecute, 100),
+        ];
+        assert!(checkpoint_ordering_issues(&checkpoints).is_empty());
+
+        checkpoints[2].1 = 50;
+        let issues = checkpoint_ordering_issues(&checkpoints);
+        assert_eq!(
+            issues,
+            ["MerkleExecute stage checkpoint 100 is beyond AccountHashing stage checkpoint 50"]
+        );
     }
 
     #[test]
@@ -214,7 +466,10 @@ mod tests {
         let cmd = Command::<EthereumChainSpecParser>::parse_from([
             "reth", "--chain", "sepolia", "to-block", "100",
         ]);
-        assert_eq!(cmd.command, Subcommands::ToBlock { target: BlockHashOrNumber::Number(100) });
+        assert_eq!(
+            cmd.command,
+            Some(Subcommands::ToBlock { target: BlockHashOrNumber::Number(100) })
+        );
         assert_eq!(cmd.env.chain.chain_id(), SEPOLIA.chain_id());
     }
 }
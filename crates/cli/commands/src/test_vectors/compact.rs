@@ -1,7 +1,8 @@
 use alloy_eips::eip4895::Withdrawals;
 use alloy_primitives::{hex, Signature, TxKind, B256};
 use arbitrary::Arbitrary;
-use eyre::{Context, Result};
+use clap::Args;
+use eyre::{ensure, Context, Result};
 use proptest::{
     prelude::{ProptestConfig, RngCore},
     test_runner::{TestRng, TestRunner},
@@ -34,11 +35,58 @@ use reth_stages_types::{
 };
 use reth_trie::{hash_builder::HashBuilderValue, TrieMask};
 use reth_trie_common::{hash_builder::HashBuilderState, StoredNibbles, StoredNibblesSubKey};
-use std::{fs::File, io::BufReader};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
 
 pub const VECTORS_FOLDER: &str = "testdata/micro/compact";
 pub const VECTOR_SIZE: usize = 100;
 
+/// Selects whether compact test vectors are written, read or verified.
+#[derive(Debug, Args)]
+#[group(multiple = false, required = true)]
+pub struct Mode {
+    /// Write test vectors to a file.
+    #[arg(long)]
+    pub write: bool,
+
+    /// Read test vectors from a file.
+    #[arg(long)]
+    pub read: bool,
+
+    /// Verify test vectors from the given directory, which may have been generated on another
+    /// commit.
+    ///
+    /// Unlike `--read`, every vector must carry its JSON value and the decoded value must be
+    /// equal to it.
+    #[arg(long, value_name = "DIR")]
+    pub verify: Option<PathBuf>,
+}
+
+/// A randomly generated value together with its `Compact` encoding.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CompactVector {
+    /// The value as JSON.
+    pub json: serde_json::Value,
+    /// The hex encoded `Compact` bytes of the value.
+    pub compact: String,
+}
+
+/// A stored test vector.
+///
+/// Vectors written before the JSON value was stored next to the bytes only hold the hex encoded
+/// `Compact` bytes.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StoredVector {
+    Compact(String),
+    WithJson(CompactVector),
+}
+
 #[macro_export]
 macro_rules! compact_types {
     (regular: [$($regular_ty:ident),*], identifier: [$($id_ty:ident),*]) => {
@@ -51,12 +99,12 @@ macro_rules! compact_types {
             )*
         ];
 
-        pub const READ_VECTORS: &[fn() -> eyre::Result<()>] = &[
+        pub const READ_VECTORS: &[fn(&std::path::Path, bool) -> eyre::Result<()>] = &[
             $(
-                read_vector::<$regular_ty> as fn() -> eyre::Result<()>,
+                read_vector::<$regular_ty> as fn(&std::path::Path, bool) -> eyre::Result<()>,
             )*
             $(
-                read_vector::<$id_ty> as fn() -> eyre::Result<()>,
+                read_vector::<$id_ty> as fn(&std::path::Path, bool) -> eyre::Result<()>,
             )*
         ];
 
@@ -135,18 +183,29 @@ compact_types!(
 );
 
 /// Generates a vector of type `T` to a file.
-pub fn generate_vectors() -> Result<()> {
-    generate_vectors_with(GENERATE_VECTORS)
+pub fn generate_vectors(seed: Option<B256>) -> Result<()> {
+    generate_vectors_with(GENERATE_VECTORS, seed)
 }
 
 pub fn read_vectors() -> Result<()> {
     read_vectors_with(READ_VECTORS)
 }
 
+/// Verifies the vectors in `folder` against their stored JSON values.
+pub fn verify_vectors(folder: &Path) -> Result<()> {
+    verify_vectors_with(READ_VECTORS, folder)
+}
+
 /// Generates a vector of type `T` to a file.
-pub fn generate_vectors_with(generator: &[fn(&mut TestRunner) -> eyre::Result<()>]) -> Result<()> {
+///
+/// If no `seed` is given, a random one is used. Either way it is printed so that the vectors can
+/// be reproduced.
+pub fn generate_vectors_with(
+    generator: &[fn(&mut TestRunner) -> eyre::Result<()>],
+    seed: Option<B256>,
+) -> Result<()> {
     // Prepare random seed for test (same method as used by proptest)
-    let seed = B256::random();
+    let seed = seed.unwrap_or_else(B256::random);
     println!("Seed for compact test vectors: {:?}", hex::encode_prefixed(seed));
 
     // Start the runner with the seed
@@ -165,33 +224,68 @@ pub fn generate_vectors_with(generator: &[fn(&mut TestRunner) -> eyre::Result<()
 
 /// Reads multiple vectors of different types ensuring their correctness by decoding and
 /// re-encoding.
-pub fn read_vectors_with(read: &[fn() -> eyre::Result<()>]) -> Result<()> {
+pub fn read_vectors_with(read: &[fn(&Path, bool) -> eyre::Result<()>]) -> Result<()> {
     fs::create_dir_all(VECTORS_FOLDER)?;
+
+    check_vectors(read, Path::new(VECTORS_FOLDER), false).map_err(|_| {
+        eyre::eyre!(
+            "If there are missing types, make sure to run `reth test-vectors compact --write` first.\n
+             If it happened during CI, ignore IF it's a new proposed type that `main` branch does not have."
+        )
+    })
+}
+
+/// Reads multiple vectors of different types from `folder`, ensuring that decoding their bytes
+/// yields their stored JSON values and that re-encoding yields the same bytes.
+pub fn verify_vectors_with(
+    read: &[fn(&Path, bool) -> eyre::Result<()>],
+    folder: &Path,
+) -> Result<()> {
+    check_vectors(read, folder, true).map_err(|failed| {
+        eyre::eyre!(
+            "{failed} types failed verification against the vectors in {}",
+            folder.display()
+        )
+    })
+}
+
+/// Runs all `read` functions on `folder` and prints their errors, returning the number of failed
+/// types.
+fn check_vectors(
+    read: &[fn(&Path, bool) -> eyre::Result<()>],
+    folder: &Path,
+    require_json: bool,
+) -> Result<(), usize> {
     let mut errors = None;
 
     for read_fn in read {
-        if let Err(err) = read_fn() {
+        if let Err(err) = read_fn(folder, require_json) {
             errors.get_or_insert_with(Vec::new).push(err);
         }
     }
 
     if let Some(err_list) = errors {
-        for error in err_list {
+        for error in &err_list {
             eprintln!("{error:?}");
         }
-        return Err(eyre::eyre!(
-            "If there are missing types, make sure to run `reth test-vectors compact --write` first.\n
-             If it happened during CI, ignore IF it's a new proposed type that `main` branch does not have."
-        ));
+        return Err(err_list.len())
     }
 
     Ok(())
 }
 
 /// Generates test vectors for a specific type `T`.
+///
+/// Each value is stored as JSON next to its `Compact` bytes. Generation fails if a value does not
+/// survive a JSON round trip, since it could then not be verified later.
 pub fn generate_vector<T>(runner: &mut TestRunner) -> Result<()>
 where
-    T: for<'a> Arbitrary<'a> + reth_codecs::Compact,
+    T: for<'a> Arbitrary<'a>
+        + reth_codecs::Compact
+        + Serialize
+        + DeserializeOwned
+        + PartialEq
+        + Debug,
 {
     let type_name = type_name::<T>();
     print!("{}", &type_name);
@@ -225,7 +319,13 @@ where
             compact_buffer.push(res as u8);
         }
 
-        values.push(hex::encode(&compact_buffer));
+        let json = serde_json::to_value(&obj)?;
+        ensure!(
+            serde_json::from_value::<T>(json.clone())? == obj,
+            "{type_name} does not round trip through JSON: {json}"
+        );
+
+        values.push(CompactVector { json, compact: hex::encode(&compact_buffer) });
     }
 
     serde_json::to_writer(
@@ -240,25 +340,34 @@ where
     Ok(())
 }
 
-/// Reads a vector of type `T` from a file and compares each item with its reconstructed version
-/// using `T::from_compact`.
-pub fn read_vector<T>() -> Result<()>
+/// Reads a vector of type `T` from a file in `folder` and compares each item with its
+/// reconstructed version using `T::from_compact`.
+///
+/// If the item carries its JSON value, the reconstructed value must also be equal to it. With
+/// `require_json`, items without a JSON value are rejected.
+pub fn read_vector<T>(folder: &Path, require_json: bool) -> Result<()>
 where
-    T: reth_codecs::Compact,
+    T: reth_codecs::Compact + DeserializeOwned + PartialEq + Debug,
 {
     let type_name = type_name::<T>();
     print!("{}", &type_name);
 
     // Read the file where the vectors are stored
-    let file_path = format!("{VECTORS_FOLDER}/{}.json", &type_name);
+    let file_path = folder.join(format!("{}.json", &type_name));
     let file =
         File::open(&file_path).wrap_err_with(|| format!("Failed to open vector {type_name}."))?;
     let reader = BufReader::new(file);
 
-    let stored_values: Vec<String> = serde_json::from_reader(reader)?;
+    let stored_values: Vec<StoredVector> = serde_json::from_reader(reader)?;
     let mut buffer = vec![];
 
-    for hex_str in stored_values {
+    for stored in stored_values {
+        let (hex_str, json) = match stored {
+            StoredVector::Compact(hex_str) => (hex_str, None),
+            StoredVector::WithJson(CompactVector { json, compact }) => (compact, Some(json)),
+        };
+        ensure!(json.is_some() || !require_json, "missing JSON value {type_name}");
+
         let mut compact_bytes = hex::decode(hex_str)?;
         let mut identifier = None;
         buffer.clear();
@@ -270,7 +379,15 @@ where
 
         let (reconstructed, _) = T::from_compact(&compact_bytes, len_or_identifier);
         reconstructed.to_compact(&mut buffer);
-        assert_eq!(buffer, compact_bytes, "mismatch {type_name}");
+        ensure!(buffer == compact_bytes, "mismatch {type_name}");
+
+        if let Some(json) = json {
+            let expected: T = serde_json::from_value(json)?;
+            ensure!(
+                reconstructed == expected,
+                "mismatch {type_name}: decoded {reconstructed:?}, expected {expected:?}"
+            );
+        }
     }
 
     println!(" ✅");
@@ -1,9 +1,11 @@
 //! Command for generating test vectors.
 
+use alloy_primitives::B256;
 use clap::{Parser, Subcommand};
 
 pub mod compact;
 pub mod tables;
+pub mod transactions;
 
 /// Generate test-vectors for different data types.
 #[derive(Debug, Parser)]
@@ -25,9 +27,27 @@ pub enum Subcommands {
     /// The generated vectors are serialized in both `json` and `Compact` formats and saved to a
     /// file.
     ///
-    /// Use the `--read` flag to read and validate the previously generated vectors from a file.
-    #[group(multiple = false, required = true)]
+    /// Use the `--read` flag to read and validate the previously generated vectors from a file,
+    /// or `--verify` to also check the decoded values against their stored JSON.
     Compact {
+        #[command(flatten)]
+        mode: compact::Mode,
+
+        /// Seed for the random generator, so that the vectors can be reproduced.
+        ///
+        /// If not set, a random seed is used and printed.
+        #[arg(long, requires = "write")]
+        seed: Option<B256>,
+    },
+    /// Randomly generate signed transactions of every type using the `--write` flag.
+    ///
+    /// Each transaction is saved to a file in both its network (EIP-2718) and block (RLP)
+    /// encodings, together with its expected hash and signer.
+    ///
+    /// Use the `--read` flag to check that previously generated vectors, possibly from another
+    /// client, decode to the expected hashes and signers.
+    #[group(multiple = false, required = true)]
+    Transactions {
         /// Write test vectors to a file.
         #[arg(long)]
         write: bool,
@@ -45,13 +65,22 @@ impl Command {
             Subcommands::Tables { names } => {
                 tables::generate_vectors(names)?;
             }
-            Subcommands::Compact { write, .. } => {
-                if write {
-                    compact::generate_vectors()?;
+            Subcommands::Compact { mode, seed } => {
+                if mode.write {
+                    compact::generate_vectors(seed)?;
+                } else if let Some(folder) = mode.verify {
+                    compact::verify_vectors(&folder)?;
                 } else {
                     compact::read_vectors()?;
                 }
             }
+            Subcommands::Transactions { write, .. } => {
+                if write {
+                    transactions::generate_vectors()?;
+                } else {
+                    transactions::read_vectors()?;
+                }
+            }
         }
         Ok(())
     }
@@ -0,0 +1,266 @@
+use alloy_consensus::{SignableTransaction, TxEip1559, TxEip2930, TxEip4844, TxEip7702, TxLegacy};
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718, Typed2718},
+    eip4844::{MAX_BLOBS_PER_BLOCK_DENCUN, VERSIONED_HASH_VERSION_KZG},
+};
+use alloy_primitives::{hex, keccak256, Address, Bytes, TxKind, B256};
+use arbitrary::Arbitrary;
+use eyre::{ensure, Context, Result};
+use proptest::{
+    prelude::{ProptestConfig, RngCore},
+    strategy::{Strategy, ValueTree},
+    test_runner::{TestRng, TestRunner},
+};
+use proptest_arbitrary_interop::arb;
+use reth_ethereum_primitives::{Transaction, TransactionSigned};
+use reth_fs_util as fs;
+use reth_primitives_traits::{crypto::secp256k1::sign_message, SignedTransaction};
+use secp256k1::{SecretKey, SECP256K1};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::{BufReader, BufWriter},
+};
+
+pub const VECTORS_FOLDER: &str = "testdata/micro/transactions";
+pub const VECTORS_FILE: &str = "transactions.json";
+pub const PER_TYPE: usize = 100;
+
+/// A signed transaction with its encodings and the values that decoders are expected to derive
+/// from them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionVector {
+    /// Short description of the transaction, e.g. `eip1559/zero-priority-fee`.
+    pub name: String,
+    /// The EIP-2718 transaction type.
+    #[serde(rename = "type")]
+    pub tx_type: u8,
+    /// The EIP-2718 encoding, as accepted by `eth_sendRawTransaction`.
+    pub network: Bytes,
+    /// The RLP encoding of the transaction in a block body.
+    pub block: Bytes,
+    /// The expected transaction hash.
+    pub hash: B256,
+    /// The expected signer of the transaction.
+    pub signer: Address,
+}
+
+/// Generates random transactions of every type, including edge cases, and writes them with their
+/// encodings to a file.
+pub fn generate_vectors() -> Result<()> {
+    // Prepare random seed for test (same method as used by proptest)
+    let seed = B256::random();
+    println!("Seed for transaction test vectors: {:?}", hex::encode_prefixed(seed));
+
+    // Start the runner with the seed
+    let config = ProptestConfig::default();
+    let rng = TestRng::from_seed(config.rng_algorithm, &seed.0);
+    let mut runner = TestRunner::new_with_rng(config, rng);
+
+    fs::create_dir_all(VECTORS_FOLDER)?;
+
+    let vectors = transaction_vectors(&mut runner, PER_TYPE)?;
+    serde_json::to_writer_pretty(
+        BufWriter::new(fs::create_file(format!("{VECTORS_FOLDER}/{VECTORS_FILE}"))?),
+        &vectors,
+    )?;
+    println!("Generated {} transaction vectors ✅", vectors.len());
+
+    Ok(())
+}
+
+/// Reads the transaction vectors from a file and checks that decoding each of its encodings
+/// reproduces the expected type, hash and signer.
+pub fn read_vectors() -> Result<()> {
+    let file_path = format!("{VECTORS_FOLDER}/{VECTORS_FILE}");
+    let file = File::open(&file_path).wrap_err_with(|| {
+        format!(
+            "Failed to open {file_path}, run `reth test-vectors transactions --write` to create it"
+        )
+    })?;
+    let vectors: Vec<TransactionVector> = serde_json::from_reader(BufReader::new(file))?;
+
+    let mut failures = 0;
+    for vector in &vectors {
+        if let Err(err) = verify_vector(vector) {
+            eprintln!("{}: {err:?}", vector.name);
+            failures += 1;
+        }
+    }
+    ensure!(failures == 0, "{failures} of {} transaction vectors failed", vectors.len());
+    println!("Verified {} transaction vectors ✅", vectors.len());
+
+    Ok(())
+}
+
+/// Generates `per_type` random transactions of every type followed by the edge cases.
+fn transaction_vectors(runner: &mut TestRunner, per_type: usize) -> Result<Vec<TransactionVector>> {
+    let mut transactions: Vec<(String, Transaction)> = Vec::new();
+    for index in 0..per_type {
+        transactions.extend([
+            (format!("legacy/random/{index}"), arbitrary::<TxLegacy>(runner)?.into()),
+            (format!("eip2930/random/{index}"), arbitrary::<TxEip2930>(runner)?.into()),
+            (format!("eip1559/random/{index}"), arbitrary::<TxEip1559>(runner)?.into()),
+            (format!("eip4844/random/{index}"), arbitrary::<TxEip4844>(runner)?.into()),
+            (format!("eip7702/random/{index}"), arbitrary::<TxEip7702>(runner)?.into()),
+        ]);
+    }
+    transactions.extend(edge_cases(runner)?);
+
+    transactions.into_iter().map(|(name, transaction)| sign(runner, name, transaction)).collect()
+}
+
+/// Returns random transactions with fields set to edge case values.
+fn edge_cases(runner: &mut TestRunner) -> Result<Vec<(String, Transaction)>> {
+    let max_blob_hashes = (0..MAX_BLOBS_PER_BLOCK_DENCUN)
+        .map(|_| {
+            let mut hash = B256::ZERO;
+            runner.rng().fill_bytes(hash.as_mut_slice());
+            hash[0] = VERSIONED_HASH_VERSION_KZG;
+            hash
+        })
+        .collect();
+
+    Ok(vec![
+        ("legacy/pre-eip155".to_string(), TxLegacy { chain_id: None, ..arbitrary(runner)? }.into()),
+        ("legacy/create".to_string(), TxLegacy { to: TxKind::Create, ..arbitrary(runner)? }.into()),
+        (
+            "eip2930/empty-access-list".to_string(),
+            TxEip2930 { access_list: Default::default(), ..arbitrary(runner)? }.into(),
+        ),
+        (
+            "eip2930/create".to_string(),
+            TxEip2930 { to: TxKind::Create, ..arbitrary(runner)? }.into(),
+        ),
+        (
+            "eip1559/empty-access-list".to_string(),
+            TxEip1559 { access_list: Default::default(), ..arbitrary(runner)? }.into(),
+        ),
+        (
+            "eip1559/zero-priority-fee".to_string(),
+            TxEip1559 { max_priority_fee_per_gas: 0, ..arbitrary(runner)? }.into(),
+        ),
+        (
+            "eip1559/create".to_string(),
+            TxEip1559 { to: TxKind::Create, ..arbitrary(runner)? }.into(),
+        ),
+        (
+            "eip4844/empty-access-list".to_string(),
+            TxEip4844 { access_list: Default::default(), ..arbitrary(runner)? }.into(),
+        ),
+        (
+            "eip4844/zero-priority-fee".to_string(),
+            TxEip4844 { max_priority_fee_per_gas: 0, ..arbitrary(runner)? }.into(),
+        ),
+        (
+            "eip4844/max-blob-hashes".to_string(),
+            TxEip4844 { blob_versioned_hashes: max_blob_hashes, ..arbitrary(runner)? }.into(),
+        ),
+        (
+            "eip7702/empty-access-list".to_string(),
+            TxEip7702 { access_list: Default::default(), ..arbitrary(runner)? }.into(),
+        ),
+        (
+            "eip7702/zero-priority-fee".to_string(),
+            TxEip7702 { max_priority_fee_per_gas: 0, ..arbitrary(runner)? }.into(),
+        ),
+    ])
+}
+
+/// Signs the transaction with a random key and returns its vector.
+fn sign(
+    runner: &mut TestRunner,
+    name: String,
+    transaction: Transaction,
+) -> Result<TransactionVector> {
+    let secret_key = loop {
+        let mut bytes = [0u8; 32];
+        runner.rng().fill_bytes(&mut bytes);
+        if let Ok(secret_key) = SecretKey::from_slice(&bytes) {
+            break secret_key
+        }
+    };
+    let signer = Address::from_raw_public_key(
+        &secret_key.public_key(SECP256K1).serialize_uncompressed()[1..],
+    );
+
+    let signature =
+        sign_message(B256::from(secret_key.secret_bytes()), transaction.signature_hash())?;
+    let transaction = TransactionSigned::new_unhashed(transaction, signature);
+    let network = Bytes::from(transaction.encoded_2718());
+
+    Ok(TransactionVector {
+        name,
+        tx_type: transaction.ty(),
+        hash: keccak256(&network),
+        block: alloy_rlp::encode(&transaction).into(),
+        network,
+        signer,
+    })
+}
+
+/// Checks that both encodings of the vector decode to the same transaction with the expected
+/// type, hash and signer, and that re-encoding it reproduces them.
+fn verify_vector(vector: &TransactionVector) -> Result<()> {
+    let transaction = TransactionSigned::decode_2718_exact(&vector.network)
+        .wrap_err("Failed to decode the network encoding")?;
+    let from_block: TransactionSigned =
+        alloy_rlp::decode_exact(&vector.block).wrap_err("Failed to decode the block encoding")?;
+    ensure!(
+        transaction == from_block,
+        "The network and block encodings decode to different transactions"
+    );
+
+    ensure!(
+        transaction.ty() == vector.tx_type,
+        "Decoded type {} instead of {}",
+        transaction.ty(),
+        vector.tx_type
+    );
+    ensure!(
+        *transaction.tx_hash() == vector.hash,
+        "Decoded hash {} instead of {}",
+        transaction.tx_hash(),
+        vector.hash
+    );
+    let signer = transaction.recover_signer()?;
+    ensure!(signer == vector.signer, "Recovered signer {signer} instead of {}", vector.signer);
+
+    ensure!(
+        transaction.encoded_2718()[..] == vector.network[..],
+        "Re-encoding does not reproduce the network encoding"
+    );
+    ensure!(
+        alloy_rlp::encode(&transaction)[..] == vector.block[..],
+        "Re-encoding does not reproduce the block encoding"
+    );
+
+    Ok(())
+}
+
+/// Generates a random value of `T`.
+fn arbitrary<T: for<'a> Arbitrary<'a> + Debug + 'static>(runner: &mut TestRunner) -> Result<T> {
+    Ok(arb::<T>().no_shrink().new_tree(runner).map_err(|e| eyre::eyre!("{e}"))?.current())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_vectors_verify() {
+        let mut runner = TestRunner::deterministic();
+        let vectors = transaction_vectors(&mut runner, 5).unwrap();
+        assert_eq!(vectors.len(), 5 * 5 + edge_cases(&mut runner).unwrap().len());
+
+        for vector in &vectors {
+            verify_vector(vector).unwrap_or_else(|err| panic!("{}: {err:?}", vector.name));
+        }
+
+        let mut corrupted = vectors[0].clone();
+        corrupted.signer = Address::ZERO;
+        assert!(verify_vector(&corrupted).is_err());
+    }
+}
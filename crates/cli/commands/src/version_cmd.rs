@@ -0,0 +1,131 @@
+//! Command that prints the build information of the binary.
+
+use clap::Parser;
+use reth_db::version::DB_VERSION;
+use reth_nippy_jar::NIPPY_JAR_VERSION;
+use reth_node_core::version::{version_metadata, RethCliVersionConsts};
+use serde::Serialize;
+use std::fmt;
+
+/// `reth version` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Print the build information as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+impl Command {
+    /// Execute `version` command
+    pub fn execute(&self) -> eyre::Result<()> {
+        let info = BuildInfo::new(version_metadata());
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&info)?);
+        } else {
+            println!("{info}");
+        }
+        Ok(())
+    }
+}
+
+/// The build information of the binary, and the versions of the database and static file formats
+/// it expects, which tell whether it can open a datadir.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildInfo {
+    /// The name of the client.
+    pub name: String,
+    /// The version from Cargo.toml.
+    pub version: String,
+    /// The full SHA of the commit.
+    pub git_sha: String,
+    /// Whether the working tree had uncommitted changes.
+    pub git_dirty: bool,
+    /// The build timestamp.
+    pub build_timestamp: String,
+    /// The build profile.
+    pub build_profile: String,
+    /// The version of the compiler.
+    pub rustc_version: String,
+    /// The target triple.
+    pub target_triple: String,
+    /// The enabled cargo features.
+    pub features: Vec<String>,
+    /// The version of the database tables, see [`DB_VERSION`].
+    pub db_version: u64,
+    /// The version of the static file format, see [`NIPPY_JAR_VERSION`].
+    pub static_file_version: usize,
+}
+
+impl BuildInfo {
+    /// Collects the build information from the version metadata.
+    pub fn new(metadata: &RethCliVersionConsts) -> Self {
+        Self {
+            name: metadata.name_client.to_string(),
+            version: metadata.cargo_pkg_version.to_string(),
+            git_sha: metadata.vergen_git_sha_long.to_string(),
+            git_dirty: metadata.vergen_git_dirty == "true",
+            build_timestamp: metadata.vergen_build_timestamp.to_string(),
+            build_profile: metadata.build_profile_name.to_string(),
+            rustc_version: metadata.vergen_rustc_semver.to_string(),
+            target_triple: metadata.vergen_cargo_target_triple.to_string(),
+            features: metadata
+                .vergen_cargo_features
+                .split(',')
+                .filter(|feature| !feature.is_empty())
+                .map(String::from)
+                .collect(),
+            db_version: DB_VERSION,
+            static_file_version: NIPPY_JAR_VERSION,
+        }
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", self.name, self.version)?;
+        writeln!(
+            f,
+            "Commit SHA: {}{}",
+            self.git_sha,
+            if self.git_dirty { " (dirty)" } else { "" }
+        )?;
+        writeln!(f, "Build Timestamp: {}", self.build_timestamp)?;
+        writeln!(f, "Build Profile: {}", self.build_profile)?;
+        writeln!(f, "Build Features: {}", self.features.join(","))?;
+        writeln!(f, "Rustc Version: {}", self.rustc_version)?;
+        writeln!(f, "Target Triple: {}", self.target_triple)?;
+        writeln!(f, "Database Version: {}", self.db_version)?;
+        write!(f, "Static File Version: {}", self.static_file_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+
+    #[test]
+    fn build_info() {
+        let metadata = RethCliVersionConsts {
+            name_client: Cow::Borrowed("Reth"),
+            cargo_pkg_version: Cow::Borrowed("1.9.3"),
+            vergen_git_sha_long: Cow::Borrowed("defa64b2"),
+            vergen_git_dirty: Cow::Borrowed("true"),
+            vergen_cargo_features: Cow::Borrowed("asm-keccak,jemalloc"),
+            ..Default::default()
+        };
+        let info = BuildInfo::new(&metadata);
+        assert!(info.git_dirty);
+        assert_eq!(info.features, ["asm-keccak", "jemalloc"]);
+        assert!(info.to_string().contains("Commit SHA: defa64b2 (dirty)"));
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["db_version"], DB_VERSION);
+        assert_eq!(json["static_file_version"], NIPPY_JAR_VERSION);
+
+        // The features of an empty build are empty rather than a single empty name
+        let info = BuildInfo::new(&RethCliVersionConsts::default());
+        assert!(info.features.is_empty());
+        assert!(!info.git_dirty);
+    }
+}
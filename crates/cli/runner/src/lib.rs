@@ -11,8 +11,18 @@
 //! Entrypoint for running commands.
 
 use reth_tasks::{TaskExecutor, TaskManager};
-use std::{future::Future, pin::pin, sync::mpsc, time::Duration};
-use tracing::{debug, error, trace};
+use std::{
+    any::Any,
+    future::Future,
+    io::Write,
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex, PoisonError,
+    },
+    time::{Duration, Instant},
+};
+use tracing::{debug, error, trace, warn};
 
 /// Executes CLI commands.
 ///
@@ -21,6 +31,11 @@ use tracing::{debug, error, trace};
 #[non_exhaustive]
 pub struct CliRunner {
     tokio_runtime: tokio::runtime::Runtime,
+    /// How long to wait for the shutdown after the first `ctrl-c` or `SIGTERM` before aborting the
+    /// process.
+    shutdown_timeout: Option<Duration>,
+    /// Flushes the logs when dropped, see [`CliRunner::with_log_guard`].
+    log_guard: Option<Box<dyn Any + Send>>,
 }
 
 impl CliRunner {
@@ -29,12 +44,29 @@ impl CliRunner {
     ///
     /// The default tokio runtime is multi-threaded, with both I/O and time drivers enabled.
     pub fn try_default_runtime() -> Result<Self, std::io::Error> {
-        Ok(Self { tokio_runtime: tokio_runtime()? })
+        Ok(Self::from_runtime(tokio_runtime()?))
     }
 
     /// Create a new [`CliRunner`] from a provided tokio [`Runtime`](tokio::runtime::Runtime).
     pub const fn from_runtime(tokio_runtime: tokio::runtime::Runtime) -> Self {
-        Self { tokio_runtime }
+        Self { tokio_runtime, shutdown_timeout: None, log_guard: None }
+    }
+
+    /// Sets how long [`CliRunner::run_command_until_exit`] waits for the shutdown after the first
+    /// `ctrl-c` or `SIGTERM` before it aborts the process.
+    ///
+    /// Without a timeout, the process is only aborted by the third signal.
+    pub const fn with_shutdown_timeout(mut self, shutdown_timeout: Option<Duration>) -> Self {
+        self.shutdown_timeout = shutdown_timeout;
+        self
+    }
+
+    /// Sets a guard that flushes the logs when dropped, like the guard of the file logger.
+    ///
+    /// The guard is dropped before the process is aborted, so that no logs are lost.
+    pub fn with_log_guard(mut self, guard: impl Send + 'static) -> Self {
+        self.log_guard = Some(Box::new(guard));
+        self
     }
 
     /// Executes an async block on the runtime and blocks until completion.
@@ -50,6 +82,10 @@ impl CliRunner {
     ///
     /// Tasks spawned by the command via the [`TaskExecutor`] are shut down and an attempt is made
     /// to drive their shutdown to completion after the command has finished.
+    ///
+    /// The first `ctrl-c` or `SIGTERM` starts the graceful shutdown. A second one reports the tasks
+    /// that are still shutting down, and a third one, or the expiry of the
+    /// [shutdown timeout](CliRunner::with_shutdown_timeout), aborts the process.
     pub fn run_command_until_exit<F, E>(
         self,
         command: impl FnOnce(CliContext) -> F,
@@ -60,12 +96,19 @@ impl CliRunner {
     {
         let AsyncCliRunner { context, mut task_manager, tokio_runtime } =
             AsyncCliRunner::new(self.tokio_runtime);
+        let watcher = ShutdownWatcher::spawn(
+            &tokio_runtime,
+            task_manager.executor(),
+            self.shutdown_timeout,
+            self.log_guard,
+        );
 
         // Executes the command until it finished or ctrl-c was fired
         let command_res = tokio_runtime.block_on(run_to_completion_or_panic(
             &mut task_manager,
             run_until_ctrl_c(command(context)),
         ));
+        watcher.command_exited();
 
         if command_res.is_err() {
             error!(target: "reth::cli", "shutting down due to error");
@@ -93,6 +136,7 @@ impl CliRunner {
         let _ = rx.recv_timeout(Duration::from_secs(5)).inspect_err(|err| {
             debug!(target: "reth::cli", %err, "tokio runtime shutdown timed out");
         });
+        watcher.finish();
 
         command_res
     }
@@ -217,6 +261,142 @@ pub fn tokio_runtime() -> Result<tokio::runtime::Runtime, std::io::Error> {
     tokio::runtime::Builder::new_multi_thread().enable_all().build()
 }
 
+/// Watches for further `ctrl-c` or `SIGTERM` once the first one started the shutdown of a command.
+///
+/// The second signal reports what the shutdown is still waiting for and how long it has been
+/// waiting. The third one, or the expiry of the shutdown timeout, flushes the logs and aborts the
+/// process.
+struct ShutdownWatcher {
+    handle: tokio::task::JoinHandle<()>,
+    command_exited: Arc<AtomicBool>,
+    log_guard: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
+}
+
+impl ShutdownWatcher {
+    /// Spawns the watcher onto the runtime, so that it keeps running while the thread that runs
+    /// the command is blocked.
+    fn spawn(
+        tokio_runtime: &tokio::runtime::Runtime,
+        executor: TaskExecutor,
+        shutdown_timeout: Option<Duration>,
+        log_guard: Option<Box<dyn Any + Send>>,
+    ) -> Self {
+        let command_exited = Arc::new(AtomicBool::new(false));
+        let log_guard = Arc::new(Mutex::new(log_guard));
+        let handle = tokio_runtime.spawn(watch_shutdown(
+            executor,
+            shutdown_timeout,
+            Arc::clone(&command_exited),
+            Arc::clone(&log_guard),
+        ));
+        Self { handle, command_exited, log_guard }
+    }
+
+    /// Marks the command future as resolved, so that only the remaining tasks are reported.
+    fn command_exited(&self) {
+        self.command_exited.store(true, Ordering::Relaxed);
+    }
+
+    /// Stops the watcher and flushes the logs.
+    fn finish(self) {
+        self.handle.abort();
+        drop(self.log_guard.lock().unwrap_or_else(PoisonError::into_inner).take());
+    }
+}
+
+/// See [`ShutdownWatcher`].
+async fn watch_shutdown(
+    executor: TaskExecutor,
+    shutdown_timeout: Option<Duration>,
+    command_exited: Arc<AtomicBool>,
+    log_guard: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
+) {
+    let Ok(mut signals) = ShutdownSignals::new() else { return };
+    if signals.recv().await.is_err() {
+        return
+    }
+    let started = Instant::now();
+
+    let timeout = async {
+        match shutdown_timeout {
+            Some(timeout) => tokio::time::sleep(timeout).await,
+            None => std::future::pending().await,
+        }
+    };
+    let mut timeout = pin!(timeout);
+
+    let mut received = 1;
+    loop {
+        tokio::select! {
+            res = signals.recv() => {
+                if res.is_err() {
+                    return
+                }
+                received += 1;
+                if received >= 3 {
+                    break
+                }
+
+                let elapsed = started.elapsed();
+                if command_exited.load(Ordering::Relaxed) {
+                    let pending = executor
+                        .pending_graceful_tasks()
+                        .into_iter()
+                        .map(|(name, count)| {
+                            if count > 1 { format!("{name} (x{count})") } else { name.to_string() }
+                        })
+                        .collect::<Vec<_>>();
+                    warn!(target: "reth::cli", ?elapsed, ?pending, "Still waiting for tasks to shut down, press ctrl-c again to force exit");
+                } else {
+                    warn!(target: "reth::cli", ?elapsed, "Still waiting for the command to exit, press ctrl-c again to force exit");
+                }
+            }
+            _ = &mut timeout => {
+                error!(target: "reth::cli", ?shutdown_timeout, "Shutdown timed out");
+                break
+            }
+        }
+    }
+
+    error!(target: "reth::cli", elapsed = ?started.elapsed(), "Forcing exit before the shutdown completed");
+    drop(log_guard.lock().unwrap_or_else(PoisonError::into_inner).take());
+    let _ = std::io::stdout().flush();
+    let _ = std::io::stderr().flush();
+    std::process::exit(1)
+}
+
+/// The signals that shut down the node: `ctrl-c` and `SIGTERM` (unix only).
+struct ShutdownSignals {
+    #[cfg(unix)]
+    sigterm: tokio::signal::unix::Signal,
+}
+
+impl ShutdownSignals {
+    /// Starts listening for the signals.
+    fn new() -> std::io::Result<Self> {
+        Ok(Self {
+            #[cfg(unix)]
+            sigterm: tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?,
+        })
+    }
+
+    /// Waits for the next signal of either kind.
+    async fn recv(&mut self) -> std::io::Result<()> {
+        #[cfg(unix)]
+        {
+            tokio::select! {
+                res = tokio::signal::ctrl_c() => res,
+                Some(()) = self.sigterm.recv() => Ok(()),
+            }
+        }
+
+        #[cfg(not(unix))]
+        {
+            tokio::signal::ctrl_c().await
+        }
+    }
+}
+
 /// Runs the given future to completion or until a critical task panicked.
 ///
 /// Returns the error if a task panicked, or the given future returned an error.
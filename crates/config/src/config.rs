@@ -41,6 +41,21 @@ impl Config {
     pub fn set_prune_config(&mut self, prune_config: PruneConfig) {
         self.prune = prune_config;
     }
+
+    /// Returns the [`PeersConfig`] for the node.
+    ///
+    /// If a peers file is provided, the peers and bans from the file are added to the
+    /// configuration, which requires the `serde` feature to read the file.
+    #[cfg(feature = "serde")]
+    pub fn peers_config_with_basic_nodes_from_file(
+        &self,
+        peers_file: Option<&Path>,
+    ) -> PeersConfig {
+        self.peers
+            .clone()
+            .with_basic_nodes_from_file(peers_file)
+            .unwrap_or_else(|_| self.peers.clone())
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -71,19 +86,6 @@ impl Config {
         }
     }
 
-    /// Returns the [`PeersConfig`] for the node.
-    ///
-    /// If a peers file is provided, the basic nodes from the file are added to the configuration.
-    pub fn peers_config_with_basic_nodes_from_file(
-        &self,
-        peers_file: Option<&Path>,
-    ) -> PeersConfig {
-        self.peers
-            .clone()
-            .with_basic_nodes_from_file(peers_file)
-            .unwrap_or_else(|_| self.peers.clone())
-    }
-
     /// Save the configuration to toml file.
     pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
         if path.extension() != Some(std::ffi::OsStr::new(EXTENSION)) {
@@ -275,12 +275,15 @@ mod tests {
     use super::*;
     use crate::test_rlp_utils::{create_fcu_json, generate_test_blocks, write_blocks_to_rlp};
     use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_cli_commands::import_core::ImportResult;
     use reth_db::mdbx::DatabaseArguments;
     use reth_payload_builder::EthPayloadBuilderAttributes;
     use reth_primitives::SealedBlock;
     use reth_provider::{
         test_utils::MockNodeTypesWithDB, BlockHashReader, BlockNumReader, BlockReaderIdExt,
+        StageCheckpointWriter,
     };
+    use reth_stages_types::StageCheckpoint;
     use std::path::PathBuf;
 
     #[tokio::test]
@@ -534,6 +537,152 @@ mod tests {
         assert_eq!(block_10_hash, test_blocks[9].hash(), "Block 10 hash should match");
     }
 
+    /// Helper to create a provider factory with an initialized genesis in the given directory
+    fn create_genesis_provider_factory(
+        chain_spec: &Arc<ChainSpec>,
+        datadir: &Path,
+    ) -> ProviderFactory<MockNodeTypesWithDB> {
+        let db_path = datadir.join("db");
+        std::fs::create_dir_all(&db_path).unwrap();
+        let db_env = reth_db::init_db(&db_path, DatabaseArguments::default()).unwrap();
+        let db = Arc::new(reth_db::test_utils::TempDatabase::new(db_env, db_path));
+
+        let provider_factory: ProviderFactory<MockNodeTypesWithDB> = ProviderFactory::new(
+            db,
+            chain_spec.clone(),
+            reth_provider::providers::StaticFileProvider::read_write(datadir.join("static_files"))
+                .unwrap(),
+            reth_provider::providers::RocksDBProvider::builder(datadir.join("rocksdb"))
+                .build()
+                .unwrap(),
+        )
+        .expect("failed to create provider factory");
+
+        reth_db_common::init::init_genesis(&provider_factory).unwrap();
+        provider_factory
+    }
+
+    /// Helper to import a file with the given import config
+    async fn import_file(
+        provider_factory: &ProviderFactory<MockNodeTypesWithDB>,
+        chain_spec: &Arc<ChainSpec>,
+        rlp_path: &Path,
+        import_config: ImportConfig,
+    ) -> eyre::Result<ImportResult> {
+        import_blocks_from_file(
+            rlp_path,
+            import_config,
+            provider_factory.clone(),
+            &Config::default(),
+            reth_node_ethereum::EthEvmConfig::new(chain_spec.clone()),
+            // Use NoopConsensus to skip gas limit validation for test imports
+            reth_consensus::noop::NoopConsensus::arc(),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_import_resume() {
+        // Tests resuming an import from a file whose first half is already in the database
+        reth_tracing::init_test_tracing();
+
+        let chain_spec = create_test_chain_spec();
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let (test_blocks, rlp_path) =
+            setup_test_blocks_and_rlp(&chain_spec, 10, temp_dir.path()).await;
+        let first_half_path = temp_dir.path().join("first_half.rlp");
+        write_blocks_to_rlp(&test_blocks[..5], &first_half_path).unwrap();
+
+        let provider_factory =
+            create_genesis_provider_factory(&chain_spec, &temp_dir.path().join("datadir"));
+
+        let result =
+            import_file(&provider_factory, &chain_spec, &first_half_path, ImportConfig::default())
+                .await
+                .unwrap();
+        assert_eq!(result.total_imported_blocks, 5);
+
+        let import_config = ImportConfig { resume: true, ..Default::default() };
+        let result =
+            import_file(&provider_factory, &chain_spec, &rlp_path, import_config).await.unwrap();
+        assert!(result.is_complete());
+        assert_eq!(result.total_decoded_blocks, 10);
+        assert_eq!(result.total_skipped_blocks, 5);
+        assert_eq!(result.total_imported_blocks, 5);
+
+        let provider = provider_factory.database_provider_ro().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 10);
+        assert_eq!(provider.block_hash(10).unwrap(), Some(test_blocks[9].hash()));
+    }
+
+    #[tokio::test]
+    async fn test_import_resume_interrupted() {
+        // Tests that resuming runs blocks that were imported, but did not go through all stages,
+        // through the remaining stages
+        reth_tracing::init_test_tracing();
+
+        let chain_spec = create_test_chain_spec();
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let (_, rlp_path) = setup_test_blocks_and_rlp(&chain_spec, 10, temp_dir.path()).await;
+
+        let provider_factory =
+            create_genesis_provider_factory(&chain_spec, &temp_dir.path().join("datadir"));
+        import_file(&provider_factory, &chain_spec, &rlp_path, ImportConfig::default())
+            .await
+            .unwrap();
+
+        // Pretend that the import was interrupted before block 6 went through the last stage
+        let provider = provider_factory.database_provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(5)).unwrap();
+        provider.commit().unwrap();
+
+        let import_config = ImportConfig { resume: true, ..Default::default() };
+        let result =
+            import_file(&provider_factory, &chain_spec, &rlp_path, import_config).await.unwrap();
+        assert_eq!(result.total_skipped_blocks, 5);
+
+        let finish = provider_factory.get_stage_checkpoint(StageId::Finish).unwrap();
+        assert_eq!(finish.map(|checkpoint| checkpoint.block_number), Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_import_resume_forked_file() {
+        // Tests that resuming fails if the file forks away from the local chain
+        reth_tracing::init_test_tracing();
+
+        let chain_spec = create_test_chain_spec();
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let (test_blocks, rlp_path) =
+            setup_test_blocks_and_rlp(&chain_spec, 5, temp_dir.path()).await;
+
+        let provider_factory =
+            create_genesis_provider_factory(&chain_spec, &temp_dir.path().join("datadir"));
+        import_file(&provider_factory, &chain_spec, &rlp_path, ImportConfig::default())
+            .await
+            .unwrap();
+
+        // Replace block 4 with a sibling, so that the file diverges from the local chain
+        let mut forked_blocks = test_blocks[..3].to_vec();
+        let mut header = test_blocks[3].header().clone();
+        header.extra_data = alloy_primitives::Bytes::from_static(b"fork");
+        forked_blocks.push(SealedBlock::seal_slow(reth_ethereum_primitives::Block {
+            header,
+            body: test_blocks[3].body().clone(),
+        }));
+        let forked_path = temp_dir.path().join("forked.rlp");
+        write_blocks_to_rlp(&forked_blocks, &forked_path).unwrap();
+
+        let import_config = ImportConfig { resume: true, ..Default::default() };
+        let err = import_file(&provider_factory, &chain_spec, &forked_path, import_config)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Block 4 of the file"), "unexpected error: {err}");
+
+        let provider = provider_factory.database_provider_ro().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 5);
+        assert_eq!(provider.block_hash(4).unwrap(), Some(test_blocks[3].hash()));
+    }
+
     #[tokio::test]
     async fn test_import_with_node_integration() {
         // Tests the full integration with node setup, forkchoice updates, and syncing
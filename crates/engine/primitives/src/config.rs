@@ -135,6 +135,8 @@ pub struct TreeConfig {
     storage_worker_count: usize,
     /// Number of account proof worker threads.
     account_worker_count: usize,
+    /// The highest block that downloaded blocks are executed up to, if any.
+    max_block: Option<u64>,
 }
 
 impl Default for TreeConfig {
@@ -163,6 +165,7 @@ impl Default for TreeConfig {
             allow_unwind_canonical_header: false,
             storage_worker_count: default_storage_worker_count(),
             account_worker_count: default_account_worker_count(),
+            max_block: None,
         }
     }
 }
@@ -219,6 +222,7 @@ impl TreeConfig {
             allow_unwind_canonical_header,
             storage_worker_count,
             account_worker_count,
+            max_block: None,
         }
     }
 
@@ -500,4 +504,15 @@ impl TreeConfig {
         self.account_worker_count = account_worker_count.max(MIN_WORKER_COUNT);
         self
     }
+
+    /// Return the highest block that downloaded blocks are executed up to.
+    pub const fn max_block(&self) -> Option<u64> {
+        self.max_block
+    }
+
+    /// Setter for the highest block that downloaded blocks are executed up to.
+    pub const fn with_max_block(mut self, max_block: Option<u64>) -> Self {
+        self.max_block = max_block;
+        self
+    }
 }
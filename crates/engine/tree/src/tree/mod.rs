@@ -462,6 +462,15 @@ where
         }
 
         trace!(target: "engine::tree", block_count = %blocks.len(), "received downloaded blocks");
+        if let Some(max_block) = self.config.max_block() {
+            blocks.retain(|block| {
+                let keep = block.number() <= max_block;
+                if !keep {
+                    debug!(target: "engine::tree", block = ?block.num_hash(), max_block, "Ignoring downloaded block above the max block");
+                }
+                keep
+            });
+        }
         let batch = self.config.max_execute_block_batch_size().min(blocks.len());
         for block in blocks.drain(..batch) {
             if let Some(event) = self.on_downloaded_block(block)? {
@@ -1560,6 +1569,12 @@ where
             self.canonical_in_memory_state.set_canonical_head(new_head);
         }
 
+        if self.config.max_block().is_some_and(|max_block| backfill_height >= max_block) {
+            // The chain must not advance any further, so there is nothing left to sync
+            info!(target: "engine::tree", backfill_height, "Backfill reached the max block");
+            return Ok(())
+        }
+
         // check if we need to run backfill again by comparing the most recent finalized height to
         // the backfill height
         let Some(sync_target_state) = self.state.forkchoice_state_tracker.sync_target_state()
@@ -8,6 +8,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+use alloy_primitives::BlockNumber;
 use futures::{Future, Stream};
 use reth_engine_primitives::BeaconEngineMessage;
 use reth_payload_primitives::PayloadTypes;
@@ -26,6 +27,9 @@ use skip_new_payload::EngineSkipNewPayload;
 pub mod reorg;
 use reorg::EngineReorg;
 
+pub mod max_block;
+use max_block::EngineMaxBlock;
+
 /// The result type for `maybe_reorg` method.
 type MaybeReorgResult<S, T, Provider, Evm, Validator, E> =
     Result<Either<EngineReorg<S, T, Provider, Evm, Validator>, S>, E>;
@@ -79,6 +83,35 @@ pub trait EngineMessageStreamExt<T: PayloadTypes>: Stream<Item = BeaconEngineMes
         }
     }
 
+    /// Answers new payloads above `max_block`, and forkchoice updates to them, with `SYNCING`.
+    fn max_block<Provider>(
+        self,
+        provider: Provider,
+        max_block: BlockNumber,
+    ) -> EngineMaxBlock<Self, Provider>
+    where
+        Self: Sized,
+    {
+        EngineMaxBlock::new(self, provider, max_block)
+    }
+
+    /// If a max block is provided, answers new payloads above it, and forkchoice updates to them,
+    /// with `SYNCING`.
+    fn maybe_max_block<Provider>(
+        self,
+        provider: Provider,
+        maybe_max_block: Option<BlockNumber>,
+    ) -> Either<EngineMaxBlock<Self, Provider>, Self>
+    where
+        Self: Sized,
+    {
+        if let Some(max_block) = maybe_max_block {
+            Either::Left(self.max_block(provider, max_block))
+        } else {
+            Either::Right(self)
+        }
+    }
+
     /// Stores engine messages at the specified location.
     fn store_messages(self, path: PathBuf) -> EngineStoreStream<Self>
     where
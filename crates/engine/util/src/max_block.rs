@@ -0,0 +1,126 @@
+//! Stream wrapper that stops the engine from advancing past a block.
+
+use alloy_primitives::{BlockNumber, B256};
+use alloy_rpc_types_engine::{PayloadStatus, PayloadStatusEnum};
+use futures::{Stream, StreamExt};
+use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload, OnForkChoiceUpdated};
+use reth_payload_primitives::PayloadTypes;
+use reth_storage_api::BlockNumReader;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+/// The number of hashes of rejected payloads that are remembered.
+const REJECTED_PAYLOADS_LIMIT: usize = 256;
+
+/// Engine API stream wrapper that answers messages that would advance the chain past the maximum
+/// block with `SYNCING`, instead of forwarding them to the engine.
+///
+/// New payloads above the maximum block are rejected, as well as forkchoice updates to one of
+/// them. Forkchoice updates to unknown blocks are forwarded while the local chain is below the
+/// maximum block, so that they can start a backfill sync, which is expected to be clamped to the
+/// maximum block.
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct EngineMaxBlock<S, Provider> {
+    #[pin]
+    stream: S,
+    provider: Provider,
+    /// The highest block that the engine is allowed to process.
+    max_block: BlockNumber,
+    /// Hashes of the latest rejected payloads.
+    rejected: VecDeque<B256>,
+    /// Whether a message was rejected already, to only warn about the first one.
+    reached: bool,
+}
+
+impl<S, Provider> EngineMaxBlock<S, Provider> {
+    /// Creates new [`EngineMaxBlock`] stream wrapper.
+    pub const fn new(stream: S, provider: Provider, max_block: BlockNumber) -> Self {
+        Self { stream, provider, max_block, rejected: VecDeque::new(), reached: false }
+    }
+}
+
+impl<S, Provider, T> Stream for EngineMaxBlock<S, Provider>
+where
+    S: Stream<Item = BeaconEngineMessage<T>>,
+    Provider: BlockNumReader,
+    T: PayloadTypes,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            let next = ready!(this.stream.poll_next_unpin(cx));
+            let item = match next {
+                Some(BeaconEngineMessage::NewPayload { payload, tx }) => {
+                    if payload.block_number() > *this.max_block {
+                        if !*this.reached {
+                            tracing::warn!(
+                                target: "engine::stream::max_block",
+                                max_block = *this.max_block,
+                                "Reached the maximum block, answering engine messages past it \
+                                 with SYNCING"
+                            );
+                            *this.reached = true;
+                        }
+                        tracing::debug!(
+                            target: "engine::stream::max_block",
+                            block_number = payload.block_number(),
+                            block_hash = %payload.block_hash(),
+                            "Rejecting new payload above the maximum block"
+                        );
+                        if this.rejected.len() == REJECTED_PAYLOADS_LIMIT {
+                            this.rejected.pop_front();
+                        }
+                        this.rejected.push_back(payload.block_hash());
+                        let _ = tx.send(Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing)));
+                        continue
+                    }
+                    Some(BeaconEngineMessage::NewPayload { payload, tx })
+                }
+                Some(BeaconEngineMessage::ForkchoiceUpdated {
+                    state,
+                    payload_attrs,
+                    tx,
+                    version,
+                }) => {
+                    let head = state.head_block_hash;
+                    let advances = if this.rejected.contains(&head) {
+                        true
+                    } else {
+                        match this.provider.block_number(head) {
+                            Ok(Some(number)) => number > *this.max_block,
+                            // An unknown head can only be synced to while below the maximum block
+                            Ok(None) => this
+                                .provider
+                                .best_block_number()
+                                .is_ok_and(|best| best >= *this.max_block),
+                            Err(err) => {
+                                tracing::debug!(target: "engine::stream::max_block", %err, "Failed to look up the forkchoice head");
+                                false
+                            }
+                        }
+                    };
+                    if advances {
+                        tracing::debug!(target: "engine::stream::max_block", ?state, "Rejecting forkchoice update past the maximum block");
+                        let _ = tx.send(Ok(OnForkChoiceUpdated::syncing()));
+                        continue
+                    }
+                    Some(BeaconEngineMessage::ForkchoiceUpdated {
+                        state,
+                        payload_attrs,
+                        tx,
+                        version,
+                    })
+                }
+                next => next,
+            };
+            return Poll::Ready(item)
+        }
+    }
+}
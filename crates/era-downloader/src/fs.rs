@@ -52,7 +52,13 @@ pub fn read_dir(
         checksums.next().transpose()?.ok_or_eyre("Got less checksums than ERA files")?;
     }
 
-    entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+    // Sort by path as well so that the order doesn't depend on the order of directory entries
+    entries.sort();
+    if let Some(((number, _), (_, path))) =
+        entries.iter().zip(entries.iter().skip(1)).find(|((left, _), (right, _))| left == right)
+    {
+        return Err(eyre!("Multiple ERA1 files with index {number}, e.g. {}", path.display()));
+    }
 
     Ok(stream::iter(entries.into_iter().skip_while(move |(n, _)| *n < start_index).map(
         move |(_, path)| {
@@ -102,3 +102,23 @@ async fn test_streaming_from_local_directory(
         },
     }
 }
+
+#[tokio::test]
+async fn test_duplicate_file_index_fails() {
+    let folder = tempfile::tempdir().unwrap();
+    let folder = folder.path().to_owned();
+
+    fs::write(folder.join("checksums.txt"), sha2::Sha256::digest(CONTENTS_0).encode_hex())
+        .await
+        .unwrap();
+    fs::write(folder.join("mainnet-00000-5ec1ffb8.era1"), CONTENTS_0).await.unwrap();
+    fs::write(folder.join("mainnet-00000-a5364e9a.era1"), CONTENTS_1).await.unwrap();
+
+    let actual = read_dir(folder.clone(), 0).map(|_| ()).unwrap_err().to_string();
+    let expected = format!(
+        "Multiple ERA1 files with index 0, e.g. {}",
+        folder.join("mainnet-00000-a5364e9a.era1").display()
+    );
+
+    assert_eq!(actual, expected);
+}
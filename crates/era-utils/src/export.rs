@@ -2,8 +2,8 @@
 //! and injecting them into era1 files with `Era1Writer`.
 
 use crate::calculate_td_by_number;
-use alloy_consensus::BlockHeader;
-use alloy_primitives::{BlockNumber, B256, U256};
+use alloy_consensus::{BlockHeader, TxReceipt};
+use alloy_primitives::{BlockNumber, Sealable, U256};
 use eyre::{eyre, Result};
 use reth_era::{
     common::file_ops::{EraFileId, StreamWriter},
@@ -165,11 +165,12 @@ where
         let mut offsets = Vec::<i64>::with_capacity(block_count);
         let mut position = VERSION_ENTRY_SIZE as i64;
         let mut blocks_written = 0;
-        let mut final_header_data = Vec::new();
+        let mut header_records = Vec::with_capacity(block_count);
 
         for (i, header) in headers.into_iter().enumerate() {
             let expected_block_number = start_block + i as u64;
 
+            let block_hash = header.hash_slow();
             let (compressed_header, compressed_body, compressed_receipts) = compress_block_data(
                 provider,
                 header,
@@ -177,12 +178,8 @@ where
                 &mut total_difficulty,
             )?;
 
-            // Save last block's header data for accumulator
-            if expected_block_number == end_block {
-                final_header_data = compressed_header.data.clone();
-            }
-
             let difficulty = TotalDifficulty::new(total_difficulty);
+            header_records.push((block_hash, difficulty.clone()));
 
             let header_size = compressed_header.data.len() + ENTRY_HEADER_SIZE;
             let body_size = compressed_body.data.len() + ENTRY_HEADER_SIZE;
@@ -217,9 +214,7 @@ where
             }
         }
         if blocks_written > 0 {
-            let accumulator_hash =
-                B256::from_slice(&final_header_data[0..32.min(final_header_data.len())]);
-            let accumulator = Accumulator::new(accumulator_hash);
+            let accumulator = Accumulator::from_header_records(header_records)?;
             let block_index = BlockIndex::new(start_block, offsets);
 
             writer.write_accumulator(&accumulator)?;
@@ -307,6 +302,8 @@ where
 
     let compressed_header = CompressedHeader::from_header(&header)?;
     let compressed_body = CompressedBody::from_body(&body)?;
+    // Receipts are stored with their bloom, like in the era1 files of other clients
+    let receipts = receipts.into_iter().map(TxReceipt::into_with_bloom).collect::<Vec<_>>();
     let compressed_receipts = CompressedReceipts::from_encodable_list(&receipts)
         .map_err(|e| eyre!("Failed to compress receipts: {}", e))?;
 
@@ -1,6 +1,8 @@
-use alloy_consensus::BlockHeader;
-use alloy_primitives::{BlockHash, BlockNumber, U256};
+use alloy_consensus::{BlockHeader, ReceiptWithBloom};
+use alloy_primitives::{hex, BlockHash, BlockNumber, Sealable, U256};
+use eyre::eyre;
 use futures_util::{Stream, StreamExt};
+use reth_chainspec::{Chain, EthChainSpec};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     table::Value,
@@ -12,17 +14,19 @@ use reth_era::{
     common::{decode::DecodeCompressedRlp, file_ops::StreamReader},
     e2s::error::E2sError,
     era1::{
+        accumulators::mainnet_accumulator_root_prefix,
         file::{BlockTupleIterator, Era1Reader},
-        types::execution::BlockTuple,
+        types::execution::{Accumulator, BlockTuple, MAX_BLOCKS_PER_ERA1},
     },
 };
 use reth_era_downloader::EraMeta;
 use reth_etl::Collector;
 use reth_fs_util as fs;
-use reth_primitives_traits::{Block, FullBlockBody, FullBlockHeader, NodePrimitives};
+use reth_primitives_traits::{Block, FullBlockBody, FullBlockHeader, NodePrimitives, ReceiptTy};
 use reth_provider::{
-    providers::StaticFileProviderRWRefMut, BlockReader, BlockWriter, StaticFileProviderFactory,
-    StaticFileSegment, StaticFileWriter,
+    providers::StaticFileProviderRWRefMut, BlockBodyIndicesProvider, BlockReader, BlockWriter,
+    ChainSpecProvider, EitherWriter, StaticFileProviderFactory, StaticFileSegment,
+    StaticFileWriter, StorageSettingsCache,
 };
 use reth_stages_types::{
     CheckpointBlockRange, EntitiesCheckpoint, HeadersCheckpoint, StageCheckpoint, StageId,
@@ -37,11 +41,17 @@ use std::{
     fmt::{Display, Formatter},
     io::{Read, Seek},
     iter::Map,
-    ops::RangeBounds,
+    ops::{RangeBounds, RangeInclusive},
     sync::mpsc,
 };
 use tracing::info;
 
+/// The stage whose checkpoint is the last block whose receipts were imported from era1 files.
+///
+/// The execution stage keeps these receipts instead of pruning them as leftovers of an unclean
+/// shutdown.
+pub const ERA_RECEIPTS_STAGE_ID: StageId = StageId::Other("EraReceipts");
+
 /// Imports blocks from `downloader` using `provider`.
 ///
 /// Returns current block height.
@@ -63,9 +73,13 @@ where
         ProviderRW: BlockWriter<Block = B>
             + DBProvider
             + StaticFileProviderFactory<Primitives: NodePrimitives<Block = B, BlockHeader = BH, BlockBody = BB>>
-            + StageCheckpointWriter,
-    > + StaticFileProviderFactory<Primitives = <<PF as DatabaseProviderFactory>::ProviderRW as NodePrimitivesProvider>::Primitives>,
+            + BlockBodyIndicesProvider
+            + StageCheckpointWriter
+            + StorageSettingsCache,
+    > + StaticFileProviderFactory<Primitives = <<PF as DatabaseProviderFactory>::ProviderRW as NodePrimitivesProvider>::Primitives>
+        + ChainSpecProvider<ChainSpec: EthChainSpec>,
 {
+    let chain = provider_factory.chain_spec().chain();
     let (tx, rx) = mpsc::channel();
 
     // Handle IO-bound async download in a background tokio task
@@ -85,11 +99,31 @@ where
         .unwrap_or_default();
 
     while let Some(meta) = rx.recv()? {
+        let meta = meta?;
+        let blocks = verify_accumulator(&meta, chain)?;
+        if *blocks.end() <= height {
+            info!(target: "era::history::import", file = %meta.path().display(), ?blocks, "Skipping already imported ERA1 file");
+            meta.mark_as_processed()?;
+            continue
+        }
+
         let from = height;
         let provider = provider_factory.database_provider_rw()?;
 
+        // Receipts are appended first, since the file may be removed once it's processed. Nodes
+        // that keep receipts in the database leave them to the execution stage.
+        let receipts = EitherWriter::receipts_destination(&provider).is_static_file();
+        if receipts {
+            process_receipts(
+                &meta,
+                &mut static_file_provider.latest_writer(StaticFileSegment::Receipts)?,
+                &provider,
+                height + 1..=*blocks.end(),
+            )?;
+        }
+
         height = process(
-            &meta?,
+            &meta,
             &mut static_file_provider.latest_writer(StaticFileSegment::Headers)?,
             &provider,
             hash_collector,
@@ -97,6 +131,9 @@ where
         )?;
 
         save_stage_checkpoints(&provider, from, height, height, height)?;
+        if receipts {
+            provider.save_stage_checkpoint(ERA_RECEIPTS_STAGE_ID, StageCheckpoint::new(height))?;
+        }
 
         provider.commit()?;
     }
@@ -110,6 +147,82 @@ where
     Ok(height)
 }
 
+/// Verifies that the accumulator of the era file described by `meta` is the root of the hashes and
+/// total difficulties of its blocks.
+///
+/// On mainnet, the accumulator also has to be the canonical accumulator of the epoch of the file,
+/// as far as its first four bytes are known from [`mainnet_accumulator_root_prefix`]. Files that
+/// don't start at an epoch are rejected there, since they have no canonical accumulator.
+///
+/// Returns the range of blocks in the file.
+pub fn verify_accumulator<Era>(
+    meta: &Era,
+    chain: Chain,
+) -> eyre::Result<RangeInclusive<BlockNumber>>
+where
+    Era: EraMeta + ?Sized,
+{
+    let mut iter = open(meta)?.iter();
+    let mut records = Vec::new();
+    let mut blocks = None::<RangeInclusive<BlockNumber>>;
+
+    for block in &mut iter {
+        let block = block?;
+        let header = block.header.decode_header()?;
+        let number = header.number;
+        blocks = match blocks {
+            None => Some(number..=number),
+            Some(blocks) if number == blocks.end() + 1 => Some(*blocks.start()..=number),
+            Some(blocks) => {
+                return Err(eyre!(
+                    "Block {number} does not follow block {} in {}",
+                    blocks.end(),
+                    meta.path().display()
+                ))
+            }
+        };
+        records.push((header.hash_slow(), block.total_difficulty));
+    }
+
+    let blocks = blocks.ok_or_else(|| eyre!("No blocks in {}", meta.path().display()))?;
+    let expected = iter
+        .accumulator()
+        .ok_or_else(|| eyre!("Missing accumulator in {}", meta.path().display()))?;
+    let actual = Accumulator::from_header_records(records)?;
+    if actual.root != expected.root {
+        return Err(eyre!(
+            "Accumulator mismatch in {}, got: {}, expected: {}",
+            meta.path().display(),
+            actual.root,
+            expected.root
+        ));
+    }
+
+    if chain == Chain::mainnet() {
+        let epoch = blocks.start() / MAX_BLOCKS_PER_ERA1 as u64;
+        let canonical = mainnet_accumulator_root_prefix(epoch)
+            .filter(|_| blocks.start().is_multiple_of(MAX_BLOCKS_PER_ERA1 as u64))
+            .ok_or_else(|| {
+                eyre!(
+                    "{} does not start at a pre-merge epoch of mainnet, got block {}",
+                    meta.path().display(),
+                    blocks.start()
+                )
+            })?;
+        if expected.root[..4] != canonical {
+            return Err(eyre!(
+                "Accumulator {} in {} is not canonical, expected one starting with 0x{} for epoch \
+                 {epoch}",
+                expected.root,
+                meta.path().display(),
+                hex::encode(canonical)
+            ));
+        }
+    }
+
+    Ok(blocks)
+}
+
 /// Saves progress of ERA import into stages sync.
 ///
 /// Since the ERA import does the same work as `HeaderStage` and `BodyStage`, it needs to inform
@@ -180,6 +293,45 @@ where
     process_iter(iter, writer, provider, hash_collector, block_numbers)
 }
 
+/// Extracts the receipts of the blocks in `block_numbers` from `meta` and appends them using
+/// `writer`.
+///
+/// The transaction numbers continue after the block before `block_numbers`, whose body has to be in
+/// `provider` already.
+pub fn process_receipts<Era, P>(
+    meta: &Era,
+    writer: &mut StaticFileProviderRWRefMut<'_, <P as NodePrimitivesProvider>::Primitives>,
+    provider: &P,
+    block_numbers: RangeInclusive<BlockNumber>,
+) -> eyre::Result<()>
+where
+    Era: EraMeta + ?Sized,
+    P: BlockBodyIndicesProvider + NodePrimitivesProvider,
+{
+    let parent = block_numbers.start().saturating_sub(1);
+    let mut next_tx_num = provider
+        .block_body_indices(parent)?
+        .ok_or_else(|| eyre!("Missing body of block {parent}"))?
+        .next_tx_num();
+
+    for block in open(meta)?.iter() {
+        let block = block?;
+        let number = block.header.decode_header()?.number;
+        if !block_numbers.contains(&number) {
+            continue
+        }
+
+        let receipts: Vec<ReceiptWithBloom<ReceiptTy<P::Primitives>>> = block.receipts.decode()?;
+        writer.increment_block(number)?;
+        for ReceiptWithBloom { receipt, .. } in receipts {
+            writer.append_receipt(next_tx_num, &receipt)?;
+            next_tx_num += 1;
+        }
+    }
+
+    Ok(())
+}
+
 type ProcessInnerIter<R, BH, BB> =
     Map<BlockTupleIterator<R>, Box<dyn Fn(Result<BlockTuple, E2sError>) -> eyre::Result<(BH, BB)>>>;
 
@@ -15,5 +15,6 @@ pub use export::{export, ExportConfig};
 /// Imports history from ERA files.
 pub use history::{
     build_index, calculate_td_by_number, decode, import, open, process, process_iter,
-    save_stage_checkpoints, ProcessIter,
+    process_receipts, save_stage_checkpoints, verify_accumulator, ProcessIter,
+    ERA_RECEIPTS_STAGE_ID,
 };
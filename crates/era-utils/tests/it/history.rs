@@ -3,10 +3,14 @@ use reqwest::{Client, Url};
 use reth_db_common::init::init_genesis;
 use reth_era::era1::types::execution::MAX_BLOCKS_PER_ERA1;
 use reth_era_downloader::{EraClient, EraStream, EraStreamConfig};
-use reth_era_utils::{export, import, ExportConfig};
+use reth_era_utils::{export, import, ExportConfig, ERA_RECEIPTS_STAGE_ID};
 use reth_etl::Collector;
 use reth_fs_util as fs;
-use reth_provider::{test_utils::create_test_provider_factory, BlockNumReader, BlockReader};
+use reth_provider::{
+    test_utils::create_test_provider_factory, BlockNumReader, BlockReader, StageCheckpointReader,
+    StaticFileProviderFactory, StaticFileSegment,
+};
+use reth_stages_types::StageCheckpoint;
 use std::str::FromStr;
 use tempfile::tempdir;
 
@@ -41,6 +45,16 @@ async fn test_history_imports_from_fresh_state_successfully() {
     let actual_block_number = import(stream, &pf, &mut hash_collector).unwrap();
 
     assert_eq!(actual_block_number, expected_block_number);
+
+    // The receipts are imported up to the same block
+    assert_eq!(
+        pf.static_file_provider().get_highest_static_file_block(StaticFileSegment::Receipts),
+        Some(expected_block_number)
+    );
+    assert_eq!(
+        pf.provider().unwrap().get_stage_checkpoint(ERA_RECEIPTS_STAGE_ID).unwrap(),
+        Some(StageCheckpoint::new(expected_block_number))
+    );
 }
 
 /// Test that verifies the complete roundtrip from importing to exporting era1 files.
@@ -0,0 +1,1927 @@
+//! Canonical accumulator roots of the mainnet era1 files.
+
+use alloy_primitives::hex;
+
+/// The first four bytes of the accumulator root of every mainnet era1 file, indexed by epoch.
+///
+/// These are the short roots that name the era1 files distributed for the pre-merge history of
+/// mainnet, e.g. `mainnet-00000-5ec1ffb8.era1`.
+pub const MAINNET_ACCUMULATOR_ROOT_PREFIXES: [[u8; 4]; 1897] = [
+    hex!("5ec1ffb8"),
+    hex!("a5364e9a"),
+    hex!("98cbd8a9"),
+    hex!("d8b8a40b"),
+    hex!("6e3baba7"),
+    hex!("5cff5a4b"),
+    hex!("678fb793"),
+    hex!("d9bc682b"),
+    hex!("12c9605f"),
+    hex!("f9e4e890"),
+    hex!("5f5d4516"),
+    hex!("30f04eb9"),
+    hex!("5ecb9bf9"),
+    hex!("d0175c1e"),
+    hex!("4f92d781"),
+    hex!("a47cb8eb"),
+    hex!("9344d8b7"),
+    hex!("43963724"),
+    hex!("efce27b4"),
+    hex!("f5434352"),
+    hex!("0c405203"),
+    hex!("20d8f1af"),
+    hex!("b694d895"),
+    hex!("11beacba"),
+    hex!("f216a28a"),
+    hex!("987cb620"),
+    hex!("3afd50ff"),
+    hex!("28083285"),
+    hex!("362fc97c"),
+    hex!("a0cb99e2"),
+    hex!("78fc5e8e"),
+    hex!("52306cf9"),
+    hex!("cb4d0c3a"),
+    hex!("0c3781bb"),
+    hex!("fac9315a"),
+    hex!("737e0757"),
+    hex!("84c7c1e7"),
+    hex!("34d06765"),
+    hex!("38aaf94c"),
+    hex!("4ad4940c"),
+    hex!("4707f60d"),
+    hex!("a6a87a9e"),
+    hex!("5c8dca3c"),
+    hex!("cb513d91"),
+    hex!("1c72a390"),
+    hex!("a87afdc1"),
+    hex!("22b3f78d"),
+    hex!("92d84372"),
+    hex!("78ae53ed"),
+    hex!("3934e960"),
+    hex!("71698ebf"),
+    hex!("2c1c7778"),
+    hex!("3a047d9a"),
+    hex!("161ee1b5"),
+    hex!("14074ce7"),
+    hex!("ae639ad8"),
+    hex!("d92c394c"),
+    hex!("4bbe776e"),
+    hex!("9ac60ed8"),
+    hex!("246c1b33"),
+    hex!("4533d0c5"),
+    hex!("122db1c2"),
+    hex!("65505079"),
+    hex!("39b21911"),
+    hex!("db26a83c"),
+    hex!("1df3a40f"),
+    hex!("07bde22d"),
+    hex!("751bac83"),
+    hex!("546a10c1"),
+    hex!("55c236ed"),
+    hex!("0ac3ebb1"),
+    hex!("2fb03713"),
+    hex!("2f9a4a75"),
+    hex!("32871a43"),
+    hex!("8aacdeee"),
+    hex!("368ce2b1"),
+    hex!("290a4131"),
+    hex!("5d736b0a"),
+    hex!("98ac3e9d"),
+    hex!("95e0734e"),
+    hex!("d778ae86"),
+    hex!("2089ffc8"),
+    hex!("382ac3bc"),
+    hex!("5ab1cca2"),
+    hex!("9ec0497d"),
+    hex!("f847bef9"),
+    hex!("0dd5eb92"),
+    hex!("c0612d68"),
+    hex!("3f433e63"),
+    hex!("0432ee13"),
+    hex!("cf23b0c8"),
+    hex!("9fbb0197"),
+    hex!("f88ab15f"),
+    hex!("b7499b8c"),
+    hex!("5c45bbb1"),
+    hex!("a3dea11d"),
+    hex!("91ff33f7"),
+    hex!("8e4ffd2b"),
+    hex!("4c5709af"),
+    hex!("d55b92c1"),
+    hex!("bb39d00a"),
+    hex!("e6df1942"),
+    hex!("2aac9e21"),
+    hex!("3fe56c2a"),
+    hex!("f3269b30"),
+    hex!("6f2f86e8"),
+    hex!("621a2527"),
+    hex!("c3e8f7cd"),
+    hex!("be1a1635"),
+    hex!("49424c3e"),
+    hex!("af29e604"),
+    hex!("1738246f"),
+    hex!("0074a32e"),
+    hex!("56e19130"),
+    hex!("10c2a569"),
+    hex!("26efb6b7"),
+    hex!("bdf3bc6e"),
+    hex!("e68e5334"),
+    hex!("1c9a2437"),
+    hex!("5fc6b689"),
+    hex!("cfefc65f"),
+    hex!("0e29b6cd"),
+    hex!("cddbda3f"),
+    hex!("7717d395"),
+    hex!("2e66a66a"),
+    hex!("82fdaab1"),
+    hex!("427637ff"),
+    hex!("65e302f1"),
+    hex!("6fdc1fed"),
+    hex!("c8705b6e"),
+    hex!("650f1d51"),
+    hex!("7b4435ca"),
+    hex!("23bd7603"),
+    hex!("c0faccfc"),
+    hex!("ec9cf3ec"),
+    hex!("66f5486b"),
+    hex!("dc4ebb94"),
+    hex!("02298200"),
+    hex!("3d231400"),
+    hex!("5f316611"),
+    hex!("f13c9fc0"),
+    hex!("17dbb0e3"),
+    hex!("e42539c8"),
+    hex!("5594a813"),
+    hex!("5ac2296f"),
+    hex!("9244d418"),
+    hex!("af9e7a53"),
+    hex!("1b6fa332"),
+    hex!("39f0b057"),
+    hex!("586c4a91"),
+    hex!("3ff89a4a"),
+    hex!("e322efe1"),
+    hex!("4d9d0d1c"),
+    hex!("6b5702b3"),
+    hex!("13608c12"),
+    hex!("d0bf6ce2"),
+    hex!("b8c3629c"),
+    hex!("97351a9d"),
+    hex!("5cee8f8c"),
+    hex!("b92f84e9"),
+    hex!("3e41d9f5"),
+    hex!("0736b99a"),
+    hex!("0a1530da"),
+    hex!("43212d3b"),
+    hex!("3744a01c"),
+    hex!("3d14fc84"),
+    hex!("97c25925"),
+    hex!("11485002"),
+    hex!("88a365fa"),
+    hex!("f18242e7"),
+    hex!("f03ea8ee"),
+    hex!("bec8ccd9"),
+    hex!("34a4c5d2"),
+    hex!("e1ae6c81"),
+    hex!("727d10b8"),
+    hex!("1bf21dcc"),
+    hex!("ecc872aa"),
+    hex!("966bdbf2"),
+    hex!("b412f22c"),
+    hex!("09e40d3b"),
+    hex!("8ce22357"),
+    hex!("ac1cf418"),
+    hex!("f770e12a"),
+    hex!("798224ae"),
+    hex!("876fdbe5"),
+    hex!("7b447a76"),
+    hex!("f1e11ac5"),
+    hex!("ce8b009f"),
+    hex!("c7bd816d"),
+    hex!("144cc97d"),
+    hex!("cbbcca51"),
+    hex!("3a7f6484"),
+    hex!("8a4639c3"),
+    hex!("52629553"),
+    hex!("c4828a4b"),
+    hex!("f50a165e"),
+    hex!("5c31919b"),
+    hex!("9e2a709b"),
+    hex!("c3e781bf"),
+    hex!("20c05ee3"),
+    hex!("dc265ad9"),
+    hex!("1356f2da"),
+    hex!("84e86114"),
+    hex!("f2da7b50"),
+    hex!("7f42b43f"),
+    hex!("afd08f8e"),
+    hex!("cf4a2c65"),
+    hex!("59a0e883"),
+    hex!("3d0bbaf2"),
+    hex!("c1041285"),
+    hex!("1eb673ab"),
+    hex!("01e5a4d0"),
+    hex!("83c3bb0a"),
+    hex!("9a09fe68"),
+    hex!("9e78dc12"),
+    hex!("b02bf96a"),
+    hex!("209c8b32"),
+    hex!("6c84f49a"),
+    hex!("acb60f14"),
+    hex!("0a773645"),
+    hex!("76ed2324"),
+    hex!("76ddd2d8"),
+    hex!("71e986b2"),
+    hex!("ca2b5c28"),
+    hex!("ff571e13"),
+    hex!("1cfe3239"),
+    hex!("b0eb8f2f"),
+    hex!("83556e9f"),
+    hex!("7651e7fb"),
+    hex!("238ed788"),
+    hex!("3826affa"),
+    hex!("b81f93c6"),
+    hex!("0cda2a75"),
+    hex!("b183167b"),
+    hex!("4a88300d"),
+    hex!("05ef6143"),
+    hex!("4164fdf4"),
+    hex!("d5c9eed4"),
+    hex!("db0d90c5"),
+    hex!("a0c972ed"),
+    hex!("9cc1a86a"),
+    hex!("8ab5ad43"),
+    hex!("491f232e"),
+    hex!("f66eb348"),
+    hex!("7b141f13"),
+    hex!("ef5b96a5"),
+    hex!("32d63aa1"),
+    hex!("7b033a6c"),
+    hex!("50916052"),
+    hex!("75e0db34"),
+    hex!("4781ac7f"),
+    hex!("c0f8b33b"),
+    hex!("afa72ca2"),
+    hex!("96c6ccb3"),
+    hex!("7b32050e"),
+    hex!("16317cf9"),
+    hex!("b4ccb50b"),
+    hex!("98808d16"),
+    hex!("3cf61b1c"),
+    hex!("051764d2"),
+    hex!("3b0545e8"),
+    hex!("a6f4bfca"),
+    hex!("1a75662f"),
+    hex!("21bf7eee"),
+    hex!("d41eb83d"),
+    hex!("e7a19561"),
+    hex!("4f172aab"),
+    hex!("7c0e1bcf"),
+    hex!("c839e6d4"),
+    hex!("9ec52dfb"),
+    hex!("4e8bc727"),
+    hex!("5294c75d"),
+    hex!("02a11db2"),
+    hex!("d81a2c41"),
+    hex!("9c4bd87d"),
+    hex!("85b9c67d"),
+    hex!("38cc1236"),
+    hex!("40c70f95"),
+    hex!("d9e0d738"),
+    hex!("d20a7b7f"),
+    hex!("54a85faa"),
+    hex!("f08b9749"),
+    hex!("a5912776"),
+    hex!("8ee7ec35"),
+    hex!("374996f2"),
+    hex!("94942585"),
+    hex!("6d47a234"),
+    hex!("f9804151"),
+    hex!("3724a8c9"),
+    hex!("60d72bd9"),
+    hex!("64cca80b"),
+    hex!("0dfa92f2"),
+    hex!("94905988"),
+    hex!("0d6c5812"),
+    hex!("f6c5c94a"),
+    hex!("4efa78d0"),
+    hex!("81c1446a"),
+    hex!("08d13a31"),
+    hex!("3d1d6d89"),
+    hex!("23728d43"),
+    hex!("de033253"),
+    hex!("15a24df9"),
+    hex!("ba653536"),
+    hex!("35fde006"),
+    hex!("377bf395"),
+    hex!("340a0b81"),
+    hex!("848e3d92"),
+    hex!("a3ff7916"),
+    hex!("48c7160c"),
+    hex!("e7948131"),
+    hex!("db22eaaf"),
+    hex!("f9e4fff9"),
+    hex!("d95903d0"),
+    hex!("73f91876"),
+    hex!("8e339794"),
+    hex!("e83123f0"),
+    hex!("e49c7af7"),
+    hex!("f3ae6a62"),
+    hex!("4bf5c84a"),
+    hex!("391837c4"),
+    hex!("bfd4677e"),
+    hex!("7189f496"),
+    hex!("344663aa"),
+    hex!("e111bfc2"),
+    hex!("be2b4b22"),
+    hex!("441fdc44"),
+    hex!("42979360"),
+    hex!("820afe74"),
+    hex!("08665862"),
+    hex!("cc85d0d9"),
+    hex!("8ffabe96"),
+    hex!("57c9aa44"),
+    hex!("3135b734"),
+    hex!("ef368f00"),
+    hex!("1c3457cf"),
+    hex!("b191a95e"),
+    hex!("b5318d5c"),
+    hex!("a84d51fe"),
+    hex!("f0b5744f"),
+    hex!("5a637c4c"),
+    hex!("6bd16b95"),
+    hex!("e0d9d5cb"),
+    hex!("203bc599"),
+    hex!("a668f92e"),
+    hex!("9cfd6013"),
+    hex!("54595ee7"),
+    hex!("2297e35e"),
+    hex!("db8912b3"),
+    hex!("1dde5ab9"),
+    hex!("7e25d7fe"),
+    hex!("2bce5715"),
+    hex!("112b61e1"),
+    hex!("2ffd764d"),
+    hex!("8d5ed81a"),
+    hex!("78c57cc6"),
+    hex!("55973475"),
+    hex!("c7493a6e"),
+    hex!("e5dd9171"),
+    hex!("60ed8451"),
+    hex!("eeb9573d"),
+    hex!("277ba2fe"),
+    hex!("fc97c47f"),
+    hex!("77f810e7"),
+    hex!("056ef66f"),
+    hex!("374faafa"),
+    hex!("a959566e"),
+    hex!("905c4a52"),
+    hex!("ed4e7dab"),
+    hex!("b4a84335"),
+    hex!("1f3be833"),
+    hex!("ccf6672e"),
+    hex!("1aa762ac"),
+    hex!("05d7f23c"),
+    hex!("9a832205"),
+    hex!("dab73b8e"),
+    hex!("e97d6f9f"),
+    hex!("6af73957"),
+    hex!("fa0bd020"),
+    hex!("85b101eb"),
+    hex!("fbe01c0b"),
+    hex!("3e90265a"),
+    hex!("0b316492"),
+    hex!("a02e585d"),
+    hex!("2198573b"),
+    hex!("0c367f63"),
+    hex!("8893c8da"),
+    hex!("30637c5e"),
+    hex!("069b5e28"),
+    hex!("82890633"),
+    hex!("def916eb"),
+    hex!("00f64677"),
+    hex!("60554823"),
+    hex!("01eb08ec"),
+    hex!("a5b5b2ed"),
+    hex!("c96f2c65"),
+    hex!("c7c79169"),
+    hex!("98f01c73"),
+    hex!("ece428f5"),
+    hex!("15d52476"),
+    hex!("07f40278"),
+    hex!("837a78da"),
+    hex!("f319bb85"),
+    hex!("28918ded"),
+    hex!("8d3e10d5"),
+    hex!("729d063e"),
+    hex!("3857def7"),
+    hex!("7be7d5cd"),
+    hex!("0a5da5b2"),
+    hex!("4ef48eb4"),
+    hex!("e4148ff7"),
+    hex!("b195d1d0"),
+    hex!("ab2766df"),
+    hex!("4fcf3d8c"),
+    hex!("371970b0"),
+    hex!("7525f2ed"),
+    hex!("4820498a"),
+    hex!("55dcafa7"),
+    hex!("9a836cea"),
+    hex!("c5248447"),
+    hex!("aa00e844"),
+    hex!("1a718263"),
+    hex!("b7a77195"),
+    hex!("05e01b82"),
+    hex!("0c5c2035"),
+    hex!("7d31cd1a"),
+    hex!("737fc200"),
+    hex!("b042cd22"),
+    hex!("a5f8fadb"),
+    hex!("2247adf4"),
+    hex!("1cae28d5"),
+    hex!("ab59cee6"),
+    hex!("ed24090f"),
+    hex!("ecfb2f47"),
+    hex!("ea10cb3f"),
+    hex!("ed8823c8"),
+    hex!("1acee0de"),
+    hex!("c3510bf4"),
+    hex!("f2dcc620"),
+    hex!("00f5e21d"),
+    hex!("5149508d"),
+    hex!("9feb9189"),
+    hex!("3f1832ca"),
+    hex!("1d30de4a"),
+    hex!("ea71b6f9"),
+    hex!("c56da958"),
+    hex!("02cff3d7"),
+    hex!("2280f1cf"),
+    hex!("3cff32e3"),
+    hex!("7dd2c4f1"),
+    hex!("6d1d274b"),
+    hex!("2b5e1149"),
+    hex!("4abe0e07"),
+    hex!("42606107"),
+    hex!("23cdebe0"),
+    hex!("b2b5e5f9"),
+    hex!("54943c8b"),
+    hex!("cab07908"),
+    hex!("82d06ed1"),
+    hex!("2bff3cd7"),
+    hex!("29e03017"),
+    hex!("6f2ddb12"),
+    hex!("86535bae"),
+    hex!("3658342e"),
+    hex!("4f586d74"),
+    hex!("2da57d6c"),
+    hex!("afdd52d6"),
+    hex!("21cf05fb"),
+    hex!("27685b9d"),
+    hex!("610466b6"),
+    hex!("8c5751e2"),
+    hex!("2e445b03"),
+    hex!("7f375623"),
+    hex!("5f279db6"),
+    hex!("5ae48eae"),
+    hex!("8b70d9c8"),
+    hex!("a8e53747"),
+    hex!("dd34a7c1"),
+    hex!("488d852f"),
+    hex!("232a4fc9"),
+    hex!("4b082f24"),
+    hex!("b54b802e"),
+    hex!("213ae81b"),
+    hex!("5d09d6ee"),
+    hex!("f3c11c6e"),
+    hex!("cdc41b84"),
+    hex!("b26f2e53"),
+    hex!("82b8c438"),
+    hex!("d834f157"),
+    hex!("fa3989a5"),
+    hex!("4db390c5"),
+    hex!("b9ad2e4d"),
+    hex!("6aa98f9f"),
+    hex!("ebdcd70d"),
+    hex!("3079f625"),
+    hex!("33f97b59"),
+    hex!("ee3904ae"),
+    hex!("cdf13c18"),
+    hex!("27f7ad95"),
+    hex!("fed48beb"),
+    hex!("d9c9a733"),
+    hex!("b11653db"),
+    hex!("b202cc73"),
+    hex!("19ef7cc0"),
+    hex!("45f20620"),
+    hex!("21e3a8f2"),
+    hex!("6dd1ba56"),
+    hex!("c0414076"),
+    hex!("a7340f2e"),
+    hex!("dde4c38d"),
+    hex!("fa5dc42a"),
+    hex!("ea2b8d47"),
+    hex!("d5d2416f"),
+    hex!("e2918e50"),
+    hex!("d39eefad"),
+    hex!("8bd8f6bd"),
+    hex!("66d4642e"),
+    hex!("ed3b1187"),
+    hex!("5ef487b2"),
+    hex!("71027029"),
+    hex!("218c3b62"),
+    hex!("062e1719"),
+    hex!("5b55367f"),
+    hex!("dbca1ed3"),
+    hex!("297cf9d5"),
+    hex!("f389f3b6"),
+    hex!("d851e822"),
+    hex!("c69972a1"),
+    hex!("2c103256"),
+    hex!("32a674ae"),
+    hex!("1bf711d1"),
+    hex!("aab9d4e3"),
+    hex!("0f51dbca"),
+    hex!("b4c6703d"),
+    hex!("bb2932ed"),
+    hex!("c65d109d"),
+    hex!("bb367122"),
+    hex!("faadd066"),
+    hex!("576b374c"),
+    hex!("d8eab6c1"),
+    hex!("2c32d06d"),
+    hex!("f3078a9b"),
+    hex!("3063ad60"),
+    hex!("90a242f8"),
+    hex!("be9dd353"),
+    hex!("1c5c7615"),
+    hex!("e6369239"),
+    hex!("21351413"),
+    hex!("0fd9e031"),
+    hex!("e318be79"),
+    hex!("2dde72e5"),
+    hex!("d89b717d"),
+    hex!("a8dfd860"),
+    hex!("d6a3521a"),
+    hex!("f702584d"),
+    hex!("700d58da"),
+    hex!("41db6d14"),
+    hex!("3503dd53"),
+    hex!("02391085"),
+    hex!("ed670fa9"),
+    hex!("63277435"),
+    hex!("e89160d7"),
+    hex!("f95c755f"),
+    hex!("97a6fdba"),
+    hex!("8aa6ac0e"),
+    hex!("ba8486b8"),
+    hex!("7cba894e"),
+    hex!("54b3e34b"),
+    hex!("40280a85"),
+    hex!("ad863b1e"),
+    hex!("dcb283ea"),
+    hex!("fb912362"),
+    hex!("b65f3342"),
+    hex!("dc59179d"),
+    hex!("21aec308"),
+    hex!("55e72fc9"),
+    hex!("f6564eb5"),
+    hex!("923096d4"),
+    hex!("5914605d"),
+    hex!("57d591a8"),
+    hex!("705b8e04"),
+    hex!("5b210184"),
+    hex!("d3dc3f99"),
+    hex!("d6b2c7ef"),
+    hex!("43400160"),
+    hex!("21b2682b"),
+    hex!("02f0b151"),
+    hex!("60d868cb"),
+    hex!("59cc91f7"),
+    hex!("efefa87c"),
+    hex!("85c8dc20"),
+    hex!("2bd79cac"),
+    hex!("d9b21bd9"),
+    hex!("9dd2dc6a"),
+    hex!("0a9de411"),
+    hex!("54bb5026"),
+    hex!("08fa2659"),
+    hex!("5e2423f9"),
+    hex!("49b11d14"),
+    hex!("050d0b97"),
+    hex!("a19b1a20"),
+    hex!("a81ae85f"),
+    hex!("9e26205a"),
+    hex!("28713614"),
+    hex!("9a115bef"),
+    hex!("ba62e002"),
+    hex!("0d936f16"),
+    hex!("ae859b84"),
+    hex!("62d75fce"),
+    hex!("d449c48d"),
+    hex!("7e60445e"),
+    hex!("2f230cba"),
+    hex!("290b8c2b"),
+    hex!("b363f647"),
+    hex!("80a4144c"),
+    hex!("20913bde"),
+    hex!("8b6e7a1e"),
+    hex!("bd35481a"),
+    hex!("624ad401"),
+    hex!("c70c6f7e"),
+    hex!("1b807ced"),
+    hex!("85065023"),
+    hex!("385fbb21"),
+    hex!("92fb5e57"),
+    hex!("642081dd"),
+    hex!("1d72d627"),
+    hex!("f7ca7cb8"),
+    hex!("4f92dd26"),
+    hex!("06e70723"),
+    hex!("313cbe43"),
+    hex!("e939c151"),
+    hex!("1fbd3e9a"),
+    hex!("333c0583"),
+    hex!("bd6027f3"),
+    hex!("2fc5d74d"),
+    hex!("5b3c9d59"),
+    hex!("95e4dede"),
+    hex!("f8124b1e"),
+    hex!("12daf758"),
+    hex!("75957ec2"),
+    hex!("7c4f218a"),
+    hex!("3cdc6132"),
+    hex!("471f9e80"),
+    hex!("551764bd"),
+    hex!("368d4f6f"),
+    hex!("81a7c3da"),
+    hex!("2582b644"),
+    hex!("7fd3548a"),
+    hex!("07e93c81"),
+    hex!("01a981a6"),
+    hex!("05bf160d"),
+    hex!("119bef0c"),
+    hex!("4d3f0b82"),
+    hex!("730975c7"),
+    hex!("2eb89f05"),
+    hex!("51b0bde3"),
+    hex!("06381406"),
+    hex!("0c072423"),
+    hex!("4b0dfe65"),
+    hex!("eafc91b6"),
+    hex!("49e7399f"),
+    hex!("376add4f"),
+    hex!("a8a70e6e"),
+    hex!("dffecb3c"),
+    hex!("6c4ef261"),
+    hex!("3eb2993d"),
+    hex!("8f406786"),
+    hex!("4ed7ee7f"),
+    hex!("a6b898d4"),
+    hex!("c4a9d166"),
+    hex!("d8d97842"),
+    hex!("be3afc5c"),
+    hex!("bf138a26"),
+    hex!("bf106779"),
+    hex!("defe7756"),
+    hex!("c6dc6388"),
+    hex!("1e729aa1"),
+    hex!("a5855d98"),
+    hex!("f3e17cb0"),
+    hex!("42e73724"),
+    hex!("f0fc97a5"),
+    hex!("a6045d36"),
+    hex!("247222f0"),
+    hex!("a6b3aedb"),
+    hex!("19fcceed"),
+    hex!("388b0bdc"),
+    hex!("a2bcc3dd"),
+    hex!("df515337"),
+    hex!("89774425"),
+    hex!("a979df6f"),
+    hex!("33e5fa30"),
+    hex!("7c95061f"),
+    hex!("062b4177"),
+    hex!("945645ca"),
+    hex!("8c6754fd"),
+    hex!("222e02bc"),
+    hex!("cebc5a0e"),
+    hex!("f73fca1b"),
+    hex!("cc3b1831"),
+    hex!("062461df"),
+    hex!("7dcae2e8"),
+    hex!("64f029a9"),
+    hex!("c0ae757b"),
+    hex!("abe351f7"),
+    hex!("ddee734c"),
+    hex!("2ea464e9"),
+    hex!("9f0bc9d6"),
+    hex!("58568eed"),
+    hex!("6aca1899"),
+    hex!("2478502c"),
+    hex!("07426af0"),
+    hex!("a70b753c"),
+    hex!("1146e2f9"),
+    hex!("560a8119"),
+    hex!("54d9396e"),
+    hex!("8979cc56"),
+    hex!("1a583ca6"),
+    hex!("4850905c"),
+    hex!("f510edaa"),
+    hex!("511c0a35"),
+    hex!("14409e14"),
+    hex!("cf91e8a7"),
+    hex!("aa869587"),
+    hex!("96e85fdb"),
+    hex!("78bf01d3"),
+    hex!("d7c27830"),
+    hex!("8859c97f"),
+    hex!("a705da9b"),
+    hex!("58d83126"),
+    hex!("2daec931"),
+    hex!("fe141ac7"),
+    hex!("4ab51ad5"),
+    hex!("11b0f603"),
+    hex!("3e648126"),
+    hex!("87195980"),
+    hex!("1a924a1b"),
+    hex!("34d9d6a7"),
+    hex!("1569f88b"),
+    hex!("288181ee"),
+    hex!("28dbb0e5"),
+    hex!("4bd79b88"),
+    hex!("c84ee780"),
+    hex!("2aacc622"),
+    hex!("165aa7d2"),
+    hex!("4505bedc"),
+    hex!("08785ac9"),
+    hex!("6ea3cb82"),
+    hex!("3ec58c85"),
+    hex!("aaaebe5b"),
+    hex!("5ab3b222"),
+    hex!("27aebfb9"),
+    hex!("cc08abf8"),
+    hex!("032ebacd"),
+    hex!("f09c12bf"),
+    hex!("876733dd"),
+    hex!("f50f5eb2"),
+    hex!("4d52bb12"),
+    hex!("0af4e42b"),
+    hex!("d1988f79"),
+    hex!("c66fcb07"),
+    hex!("a6242a1c"),
+    hex!("824af748"),
+    hex!("76b41584"),
+    hex!("b3ed5869"),
+    hex!("713aaaf6"),
+    hex!("3a6de7d9"),
+    hex!("18b5d602"),
+    hex!("6b5ade6c"),
+    hex!("a0084763"),
+    hex!("8dc563b6"),
+    hex!("efd6696f"),
+    hex!("d9db47f4"),
+    hex!("2aa70a70"),
+    hex!("44dcb9ab"),
+    hex!("e4dcc8e8"),
+    hex!("63f82331"),
+    hex!("f24f282e"),
+    hex!("382525f1"),
+    hex!("2d3a5e7a"),
+    hex!("9a24e966"),
+    hex!("0be7242c"),
+    hex!("0b8c7a94"),
+    hex!("6e9ae080"),
+    hex!("e888e6c2"),
+    hex!("03027295"),
+    hex!("b6efb516"),
+    hex!("79728b75"),
+    hex!("0586eef4"),
+    hex!("a0d4c8c2"),
+    hex!("a55feb48"),
+    hex!("9b95d3aa"),
+    hex!("4a62280e"),
+    hex!("064776da"),
+    hex!("e9a29542"),
+    hex!("93ac12ba"),
+    hex!("b199a309"),
+    hex!("284915ec"),
+    hex!("80b8a598"),
+    hex!("40e902aa"),
+    hex!("25bb39c7"),
+    hex!("565a1bf8"),
+    hex!("6723d6d9"),
+    hex!("2c9057ba"),
+    hex!("75e347ad"),
+    hex!("afb8c20f"),
+    hex!("472b0432"),
+    hex!("a00ad773"),
+    hex!("4fabbfd5"),
+    hex!("ebadaa84"),
+    hex!("71a5a039"),
+    hex!("2a78afe8"),
+    hex!("ddc91137"),
+    hex!("47305f69"),
+    hex!("6492e812"),
+    hex!("9ccf4c43"),
+    hex!("02f878d6"),
+    hex!("e97a4d00"),
+    hex!("13c60a90"),
+    hex!("6f8e5552"),
+    hex!("8143cae1"),
+    hex!("3894e28c"),
+    hex!("97b69aec"),
+    hex!("22732c18"),
+    hex!("6977fa0d"),
+    hex!("0d164934"),
+    hex!("0de9eb6c"),
+    hex!("14eaae63"),
+    hex!("e0848f10"),
+    hex!("6846201d"),
+    hex!("e89c0e11"),
+    hex!("3096af76"),
+    hex!("55e8b4c2"),
+    hex!("c501837e"),
+    hex!("f7000808"),
+    hex!("5d73b97a"),
+    hex!("f240decc"),
+    hex!("fbd0a368"),
+    hex!("fbd2c4fe"),
+    hex!("8bdc3304"),
+    hex!("2a2047ce"),
+    hex!("84c88273"),
+    hex!("b96ae20a"),
+    hex!("5cfe6abd"),
+    hex!("b7d8f3a6"),
+    hex!("c14d7f51"),
+    hex!("c5eea731"),
+    hex!("aea57615"),
+    hex!("be228f4a"),
+    hex!("5721c5a9"),
+    hex!("4f17a7ba"),
+    hex!("81731fd5"),
+    hex!("c8596aa5"),
+    hex!("d9d76d29"),
+    hex!("3db9cab9"),
+    hex!("05eacb14"),
+    hex!("78744453"),
+    hex!("b042cdda"),
+    hex!("c3dd963b"),
+    hex!("1caf90ed"),
+    hex!("7d89c8e7"),
+    hex!("ec9f0685"),
+    hex!("608f5138"),
+    hex!("bc713924"),
+    hex!("f5d2654f"),
+    hex!("29f0d1bd"),
+    hex!("ba09ed24"),
+    hex!("ef98f25e"),
+    hex!("62ec875a"),
+    hex!("6e38f92d"),
+    hex!("ada339e0"),
+    hex!("687f70ac"),
+    hex!("69305b66"),
+    hex!("dfb48357"),
+    hex!("1fe0bd68"),
+    hex!("6754774c"),
+    hex!("89ed1e98"),
+    hex!("918e70e4"),
+    hex!("b174fe36"),
+    hex!("1ee06c60"),
+    hex!("acf82dea"),
+    hex!("194ae199"),
+    hex!("88dc77d7"),
+    hex!("291ee430"),
+    hex!("e0bd01b9"),
+    hex!("24ee5653"),
+    hex!("f4649173"),
+    hex!("18be5807"),
+    hex!("7b8e7f87"),
+    hex!("ebfbdc02"),
+    hex!("2911ec46"),
+    hex!("7bc66f0a"),
+    hex!("de4cc0f1"),
+    hex!("1aa1a5b9"),
+    hex!("b5354846"),
+    hex!("75d76eee"),
+    hex!("81aa556e"),
+    hex!("47043527"),
+    hex!("1ab5749f"),
+    hex!("b81f63bc"),
+    hex!("ac96c902"),
+    hex!("922b1cf4"),
+    hex!("0f8ce285"),
+    hex!("f6f06a90"),
+    hex!("65bd2e95"),
+    hex!("3b916edf"),
+    hex!("2ebf7c0d"),
+    hex!("9e7868aa"),
+    hex!("6fe8a5a9"),
+    hex!("fc681d3f"),
+    hex!("65f73397"),
+    hex!("38ce4f9f"),
+    hex!("d44a929f"),
+    hex!("fc91f464"),
+    hex!("9acf8a7e"),
+    hex!("07907337"),
+    hex!("822dfb1a"),
+    hex!("62602cad"),
+    hex!("8b6dd223"),
+    hex!("b309c469"),
+    hex!("8974583a"),
+    hex!("d139d7b4"),
+    hex!("5b83da49"),
+    hex!("7a62f3b8"),
+    hex!("30f2071b"),
+    hex!("9f05e151"),
+    hex!("8d0da501"),
+    hex!("9de1930e"),
+    hex!("ac17e41e"),
+    hex!("ad6865ca"),
+    hex!("3feef311"),
+    hex!("6e2d1f4b"),
+    hex!("228684d7"),
+    hex!("2485471e"),
+    hex!("f2f69e07"),
+    hex!("44eff5ae"),
+    hex!("ac8cf5be"),
+    hex!("c052cdd4"),
+    hex!("0d1ce1ba"),
+    hex!("20d08362"),
+    hex!("fb915a95"),
+    hex!("2d5f2cb9"),
+    hex!("31a360b2"),
+    hex!("6247e765"),
+    hex!("c352aa92"),
+    hex!("b8b31db9"),
+    hex!("2a8be083"),
+    hex!("80961bb9"),
+    hex!("caefe64b"),
+    hex!("ccd88aff"),
+    hex!("11d91c25"),
+    hex!("209f845f"),
+    hex!("4a9dede7"),
+    hex!("6493fcf8"),
+    hex!("611a2c46"),
+    hex!("81e7d017"),
+    hex!("19895bb2"),
+    hex!("443f9153"),
+    hex!("3dcb543b"),
+    hex!("a2d2aa39"),
+    hex!("24f3f044"),
+    hex!("8fc647c4"),
+    hex!("680241a4"),
+    hex!("759690ad"),
+    hex!("12b37dfb"),
+    hex!("2b4168b7"),
+    hex!("a7136656"),
+    hex!("8a62b405"),
+    hex!("73e45b2c"),
+    hex!("39942459"),
+    hex!("dce8aff6"),
+    hex!("49be4756"),
+    hex!("68c57c6a"),
+    hex!("c4f6b046"),
+    hex!("13a7fe3f"),
+    hex!("135d2ae1"),
+    hex!("922bb3c2"),
+    hex!("27b6441b"),
+    hex!("63eafa02"),
+    hex!("05bdc732"),
+    hex!("95d7750a"),
+    hex!("8cf0d624"),
+    hex!("a8768f5c"),
+    hex!("c4e8ddcf"),
+    hex!("d4ecd7c3"),
+    hex!("d39b640f"),
+    hex!("22592d52"),
+    hex!("e4e144f9"),
+    hex!("b5e7db59"),
+    hex!("9316c767"),
+    hex!("871302ef"),
+    hex!("1c3a7616"),
+    hex!("a666b389"),
+    hex!("3154c955"),
+    hex!("d078def2"),
+    hex!("6b2615f1"),
+    hex!("6ed3f90f"),
+    hex!("d3223ce7"),
+    hex!("016c769b"),
+    hex!("e19a4c9d"),
+    hex!("36c9ff39"),
+    hex!("83c72fa2"),
+    hex!("ddcc6036"),
+    hex!("2d0fc419"),
+    hex!("f69f7b39"),
+    hex!("17632710"),
+    hex!("fbfc0216"),
+    hex!("584687a4"),
+    hex!("df8970e8"),
+    hex!("2fbbff92"),
+    hex!("235c9a91"),
+    hex!("fa09b4f1"),
+    hex!("3cacc95c"),
+    hex!("dbcb3c64"),
+    hex!("6ac002dd"),
+    hex!("dd1763be"),
+    hex!("c4e0e059"),
+    hex!("d2d13e5e"),
+    hex!("a93277ff"),
+    hex!("019e6db0"),
+    hex!("763ea921"),
+    hex!("45d27951"),
+    hex!("37e8f37e"),
+    hex!("6654a92d"),
+    hex!("b81a65c1"),
+    hex!("b72e8700"),
+    hex!("75f6d852"),
+    hex!("cd13479c"),
+    hex!("1ff6a9b9"),
+    hex!("6c76c020"),
+    hex!("78f99056"),
+    hex!("3dcb5e2a"),
+    hex!("373e75f1"),
+    hex!("f06e62b5"),
+    hex!("3f19793e"),
+    hex!("a9765c1c"),
+    hex!("1dcfe017"),
+    hex!("946b550f"),
+    hex!("b953b4b7"),
+    hex!("8356dd44"),
+    hex!("3cefbeb9"),
+    hex!("4765ee42"),
+    hex!("c559659c"),
+    hex!("1b1f9767"),
+    hex!("ed5a8fb5"),
+    hex!("a396fcbd"),
+    hex!("33bd34bd"),
+    hex!("e62bdcaa"),
+    hex!("37db60cd"),
+    hex!("d02ff344"),
+    hex!("27c44206"),
+    hex!("d74dcd43"),
+    hex!("583482c4"),
+    hex!("b1375fb5"),
+    hex!("fc68a5db"),
+    hex!("038d0dc5"),
+    hex!("fc630d10"),
+    hex!("dc564afc"),
+    hex!("75fba5d5"),
+    hex!("f2a347f3"),
+    hex!("7e00f70a"),
+    hex!("7c557845"),
+    hex!("d6b98869"),
+    hex!("7edcc464"),
+    hex!("84fae3df"),
+    hex!("97a50adf"),
+    hex!("25e68a39"),
+    hex!("88ea91bd"),
+    hex!("77a58ff5"),
+    hex!("95926dd9"),
+    hex!("9e4b22f0"),
+    hex!("5451d56c"),
+    hex!("dd746cbf"),
+    hex!("e7162b85"),
+    hex!("a0266c31"),
+    hex!("fb390415"),
+    hex!("7d668fd5"),
+    hex!("38bef7ff"),
+    hex!("55f5a07a"),
+    hex!("61a13d92"),
+    hex!("227e7127"),
+    hex!("f1a39e59"),
+    hex!("1b7d0990"),
+    hex!("679b4359"),
+    hex!("5e7dea25"),
+    hex!("cdcb8e56"),
+    hex!("5f2ca307"),
+    hex!("12230dff"),
+    hex!("6eb5d461"),
+    hex!("45541bfe"),
+    hex!("09f2f43e"),
+    hex!("4519917d"),
+    hex!("506362e6"),
+    hex!("408e7c24"),
+    hex!("4f1216d3"),
+    hex!("f7943fda"),
+    hex!("a2458aba"),
+    hex!("c3174a59"),
+    hex!("5475a30e"),
+    hex!("d2ce1e42"),
+    hex!("5fb12ec4"),
+    hex!("1cd92052"),
+    hex!("22b095d4"),
+    hex!("844b63b3"),
+    hex!("bcce4dd3"),
+    hex!("c9711792"),
+    hex!("5035027c"),
+    hex!("9331be3b"),
+    hex!("901078cb"),
+    hex!("f8edc0d4"),
+    hex!("42362545"),
+    hex!("bc521cea"),
+    hex!("199eeafe"),
+    hex!("d3446282"),
+    hex!("1c93ba5b"),
+    hex!("beafcef8"),
+    hex!("71ec790b"),
+    hex!("6f6094bf"),
+    hex!("053afb71"),
+    hex!("98396ff4"),
+    hex!("57479712"),
+    hex!("1554c473"),
+    hex!("5db300fa"),
+    hex!("b9aeb88a"),
+    hex!("a8849157"),
+    hex!("0b7bcf33"),
+    hex!("224f4405"),
+    hex!("0b6064b3"),
+    hex!("c7344cf2"),
+    hex!("ad12ba38"),
+    hex!("3a386e6d"),
+    hex!("c77dcea6"),
+    hex!("a25a8fc1"),
+    hex!("0225209e"),
+    hex!("1ba9f77c"),
+    hex!("8e1c1d63"),
+    hex!("3197a1d6"),
+    hex!("d28f2c9f"),
+    hex!("6cf8d26d"),
+    hex!("62215680"),
+    hex!("44e4142c"),
+    hex!("1a70666e"),
+    hex!("9bef85d3"),
+    hex!("48066cf2"),
+    hex!("112745d1"),
+    hex!("c07f5625"),
+    hex!("ff3489f3"),
+    hex!("aa9a507e"),
+    hex!("ae05fa5d"),
+    hex!("0dca795f"),
+    hex!("b970027b"),
+    hex!("b2f6f517"),
+    hex!("ae9dea31"),
+    hex!("1cb2b8d4"),
+    hex!("28b41e38"),
+    hex!("9cc1f6f0"),
+    hex!("1d470ae1"),
+    hex!("b516307f"),
+    hex!("8c4c1119"),
+    hex!("d7bb8fbd"),
+    hex!("12ec0bec"),
+    hex!("4fafaf52"),
+    hex!("d0e1b363"),
+    hex!("caff5303"),
+    hex!("070dea60"),
+    hex!("d285b66e"),
+    hex!("2eb4c50f"),
+    hex!("ff4e71c2"),
+    hex!("4116850a"),
+    hex!("5b698645"),
+    hex!("a23912b4"),
+    hex!("0737b4e5"),
+    hex!("df35982e"),
+    hex!("cc7466dd"),
+    hex!("9abdb4ae"),
+    hex!("cdbdfbc6"),
+    hex!("d79c75b8"),
+    hex!("0d2dbfd7"),
+    hex!("94adbb4c"),
+    hex!("d5e0dc5a"),
+    hex!("5c34962d"),
+    hex!("1de75523"),
+    hex!("aac82868"),
+    hex!("58996635"),
+    hex!("74b8aa44"),
+    hex!("38d85671"),
+    hex!("2a82d902"),
+    hex!("528d123a"),
+    hex!("dcefe6dd"),
+    hex!("25b5519c"),
+    hex!("a8eec328"),
+    hex!("09125340"),
+    hex!("13085b0b"),
+    hex!("e8d4138f"),
+    hex!("7a4ee217"),
+    hex!("7c6dae3a"),
+    hex!("7d063ead"),
+    hex!("68a0b792"),
+    hex!("94c96976"),
+    hex!("a6c39440"),
+    hex!("f7a5ab53"),
+    hex!("84b9e12a"),
+    hex!("a6b6a963"),
+    hex!("4a0c7f03"),
+    hex!("5486c645"),
+    hex!("08a5313b"),
+    hex!("97510371"),
+    hex!("7bbd5580"),
+    hex!("61e11a8c"),
+    hex!("37459490"),
+    hex!("4d0cc348"),
+    hex!("2008dcc1"),
+    hex!("842d4195"),
+    hex!("a9c13697"),
+    hex!("a6435d49"),
+    hex!("71e5659c"),
+    hex!("1791e683"),
+    hex!("b250520d"),
+    hex!("a13eb7c2"),
+    hex!("5c83a392"),
+    hex!("c13220ea"),
+    hex!("5f56b030"),
+    hex!("512900cb"),
+    hex!("90c06f46"),
+    hex!("096acd86"),
+    hex!("1a1baa66"),
+    hex!("34f529a2"),
+    hex!("ad7198e0"),
+    hex!("2d858029"),
+    hex!("bb9ed6f3"),
+    hex!("024d8b09"),
+    hex!("4fbd23d8"),
+    hex!("b70e3603"),
+    hex!("76e468cb"),
+    hex!("7cd7b651"),
+    hex!("d077ca78"),
+    hex!("ccf5a0f7"),
+    hex!("d7666847"),
+    hex!("3d10b175"),
+    hex!("cfc709d6"),
+    hex!("f6f1e083"),
+    hex!("cbeef9b0"),
+    hex!("02688fe8"),
+    hex!("20ebd652"),
+    hex!("ceb89cc1"),
+    hex!("6ca6a14a"),
+    hex!("40769f6c"),
+    hex!("f2f56ea6"),
+    hex!("c0e897c0"),
+    hex!("58d2fe58"),
+    hex!("e2cdcc7c"),
+    hex!("379da93b"),
+    hex!("afede008"),
+    hex!("eeede50f"),
+    hex!("c5b81847"),
+    hex!("96748f67"),
+    hex!("5c3badc9"),
+    hex!("2599ae05"),
+    hex!("2b44aee4"),
+    hex!("4952c512"),
+    hex!("713aba14"),
+    hex!("1ae7b5ef"),
+    hex!("6f90c321"),
+    hex!("61d416a8"),
+    hex!("aee21385"),
+    hex!("9fa06b72"),
+    hex!("53a8cc3b"),
+    hex!("1366aabf"),
+    hex!("2631621e"),
+    hex!("3dd94158"),
+    hex!("ed99cb91"),
+    hex!("3e65584f"),
+    hex!("69ccf142"),
+    hex!("66fbd4f1"),
+    hex!("7f448c5e"),
+    hex!("3db6b5ee"),
+    hex!("63adf378"),
+    hex!("0c89bb3e"),
+    hex!("399bff64"),
+    hex!("1b14df37"),
+    hex!("7bb5b295"),
+    hex!("4ba003f3"),
+    hex!("2ab6865f"),
+    hex!("a5598247"),
+    hex!("8357bfd9"),
+    hex!("b502eea2"),
+    hex!("f9cc2497"),
+    hex!("5ad4fdc8"),
+    hex!("4de64a70"),
+    hex!("1cd8e301"),
+    hex!("461721aa"),
+    hex!("d776cb85"),
+    hex!("873eb969"),
+    hex!("3466a370"),
+    hex!("f7c7ef3a"),
+    hex!("eab36fbd"),
+    hex!("d38b96b7"),
+    hex!("9a8e547a"),
+    hex!("044263f3"),
+    hex!("2a6fa840"),
+    hex!("25e8cecf"),
+    hex!("4783a5b4"),
+    hex!("05128f56"),
+    hex!("f8ad3107"),
+    hex!("564f335c"),
+    hex!("0dd93fff"),
+    hex!("20eaa74f"),
+    hex!("d00fc022"),
+    hex!("1f67a41c"),
+    hex!("2f77e7c8"),
+    hex!("8eaf029a"),
+    hex!("0e9134c9"),
+    hex!("36d0712a"),
+    hex!("b196f52d"),
+    hex!("052a3539"),
+    hex!("ed3d98e8"),
+    hex!("6711d975"),
+    hex!("c90257cc"),
+    hex!("d23ae50b"),
+    hex!("b2c5b1b5"),
+    hex!("803d224b"),
+    hex!("f7082c22"),
+    hex!("66c7f004"),
+    hex!("04af06fa"),
+    hex!("44c4c046"),
+    hex!("570d0caa"),
+    hex!("d4cb7a0d"),
+    hex!("b10b22ec"),
+    hex!("ea52129a"),
+    hex!("4f88b085"),
+    hex!("83f1204e"),
+    hex!("572dcecf"),
+    hex!("45db98fb"),
+    hex!("79ac113e"),
+    hex!("2e02ff93"),
+    hex!("1c8da535"),
+    hex!("9983596d"),
+    hex!("4143f16c"),
+    hex!("2c80acab"),
+    hex!("70afebf0"),
+    hex!("0d957f43"),
+    hex!("a954d4bb"),
+    hex!("d259e3db"),
+    hex!("a393c46b"),
+    hex!("febdbdcd"),
+    hex!("c205cffc"),
+    hex!("e55d1bc5"),
+    hex!("18129517"),
+    hex!("62600923"),
+    hex!("b7bc048b"),
+    hex!("63f08fbe"),
+    hex!("d8f90a76"),
+    hex!("3b7ca4ef"),
+    hex!("a5aeba3b"),
+    hex!("75faba09"),
+    hex!("53743d70"),
+    hex!("dcc29cd5"),
+    hex!("73301488"),
+    hex!("c5358154"),
+    hex!("6ae35c6d"),
+    hex!("40401985"),
+    hex!("4f6b2658"),
+    hex!("d0b1559c"),
+    hex!("ef5b459d"),
+    hex!("08dfe9e3"),
+    hex!("80f7dfd0"),
+    hex!("4b47710e"),
+    hex!("cc79e6c6"),
+    hex!("d76553f6"),
+    hex!("d7efc68f"),
+    hex!("17ea70af"),
+    hex!("f4a498df"),
+    hex!("027e2a2d"),
+    hex!("b90ef17e"),
+    hex!("b4719483"),
+    hex!("b999269e"),
+    hex!("662f1591"),
+    hex!("108ccbef"),
+    hex!("54d29629"),
+    hex!("6d7bcae5"),
+    hex!("fd477f0b"),
+    hex!("57b72b9a"),
+    hex!("beacd779"),
+    hex!("4b6c0027"),
+    hex!("485b0ed3"),
+    hex!("402cc664"),
+    hex!("547da0f4"),
+    hex!("058c55b0"),
+    hex!("951ddf6c"),
+    hex!("85a7797d"),
+    hex!("64376e67"),
+    hex!("58445b6b"),
+    hex!("6a2d6ce4"),
+    hex!("330e88ac"),
+    hex!("3bf42cf0"),
+    hex!("239fa3d0"),
+    hex!("76707dbf"),
+    hex!("4bcaea6c"),
+    hex!("cc5b5c9a"),
+    hex!("ce3fa54e"),
+    hex!("d06c9a04"),
+    hex!("e131599e"),
+    hex!("1a628757"),
+    hex!("e7d4a880"),
+    hex!("85d3f303"),
+    hex!("e1e41aa6"),
+    hex!("eba27a23"),
+    hex!("b0ddf49d"),
+    hex!("651ac24a"),
+    hex!("a76ac2e0"),
+    hex!("209624ba"),
+    hex!("af729a25"),
+    hex!("a53b25ca"),
+    hex!("00cba9ae"),
+    hex!("da3d50bf"),
+    hex!("fe77f727"),
+    hex!("1053d23c"),
+    hex!("1f9ee408"),
+    hex!("162769df"),
+    hex!("6904f505"),
+    hex!("66837954"),
+    hex!("55bb8c9a"),
+    hex!("37cb2c92"),
+    hex!("05b6cf1b"),
+    hex!("cf249616"),
+    hex!("8500cec0"),
+    hex!("f9b23ca9"),
+    hex!("2e9e77d2"),
+    hex!("8c071005"),
+    hex!("53b8caf6"),
+    hex!("68be6c9f"),
+    hex!("12a34f39"),
+    hex!("ff0a7be9"),
+    hex!("64d4fc5b"),
+    hex!("2205cdfc"),
+    hex!("e4ccf743"),
+    hex!("77202058"),
+    hex!("467addf0"),
+    hex!("8bd916a8"),
+    hex!("332b343d"),
+    hex!("595d9cbe"),
+    hex!("aed3cc6a"),
+    hex!("41f0520f"),
+    hex!("e2a38fbc"),
+    hex!("ef37dd9b"),
+    hex!("145a661e"),
+    hex!("9821beb8"),
+    hex!("728597bd"),
+    hex!("5ad80991"),
+    hex!("6b884cac"),
+    hex!("869cb3c7"),
+    hex!("5cac1bcb"),
+    hex!("b9278411"),
+    hex!("32f5e0e5"),
+    hex!("b43138d2"),
+    hex!("34bcded8"),
+    hex!("11f80fd3"),
+    hex!("18ea77d0"),
+    hex!("f1aadb7c"),
+    hex!("d00e9e6f"),
+    hex!("7ffab11d"),
+    hex!("6039b280"),
+    hex!("8e889e14"),
+    hex!("773c0e11"),
+    hex!("df1babfe"),
+    hex!("6d328022"),
+    hex!("1c7eb43c"),
+    hex!("10ffef9e"),
+    hex!("7785bbd4"),
+    hex!("b09a9b8e"),
+    hex!("1919baf8"),
+    hex!("6338bf7f"),
+    hex!("46fb7a9b"),
+    hex!("18fb7c8f"),
+    hex!("47a32b9d"),
+    hex!("333b7e86"),
+    hex!("fe24e3c1"),
+    hex!("81c78370"),
+    hex!("857187a1"),
+    hex!("ad00e0b4"),
+    hex!("8994eaa1"),
+    hex!("cf4abf0e"),
+    hex!("ae5f5367"),
+    hex!("a121c10e"),
+    hex!("2110d171"),
+    hex!("56ec2844"),
+    hex!("ba8ad4f1"),
+    hex!("c56c3ef6"),
+    hex!("57bc4104"),
+    hex!("122d8e0f"),
+    hex!("42b25fd3"),
+    hex!("b1acea4e"),
+    hex!("34280f11"),
+    hex!("503bfdfd"),
+    hex!("1b74997d"),
+    hex!("edfb26fb"),
+    hex!("da405d13"),
+    hex!("3efe0cda"),
+    hex!("809b00b0"),
+    hex!("c6923521"),
+    hex!("2ef9dbc9"),
+    hex!("23a65ef9"),
+    hex!("17e97c49"),
+    hex!("38815f39"),
+    hex!("c31ef6bf"),
+    hex!("c5393ba2"),
+    hex!("b478f53b"),
+    hex!("b8013f87"),
+    hex!("f23ee995"),
+    hex!("21b534cf"),
+    hex!("9fbff7e7"),
+    hex!("e5141a21"),
+    hex!("87aec91b"),
+    hex!("b041bb19"),
+    hex!("e41b5ac2"),
+    hex!("56a66926"),
+    hex!("898d4f37"),
+    hex!("935b5d7a"),
+    hex!("4ad5c3e3"),
+    hex!("be94d5d0"),
+    hex!("40fa62d3"),
+    hex!("110e8af3"),
+    hex!("6e412e20"),
+    hex!("8ffe28ad"),
+    hex!("e93cd0a0"),
+    hex!("77f19add"),
+    hex!("f583c727"),
+    hex!("326bed46"),
+    hex!("558f5bae"),
+    hex!("17af0f4e"),
+    hex!("b3d28e9b"),
+    hex!("6ce3df5c"),
+    hex!("70b295e4"),
+    hex!("5694754d"),
+    hex!("b7d62fb8"),
+    hex!("1c4ea22b"),
+    hex!("3f50bd7f"),
+    hex!("56e96bf3"),
+    hex!("4c0b5a25"),
+    hex!("b84376f6"),
+    hex!("a859e797"),
+    hex!("b8f73328"),
+    hex!("5075b4c4"),
+    hex!("d9a68897"),
+    hex!("4c44c323"),
+    hex!("ba0d8406"),
+    hex!("b730d9fb"),
+    hex!("2a36dd0a"),
+    hex!("cb6390d2"),
+    hex!("7be29447"),
+    hex!("d42b2e59"),
+    hex!("3259acc6"),
+    hex!("15c4efe6"),
+    hex!("2d99a1dc"),
+    hex!("ccd34c1e"),
+    hex!("0f3371da"),
+    hex!("6acbe79c"),
+    hex!("975db585"),
+    hex!("f8d968d9"),
+    hex!("5250d82f"),
+    hex!("8817dc1d"),
+    hex!("16cbfdac"),
+    hex!("cbaf55ad"),
+    hex!("a12fc593"),
+    hex!("310c6496"),
+    hex!("5345e3d4"),
+    hex!("2280e4c1"),
+    hex!("893942f1"),
+    hex!("237ef759"),
+    hex!("1aec3b6e"),
+    hex!("fc92fcf6"),
+    hex!("eacd9d2b"),
+    hex!("a22bf288"),
+    hex!("1a439d07"),
+    hex!("947ddced"),
+    hex!("3a831bed"),
+    hex!("9028d18b"),
+    hex!("598f030e"),
+    hex!("f9043297"),
+    hex!("8a587dfc"),
+    hex!("43c1dcc3"),
+    hex!("92a983f2"),
+    hex!("1c243107"),
+    hex!("1f943824"),
+    hex!("de1cad89"),
+    hex!("2d72b6e4"),
+    hex!("393498cd"),
+    hex!("fa098e6d"),
+    hex!("379cb7fe"),
+    hex!("62641130"),
+    hex!("63224c34"),
+    hex!("a972b2e4"),
+    hex!("1370376c"),
+    hex!("3c5940a5"),
+    hex!("fdcf0f05"),
+    hex!("7779354b"),
+    hex!("fc12f95b"),
+    hex!("2118c3d0"),
+    hex!("f94bdd17"),
+    hex!("aeb7c436"),
+    hex!("f56b793b"),
+    hex!("6cf982a2"),
+    hex!("c6a9ee35"),
+    hex!("0497bfd9"),
+    hex!("9a50fff5"),
+    hex!("a1d88a26"),
+    hex!("00e0c17d"),
+    hex!("8fec155e"),
+    hex!("7642a7d3"),
+    hex!("0a337fdb"),
+    hex!("2b8f0227"),
+    hex!("ceafb201"),
+    hex!("99fdde4b"),
+    hex!("0d642ed6"),
+    hex!("01a109f9"),
+    hex!("409c216e"),
+    hex!("608abfc5"),
+    hex!("546296f9"),
+    hex!("210f4a03"),
+    hex!("f300016d"),
+    hex!("d126355b"),
+    hex!("fccf39ee"),
+    hex!("62719ac2"),
+    hex!("d410e2d7"),
+    hex!("3a2643d1"),
+    hex!("b86b68f1"),
+    hex!("79abcea6"),
+    hex!("9d2445ad"),
+    hex!("a0719f02"),
+    hex!("3825eb62"),
+    hex!("49d1a71d"),
+    hex!("1ec93093"),
+    hex!("d52d9f3e"),
+    hex!("438a5c23"),
+    hex!("b811a1ab"),
+    hex!("2dba253d"),
+    hex!("c0975217"),
+    hex!("926f0429"),
+    hex!("88bc22af"),
+    hex!("d10229ae"),
+    hex!("f60a479f"),
+    hex!("45574d29"),
+    hex!("f4d925c6"),
+    hex!("05561645"),
+    hex!("bffe5d04"),
+    hex!("db04ce69"),
+    hex!("836dd3c2"),
+    hex!("ccdd4d37"),
+    hex!("df78d1aa"),
+    hex!("bc1a2596"),
+    hex!("ba444550"),
+    hex!("c772985e"),
+    hex!("0cd44f84"),
+    hex!("108b8139"),
+    hex!("92dc53a4"),
+    hex!("ac1c8ccb"),
+    hex!("2f466ff6"),
+    hex!("4dc5edd2"),
+    hex!("72d321b5"),
+    hex!("d0684723"),
+    hex!("651f1c70"),
+    hex!("0013c08b"),
+    hex!("7825d5b2"),
+    hex!("84e21383"),
+    hex!("a5681587"),
+    hex!("c84283fa"),
+    hex!("ab3af7a0"),
+    hex!("d0adeec0"),
+    hex!("827de27e"),
+    hex!("558e2125"),
+    hex!("8d75bc10"),
+    hex!("84ccda7e"),
+    hex!("3490d679"),
+    hex!("4533f7b3"),
+    hex!("abf10629"),
+    hex!("d67018bd"),
+    hex!("16b9e877"),
+    hex!("35b0af7b"),
+    hex!("2753d9cd"),
+    hex!("e40b2ea4"),
+    hex!("fcbdf0e3"),
+    hex!("e5f342e2"),
+    hex!("af511202"),
+    hex!("d0e6d3d3"),
+    hex!("66ddcfa8"),
+    hex!("8887a5b6"),
+    hex!("bb0ab38c"),
+    hex!("64bf045d"),
+    hex!("7a8fba5b"),
+    hex!("26481dad"),
+    hex!("05f0a174"),
+    hex!("ac53e2ec"),
+    hex!("9dbcd976"),
+    hex!("bb315f50"),
+    hex!("b877548b"),
+    hex!("3d2c2aad"),
+    hex!("d42cdf89"),
+    hex!("8d97b217"),
+    hex!("49e0b594"),
+    hex!("01b23d08"),
+    hex!("9c316b67"),
+    hex!("cea5c210"),
+    hex!("3d9833e9"),
+    hex!("073ad3dc"),
+    hex!("9c454cbc"),
+    hex!("0d424bdb"),
+    hex!("c8426624"),
+    hex!("850425a7"),
+    hex!("5f7468d2"),
+    hex!("68199dcd"),
+    hex!("1bc39088"),
+    hex!("ac6bdf4c"),
+    hex!("616a4213"),
+    hex!("675a2efc"),
+    hex!("b3466ce1"),
+    hex!("efe87a15"),
+    hex!("8a93b06d"),
+    hex!("c53f6fe8"),
+    hex!("beebbc85"),
+    hex!("2daba19a"),
+    hex!("69ddf701"),
+    hex!("ac2ebcf4"),
+    hex!("c428eb52"),
+    hex!("940116ce"),
+    hex!("1aff873b"),
+    hex!("f19dc7c1"),
+    hex!("50ba9e24"),
+    hex!("6244c9ba"),
+    hex!("d9ca0531"),
+    hex!("019f04b7"),
+    hex!("0bd9139d"),
+    hex!("a85a5ebe"),
+    hex!("e07d8fc1"),
+    hex!("cdeae685"),
+    hex!("d4b05748"),
+    hex!("2b75c3c9"),
+    hex!("0b51aa4b"),
+    hex!("5b7da9fd"),
+    hex!("2d581b43"),
+    hex!("053468cb"),
+    hex!("c4ea7355"),
+    hex!("390296d2"),
+    hex!("0bc99a43"),
+    hex!("831ca140"),
+    hex!("6ea854bc"),
+    hex!("cbe74e16"),
+    hex!("afde3d1a"),
+    hex!("265425af"),
+    hex!("ffa83edd"),
+    hex!("2d7f0f5a"),
+    hex!("7b69dcfe"),
+    hex!("c08c9611"),
+    hex!("6067e2da"),
+    hex!("50262a09"),
+    hex!("0488926e"),
+    hex!("7477c0a1"),
+    hex!("646def35"),
+    hex!("84f4fc01"),
+    hex!("308e0580"),
+    hex!("8b98854f"),
+    hex!("7c7bd992"),
+    hex!("ef9dd1ae"),
+    hex!("c908c0cb"),
+    hex!("4c223079"),
+    hex!("58e9482f"),
+    hex!("e9b64e43"),
+    hex!("69d58dfd"),
+    hex!("56508960"),
+    hex!("e47ad77c"),
+    hex!("c92526a4"),
+    hex!("0f318bcd"),
+    hex!("cc56b7c7"),
+    hex!("8a0d9bcd"),
+    hex!("8c0c7922"),
+    hex!("490072e1"),
+    hex!("441a3c8c"),
+    hex!("9d3c41ed"),
+    hex!("6023b5ad"),
+    hex!("848902b0"),
+    hex!("56f9c62b"),
+    hex!("01a7fc06"),
+    hex!("9ff0b054"),
+    hex!("0601be62"),
+    hex!("c73b916a"),
+    hex!("4ff9deb8"),
+    hex!("6dcf0704"),
+    hex!("37725e0b"),
+    hex!("bb264cc3"),
+    hex!("8f3dccda"),
+    hex!("8cae5bdc"),
+    hex!("7e1c1704"),
+    hex!("db34d4b8"),
+    hex!("0e2fc599"),
+    hex!("0dabc8f4"),
+    hex!("648d0dd2"),
+    hex!("77485ef3"),
+    hex!("e291673e"),
+    hex!("d1d496b1"),
+    hex!("33a5546b"),
+    hex!("34123297"),
+    hex!("3ca0aead"),
+    hex!("e949b50c"),
+    hex!("07cb6fc5"),
+    hex!("8edf5827"),
+    hex!("f95306bb"),
+    hex!("4e1d05e2"),
+    hex!("a24b5a4a"),
+    hex!("81d9a952"),
+    hex!("66534ad9"),
+    hex!("37ac1801"),
+    hex!("3f1a883d"),
+    hex!("45d0f53f"),
+    hex!("6ccaab76"),
+    hex!("9ab98ad9"),
+    hex!("80d86e96"),
+    hex!("1b4ae400"),
+    hex!("e0064cce"),
+    hex!("a2d04240"),
+    hex!("9a493dd7"),
+    hex!("f534c5f3"),
+    hex!("c34b86c6"),
+    hex!("918b86fa"),
+    hex!("17e7111c"),
+    hex!("e118ce72"),
+    hex!("d7b7b15d"),
+    hex!("3fe7a945"),
+    hex!("6ad87d35"),
+    hex!("7f981244"),
+    hex!("c17dc5a3"),
+    hex!("fa5fbccb"),
+    hex!("540009e5"),
+    hex!("41ed6fe7"),
+    hex!("2afebfec"),
+    hex!("7289db8d"),
+    hex!("b1198d24"),
+    hex!("0bef1261"),
+    hex!("fdca337f"),
+    hex!("a45d06ec"),
+    hex!("87fe297b"),
+    hex!("e26e40bd"),
+    hex!("ec6b0c03"),
+    hex!("07950048"),
+    hex!("e287972b"),
+    hex!("2a6039e5"),
+    hex!("5567d80f"),
+    hex!("3153e10a"),
+    hex!("03b0963d"),
+    hex!("03b93b15"),
+    hex!("3e05673e"),
+    hex!("2de5f285"),
+    hex!("113a0599"),
+    hex!("71675e40"),
+    hex!("eb0e8dce"),
+    hex!("27b488ec"),
+    hex!("a405f5f3"),
+    hex!("de7294f5"),
+    hex!("1b3a9f72"),
+    hex!("bb986915"),
+    hex!("10e8b0ee"),
+    hex!("e0a70c38"),
+    hex!("96299d52"),
+    hex!("056f7f9b"),
+    hex!("be17dd25"),
+    hex!("31641a08"),
+    hex!("deb651e1"),
+    hex!("500103a0"),
+    hex!("168be6df"),
+    hex!("d69771c5"),
+    hex!("3cf6a306"),
+    hex!("69891a16"),
+    hex!("fb7b596a"),
+    hex!("d864488d"),
+    hex!("3dc73a6d"),
+    hex!("ca53dc21"),
+    hex!("d571c79a"),
+    hex!("c71b3cef"),
+    hex!("b43aeeec"),
+    hex!("24fb7782"),
+    hex!("41ac34f8"),
+    hex!("54983d68"),
+    hex!("56db2145"),
+    hex!("793c08df"),
+    hex!("0412a89e"),
+    hex!("8d991285"),
+    hex!("44df0aab"),
+    hex!("bd172681"),
+    hex!("5732d988"),
+    hex!("5480f074"),
+    hex!("b5e8b2b0"),
+    hex!("dededef3"),
+    hex!("cdbce5e3"),
+    hex!("4a09fe43"),
+    hex!("ff7a1b11"),
+    hex!("0f7bffdd"),
+    hex!("9671b1ed"),
+    hex!("1b07973b"),
+    hex!("80400894"),
+    hex!("3f81607c"),
+    hex!("e6ebe562"),
+];
+
+/// Returns the first four bytes of the canonical accumulator root of the given mainnet epoch, i.e.
+/// of the era1 file that starts at block `epoch * MAX_BLOCKS_PER_ERA1`.
+///
+/// Returns `None` for epochs after the merge, which have no era1 file.
+pub fn mainnet_accumulator_root_prefix(epoch: u64) -> Option<[u8; 4]> {
+    MAINNET_ACCUMULATOR_ROOT_PREFIXES.get(usize::try_from(epoch).ok()?).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mainnet_accumulator_root_prefix() {
+        assert_eq!(mainnet_accumulator_root_prefix(0), Some(hex!("5ec1ffb8")));
+        assert_eq!(mainnet_accumulator_root_prefix(1896), Some(hex!("e6ebe562")));
+        assert_eq!(mainnet_accumulator_root_prefix(1897), None);
+    }
+}
@@ -115,6 +115,14 @@ impl<R: Read> BlockTupleIterator<R> {
             block_index: None,
         }
     }
+
+    /// Returns the accumulator of the file, once it has been read.
+    ///
+    /// The accumulator follows all block tuples, so it is only available after iterating over all
+    /// of them.
+    pub const fn accumulator(&self) -> Option<&Accumulator> {
+        self.accumulator.as_ref()
+    }
 }
 
 impl<R: Read + Seek> Iterator for BlockTupleIterator<R> {
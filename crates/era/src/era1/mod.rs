@@ -1,4 +1,5 @@
 //! Core era1 primitives and file handling.
 
+pub mod accumulators;
 pub mod file;
 pub mod types;
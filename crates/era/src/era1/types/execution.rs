@@ -76,6 +76,7 @@ use crate::{
 use alloy_consensus::{Block, BlockBody, Header};
 use alloy_primitives::{B256, U256};
 use alloy_rlp::{Decodable, Encodable};
+use sha2::{Digest, Sha256};
 use snap::{read::FrameDecoder, write::FrameEncoder};
 use std::{
     io::{Read, Write},
@@ -423,19 +424,9 @@ impl TotalDifficulty {
         Self { value }
     }
 
-    /// Convert to an [`Entry`]
+    /// Convert to an [`Entry`], encoding the value as an SSZ `uint256`, i.e. little-endian
     pub fn to_entry(&self) -> Entry {
-        let mut data = [0u8; 32];
-
-        let be_bytes = self.value.to_be_bytes_vec();
-
-        if be_bytes.len() <= 32 {
-            data[32 - be_bytes.len()..].copy_from_slice(&be_bytes);
-        } else {
-            data.copy_from_slice(&be_bytes[be_bytes.len() - 32..]);
-        }
-
-        Entry::new(TOTAL_DIFFICULTY, data.to_vec())
+        Entry::new(TOTAL_DIFFICULTY, self.value.to_le_bytes::<32>().to_vec())
     }
 
     /// Create from an [`Entry`]
@@ -454,8 +445,8 @@ impl TotalDifficulty {
             )));
         }
 
-        // Convert 32-byte array to U256
-        let value = U256::from_be_slice(&entry.data);
+        // SSZ `uint256` is little-endian
+        let value = U256::from_le_slice(&entry.data);
 
         Ok(Self { value })
     }
@@ -501,6 +492,54 @@ impl Accumulator {
 
         Ok(Self { root: B256::from(root) })
     }
+
+    /// Computes the accumulator of the given header records, i.e. the block hash and total
+    /// difficulty of each block of a file.
+    ///
+    /// The total difficulty is hashed as encoded in its [`TotalDifficulty`] entry.
+    pub fn from_header_records(
+        records: impl IntoIterator<Item = (B256, TotalDifficulty)>,
+    ) -> Result<Self, E2sError> {
+        let mut layer = records
+            .into_iter()
+            .map(|(block_hash, total_difficulty)| {
+                sha256_pair(block_hash.as_slice(), &total_difficulty.to_entry().data)
+            })
+            .collect::<Vec<_>>();
+        let len = layer.len();
+        if len > MAX_BLOCKS_PER_ERA1 {
+            return Err(E2sError::Ssz(format!(
+                "Too many header records for Accumulator: expected at most {MAX_BLOCKS_PER_ERA1}, got {len}"
+            )));
+        }
+
+        // Merkleize the list up to its limit, padding each layer with the root of an empty subtree
+        let mut zero = B256::ZERO;
+        for _ in 0..MAX_BLOCKS_PER_ERA1.trailing_zeros() {
+            if layer.len() % 2 == 1 {
+                layer.push(zero);
+            }
+            layer = layer
+                .chunks_exact(2)
+                .map(|pair| sha256_pair(pair[0].as_slice(), pair[1].as_slice()))
+                .collect();
+            zero = sha256_pair(zero.as_slice(), zero.as_slice());
+        }
+        let root = layer.first().copied().unwrap_or(zero);
+
+        let mut length = [0u8; 32];
+        length[..8].copy_from_slice(&(len as u64).to_le_bytes());
+
+        Ok(Self { root: sha256_pair(root.as_slice(), &length) })
+    }
+}
+
+/// Returns the SHA-256 hash of the concatenation of two 32 byte chunks.
+fn sha256_pair(left: &[u8], right: &[u8]) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
 }
 
 /// A block tuple in an Era1 file, containing all components for a single block
@@ -608,6 +647,19 @@ mod tests {
         assert_eq!(recovered.value, value);
     }
 
+    #[test]
+    fn test_total_difficulty_little_endian() {
+        // Total difficulty of the mainnet genesis block
+        let total_difficulty = TotalDifficulty::new(U256::from(0x400000000u64));
+
+        let mut expected = [0u8; 32];
+        expected[4] = 0x04;
+        assert_eq!(total_difficulty.to_entry().data, expected);
+
+        let entry = Entry::new(TOTAL_DIFFICULTY, expected.to_vec());
+        assert_eq!(TotalDifficulty::from_entry(&entry).unwrap().value, U256::from(17179869184u64));
+    }
+
     #[test]
     fn test_compression_roundtrip() {
         let rlp_data = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
@@ -715,4 +767,36 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_accumulator_from_header_records() {
+        let records = [
+            (B256::repeat_byte(1), TotalDifficulty::new(U256::from(1))),
+            (B256::repeat_byte(2), TotalDifficulty::new(U256::from(3))),
+        ];
+        let accumulator = Accumulator::from_header_records(records.clone()).unwrap();
+
+        // Both records share the leftmost subtree, every other subtree of the list is empty
+        let leaves = records
+            .iter()
+            .map(|(hash, td)| sha256_pair(hash.as_slice(), &td.value.to_le_bytes::<32>()))
+            .collect::<Vec<_>>();
+        let mut root = sha256_pair(leaves[0].as_slice(), leaves[1].as_slice());
+        let mut zero = sha256_pair(&[0; 32], &[0; 32]);
+        for _ in 1..13 {
+            root = sha256_pair(root.as_slice(), zero.as_slice());
+            zero = sha256_pair(zero.as_slice(), zero.as_slice());
+        }
+        let mut length = [0u8; 32];
+        length[0] = 2;
+        assert_eq!(accumulator.root, sha256_pair(root.as_slice(), &length));
+
+        let other = Accumulator::from_header_records([records[0].clone()]).unwrap();
+        assert_ne!(other.root, accumulator.root);
+
+        let too_many = (0..=MAX_BLOCKS_PER_ERA1)
+            .map(|_| (B256::ZERO, TotalDifficulty::new(U256::ZERO)))
+            .collect::<Vec<_>>();
+        assert!(Accumulator::from_header_records(too_many).is_err());
+    }
 }
@@ -3,13 +3,15 @@ use eyre::{eyre, Result};
 use reth_chainspec::{ChainSpec, EthChainSpec, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    common::{CliComponentsBuilder, CliNodeTypes, HeaderMut},
+    common::{CliComponentsBuilder, CliNodeTypes, EvmFor, HeaderMut},
     launcher::{FnLauncher, Launcher},
 };
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
+use reth_evm::{ConfigureEvm, NextBlockEnvAttributes};
 use reth_node_api::NodePrimitives;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
+use reth_node_core::args::OtlpGuard;
 use reth_node_ethereum::{consensus::EthBeaconConsensus, EthEvmConfig, EthereumNode};
 use reth_node_metrics::recorder::install_prometheus_recorder;
 use reth_rpc_server_types::RpcModuleValidator;
@@ -54,14 +56,22 @@ where
     ///
     /// This accepts a closure that is used to launch the node via the
     /// [`NodeCommand`](reth_cli_commands::node::NodeCommand).
-    pub fn run(self, launcher: impl Launcher<C, Ext>) -> Result<()>
+    pub fn run(mut self, launcher: impl Launcher<C, Ext>) -> Result<()>
     where
         C: ChainSpecParser<ChainSpec = ChainSpec>,
     {
+        if let Commands::Node(command) = &mut self.cli.command {
+            command.apply_hardfork_overrides()?;
+        }
+
         let components = |spec: Arc<ChainSpec>| {
             (EthEvmConfig::ethereum(spec.clone()), Arc::new(EthBeaconConsensus::new(spec)))
         };
 
+        if matches!(&self.cli.command, Commands::Node(command) if command.read_only) {
+            return self.run_read_only()
+        }
+
         self.run_with_components::<EthereumNode>(components, |builder, ext| async move {
             launcher.entrypoint(builder, ext).await
         })
@@ -82,8 +92,36 @@ where
     ) -> Result<()>
     where
         N: CliNodeTypes<Primitives: NodePrimitives<BlockHeader: HeaderMut>, ChainSpec: Hardforks>,
+        EvmFor<N>: ConfigureEvm<NextBlockEnvCtx: From<NextBlockEnvAttributes>>,
         C: ChainSpecParser<ChainSpec = N::ChainSpec>,
     {
+        if let Some(result) = run_stdout_command(&self.cli) {
+            return result
+        }
+
+        // Flushes OTLP spans that are still buffered once the command exits
+        let _otlp_guard = OtlpGuard::new();
+        let runner = self.init_runner()?;
+        run_commands_with::<C, Ext, Rpc, N>(self.cli, runner, components, launcher)
+    }
+
+    /// Serves RPC from the datadir of the node command without syncing, see `--read-only`.
+    fn run_read_only(mut self) -> Result<()>
+    where
+        C: ChainSpecParser<ChainSpec = ChainSpec>,
+    {
+        let _otlp_guard = OtlpGuard::new();
+        let runner = self.init_runner()?;
+        let Commands::Node(command) = self.cli.command else {
+            unreachable!("read-only mode is only available for the node command")
+        };
+        runner
+            .with_shutdown_timeout(command.shutdown_timeout)
+            .run_command_until_exit(|ctx| crate::read_only::run(*command, ctx))
+    }
+
+    /// Returns the runner with tracing and the prometheus recorder initialized.
+    fn init_runner(&mut self) -> Result<CliRunner> {
         let runner = match self.runner.take() {
             Some(runner) => runner,
             None => CliRunner::try_default_runtime()?,
@@ -96,11 +134,12 @@ where
         }
 
         self.init_tracing(&runner)?;
+        let runner = runner.with_log_guard(self.guard.take());
 
         // Install the prometheus recorder to be sure to record all metrics
         let _ = install_prometheus_recorder();
 
-        run_commands_with::<C, Ext, Rpc, N>(self.cli, runner, components, launcher)
+        Ok(runner)
     }
 
     /// Initializes tracing with the configured options.
@@ -115,6 +154,23 @@ where
     }
 }
 
+/// Runs the commands that only write their output to stdout, returning `None` for all other
+/// commands.
+///
+/// These run before tracing is initialized, which would log to stdout as well.
+pub(crate) fn run_stdout_command<C, Ext, Rpc>(cli: &Cli<C, Ext, Rpc>) -> Option<Result<()>>
+where
+    C: ChainSpecParser,
+    Ext: clap::Args + fmt::Debug,
+    Rpc: RpcModuleValidator,
+{
+    match &cli.command {
+        Commands::Completions(command) => Some(command.execute::<Cli<C, Ext, Rpc>>()),
+        Commands::Version(command) => Some(command.execute()),
+        _ => None,
+    }
+}
+
 /// Run CLI commands with the provided runner, components and launcher.
 /// This is the shared implementation used by both `CliApp` and Cli methods.
 pub(crate) fn run_commands_with<C, Ext, Rpc, N>(
@@ -131,6 +187,7 @@ where
     Ext: clap::Args + fmt::Debug,
     Rpc: RpcModuleValidator,
     N: CliNodeTypes<Primitives: NodePrimitives<BlockHeader: HeaderMut>, ChainSpec: Hardforks>,
+    EvmFor<N>: ConfigureEvm<NextBlockEnvCtx: From<NextBlockEnvAttributes>>,
 {
     match cli.command {
         Commands::Node(command) => {
@@ -142,7 +199,7 @@ where
                 Rpc::validate_selection(ws_api, "ws.api").map_err(|e| eyre!("{e}"))?;
             }
 
-            runner.run_command_until_exit(|ctx| {
+            runner.with_shutdown_timeout(command.shutdown_timeout).run_command_until_exit(|ctx| {
                 command.execute(ctx, FnLauncher::new::<C, Ext>(launcher))
             })
         }
@@ -152,10 +209,11 @@ where
             runner.run_blocking_until_ctrl_c(command.execute::<N, _>(components))
         }
         Commands::ImportEra(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
+        Commands::Export(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
         Commands::ExportEra(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
         Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
         Commands::Db(command) => {
-            runner.run_blocking_command_until_exit(|ctx| command.execute::<N>(ctx))
+            runner.run_blocking_command_until_exit(|ctx| command.execute::<N, _>(ctx, components))
         }
         Commands::Download(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
         Commands::Stage(command) => {
@@ -167,6 +225,10 @@ where
         #[cfg(feature = "dev")]
         Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
         Commands::ReExecute(command) => runner.run_until_ctrl_c(command.execute::<N>(components)),
+        Commands::Recover(command) => runner.run_until_ctrl_c(command.execute::<N>()),
+        Commands::Debug(command) => runner.run_until_ctrl_c(command.execute::<N>(components)),
+        Commands::Completions(command) => command.execute::<Cli<C, Ext, Rpc>>(),
+        Commands::Version(command) => command.execute(),
     }
 }
 
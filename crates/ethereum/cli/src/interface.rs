@@ -1,31 +1,43 @@
 //! CLI definition and entrypoint to executable
 
 use crate::{
-    app::{run_commands_with, CliApp},
+    app::{run_commands_with, run_stdout_command, CliApp},
     chainspec::EthereumChainSpecParser,
 };
-use clap::{Parser, Subcommand};
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
 use reth_chainspec::{ChainSpec, EthChainSpec, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    common::{CliComponentsBuilder, CliNodeTypes, HeaderMut},
-    config_cmd, db, download, dump_genesis, export_era, import, import_era, init_cmd, init_state,
+    cli_defaults::CliDefaults,
+    cli_env,
+    common::{CliComponentsBuilder, CliNodeTypes, EvmFor, HeaderMut},
+    completions, config_cmd, db, debug_cmd, download, dump_genesis, export, export_era, import,
+    import_era, init_cmd, init_state,
     launcher::FnLauncher,
     node::{self, NoArgs},
-    p2p, prune, re_execute, stage,
+    p2p, prune, re_execute, recover, stage, version_cmd,
 };
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
+use reth_evm::{ConfigureEvm, NextBlockEnvAttributes};
 use reth_node_api::NodePrimitives;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use reth_node_core::{
-    args::{LogArgs, OtlpInitStatus, TraceArgs},
+    args::{DatadirArgs, LogArgs, OtlpGuard, OtlpInitStatus, TraceArgs},
+    dirs::{DataDirPath, MaybePlatformPath},
     version::version_metadata,
 };
 use reth_node_metrics::recorder::install_prometheus_recorder;
 use reth_rpc_server_types::{DefaultRpcModuleValidator, RpcModuleValidator};
 use reth_tracing::{FileWorkerGuard, Layers};
-use std::{ffi::OsString, fmt, future::Future, marker::PhantomData, sync::Arc};
+use std::{
+    ffi::{OsStr, OsString},
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    path::PathBuf,
+    sync::Arc,
+};
 use tracing::{info, warn};
 
 /// The main reth cli interface.
@@ -50,6 +62,12 @@ pub struct Cli<
     #[command(flatten)]
     pub traces: TraceArgs,
 
+    /// The path to the config file whose `[cli]` table holds defaults for the CLI arguments.
+    ///
+    /// Defaults to the `--config` of the subcommand, or `reth.toml` in its datadir.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment)]
+    pub config: Option<PathBuf>,
+
     /// Type marker for the RPC module validator
     #[arg(skip)]
     pub _phantom: PhantomData<Rpc>,
@@ -58,7 +76,7 @@ pub struct Cli<
 impl Cli {
     /// Parsers only the default CLI arguments
     pub fn parse_args() -> Self {
-        Self::parse()
+        Self::parse_with_config()
     }
 
     /// Parsers only the default CLI arguments from the given iterator
@@ -67,7 +85,84 @@ impl Cli {
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
     {
-        Self::try_parse_from(itr)
+        Self::try_parse_with_config_from(itr)
+    }
+}
+
+impl<C, Ext, Rpc> Cli<C, Ext, Rpc>
+where
+    C: ChainSpecParser<ChainSpec: EthChainSpec>,
+    Ext: clap::Args + fmt::Debug,
+    Rpc: RpcModuleValidator,
+{
+    /// Parses the CLI arguments of the process, and their environment variables, see
+    /// [`cli_env`], using the `[cli]` table of the config file as defaults, see [`CliDefaults`].
+    ///
+    /// Exits the process on error, like [`Parser::parse`].
+    pub fn parse_with_config() -> Self {
+        Self::try_parse_with_config_from(std::env::args_os()).unwrap_or_else(|err| err.exit())
+    }
+
+    /// Parses the CLI arguments from the given iterator, and their environment variables, see
+    /// [`cli_env`], using the `[cli]` table of the config file as defaults, see [`CliDefaults`].
+    ///
+    /// The config file is the one passed with the top-level `--config`, the `--config` of the
+    /// subcommand or `reth.toml` in the datadir of the subcommand, in that order.
+    pub fn try_parse_with_config_from<I, T>(itr: I) -> Result<Self, clap::error::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let args = itr.into_iter().map(Into::into).collect::<Vec<OsString>>();
+        let matches = cli_env::with_env(Self::command()).try_get_matches_from(args.clone())?;
+        let cli = Self::from_arg_matches(&matches)?;
+        let Some(config_path) = cli.config_path(&matches) else { return Ok(cli) };
+
+        let defaults = CliDefaults::from_path(config_path)
+            .map_err(|err| clap::Error::raw(clap::error::ErrorKind::Io, format!("{err}\n")))?;
+        let command = defaults.apply(cli_env::with_env(Self::command()));
+        let matches = command.clone().try_get_matches_from(args)?;
+        let mut cli = Self::from_arg_matches(&matches)?;
+
+        if let Commands::Config(config) = &mut cli.command &&
+            config.show_effective() &&
+            let Some((_, config_matches)) = matches.subcommand()
+        {
+            config.set_effective_values(defaults.effective_values(&command, config_matches));
+        }
+
+        Ok(cli)
+    }
+
+    /// Returns the path of the config file holding the CLI defaults for the parsed command.
+    ///
+    /// The arguments of the subcommands are looked up by their long name, since their ids differ
+    /// between subcommands. Arguments of nested subcommands take precedence.
+    fn config_path(&self, matches: &ArgMatches) -> Option<PathBuf> {
+        if let Some(path) = &self.config {
+            return Some(path.clone())
+        }
+
+        let root = Self::command();
+        let (mut command, mut matches) = (&root, matches);
+        let (mut config, mut datadir) = (None, None);
+        while let Some((name, subcommand_matches)) = matches.subcommand() {
+            command = command.find_subcommand(name)?;
+            matches = subcommand_matches;
+            let value = |long| {
+                let arg = command.get_arguments().find(|arg| arg.get_long() == Some(long))?;
+                matches.get_raw(arg.get_id().as_str())?.next().map(OsStr::to_os_string)
+            };
+            config = value("config").or(config);
+            datadir = value("datadir").or(datadir);
+        }
+
+        if let Some(config) = config {
+            return Some(config.into())
+        }
+        let datadir = datadir?.to_str()?.parse::<MaybePlatformPath<DataDirPath>>().ok()?;
+        let chain = self.command.chain_spec()?.chain();
+        Some(DatadirArgs { datadir, ..Default::default() }.resolve_datadir(chain).config())
     }
 }
 
@@ -150,6 +245,7 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug, Rpc: RpcModuleValidator>
     ) -> eyre::Result<()>
     where
         N: CliNodeTypes<Primitives: NodePrimitives<BlockHeader: HeaderMut>, ChainSpec: Hardforks>,
+        EvmFor<N>: ConfigureEvm<NextBlockEnvCtx: From<NextBlockEnvAttributes>>,
         C: ChainSpecParser<ChainSpec = N::ChainSpec>,
     {
         self.with_runner_and_components(CliRunner::try_default_runtime()?, components, launcher)
@@ -198,14 +294,22 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug, Rpc: RpcModuleValidator>
     ) -> eyre::Result<()>
     where
         N: CliNodeTypes<Primitives: NodePrimitives<BlockHeader: HeaderMut>, ChainSpec: Hardforks>,
+        EvmFor<N>: ConfigureEvm<NextBlockEnvCtx: From<NextBlockEnvAttributes>>,
         C: ChainSpecParser<ChainSpec = N::ChainSpec>,
     {
+        if let Some(result) = run_stdout_command(&self) {
+            return result
+        }
+
         // Add network name if available to the logs dir
         if let Some(chain_spec) = self.command.chain_spec() {
             self.logs.log_file_directory =
                 self.logs.log_file_directory.join(chain_spec.chain().to_string());
         }
-        let _guard = self.init_tracing(&runner, Layers::new())?;
+        // Flushes OTLP spans that are still buffered once the command exits
+        let _otlp_guard = OtlpGuard::new();
+        let guard = self.init_tracing(&runner, Layers::new())?;
+        let runner = runner.with_log_guard(guard);
 
         // Install the prometheus recorder to be sure to record all metrics
         let _ = install_prometheus_recorder();
@@ -258,6 +362,9 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// This syncs RLP encoded blocks from a file or files.
     #[command(name = "import")]
     Import(import::ImportCommand<C>),
+    /// This exports canonical blocks to RLP encoded files.
+    #[command(name = "export")]
+    Export(export::ExportCommand<C>),
     /// This syncs ERA encoded blocks from a directory.
     #[command(name = "import-era")]
     ImportEra(import_era::ImportEraCommand<C>),
@@ -291,6 +398,18 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<C>),
+    /// Recover corrupted database tables
+    #[command(name = "recover")]
+    Recover(recover::Command<C>),
+    /// Various debug routines
+    #[command(name = "debug")]
+    Debug(debug_cmd::Command<C>),
+    /// Generate shell completions
+    #[command(name = "completions")]
+    Completions(completions::Command),
+    /// Print the build information and the database formats the binary expects
+    #[command(name = "version")]
+    Version(version_cmd::Command),
 }
 
 impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
@@ -301,6 +420,7 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
             Self::Init(cmd) => cmd.chain_spec(),
             Self::InitState(cmd) => cmd.chain_spec(),
             Self::Import(cmd) => cmd.chain_spec(),
+            Self::Export(cmd) => cmd.chain_spec(),
             Self::ExportEra(cmd) => cmd.chain_spec(),
             Self::ImportEra(cmd) => cmd.chain_spec(),
             Self::DumpGenesis(cmd) => cmd.chain_spec(),
@@ -313,6 +433,10 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
             Self::Config(_) => None,
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::Recover(cmd) => cmd.chain_spec(),
+            Self::Debug(cmd) => cmd.chain_spec(),
+            Self::Completions(_) => None,
+            Self::Version(_) => None,
         }
     }
 }
@@ -350,6 +474,112 @@ mod tests {
         }
     }
 
+    /// Tests that every argument of every command can be set with an environment variable
+    /// following the naming convention, unless it's exempted.
+    #[test]
+    fn env_vars_of_all_arguments() {
+        fn check(command: &clap::Command, path: &str) {
+            let mut names = std::collections::HashSet::new();
+            for arg in command.get_arguments().filter(|arg| !cli_env::is_exempt(arg)) {
+                let long = arg.get_long().expect("not exempt");
+                let Some(env) = arg.get_env().and_then(|env| env.to_str()) else {
+                    panic!("--{long} of `{path}` has no environment variable")
+                };
+                // Arguments may declare a standard variable that doesn't have the prefix
+                if env.starts_with(cli_env::CLI_ENV_PREFIX) {
+                    assert_eq!(env, cli_env::env_name(long), "--{long} of `{path}`");
+                }
+                assert!(names.insert(env), "{env} is used twice by `{path}`");
+            }
+            for sub in command.get_subcommands() {
+                check(sub, &format!("{path} {}", sub.get_name()));
+            }
+        }
+
+        let mut command = cli_env::with_env(Cli::<EthereumChainSpecParser, NoArgs>::command());
+        command.build();
+        check(&command, "reth");
+
+        let vars = cli_env::env_vars(&command);
+        assert_eq!(vars["datadir"], "RETH_DATADIR");
+        assert_eq!(vars["chain"], "RETH_CHAIN");
+        assert_eq!(vars["log.stdout.filter"], "RETH_LOG_STDOUT_FILTER");
+        assert_eq!(vars["tracing-otlp"], "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT");
+    }
+
+    #[test]
+    fn config_path_of_subcommand() {
+        let config_path = |args: &[&str]| {
+            let matches = Cli::<EthereumChainSpecParser, NoArgs>::command()
+                .try_get_matches_from(args)
+                .unwrap();
+            Cli::<EthereumChainSpecParser, NoArgs>::from_arg_matches(&matches)
+                .unwrap()
+                .config_path(&matches)
+        };
+
+        assert_eq!(
+            config_path(&["reth", "--config", "cli.toml", "node", "--config", "node.toml"]),
+            Some(PathBuf::from("cli.toml"))
+        );
+        assert_eq!(
+            config_path(&["reth", "node", "--config", "node.toml"]),
+            Some("node.toml".into())
+        );
+        assert_eq!(
+            config_path(&["reth", "db", "--datadir", "/data", "stats"]),
+            Some(PathBuf::from("/data/reth.toml"))
+        );
+        assert_eq!(config_path(&["reth", "config"]), None);
+    }
+
+    #[test]
+    fn parse_with_config_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = temp_dir.path().join("reth.toml");
+        std::fs::write(
+            &config,
+            "[cli]
+\"log.file.name\" = \"custom.log\"\n",
+        )
+        .unwrap();
+
+        let config = config.to_str().unwrap();
+        let reth = Cli::try_parse_with_config_from(["reth", "--config", config, "node"]).unwrap();
+        assert_eq!(reth.logs.log_file_name, "custom.log");
+
+        let reth = Cli::try_parse_with_config_from([
+            "reth",
+            "--config",
+            config,
+            "node",
+            "--log.file.name",
+            "cli.log",
+        ])
+        .unwrap();
+        assert_eq!(reth.logs.log_file_name, "cli.log");
+    }
+
+    #[test]
+    fn parse_version_command() {
+        let reth = Cli::try_parse_args_from(["reth", "version", "--json"]).unwrap();
+        assert!(matches!(reth.command, Commands::Version(_)));
+        assert!(reth.command.chain_spec().is_none());
+    }
+
+    #[test]
+    fn generate_completions() {
+        use completions::Shell;
+
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let mut out = Vec::new();
+            completions::Command::new(shell).generate::<Cli>(&mut out);
+            let script = String::from_utf8(out).unwrap();
+            assert!(!script.is_empty(), "{shell}");
+            assert!(script.contains("node"), "{shell}");
+        }
+    }
+
     /// Tests that the log directory is parsed correctly when using the node command. It's
     /// always tied to the specific chain's name.
     #[test]
@@ -13,6 +13,7 @@ pub mod app;
 /// Chain specification parser.
 pub mod chainspec;
 pub mod interface;
+mod read_only;
 
 pub use app::CliApp;
 pub use interface::{Cli, Commands};
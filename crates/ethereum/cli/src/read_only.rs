@@ -0,0 +1,241 @@
+//! Read-only mode of the node command, which serves RPC from an existing datadir without syncing.
+
+use jsonrpsee::{
+    types::{error::METHOD_NOT_FOUND_CODE, ErrorObject},
+    RpcModule,
+};
+use reth_chainspec::{ChainSpec, EthChainSpec};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::node::NodeCommand;
+use reth_cli_runner::CliContext;
+use reth_db::{open_db_read_only, DatabaseEnv};
+use reth_network_api::noop::NoopNetwork;
+use reth_node_api::NodeTypesWithDBAdapter;
+use reth_node_ethereum::{consensus::EthBeaconConsensus, EthEvmConfig, EthereumNode};
+use reth_provider::{
+    providers::{BlockchainProvider, RocksDBProvider, StaticFileProviderBuilder},
+    BlockNumReader, CanonChainTracker, ChainStateBlockReader, HeaderProvider, ProviderFactory,
+    ProviderResult, StorageSettingsCache,
+};
+use reth_rpc::EthApiBuilder;
+use reth_rpc_builder::{config::RethRpcServerConfig, RpcModuleBuilder};
+use reth_rpc_server_types::{RethRpcModule, RpcModuleSelection};
+use reth_transaction_pool::noop::NoopTransactionPool;
+use std::{collections::HashSet, fmt, sync::Arc, time::Duration};
+use tracing::{info, warn};
+
+/// How often the head is updated to the blocks that a node writing to the datadir persisted.
+const HEAD_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The node types of the database that is served.
+type ReadOnlyNode = NodeTypesWithDBAdapter<EthereumNode, Arc<DatabaseEnv>>;
+
+/// The namespaces that only serve existing chain data.
+const READ_ONLY_MODULES: [RethRpcModule; 7] = [
+    RethRpcModule::Eth,
+    RethRpcModule::Debug,
+    RethRpcModule::Trace,
+    RethRpcModule::Ots,
+    RethRpcModule::Net,
+    RethRpcModule::Web3,
+    RethRpcModule::Rpc,
+];
+
+/// The methods of the `eth` namespace that require new chain data.
+const UNSUPPORTED_METHODS: [&str; 6] = [
+    "eth_sendRawTransaction",
+    "eth_sendRawTransactionSync",
+    "eth_sendTransaction",
+    "eth_subscribe",
+    "eth_newBlockFilter",
+    "eth_newPendingTransactionFilter",
+];
+
+/// Serves RPC from the datadir of the node command without syncing, see `--read-only`.
+///
+/// The database is opened read-only and the datadir isn't locked, so another node may keep
+/// syncing into it. Only the blocks it persisted are served though, which lag behind its tip by
+/// the blocks it still keeps in memory, and every read transaction keeps it from reusing the pages
+/// freed since then, so long-running requests grow its database.
+///
+/// The head, safe and finalized blocks follow what's persisted, see [`update_head`].
+pub(crate) async fn run<C, Ext>(command: NodeCommand<C, Ext>, ctx: CliContext) -> eyre::Result<()>
+where
+    C: ChainSpecParser<ChainSpec = ChainSpec>,
+    Ext: clap::Args + fmt::Debug,
+{
+    let NodeCommand { datadir, chain, db, rpc, .. } = command;
+    let data_dir = datadir.resolve_datadir(chain.chain());
+
+    let db_path = data_dir.db();
+    info!(target: "reth::cli", path = ?db_path, "Opening database read-only");
+    let database = Arc::new(open_db_read_only(&db_path, db.database_args())?);
+    let static_file_provider = StaticFileProviderBuilder::read_only(data_dir.static_files())?
+        .with_genesis_block_number(chain.genesis().number.unwrap_or_default())
+        .build()?;
+    // Picks up the static files that a node writing to the datadir creates
+    static_file_provider.watch_directory();
+
+    // RocksDB can't be opened read-only without its lock, so an empty instance is opened in a
+    // temporary directory, and the node refuses to start if any table is stored in RocksDB
+    let rocksdb_dir = tempfile::tempdir()?;
+    let rocksdb_provider = RocksDBProvider::builder(rocksdb_dir.path())
+        .with_default_tables()
+        .with_database_log_level(db.log_level)
+        .build()?;
+
+    let factory = ProviderFactory::<ReadOnlyNode>::new(
+        database,
+        chain.clone(),
+        static_file_provider,
+        rocksdb_provider,
+    )?;
+    let settings = factory.cached_storage_settings();
+    if settings.storages_history_in_rocksdb ||
+        settings.transaction_hash_numbers_in_rocksdb ||
+        settings.account_history_in_rocksdb
+    {
+        eyre::bail!("`--read-only` doesn't support datadirs that store tables in RocksDB")
+    }
+    let provider = BlockchainProvider::new(factory.clone())?;
+    let evm_config = EthEvmConfig::ethereum(chain.clone());
+
+    let eth_config = rpc.eth_config();
+    let eth_api = EthApiBuilder::new(
+        provider.clone(),
+        NoopTransactionPool::default(),
+        NoopNetwork::default(),
+        evm_config.clone(),
+    )
+    .task_spawner(ctx.task_executor.clone())
+    .eth_state_cache_config(eth_config.cache)
+    .gas_oracle_config(eth_config.gas_oracle)
+    .gas_cap(eth_config.rpc_gas_cap.into())
+    .max_simulate_blocks(eth_config.rpc_max_simulate_blocks)
+    .eth_proof_window(eth_config.eth_proof_window)
+    .fee_history_cache_config(eth_config.fee_history_cache)
+    .proof_permits(eth_config.proof_permits)
+    .build();
+
+    let mut module_config = rpc.transport_rpc_module_config();
+    retain_read_only_modules(module_config.http_mut());
+    retain_read_only_modules(module_config.ws_mut());
+    retain_read_only_modules(module_config.ipc_mut());
+
+    let mut modules = RpcModuleBuilder::default()
+        .with_provider(provider)
+        .with_noop_pool()
+        .with_noop_network()
+        .with_executor(Box::new(ctx.task_executor))
+        .with_evm_config(evm_config)
+        .with_consensus(EthBeaconConsensus::new(chain))
+        .build(module_config, eth_api, Default::default());
+    modules.add_or_replace_if_module_configured(RethRpcModule::Eth, unsupported_methods())?;
+
+    let handle = rpc.rpc_server_config().start(&modules).await?;
+    info!(
+        target: "reth::cli",
+        http = ?handle.http_local_addr(),
+        ws = ?handle.ws_local_addr(),
+        ipc = ?handle.ipc_endpoint(),
+        "Serving RPC read-only"
+    );
+
+    // The server runs until the node is stopped, and the temporary directory must outlive it.
+    // Meanwhile, the head follows the blocks that are persisted to the database.
+    let _handle = handle;
+    let _rocksdb_dir = rocksdb_dir;
+    let mut interval = tokio::time::interval(HEAD_POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(err) = update_head(&factory, &provider) {
+            warn!(target: "reth::cli", %err, "Failed to update the head from the database");
+        }
+    }
+}
+
+/// Updates the head, safe and finalized blocks of the provider to the ones last persisted to the
+/// database.
+///
+/// The provider pins the blocks it was created with, so without this it would keep serving the
+/// head of the datadir at startup while another node keeps syncing into it.
+fn update_head(
+    factory: &ProviderFactory<ReadOnlyNode>,
+    provider: &BlockchainProvider<ReadOnlyNode>,
+) -> ProviderResult<()> {
+    let database = factory.provider()?;
+    let best = database.chain_info()?;
+    if best.best_hash != provider.canonical_in_memory_state().get_canonical_head().hash() &&
+        let Some(header) = database.sealed_header(best.best_number)?
+    {
+        provider.set_canonical_head(header);
+    }
+    if let Some(header) = database
+        .last_safe_block_number()?
+        .map(|number| database.sealed_header(number))
+        .transpose()?
+        .flatten()
+    {
+        provider.set_safe(header);
+    }
+    if let Some(header) = database
+        .last_finalized_block_number()?
+        .map(|number| database.sealed_header(number))
+        .transpose()?
+        .flatten()
+    {
+        provider.set_finalized(header);
+    }
+    Ok(())
+}
+
+/// Removes the namespaces that require new chain data from the selection.
+fn retain_read_only_modules(selection: &mut Option<RpcModuleSelection>) {
+    let Some(modules) = selection else { return };
+    let (kept, dropped): (HashSet<_>, HashSet<_>) =
+        modules.iter_selection().partition(|module| READ_ONLY_MODULES.contains(module));
+    if !dropped.is_empty() {
+        warn!(target: "reth::cli", ?dropped, "Namespaces are not available in read-only mode");
+    }
+    *modules = RpcModuleSelection::Selection(kept);
+}
+
+/// Returns the methods that replace the [`UNSUPPORTED_METHODS`] with an error.
+fn unsupported_methods() -> RpcModule<()> {
+    let mut module = RpcModule::new(());
+    for method in UNSUPPORTED_METHODS {
+        module
+            .register_method(method, move |_, _, _| {
+                Err::<(), _>(ErrorObject::owned(
+                    METHOD_NOT_FOUND_CODE,
+                    format!("{method} is not supported in read-only mode"),
+                    None::<()>,
+                ))
+            })
+            .expect("method names are unique");
+    }
+    module
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_modules_that_need_new_chain_data() {
+        let mut selection = Some(RpcModuleSelection::All);
+        retain_read_only_modules(&mut selection);
+        assert_eq!(selection.unwrap().into_selection(), HashSet::from(READ_ONLY_MODULES));
+
+        let mut selection =
+            Some(RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Txpool]));
+        retain_read_only_modules(&mut selection);
+        assert_eq!(selection.unwrap().into_selection(), HashSet::from([RethRpcModule::Eth]));
+
+        let mut selection = None;
+        retain_read_only_modules(&mut selection);
+        assert_eq!(selection, None);
+
+        assert_eq!(unsupported_methods().method_names().count(), UNSUPPORTED_METHODS.len());
+    }
+}
@@ -115,7 +115,9 @@ impl ChainHardforks {
     /// based on the `Ord` implementation of [`ForkCondition`].
     ///
     /// If the fork already exists (regardless of its current condition type), it will be removed
-    /// and re-inserted at the appropriate position based on the new condition.
+    /// and re-inserted at the appropriate position based on the new condition. It keeps its
+    /// position among forks with an equal condition, so that forks activating together stay in
+    /// fork order. A new fork is inserted after the forks with an equal condition.
     ///
     /// # Ordering Behavior
     ///
@@ -137,14 +139,25 @@ impl ChainHardforks {
     /// ```
     pub fn insert<H: Hardfork>(&mut self, fork: H, condition: ForkCondition) {
         // Remove existing fork if it exists
+        let existing =
+            self.forks.iter().position(|(inner_fork, _)| inner_fork.name() == fork.name());
         self.remove(&fork);
 
-        // Find the correct position based on ForkCondition's Ord implementation
-        let pos = self
-            .forks
-            .iter()
-            .position(|(_, existing_condition)| *existing_condition > condition)
-            .unwrap_or(self.forks.len());
+        // Keep the position of an existing fork if it's still ordered there, otherwise find the
+        // correct position based on ForkCondition's Ord implementation
+        let pos = match existing {
+            Some(pos)
+                if self.forks[..pos].last().is_none_or(|(_, previous)| *previous <= condition) &&
+                    self.forks.get(pos).is_none_or(|(_, next)| condition <= *next) =>
+            {
+                pos
+            }
+            _ => self
+                .forks
+                .iter()
+                .position(|(_, existing_condition)| *existing_condition > condition)
+                .unwrap_or(self.forks.len()),
+        };
 
         self.map.insert(fork.name(), condition);
         self.forks.insert(pos, (Box::new(fork), condition));
@@ -285,6 +298,29 @@ mod tests {
         assert_eq!(fork_list[2].1, ForkCondition::Timestamp(2000));
     }
 
+    #[test]
+    fn insert_keeps_fork_order_of_equal_conditions() {
+        let mut forks = ChainHardforks::default();
+        forks.insert(AHardfork::A1, ForkCondition::Timestamp(1000));
+        forks.insert(AHardfork::A2, ForkCondition::Timestamp(2000));
+        forks.insert(AHardfork::A3, ForkCondition::Timestamp(3000));
+
+        // A2 moves to the activation of A3, but still activates before it
+        forks.insert(AHardfork::A2, ForkCondition::Timestamp(3000));
+        let names = forks.forks_iter().map(|(fork, _)| fork.name()).collect::<Vec<_>>();
+        assert_eq!(names, ["A1", "A2", "A3"]);
+
+        // A1 moves to the activation of A2, and so do both to the activation of A3
+        forks.insert(AHardfork::A1, ForkCondition::Timestamp(3000));
+        let names = forks.forks_iter().map(|(fork, _)| fork.name()).collect::<Vec<_>>();
+        assert_eq!(names, ["A1", "A2", "A3"]);
+
+        // A new fork with an equal condition activates after the existing ones
+        forks.insert(BHardfork::B1, ForkCondition::Timestamp(3000));
+        let names = forks.forks_iter().map(|(fork, _)| fork.name()).collect::<Vec<_>>();
+        assert_eq!(names, ["A1", "A2", "A3", "B1"]);
+    }
+
     #[test]
     fn extend_maintains_order() {
         let mut forks = ChainHardforks::default();
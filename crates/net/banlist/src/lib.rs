@@ -112,6 +112,11 @@ impl BanList {
         self.banned_peers.contains_key(peer_id)
     }
 
+    /// Returns the banned peers, with the timestamp that their ban expires at, if any.
+    pub fn banned_peers(&self) -> impl Iterator<Item = (PeerId, Option<Instant>)> + '_ {
+        self.banned_peers.iter().map(|(peer_id, until)| (*peer_id, *until))
+    }
+
     /// Unbans the ip address
     pub fn unban_ip(&mut self, ip: &IpAddr) {
         self.banned_ips.remove(ip);
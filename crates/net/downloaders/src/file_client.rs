@@ -197,6 +197,19 @@ impl<B: FullBlock> FileClient<B> {
     pub fn total_transactions(&self) -> usize {
         self.bodies.iter().fold(0, |acc, (_, body)| acc + body.transactions().len())
     }
+
+    /// Removes all blocks up to and including the given block number from the client.
+    pub fn remove_blocks_up_to(&mut self, block: BlockNumber) {
+        self.headers.retain(|number, _| *number > block);
+        let bodies = &mut self.bodies;
+        self.hash_to_number.retain(|hash, number| {
+            let keep = *number > block;
+            if !keep {
+                bodies.remove(hash);
+            }
+            keep
+        });
+    }
 }
 
 struct FileClientBuilder<B: Block> {
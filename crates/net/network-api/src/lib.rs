@@ -40,7 +40,12 @@ use reth_eth_wire_types::{
 };
 use reth_network_p2p::sync::NetworkSyncUpdater;
 use reth_network_peers::NodeRecord;
-use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
+use std::{
+    future::Future,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
 
 /// The `PeerId` type.
 pub type PeerId = alloy_primitives::B512;
@@ -221,6 +226,11 @@ pub trait Peers: PeersInfo {
         &self,
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<Reputation>, NetworkError>> + Send;
+
+    /// Returns the [`StaticPeerInfo`] for all [`PeerKind::Static`] peers, connected or not.
+    fn get_static_peers(
+        &self,
+    ) -> impl Future<Output = Result<Vec<StaticPeerInfo>, NetworkError>> + Send;
 }
 
 /// Info about an active peer session.
@@ -250,6 +260,74 @@ pub struct PeerInfo {
     pub session_established: Instant,
     /// The peer's connection kind
     pub kind: PeerKind,
+    /// The statistics of the block header and body responses of the peer, if it's available for
+    /// requests.
+    pub fetch_score: Option<PeerFetchScore>,
+}
+
+/// The rolling statistics of the block header and body responses of a peer, which rank the peer
+/// for the requests of the downloaders.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeerFetchScore {
+    /// The score of the peer, higher is better.
+    pub score: f64,
+    /// The number of responses, including timed out requests.
+    pub responses: u64,
+    /// The average response latency.
+    pub latency: Duration,
+    /// The rate of requests that timed out.
+    pub timeout_rate: f64,
+    /// The rate of responses that failed validation.
+    pub validation_failure_rate: f64,
+    /// The average throughput of useful bytes, in bytes per second.
+    pub throughput: f64,
+    /// Whether the peer isn't sent requests because too many of its responses failed validation.
+    pub in_cooldown: bool,
+}
+
+/// Info about a static peer, which the node keeps connected.
+#[derive(Debug, Clone)]
+pub struct StaticPeerInfo {
+    /// The peer's node record
+    pub record: NodeRecord,
+    /// The state of the connection to the peer
+    pub state: StaticPeerState,
+    /// The attempts to reconnect to the peer since the last session with it
+    pub reconnect_attempts: u32,
+    /// When the node last had a session with the peer, if it had one
+    pub last_seen: Option<SystemTime>,
+    /// When the peer is dialed again, if it's backed off after a failed attempt
+    pub next_attempt: Option<Instant>,
+}
+
+/// The state of the connection to a static peer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StaticPeerState {
+    /// There's an active session with the peer.
+    Connected,
+    /// The session with the peer is being disconnected.
+    Disconnecting,
+    /// The peer is being dialed.
+    Dialing,
+    /// The peer is backed off until the next attempt.
+    BackedOff,
+    /// The peer is banned for misbehaving.
+    Banned,
+    /// The peer isn't connected, and is dialed on the next refill of the peer slots.
+    Idle,
+}
+
+impl std::fmt::Display for StaticPeerState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Connected => write!(f, "connected"),
+            Self::Disconnecting => write!(f, "disconnecting"),
+            Self::Dialing => write!(f, "dialing"),
+            Self::BackedOff => write!(f, "backedOff"),
+            Self::Banned => write!(f, "banned"),
+            Self::Idle => write!(f, "idle"),
+        }
+    }
 }
 
 /// The direction of the connection.
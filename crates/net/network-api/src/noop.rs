@@ -11,7 +11,7 @@ use crate::{
     test_utils::{PeersHandle, PeersHandleProvider},
     BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerId, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    PeersInfo, StaticPeerInfo,
 };
 use alloy_rpc_types_admin::EthProtocolInfo;
 use enr::{secp256k1::SecretKey, Enr};
@@ -167,6 +167,10 @@ where
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    async fn get_static_peers(&self) -> Result<Vec<StaticPeerInfo>, NetworkError> {
+        Ok(vec![])
+    }
 }
 
 impl<Net> BlockDownloaderProvider for NoopNetwork<Net>
@@ -5,7 +5,7 @@ use std::net::SocketAddr;
 
 use derive_more::Constructor;
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::{Peer, ReputationChangeKind};
+use reth_network_types::{Peer, PersistedPeers, ReputationChangeKind};
 use tokio::sync::{mpsc, oneshot};
 
 /// Provides an API for managing the peers of the network.
@@ -64,6 +64,15 @@ impl PeersHandle {
 
         rx.await.unwrap_or_default()
     }
+
+    /// Returns the peers and the temporary bans of the peer set, as they are written to the peers
+    /// file.
+    pub async fn persisted_peers(&self) -> PersistedPeers {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::GetPersistedPeers(tx));
+
+        rx.await.unwrap_or_default()
+    }
 }
 
 /// Commands the `PeersManager` listens for.
@@ -81,4 +90,6 @@ pub enum PeerCommand {
     GetPeer(PeerId, oneshot::Sender<Option<Peer>>),
     /// Get node information on all peers
     GetPeers(oneshot::Sender<Vec<NodeRecord>>),
+    /// Get the peers and the temporary bans to write to the peers file
+    GetPersistedPeers(oneshot::Sender<PersistedPeers>),
 }
@@ -30,6 +30,10 @@ pub use peers::{
         DEFAULT_REPUTATION,
     },
     state::PeerConnectionState,
-    ConnectionsConfig, Peer, PeersConfig,
+    ConnectionsConfig, Peer, PeerScoringConfig, PeersConfig, PersistedPeers,
 };
 pub use session::{SessionLimits, SessionsConfig};
+
+// Enables the serde impls of `PeerId`
+#[cfg(feature = "serde")]
+use alloy_primitives as _;
@@ -1,17 +1,10 @@
 //! Configuration for peering.
 
-use std::{
-    collections::HashSet,
-    io::{self, ErrorKind},
-    path::Path,
-    time::Duration,
-};
+use std::{collections::HashSet, time::Duration};
 
+use crate::{BackoffKind, PeerScoringConfig, PersistedPeers, ReputationChangeWeights};
 use reth_net_banlist::{BanList, IpFilter};
 use reth_network_peers::{NodeRecord, TrustedPeer};
-use tracing::info;
-
-use crate::{BackoffKind, ReputationChangeWeights};
 
 /// Maximum number of available slots for outbound sessions.
 pub const DEFAULT_MAX_COUNT_PEERS_OUTBOUND: u32 = 100;
@@ -95,6 +88,34 @@ impl Default for PeerBackoffDurations {
     }
 }
 
+/// The backoff between the attempts to reconnect to a static peer.
+///
+/// The backoff starts at the minimum duration and doubles with every attempt, up to the maximum
+/// duration. It's reset once a session with the peer is established.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StaticReconnectBackoff {
+    /// The backoff after a session with the peer was closed, or the first attempt failed.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub min: Duration,
+    /// The maximum backoff.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub max: Duration,
+}
+
+impl StaticReconnectBackoff {
+    /// Returns the backoff before the next attempt, given the attempts since the last session.
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        self.min.saturating_mul(2u32.saturating_pow(attempts)).min(self.max.max(self.min))
+    }
+}
+
+impl Default for StaticReconnectBackoff {
+    fn default() -> Self {
+        Self { min: Duration::from_secs(1), max: Duration::from_secs(60) }
+    }
+}
+
 /// Tracks stats about connected nodes
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
@@ -131,6 +152,13 @@ pub struct PeersConfig {
     /// Connect to or accept from trusted nodes only?
     #[cfg_attr(feature = "serde", serde(alias = "connect_trusted_nodes_only"))]
     pub trusted_nodes_only: bool,
+    /// Static nodes that are always kept connected.
+    ///
+    /// They're redialed with the [`StaticReconnectBackoff`] when the session ends or a dial fails,
+    /// and connected even if all slots for peers are taken.
+    pub static_nodes: Vec<TrustedPeer>,
+    /// The backoff between the attempts to reconnect to static nodes.
+    pub static_reconnect_backoff: StaticReconnectBackoff,
     /// Interval to update trusted nodes DNS resolution
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub trusted_nodes_resolution_interval: Duration,
@@ -147,6 +175,9 @@ pub struct PeersConfig {
     /// Basic nodes to connect to.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub basic_nodes: HashSet<NodeRecord>,
+    /// Peers and bans of an earlier run, read from the peers file.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persisted_peers: PersistedPeers,
     /// How long to ban bad peers.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub ban_duration: Duration,
@@ -172,6 +203,8 @@ pub struct PeersConfig {
     /// IPs within the specified CIDR ranges will be allowed.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub ip_filter: IpFilter,
+    /// How to rank peers for the block header and body requests of the downloaders.
+    pub scoring: PeerScoringConfig,
 }
 
 impl Default for PeersConfig {
@@ -186,11 +219,15 @@ impl Default for PeersConfig {
             backoff_durations: Default::default(),
             trusted_nodes: Default::default(),
             trusted_nodes_only: false,
+            static_nodes: Default::default(),
+            static_reconnect_backoff: Default::default(),
             trusted_nodes_resolution_interval: Duration::from_secs(60 * 60),
             basic_nodes: Default::default(),
+            persisted_peers: Default::default(),
             max_backoff_count: 5,
             incoming_ip_throttle_duration: INBOUND_IP_THROTTLE_DURATION,
             ip_filter: IpFilter::default(),
+            scoring: Default::default(),
         }
     }
 }
@@ -260,12 +297,46 @@ impl PeersConfig {
         self
     }
 
+    /// Nodes to always keep connected.
+    pub fn with_static_nodes(mut self, nodes: Vec<TrustedPeer>) -> Self {
+        self.static_nodes = nodes;
+        self
+    }
+
+    /// Configures the backoff between the attempts to reconnect to static nodes.
+    pub const fn with_static_reconnect_backoff(mut self, backoff: StaticReconnectBackoff) -> Self {
+        self.static_reconnect_backoff = backoff;
+        self
+    }
+
+    /// Configures the minimum and maximum backoff between the attempts to reconnect to static
+    /// nodes, if set.
+    pub const fn with_static_reconnect_backoff_opt(
+        mut self,
+        min: Option<Duration>,
+        max: Option<Duration>,
+    ) -> Self {
+        if let Some(min) = min {
+            self.static_reconnect_backoff.min = min;
+        }
+        if let Some(max) = max {
+            self.static_reconnect_backoff.max = max;
+        }
+        self
+    }
+
     /// Nodes available at launch.
     pub fn with_basic_nodes(mut self, nodes: HashSet<NodeRecord>) -> Self {
         self.basic_nodes = nodes;
         self
     }
 
+    /// Peers and bans of an earlier run to seed the peer set with.
+    pub fn with_persisted_peers(mut self, persisted_peers: PersistedPeers) -> Self {
+        self.persisted_peers = persisted_peers;
+        self
+    }
+
     /// Configures the max allowed backoff count.
     pub const fn with_max_backoff_count(mut self, max_backoff_count: u8) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -287,25 +358,38 @@ impl PeersConfig {
         self
     }
 
+    /// Configures how to rank peers for the requests of the downloaders.
+    pub const fn with_scoring(mut self, scoring: PeerScoringConfig) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
     /// Returns the maximum number of peers, inbound and outbound.
     pub const fn max_peers(&self) -> usize {
         self.connection_info.max_outbound + self.connection_info.max_inbound
     }
 
-    /// Read from file nodes available at launch. Ignored if None.
+    /// Read from the peers file the peers and bans of an earlier run. Ignored if None, or if the
+    /// file doesn't exist.
+    ///
+    /// See [`PersistedPeers::read_from_file`].
+    #[cfg(feature = "serde")]
     pub fn with_basic_nodes_from_file(
         self,
-        optional_file: Option<impl AsRef<Path>>,
-    ) -> Result<Self, io::Error> {
+        optional_file: Option<impl AsRef<std::path::Path>>,
+    ) -> Result<Self, std::io::Error> {
         let Some(file_path) = optional_file else { return Ok(self) };
-        let reader = match std::fs::File::open(file_path.as_ref()) {
-            Ok(file) => io::BufReader::new(file),
-            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(self),
-            Err(e) => Err(e)?,
+        let Some(peers) = PersistedPeers::read_from_file(file_path.as_ref())? else {
+            return Ok(self)
         };
-        info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
-        let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
-        Ok(self.with_basic_nodes(nodes))
+        tracing::info!(
+            target: "net::peers",
+            file = %file_path.as_ref().display(),
+            peers = peers.peers.len(),
+            banned = peers.banned.len(),
+            "Loading saved peers"
+        );
+        Ok(self.with_persisted_peers(peers))
     }
 
     /// Configure the IP filter for restricting network connections to specific IP ranges.
@@ -1,13 +1,18 @@
 pub mod addr;
 pub mod config;
 pub mod kind;
+pub mod persisted;
 pub mod reputation;
+pub mod scoring;
 pub mod state;
 
 pub use config::{ConnectionsConfig, PeersConfig};
+pub use persisted::{BannedPeer, PersistedPeer, PersistedPeers};
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
+pub use scoring::PeerScoringConfig;
 
 use alloy_eip2124::ForkId;
+use std::time::SystemTime;
 use tracing::trace;
 
 use crate::{
@@ -35,6 +40,10 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// Counts the attempts to reconnect to a static peer since the last session with it.
+    pub reconnect_attempts: u32,
+    /// When the node last established a session with the peer, if it did.
+    pub last_seen: Option<SystemTime>,
 }
 
 // === impl Peer ===
@@ -66,6 +75,8 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            reconnect_attempts: 0,
+            last_seen: None,
         }
     }
 
@@ -0,0 +1,206 @@
+//! The peer set as it's written to the peers file, which seeds the peer set after a restart.
+
+use alloy_eip2124::ForkId;
+use reth_network_peers::{NodeRecord, PeerId};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The known peers and the temporarily banned peers of the peer set.
+///
+/// The node writes these to the peers file periodically and on shutdown, and seeds the peer set
+/// with them on startup, before discovery finds any peers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct PersistedPeers {
+    /// The known peers.
+    pub peers: Vec<PersistedPeer>,
+    /// The peers that are banned until a point in time.
+    ///
+    /// Indefinite bans are not persisted, since they come from the configuration.
+    pub banned: Vec<BannedPeer>,
+}
+
+impl PersistedPeers {
+    /// Reads the peers file, and returns `None` if it doesn't exist.
+    ///
+    /// Files that only contain a list of enodes, as written by earlier versions, are read as peers
+    /// with the default reputation.
+    #[cfg(feature = "serde")]
+    pub fn read_from_file(path: &std::path::Path) -> std::io::Result<Option<Self>> {
+        /// The formats of the peers file.
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum PeersFile {
+            Enodes(Vec<NodeRecord>),
+            Peers(PersistedPeers),
+        }
+
+        let reader = match std::fs::File::open(path) {
+            Ok(file) => std::io::BufReader::new(file),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let peers = match serde_json::from_reader(reader)? {
+            PeersFile::Enodes(enodes) => {
+                Self { peers: enodes.into_iter().map(PersistedPeer::new).collect(), banned: vec![] }
+            }
+            PeersFile::Peers(peers) => peers,
+        };
+        Ok(Some(peers))
+    }
+
+    /// Merges the other peer set into this one.
+    ///
+    /// Of the peers that are in both sets, the one that was seen last is kept, and bans are kept
+    /// until the later expiry.
+    pub fn merge(&mut self, other: Self) {
+        let mut peers =
+            self.peers.drain(..).map(|peer| (peer.enode.id, peer)).collect::<HashMap<_, _>>();
+        for peer in other.peers {
+            match peers.entry(peer.enode.id) {
+                Entry::Occupied(mut entry) => {
+                    if peer.last_seen > entry.get().last_seen {
+                        entry.insert(peer);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(peer);
+                }
+            }
+        }
+        self.peers = peers.into_values().collect();
+
+        let mut banned =
+            self.banned.drain(..).map(|ban| (ban.id, ban.until)).collect::<HashMap<_, _>>();
+        for ban in other.banned {
+            let until = banned.entry(ban.id).or_default();
+            *until = ban.until.max(*until);
+        }
+        self.banned = banned.into_iter().map(|(id, until)| BannedPeer { id, until }).collect();
+    }
+
+    /// Removes the bans that expired at the given unix timestamp.
+    pub fn remove_expired_bans(&mut self, now: u64) {
+        self.banned.retain(|ban| ban.until > now);
+    }
+}
+
+/// A known peer of the peer set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PersistedPeer {
+    /// The enode of the peer, with the addresses that it announced via discovery.
+    pub enode: NodeRecord,
+    /// The [`ForkId`] that the peer announced via discovery.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub fork_id: Option<ForkId>,
+    /// The reputation of the peer.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reputation: i32,
+    /// When the node last established a session with the peer, in seconds since the unix epoch.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub last_seen: Option<u64>,
+}
+
+impl PersistedPeer {
+    /// Returns a peer with the default reputation that the node never had a session with.
+    pub const fn new(enode: NodeRecord) -> Self {
+        Self { enode, fork_id: None, reputation: crate::DEFAULT_REPUTATION, last_seen: None }
+    }
+}
+
+/// A peer that is banned until a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BannedPeer {
+    /// The id of the peer.
+    pub id: PeerId,
+    /// When the ban expires, in seconds since the unix epoch.
+    pub until: u64,
+}
+
+/// Returns the seconds since the unix epoch of the given time.
+pub fn unix_timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn enode(id: u8) -> NodeRecord {
+        NodeRecord::new((Ipv4Addr::LOCALHOST, 30303).into(), PeerId::with_last_byte(id))
+    }
+
+    #[test]
+    fn merge_keeps_last_seen_peer_and_latest_ban() {
+        let mut peers = PersistedPeers {
+            peers: vec![
+                PersistedPeer {
+                    last_seen: Some(10),
+                    reputation: -1,
+                    ..PersistedPeer::new(enode(1))
+                },
+                PersistedPeer { last_seen: Some(30), ..PersistedPeer::new(enode(2)) },
+            ],
+            banned: vec![BannedPeer { id: PeerId::with_last_byte(3), until: 100 }],
+        };
+        peers.merge(PersistedPeers {
+            peers: vec![
+                PersistedPeer { last_seen: Some(20), ..PersistedPeer::new(enode(1)) },
+                PersistedPeer { last_seen: None, reputation: -1, ..PersistedPeer::new(enode(2)) },
+                PersistedPeer::new(enode(4)),
+            ],
+            banned: vec![
+                BannedPeer { id: PeerId::with_last_byte(3), until: 50 },
+                BannedPeer { id: PeerId::with_last_byte(4), until: 200 },
+            ],
+        });
+
+        peers.peers.sort_by_key(|peer| peer.enode.id);
+        assert_eq!(
+            peers.peers,
+            vec![
+                PersistedPeer { last_seen: Some(20), ..PersistedPeer::new(enode(1)) },
+                PersistedPeer { last_seen: Some(30), ..PersistedPeer::new(enode(2)) },
+                PersistedPeer::new(enode(4)),
+            ]
+        );
+
+        peers.remove_expired_bans(150);
+        assert_eq!(peers.banned, vec![BannedPeer { id: PeerId::with_last_byte(4), until: 200 }]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn read_peers_file() {
+        let dir = std::env::temp_dir().join(format!("reth-peers-file-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("peers.json");
+        assert_eq!(PersistedPeers::read_from_file(&path).unwrap(), None);
+
+        // The list of enodes of earlier versions
+        std::fs::write(&path, serde_json::to_vec(&[enode(1)]).unwrap()).unwrap();
+        assert_eq!(
+            PersistedPeers::read_from_file(&path).unwrap(),
+            Some(PersistedPeers { peers: vec![PersistedPeer::new(enode(1))], banned: vec![] })
+        );
+
+        let peers = PersistedPeers {
+            peers: vec![PersistedPeer {
+                reputation: -1024,
+                last_seen: Some(1),
+                ..PersistedPeer::new(enode(2))
+            }],
+            banned: vec![BannedPeer { id: PeerId::with_last_byte(3), until: 100 }],
+        };
+        std::fs::write(&path, serde_json::to_vec(&peers).unwrap()).unwrap();
+        assert_eq!(PersistedPeers::read_from_file(&path).unwrap(), Some(peers));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
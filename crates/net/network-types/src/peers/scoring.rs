@@ -0,0 +1,110 @@
+//! Configuration for ranking peers by the quality of their responses.
+
+use std::time::Duration;
+
+/// How long a peer that sent too many invalid responses isn't sent requests by default.
+pub const DEFAULT_SCORING_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// How the block header and body requests of the downloaders are assigned to peers.
+///
+/// Every response updates rolling statistics of the peer: the latency, the timeout rate, the rate
+/// of responses that failed validation and the throughput of useful bytes. Idle peers are ranked by
+/// a score that weighs these statistics, while every [`Self::exploration_interval`]-th request goes
+/// to the least sampled idle peer, so that new peers and peers that improved are measured too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PeerScoringConfig {
+    /// Whether requests are assigned by the score of the peers.
+    ///
+    /// Otherwise, the peer with the lowest request timeout is picked.
+    pub enabled: bool,
+    /// Weight of the average response latency, per second.
+    pub latency_weight: u32,
+    /// Weight of the rate of requests that timed out.
+    pub timeout_weight: u32,
+    /// Weight of the rate of responses that failed validation.
+    pub validation_failure_weight: u32,
+    /// Weight of the average throughput of useful bytes, per MiB/s.
+    pub throughput_weight: u32,
+    /// Every n-th request is sent to the idle peer with the fewest responses.
+    ///
+    /// `0` disables the exploration.
+    pub exploration_interval: u32,
+    /// The percentage of responses that failed validation above which a peer is put in cooldown.
+    pub validation_failure_threshold: u8,
+    /// The number of responses of a peer before it can be put in cooldown.
+    pub min_samples: u32,
+    /// How long a peer in cooldown isn't sent requests, unless no other peer is idle.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub cooldown: Duration,
+}
+
+impl Default for PeerScoringConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            latency_weight: 1,
+            timeout_weight: 4,
+            validation_failure_weight: 8,
+            throughput_weight: 1,
+            exploration_interval: 10,
+            validation_failure_threshold: 50,
+            min_samples: 4,
+            cooldown: DEFAULT_SCORING_COOLDOWN,
+        }
+    }
+}
+
+impl PeerScoringConfig {
+    /// Returns the configuration that picks peers by their request timeout only, like before
+    /// scoring was introduced.
+    pub fn disabled() -> Self {
+        Self { enabled: false, ..Default::default() }
+    }
+
+    /// Returns the score of a peer with the given rolling statistics, higher is better.
+    ///
+    /// The rates are in `0..=1`, the throughput in bytes per second.
+    pub fn score(
+        &self,
+        latency: Duration,
+        timeout_rate: f64,
+        validation_failure_rate: f64,
+        throughput: f64,
+    ) -> f64 {
+        f64::from(self.throughput_weight) * throughput / (1024.0 * 1024.0) -
+            f64::from(self.latency_weight) * latency.as_secs_f64() -
+            f64::from(self.timeout_weight) * timeout_rate -
+            f64::from(self.validation_failure_weight) * validation_failure_rate
+    }
+
+    /// Returns `true` if a peer with this many responses and validation failure rate is put in
+    /// cooldown.
+    pub fn exceeds_failure_threshold(&self, samples: u64, validation_failure_rate: f64) -> bool {
+        samples >= u64::from(self.min_samples) &&
+            validation_failure_rate * 100.0 > f64::from(self.validation_failure_threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_prefers_fast_and_valid_peers() {
+        let config = PeerScoringConfig::default();
+        let fast = config.score(Duration::from_millis(50), 0.0, 0.0, 2.0 * 1024.0 * 1024.0);
+        let slow = config.score(Duration::from_secs(2), 0.0, 0.0, 64.0 * 1024.0);
+        let malicious = config.score(Duration::from_millis(50), 0.0, 0.9, 2.0 * 1024.0 * 1024.0);
+        let flaky = config.score(Duration::from_millis(50), 0.5, 0.0, 2.0 * 1024.0 * 1024.0);
+        assert!(fast > slow);
+        assert!(fast > malicious);
+        assert!(fast > flaky);
+        assert!(slow > malicious);
+
+        assert!(!config.exceeds_failure_threshold(3, 1.0));
+        assert!(!config.exceeds_failure_threshold(4, 0.5));
+        assert!(config.exceeds_failure_threshold(4, 0.6));
+    }
+}
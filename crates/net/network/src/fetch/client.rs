@@ -35,6 +35,8 @@ use tokio::sync::{mpsc::UnboundedSender, oneshot};
 pub struct FetchClient<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// Sender half of the request channel.
     pub(crate) request_tx: UnboundedSender<DownloadRequest<N>>,
+    /// Sender half of the channel that reports peers whose responses failed validation.
+    pub(crate) bad_responses_tx: UnboundedSender<PeerId>,
     /// The handle to the peers
     pub(crate) peers_handle: PeersHandle,
     /// Number of active peer sessions the node's currently handling.
@@ -44,6 +46,7 @@ pub struct FetchClient<N: NetworkPrimitives = EthNetworkPrimitives> {
 impl<N: NetworkPrimitives> DownloadClient for FetchClient<N> {
     fn report_bad_message(&self, peer_id: PeerId) {
         self.peers_handle.reputation_change(peer_id, ReputationChangeKind::BadMessage);
+        let _ = self.bad_responses_tx.send(peer_id);
     }
 
     fn num_connected_peers(&self) -> usize {
@@ -1,6 +1,7 @@
 //! Fetch data from the network.
 
 mod client;
+mod score;
 
 pub use client::FetchClient;
 
@@ -10,14 +11,16 @@ use futures::StreamExt;
 use reth_eth_wire::{
     Capabilities, EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives,
 };
-use reth_network_api::test_utils::PeersHandle;
+use reth_network_api::{test_utils::PeersHandle, PeerFetchScore};
 use reth_network_p2p::{
     error::{EthResponseValidator, PeerRequestResult, RequestError, RequestResult},
     headers::client::HeadersRequest,
     priority::Priority,
 };
 use reth_network_peers::PeerId;
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{PeerScoringConfig, ReputationChangeKind};
+use reth_primitives_traits::InMemorySize;
+use score::PeerStats;
 use std::{
     collections::{HashMap, VecDeque},
     ops::RangeInclusive,
@@ -26,6 +29,7 @@ use std::{
         Arc,
     },
     task::{Context, Poll},
+    time::Instant,
 };
 use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -57,13 +61,26 @@ pub struct StateFetcher<N: NetworkPrimitives = EthNetworkPrimitives> {
     download_requests_rx: UnboundedReceiverStream<DownloadRequest<N>>,
     /// Sender for download requests, used to detach a [`FetchClient`]
     download_requests_tx: UnboundedSender<DownloadRequest<N>>,
+    /// Receiver for the peers whose responses failed validation in the downloaders
+    bad_responses_rx: UnboundedReceiverStream<PeerId>,
+    /// Sender for the peers whose responses failed validation, used to detach a [`FetchClient`]
+    bad_responses_tx: UnboundedSender<PeerId>,
+    /// How requests are assigned to peers.
+    scoring: PeerScoringConfig,
+    /// The number of requests that were assigned to a peer, which paces the exploration.
+    num_assigned_requests: u64,
 }
 
 // === impl StateSyncer ===
 
 impl<N: NetworkPrimitives> StateFetcher<N> {
-    pub(crate) fn new(peers_handle: PeersHandle, num_active_peers: Arc<AtomicUsize>) -> Self {
+    pub(crate) fn new(
+        peers_handle: PeersHandle,
+        num_active_peers: Arc<AtomicUsize>,
+        scoring: PeerScoringConfig,
+    ) -> Self {
         let (download_requests_tx, download_requests_rx) = mpsc::unbounded_channel();
+        let (bad_responses_tx, bad_responses_rx) = mpsc::unbounded_channel();
         Self {
             inflight_headers_requests: Default::default(),
             inflight_bodies_requests: Default::default(),
@@ -73,6 +90,10 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             queued_requests: Default::default(),
             download_requests_rx: UnboundedReceiverStream::new(download_requests_rx),
             download_requests_tx,
+            bad_responses_rx: UnboundedReceiverStream::new(bad_responses_rx),
+            bad_responses_tx,
+            scoring,
+            num_assigned_requests: 0,
         }
     }
 
@@ -96,6 +117,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 timeout,
                 last_response_likely_bad: false,
                 range_info,
+                stats: Default::default(),
             },
         );
     }
@@ -137,16 +159,66 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         }
     }
 
+    /// Returns the statistics of the responses of the peer, if it's connected.
+    pub(crate) fn peer_fetch_score(&self, peer_id: &PeerId) -> Option<PeerFetchScore> {
+        let peer = self.peers.get(peer_id)?;
+        Some(peer.stats.fetch_score(&self.scoring, Instant::now()))
+    }
+
+    /// Returns the idle peer the request is assigned to.
+    ///
+    /// With scoring, every n-th request explores the least sampled peer instead of the best one.
+    fn next_peer(&mut self, request: &DownloadRequest<N>) -> Option<PeerId> {
+        let requirement = request.best_peer_requirements();
+        let explore = self.scoring.enabled &&
+            self.scoring.exploration_interval != 0 &&
+            (self.num_assigned_requests + 1) % self.scoring.exploration_interval as u64 == 0;
+        let peer_id = explore
+            .then(|| self.least_sampled_peer(&requirement))
+            .flatten()
+            .or_else(|| self.next_best_peer(requirement))?;
+        self.num_assigned_requests += 1;
+        Some(peer_id)
+    }
+
+    /// Returns the idle peer with the fewest responses that isn't in cooldown and meets the
+    /// requirements, preferring the lowest timeout among those.
+    fn least_sampled_peer(&self, requirement: &BestPeerRequirements) -> Option<PeerId> {
+        let now = Instant::now();
+        self.peers
+            .iter()
+            .filter(|(_, peer)| {
+                peer.state.is_idle() && !peer.stats.in_cooldown(now) && peer.meets(requirement)
+            })
+            .min_by_key(|(_, peer)| (peer.stats.samples(), peer.timeout()))
+            .map(|(peer_id, _)| *peer_id)
+    }
+
     /// Returns the _next_ idle peer that's ready to accept a request,
     /// prioritizing those with the lowest timeout/latency and those that recently responded with
     /// adequate data. Additionally, if full blocks are required this prioritizes peers that have
-    /// full history available
+    /// full history available.
+    ///
+    /// With scoring, peers in cooldown are only picked if no other peer is idle, and the score of
+    /// the peers takes precedence over their timeout.
     fn next_best_peer(&self, requirement: BestPeerRequirements) -> Option<PeerId> {
+        let now = Instant::now();
         let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
 
         let mut best_peer = idle.next()?;
 
         for maybe_better in idle {
+            if self.scoring.enabled {
+                match (best_peer.1.stats.in_cooldown(now), maybe_better.1.stats.in_cooldown(now)) {
+                    (true, false) => {
+                        best_peer = maybe_better;
+                        continue
+                    }
+                    (false, true) => continue,
+                    _ => {}
+                }
+            }
+
             // replace best peer if our current best peer sent us a bad response last time
             if best_peer.1.last_response_likely_bad && !maybe_better.1.last_response_likely_bad {
                 best_peer = maybe_better;
@@ -159,10 +231,26 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 continue
             }
 
+            if maybe_better.1.last_response_likely_bad {
+                continue
+            }
+
+            // replace best peer if this peer has a better score and both have same range quality
+            if self.scoring.enabled {
+                let (score, best_score) = (
+                    maybe_better.1.stats.score(&self.scoring),
+                    best_peer.1.stats.score(&self.scoring),
+                );
+                if score != best_score {
+                    if score > best_score {
+                        best_peer = maybe_better;
+                    }
+                    continue
+                }
+            }
+
             // replace best peer if this peer has better rtt and both have same range quality
-            if maybe_better.1.timeout() < best_peer.1.timeout() &&
-                !maybe_better.1.last_response_likely_bad
-            {
+            if maybe_better.1.timeout() < best_peer.1.timeout() {
                 best_peer = maybe_better;
             }
         }
@@ -178,7 +266,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         }
 
         let request = self.queued_requests.pop_front().expect("not empty");
-        let Some(peer_id) = self.next_best_peer(request.best_peer_requirements()) else {
+        let Some(peer_id) = self.next_peer(&request) else {
             // need to put back the the request
             self.queued_requests.push_front(request);
             return PollAction::NoPeersAvailable
@@ -191,6 +279,12 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
 
     /// Advance the state the syncer
     pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<FetchAction> {
+        while let Poll::Ready(Some(peer_id)) = self.bad_responses_rx.poll_next_unpin(cx) {
+            if let Some(peer) = self.peers.get_mut(&peer_id) {
+                peer.stats.on_validation_failure(&self.scoring);
+            }
+        }
+
         // drain buffered actions first
         loop {
             let no_peers_available = match self.poll_action() {
@@ -241,7 +335,8 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
 
         match req {
             DownloadRequest::GetBlockHeaders { request, response, .. } => {
-                let inflight = Request { request: request.clone(), response };
+                let inflight =
+                    Request { request: request.clone(), response, started: Instant::now() };
                 self.inflight_headers_requests.insert(peer_id, inflight);
                 let HeadersRequest { start, limit, direction } = request;
                 BlockRequest::GetBlockHeaders(GetBlockHeaders {
@@ -252,7 +347,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 })
             }
             DownloadRequest::GetBlockBodies { request, response, .. } => {
-                let inflight = Request { request: (), response };
+                let inflight = Request { request: (), response, started: Instant::now() };
                 self.inflight_bodies_requests.insert(peer_id, inflight);
                 BlockRequest::GetBlockBodies(GetBlockBodies(request))
             }
@@ -261,9 +356,13 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
 
     /// Returns a new followup request for the peer.
     ///
+    /// With scoring, the request goes to the best idle peer instead, which may be this peer.
+    ///
     /// Caution: this expects that the peer is _not_ closed.
     fn followup_request(&mut self, peer_id: PeerId) -> Option<BlockResponseOutcome> {
         let req = self.queued_requests.pop_front()?;
+        let peer_id =
+            if self.scoring.enabled { self.next_peer(&req).unwrap_or(peer_id) } else { peer_id };
         let req = self.prepare_block_request(peer_id, req);
         Some(BlockResponseOutcome::Request(peer_id, req))
     }
@@ -286,6 +385,9 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         let is_likely_bad_response =
             resp.as_ref().is_some_and(|r| res.is_likely_bad_headers_response(&r.request));
 
+        let started = resp.as_ref().map(|resp| resp.started);
+        let sample = ResponseSample::new(&res, is_likely_bad_response);
+
         if let Some(resp) = resp {
             // delegate the response
             let _ = resp.response.send(res.map(|h| (peer_id, h).into()));
@@ -294,6 +396,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
+            peer.record_response(&self.scoring, started, sample);
 
             // If the peer is still ready to accept new requests, we try to send a followup
             // request immediately.
@@ -315,13 +418,17 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         res: RequestResult<Vec<N::BlockBody>>,
     ) -> Option<BlockResponseOutcome> {
         let is_likely_bad_response = res.as_ref().map_or(true, |bodies| bodies.is_empty());
+        let sample = ResponseSample::new(&res, is_likely_bad_response);
 
-        if let Some(resp) = self.inflight_bodies_requests.remove(&peer_id) {
+        let started = self.inflight_bodies_requests.remove(&peer_id).map(|resp| {
+            let started = resp.started;
             let _ = resp.response.send(res.map(|b| (peer_id, b).into()));
-        }
+            started
+        });
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
+            peer.record_response(&self.scoring, started, sample);
 
             if peer.state.on_request_finished() && !is_likely_bad_response {
                 return self.followup_request(peer_id)
@@ -334,6 +441,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     pub(crate) fn client(&self) -> FetchClient<N> {
         FetchClient {
             request_tx: self.download_requests_tx.clone(),
+            bad_responses_tx: self.bad_responses_tx.clone(),
             peers_handle: self.peers_handle.clone(),
             num_active_peers: Arc::clone(&self.num_active_peers),
         }
@@ -370,6 +478,8 @@ struct Peer {
     last_response_likely_bad: bool,
     /// Tracks the range info for the peer.
     range_info: Option<BlockRangeInfo>,
+    /// Rolling statistics of the responses of the peer.
+    stats: PeerStats,
 }
 
 impl Peer {
@@ -377,6 +487,24 @@ impl Peer {
         self.timeout.load(Ordering::Relaxed)
     }
 
+    /// Records the response to a request that was sent at `started`.
+    ///
+    /// Responses to requests that aren't tracked aren't recorded, except for timeouts.
+    fn record_response(
+        &mut self,
+        config: &PeerScoringConfig,
+        started: Option<Instant>,
+        sample: ResponseSample,
+    ) {
+        match (sample, started) {
+            (ResponseSample::Timeout, _) => self.stats.on_timeout(),
+            (ResponseSample::Response { bytes, valid }, Some(started)) => {
+                self.stats.on_response(config, started.elapsed(), bytes, valid)
+            }
+            _ => {}
+        }
+    }
+
     /// Returns the earliest block number available from the peer.
     fn earliest(&self) -> u64 {
         self.range_info.as_ref().map_or(0, |info| info.earliest())
@@ -434,6 +562,17 @@ impl Peer {
         }
     }
 
+    /// Returns true if the peer can serve requests with the given requirements.
+    fn meets(&self, requirement: &BestPeerRequirements) -> bool {
+        match requirement {
+            BestPeerRequirements::None => true,
+            BestPeerRequirements::FullBlockRange(range) => self.range().is_none_or(|self_r| {
+                self_r.contains(range.start()) && self_r.contains(range.end())
+            }),
+            BestPeerRequirements::FullBlock => self.has_full_history(),
+        }
+    }
+
     /// Returns true if this peer is better than the other peer based on the given requirements.
     fn is_better(&self, other: &Self, requirement: &BestPeerRequirements) -> bool {
         match requirement {
@@ -444,6 +583,36 @@ impl Peer {
     }
 }
 
+/// How a response counts towards the statistics of the peer.
+#[derive(Debug, Clone, Copy)]
+enum ResponseSample {
+    /// The request timed out.
+    Timeout,
+    /// The peer responded, with a response that is either valid or likely bad.
+    Response {
+        /// The in-memory size of the response.
+        bytes: usize,
+        /// Whether the response is not likely bad.
+        valid: bool,
+    },
+    /// The request failed for reasons unrelated to the peer's quality, like a dropped connection.
+    Ignored,
+}
+
+impl ResponseSample {
+    fn new<T: InMemorySize>(res: &RequestResult<Vec<T>>, is_likely_bad: bool) -> Self {
+        match res {
+            Ok(items) => Self::Response {
+                bytes: items.iter().map(InMemorySize::size).sum(),
+                valid: !is_likely_bad,
+            },
+            Err(RequestError::Timeout) => Self::Timeout,
+            Err(RequestError::BadResponse) => Self::Response { bytes: 0, valid: false },
+            Err(_) => Self::Ignored,
+        }
+    }
+}
+
 /// Tracks the state of an individual peer
 #[derive(Debug)]
 enum PeerState {
@@ -487,6 +656,8 @@ struct Request<Req, Resp> {
     // TODO: this can be attached to the response in error case
     request: Req,
     response: oneshot::Sender<Resp>,
+    /// When the request was sent to the peer.
+    started: Instant,
 }
 
 /// Requests that can be sent to the Syncer from a [`FetchClient`]
@@ -585,13 +756,16 @@ mod tests {
     use crate::{peers::PeersManager, PeersConfig};
     use alloy_consensus::Header;
     use alloy_primitives::B512;
-    use std::future::poll_fn;
+    use std::{collections::HashSet, future::poll_fn, time::Duration};
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_poll_fetcher() {
         let manager = PeersManager::new(PeersConfig::default());
-        let mut fetcher =
-            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let mut fetcher = StateFetcher::<EthNetworkPrimitives>::new(
+            manager.handle(),
+            Default::default(),
+            Default::default(),
+        );
 
         poll_fn(move |cx| {
             assert!(fetcher.poll(cx).is_pending());
@@ -612,8 +786,11 @@ mod tests {
     #[tokio::test]
     async fn test_peer_rotation() {
         let manager = PeersManager::new(PeersConfig::default());
-        let mut fetcher =
-            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let mut fetcher = StateFetcher::<EthNetworkPrimitives>::new(
+            manager.handle(),
+            Default::default(),
+            Default::default(),
+        );
         // Add a few random peers
         let peer1 = B512::random();
         let peer2 = B512::random();
@@ -651,8 +828,11 @@ mod tests {
     #[tokio::test]
     async fn test_peer_prioritization() {
         let manager = PeersManager::new(PeersConfig::default());
-        let mut fetcher =
-            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let mut fetcher = StateFetcher::<EthNetworkPrimitives>::new(
+            manager.handle(),
+            Default::default(),
+            Default::default(),
+        );
         // Add a few random peers
         let peer1 = B512::random();
         let peer2 = B512::random();
@@ -696,11 +876,242 @@ mod tests {
         assert_eq!(fetcher.next_best_peer(BestPeerRequirements::None), Some(peer2));
     }
 
+    /// A test peer that responds after a fixed latency, with headers that don't match the request
+    /// if it's malicious.
+    #[derive(Debug, Clone, Copy)]
+    struct SimPeer {
+        latency: Duration,
+        malicious: bool,
+    }
+
+    /// Assigns the single header requests of the simulation to peers.
+    trait Scheduler {
+        /// Queues the request for the header of the block.
+        fn queue(&mut self, block: u64, front: bool);
+
+        /// Returns the number of queued requests.
+        fn num_queued(&self) -> usize;
+
+        /// Assigns queued requests to idle peers.
+        fn assign(&mut self) -> Vec<(PeerId, u64)>;
+
+        /// Handles the response of the peer, and returns the followup request it triggered.
+        fn on_response(
+            &mut self,
+            peer_id: PeerId,
+            block: u64,
+            peer: SimPeer,
+        ) -> Option<(PeerId, u64)>;
+    }
+
+    /// Assigns requests to the idle peers in turn.
+    struct RoundRobin {
+        peers: Vec<PeerId>,
+        idle: HashSet<PeerId>,
+        next: usize,
+        queued: VecDeque<u64>,
+    }
+
+    impl Scheduler for RoundRobin {
+        fn queue(&mut self, block: u64, front: bool) {
+            if front {
+                self.queued.push_front(block)
+            } else {
+                self.queued.push_back(block)
+            }
+        }
+
+        fn num_queued(&self) -> usize {
+            self.queued.len()
+        }
+
+        fn assign(&mut self) -> Vec<(PeerId, u64)> {
+            let mut assigned = Vec::new();
+            while !self.idle.is_empty() {
+                let Some(block) = self.queued.pop_front() else { break };
+                loop {
+                    let peer_id = self.peers[self.next % self.peers.len()];
+                    self.next += 1;
+                    if self.idle.remove(&peer_id) {
+                        assigned.push((peer_id, block));
+                        break
+                    }
+                }
+            }
+            assigned
+        }
+
+        fn on_response(&mut self, peer_id: PeerId, _: u64, _: SimPeer) -> Option<(PeerId, u64)> {
+            self.idle.insert(peer_id);
+            None
+        }
+    }
+
+    impl Scheduler for StateFetcher<EthNetworkPrimitives> {
+        fn queue(&mut self, block: u64, front: bool) {
+            let (response, _) = oneshot::channel();
+            let request = DownloadRequest::GetBlockHeaders {
+                request: HeadersRequest {
+                    start: block.into(),
+                    limit: 1,
+                    direction: Default::default(),
+                },
+                response,
+                priority: Priority::default(),
+            };
+            if front {
+                self.queued_requests.push_front(request)
+            } else {
+                self.queued_requests.push_back(request)
+            }
+        }
+
+        fn num_queued(&self) -> usize {
+            self.queued_requests.len()
+        }
+
+        fn assign(&mut self) -> Vec<(PeerId, u64)> {
+            let mut assigned = Vec::new();
+            while let PollAction::Ready(FetchAction::BlockRequest { peer_id, request }) =
+                self.poll_action()
+            {
+                assigned.push((peer_id, requested_block(&request)));
+            }
+            assigned
+        }
+
+        fn on_response(
+            &mut self,
+            peer_id: PeerId,
+            block: u64,
+            peer: SimPeer,
+        ) -> Option<(PeerId, u64)> {
+            // the request was sent the latency of the peer ago in simulated time
+            self.inflight_headers_requests.get_mut(&peer_id).unwrap().started =
+                Instant::now().checked_sub(peer.latency).unwrap();
+            let number = if peer.malicious { block + 1 } else { block };
+            match self.on_block_headers_response(
+                peer_id,
+                Ok(vec![Header { number, ..Default::default() }]),
+            ) {
+                Some(BlockResponseOutcome::Request(peer_id, request)) => {
+                    Some((peer_id, requested_block(&request)))
+                }
+                _ => None,
+            }
+        }
+    }
+
+    fn requested_block(request: &BlockRequest) -> u64 {
+        match request {
+            BlockRequest::GetBlockHeaders(request) => request.start_block.as_number().unwrap(),
+            BlockRequest::GetBlockBodies(_) => unreachable!(),
+        }
+    }
+
+    /// Downloads the headers of a block range with a limited number of requests in flight, like
+    /// the downloaders do, and returns the simulated time it took.
+    ///
+    /// Malicious responses are requested again.
+    fn simulate(population: &HashMap<PeerId, SimPeer>, scheduler: &mut impl Scheduler) -> Duration {
+        const NUM_BLOCKS: u64 = 200;
+        const MAX_CONCURRENT_REQUESTS: usize = 3;
+
+        let mut now = Duration::ZERO;
+        let mut inflight: Vec<(Duration, PeerId, u64)> = Vec::new();
+        let (mut next_block, mut downloaded) = (0, 0);
+        while downloaded < NUM_BLOCKS {
+            while next_block < NUM_BLOCKS &&
+                inflight.len() + scheduler.num_queued() < MAX_CONCURRENT_REQUESTS
+            {
+                scheduler.queue(next_block, false);
+                next_block += 1;
+            }
+            for (peer_id, block) in scheduler.assign() {
+                inflight.push((now + population[&peer_id].latency, peer_id, block));
+            }
+
+            let next = (0..inflight.len()).min_by_key(|idx| inflight[*idx].0).unwrap();
+            let (done, peer_id, block) = inflight.swap_remove(next);
+            now = done;
+            let peer = population[&peer_id];
+            if peer.malicious {
+                scheduler.queue(block, true);
+            } else {
+                downloaded += 1;
+            }
+            if let Some((peer_id, block)) = scheduler.on_response(peer_id, block, peer) {
+                inflight.push((now + population[&peer_id].latency, peer_id, block));
+            }
+        }
+        now
+    }
+
+    #[tokio::test]
+    async fn test_scoring_outperforms_round_robin() {
+        let fast = SimPeer { latency: Duration::from_millis(50), malicious: false };
+        let slow = SimPeer { latency: Duration::from_secs(1), malicious: false };
+        let malicious = SimPeer { latency: Duration::from_millis(20), malicious: true };
+        let population = [fast, fast, slow, slow, slow, malicious, malicious]
+            .into_iter()
+            .map(|peer| (B512::random(), peer))
+            .collect::<HashMap<_, _>>();
+
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::<EthNetworkPrimitives>::new(
+            manager.handle(),
+            Default::default(),
+            PeerScoringConfig::default(),
+        );
+        for peer_id in population.keys() {
+            fetcher.new_active_peer(
+                *peer_id,
+                B256::random(),
+                1_000,
+                Arc::new(Capabilities::from(vec![])),
+                Arc::new(AtomicU64::new(100)),
+                None,
+            );
+        }
+        let scored = simulate(&population, &mut fetcher);
+
+        let mut round_robin = RoundRobin {
+            peers: population.keys().copied().collect(),
+            idle: population.keys().copied().collect(),
+            next: 0,
+            queued: Default::default(),
+        };
+        let round_robin = simulate(&population, &mut round_robin);
+
+        assert!(scored * 2 < round_robin, "scored: {scored:?}, round robin: {round_robin:?}");
+
+        // the malicious peers are in cooldown, and the fast peers have the best scores
+        let scores = population
+            .iter()
+            .map(|(peer_id, peer)| (*peer, fetcher.peer_fetch_score(peer_id).unwrap()))
+            .collect::<Vec<_>>();
+        for (peer, score) in &scores {
+            assert_eq!(score.in_cooldown, peer.malicious);
+        }
+        let worst_fast = scores
+            .iter()
+            .filter(|(peer, _)| peer.latency == fast.latency)
+            .map(|(_, score)| score.score)
+            .fold(f64::INFINITY, f64::min);
+        assert!(scores
+            .iter()
+            .filter(|(peer, _)| peer.latency == slow.latency)
+            .all(|(_, score)| score.score < worst_fast));
+    }
+
     #[tokio::test]
     async fn test_on_block_headers_response() {
         let manager = PeersManager::new(PeersConfig::default());
-        let mut fetcher =
-            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let mut fetcher = StateFetcher::<EthNetworkPrimitives>::new(
+            manager.handle(),
+            Default::default(),
+            Default::default(),
+        );
         let peer_id = B512::random();
 
         assert_eq!(fetcher.on_block_headers_response(peer_id, Ok(vec![Header::default()])), None);
@@ -730,8 +1141,11 @@ mod tests {
     #[tokio::test]
     async fn test_header_response_outcome() {
         let manager = PeersManager::new(PeersConfig::default());
-        let mut fetcher =
-            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default());
+        let mut fetcher = StateFetcher::<EthNetworkPrimitives>::new(
+            manager.handle(),
+            Default::default(),
+            Default::default(),
+        );
         let peer_id = B512::random();
 
         let request_pair = || {
@@ -743,6 +1157,7 @@ mod tests {
                     direction: Default::default(),
                 },
                 response: tx,
+                started: Instant::now(),
             };
             let header = Header { number: 0, ..Default::default() };
             (req, header)
@@ -794,6 +1209,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 100, B256::random())),
+            stats: Default::default(),
         };
 
         let peer2 = Peer {
@@ -804,6 +1220,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(20)),
             last_response_likely_bad: false,
             range_info: None,
+            stats: Default::default(),
         };
 
         // With None requirement, is_better should always return false
@@ -822,6 +1239,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 100, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer without full history (earliest = 50)
@@ -833,6 +1251,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(50, 100, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer without range info (treated as full history)
@@ -844,6 +1263,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: None,
+            stats: Default::default(),
         };
 
         // Peer with full history is better than peer without
@@ -872,6 +1292,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 100, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer that doesn't cover the range (earliest too high)
@@ -883,6 +1304,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(70, 100, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer that covers the requested range is better than one that doesn't
@@ -906,6 +1328,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 50, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer without full history that also covers the range
@@ -917,6 +1340,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(30, 50, B256::random())),
+            stats: Default::default(),
         };
 
         // When both cover the range, prefer none
@@ -938,6 +1362,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 50, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer without full history that also covers the range
@@ -949,6 +1374,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(30, 50, B256::random())),
+            stats: Default::default(),
         };
 
         // When both cover the range, prefer lower start value
@@ -970,6 +1396,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 30, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer without full history that also doesn't cover the range
@@ -981,6 +1408,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(10, 30, B256::random())),
+            stats: Default::default(),
         };
 
         // When neither covers the range, prefer full history
@@ -1002,6 +1430,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(30, 100, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer without range info
@@ -1013,6 +1442,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: None,
+            stats: Default::default(),
         };
 
         // Peer without range info is not better (we prefer peers with known ranges)
@@ -1038,6 +1468,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(30, 100, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer without range info (treated as full history with unknown latest)
@@ -1049,6 +1480,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: None,
+            stats: Default::default(),
         };
 
         // Peer with range that covers is better than peer without range info
@@ -1073,6 +1505,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(70, 100, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer without range info (treated as full history)
@@ -1084,6 +1517,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: None,
+            stats: Default::default(),
         };
 
         // Peer with range that doesn't cover is not better
@@ -1109,6 +1543,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(50, 100, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer that's one block short at the start
@@ -1120,6 +1555,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(51, 100, B256::random())),
+            stats: Default::default(),
         };
 
         // Peer that's one block short at the end
@@ -1131,6 +1567,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(50, 99, B256::random())),
+            stats: Default::default(),
         };
 
         // Exact coverage is better than short coverage
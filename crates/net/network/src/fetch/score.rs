@@ -0,0 +1,159 @@
+//! Rolling statistics of the responses of a peer, which rank the peers for requests.
+
+use reth_network_api::PeerFetchScore;
+use reth_network_types::PeerScoringConfig;
+use std::time::{Duration, Instant};
+
+/// The weight of a new sample in the rolling averages.
+const SMOOTHING: f64 = 0.2;
+
+/// Tracks the responses of a peer to block header and body requests.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct PeerStats {
+    /// The number of responses, including timed out requests.
+    samples: u64,
+    /// The rolling average of the response latency, in seconds.
+    latency: f64,
+    /// The rolling rate of requests that timed out.
+    timeout_rate: f64,
+    /// The rolling rate of responses that failed validation.
+    validation_failure_rate: f64,
+    /// The rolling average of the throughput, in bytes per second.
+    throughput: f64,
+    /// Until when the peer isn't sent requests, because too many of its responses failed
+    /// validation.
+    cooldown_until: Option<Instant>,
+}
+
+impl PeerStats {
+    /// Records a response that arrived after the given latency.
+    ///
+    /// Responses that are likely bad, like empty ones, count as validation failures and don't
+    /// contribute to the throughput.
+    pub(crate) fn on_response(
+        &mut self,
+        config: &PeerScoringConfig,
+        latency: Duration,
+        bytes: usize,
+        valid: bool,
+    ) {
+        let latency = latency.as_secs_f64();
+        let throughput = if valid { bytes as f64 / latency.max(f64::EPSILON) } else { 0.0 };
+        self.latency = self.average(self.latency, latency);
+        self.throughput = self.average(self.throughput, throughput);
+        self.timeout_rate = self.average(self.timeout_rate, 0.0);
+        self.validation_failure_rate =
+            self.average(self.validation_failure_rate, if valid { 0.0 } else { 1.0 });
+        self.samples += 1;
+        if !valid {
+            self.check_cooldown(config);
+        }
+    }
+
+    /// Records a request that timed out.
+    pub(crate) fn on_timeout(&mut self) {
+        self.timeout_rate = self.average(self.timeout_rate, 1.0);
+        self.samples += 1;
+    }
+
+    /// Records a response that a downloader reported as invalid after it was delivered.
+    pub(crate) fn on_validation_failure(&mut self, config: &PeerScoringConfig) {
+        self.validation_failure_rate = self.average(self.validation_failure_rate, 1.0);
+        self.check_cooldown(config);
+    }
+
+    /// Returns `true` if the peer isn't sent requests at the given time.
+    pub(crate) fn in_cooldown(&self, now: Instant) -> bool {
+        self.cooldown_until.is_some_and(|until| now < until)
+    }
+
+    /// Returns the number of responses.
+    pub(crate) const fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Returns the score of the peer, higher is better.
+    ///
+    /// Peers without responses have the score of a peer with perfect, but zero throughput
+    /// responses.
+    pub(crate) fn score(&self, config: &PeerScoringConfig) -> f64 {
+        config.score(
+            Duration::from_secs_f64(self.latency),
+            self.timeout_rate,
+            self.validation_failure_rate,
+            self.throughput,
+        )
+    }
+
+    /// Returns the statistics as reported by the network handle.
+    pub(crate) fn fetch_score(&self, config: &PeerScoringConfig, now: Instant) -> PeerFetchScore {
+        PeerFetchScore {
+            score: self.score(config),
+            responses: self.samples,
+            latency: Duration::from_secs_f64(self.latency),
+            timeout_rate: self.timeout_rate,
+            validation_failure_rate: self.validation_failure_rate,
+            throughput: self.throughput,
+            in_cooldown: self.in_cooldown(now),
+        }
+    }
+
+    /// Puts the peer in cooldown if too many of its responses failed validation.
+    ///
+    /// The statistics start over after the cooldown, so the peer is measured again once it's
+    /// sent requests.
+    fn check_cooldown(&mut self, config: &PeerScoringConfig) {
+        if config.exceeds_failure_threshold(self.samples, self.validation_failure_rate) {
+            *self =
+                Self { cooldown_until: Some(Instant::now() + config.cooldown), ..Self::default() };
+        }
+    }
+
+    /// Returns the rolling average with the new sample, which is the sample itself for the first
+    /// one.
+    fn average(&self, average: f64, sample: f64) -> f64 {
+        if self.samples == 0 {
+            sample
+        } else {
+            average + SMOOTHING * (sample - average)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cooldown_after_validation_failures() {
+        let config = PeerScoringConfig::default();
+        let mut stats = PeerStats::default();
+        let now = Instant::now();
+
+        stats.on_response(&config, Duration::from_millis(100), 1024, true);
+        assert_eq!(stats.samples(), 1);
+        assert!((stats.throughput - 10240.0).abs() < 1.0);
+
+        for _ in 0..3 {
+            stats.on_response(&config, Duration::from_millis(100), 1024, false);
+        }
+        assert!(!stats.in_cooldown(now));
+        stats.on_validation_failure(&config);
+        assert!(stats.in_cooldown(Instant::now()));
+        assert_eq!(stats.samples(), 0);
+        assert!(!stats.in_cooldown(Instant::now() + config.cooldown));
+    }
+
+    #[test]
+    fn timeouts_lower_the_score() {
+        let config = PeerScoringConfig::default();
+        let mut responsive = PeerStats::default();
+        let mut flaky = PeerStats::default();
+        for _ in 0..4 {
+            responsive.on_response(&config, Duration::from_millis(200), 1024, true);
+            flaky.on_response(&config, Duration::from_millis(200), 1024, true);
+            flaky.on_timeout();
+        }
+        assert!(responsive.score(&config) > flaky.score(&config));
+    }
+}
@@ -40,7 +40,6 @@ use futures::{Future, StreamExt};
 use parking_lot::Mutex;
 use reth_chainspec::EnrForkIdEntry;
 use reth_eth_wire::{DisconnectReason, EthNetworkPrimitives, NetworkPrimitives};
-use reth_fs_util::{self as fs, FsPathError};
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
 use reth_network_api::{
     events::{PeerEvent, SessionInfo},
@@ -55,7 +54,6 @@ use reth_tokio_util::EventSender;
 use secp256k1::SecretKey;
 use std::{
     net::SocketAddr,
-    path::Path,
     pin::Pin,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
@@ -254,6 +252,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             required_block_hashes,
         } = config;
 
+        let peer_scoring = peers_config.scoring;
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
 
@@ -313,6 +312,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discovery,
             peers_manager,
             Arc::clone(&num_active_peers),
+            peer_scoring,
         );
 
         let swarm = Swarm::new(incoming, sessions, state);
@@ -432,13 +432,19 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         self.swarm.state().peers().handle()
     }
 
-    /// Collect the peers from the [`NetworkManager`] and write them to the given
-    /// `persistent_peers_file`.
-    pub fn write_peers_to_file(&self, persistent_peers_file: &Path) -> Result<(), FsPathError> {
-        let known_peers = self.all_peers().collect::<Vec<_>>();
-        persistent_peers_file.parent().map(fs::create_dir_all).transpose()?;
-        reth_fs_util::write_json_file(persistent_peers_file, &known_peers)?;
-        Ok(())
+    /// Collect the peers, with their reputation, and the temporary bans from the
+    /// [`NetworkManager`] and write them to the given `persistent_peers_file`.
+    ///
+    /// See [`write_peers_file`](crate::peers::write_peers_file).
+    #[cfg(feature = "serde")]
+    pub fn write_peers_to_file(
+        &self,
+        persistent_peers_file: &std::path::Path,
+    ) -> Result<(), reth_fs_util::FsPathError> {
+        crate::peers::write_peers_file(
+            persistent_peers_file,
+            &self.swarm.state().peers().persisted_peers(),
+        )
     }
 
     /// Returns a new [`FetchClient`] that can be cloned and shared.
@@ -711,6 +717,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             NetworkHandleMessage::GetReputationById(peer_id, tx) => {
                 let _ = tx.send(self.swarm.state_mut().peers().get_reputation(&peer_id));
             }
+            NetworkHandleMessage::GetStaticPeers(tx) => {
+                let _ = tx.send(self.swarm.state().peers().static_peers());
+            }
             NetworkHandleMessage::FetchClient(tx) => {
                 let _ = tx.send(self.fetch_client());
             }
@@ -1004,11 +1013,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             .active_sessions()
             .iter()
             .filter_map(|(&peer_id, session)| {
-                self.swarm
-                    .state()
-                    .peers()
-                    .peer_by_id(peer_id)
-                    .map(|(record, kind)| session.peer_info(&record, kind))
+                self.swarm.state().peers().peer_by_id(peer_id).map(|(record, kind)| {
+                    session.peer_info(&record, kind, self.swarm.state().peer_fetch_score(&peer_id))
+                })
             })
             .collect()
     }
@@ -1018,11 +1025,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
     /// Returns `None` if there's no active session to the peer.
     fn get_peer_info_by_id(&self, peer_id: PeerId) -> Option<PeerInfo> {
         self.swarm.sessions().active_sessions().get(&peer_id).and_then(|session| {
-            self.swarm
-                .state()
-                .peers()
-                .peer_by_id(peer_id)
-                .map(|(record, kind)| session.peer_info(&record, kind))
+            self.swarm.state().peers().peer_by_id(peer_id).map(|(record, kind)| {
+                session.peer_info(&record, kind, self.swarm.state().peer_fetch_score(&peer_id))
+            })
         })
     }
 
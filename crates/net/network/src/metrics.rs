@@ -5,6 +5,8 @@ use reth_metrics::{
     metrics::{Counter, Gauge},
     Metrics,
 };
+use reth_network_peers::PeerId;
+use std::collections::HashMap;
 
 /// Scope for monitoring transactions sent from the manager to the tx manager
 pub(crate) const NETWORK_POOL_TRANSACTIONS_SCOPE: &str = "network.pool.transactions";
@@ -77,6 +79,33 @@ pub struct NetworkMetrics {
     pub(crate) acc_duration_poll_swarm: Gauge,
 }
 
+/// Metrics of a static peer, labeled with its peer id
+#[derive(Metrics)]
+#[metrics(scope = "network.static_peers")]
+pub struct StaticPeerMetrics {
+    /// Whether there's an active session with the peer
+    pub(crate) connected: Gauge,
+    /// Number of attempts to reconnect to the peer since the last session with it
+    pub(crate) reconnect_attempts: Gauge,
+    /// Total number of dials of the peer
+    pub(crate) dials: Counter,
+    /// Total number of sessions established with the peer
+    pub(crate) sessions: Counter,
+}
+
+/// The [`StaticPeerMetrics`] of all static peers
+#[derive(Debug, Default)]
+pub(crate) struct StaticPeersMetrics(HashMap<PeerId, StaticPeerMetrics>);
+
+impl StaticPeersMetrics {
+    /// Returns the metrics of the static peer
+    pub(crate) fn peer(&mut self, peer_id: PeerId) -> &StaticPeerMetrics {
+        self.0.entry(peer_id).or_insert_with(|| {
+            StaticPeerMetrics::new_with_labels(&[("peer_id", peer_id.to_string())])
+        })
+    }
+}
+
 /// Metrics for `SessionManager`
 #[derive(Metrics)]
 #[metrics(scope = "network")]
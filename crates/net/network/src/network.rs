@@ -18,7 +18,7 @@ use reth_network_api::{
     test_utils::{PeersHandle, PeersHandleProvider},
     BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    PeersInfo, StaticPeerInfo,
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
@@ -379,6 +379,12 @@ impl<N: NetworkPrimitives> Peers for NetworkHandle<N> {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+
+    async fn get_static_peers(&self) -> Result<Vec<StaticPeerInfo>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetStaticPeers(tx));
+        Ok(rx.await?)
+    }
 }
 
 impl<N: NetworkPrimitives> PeersHandleProvider for NetworkHandle<N> {
@@ -557,6 +563,8 @@ pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives
     GetPeerInfosByPeerKind(PeerKind, oneshot::Sender<Vec<PeerInfo>>),
     /// Gets the reputation for a specific peer via a oneshot sender.
     GetReputationById(PeerId, oneshot::Sender<Option<Reputation>>),
+    /// Gets the `StaticPeerInfo` of all static peers via a oneshot sender.
+    GetStaticPeers(oneshot::Sender<Vec<StaticPeerInfo>>),
     /// Retrieves the `TransactionsHandle` via a oneshot sender.
     GetTransactionsHandle(oneshot::Sender<Option<TransactionsHandle<N>>>),
     /// Initiates a graceful shutdown of the network via a oneshot sender.
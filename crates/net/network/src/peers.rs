@@ -2,6 +2,7 @@
 
 use crate::{
     error::SessionError,
+    metrics::StaticPeersMetrics,
     session::{Direction, PendingSessionHandshakeError},
     swarm::NetworkConnectionState,
     trusted_peers_resolver::TrustedPeersResolver,
@@ -11,15 +12,20 @@ use futures::StreamExt;
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_ethereum_forks::ForkId;
 use reth_net_banlist::BanList;
-use reth_network_api::test_utils::{PeerCommand, PeersHandle};
+use reth_network_api::{
+    test_utils::{PeerCommand, PeersHandle},
+    StaticPeerInfo, StaticPeerState,
+};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
     is_connection_failed_reputation,
     peers::{
-        config::PeerBackoffDurations,
+        config::{PeerBackoffDurations, StaticReconnectBackoff},
+        persisted::unix_timestamp,
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
+        BannedPeer, PersistedPeer,
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
+    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig, PersistedPeers,
     ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
 };
 use std::{
@@ -28,7 +34,7 @@ use std::{
     io::{self},
     net::{IpAddr, SocketAddr},
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use thiserror::Error;
 use tokio::{
@@ -90,6 +96,10 @@ pub struct PeersManager {
     net_connection_state: NetworkConnectionState,
     /// How long to temporarily ban ip on an incoming connection attempt.
     incoming_ip_throttle_duration: Duration,
+    /// The backoff between the attempts to reconnect to static peers.
+    static_reconnect_backoff: StaticReconnectBackoff,
+    /// Metrics of the static peers.
+    static_peer_metrics: StaticPeersMetrics,
     /// IP address filter for restricting network connections to specific IP ranges.
     ip_filter: reth_net_banlist::IpFilter,
 }
@@ -101,13 +111,16 @@ impl PeersManager {
             refill_slots_interval,
             connection_info,
             reputation_weights,
-            ban_list,
+            mut ban_list,
             ban_duration,
             backoff_durations,
             trusted_nodes,
             trusted_nodes_only,
+            static_nodes,
+            static_reconnect_backoff,
             trusted_nodes_resolution_interval,
             basic_nodes,
+            persisted_peers,
             max_backoff_count,
             incoming_ip_throttle_duration,
             ip_filter,
@@ -116,9 +129,11 @@ impl PeersManager {
         let now = Instant::now();
 
         // We use half of the interval to decrease the max duration to `150%` in worst case
-        let unban_interval = ban_duration.min(backoff_durations.low) / 2;
+        let unban_interval =
+            ban_duration.min(backoff_durations.low).min(static_reconnect_backoff.min) / 2;
 
-        let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
+        let mut peers =
+            HashMap::with_capacity(trusted_nodes.len() + static_nodes.len() + basic_nodes.len());
         let mut trusted_peer_ids = HashSet::with_capacity(trusted_nodes.len());
 
         for trusted_peer in &trusted_nodes {
@@ -135,19 +150,61 @@ impl PeersManager {
             }
         }
 
+        for static_peer in &static_nodes {
+            match static_peer.resolve_blocking() {
+                Ok(NodeRecord { address, tcp_port, udp_port, id }) => {
+                    peers.entry(id).or_insert_with(|| {
+                        Peer::with_kind(
+                            PeerAddr::new_with_ports(address, tcp_port, Some(udp_port)),
+                            PeerKind::Static,
+                        )
+                    });
+                }
+                Err(err) => {
+                    warn!(target: "net::peers", ?err, "Failed to resolve static peer");
+                }
+            }
+        }
+
         for NodeRecord { address, tcp_port, udp_port, id } in basic_nodes {
             peers.entry(id).or_insert_with(|| {
                 Peer::new(PeerAddr::new_with_ports(address, tcp_port, Some(udp_port)))
             });
         }
 
+        // restore the peers of the last run with their reputation, and the bans that didn't expire
+        // while the node was stopped
+        let PersistedPeers { peers: persisted_peers, banned } = persisted_peers;
+        for PersistedPeer { enode, fork_id, reputation, last_seen } in persisted_peers {
+            let NodeRecord { address, tcp_port, udp_port, id } = enode;
+            let peer = peers.entry(id).or_insert_with(|| {
+                Peer::new(PeerAddr::new_with_ports(address, tcp_port, Some(udp_port)))
+            });
+            peer.reputation = reputation;
+            peer.fork_id = fork_id.map(Box::new);
+            peer.last_seen = last_seen.map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+        }
+        let unix_now = SystemTime::now();
+        for BannedPeer { id, until } in banned {
+            if let Ok(remaining) =
+                (UNIX_EPOCH + Duration::from_secs(until)).duration_since(unix_now)
+            {
+                ban_list.ban_peer_until(id, std::time::Instant::now() + remaining);
+            }
+        }
+        for (peer_id, peer) in &mut peers {
+            if peer.is_banned() && !ban_list.is_banned_peer(peer_id) {
+                peer.unban();
+            }
+        }
+
         trace!(target: "net::peers", trusted_peers=?trusted_peer_ids, "Initialized peers manager");
 
         Self {
             peers,
             trusted_peer_ids,
             trusted_peers_resolver: TrustedPeersResolver::new(
-                trusted_nodes,
+                trusted_nodes.into_iter().chain(static_nodes).collect(),
                 tokio::time::interval(trusted_nodes_resolution_interval), // 1 hour
             ),
             manager_tx,
@@ -166,6 +223,8 @@ impl PeersManager {
             max_backoff_count,
             net_connection_state: NetworkConnectionState::default(),
             incoming_ip_throttle_duration,
+            static_reconnect_backoff,
+            static_peer_metrics: Default::default(),
             ip_filter,
         }
     }
@@ -193,6 +252,80 @@ impl PeersManager {
         })
     }
 
+    /// Returns the peers and the temporary bans, as they are written to the peers file.
+    ///
+    /// Peers that only connected to us are skipped, since their address is not the one they
+    /// listen on.
+    pub(crate) fn persisted_peers(&self) -> PersistedPeers {
+        let peers = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| !peer.remove_after_disconnect)
+            .map(|(peer_id, peer)| PersistedPeer {
+                enode: NodeRecord::new_with_ports(
+                    peer.addr.tcp().ip(),
+                    peer.addr.tcp().port(),
+                    peer.addr.udp().map(|addr| addr.port()),
+                    *peer_id,
+                ),
+                fork_id: peer.fork_id.as_deref().copied(),
+                reputation: peer.reputation,
+                last_seen: peer.last_seen.map(unix_timestamp),
+            })
+            .collect();
+
+        let now = std::time::Instant::now();
+        let unix_now = SystemTime::now();
+        let banned = self
+            .ban_list
+            .banned_peers()
+            .filter_map(|(id, until)| {
+                let remaining = until?.checked_duration_since(now)?;
+                Some(BannedPeer { id, until: unix_timestamp(unix_now + remaining) })
+            })
+            .collect();
+
+        PersistedPeers { peers, banned }
+    }
+
+    /// Returns the static peers and the state of the connection to them.
+    pub(crate) fn static_peers(&self) -> Vec<StaticPeerInfo> {
+        self.peers
+            .iter()
+            .filter(|(_, peer)| peer.is_static())
+            .map(|(peer_id, peer)| {
+                let state = if peer.is_banned() || self.ban_list.is_banned_peer(peer_id) {
+                    StaticPeerState::Banned
+                } else {
+                    match peer.state {
+                        PeerConnectionState::In | PeerConnectionState::Out => {
+                            StaticPeerState::Connected
+                        }
+                        PeerConnectionState::DisconnectingIn |
+                        PeerConnectionState::DisconnectingOut => StaticPeerState::Disconnecting,
+                        PeerConnectionState::PendingOut => StaticPeerState::Dialing,
+                        PeerConnectionState::Idle if peer.is_backed_off() => {
+                            StaticPeerState::BackedOff
+                        }
+                        PeerConnectionState::Idle => StaticPeerState::Idle,
+                    }
+                };
+                StaticPeerInfo {
+                    record: NodeRecord::new_with_ports(
+                        peer.addr.tcp().ip(),
+                        peer.addr.tcp().port(),
+                        peer.addr.udp().map(|addr| addr.port()),
+                        *peer_id,
+                    ),
+                    state,
+                    reconnect_attempts: peer.reconnect_attempts,
+                    last_seen: peer.last_seen,
+                    next_attempt: self.backed_off_peers.get(peer_id).copied(),
+                }
+            })
+            .collect()
+    }
+
     /// Returns the `NodeRecord` and `PeerKind` for the given peer id
     pub(crate) fn peer_by_id(&self, peer_id: PeerId) -> Option<(NodeRecord, PeerKind)> {
         self.peers.get(&peer_id).map(|v| {
@@ -344,6 +477,7 @@ impl PeersManager {
 
         // check if the peer is trustable or not
         let mut is_trusted = self.trusted_peer_ids.contains(&peer_id);
+        let mut is_static = false;
         if self.trusted_nodes_only && !is_trusted {
             self.queued_actions.push_back(PeerAction::DisconnectUntrustedIncoming { peer_id });
             return
@@ -366,14 +500,24 @@ impl PeersManager {
                 }
 
                 peer.state = PeerConnectionState::In;
+                peer.last_seen = Some(SystemTime::now());
 
                 is_trusted = is_trusted || peer.is_trusted();
+                is_static = peer.is_static();
+                if is_static {
+                    peer.reconnect_attempts = 0;
+                    let metrics = self.static_peer_metrics.peer(peer_id);
+                    metrics.connected.set(1);
+                    metrics.reconnect_attempts.set(0);
+                    metrics.sessions.increment(1);
+                }
             }
             Entry::Vacant(entry) => {
                 // peer is missing in the table, we add it but mark it as to be removed after
                 // disconnect, because we only know the outgoing port
                 let mut peer = Peer::with_state(PeerAddr::from_tcp(addr), PeerConnectionState::In);
                 peer.remove_after_disconnect = true;
+                peer.last_seen = Some(SystemTime::now());
                 entry.insert(peer);
                 self.queued_actions.push_back(PeerAction::PeerAdded(peer_id));
             }
@@ -383,8 +527,9 @@ impl PeersManager {
         // increment new incoming connection
         self.connection_info.inc_in();
 
-        // disconnect the peer if we don't have capacity for more inbound connections
-        if !is_trusted && !has_in_capacity {
+        // disconnect the peer if we don't have capacity for more inbound connections, static peers
+        // are kept regardless
+        if !is_trusted && !is_static && !has_in_capacity {
             self.queued_actions.push_back(PeerAction::Disconnect {
                 peer_id,
                 reason: Some(DisconnectReason::TooManyPeers),
@@ -559,12 +704,22 @@ impl PeersManager {
                     // give the remote time to also properly register the closed session and clean
                     // up and to avoid any issues with ip throttling on the remote in case this
                     // session was terminated right away.
+                    let backoff = if peer.is_static() {
+                        // static peers are redialed sooner, if the remote throttles our ip the
+                        // failed attempts increase the backoff
+                        let backoff =
+                            self.static_reconnect_backoff.backoff(peer.reconnect_attempts);
+                        peer.reconnect_attempts = peer.reconnect_attempts.saturating_add(1);
+                        let metrics = self.static_peer_metrics.peer(peer_id);
+                        metrics.connected.set(0);
+                        metrics.reconnect_attempts.set(peer.reconnect_attempts);
+                        backoff
+                    } else {
+                        self.incoming_ip_throttle_duration
+                    };
                     peer.backed_off = true;
-                    self.backed_off_peers.insert(
-                        peer_id,
-                        std::time::Instant::now() + self.incoming_ip_throttle_duration,
-                    );
-                    trace!(target: "net::peers", ?peer_id, kind=?peer.kind, duration=?self.incoming_ip_throttle_duration, "backing off on gracefully closed session");
+                    self.backed_off_peers.insert(peer_id, Instant::now().into_std() + backoff);
+                    trace!(target: "net::peers", ?peer_id, kind=?peer.kind, duration=?backoff, "backing off on gracefully closed session");
                 }
             }
             Entry::Vacant(_) => return,
@@ -580,6 +735,15 @@ impl PeersManager {
             self.connection_info.decr_state(peer.state);
             self.connection_info.inc_out();
             peer.state = PeerConnectionState::Out;
+            peer.last_seen = Some(SystemTime::now());
+
+            if peer.is_static() {
+                peer.reconnect_attempts = 0;
+                let metrics = self.static_peer_metrics.peer(peer_id);
+                metrics.connected.set(1);
+                metrics.reconnect_attempts.set(0);
+                metrics.sessions.increment(1);
+            }
         }
     }
 
@@ -638,8 +802,8 @@ impl PeersManager {
             // issues.
             if let Entry::Occupied(mut entry) = self.peers.entry(*peer_id) {
                 self.connection_info.decr_state(entry.get().state);
-                // only remove if the peer is not trusted
-                if entry.get().is_trusted() {
+                // only remove if the peer is not trusted or static
+                if entry.get().is_trusted() || entry.get().is_static() {
                     entry.get_mut().state = PeerConnectionState::Idle;
                 } else {
                     entry.remove();
@@ -661,8 +825,18 @@ impl PeersManager {
             let mut remove_peer = false;
 
             if let Some(peer) = self.peers.get_mut(peer_id) {
-                if let Some(kind) = err.should_backoff() {
-                    if peer.is_trusted() || peer.is_static() {
+                if peer.is_static() {
+                    // static peers are always redialed, after a backoff that grows with the
+                    // attempts since the last session
+                    let backoff = self.static_reconnect_backoff.backoff(peer.reconnect_attempts);
+                    peer.reconnect_attempts = peer.reconnect_attempts.saturating_add(1);
+                    backoff_until = Some(Instant::now().into_std() + backoff);
+                    let metrics = self.static_peer_metrics.peer(*peer_id);
+                    metrics.connected.set(0);
+                    metrics.reconnect_attempts.set(peer.reconnect_attempts);
+                    trace!(target: "net::peers", ?peer_id, ?backoff, attempts=peer.reconnect_attempts, "backing off static peer");
+                } else if let Some(kind) = err.should_backoff() {
+                    if peer.is_trusted() {
                         // provide a bit more leeway for trusted peers and use a lower backoff so
                         // that we keep re-trying them after backing off shortly, but we should at
                         // least backoff for the low duration to not violate the ip based inbound
@@ -975,7 +1149,7 @@ impl PeersManager {
     /// [`PeerAction::Connect`] actions.
     ///
     /// New connections are only initiated, if slots are available and appropriate peers are
-    /// available.
+    /// available. Static peers are dialed regardless of the slots, once their backoff expired.
     fn fill_outbound_slots(&mut self) {
         self.tick();
 
@@ -984,6 +1158,30 @@ impl PeersManager {
             return
         }
 
+        if !self.trusted_nodes_only {
+            for (peer_id, peer) in &mut self.peers {
+                if !peer.is_static() ||
+                    peer.is_backed_off() ||
+                    peer.is_banned() ||
+                    self.ban_list.is_banned_peer(peer_id) ||
+                    self.ban_list.is_banned_ip(&peer.addr.tcp().ip()) ||
+                    !peer.state.is_unconnected()
+                {
+                    continue
+                }
+
+                trace!(target: "net::peers", ?peer_id, addr=?peer.addr, attempts=peer.reconnect_attempts, "schedule outbound connection to static peer");
+
+                peer.state = PeerConnectionState::PendingOut;
+                self.connection_info.inc_pending_out();
+                self.static_peer_metrics.peer(*peer_id).dials.increment(1);
+                self.queued_actions.push_back(PeerAction::Connect {
+                    peer_id: *peer_id,
+                    remote_addr: peer.addr.tcp(),
+                });
+            }
+        }
+
         // as long as there are slots available fill them with the best peers
         while self.connection_info.has_out_capacity() {
             let action = {
@@ -1060,11 +1258,15 @@ impl PeersManager {
                     PeerCommand::GetPeers(tx) => {
                         let _ = tx.send(self.iter_peers().collect());
                     }
+                    PeerCommand::GetPersistedPeers(tx) => {
+                        let _ = tx.send(self.persisted_peers());
+                    }
                 }
             }
 
             if self.release_interval.poll_tick(cx).is_ready() {
-                let now = std::time::Instant::now();
+                // the clock of the intervals, which can be paused in tests
+                let now = Instant::now().into_std();
                 let (_, unbanned_peers) = self.ban_list.evict(now);
 
                 for peer_id in unbanned_peers {
@@ -1076,15 +1278,22 @@ impl PeersManager {
 
                 // clear the backoff list of expired backoffs, and mark the relevant peers as
                 // ready to be dialed
+                let mut released_static_peer = false;
                 self.backed_off_peers.retain(|peer_id, until| {
                     if now > *until {
                         if let Some(peer) = self.peers.get_mut(peer_id) {
                             peer.backed_off = false;
+                            released_static_peer |= peer.is_static();
                         }
                         return false
                     }
                     true
-                })
+                });
+
+                // redial static peers right away instead of on the next refill
+                if released_static_peer {
+                    self.fill_outbound_slots();
+                }
             }
 
             while self.refill_slots_interval.poll_tick(cx).is_ready() {
@@ -1102,6 +1311,21 @@ impl PeersManager {
     }
 }
 
+/// Writes the peers to the peers file.
+///
+/// The peers are written to a temporary file first, which then replaces the peers file, so that the
+/// file is never left half written.
+#[cfg(feature = "serde")]
+pub fn write_peers_file(
+    path: &std::path::Path,
+    peers: &PersistedPeers,
+) -> Result<(), reth_fs_util::FsPathError> {
+    path.parent().map(reth_fs_util::create_dir_all).transpose()?;
+    let tmp_path = path.with_extension("json.tmp");
+    reth_fs_util::write_json_file(&tmp_path, peers)?;
+    reth_fs_util::rename(&tmp_path, path)
+}
+
 impl Default for PeersManager {
     fn default() -> Self {
         Self::new(Default::default())
@@ -1269,9 +1493,13 @@ mod tests {
     };
     use reth_net_banlist::BanList;
     use reth_network_api::Direction;
-    use reth_network_peers::{PeerId, TrustedPeer};
+    use reth_network_peers::{NodeRecord, PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, Peer, ReputationChangeKind,
+        peers::{
+            config::StaticReconnectBackoff,
+            reputation::{BANNED_REPUTATION, DEFAULT_REPUTATION},
+        },
+        BackoffKind, Peer, ReputationChangeKind,
     };
     use std::{
         future::{poll_fn, Future},
@@ -2669,6 +2897,64 @@ mod tests {
         assert!(!peer.remove_after_disconnect);
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn test_static_peer_reconnect_backoff() {
+        let peer_id = PeerId::random();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let backoff =
+            StaticReconnectBackoff { min: Duration::from_secs(1), max: Duration::from_secs(4) };
+        let config = PeersConfig::test()
+            .with_static_nodes(vec![NodeRecord::new(addr, peer_id).into()])
+            .with_static_reconnect_backoff(backoff);
+        let mut peers = PeersManager::new(config);
+
+        // the static peer is dialed on startup
+        match event!(peers) {
+            PeerAction::Connect { peer_id: id, .. } => assert_eq!(id, peer_id),
+            _ => unreachable!(),
+        }
+
+        // every failed dial doubles the backoff, up to the maximum
+        for (attempts, secs) in [(1, 1), (2, 2), (3, 4), (4, 4)] {
+            let err = io::Error::from(io::ErrorKind::ConnectionRefused);
+            peers.on_outgoing_connection_failure(&addr, &peer_id, &err);
+            let peer = peers.peers.get(&peer_id).unwrap();
+            assert_eq!(peer.reconnect_attempts, attempts);
+            assert!(peer.is_backed_off());
+            assert!(!peer.is_banned());
+
+            let start = tokio::time::Instant::now();
+            match event!(peers) {
+                PeerAction::Connect { peer_id: id, .. } => assert_eq!(id, peer_id),
+                _ => unreachable!(),
+            }
+            let elapsed = start.elapsed();
+            assert!(elapsed >= Duration::from_secs(secs), "{elapsed:?}");
+            assert!(elapsed < Duration::from_secs(secs) + backoff.min, "{elapsed:?}");
+        }
+
+        // a session resets the backoff
+        peers.on_active_outgoing_established(peer_id);
+        assert_eq!(peers.peers.get(&peer_id).unwrap().reconnect_attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn test_static_peer_banned_is_not_dialed() {
+        let peer_id = PeerId::random();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let config = PeersConfig::test()
+            .with_static_nodes(vec![NodeRecord::new(addr, peer_id).into()])
+            .with_ban_list(BanList::new(vec![], vec![addr.ip()]));
+        let mut peers = PeersManager::new(config);
+
+        poll_fn(|cx| {
+            assert!(peers.poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        assert!(peers.peers.get(&peer_id).unwrap().state.is_unconnected());
+    }
+
     #[tokio::test]
     async fn test_peer_reconnect_after_graceful_close_respects_throttle() {
         let throttle_duration = Duration::from_millis(100);
@@ -3211,4 +3497,32 @@ mod tests {
         assert!(peers.on_incoming_pending_session(ip2).is_ok());
         assert!(peers.on_incoming_pending_session(ip3).is_ok());
     }
+
+    #[tokio::test]
+    async fn test_restore_persisted_peers() {
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let (banned, idle) = (PeerId::random(), PeerId::random());
+        let mut peers = PeersManager::default();
+        peers.add_peer(banned, PeerAddr::from_tcp(socket_addr), None);
+        peers.add_peer(idle, PeerAddr::from_tcp(socket_addr), None);
+        peers.peers.get_mut(&banned).unwrap().reputation = BANNED_REPUTATION - 1;
+        peers.ban_peer(banned);
+        peers.peers.get_mut(&idle).unwrap().reputation = -1;
+
+        let persisted = peers.persisted_peers();
+        assert_eq!(persisted.peers.len(), 2);
+        assert_eq!(persisted.banned.len(), 1);
+
+        let restored = PeersManager::new(PeersConfig::default().with_persisted_peers(persisted));
+        assert!(restored.ban_list.is_banned_peer(&banned));
+        assert_eq!(restored.get_reputation(&banned), Some(BANNED_REPUTATION - 1));
+        assert_eq!(restored.get_reputation(&idle), Some(-1));
+
+        // a ban that expired while the node was stopped is lifted together with the reputation
+        let mut persisted = peers.persisted_peers();
+        persisted.banned.clear();
+        let restored = PeersManager::new(PeersConfig::default().with_persisted_peers(persisted));
+        assert!(!restored.ban_list.is_banned_peer(&banned));
+        assert_eq!(restored.get_reputation(&banned), Some(DEFAULT_REPUTATION));
+    }
 }
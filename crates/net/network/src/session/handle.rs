@@ -10,7 +10,7 @@ use reth_eth_wire::{
     errors::EthStreamError, Capabilities, DisconnectReason, EthVersion, NetworkPrimitives,
     UnifiedStatus,
 };
-use reth_network_api::PeerInfo;
+use reth_network_api::{PeerFetchScore, PeerInfo};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::PeerKind;
 use std::{io, net::SocketAddr, sync::Arc, time::Instant};
@@ -136,7 +136,12 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
     }
 
     /// Extracts the [`PeerInfo`] from the session handle.
-    pub(crate) fn peer_info(&self, record: &NodeRecord, kind: PeerKind) -> PeerInfo {
+    pub(crate) fn peer_info(
+        &self,
+        record: &NodeRecord,
+        kind: PeerKind,
+        fetch_score: Option<PeerFetchScore>,
+    ) -> PeerInfo {
         PeerInfo {
             remote_id: self.remote_id,
             direction: self.direction,
@@ -150,6 +155,7 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
             status: self.status.clone(),
             session_established: self.established,
             kind,
+            fetch_score,
         }
     }
 }
@@ -17,9 +17,11 @@ use reth_eth_wire::{
     NewBlockHashes, NewBlockPayload, UnifiedStatus,
 };
 use reth_ethereum_forks::ForkId;
-use reth_network_api::{DiscoveredEvent, DiscoveryEvent, PeerRequest, PeerRequestSender};
+use reth_network_api::{
+    DiscoveredEvent, DiscoveryEvent, PeerFetchScore, PeerRequest, PeerRequestSender,
+};
 use reth_network_peers::PeerId;
-use reth_network_types::{PeerAddr, PeerKind};
+use reth_network_types::{PeerAddr, PeerKind, PeerScoringConfig};
 use reth_primitives_traits::Block;
 use std::{
     collections::{HashMap, VecDeque},
@@ -102,8 +104,10 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         discovery: Discovery,
         peers_manager: PeersManager,
         num_active_peers: Arc<AtomicUsize>,
+        peer_scoring: PeerScoringConfig,
     ) -> Self {
-        let state_fetcher = StateFetcher::new(peers_manager.handle(), num_active_peers);
+        let state_fetcher =
+            StateFetcher::new(peers_manager.handle(), num_active_peers, peer_scoring);
         Self {
             active_peers: Default::default(),
             peers_manager,
@@ -134,6 +138,11 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         self.state_fetcher.client()
     }
 
+    /// Returns the statistics of the block header and body responses of the peer.
+    pub(crate) fn peer_fetch_score(&self, peer_id: &PeerId) -> Option<PeerFetchScore> {
+        self.state_fetcher.peer_fetch_score(peer_id)
+    }
+
     /// How many peers we're currently connected to.
     pub fn num_active_peers(&self) -> usize {
         self.active_peers.len()
@@ -597,7 +606,7 @@ mod tests {
             queued_messages: Default::default(),
             client: BlockNumReader(Box::new(NoopProvider::default())),
             discovery: Discovery::noop(),
-            state_fetcher: StateFetcher::new(handle, Default::default()),
+            state_fetcher: StateFetcher::new(handle, Default::default(), Default::default()),
         }
     }
 
@@ -12,19 +12,22 @@ use reth_network::{
 };
 use reth_network_api::{
     events::{PeerEvent, SessionInfo},
-    NetworkInfo, Peers, PeersInfo,
+    NetworkInfo, Peers, PeersInfo, StaticPeerState,
 };
 use reth_network_p2p::{
     headers::client::{HeadersClient, HeadersRequest},
     sync::{NetworkSyncUpdater, SyncState},
 };
 use reth_network_peers::{mainnet_nodes, NodeRecord, TrustedPeer};
-use reth_network_types::peers::config::PeerBackoffDurations;
+use reth_network_types::peers::config::{PeerBackoffDurations, StaticReconnectBackoff};
 use reth_storage_api::noop::NoopProvider;
 use reth_tracing::init_test_tracing;
 use reth_transaction_pool::test_utils::testing_pool;
 use secp256k1::SecretKey;
-use std::time::Duration;
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    time::Duration,
+};
 use tokio::task;
 use url::Host;
 
@@ -784,3 +787,61 @@ async fn test_reconnect_trusted() {
         Err(_) => panic!("Trusted peer did not reconnect in time"),
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reconnect_static_peer() {
+    reth_tracing::init_test_tracing();
+
+    // the static peer keeps its key and address across restarts
+    let static_secret_key = SecretKey::new(&mut rand_08::thread_rng());
+    let start_static_peer = |addr: SocketAddr| async move {
+        let config = NetworkConfigBuilder::<EthNetworkPrimitives>::new(static_secret_key)
+            .listener_addr(addr)
+            .disable_discovery()
+            .build_with_noop_provider(MAINNET.clone());
+        let network = NetworkManager::new(config).await.unwrap();
+        (network.local_addr(), *network.peer_id(), task::spawn(network))
+    };
+    let (static_addr, static_peer_id, static_peer) =
+        start_static_peer((Ipv4Addr::LOCALHOST, 0).into()).await;
+
+    let backoff =
+        StaticReconnectBackoff { min: Duration::from_millis(100), max: Duration::from_secs(1) };
+    let peers_config = PeersConfig::default()
+        .with_static_nodes(vec![NodeRecord::new(static_addr, static_peer_id).into()])
+        .with_static_reconnect_backoff(backoff);
+    let config = NetworkConfigBuilder::new(SecretKey::new(&mut rand_08::thread_rng()))
+        .listener_port(0)
+        .disable_discovery()
+        .peer_config(peers_config)
+        .build_with_noop_provider(MAINNET.clone());
+    let network = NetworkManager::<EthNetworkPrimitives>::new(config).await.unwrap();
+    let handle = network.handle().clone();
+    let mut events = NetworkEventStream::new(handle.event_listener());
+    task::spawn(network);
+
+    // the static peer is dialed on startup
+    assert_eq!(events.next_session_established().await.unwrap(), static_peer_id);
+    let static_peers = handle.get_static_peers().await.unwrap();
+    assert_eq!(static_peers.len(), 1);
+    assert_eq!(static_peers[0].state, StaticPeerState::Connected);
+
+    // kill the static peer, the dials fail until it's restarted
+    static_peer.abort();
+    let (peer_id, _) = events.next_session_closed().await.unwrap();
+    assert_eq!(peer_id, static_peer_id);
+    let static_peers = handle.get_static_peers().await.unwrap();
+    assert_ne!(static_peers[0].state, StaticPeerState::Connected);
+    assert!(static_peers[0].reconnect_attempts > 0);
+
+    // once restarted, the static peer is redialed within the maximum backoff
+    let (_, _, _static_peer) = start_static_peer(static_addr).await;
+    let peer_id = tokio::time::timeout(backoff.max * 2, events.next_session_established())
+        .await
+        .expect("static peer was not redialed in time")
+        .unwrap();
+    assert_eq!(peer_id, static_peer_id);
+    let static_peers = handle.get_static_peers().await.unwrap();
+    assert_eq!(static_peers[0].state, StaticPeerState::Connected);
+    assert_eq!(static_peers[0].reconnect_attempts, 0);
+}
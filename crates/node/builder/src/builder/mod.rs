@@ -15,10 +15,12 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_exex::ExExContext;
 use reth_network::{
+    peers::write_peers_file,
     transactions::{TransactionPropagationPolicy, TransactionsManagerConfig},
     NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
     NetworkPrimitives,
 };
+use reth_network_api::test_utils::PeersHandleProvider;
 use reth_node_api::{
     FullNodeTypes, FullNodeTypesAdapter, NodeAddOns, NodeTypes, NodeTypesWithDBAdapter,
 };
@@ -35,11 +37,14 @@ use reth_provider::{
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
 use secp256k1::SecretKey;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tracing::{info, trace, warn};
 
 pub mod add_ons;
 
+/// How often the known peers are written to the peers file while the node is running.
+const PERSIST_PEERS_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 mod states;
 pub use states::*;
 
@@ -864,6 +869,23 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
 
         let default_peers_path = self.config().datadir().known_peers();
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
+        if let Some(peers_file) = known_peers_file.clone() {
+            let peers_handle = handle.peers_handle().clone();
+            self.executor.spawn(Box::pin(async move {
+                let mut interval = tokio::time::interval(PERSIST_PEERS_INTERVAL);
+                // the first tick completes immediately
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    let peers = peers_handle.persisted_peers().await;
+                    if let Err(err) = write_peers_file(&peers_file, &peers) {
+                        warn!(target: "reth::cli", %err, "Failed to write network peers to file");
+                    } else {
+                        trace!(target: "reth::cli", peers_file=?peers_file, num_peers=%peers.peers.len(), "Saved current peers");
+                    }
+                }
+            }));
+        }
         self.executor.spawn_critical_with_graceful_shutdown_signal(
             "p2p network task",
             |shutdown| {
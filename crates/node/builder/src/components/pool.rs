@@ -5,6 +5,7 @@ use alloy_primitives::Address;
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::EthereumHardforks;
 use reth_node_api::{NodeTypes, TxTy};
+use reth_tasks::lanes::BlockingTaskLane;
 use reth_transaction_pool::{
     blobstore::DiskFileBlobStore, BlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction,
     SubPoolLimit, TransactionPool, TransactionValidationTaskExecutor, TransactionValidator,
@@ -260,7 +261,7 @@ where
             client,
             pool,
             chain_events,
-            ctx.task_executor().clone(),
+            ctx.task_executor().clone().with_blocking_lane(BlockingTaskLane::Maintenance),
             reth_transaction_pool::maintain::MaintainPoolConfig {
                 max_tx_lifetime: pool_config.max_queued_lifetime,
                 no_local_exemptions: pool_config.local_transactions_config.no_exemptions,
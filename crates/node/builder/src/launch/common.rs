@@ -42,7 +42,11 @@ use reth_chainspec::{Chain, EthChainSpec, EthereumHardforks};
 use reth_config::{config::EtlConfig, PruneConfig};
 use reth_consensus::noop::NoopConsensus;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
-use reth_db_common::init::{init_genesis_with_settings, InitStorageError};
+use reth_db_common::{
+    consistency::{StorageHeights, INCONSISTENCY_INSTRUCTIONS},
+    init::{init_genesis_with_settings, InitStorageError},
+    migration::{migrate, MigrationError},
+};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_engine_local::MiningMode;
 use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
@@ -469,6 +473,9 @@ where
     /// Returns the [`ProviderFactory`] for the attached storage after executing a consistent check
     /// between the database and static files. **It may execute a pipeline unwind if it fails this
     /// check.**
+    ///
+    /// Returns an error if the storage is inconsistent in a way that can't be healed, see
+    /// [`StorageHeights::inconsistencies`].
     pub async fn create_provider_factory<N, Evm>(&self) -> eyre::Result<ProviderFactory<N>>
     where
         N: ProviderNodeTypes<DB = DB, ChainSpec = ChainSpec>,
@@ -501,17 +508,25 @@ where
         )?
         .with_prune_modes(self.prune_modes());
 
+        let heights = StorageHeights::read(&factory.provider()?)?;
+        let inconsistencies = heights.inconsistencies();
+        if !inconsistencies.is_empty() {
+            warn!(target: "reth::cli", "Storage heights:\n{heights}");
+            for inconsistency in &inconsistencies {
+                warn!(target: "reth::cli", healable = inconsistency.healable, "Inconsistent storage: {inconsistency}");
+            }
+        }
+
         // Check for consistency between database and static files. If it fails, it unwinds to
         // the first block that's consistent between database and static files.
         if let Some(unwind_target) =
             factory.static_file_provider().check_consistency(&factory.provider()?)?
         {
-            // Highly unlikely to happen, and given its destructive nature, it's better to panic
-            // instead.
-            assert_ne!(
-                unwind_target,
-                PipelineTarget::Unwind(0),
-                "A static file <> database inconsistency was found that would trigger an unwind to block 0"
+            // Highly unlikely to happen, and given its destructive nature, it's better to refuse
+            // to start instead.
+            eyre::ensure!(
+                unwind_target != PipelineTarget::Unwind(0),
+                "A static file <> database inconsistency was found that would trigger an unwind to block 0. {INCONSISTENCY_INSTRUCTIONS}"
             );
 
             info!(target: "reth::cli", unwind_target = %unwind_target, "Executing an unwind after a failed storage consistency check.");
@@ -552,6 +567,16 @@ where
             })?;
         }
 
+        let healed = StorageHeights::read(&factory.provider()?)?;
+        heights.log_changes(&healed);
+        if let Some(inconsistency) =
+            healed.inconsistencies().into_iter().find(|inconsistency| !inconsistency.healable)
+        {
+            eyre::bail!(
+                "Inconsistent storage that can't be healed: {inconsistency}.\n{healed}\n{INCONSISTENCY_INSTRUCTIONS}"
+            )
+        }
+
         Ok(factory)
     }
 
@@ -650,6 +675,22 @@ where
         Ok(self)
     }
 
+    /// Applies the pending database migrations, and fails if one of them is destructive unless
+    /// `--db.auto-migrate` is set.
+    ///
+    /// Must be called after the genesis is initialized, which sets the schema version of new
+    /// databases.
+    pub fn with_migrations(self) -> eyre::Result<Self> {
+        match migrate(self.provider_factory(), self.node_config().db.auto_migrate) {
+            Ok(_) => Ok(self),
+            Err(err @ MigrationError::Destructive { .. }) => eyre::bail!(
+                "The database has a pending {err}, apply it with `reth db migrate` or start the \
+                 node with `--db.auto-migrate`"
+            ),
+            Err(err) => Err(err.into()),
+        }
+    }
+
     /// Write the genesis block and state if it has not already been written
     pub fn init_genesis(&self) -> Result<B256, InitStorageError> {
         init_genesis_with_settings(
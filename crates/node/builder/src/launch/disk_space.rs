@@ -0,0 +1,144 @@
+//! Monitor of the free space of the disks that the node writes to.
+
+use reth_metrics::{metrics::Gauge, Metrics};
+use reth_tasks::TaskExecutor;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use sysinfo::Disks;
+use tokio::sync::watch;
+use tracing::{debug, error, info};
+
+/// Interval between checks of the free disk space.
+const DISK_SPACE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Periodically checks the free space of the volumes of the storage directories.
+///
+/// Once the free space of a volume drops below the minimum, the pipeline is paused and a graceful
+/// shutdown of the node is initiated, instead of letting a write fail once the disk is full. The
+/// directories may be on different volumes, e.g. if the static files are on a separate mount.
+#[derive(Debug)]
+pub(crate) struct DiskSpaceMonitor {
+    /// Minimum free space of each volume in bytes.
+    min_free_space: u64,
+    /// Pauses the pipeline, see [`reth_stages::Pipeline::set_pause_signal`].
+    pause_tx: watch::Sender<bool>,
+    directories: Vec<MonitoredDirectory>,
+}
+
+impl DiskSpaceMonitor {
+    /// Creates a new monitor that pauses the pipeline with `pause_tx`.
+    pub(crate) const fn new(min_free_space: u64, pause_tx: watch::Sender<bool>) -> Self {
+        Self { min_free_space, pause_tx, directories: Vec::new() }
+    }
+
+    /// Adds a directory whose volume is monitored, `name` is used in logs and metrics.
+    pub(crate) fn with_directory(mut self, name: &'static str, path: PathBuf) -> Self {
+        let metrics = DiskSpaceMetrics::new_with_labels(&[("directory", name)]);
+        self.directories.push(MonitoredDirectory { name, path, metrics });
+        self
+    }
+
+    /// Spawns the monitor, which stops after initiating the shutdown.
+    pub(crate) fn spawn(self, executor: &TaskExecutor) {
+        let task_executor = executor.clone();
+        executor.spawn(Box::pin(async move {
+            let mut interval = tokio::time::interval(DISK_SPACE_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                if !self.check() {
+                    continue
+                }
+
+                self.pause_tx.send_replace(true);
+                if task_executor.initiate_graceful_shutdown().is_err() {
+                    debug!(target: "reth::cli", "Node is already shutting down");
+                }
+                break
+            }
+        }));
+    }
+
+    /// Updates the metrics of the volumes, and returns whether one of them is low on space.
+    fn check(&self) -> bool {
+        let disks = Disks::new_with_refreshed_list();
+        let mounts = disks.iter().map(|disk| (disk.mount_point(), disk.available_space()));
+
+        let mut low_on_space = false;
+        for directory in &self.directories {
+            // The directory may not be created yet
+            let path = directory.path.canonicalize().unwrap_or_else(|_| directory.path.clone());
+            let Some(available) = available_space(mounts.clone(), &path) else {
+                debug!(target: "reth::cli", directory = directory.name, ?path, "Volume of directory not found");
+                continue
+            };
+
+            directory.metrics.available_bytes.set(available as f64);
+            let low = available < self.min_free_space;
+            directory.metrics.low_space.set(low as u8 as f64);
+            if low {
+                error!(
+                    target: "reth::cli",
+                    directory = directory.name,
+                    ?path,
+                    available,
+                    min_free_space = self.min_free_space,
+                    "Disk space is below the minimum, pausing the pipeline and shutting down. \
+                     Free up disk space or lower `--db.min-free-space` before restarting."
+                );
+                low_on_space = true;
+            }
+        }
+
+        if low_on_space {
+            info!(target: "reth::cli", "Initiating graceful shutdown because of low disk space");
+        }
+        low_on_space
+    }
+}
+
+/// A storage directory whose volume is monitored.
+#[derive(Debug)]
+struct MonitoredDirectory {
+    name: &'static str,
+    path: PathBuf,
+    metrics: DiskSpaceMetrics,
+}
+
+/// Metrics of the free space of the volume of a storage directory.
+#[derive(Metrics)]
+#[metrics(scope = "storage.disk")]
+struct DiskSpaceMetrics {
+    /// Available space of the volume in bytes
+    available_bytes: Gauge,
+    /// Whether the available space is below the minimum, 1 if it is
+    low_space: Gauge,
+}
+
+/// Returns the available space of the mount that contains `path`, which is the one with the
+/// longest mount point that `path` starts with.
+fn available_space<'a>(
+    mounts: impl IntoIterator<Item = (&'a Path, u64)>,
+    path: &Path,
+) -> Option<u64> {
+    mounts
+        .into_iter()
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.components().count())
+        .map(|(_, available)| available)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_volume_of_path() {
+        let mounts = [(Path::new("/"), 1), (Path::new("/mnt/static"), 2), (Path::new("/mnt"), 3)];
+        assert_eq!(available_space(mounts, Path::new("/var/lib/reth")), Some(1));
+        assert_eq!(available_space(mounts, Path::new("/mnt/static/files")), Some(2));
+        assert_eq!(available_space(mounts, Path::new("/mnt/staticfiles")), Some(3));
+        assert_eq!(available_space(mounts[1..].iter().copied(), Path::new("/var")), None);
+    }
+}
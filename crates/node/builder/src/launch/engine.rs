@@ -3,6 +3,7 @@
 use crate::{
     common::{Attached, LaunchContextWith, WithConfigs},
     hooks::NodeHooks,
+    launch::{disk_space::DiskSpaceMonitor, health::HealthServer, summary::StartupSummary},
     rpc::{EngineValidatorAddOn, EngineValidatorBuilder, RethRpcAddOns, RpcHandle},
     setup::build_networked_pipeline,
     AddOns, AddOnsContext, FullNode, LaunchContext, LaunchNode, NodeAdapter,
@@ -33,11 +34,11 @@ use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     BlockNumReader, MetadataProvider,
 };
-use reth_tasks::TaskExecutor;
+use reth_tasks::{lanes::BlockingTaskLane, TaskExecutor};
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
 use std::{future::Future, pin::Pin, sync::Arc};
-use tokio::sync::{mpsc::unbounded_channel, oneshot};
+use tokio::sync::{mpsc::unbounded_channel, oneshot, watch};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::warn;
 
@@ -85,6 +86,7 @@ impl EngineNodeLauncher {
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
+        let engine_tree_config = engine_tree_config.with_max_block(config.debug.terminate_at_block);
 
         // setup the launch context
         let ctx = ctx
@@ -123,8 +125,14 @@ impl EngineNodeLauncher {
                 debug!(target: "reth::cli", chain=%this.chain_id(), genesis=?this.genesis_hash(), "Initializing genesis");
             })
             .with_genesis()?
+            .with_migrations()?
             .inspect(|this: &LaunchContextWith<Attached<WithConfigs<<T::Types as NodeTypes>::ChainSpec>, _>>| {
                 info!(target: "reth::cli", "\n{}", this.chain_spec().display_hardforks());
+                let args = std::env::args().collect::<Vec<_>>();
+                let prune_config = this.prune_config();
+                let summary =
+                    StartupSummary::new(this.node_config(), this.toml_config(), &prune_config, &args);
+                summary.log();
             })
             .with_metrics_task()
             // passing FullNodeTypes as type parameter here so that we can build
@@ -144,10 +152,33 @@ impl EngineNodeLauncher {
 
         let node_config = ctx.node_config();
 
+        // The health check server answers before the pipeline starts, and reports the node as
+        // unhealthy until it's launched
+        let health_server = match node_config.health.socket_addr() {
+            Some(addr) => {
+                let server = HealthServer::new(
+                    network_handle.clone(),
+                    ctx.blockchain_db().clone(),
+                    node_config.health.health_ready_distance,
+                );
+                server.spawn(addr, ctx.task_executor()).await?;
+                Some(server)
+            }
+            None => None,
+        };
+
         // We always assume that node is syncing after a restart
         network_handle.update_sync_state(SyncState::Syncing);
 
         let max_block = ctx.max_block(network_client.clone()).await?;
+        if let Some(terminate_at_block) = node_config.debug.terminate_at_block {
+            warn!(
+                target: "reth::cli",
+                terminate_at_block,
+                "Sync is clamped by --debug.terminate-at-block. The node keeps running at this \
+                 block, and answers engine messages past it with SYNCING."
+            );
+        }
 
         let static_file_producer = ctx.static_file_producer();
         let static_file_producer_events = static_file_producer.lock().events();
@@ -155,12 +186,16 @@ impl EngineNodeLauncher {
 
         let consensus = Arc::new(ctx.components().consensus().clone());
 
-        let pipeline = build_networked_pipeline(
+        // Blocking work of the pipeline and the engine is limited by its own lane, so that it
+        // can't starve RPC requests of blocking threads
+        let sync_executor = ctx.task_executor().clone().with_blocking_lane(BlockingTaskLane::Sync);
+
+        let mut pipeline = build_networked_pipeline(
             &ctx.toml_config().stages,
             network_client.clone(),
             consensus.clone(),
             ctx.provider_factory().clone(),
-            ctx.task_executor(),
+            &sync_executor,
             ctx.sync_metrics_tx(),
             ctx.prune_config(),
             max_block,
@@ -170,6 +205,15 @@ impl EngineNodeLauncher {
             ctx.era_import_source(),
         )?;
 
+        if let Some(min_free_space) = node_config.db.min_free_space() {
+            let (pause_tx, pause_rx) = watch::channel(false);
+            pipeline.set_pause_signal(pause_rx);
+            DiskSpaceMonitor::new(min_free_space, pause_tx)
+                .with_directory("db", ctx.data_dir().db())
+                .with_directory("static_files", ctx.data_dir().static_files())
+                .spawn(ctx.task_executor());
+        }
+
         // The new engine writes directly to static files. This ensures that they're up to the tip.
         pipeline.move_to_static_files()?;
 
@@ -185,6 +229,13 @@ impl EngineNodeLauncher {
         info!(target: "reth::cli", prune_config=?ctx.prune_config(), "Pruner initialized");
 
         let event_sender = EventSender::default();
+        if let Some(health_server) = &health_server {
+            health_server.track_events(
+                ctx.task_executor(),
+                pipeline.events(),
+                event_sender.new_listener(),
+            );
+        }
 
         let beacon_engine_handle = ConsensusEngineHandle::new(consensus_engine_tx.clone());
 
@@ -208,6 +259,7 @@ impl EngineNodeLauncher {
 
         // Create the consensus engine stream with optional reorg
         let consensus_engine_stream = UnboundedReceiverStream::from(consensus_engine_rx)
+            .maybe_max_block(ctx.blockchain_db().clone(), node_config.debug.terminate_at_block)
             .maybe_skip_fcu(node_config.debug.skip_fcu)
             .maybe_skip_new_payload(node_config.debug.skip_new_payload)
             .maybe_reorg(
@@ -229,7 +281,7 @@ impl EngineNodeLauncher {
             network_client.clone(),
             Box::pin(consensus_engine_stream),
             pipeline,
-            Box::new(ctx.task_executor().clone()),
+            Box::new(sync_executor),
             ctx.provider_factory().clone(),
             ctx.blockchain_db().clone(),
             pruner,
@@ -278,6 +330,7 @@ impl EngineNodeLauncher {
         let provider = ctx.blockchain_db().clone();
         let (exit, rx) = oneshot::channel();
         let terminate_after_backfill = ctx.terminate_after_initial_backfill();
+        let exit_after_sync = ctx.node_config().exit_after_sync;
         let startup_sync_state_idle = ctx.node_config().debug.startup_sync_state_idle;
 
         info!(target: "reth::cli", "Starting consensus engine");
@@ -291,6 +344,8 @@ impl EngineNodeLauncher {
             }
 
             let mut res = Ok(());
+            // Whether a backfill sync is running, until which the initial sync is not finished
+            let mut backfill_running = initial_target.is_some();
 
             // advance the chain and await payloads built locally to add into the engine api tree handler to prevent re-execution if that block is received as payload from the CL
             loop {
@@ -306,15 +361,24 @@ impl EngineNodeLauncher {
                         debug!(target: "reth::cli", "Event: {event}");
                         match event {
                             ChainEvent::BackfillSyncFinished => {
+                                backfill_running = false;
                                 if terminate_after_backfill {
                                     debug!(target: "reth::cli", "Terminating after initial backfill");
                                     break
                                 }
+                                // Leaving the loop drops the engine service, the tree then
+                                // still finishes a block it may already be executing before it
+                                // exits
+                                if exit_after_sync {
+                                    info!(target: "reth::cli", "Initial sync finished, shutting down");
+                                    break
+                                }
                                 if startup_sync_state_idle {
                                     network_handle.update_sync_state(SyncState::Idle);
                                 }
                             }
                             ChainEvent::BackfillSyncStarted => {
+                                backfill_running = true;
                                 network_handle.update_sync_state(SyncState::Syncing);
                             }
                             ChainEvent::FatalError => {
@@ -342,7 +406,15 @@ impl EngineNodeLauncher {
                                     };
                                     network_handle.update_block_range(updated);
                                 }
+                                let is_canonical = ev.canonical_header().is_some();
                                 event_sender.notify(ev);
+
+                                // Without a backfill sync, the initial sync is finished once the
+                                // engine has made the first live synced block canonical
+                                if exit_after_sync && is_canonical && !backfill_running {
+                                    info!(target: "reth::cli", "Caught up with the chain tip, shutting down");
+                                    break
+                                }
                             }
                         }
                     }
@@ -373,10 +445,14 @@ impl EngineNodeLauncher {
 
         ctx.spawn_ethstats().await?;
 
+        if let Some(health_server) = health_server {
+            health_server.set_launched();
+        }
+
         let handle = NodeHandle {
             node_exit_future: NodeExitFuture::new(
                 async { rx.await? },
-                full_node.config.debug.terminate,
+                full_node.config.debug.terminate || full_node.config.exit_after_sync,
             ),
             node: full_node,
         };
@@ -0,0 +1,284 @@
+//! Plain HTTP health check server, which is independent of JSON-RPC.
+
+use eyre::WrapErr;
+use futures::{Stream, StreamExt};
+use http::{header::CONTENT_TYPE, HeaderValue, Method, Request, Response, StatusCode};
+use reth_engine_primitives::ConsensusEngineEvent;
+use reth_network_api::{NetworkInfo, PeersInfo};
+use reth_primitives_traits::NodePrimitives;
+use reth_provider::BlockNumReader;
+use reth_stages::{PipelineEvent, StageId};
+use reth_tasks::TaskExecutor;
+use serde_json::json;
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tokio::net::TcpListener;
+use tracing::{debug, error, info};
+
+/// Name of the task of the health check server.
+const HEALTH_SERVER_TASK: &str = "health server";
+
+/// Interval between checks whether the other tasks have finished during a graceful shutdown.
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// State of the node that is tracked by the [`HealthServer`].
+#[derive(Debug, Default)]
+struct HealthState {
+    /// Whether the node has finished launching.
+    launched: AtomicBool,
+    /// Whether a graceful shutdown of the node has been initiated.
+    shutting_down: AtomicBool,
+    /// The highest known block, from pipeline targets and blocks received from the consensus
+    /// layer.
+    tip: AtomicU64,
+    /// The stage that the pipeline runs, or ran last.
+    stage: Mutex<Option<StageId>>,
+}
+
+/// Serves `GET /health`, `GET /ready` and `GET /status` over plain HTTP, for load balancers and
+/// watchdogs that can't use JSON-RPC.
+///
+/// - `/health` succeeds once the node is launched, until a graceful shutdown is initiated.
+/// - `/ready` succeeds once the node is healthy, has finished its initial sync, and is at most the
+///   ready distance behind the highest known block.
+/// - `/status` returns the current block, sync stage, peer count and uptime as JSON.
+///
+/// During a graceful shutdown, the server keeps answering until all other tasks that delay the
+/// shutdown have finished.
+#[derive(Debug, Clone)]
+pub(crate) struct HealthServer<Network, Provider> {
+    state: Arc<HealthState>,
+    network: Network,
+    provider: Provider,
+    ready_distance: u64,
+    started_at: Instant,
+}
+
+impl<Network, Provider> HealthServer<Network, Provider>
+where
+    Network: NetworkInfo + PeersInfo + Clone + 'static,
+    Provider: BlockNumReader + Clone + 'static,
+{
+    /// Creates a new server, which isn't healthy until [`Self::set_launched`] is called.
+    pub(crate) fn new(network: Network, provider: Provider, ready_distance: u64) -> Self {
+        Self {
+            state: Default::default(),
+            network,
+            provider,
+            ready_distance,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Marks the node as launched.
+    pub(crate) fn set_launched(&self) {
+        self.state.launched.store(true, Ordering::Relaxed);
+    }
+
+    /// Binds the server to the address and spawns it.
+    pub(crate) async fn spawn(
+        &self,
+        addr: SocketAddr,
+        executor: &TaskExecutor,
+    ) -> eyre::Result<()> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .wrap_err_with(|| format!("Could not start health check server at {addr}"))?;
+        info!(target: "reth::cli", addr = %listener.local_addr()?, "Starting health check server");
+
+        let server = self.clone();
+        let task_executor = executor.clone();
+        executor.spawn_critical_with_graceful_shutdown_signal(HEALTH_SERVER_TASK, |mut signal| {
+            Box::pin(async move {
+                let mut interval = tokio::time::interval(SHUTDOWN_POLL_INTERVAL);
+                let mut shutdown_guard = None;
+                loop {
+                    let io = tokio::select! {
+                        guard = &mut signal, if shutdown_guard.is_none() => {
+                            server.state.shutting_down.store(true, Ordering::Relaxed);
+                            shutdown_guard = Some(guard);
+                            continue
+                        }
+                        _ = interval.tick(), if shutdown_guard.is_some() => {
+                            let pending = task_executor.pending_graceful_tasks();
+                            if pending.iter().all(|(name, _)| *name == HEALTH_SERVER_TASK) {
+                                break
+                            }
+                            continue
+                        }
+                        io = listener.accept() => match io {
+                            Ok((stream, _remote_addr)) => stream,
+                            Err(err) => {
+                                error!(target: "reth::cli", %err, "Failed to accept health check connection");
+                                continue
+                            }
+                        }
+                    };
+
+                    let server = server.clone();
+                    let service = tower::service_fn(move |request| {
+                        let response = server.respond(&request);
+                        async move { Ok::<_, Infallible>(response) }
+                    });
+                    tokio::task::spawn(async move {
+                        let _ = jsonrpsee::server::serve(io, service).await.inspect_err(
+                            |error| debug!(target: "reth::cli", %error, "Failed to serve health check"),
+                        );
+                    });
+                }
+                drop(shutdown_guard);
+            })
+        });
+
+        Ok(())
+    }
+
+    /// Spawns a task that tracks the pipeline and engine events, to know the sync stage and the
+    /// highest known block.
+    pub(crate) fn track_events<N: NodePrimitives>(
+        &self,
+        executor: &TaskExecutor,
+        pipeline_events: impl Stream<Item = PipelineEvent> + Send + Unpin + 'static,
+        engine_events: impl Stream<Item = ConsensusEngineEvent<N>> + Send + Unpin + 'static,
+    ) {
+        let state = self.state.clone();
+        let mut pipeline_events = pipeline_events.fuse();
+        let mut engine_events = engine_events.fuse();
+        executor.spawn(Box::pin(async move {
+            loop {
+                tokio::select! {
+                    Some(event) = pipeline_events.next() => state.on_pipeline_event(&event),
+                    Some(event) = engine_events.next() => state.on_engine_event(&event),
+                    else => break,
+                }
+            }
+        }));
+    }
+
+    /// Answers a request.
+    fn respond<B>(&self, request: &Request<B>) -> Response<String> {
+        if request.method() != Method::GET {
+            return text_response(StatusCode::METHOD_NOT_ALLOWED, "method not allowed")
+        }
+
+        let healthy = self.state.launched.load(Ordering::Relaxed) &&
+            !self.state.shutting_down.load(Ordering::Relaxed);
+        match request.uri().path() {
+            "/health" if healthy => text_response(StatusCode::OK, "ok"),
+            "/health" => text_response(StatusCode::SERVICE_UNAVAILABLE, "unavailable"),
+            "/ready" => {
+                let synced = self.provider.best_block_number().is_ok_and(|block| {
+                    self.state.tip.load(Ordering::Relaxed).saturating_sub(block) <=
+                        self.ready_distance
+                });
+                if healthy && synced && !self.network.is_initially_syncing() {
+                    text_response(StatusCode::OK, "ready")
+                } else {
+                    text_response(StatusCode::SERVICE_UNAVAILABLE, "not ready")
+                }
+            }
+            "/status" => {
+                let block = self.provider.best_block_number().ok();
+                let syncing = self.network.is_syncing();
+                let stage = syncing
+                    .then(|| *self.state.stage.lock().unwrap_or_else(|err| err.into_inner()))
+                    .flatten();
+                let status = json!({
+                    "healthy": healthy,
+                    "block": block,
+                    "tip": block.map(|block| block.max(self.state.tip.load(Ordering::Relaxed))),
+                    "syncing": syncing,
+                    "stage": stage.map(|stage| stage.to_string()),
+                    "peers": self.network.num_connected_peers(),
+                    "uptime_secs": self.started_at.elapsed().as_secs(),
+                });
+                let mut response = Response::new(status.to_string());
+                response
+                    .headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                response
+            }
+            _ => text_response(StatusCode::NOT_FOUND, "not found"),
+        }
+    }
+}
+
+impl HealthState {
+    fn on_pipeline_event(&self, event: &PipelineEvent) {
+        if let PipelineEvent::Run { stage_id, target, .. } = event {
+            *self.stage.lock().unwrap_or_else(|err| err.into_inner()) = Some(*stage_id);
+            if let Some(target) = target {
+                self.tip.fetch_max(*target, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn on_engine_event<N: NodePrimitives>(&self, event: &ConsensusEngineEvent<N>) {
+        if let ConsensusEngineEvent::BlockReceived(block) = event {
+            self.tip.fetch_max(block.number, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Returns a plain text response with the status.
+fn text_response(status: StatusCode, body: &'static str) -> Response<String> {
+    let mut response = Response::new(body.to_string());
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::B256;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_provider::test_utils::MockEthProvider;
+
+    fn get(server: &HealthServer<NoopNetwork, MockEthProvider>, path: &str) -> Response<String> {
+        server.respond(&Request::get(path).body(()).unwrap())
+    }
+
+    #[test]
+    fn answers_health_checks() {
+        let provider = MockEthProvider::default();
+        provider.add_header(B256::ZERO, Header { number: 100, ..Default::default() });
+        let server = HealthServer::new(NoopNetwork::default(), provider, 16);
+
+        assert_eq!(get(&server, "/health").status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(get(&server, "/ready").status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        server.set_launched();
+        assert_eq!(get(&server, "/health").status(), StatusCode::OK);
+        assert_eq!(get(&server, "/ready").status(), StatusCode::OK);
+
+        server.state.on_pipeline_event(&PipelineEvent::Run {
+            pipeline_stages_progress: reth_stages::PipelineStagesProgress { current: 1, total: 1 },
+            stage_id: StageId::Headers,
+            checkpoint: None,
+            target: Some(117),
+        });
+        assert_eq!(get(&server, "/ready").status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let status: serde_json::Value =
+            serde_json::from_str(get(&server, "/status").body()).unwrap();
+        assert_eq!(status["block"], 100);
+        assert_eq!(status["tip"], 117);
+        assert_eq!(status["peers"], 0);
+
+        server.state.shutting_down.store(true, Ordering::Relaxed);
+        assert_eq!(get(&server, "/health").status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        assert_eq!(get(&server, "/metrics").status(), StatusCode::NOT_FOUND);
+        let post = Request::post("/health").body(()).unwrap();
+        assert_eq!(server.respond(&post).status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+}
@@ -5,7 +5,10 @@ mod exex;
 pub mod invalid_block_hook;
 
 pub(crate) mod debug;
+mod disk_space;
 pub(crate) mod engine;
+mod health;
+mod summary;
 
 pub use common::LaunchContext;
 pub use exex::ExExLauncher;
@@ -0,0 +1,339 @@
+//! Summary of the effective configuration that is logged once on startup.
+
+use human_bytes::human_bytes;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_config::{Config, PruneConfig};
+use reth_node_core::node_config::NodeConfig;
+use reth_rpc_builder::{config::RethRpcServerConfig, RpcModuleSelection};
+use reth_tracing::LogFormat;
+use serde_json::{json, Value};
+use std::{fmt, net::IpAddr, path::Path};
+use tracing::info;
+
+/// Where the value of a setting comes from.
+///
+/// None of the settings of the summary can be set with an environment variable, so they come
+/// from a command line flag, the config file or the defaults of the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SettingSource {
+    /// One of the flags of the setting was passed on the command line.
+    Flag,
+    /// The setting was read from the config file.
+    Config,
+    /// The setting has its default value.
+    Default,
+}
+
+impl SettingSource {
+    /// Returns [`Self::Flag`] if one of the `flags` is in the command line `args`, and `otherwise`
+    /// if not.
+    fn detect(args: &[String], flags: &[&str], otherwise: Self) -> Self {
+        let passed = args.iter().any(|arg| {
+            flags.iter().any(|flag| {
+                arg.strip_prefix(flag).is_some_and(|rest| rest.is_empty() || rest.starts_with('='))
+            })
+        });
+        if passed {
+            Self::Flag
+        } else {
+            otherwise
+        }
+    }
+
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Flag => "flag",
+            Self::Config => "config",
+            Self::Default => "default",
+        }
+    }
+}
+
+impl fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A setting of the [`StartupSummary`].
+#[derive(Debug)]
+struct Setting {
+    name: &'static str,
+    /// The value rendered for the terminal.
+    text: String,
+    /// The value rendered for JSON logs.
+    json: Value,
+    source: SettingSource,
+}
+
+/// The effective configuration of the node, logged once before the node starts syncing.
+///
+/// Secrets are never part of the summary, only the paths of the files they are read from.
+#[derive(Debug)]
+pub(crate) struct StartupSummary {
+    settings: Vec<Setting>,
+}
+
+impl StartupSummary {
+    /// Summarizes the configuration of the node, `args` are the command line arguments that are
+    /// used to tell which settings were passed as flags.
+    pub(crate) fn new<ChainSpec>(
+        config: &NodeConfig<ChainSpec>,
+        toml_config: &Config,
+        prune_config: &PruneConfig,
+        args: &[String],
+    ) -> Self
+    where
+        ChainSpec: EthChainSpec + EthereumHardforks,
+    {
+        let data_dir = config.datadir();
+        let mut settings = Vec::new();
+        let mut push = |name, text, json, source| {
+            settings.push(Setting { name, text, json, source });
+        };
+
+        let datadir_source = SettingSource::detect(args, &["--datadir"], SettingSource::Default);
+        push(
+            "datadir",
+            data_dir.data_dir().display().to_string(),
+            json!(data_dir.data_dir()),
+            datadir_source,
+        );
+        let db = data_dir.db();
+        let db_size = dir_size(&db);
+        push(
+            "database",
+            format!("{}, {}", db.display(), human_bytes(db_size as f64)),
+            json!({ "path": db, "size": db_size }),
+            datadir_source,
+        );
+        let static_files = data_dir.static_files();
+        let static_files_size = dir_size(&static_files);
+        push(
+            "static files",
+            format!("{}, {}", static_files.display(), human_bytes(static_files_size as f64)),
+            json!({ "path": static_files, "size": static_files_size }),
+            SettingSource::detect(
+                args,
+                &["--datadir.static-files", "--datadir.static_files"],
+                datadir_source,
+            ),
+        );
+        let config_path = config.config.clone().unwrap_or_else(|| data_dir.config());
+        push(
+            "config",
+            config_path.display().to_string(),
+            json!(config_path),
+            SettingSource::detect(args, &["--config"], datadir_source),
+        );
+
+        let chain = config.chain.chain();
+        let genesis_hash = config.chain.genesis_hash();
+        push(
+            "chain",
+            format!("{chain} ({}), genesis {genesis_hash}", chain.id()),
+            json!({ "name": chain.to_string(), "id": chain.id(), "genesis_hash": genesis_hash }),
+            SettingSource::detect(args, &["--chain"], SettingSource::Default),
+        );
+
+        let rpc = &config.rpc;
+        let modules = rpc.transport_rpc_module_config();
+        let (text, json) = server(modules.http(), rpc.http_addr, rpc.http_port);
+        push(
+            "http",
+            text,
+            json,
+            SettingSource::detect(
+                args,
+                &["--http", "--http.addr", "--http.port", "--http.api", "--dev"],
+                SettingSource::Default,
+            ),
+        );
+        let (text, json) = server(modules.ws(), rpc.ws_addr, rpc.ws_port);
+        push(
+            "ws",
+            text,
+            json,
+            SettingSource::detect(
+                args,
+                &["--ws", "--ws.addr", "--ws.port", "--ws.api"],
+                SettingSource::Default,
+            ),
+        );
+        let ipc = rpc.is_ipc_enabled().then(|| rpc.ipcpath.clone());
+        push(
+            "ipc",
+            ipc.clone().unwrap_or_else(|| "disabled".to_string()),
+            json!({ "enabled": ipc.is_some(), "path": ipc }),
+            SettingSource::detect(args, &["--ipcdisable", "--ipcpath"], SettingSource::Default),
+        );
+        let jwt_secret = rpc.auth_jwtsecret.clone().unwrap_or_else(|| data_dir.jwt());
+        let auth_address = format!("{}:{}", rpc.auth_addr, rpc.auth_port);
+        push(
+            "authrpc",
+            if rpc.disable_auth_server {
+                "disabled".to_string()
+            } else {
+                format!("{auth_address}, jwt secret {}", jwt_secret.display())
+            },
+            json!({
+                "enabled": !rpc.disable_auth_server,
+                "address": auth_address,
+                "jwt_secret_path": jwt_secret,
+            }),
+            SettingSource::detect(
+                args,
+                &[
+                    "--authrpc.addr",
+                    "--authrpc.port",
+                    "--authrpc.jwtsecret",
+                    "--disable-auth-server",
+                ],
+                SettingSource::Default,
+            ),
+        );
+
+        let prune_source = if config.prune_config().is_some() {
+            SettingSource::Flag
+        } else if !toml_config.prune.is_default() {
+            SettingSource::Config
+        } else {
+            SettingSource::Default
+        };
+        let (text, json) = pruning(prune_config);
+        push("pruning", text, json, prune_source);
+
+        Self { settings }
+    }
+
+    /// Returns the summary as a JSON object, with the value and source of each setting.
+    pub(crate) fn to_json(&self) -> Value {
+        self.settings
+            .iter()
+            .map(|setting| {
+                let value = json!({ "value": setting.json, "source": setting.source.as_str() });
+                (setting.name.replace(' ', "_"), value)
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+
+    /// Logs the summary as a single event, which holds the summary as JSON if the logs are
+    /// written to stdout as JSON.
+    pub(crate) fn log(&self) {
+        if reth_tracing::stdout_log_format() == Some(LogFormat::Json) {
+            info!(target: "reth::cli", summary = %self.to_json(), "Startup summary");
+        } else {
+            info!(target: "reth::cli", "{self}");
+        }
+    }
+}
+
+impl fmt::Display for StartupSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Startup summary")?;
+        for Setting { name, text, source, .. } in &self.settings {
+            write!(f, "\n  {name:<13} {text} ({source})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders an RPC server, which is disabled if it serves no modules.
+fn server(modules: Option<&RpcModuleSelection>, addr: IpAddr, port: u16) -> (String, Value) {
+    let Some(modules) = modules else {
+        return ("disabled".to_string(), json!({ "enabled": false }))
+    };
+    let mut modules = modules.iter_selection().map(|module| module.to_string()).collect::<Vec<_>>();
+    modules.sort_unstable();
+    let address = format!("{addr}:{port}");
+    let text = format!("{address} [{}]", modules.join(", "));
+    (text, json!({ "enabled": true, "address": address, "modules": modules }))
+}
+
+/// Renders the segments that are pruned, the node is an archive node if there are none.
+fn pruning(config: &PruneConfig) -> (String, Value) {
+    let segments = &config.segments;
+    let mut pruned = [
+        ("sender_recovery", segments.sender_recovery),
+        ("transaction_lookup", segments.transaction_lookup),
+        ("receipts", segments.receipts),
+        ("account_history", segments.account_history),
+        ("storage_history", segments.storage_history),
+        ("bodies_history", segments.bodies_history),
+    ]
+    .into_iter()
+    .filter_map(|(segment, mode)| Some((segment, format!("{:?}", mode?))))
+    .collect::<Vec<_>>();
+    if !segments.receipts_log_filter.is_empty() {
+        pruned.push(("receipts_log_filter", "configured".to_string()));
+    }
+
+    if pruned.is_empty() {
+        return ("archive".to_string(), json!({ "archive": true }))
+    }
+    let text = pruned.iter().map(|(segment, mode)| format!("{segment}={mode}")).collect::<Vec<_>>();
+    let segments = pruned
+        .into_iter()
+        .map(|(segment, mode)| (segment.to_string(), Value::String(mode)))
+        .collect::<serde_json::Map<_, _>>();
+    (text.join(", "), json!({ "archive": false, "segments": segments }))
+}
+
+/// Returns the total size of the files in the directory, or zero if it doesn't exist.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else { return 0 };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::DEV;
+    use reth_node_core::args::DatadirArgs;
+
+    #[test]
+    fn renders_default_dev_config() {
+        let mut config = NodeConfig::new(DEV.clone()).dev().with_datadir_args(DatadirArgs {
+            datadir: "/tmp/reth-summary".parse().unwrap(),
+            ..Default::default()
+        });
+        // `--dev` enables the HTTP server
+        config.rpc.http = true;
+        let args = ["reth", "node", "--dev", "--datadir=/tmp/reth-summary"].map(String::from);
+
+        let summary =
+            StartupSummary::new(&config, &Config::default(), &PruneConfig::default(), &args);
+        let genesis_hash = DEV.genesis_hash();
+        assert_eq!(
+            summary.to_string(),
+            format!(
+                "Startup summary
+  datadir       /tmp/reth-summary/dev (flag)
+  database      /tmp/reth-summary/dev/db, 0 B (flag)
+  static files  /tmp/reth-summary/dev/static_files, 0 B (flag)
+  config        /tmp/reth-summary/dev/reth.toml (flag)
+  chain         dev (1337), genesis {genesis_hash} (default)
+  http          127.0.0.1:8545 [eth, net, web3] (flag)
+  ws            disabled (default)
+  ipc           /tmp/reth.ipc (default)
+  authrpc       127.0.0.1:8551, jwt secret /tmp/reth-summary/dev/jwt.hex (default)
+  pruning       archive (default)"
+            )
+        );
+
+        let json = summary.to_json();
+        assert_eq!(json["datadir"], json!({ "value": "/tmp/reth-summary/dev", "source": "flag" }));
+        assert_eq!(json["static_files"]["value"]["size"], 0);
+        assert_eq!(json["http"]["value"]["modules"], json!(["eth", "net", "web3"]));
+        assert_eq!(json["authrpc"]["value"]["jwt_secret_path"], "/tmp/reth-summary/dev/jwt.hex");
+        assert_eq!(json["pruning"], json!({ "value": { "archive": true }, "source": "default" }));
+    }
+}
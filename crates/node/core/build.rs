@@ -1,7 +1,7 @@
 #![allow(missing_docs)]
 
 use std::{env, error::Error};
-use vergen::{BuildBuilder, CargoBuilder, Emitter};
+use vergen::{BuildBuilder, CargoBuilder, Emitter, RustcBuilder};
 use vergen_git2::Git2Builder;
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -15,6 +15,10 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     emitter.add_instructions(&cargo_builder)?;
 
+    let rustc_builder = RustcBuilder::default().semver(true).build()?;
+
+    emitter.add_instructions(&rustc_builder)?;
+
     let git_builder =
         Git2Builder::default().describe(false, true, None).dirty(true).sha(false).build()?;
 
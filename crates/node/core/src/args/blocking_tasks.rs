@@ -0,0 +1,80 @@
+//! clap [Args](clap::Args) for the limits of blocking tasks
+
+use clap::{builder::RangedU64ValueParser, Args};
+use reth_tasks::lanes::{DEFAULT_MAX_MAINTENANCE_BLOCKING_TASKS, DEFAULT_MAX_SYNC_BLOCKING_TASKS};
+
+/// Parameters for the maximum number of concurrently running blocking tasks of each kind of work.
+///
+/// Each kind of work is limited separately, so that e.g. blocking tasks of the sync can't starve
+/// `eth_call` requests of blocking threads. The blocking tasks of RPC requests are limited by
+/// `--rpc.max-blocking-io-requests`.
+#[derive(Debug, Args, PartialEq, Eq, Clone, Copy)]
+#[command(next_help_heading = "Blocking Tasks")]
+pub struct BlockingTaskArgs {
+    /// Maximum number of concurrently running blocking tasks that the pipeline and the engine
+    /// spawn through the task executor.
+    ///
+    /// The pipeline itself, its stages and the commits of the execution stage run on dedicated
+    /// threads and thread pools, which this doesn't limit.
+    #[arg(
+        long = "sync.max-blocking-threads",
+        value_name = "COUNT",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..),
+        default_value_t = DEFAULT_MAX_SYNC_BLOCKING_TASKS
+    )]
+    pub sync_max_blocking_threads: usize,
+
+    /// Maximum number of concurrently running blocking tasks of background maintenance, e.g. of
+    /// the transaction pool.
+    #[arg(
+        long = "maintenance.max-blocking-threads",
+        value_name = "COUNT",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..),
+        default_value_t = DEFAULT_MAX_MAINTENANCE_BLOCKING_TASKS
+    )]
+    pub maintenance_max_blocking_threads: usize,
+}
+
+impl Default for BlockingTaskArgs {
+    fn default() -> Self {
+        Self {
+            sync_max_blocking_threads: DEFAULT_MAX_SYNC_BLOCKING_TASKS,
+            maintenance_max_blocking_threads: DEFAULT_MAX_MAINTENANCE_BLOCKING_TASKS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_blocking_task_args() {
+        let args = CommandParser::<BlockingTaskArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, BlockingTaskArgs::default());
+
+        let args = CommandParser::<BlockingTaskArgs>::parse_from([
+            "reth",
+            "--sync.max-blocking-threads",
+            "8",
+        ])
+        .args;
+        assert_eq!(args.sync_max_blocking_threads, 8);
+        assert_eq!(args.maintenance_max_blocking_threads, DEFAULT_MAX_MAINTENANCE_BLOCKING_TASKS);
+
+        assert!(CommandParser::<BlockingTaskArgs>::try_parse_from([
+            "reth",
+            "--maintenance.max-blocking-threads",
+            "0",
+        ])
+        .is_err());
+    }
+}
@@ -9,7 +9,7 @@ use clap::{
     value_parser, Arg, Args, Command, Error,
 };
 use reth_db::{
-    mdbx::{MaxReadTransactionDuration, SyncMode},
+    mdbx::{MaxReadTransactionDuration, SyncMode, GIGABYTE},
     ClientVersion,
 };
 use reth_storage_errors::db::LogLevel;
@@ -60,9 +60,36 @@ pub struct DatabaseArgs {
         value_parser = value_parser!(SyncMode),
     )]
     pub sync_mode: Option<SyncMode>,
+    /// Minimum free disk space (e.g., 5GB, 500MB), 0 to disable the check.
+    ///
+    /// The free space of the volumes of the database and the static files is checked
+    /// periodically. If it drops below this threshold, the pipeline is paused and the node shuts
+    /// down gracefully instead of failing a write when the disk is full.
+    ///
+    /// The default value is 5GB.
+    #[arg(long = "db.min-free-space", value_parser = parse_byte_size)]
+    pub min_free_space: Option<usize>,
+    /// Apply the pending destructive database migrations on startup.
+    ///
+    /// Migrations that only add data are always applied on startup. Without this flag, the node
+    /// refuses to start if a migration that rewrites existing data is pending, which can be
+    /// applied with `reth db migrate`.
+    #[arg(long = "db.auto-migrate")]
+    pub auto_migrate: bool,
 }
 
 impl DatabaseArgs {
+    /// Default minimum free disk space, see [`Self::min_free_space`].
+    pub const DEFAULT_MIN_FREE_SPACE: usize = 5 * GIGABYTE;
+
+    /// Returns the minimum free disk space in bytes, or `None` if the check is disabled.
+    pub fn min_free_space(&self) -> Option<u64> {
+        match self.min_free_space.unwrap_or(Self::DEFAULT_MIN_FREE_SPACE) {
+            0 => None,
+            bytes => Some(bytes as u64),
+        }
+    }
+
     /// Returns default database arguments with configured log level and client version.
     pub fn database_args(&self) -> reth_db::mdbx::DatabaseArguments {
         self.get_database_args(default_client_version())
@@ -252,6 +279,19 @@ mod tests {
         assert_eq!(cmd.args.growth_step, Some(GIGABYTE * 4));
     }
 
+    #[test]
+    fn test_command_parser_with_min_free_space() {
+        let args = CommandParser::<DatabaseArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.min_free_space(), Some(5 * GIGABYTE as u64));
+
+        let cmd =
+            CommandParser::<DatabaseArgs>::parse_from(["reth", "--db.min-free-space", "500MB"]);
+        assert_eq!(cmd.args.min_free_space(), Some(500 * MEGABYTE as u64));
+
+        let cmd = CommandParser::<DatabaseArgs>::parse_from(["reth", "--db.min-free-space", "0"]);
+        assert_eq!(cmd.args.min_free_space(), None);
+    }
+
     #[test]
     fn test_command_parser_with_invalid_growth_step() {
         let result =
@@ -26,6 +26,13 @@ pub struct DebugArgs {
     #[arg(long = "debug.max-block", help_heading = "Debug")]
     pub max_block: Option<u64>,
 
+    /// Runs the sync only up to the specified block, and keeps the node running afterwards.
+    ///
+    /// Engine API messages that would advance the chain past the block are answered with
+    /// `SYNCING`.
+    #[arg(long = "debug.terminate-at-block", help_heading = "Debug", conflicts_with = "max_block")]
+    pub terminate_at_block: Option<u64>,
+
     /// Runs a fake consensus client that advances the chain using recent block hashes
     /// on Etherscan. If specified, requires an `ETHERSCAN_API_KEY` environment variable.
     #[arg(
@@ -116,6 +123,7 @@ impl Default for DebugArgs {
             terminate: false,
             tip: None,
             max_block: None,
+            terminate_at_block: None,
             etherscan: None,
             rpc_consensus_url: None,
             skip_fcu: None,
@@ -357,6 +365,26 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn test_parse_terminate_at_block() {
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.terminate-at-block",
+            "17000000",
+        ])
+        .args;
+        assert_eq!(args, DebugArgs { terminate_at_block: Some(17_000_000), ..Default::default() });
+
+        let conflicting = CommandParser::<DebugArgs>::try_parse_from([
+            "reth",
+            "--debug.terminate-at-block",
+            "10",
+            "--debug.max-block",
+            "10",
+        ]);
+        assert!(conflicting.is_err());
+    }
+
     #[test]
     fn test_parse_invalid_block_args_none() {
         let expected_args = DebugArgs {
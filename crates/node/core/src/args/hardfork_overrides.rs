@@ -0,0 +1,255 @@
+//! clap [Args](clap::Args) for overriding hardfork activations
+
+use clap::Args;
+use reth_chainspec::ChainSpec;
+use reth_ethereum_forks::{EthereumHardfork, ForkCondition};
+
+/// Timestamp based hardforks in the order they must activate.
+const TIMESTAMP_HARDFORKS: [EthereumHardfork; 9] = [
+    EthereumHardfork::Shanghai,
+    EthereumHardfork::Cancun,
+    EthereumHardfork::Prague,
+    EthereumHardfork::Osaka,
+    EthereumHardfork::Bpo1,
+    EthereumHardfork::Bpo2,
+    EthereumHardfork::Bpo3,
+    EthereumHardfork::Bpo4,
+    EthereumHardfork::Bpo5,
+];
+
+/// Parameters for overriding the activation of hardforks that are not finalized yet.
+///
+/// This is intended for devnets and pre-release testnets that frequently change activation
+/// timestamps.
+#[derive(Debug, Args, PartialEq, Eq, Default, Clone, Copy)]
+#[command(next_help_heading = "Overrides")]
+pub struct HardforkOverrideArgs {
+    /// Manually specify the Prague fork timestamp, overriding the bundled setting.
+    #[arg(long = "override.prague", value_name = "TIMESTAMP")]
+    pub prague: Option<u64>,
+
+    /// Manually specify the Osaka fork timestamp, overriding the bundled setting.
+    #[arg(long = "override.osaka", value_name = "TIMESTAMP")]
+    pub osaka: Option<u64>,
+
+    /// Manually specify the BPO1 fork timestamp, overriding the bundled setting.
+    #[arg(long = "override.bpo1", value_name = "TIMESTAMP")]
+    pub bpo1: Option<u64>,
+
+    /// Manually specify the BPO2 fork timestamp, overriding the bundled setting.
+    #[arg(long = "override.bpo2", value_name = "TIMESTAMP")]
+    pub bpo2: Option<u64>,
+
+    /// Manually specify the BPO3 fork timestamp, overriding the bundled setting.
+    #[arg(long = "override.bpo3", value_name = "TIMESTAMP")]
+    pub bpo3: Option<u64>,
+
+    /// Manually specify the BPO4 fork timestamp, overriding the bundled setting.
+    #[arg(long = "override.bpo4", value_name = "TIMESTAMP")]
+    pub bpo4: Option<u64>,
+
+    /// Manually specify the BPO5 fork timestamp, overriding the bundled setting.
+    #[arg(long = "override.bpo5", value_name = "TIMESTAMP")]
+    pub bpo5: Option<u64>,
+}
+
+impl HardforkOverrideArgs {
+    /// Returns all configured overrides as `(hardfork, timestamp)` pairs.
+    pub fn overrides(&self) -> Vec<(EthereumHardfork, u64)> {
+        [
+            (EthereumHardfork::Prague, self.prague),
+            (EthereumHardfork::Osaka, self.osaka),
+            (EthereumHardfork::Bpo1, self.bpo1),
+            (EthereumHardfork::Bpo2, self.bpo2),
+            (EthereumHardfork::Bpo3, self.bpo3),
+            (EthereumHardfork::Bpo4, self.bpo4),
+            (EthereumHardfork::Bpo5, self.bpo5),
+        ]
+        .into_iter()
+        .filter_map(|(fork, timestamp)| timestamp.map(|timestamp| (fork, timestamp)))
+        .collect()
+    }
+
+    /// Returns `true` if no override is configured.
+    pub fn is_empty(&self) -> bool {
+        self.overrides().is_empty()
+    }
+
+    /// Applies the overrides to the given [`ChainSpec`].
+    ///
+    /// Since the fork id and the validation of blocks are derived from the hardforks of the chain
+    /// spec, the overrides are reflected in both.
+    ///
+    /// Returns an error if, after applying the overrides, a timestamp based hardfork would
+    /// activate before one of its predecessors.
+    pub fn apply(&self, chain_spec: &mut ChainSpec) -> Result<(), HardforkOverrideError> {
+        for (fork, timestamp) in self.overrides() {
+            chain_spec.hardforks.insert(fork, ForkCondition::Timestamp(timestamp));
+        }
+
+        let mut previous: Option<(EthereumHardfork, u64)> = None;
+        for fork in TIMESTAMP_HARDFORKS {
+            let ForkCondition::Timestamp(timestamp) = chain_spec.hardforks.fork(fork) else {
+                continue
+            };
+
+            if let Some((previous_fork, previous_timestamp)) = previous &&
+                timestamp < previous_timestamp
+            {
+                return Err(HardforkOverrideError::OutOfOrder {
+                    fork,
+                    timestamp,
+                    previous_fork,
+                    previous_timestamp,
+                })
+            }
+            previous = Some((fork, timestamp));
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when the hardfork overrides result in an invalid chain spec.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum HardforkOverrideError {
+    /// A hardfork activates before a hardfork that must precede it.
+    #[error(
+        "{fork:?} at timestamp {timestamp} would activate before {previous_fork:?} at timestamp {previous_timestamp}"
+    )]
+    OutOfOrder {
+        /// The hardfork that activates too early.
+        fork: EthereumHardfork,
+        /// The activation timestamp of the hardfork.
+        timestamp: u64,
+        /// The hardfork that must activate first.
+        previous_fork: EthereumHardfork,
+        /// The activation timestamp of the preceding hardfork.
+        previous_timestamp: u64,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
+    use alloy_primitives::B256;
+    use clap::Parser;
+    use reth_chainspec::{EthereumHardforks, Hardforks, MAINNET};
+    use reth_consensus::{ConsensusError, HeaderValidator};
+    use reth_ethereum_consensus::EthBeaconConsensus;
+    use reth_primitives_traits::SealedHeader;
+    use std::sync::Arc;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn parse_overrides() {
+        let args = CommandParser::<HardforkOverrideArgs>::parse_from([
+            "reth",
+            "--override.osaka",
+            "100",
+            "--override.bpo1",
+            "200",
+        ])
+        .args;
+        assert_eq!(
+            args.overrides(),
+            vec![(EthereumHardfork::Osaka, 100), (EthereumHardfork::Bpo1, 200)]
+        );
+    }
+
+    #[test]
+    fn override_changes_activation_and_fork_id() {
+        let mut spec = (**MAINNET).clone();
+        for fork in &TIMESTAMP_HARDFORKS[3..] {
+            spec.hardforks.remove(fork);
+        }
+        let fork_id_before = spec.latest_fork_id();
+
+        let timestamp = 2_000_000_000;
+        HardforkOverrideArgs { osaka: Some(timestamp), ..Default::default() }
+            .apply(&mut spec)
+            .unwrap();
+
+        assert!(!spec.is_osaka_active_at_timestamp(timestamp - 1));
+        assert!(spec.is_osaka_active_at_timestamp(timestamp));
+        assert_ne!(spec.latest_fork_id(), fork_id_before);
+    }
+
+    #[test]
+    fn override_changes_header_validation() {
+        let mut spec = (**MAINNET).clone();
+        for fork in &TIMESTAMP_HARDFORKS[2..] {
+            spec.hardforks.remove(fork);
+        }
+
+        let timestamp = 2_000_000_000;
+        HardforkOverrideArgs { prague: Some(timestamp), ..Default::default() }
+            .apply(&mut spec)
+            .unwrap();
+        let consensus = EthBeaconConsensus::new(Arc::new(spec));
+
+        // A Cancun header without the requests hash of Prague
+        let header = |timestamp| {
+            SealedHeader::seal_slow(Header {
+                number: 30_000_000,
+                timestamp,
+                gas_limit: 30_000_000,
+                base_fee_per_gas: Some(1),
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                withdrawals_root: Some(EMPTY_ROOT_HASH),
+                blob_gas_used: Some(0),
+                excess_blob_gas: Some(0),
+                parent_beacon_block_root: Some(B256::ZERO),
+                ..Default::default()
+            })
+        };
+        assert!(consensus.validate_header(&header(timestamp - 1)).is_ok());
+        assert!(matches!(
+            consensus.validate_header(&header(timestamp)),
+            Err(ConsensusError::RequestsHashMissing)
+        ));
+    }
+
+    #[test]
+    fn overrides_keep_fork_order() {
+        // Osaka activates together with BPO1, but still before it
+        let mut spec = (**MAINNET).clone();
+        let ForkCondition::Timestamp(bpo1) = spec.hardforks.fork(EthereumHardfork::Bpo1) else {
+            panic!("BPO1 is not scheduled on mainnet")
+        };
+        HardforkOverrideArgs { osaka: Some(bpo1), ..Default::default() }.apply(&mut spec).unwrap();
+
+        let forks = spec.forks_iter().map(|(fork, _)| fork.name()).collect::<Vec<_>>();
+        let position = |fork: EthereumHardfork| forks.iter().position(|name| *name == fork.name());
+        assert!(position(EthereumHardfork::Osaka) < position(EthereumHardfork::Bpo1));
+    }
+
+    #[test]
+    fn reject_out_of_order_overrides() {
+        let mut spec = (**MAINNET).clone();
+        let err = HardforkOverrideArgs {
+            osaka: Some(2_000_000_000),
+            bpo1: Some(1_900_000_000),
+            ..Default::default()
+        }
+        .apply(&mut spec)
+        .unwrap_err();
+
+        assert_eq!(
+            err,
+            HardforkOverrideError::OutOfOrder {
+                fork: EthereumHardfork::Bpo1,
+                timestamp: 1_900_000_000,
+                previous_fork: EthereumHardfork::Osaka,
+                previous_timestamp: 2_000_000_000,
+            }
+        );
+    }
+}
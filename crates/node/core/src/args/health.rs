@@ -0,0 +1,81 @@
+//! clap [Args](clap::Args) for the health check server
+
+use clap::Args;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+/// Default maximum number of blocks that a ready node may be behind the tip.
+pub const DEFAULT_HEALTH_READY_DISTANCE: u64 = 16;
+
+/// Parameters for the plain HTTP health check server, which is independent of JSON-RPC.
+///
+/// The server serves `GET /health`, `GET /ready` and `GET /status`, and is disabled unless a port
+/// is set.
+#[derive(Debug, Args, PartialEq, Eq, Clone, Copy)]
+#[command(next_help_heading = "Health")]
+pub struct HealthArgs {
+    /// Address of the health check server.
+    #[arg(id = "health.addr", long = "health.addr", value_name = "ADDR", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub health_addr: IpAddr,
+
+    /// Enable the health check server on the given port.
+    #[arg(id = "health.port", long = "health.port", value_name = "PORT")]
+    pub health_port: Option<u16>,
+
+    /// Maximum number of blocks that the node may be behind the highest known block for
+    /// `GET /ready` to succeed.
+    #[arg(
+        id = "health.ready-distance",
+        long = "health.ready-distance",
+        value_name = "BLOCKS",
+        default_value_t = DEFAULT_HEALTH_READY_DISTANCE
+    )]
+    pub health_ready_distance: u64,
+}
+
+impl HealthArgs {
+    /// Returns the address of the health check server, if it's enabled.
+    pub fn socket_addr(&self) -> Option<SocketAddr> {
+        self.health_port.map(|port| SocketAddr::new(self.health_addr, port))
+    }
+}
+
+impl Default for HealthArgs {
+    fn default() -> Self {
+        Self {
+            health_addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            health_port: None,
+            health_ready_distance: DEFAULT_HEALTH_READY_DISTANCE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_health_args() {
+        let args = CommandParser::<HealthArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, HealthArgs::default());
+        assert_eq!(args.socket_addr(), None);
+
+        let args = CommandParser::<HealthArgs>::parse_from([
+            "reth",
+            "--health.addr",
+            "0.0.0.0",
+            "--health.port",
+            "8080",
+        ])
+        .args;
+        assert_eq!(args.socket_addr(), Some("0.0.0.0:8080".parse().unwrap()));
+        assert_eq!(args.health_ready_distance, DEFAULT_HEALTH_READY_DISTANCE);
+    }
+}
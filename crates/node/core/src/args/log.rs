@@ -15,6 +15,13 @@ const MB_TO_BYTES: u64 = 1024 * 1024;
 #[derive(Debug, Args)]
 #[command(next_help_heading = "Logging")]
 pub struct LogArgs {
+    /// The format to use for logs written to stdout and the log file.
+    ///
+    /// Overrides `--log.stdout.format` and `--log.file.format`. Logs written to journald are not
+    /// affected.
+    #[arg(long = "log.format", value_name = "FORMAT", global = true)]
+    pub log_format: Option<LogFormat>,
+
     /// The format to use for logs written to stdout.
     #[arg(long = "log.stdout.format", value_name = "FORMAT", global = true, default_value_t = LogFormat::Terminal)]
     pub log_stdout_format: LogFormat,
@@ -23,12 +30,26 @@ pub struct LogArgs {
     #[arg(long = "log.stdout.filter", value_name = "FILTER", global = true, default_value = "")]
     pub log_stdout_filter: String,
 
+    /// The minimum level of logs written to stdout, instead of the level set by `--verbosity`.
+    ///
+    /// `--quiet` still silences all log output.
+    #[arg(long = "log.stdout.level", value_name = "LEVEL", global = true)]
+    pub log_stdout_level: Option<LogLevel>,
+
     /// The format to use for logs written to the log file.
     #[arg(long = "log.file.format", value_name = "FORMAT", global = true, default_value_t = LogFormat::Terminal)]
     pub log_file_format: LogFormat,
 
     /// The filter to use for logs written to the log file.
-    #[arg(long = "log.file.filter", value_name = "FILTER", global = true, default_value = "debug")]
+    ///
+    /// `--log.filter` is accepted as a deprecated alias.
+    #[arg(
+        long = "log.file.filter",
+        alias = "log.filter",
+        value_name = "FILTER",
+        global = true,
+        default_value = "debug"
+    )]
     pub log_file_filter: String,
 
     /// The path to put log files in.
@@ -61,6 +82,10 @@ pub struct LogArgs {
     )]
     pub journald_filter: String,
 
+    /// Measure the time spent logging and report it as metrics.
+    #[arg(long = "log.timing", global = true)]
+    pub timing: bool,
+
     /// Sets whether or not the formatter emits ANSI terminal escape codes for colors and other
     /// text formatting.
     #[arg(
@@ -78,15 +103,32 @@ pub struct LogArgs {
 
 impl LogArgs {
     /// Creates a [`LayerInfo`] instance.
-    fn layer_info(&self, format: LogFormat, filter: String, use_color: bool) -> LayerInfo {
+    fn layer_info(
+        &self,
+        format: LogFormat,
+        directive: Directive,
+        filter: String,
+        use_color: bool,
+    ) -> LayerInfo {
         LayerInfo::new(
             format,
-            self.verbosity.directive().to_string(),
+            directive.to_string(),
             filter,
             use_color.then(|| self.color.to_string()),
         )
     }
 
+    /// Returns the default directive of the logs written to stdout, which is the level of
+    /// `--log.stdout.level` if set, and of the verbosity otherwise.
+    ///
+    /// `--quiet` silences the logs either way.
+    pub fn stdout_directive(&self) -> Directive {
+        match self.log_stdout_level {
+            Some(level) if !self.verbosity.quiet => Level::from(level).into(),
+            _ => self.verbosity.directive(),
+        }
+    }
+
     /// File info from the current log options.
     fn file_info(&self) -> FileInfo {
         FileInfo::new(
@@ -114,9 +156,15 @@ impl LogArgs {
         &self,
         layers: Layers,
     ) -> eyre::Result<Option<FileWorkerGuard>> {
-        let mut tracer = RethTracer::new();
+        let mut tracer = RethTracer::new().with_timing(self.timing);
 
-        let stdout = self.layer_info(self.log_stdout_format, self.log_stdout_filter.clone(), true);
+        let stdout_format = self.log_format.unwrap_or(self.log_stdout_format);
+        let stdout = self.layer_info(
+            stdout_format,
+            self.stdout_directive(),
+            self.log_stdout_filter.clone(),
+            true,
+        );
         tracer = tracer.with_stdout(stdout);
 
         if self.journald {
@@ -125,7 +173,13 @@ impl LogArgs {
 
         if self.log_file_max_files > 0 {
             let info = self.file_info();
-            let file = self.layer_info(self.log_file_format, self.log_file_filter.clone(), false);
+            let file_format = self.log_format.unwrap_or(self.log_file_format);
+            let file = self.layer_info(
+                file_format,
+                self.verbosity.directive(),
+                self.log_file_filter.clone(),
+                false,
+            );
             tracer = tracer.with_file(file, info);
         }
 
@@ -139,7 +193,7 @@ impl LogArgs {
 pub enum ColorMode {
     /// Colors on
     Always,
-    /// Auto-detect
+    /// Auto-detect, colors are used if stdout is a terminal and `NO_COLOR` is not set
     Auto,
     /// Colors off
     Never,
@@ -155,6 +209,33 @@ impl Display for ColorMode {
     }
 }
 
+/// A minimum log level.
+#[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
+pub enum LogLevel {
+    /// Errors
+    Error,
+    /// Warnings
+    Warn,
+    /// Info
+    Info,
+    /// Debug
+    Debug,
+    /// Traces
+    Trace,
+}
+
+impl From<LogLevel> for Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => Self::ERROR,
+            LogLevel::Warn => Self::WARN,
+            LogLevel::Info => Self::INFO,
+            LogLevel::Debug => Self::DEBUG,
+            LogLevel::Trace => Self::TRACE,
+        }
+    }
+}
+
 /// The verbosity settings for the cli.
 #[derive(Debug, Copy, Clone, Args)]
 #[command(next_help_heading = "Display")]
@@ -175,21 +256,123 @@ pub struct Verbosity {
 }
 
 impl Verbosity {
-    /// Get the corresponding [Directive] for the given verbosity, or none if the verbosity
-    /// corresponds to silent.
+    /// Get the corresponding [Directive] for the given verbosity, which is off if silenced.
+    ///
+    /// Silencing wins over any verbosity, and a verbosity of zero is treated like `-v`.
     pub fn directive(&self) -> Directive {
         if self.quiet {
-            LevelFilter::OFF.into()
-        } else {
-            let level = match self.verbosity - 1 {
-                0 => Level::ERROR,
-                1 => Level::WARN,
-                2 => Level::INFO,
-                3 => Level::DEBUG,
-                _ => Level::TRACE,
-            };
-
-            level.into()
+            return LevelFilter::OFF.into()
+        }
+
+        let level = match self.verbosity.saturating_sub(1) {
+            0 => Level::ERROR,
+            1 => Level::WARN,
+            2 => Level::INFO,
+            3 => Level::DEBUG,
+            _ => Level::TRACE,
+        };
+        level.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    fn directives(filter: &str) -> Vec<Directive> {
+        filter.split(',').map(|directive| directive.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn parse_separate_filters() {
+        let args = CommandParser::<LogArgs>::parse_from([
+            "reth",
+            "--log.file.filter",
+            "reth_downloaders=trace,info",
+            "--log.stdout.filter",
+            "reth_stages=debug",
+        ])
+        .args;
+        assert_eq!(args.log_file_filter, "reth_downloaders=trace,info");
+        assert_eq!(args.log_stdout_filter, "reth_stages=debug");
+        assert_eq!(directives(&args.log_file_filter).len(), 2);
+        assert_eq!(directives(&args.log_stdout_filter).len(), 1);
+
+        // The verbosity is the default directive of the stdout layer, not a replacement for its
+        // filter.
+        assert_eq!(args.verbosity.directive(), Level::INFO.into());
+    }
+
+    #[test]
+    fn parse_deprecated_filter_alias() {
+        let args = CommandParser::<LogArgs>::parse_from([
+            "reth",
+            "--log.filter",
+            "reth_downloaders=trace",
+        ])
+        .args;
+        assert_eq!(args.log_file_filter, "reth_downloaders=trace");
+        assert_eq!(args.log_stdout_filter, "");
+    }
+
+    #[test]
+    fn parse_log_format() {
+        let args = CommandParser::<LogArgs>::parse_from(["reth", "--log.format", "json"]).args;
+        assert_eq!(args.log_format, Some(LogFormat::Json));
+
+        let args = CommandParser::<LogArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.log_format, None);
+    }
+
+    #[test]
+    fn verbosity_directive() {
+        let directive = |verbosity, quiet| Verbosity { verbosity, quiet }.directive();
+        assert_eq!(directive(0, false), Level::ERROR.into());
+        assert_eq!(directive(1, false), Level::ERROR.into());
+        assert_eq!(directive(2, false), Level::WARN.into());
+        assert_eq!(directive(3, false), Level::INFO.into());
+        assert_eq!(directive(4, false), Level::DEBUG.into());
+        assert_eq!(directive(5, false), Level::TRACE.into());
+        assert_eq!(directive(6, false), Level::TRACE.into());
+        for verbosity in [0, 1, 5, u8::MAX] {
+            assert_eq!(directive(verbosity, true), LevelFilter::OFF.into());
         }
     }
+
+    #[test]
+    fn parse_verbosity() {
+        let args = CommandParser::<LogArgs>::parse_from(["reth", "-q"]).args;
+        assert_eq!(args.stdout_directive(), LevelFilter::OFF.into());
+
+        // Silencing wins over the verbosity
+        let args = CommandParser::<LogArgs>::parse_from(["reth", "-q", "-vvvv"]).args;
+        assert_eq!(args.verbosity.directive(), LevelFilter::OFF.into());
+
+        let args = CommandParser::<LogArgs>::parse_from(["reth", "-vvvv"]).args;
+        assert_eq!(args.stdout_directive(), Level::DEBUG.into());
+    }
+
+    #[test]
+    fn parse_stdout_level() {
+        let args =
+            CommandParser::<LogArgs>::parse_from(["reth", "-vvvvv", "--log.stdout.level", "warn"])
+                .args;
+        assert_eq!(args.log_stdout_level, Some(LogLevel::Warn));
+        assert_eq!(args.stdout_directive(), Level::WARN.into());
+        // The level only applies to stdout
+        assert_eq!(args.verbosity.directive(), Level::TRACE.into());
+
+        let args =
+            CommandParser::<LogArgs>::parse_from(["reth", "-q", "--log.stdout.level", "trace"])
+                .args;
+        assert_eq!(args.stdout_directive(), LevelFilter::OFF.into());
+    }
 }
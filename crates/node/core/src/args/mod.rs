@@ -22,11 +22,11 @@ pub use database::DatabaseArgs;
 
 /// LogArgs struct for configuring the logger
 mod log;
-pub use log::{ColorMode, LogArgs, Verbosity};
+pub use log::{ColorMode, LogArgs, LogLevel, Verbosity};
 
 /// `TraceArgs` for tracing and spans support
 mod trace;
-pub use trace::{OtlpInitStatus, TraceArgs};
+pub use trace::{OtlpGuard, OtlpInitStatus, TraceArgs};
 
 /// `MetricArgs` to configure metrics.
 mod metric;
@@ -80,5 +80,17 @@ pub use era::{DefaultEraHost, EraArgs, EraSourceArgs};
 mod static_files;
 pub use static_files::StaticFilesArgs;
 
+/// `HealthArgs` for configuring the health check server.
+mod health;
+pub use health::{HealthArgs, DEFAULT_HEALTH_READY_DISTANCE};
+
+/// `BlockingTaskArgs` for limiting the blocking tasks of each kind of work.
+mod blocking_tasks;
+pub use blocking_tasks::BlockingTaskArgs;
+
+/// `HardforkOverrideArgs` for overriding hardfork activations.
+mod hardfork_overrides;
+pub use hardfork_overrides::{HardforkOverrideArgs, HardforkOverrideError};
+
 mod error;
 pub mod types;
@@ -6,12 +6,15 @@ use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     ops::Not,
     path::PathBuf,
+    time::Duration,
 };
 
 use crate::version::version_metadata;
 use clap::Args;
 use reth_chainspec::EthChainSpec;
-use reth_cli_util::{get_secret_key, load_secret_key::SecretKeyError};
+use reth_cli_util::{
+    get_secret_key, load_secret_key::SecretKeyError, parse_duration_from_secs_or_ms,
+};
 use reth_config::Config;
 use reth_discv4::{NodeRecord, DEFAULT_DISCOVERY_ADDR, DEFAULT_DISCOVERY_PORT};
 use reth_discv5::{
@@ -62,6 +65,33 @@ pub struct NetworkArgs {
     #[arg(long)]
     pub trusted_only: bool,
 
+    #[expect(clippy::doc_markdown)]
+    /// Comma separated enode URLs of static peers, which are always kept connected.
+    ///
+    /// Static peers are redialed when the session ends or a dial fails, and connected even if
+    /// all peer slots are taken.
+    ///
+    /// --p2p.static-peers enode://abcd@192.168.0.1:30303
+    #[arg(long = "p2p.static-peers", value_name = "ENODES", value_delimiter = ',')]
+    pub static_peers: Vec<TrustedPeer>,
+
+    /// The backoff before redialing a static peer, which doubles with every attempt since the
+    /// last session with the peer. default: 1s
+    #[arg(
+        long = "p2p.static-reconnect-min",
+        value_name = "DURATION",
+        value_parser = parse_duration_from_secs_or_ms
+    )]
+    pub static_reconnect_min: Option<Duration>,
+
+    /// The maximum backoff between the attempts to redial a static peer. default: 60s
+    #[arg(
+        long = "p2p.static-reconnect-max",
+        value_name = "DURATION",
+        value_parser = parse_duration_from_secs_or_ms
+    )]
+    pub static_reconnect_max: Option<Duration>,
+
     /// Comma separated enode URLs for P2P discovery bootstrap.
     ///
     /// Will fall back to a network-specific default if not specified.
@@ -72,8 +102,9 @@ pub struct NetworkArgs {
     #[arg(long, default_value_t = 0)]
     pub dns_retries: usize,
 
-    /// The path to the known peers file. Connected peers are dumped to this file on nodes
-    /// shutdown, and read on startup. Cannot be used with `--no-persist-peers`.
+    /// The path to the known peers file. The known peers, with their reputation, and the
+    /// banned peers are written to this file periodically and on node shutdown, and read on
+    /// startup. Cannot be used with `--no-persist-peers`.
     #[arg(long, value_name = "FILE", verbatim_doc_comment, conflicts_with = "no_persist_peers")]
     pub peers_file: Option<PathBuf>,
 
@@ -327,13 +358,15 @@ impl NetworkArgs {
 
         // Configure peer connections
         let ip_filter = self.ip_filter().unwrap_or_default();
-        let peers_config = config
+        let mut peers_config = config
             .peers_config_with_basic_nodes_from_file(
                 self.persistent_peers_file(peers_file).as_deref(),
             )
             .with_max_inbound_opt(self.resolved_max_inbound_peers())
             .with_max_outbound_opt(self.resolved_max_outbound_peers())
+            .with_static_reconnect_backoff_opt(self.static_reconnect_min, self.static_reconnect_max)
             .with_ip_filter(ip_filter);
+        peers_config.static_nodes.extend(self.static_peers.clone());
 
         // Configure basic network stack
         NetworkConfigBuilder::<N>::new(secret_key)
@@ -462,6 +495,9 @@ impl Default for NetworkArgs {
             discovery: DiscoveryArgs::default(),
             trusted_peers: vec![],
             trusted_only: false,
+            static_peers: vec![],
+            static_reconnect_min: None,
+            static_reconnect_max: None,
             bootnodes: None,
             dns_retries: 0,
             peers_file: None,
@@ -715,6 +751,7 @@ mod tests {
     use clap::Parser;
     use reth_chainspec::MAINNET;
     use reth_config::Config;
+    use reth_network::types::peers::config::StaticReconnectBackoff;
     use reth_network_peers::NodeRecord;
     use secp256k1::SecretKey;
     use std::{
@@ -777,6 +814,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_static_peer_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--p2p.static-peers",
+            "enode://d860a01f9722d78051619d1e2351aba3f43f943f6f00718d1b9baa4101932a1f5011f16bb2b1bb35db20d6fe28fa0bf09636d26a87d31de9ec6203eeedb1f666@18.138.108.67:30303",
+            "--p2p.static-reconnect-min",
+            "500ms",
+            "--p2p.static-reconnect-max",
+            "30s",
+        ])
+        .args;
+        assert_eq!(
+            args.static_peers,
+            vec!["enode://d860a01f9722d78051619d1e2351aba3f43f943f6f00718d1b9baa4101932a1f5011f16bb2b1bb35db20d6fe28fa0bf09636d26a87d31de9ec6203eeedb1f666@18.138.108.67:30303".parse().unwrap()]
+        );
+
+        let secret_key = SecretKey::from_byte_array(&[1u8; 32]).unwrap();
+        let net_cfg = args
+            .network_config::<reth_network::EthNetworkPrimitives>(
+                &Config::default(),
+                MAINNET.clone(),
+                secret_key,
+                PathBuf::new(),
+            )
+            .build_with_noop_provider(MAINNET.clone());
+        assert_eq!(net_cfg.peers_config.static_nodes, args.static_peers);
+        assert_eq!(
+            net_cfg.peers_config.static_reconnect_backoff,
+            StaticReconnectBackoff {
+                min: Duration::from_millis(500),
+                max: Duration::from_secs(30)
+            }
+        );
+    }
+
     #[test]
     fn parse_retry_strategy_args() {
         let tests = vec![0, 10];
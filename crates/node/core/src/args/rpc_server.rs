@@ -541,6 +541,9 @@ pub struct RpcServerArgs {
     /// Blocking IO requests include `eth_call`, `eth_estimateGas`, and similar methods that
     /// require EVM execution. These are spawned as blocking tasks to avoid blocking the async
     /// runtime.
+    ///
+    /// This also limits all blocking tasks of RPC requests, separately from the blocking tasks of
+    /// the sync and of background maintenance.
     #[arg(long = "rpc.max-blocking-io-requests", alias = "rpc-max-blocking-io-requests", value_name = "COUNT", default_value_t = DefaultRpcServerArgs::get_global().rpc_max_blocking_io_requests)]
     pub rpc_max_blocking_io_requests: usize,
 
@@ -3,6 +3,7 @@
 use clap::Parser;
 use eyre::WrapErr;
 use reth_tracing::{tracing_subscriber::EnvFilter, Layers};
+pub use reth_tracing_otlp::OtlpGuard;
 use reth_tracing_otlp::OtlpProtocol;
 use url::Url;
 
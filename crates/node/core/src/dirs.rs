@@ -320,11 +320,12 @@ impl<D> ChainPath<D> {
         self.data_dir().join("discovery-secret")
     }
 
-    /// Returns the path to the known peers file for this chain.
+    /// Returns the path to the known peers file for this chain, with the peers and the
+    /// temporary bans of the peer set.
     ///
-    /// `<DIR>/<CHAIN_ID>/known-peers.json`
+    /// `<DIR>/<CHAIN_ID>/peers.json`
     pub fn known_peers(&self) -> PathBuf {
-        self.data_dir().join("known-peers.json")
+        self.data_dir().join("peers.json")
     }
 
     /// Returns the path to the blobstore directory for this chain where blobs of unfinalized
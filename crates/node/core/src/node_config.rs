@@ -2,8 +2,8 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, StaticFilesArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, HealthArgs, NetworkArgs,
+        PayloadBuilderArgs, PruningArgs, RpcServerArgs, StaticFilesArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     utils::get_single_header,
@@ -150,6 +150,12 @@ pub struct NodeConfig<ChainSpec> {
 
     /// All static files related arguments
     pub static_files: StaticFilesArgs,
+
+    /// All health check server related arguments with --health prefix
+    pub health: HealthArgs,
+
+    /// Whether the node should shut down gracefully once it has finished its initial sync.
+    pub exit_after_sync: bool,
 }
 
 impl NodeConfig<ChainSpec> {
@@ -181,6 +187,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             engine: EngineArgs::default(),
             era: EraArgs::default(),
             static_files: StaticFilesArgs::default(),
+            health: HealthArgs::default(),
+            exit_after_sync: false,
         }
     }
 
@@ -255,6 +263,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             engine,
             era,
             static_files,
+            health,
+            exit_after_sync,
             ..
         } = self;
         NodeConfig {
@@ -274,6 +284,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             engine,
             era,
             static_files,
+            health,
+            exit_after_sync,
         }
     }
 
@@ -283,6 +295,12 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Sets whether the node should shut down gracefully once it has finished its initial sync.
+    pub const fn with_exit_after_sync(mut self, exit_after_sync: bool) -> Self {
+        self.exit_after_sync = exit_after_sync;
+        self
+    }
+
     /// Set the instance for the node
     pub const fn with_instance(mut self, instance: u16) -> Self {
         self.instance = Some(instance);
@@ -363,6 +381,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
     {
         let max_block = if let Some(block) = self.debug.max_block {
             Some(block)
+        } else if let Some(block) = self.debug.terminate_at_block {
+            Some(block)
         } else if let Some(tip) = self.debug.tip {
             Some(self.lookup_or_fetch_tip(provider, network_client, tip).await?)
         } else {
@@ -544,6 +564,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             engine: self.engine,
             era: self.era,
             static_files: self.static_files,
+            health: self.health,
+            exit_after_sync: self.exit_after_sync,
         }
     }
 
@@ -585,6 +607,8 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             engine: self.engine.clone(),
             era: self.era.clone(),
             static_files: self.static_files,
+            health: self.health,
+            exit_after_sync: self.exit_after_sync,
         }
     }
 }
@@ -35,6 +35,9 @@ pub struct RethCliVersionConsts {
     /// The 8 character short SHA of the latest commit.
     pub vergen_git_sha: Cow<'static, str>,
 
+    /// Whether the working tree had uncommitted changes, `true` or `false`.
+    pub vergen_git_dirty: Cow<'static, str>,
+
     /// The build timestamp.
     pub vergen_build_timestamp: Cow<'static, str>,
 
@@ -44,6 +47,9 @@ pub struct RethCliVersionConsts {
     /// The build features.
     pub vergen_cargo_features: Cow<'static, str>,
 
+    /// The version of the compiler.
+    pub vergen_rustc_semver: Cow<'static, str>,
+
     /// The short version information for reth.
     pub short_version: Cow<'static, str>,
 
@@ -111,9 +117,11 @@ pub fn default_reth_version_metadata() -> RethCliVersionConsts {
         cargo_pkg_version: Cow::Borrowed(env!("CARGO_PKG_VERSION")),
         vergen_git_sha_long: Cow::Borrowed(env!("VERGEN_GIT_SHA")),
         vergen_git_sha: Cow::Borrowed(env!("VERGEN_GIT_SHA_SHORT")),
+        vergen_git_dirty: Cow::Borrowed(env!("VERGEN_GIT_DIRTY")),
         vergen_build_timestamp: Cow::Borrowed(env!("VERGEN_BUILD_TIMESTAMP")),
         vergen_cargo_target_triple: Cow::Borrowed(env!("VERGEN_CARGO_TARGET_TRIPLE")),
         vergen_cargo_features: Cow::Borrowed(env!("VERGEN_CARGO_FEATURES")),
+        vergen_rustc_semver: Cow::Borrowed(env!("VERGEN_RUSTC_SEMVER")),
         short_version: Cow::Borrowed(env!("RETH_SHORT_VERSION")),
         long_version: Cow::Owned(format!(
             "{}\n{}\n{}\n{}\n{}",
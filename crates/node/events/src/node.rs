@@ -6,12 +6,14 @@ use alloy_primitives::{BlockNumber, B256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use futures::Stream;
 use reth_engine_primitives::{ConsensusEngineEvent, ForkchoiceStatus};
+use reth_metrics::{metrics::Gauge, Metrics};
 use reth_network_api::PeersInfo;
 use reth_primitives_traits::{format_gas, format_gas_throughput, BlockBody, NodePrimitives};
 use reth_prune_types::PrunerEvent;
 use reth_stages::{EntitiesCheckpoint, ExecOutput, PipelineEvent, StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileProducerEvent;
 use std::{
+    collections::HashMap,
     fmt::{Display, Formatter},
     future::Future,
     pin::Pin,
@@ -24,6 +26,9 @@ use tracing::{debug, info, warn};
 /// Interval of reporting node state.
 const INFO_MESSAGE_INTERVAL: Duration = Duration::from_secs(25);
 
+/// Weight of the latest throughput of a stage in its moving average, which the ETA is based on.
+const THROUGHPUT_SMOOTHING_FACTOR: f64 = 0.2;
+
 /// The current high-level state of the node, including the node's database environment, network
 /// connections, current processing stage, and the latest block information. It provides
 /// methods to handle different types of events that affect the node's state, such as pipeline
@@ -43,13 +48,12 @@ struct NodeState {
     finalized_block_hash: Option<B256>,
     /// The time when we last logged a status message
     last_status_log_time: Option<u64>,
+    /// Progress metrics by stage.
+    stage_metrics: HashMap<StageId, StageProgressMetrics>,
 }
 
 impl NodeState {
-    const fn new(
-        peers_info: Option<Box<dyn PeersInfo>>,
-        latest_block: Option<BlockNumber>,
-    ) -> Self {
+    fn new(peers_info: Option<Box<dyn PeersInfo>>, latest_block: Option<BlockNumber>) -> Self {
         Self {
             peers_info,
             current_stage: None,
@@ -58,6 +62,7 @@ impl NodeState {
             safe_block_hash: None,
             finalized_block_hash: None,
             last_status_log_time: None,
+            stage_metrics: HashMap::new(),
         }
     }
 
@@ -83,6 +88,28 @@ impl NodeState {
         CurrentStage { stage_id, eta, checkpoint, entities_checkpoint, target }
     }
 
+    /// Updates the progress metrics of the current stage, so that they match the status logs.
+    fn record_stage_metrics(&mut self) {
+        let Some(current_stage) = &self.current_stage else { return };
+        let metrics = self.stage_metrics.entry(current_stage.stage_id).or_insert_with(|| {
+            StageProgressMetrics::new_with_labels(&[("stage", current_stage.stage_id.to_string())])
+        });
+
+        if let Some(progress) = current_stage.progress() &&
+            progress.total > 0
+        {
+            metrics
+                .progress_percentage
+                .set(100.0 * progress.processed as f64 / progress.total as f64);
+        }
+        if let Some(remaining) = current_stage.eta.remaining() {
+            metrics.eta_seconds.set(remaining.as_secs_f64());
+        }
+        if let Some(per_second) = current_stage.eta.per_second {
+            metrics.entities_per_second.set(per_second);
+        }
+    }
+
     /// Processes an event emitted by the pipeline
     fn handle_pipeline_event(&mut self, event: PipelineEvent) {
         match event {
@@ -104,7 +131,7 @@ impl NodeState {
                 let checkpoint = checkpoint.unwrap_or_default();
                 let current_stage = self.build_current_stage(stage_id, checkpoint, target);
 
-                if let Some(stage_eta) = current_stage.eta.fmt_for_stage(stage_id) {
+                if let Some(stage_eta) = current_stage.eta.fmt_eta() {
                     info!(
                         pipeline_stages = %pipeline_stages_progress,
                         stage = %stage_id,
@@ -137,13 +164,20 @@ impl NodeState {
                 if let Some(current_stage) = self.current_stage.as_mut() {
                     current_stage.checkpoint = checkpoint;
                     current_stage.entities_checkpoint = checkpoint.entities();
-                    current_stage.eta.update(stage_id, checkpoint);
+                    match current_stage.progress() {
+                        Some(progress) => current_stage.eta.update(
+                            stage_id,
+                            progress.processed,
+                            Some(progress.total),
+                        ),
+                        None => current_stage.eta.update(stage_id, checkpoint.block_number, None),
+                    }
 
                     let target = OptionalField(current_stage.target);
-                    let stage_progress = current_stage
-                        .entities_checkpoint
-                        .and_then(|entities| entities.fmt_percentage());
-                    let stage_eta = current_stage.eta.fmt_for_stage(stage_id);
+                    let stage_progress =
+                        current_stage.progress().and_then(|progress| progress.fmt_percentage());
+                    let stage_eta = current_stage.eta.fmt_eta();
+                    let stage_throughput = current_stage.eta.fmt_throughput();
 
                     let message = if done { "Finished stage" } else { "Committed stage progress" };
 
@@ -169,27 +203,30 @@ impl NodeState {
                                 "{message}",
                             )
                         }
-                        (None, Some(stage_eta)) => {
-                            info!(
-                                pipeline_stages = %pipeline_stages_progress,
-                                stage = %stage_id,
-                                checkpoint = %checkpoint.block_number,
-                                %target,
-                                %stage_eta,
-                                "{message}",
-                            )
-                        }
-                        (None, None) => {
-                            info!(
-                                pipeline_stages = %pipeline_stages_progress,
-                                stage = %stage_id,
-                                checkpoint = %checkpoint.block_number,
-                                %target,
-                                "{message}",
-                            )
+                        (None, _) => {
+                            // Without a known total, the throughput shows that the stage advances
+                            if let Some(stage_throughput) = stage_throughput {
+                                info!(
+                                    pipeline_stages = %pipeline_stages_progress,
+                                    stage = %stage_id,
+                                    checkpoint = %checkpoint.block_number,
+                                    %target,
+                                    %stage_throughput,
+                                    "{message}",
+                                )
+                            } else {
+                                info!(
+                                    pipeline_stages = %pipeline_stages_progress,
+                                    stage = %stage_id,
+                                    checkpoint = %checkpoint.block_number,
+                                    %target,
+                                    "{message}",
+                                )
+                            }
                         }
                     }
                 }
+                self.record_stage_metrics();
 
                 if done {
                     self.current_stage = None;
@@ -344,6 +381,19 @@ struct CurrentStage {
     target: Option<BlockNumber>,
 }
 
+impl CurrentStage {
+    /// Returns the progress of the stage from its entities checkpoint, or from its checkpoint and
+    /// target blocks if the stage doesn't report its entities.
+    fn progress(&self) -> Option<EntitiesCheckpoint> {
+        self.entities_checkpoint.or_else(|| {
+            self.target.map(|target| EntitiesCheckpoint {
+                processed: self.checkpoint.block_number,
+                total: target,
+            })
+        })
+    }
+}
+
 /// A node event.
 #[derive(Debug, derive_more::From)]
 pub enum NodeEvent<N: NodePrimitives> {
@@ -401,12 +451,14 @@ where
         let mut this = self.project();
 
         while this.info_interval.poll_tick(cx).is_ready() {
-            if let Some(CurrentStage { stage_id, eta, checkpoint, entities_checkpoint, target }) =
-                &this.state.current_stage
-            {
+            this.state.record_stage_metrics();
+
+            if let Some(current_stage) = &this.state.current_stage {
+                let CurrentStage { stage_id, eta, checkpoint, target, .. } = current_stage;
                 let stage_progress =
-                    entities_checkpoint.and_then(|entities| entities.fmt_percentage());
-                let stage_eta = eta.fmt_for_stage(*stage_id);
+                    current_stage.progress().and_then(|progress| progress.fmt_percentage());
+                let stage_eta = eta.fmt_eta();
+                let stage_throughput = eta.fmt_throughput();
 
                 match (stage_progress, stage_eta) {
                     (Some(stage_progress), Some(stage_eta)) => {
@@ -432,26 +484,27 @@ where
                             "Status"
                         )
                     }
-                    (None, Some(stage_eta)) => {
-                        info!(
-                            target: "reth::cli",
-                            connected_peers = this.state.num_connected_peers(),
-                            stage = %stage_id,
-                            checkpoint = checkpoint.block_number,
-                            target = %OptionalField(*target),
-                            %stage_eta,
-                            "Status"
-                        )
-                    }
-                    (None, None) => {
-                        info!(
-                            target: "reth::cli",
-                            connected_peers = this.state.num_connected_peers(),
-                            stage = %stage_id,
-                            checkpoint = checkpoint.block_number,
-                            target = %OptionalField(*target),
-                            "Status"
-                        )
+                    (None, _) => {
+                        if let Some(stage_throughput) = stage_throughput {
+                            info!(
+                                target: "reth::cli",
+                                connected_peers = this.state.num_connected_peers(),
+                                stage = %stage_id,
+                                checkpoint = checkpoint.block_number,
+                                target = %OptionalField(*target),
+                                %stage_throughput,
+                                "Status"
+                            )
+                        } else {
+                            info!(
+                                target: "reth::cli",
+                                connected_peers = this.state.num_connected_peers(),
+                                stage = %stage_id,
+                                checkpoint = checkpoint.block_number,
+                                target = %OptionalField(*target),
+                                "Status"
+                            )
+                        }
                     }
                 }
             } else {
@@ -509,43 +562,53 @@ where
 /// A container calculating the estimated time that a stage will complete in, based on stage
 /// checkpoints reported by the pipeline.
 ///
-/// One `Eta` is only valid for a single stage.
+/// The ETA is based on an exponential moving average of the throughput of the stage, so that it
+/// doesn't jump with every checkpoint. One `Eta` is only valid for a single stage.
 #[derive(Default, Copy, Clone)]
 struct Eta {
-    /// The last stage checkpoint
-    last_checkpoint: EntitiesCheckpoint,
+    /// The number of processed entities, or the block number, of the last checkpoint
+    last_processed: u64,
     /// The last time the stage reported its checkpoint
     last_checkpoint_time: Option<Instant>,
+    /// The moving average of the number of entities processed per second
+    per_second: Option<f64>,
     /// The current ETA
     eta: Option<Duration>,
 }
 
 impl Eta {
-    /// Update the ETA given the checkpoint, if possible.
-    fn update(&mut self, stage: StageId, checkpoint: StageCheckpoint) {
-        let Some(current) = checkpoint.entities() else { return };
-
+    /// Update the throughput and, if the `total` number of entities is known, the ETA given the
+    /// number of `processed` entities.
+    fn update(&mut self, stage: StageId, processed: u64, total: Option<u64>) {
         if let Some(last_checkpoint_time) = &self.last_checkpoint_time {
-            let Some(processed_since_last) =
-                current.processed.checked_sub(self.last_checkpoint.processed)
-            else {
+            let Some(processed_since_last) = processed.checked_sub(self.last_processed) else {
+                self.per_second = None;
                 self.eta = None;
-                debug!(target: "reth::cli", %stage, ?current, ?self.last_checkpoint, "Failed to calculate the ETA: processed entities is less than the last checkpoint");
+                debug!(target: "reth::cli", %stage, processed, last_processed = self.last_processed, "Failed to calculate the ETA: processed entities is less than the last checkpoint");
                 return
             };
-            let elapsed = last_checkpoint_time.elapsed();
-            let per_second = processed_since_last as f64 / elapsed.as_secs_f64();
 
-            let Some(remaining) = current.total.checked_sub(current.processed) else {
-                self.eta = None;
-                debug!(target: "reth::cli", %stage, ?current, "Failed to calculate the ETA: total entities is less than processed entities");
-                return
-            };
+            let elapsed = last_checkpoint_time.elapsed().as_secs_f64();
+            if elapsed > 0.0 {
+                let current = processed_since_last as f64 / elapsed;
+                self.per_second = Some(self.per_second.map_or(current, |average| {
+                    (current - average).mul_add(THROUGHPUT_SMOOTHING_FACTOR, average)
+                }));
+            }
 
-            self.eta = Duration::try_from_secs_f64(remaining as f64 / per_second).ok();
+            self.eta = match total.map(|total| total.checked_sub(processed)) {
+                Some(Some(remaining)) => self.per_second.and_then(|per_second| {
+                    Duration::try_from_secs_f64(remaining as f64 / per_second).ok()
+                }),
+                Some(None) => {
+                    debug!(target: "reth::cli", %stage, processed, ?total, "Failed to calculate the ETA: total entities is less than processed entities");
+                    None
+                }
+                None => None,
+            };
         }
 
-        self.last_checkpoint = current;
+        self.last_processed = processed;
         self.last_checkpoint_time = Some(Instant::now());
     }
 
@@ -554,22 +617,36 @@ impl Eta {
         self.eta.zip(self.last_checkpoint_time).is_some()
     }
 
-    /// Format ETA for a given stage.
-    ///
-    /// NOTE: Currently ETA is enabled only for the stages that have predictable progress.
-    /// It's not the case for network-dependent ([`StageId::Headers`] and [`StageId::Bodies`]) and
-    /// [`StageId::Execution`] stages.
-    fn fmt_for_stage(&self, stage: StageId) -> Option<String> {
-        if !self.is_available() ||
-            matches!(stage, StageId::Headers | StageId::Bodies | StageId::Execution)
-        {
-            None
-        } else {
-            Some(self.to_string())
-        }
+    /// Returns the remaining time until the stage completes, if the ETA is available.
+    fn remaining(&self) -> Option<Duration> {
+        let (eta, last_checkpoint_time) = self.eta.zip(self.last_checkpoint_time)?;
+        Some(eta.saturating_sub(last_checkpoint_time.elapsed()))
+    }
+
+    /// Format ETA, if it's available.
+    fn fmt_eta(&self) -> Option<String> {
+        self.is_available().then(|| self.to_string())
+    }
+
+    /// Format the throughput of a stage that doesn't know the total number of entities, in blocks
+    /// per second.
+    fn fmt_throughput(&self) -> Option<String> {
+        self.per_second.map(|per_second| format!("{per_second:.2}blocks/s"))
     }
 }
 
+/// Progress metrics of a stage, matching the status logs.
+#[derive(Metrics)]
+#[metrics(scope = "sync")]
+struct StageProgressMetrics {
+    /// The percentage of the entities of the stage that are processed, if the total is known.
+    progress_percentage: Gauge,
+    /// The estimated number of seconds until the stage completes.
+    eta_seconds: Gauge,
+    /// The moving average of the number of entities, or blocks, processed per second.
+    entities_per_second: Gauge,
+}
+
 impl Display for Eta {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if let Some((eta, last_checkpoint_time)) = self.eta.zip(self.last_checkpoint_time) {
@@ -609,4 +686,34 @@ mod tests {
 
         assert_eq!(eta, "13m37s");
     }
+
+    #[test]
+    fn eta_uses_moving_average() {
+        let mut eta = Eta::default();
+        eta.update(StageId::AccountHashing, 0, Some(10_000));
+        assert!(eta.fmt_eta().is_none());
+
+        // 1000 entities per second
+        eta.last_checkpoint_time = Some(Instant::now() - Duration::from_secs(1));
+        eta.update(StageId::AccountHashing, 1_000, Some(10_000));
+        let per_second = eta.per_second.unwrap();
+        assert!((per_second - 1_000.0).abs() < 10.0, "{per_second}");
+        assert!(eta.fmt_eta().is_some());
+
+        // A stall only slows down the average
+        eta.last_checkpoint_time = Some(Instant::now() - Duration::from_secs(1));
+        eta.update(StageId::AccountHashing, 1_000, Some(10_000));
+        let per_second = eta.per_second.unwrap();
+        assert!((per_second - 800.0).abs() < 10.0, "{per_second}");
+        let remaining = eta.remaining().unwrap().as_secs_f64();
+        assert!((remaining - 9_000.0 / 800.0).abs() < 1.0, "{remaining}");
+
+        // Without a total there is only the throughput
+        let mut blocks = Eta::default();
+        blocks.update(StageId::Finish, 10, None);
+        blocks.last_checkpoint_time = Some(Instant::now() - Duration::from_secs(2));
+        blocks.update(StageId::Finish, 20, None);
+        assert!(blocks.fmt_eta().is_none());
+        assert!(blocks.fmt_throughput().unwrap().ends_with("blocks/s"));
+    }
 }
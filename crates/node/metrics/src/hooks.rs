@@ -45,6 +45,7 @@ impl Default for HooksBuilder {
                 Box::new(|| Collector::default().collect()),
                 Box::new(collect_memory_stats),
                 Box::new(collect_io_stats),
+                Box::new(collect_log_timing_stats),
             ],
         }
     }
@@ -164,3 +165,24 @@ fn collect_io_stats() {
 
 #[cfg(not(target_os = "linux"))]
 const fn collect_io_stats() {}
+
+/// Collects the time spent logging, if the node logs with `--log.timing`.
+///
+/// The buckets are cumulative, like the buckets of a Prometheus histogram.
+fn collect_log_timing_stats() {
+    use metrics::{counter, gauge};
+
+    let Some(stats) = reth_tracing::log_timing_stats() else { return };
+    let snapshot = stats.snapshot();
+
+    counter!("tracing.log_timing.events").absolute(snapshot.events);
+    gauge!("tracing.log_timing.total_seconds").set(snapshot.total.as_secs_f64());
+    gauge!("tracing.log_timing.max_seconds").set(snapshot.max.as_secs_f64());
+
+    let bounds = reth_tracing::LOG_TIMING_BUCKETS.map(|bound| bound.as_secs_f64().to_string());
+    let mut cumulative = 0;
+    for (bound, events) in bounds.into_iter().chain(["+Inf".to_string()]).zip(snapshot.buckets) {
+        cumulative += events;
+        counter!("tracing.log_timing.events_bucket", "le" => bound).absolute(cumulative);
+    }
+}
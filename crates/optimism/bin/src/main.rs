@@ -1,6 +1,5 @@
 #![allow(missing_docs, rustdoc::missing_crate_level_docs)]
 
-use clap::Parser;
 use reth_optimism_cli::{chainspec::OpChainSpecParser, Cli};
 use reth_optimism_node::{args::RollupArgs, OpNode};
 use tracing::info;
@@ -18,14 +17,14 @@ fn main() {
         }
     }
 
-    if let Err(err) =
-        Cli::<OpChainSpecParser, RollupArgs>::parse().run(async move |builder, rollup_args| {
+    if let Err(err) = Cli::<OpChainSpecParser, RollupArgs>::parse_with_env().run(
+        async move |builder, rollup_args| {
             info!(target: "reth::cli", "Launching node");
             let handle =
                 builder.node(OpNode::new(rollup_args)).launch_with_debug_capabilities().await?;
             handle.node_exit_future.await
-        })
-    {
+        },
+    ) {
         eprintln!("Error: {err:?}");
         std::process::exit(1);
     }
@@ -3,7 +3,7 @@ use eyre::{eyre, Result};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::launcher::Launcher;
 use reth_cli_runner::CliRunner;
-use reth_node_core::args::OtlpInitStatus;
+use reth_node_core::args::{OtlpGuard, OtlpInitStatus};
 use reth_node_metrics::recorder::install_prometheus_recorder;
 use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_consensus::OpBeaconConsensus;
@@ -52,6 +52,11 @@ where
     /// This accepts a closure that is used to launch the node via the
     /// [`NodeCommand`](reth_cli_commands::node::NodeCommand).
     pub fn run(mut self, launcher: impl Launcher<C, Ext>) -> Result<()> {
+        // The version is written to stdout, which tracing would log to as well
+        if let Commands::Version(command) = &self.cli.command {
+            return command.execute()
+        }
+
         let runner = match self.runner.take() {
             Some(runner) => runner,
             None => CliRunner::try_default_runtime()?,
@@ -64,7 +69,10 @@ where
                 self.cli.logs.log_file_directory.join(chain_spec.chain.to_string());
         }
 
+        // Flushes OTLP spans that are still buffered once the command exits
+        let _otlp_guard = OtlpGuard::new();
         self.init_tracing(&runner)?;
+        let runner = runner.with_log_guard(self.guard.take());
 
         // Install the prometheus recorder to be sure to record all metrics
         let _ = install_prometheus_recorder();
@@ -83,7 +91,9 @@ where
                     Rpc::validate_selection(ws_api, "ws.api").map_err(|e| eyre!("{e}"))?;
                 }
 
-                runner.run_command_until_exit(|ctx| command.execute(ctx, launcher))
+                runner
+                    .with_shutdown_timeout(command.shutdown_timeout)
+                    .run_command_until_exit(|ctx| command.execute(ctx, launcher))
             }
             Commands::Init(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<OpNode>())
@@ -98,9 +108,9 @@ where
                 runner.run_blocking_until_ctrl_c(command.execute::<OpNode>())
             }
             Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
-            Commands::Db(command) => {
-                runner.run_blocking_command_until_exit(|ctx| command.execute::<OpNode>(ctx))
-            }
+            Commands::Db(command) => runner.run_blocking_command_until_exit(|ctx| {
+                command.execute::<OpNode, _>(ctx, components)
+            }),
             Commands::Stage(command) => {
                 runner.run_command_until_exit(|ctx| command.execute::<OpNode, _>(ctx, components))
             }
@@ -112,6 +122,7 @@ where
             Commands::ReExecute(command) => {
                 runner.run_until_ctrl_c(command.execute::<OpNode>(components))
             }
+            Commands::Version(command) => command.execute(),
         }
     }
 
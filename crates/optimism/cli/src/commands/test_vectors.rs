@@ -1,5 +1,6 @@
 //! Command for generating test vectors.
 
+use alloy_primitives::B256;
 use clap::{Parser, Subcommand};
 use op_alloy_consensus::TxDeposit;
 use proptest::test_runner::TestRunner;
@@ -34,15 +35,17 @@ pub enum Subcommands {
     },
     /// Generates test vectors for `Compact` types with `--write`. Reads and checks generated
     /// vectors with `--read`.
-    #[group(multiple = false, required = true)]
+    ///
+    /// Checks the decoded values against their stored JSON with `--verify`.
     Compact {
-        /// Write test vectors to a file.
-        #[arg(long)]
-        write: bool,
+        #[command(flatten)]
+        mode: compact::Mode,
 
-        /// Read test vectors from a file.
-        #[arg(long)]
-        read: bool,
+        /// Seed for the random generator, so that the vectors can be reproduced.
+        ///
+        /// If not set, a random seed is used and printed.
+        #[arg(long, requires = "write")]
+        seed: Option<B256>,
     },
 }
 
@@ -53,16 +56,23 @@ impl Command {
             Subcommands::Tables { names } => {
                 tables::generate_vectors(names)?;
             }
-            Subcommands::Compact { write, .. } => {
+            Subcommands::Compact { mode, seed } => {
                 compact_types!(
                     regular: [
                         TxDeposit
                     ], identifier: []
                 );
 
-                if write {
-                    compact::generate_vectors_with(ETH_GENERATE_VECTORS)?;
-                    compact::generate_vectors_with(GENERATE_VECTORS)?;
+                if mode.write {
+                    compact::generate_vectors_with(
+                        &[ETH_GENERATE_VECTORS, GENERATE_VECTORS].concat(),
+                        seed,
+                    )?;
+                } else if let Some(folder) = mode.verify {
+                    compact::verify_vectors_with(
+                        &[ETH_READ_VECTORS, READ_VECTORS].concat(),
+                        &folder,
+                    )?;
                 } else {
                     compact::read_vectors_with(ETH_READ_VECTORS)?;
                     compact::read_vectors_with(READ_VECTORS)?;
@@ -40,11 +40,11 @@ use reth_rpc_server_types::{DefaultRpcModuleValidator, RpcModuleValidator};
 use std::{ffi::OsString, fmt, marker::PhantomData, sync::Arc};
 
 use chainspec::OpChainSpecParser;
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
 use commands::Commands;
 use futures_util::Future;
 use reth_cli::chainspec::ChainSpecParser;
-use reth_cli_commands::launcher::FnLauncher;
+use reth_cli_commands::{cli_env, launcher::FnLauncher};
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
@@ -88,7 +88,7 @@ pub struct Cli<
 impl Cli {
     /// Parsers only the default CLI arguments
     pub fn parse_args() -> Self {
-        Self::parse()
+        Self::parse_with_env()
     }
 
     /// Parsers only the default CLI arguments from the given iterator
@@ -97,7 +97,7 @@ impl Cli {
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
     {
-        Self::try_parse_from(itr)
+        Self::try_parse_with_env_from(itr)
     }
 }
 
@@ -107,6 +107,25 @@ where
     Ext: clap::Args + fmt::Debug,
     Rpc: RpcModuleValidator,
 {
+    /// Parses the CLI arguments of the process, and their environment variables, see
+    /// [`cli_env`].
+    ///
+    /// Exits the process on error, like [`Parser::parse`].
+    pub fn parse_with_env() -> Self {
+        Self::try_parse_with_env_from(std::env::args_os()).unwrap_or_else(|err| err.exit())
+    }
+
+    /// Parses the CLI arguments from the given iterator, and their environment variables, see
+    /// [`cli_env`].
+    pub fn try_parse_with_env_from<I, T>(itr: I) -> Result<Self, clap::error::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let matches = cli_env::with_env(Self::command()).try_get_matches_from(itr)?;
+        Self::from_arg_matches(&matches)
+    }
+
     /// Configures the CLI and returns a [`CliApp`] instance.
     ///
     /// This method is used to prepare the CLI for execution by wrapping it in a
@@ -1,6 +1,26 @@
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_network_peers::{AnyNode, NodeRecord};
+use serde::{Deserialize, Serialize};
+
+/// A static peer, which the node keeps connected, as returned by `admin_staticPeers`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaticPeerInfo {
+    /// The enode of the peer.
+    pub enode: String,
+    /// The state of the connection to the peer, one of `connected`, `disconnecting`, `dialing`,
+    /// `backedOff`, `banned` or `idle`.
+    pub state: String,
+    /// The attempts to reconnect to the peer since the last session with it.
+    pub reconnect_attempts: u32,
+    /// When the node last had a session with the peer, in seconds since the unix epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<u64>,
+    /// The milliseconds until the next attempt to reconnect, if the peer is backed off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_in: Option<u64>,
+}
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -34,6 +54,11 @@ pub trait AdminApi {
     #[method(name = "peers")]
     async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
 
+    /// Returns the static peers, which the node keeps connected and redials with a backoff, and
+    /// the state of the connection to them.
+    #[method(name = "staticPeers")]
+    async fn static_peers(&self) -> RpcResult<Vec<StaticPeerInfo>>;
+
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
         name = "peerEvents",
@@ -146,6 +146,7 @@ where
     AdminApiClient::add_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+    AdminApiClient::static_peers(client).await.unwrap();
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
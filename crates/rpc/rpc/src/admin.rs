@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Instant, UNIX_EPOCH},
+};
 
 use alloy_genesis::ChainConfig;
 use alloy_rpc_types_admin::{
@@ -8,10 +11,10 @@ use alloy_rpc_types_admin::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
-use reth_network_api::{NetworkInfo, Peers};
+use reth_network_api::{NetworkInfo, PeerFetchScore, Peers};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
 use reth_network_types::PeerKind;
-use reth_rpc_api::AdminApiServer;
+use reth_rpc_api::{AdminApiServer, StaticPeerInfo};
 use reth_rpc_server_types::ToRpcResult;
 use reth_transaction_pool::TransactionPool;
 use revm_primitives::keccak256;
@@ -91,7 +94,11 @@ where
                 protocols: PeerProtocolInfo {
                     eth: Some(EthPeerInfo::Info(EthInfo { version: peer.status.version as u64 })),
                     snap: None,
-                    other: Default::default(),
+                    other: peer
+                        .fetch_score
+                        .map(|score| ("fetch".to_string(), fetch_score_json(&score)))
+                        .into_iter()
+                        .collect(),
                 },
             })
         }
@@ -99,6 +106,27 @@ where
         Ok(infos)
     }
 
+    /// Handler for `admin_staticPeers`
+    async fn static_peers(&self) -> RpcResult<Vec<StaticPeerInfo>> {
+        let peers = self.network.get_static_peers().await.to_rpc_result()?;
+        let now = Instant::now();
+
+        Ok(peers
+            .into_iter()
+            .map(|peer| StaticPeerInfo {
+                enode: peer.record.to_string(),
+                state: peer.state.to_string(),
+                reconnect_attempts: peer.reconnect_attempts,
+                last_seen: peer
+                    .last_seen
+                    .map(|time| time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()),
+                next_attempt_in: peer
+                    .next_attempt
+                    .map(|at| at.saturating_duration_since(now).as_millis() as u64),
+            })
+            .collect())
+    }
+
     /// Handler for `admin_nodeInfo`
     async fn node_info(&self) -> RpcResult<NodeInfo> {
         let enode = self.network.local_node_record();
@@ -200,3 +228,17 @@ impl<N, ChainSpec, Pool> std::fmt::Debug for AdminApi<N, ChainSpec, Pool> {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
 }
+
+/// Returns the statistics that rank the peer for block header and body requests, which
+/// `admin_peers` reports under the `fetch` protocol entry.
+fn fetch_score_json(score: &PeerFetchScore) -> serde_json::Value {
+    serde_json::json!({
+        "score": score.score,
+        "responses": score.responses,
+        "latencyMs": score.latency.as_millis() as u64,
+        "timeoutRate": score.timeout_rate,
+        "validationFailureRate": score.validation_failure_rate,
+        "throughput": score.throughput as u64,
+        "inCooldown": score.in_cooldown,
+    })
+}
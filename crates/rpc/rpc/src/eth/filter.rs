@@ -2,7 +2,7 @@
 
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::{Sealable, TxHash};
+use alloy_primitives::TxHash;
 use alloy_rpc_types_eth::{
     BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, Log,
     PendingTransactionFilterKind,
@@ -654,29 +654,18 @@ where
         for (from, to) in
             BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
         {
-            let headers = self.provider().headers_range(from..=to)?;
-
-            let mut headers_iter = headers.into_iter().peekable();
-
-            while let Some(header) = headers_iter.next() {
-                if !filter.matches_bloom(header.logs_bloom()) {
+            // only the blooms are needed to find the matching blocks, whose headers are loaded
+            // afterwards
+            for (number, bloom) in self.provider().block_bloom_range(from..=to)? {
+                if !filter.matches_bloom(bloom) {
                     continue
                 }
 
-                let current_number = header.number();
-
-                let block_hash = match headers_iter.peek() {
-                    Some(next_header) if next_header.number() == current_number + 1 => {
-                        // Headers are consecutive, use the more efficient parent_hash
-                        next_header.parent_hash()
-                    }
-                    _ => {
-                        // Headers not consecutive or last header, calculate hash
-                        header.hash_slow()
-                    }
-                };
-
-                matching_headers.push(SealedHeader::new(header, block_hash));
+                let header = self
+                    .provider()
+                    .sealed_header(number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                matching_headers.push(header);
             }
         }
 
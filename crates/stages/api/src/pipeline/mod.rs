@@ -92,6 +92,8 @@ pub struct Pipeline<N: ProviderNodeTypes> {
     /// Number of consecutive unwind attempts due to [`StageError::DetachedHead`] for the current
     /// fork.
     detached_head_attempts: u64,
+    /// Pauses the execution of stages while it's `true`, see [`Pipeline::set_pause_signal`].
+    pause: Option<watch::Receiver<bool>>,
 }
 
 impl<N: ProviderNodeTypes> Pipeline<N> {
@@ -115,6 +117,15 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
         });
     }
 
+    /// Sets a signal that pauses the pipeline while it's `true`.
+    ///
+    /// The pipeline waits before executing the next batch of a stage, so a batch that is being
+    /// executed is still committed. If the sender is dropped while the pipeline is paused, it stays
+    /// paused.
+    pub fn set_pause_signal(&mut self, pause: watch::Receiver<bool>) {
+        self.pause = Some(pause);
+    }
+
     /// Listen for events on the pipeline.
     pub fn events(&self) -> EventStream<PipelineEvent> {
         self.event_sender.new_listener()
@@ -443,6 +454,16 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
                 })
             }
 
+            let paused = self.pause.as_ref().is_some_and(|pause| *pause.borrow());
+            if paused && let Some(pause) = &mut self.pause {
+                warn!(target: "sync::pipeline", stage = %stage_id, "Pipeline paused");
+                if pause.wait_for(|paused| !paused).await.is_err() {
+                    // Nothing can resume the pipeline anymore
+                    std::future::pending::<()>().await;
+                }
+                info!(target: "sync::pipeline", stage = %stage_id, "Pipeline resumed");
+            }
+
             let exec_input = ExecInput { target, checkpoint: prev_checkpoint };
 
             self.event_sender.notify(PipelineEvent::Prepare {
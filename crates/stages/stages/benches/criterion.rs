@@ -2,19 +2,24 @@
 #![allow(unexpected_cfgs)]
 
 use alloy_primitives::BlockNumber;
-use criterion::{criterion_main, measurement::WallTime, BenchmarkGroup, Criterion};
+use criterion::{criterion_main, measurement::WallTime, BenchmarkGroup, Criterion, Throughput};
+use reth_chainspec::ChainSpecBuilder;
 use reth_config::config::{EtlConfig, TransactionLookupConfig};
+use reth_consensus::noop::NoopConsensus;
 use reth_db::{test_utils::TempDatabase, Database, DatabaseEnv};
+use reth_evm_ethereum::EthEvmConfig;
+use reth_exex::ExExManagerHandle;
 use reth_provider::{
     test_utils::MockNodeTypesWithDB, DBProvider, DatabaseProvider, DatabaseProviderFactory,
+    StageCheckpointWriter,
 };
 use reth_stages::{
-    stages::{MerkleStage, SenderRecoveryStage, TransactionLookupStage},
-    test_utils::TestStageDB,
-    StageCheckpoint,
+    stages::{ExecutionStage, MerkleStage, SenderRecoveryStage, TransactionLookupStage},
+    test_utils::{ExecutableChain, TestStageDB},
+    ExecutionStageThresholds, StageCheckpoint,
 };
-use reth_stages_api::{ExecInput, Stage, StageExt, UnwindInput};
-use std::ops::RangeInclusive;
+use reth_stages_api::{ExecInput, ExecOutput, Stage, StageExt, StageId, UnwindInput};
+use std::{ops::RangeInclusive, sync::Arc};
 use tokio::runtime::Runtime;
 
 mod setup;
@@ -35,6 +40,7 @@ fn run_benches(criterion: &mut Criterion) {
     account_hashing(criterion, &runtime);
     senders(criterion, &runtime);
     merkle(criterion, &runtime);
+    execution(criterion);
 }
 
 #[cfg(not(codspeed))]
@@ -138,6 +144,63 @@ fn merkle(c: &mut Criterion, runtime: &Runtime) {
     );
 }
 
+/// Executes blocks with a commit after every block, with and without pipelining the commits.
+fn execution(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Stages");
+    // don't need to run each stage for that many times
+    group.sample_size(10);
+
+    let num_blocks = 200;
+    let chain = ExecutableChain::generate(num_blocks, 100);
+    let gas = chain.blocks.iter().map(|block| block.gas_used).sum();
+    group.throughput(Throughput::Elements(gas));
+
+    for pipelined in [false, true] {
+        let label = if pipelined { "Execution-pipelined-commits" } else { "Execution" };
+        group.bench_function(label, |b| {
+            b.iter_with_setup(
+                || {
+                    let db = TestStageDB::default();
+                    chain.insert(&db).unwrap();
+                    db
+                },
+                |db| {
+                    let mut stage = ExecutionStage::new(
+                        EthEvmConfig::new(Arc::new(
+                            ChainSpecBuilder::mainnet().berlin_activated().build(),
+                        )),
+                        NoopConsensus::arc(),
+                        ExecutionStageThresholds {
+                            max_blocks: Some(1),
+                            max_changes: None,
+                            max_cumulative_gas: None,
+                            max_duration: None,
+                        },
+                        u64::MAX,
+                        ExExManagerHandle::empty(),
+                    );
+                    let mut input = ExecInput { target: Some(num_blocks), checkpoint: None };
+                    if pipelined {
+                        stage.execute_pipelined(&db.factory, input).unwrap();
+                        return
+                    }
+                    loop {
+                        let provider = db.factory.database_provider_rw().unwrap();
+                        let ExecOutput { checkpoint, done } =
+                            stage.execute(&provider, input).unwrap();
+                        provider.save_stage_checkpoint(StageId::Execution, checkpoint).unwrap();
+                        provider.commit().unwrap();
+                        input.checkpoint = Some(checkpoint);
+                        if done {
+                            break
+                        }
+                    }
+                },
+            )
+        });
+    }
+}
+
 fn measure_stage<F, S>(
     runtime: &Runtime,
     group: &mut BenchmarkGroup<'_, WallTime>,
@@ -1,21 +1,25 @@
 use crate::stages::MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD;
 use alloy_consensus::BlockHeader;
-use alloy_primitives::BlockNumber;
+use alloy_primitives::{Address, BlockNumber, B256, U256};
 use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_db::{static_file::HeaderMask, tables};
+use reth_era_utils::ERA_RECEIPTS_STAGE_ID;
 use reth_evm::{execute::Executor, metrics::ExecutorMetrics, ConfigureEvm};
 use reth_execution_types::Chain;
 use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
-use reth_primitives_traits::{format_gas_throughput, BlockBody, NodePrimitives};
+use reth_primitives_traits::{format_gas_throughput, BlockBody, NodePrimitives, RecoveredBlock};
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
-    BlockHashReader, BlockReader, DBProvider, EitherWriter, ExecutionOutcome, HeaderProvider,
-    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateWriter,
+    BlockHashReader, BlockReader, DBProvider, DatabaseProviderFactory, EitherWriter,
+    ExecutionOutcome, HeaderProvider, LatestStateProviderRef, OriginalValuesKnown, ProviderError,
+    ProviderResult, StageCheckpointReader, StageCheckpointWriter, StateWriter,
     StaticFileProviderFactory, StatsReader, StorageSettingsCache, TransactionVariant,
 };
-use reth_revm::database::StateProviderDatabase;
+use reth_revm::{
+    bytecode::Bytecode, database::StateProviderDatabase, state::AccountInfo, Database,
+};
 use reth_stages_api::{
     BlockErrorKind, CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput,
     ExecutionCheckpoint, ExecutionStageThresholds, Stage, StageCheckpoint, StageError, StageId,
@@ -27,6 +31,7 @@ use std::{
     ops::RangeInclusive,
     sync::Arc,
     task::{ready, Context, Poll},
+    thread::{self, JoinHandle},
     time::{Duration, Instant},
 };
 use tracing::*;
@@ -189,6 +194,7 @@ where
             + DBProvider
             + BlockReader
             + HeaderProvider
+            + StageCheckpointReader
             + StorageSettingsCache,
     {
         // On old nodes, if there's any receipts pruning configured, receipts are written directly
@@ -220,11 +226,24 @@ where
             // It can be equal when it's a chain of empty blocks, but we still need to update the
             // last block in the range.
             Ordering::Greater | Ordering::Equal => {
+                // Receipts imported from era1 files are kept, they're skipped when written again
+                let imported = provider
+                    .get_stage_checkpoint(ERA_RECEIPTS_STAGE_ID)?
+                    .map(|checkpoint| checkpoint.block_number.min(static_file_block_num))
+                    .unwrap_or_default();
+                let (keep_block, next_receipt_num) = if imported > checkpoint {
+                    let next_imported_receipt_num =
+                        provider.block_body_indices(imported)?.map(|b| b.next_tx_num());
+                    (imported, next_imported_receipt_num.unwrap_or(next_receipt_num))
+                } else {
+                    (checkpoint, next_receipt_num)
+                };
+
                 let mut static_file_producer =
                     static_file_provider.latest_writer(StaticFileSegment::Receipts)?;
                 static_file_producer.prune_receipts(
                     next_static_file_receipt_num.saturating_sub(next_receipt_num),
-                    checkpoint,
+                    keep_block,
                 )?;
                 // Since this is a database <-> static file inconsistency, we commit the change
                 // straight away.
@@ -252,50 +271,40 @@ where
 
         Ok(())
     }
-}
 
-impl<E, Provider> Stage<Provider> for ExecutionStage<E>
-where
-    E: ConfigureEvm,
-    Provider: DBProvider
-        + BlockReader<
-            Block = <E::Primitives as NodePrimitives>::Block,
-            Header = <E::Primitives as NodePrimitives>::BlockHeader,
-        > + StaticFileProviderFactory<
-            Primitives: NodePrimitives<BlockHeader: reth_db_api::table::Value>,
-        > + StatsReader
-        + BlockHashReader
-        + StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>
-        + StorageSettingsCache,
-{
-    /// Return the id of the stage
-    fn id(&self) -> StageId {
-        StageId::Execution
-    }
+    /// Sends the [`ExExNotification`] for the executed blocks that were committed, if any.
+    fn notify_chain_committed(&mut self) {
+        let Some(chain) = self.post_execute_commit_input.take() else { return };
 
-    fn poll_execute_ready(
-        &mut self,
-        cx: &mut Context<'_>,
-        _: ExecInput,
-    ) -> Poll<Result<(), StageError>> {
-        ready!(self.exex_manager_handle.poll_ready(cx));
-
-        Poll::Ready(Ok(()))
+        // NOTE: We can ignore the error here, since an error means that the channel is closed,
+        // which means the manager has died, which then in turn means the node is shutting down.
+        let _ = self.exex_manager_handle.send(
+            ExExNotificationSource::Pipeline,
+            ExExNotification::ChainCommitted { new: Arc::new(chain) },
+        );
     }
 
-    /// Execute the stage
-    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
-        if input.target_reached() {
-            return Ok(ExecOutput::done(input.checkpoint()))
-        }
-
+    /// Executes the blocks of the input against the given state, until the target or the end of
+    /// the batch is reached.
+    fn execute_batch<Provider, DB>(
+        &self,
+        provider: &Provider,
+        db: DB,
+        input: ExecInput,
+    ) -> Result<ExecutedBatch<E::Primitives>, StageError>
+    where
+        Provider: BlockReader<
+                Block = <E::Primitives as NodePrimitives>::Block,
+                Header = <E::Primitives as NodePrimitives>::BlockHeader,
+            > + StaticFileProviderFactory<
+                Primitives: NodePrimitives<BlockHeader: reth_db_api::table::Value>,
+            >,
+        DB: Database,
+    {
         let start_block = input.next_block();
         let max_block = input.target();
         let static_file_provider = provider.static_file_provider();
 
-        self.ensure_consistency(provider, input.checkpoint().block_number, None)?;
-
-        let db = StateProviderDatabase(LatestStateProviderRef::new(provider));
         let mut executor = self.evm_config.batch_executor(db);
 
         // Progress tracking
@@ -397,7 +406,7 @@ where
 
         // prepare execution output for writing
         let time = Instant::now();
-        let mut state = ExecutionOutcome::from_blocks(
+        let outcome = ExecutionOutcome::from_blocks(
             start_block,
             executor.into_state().take_bundle(),
             results,
@@ -413,6 +422,38 @@ where
             "Finished executing block range"
         );
 
+        debug!(
+            target: "sync::stages::execution",
+            block_fetch = ?fetch_block_duration,
+            execution = ?execution_duration,
+            write_preparation = ?write_preparation_duration,
+            "Execution time"
+        );
+
+        Ok(ExecutedBatch {
+            outcome,
+            blocks,
+            checkpoint: StageCheckpoint::new(stage_progress)
+                .with_execution_stage_checkpoint(stage_checkpoint),
+            done: stage_progress == max_block,
+        })
+    }
+
+    /// Returns the outcome of the executed batch to write, after preparing the input of the post
+    /// execute commit hook and clearing the reverts that are pruned anyway.
+    fn prepare_write<Provider>(
+        &mut self,
+        provider: &Provider,
+        batch: ExecutedBatch<E::Primitives>,
+        input: ExecInput,
+    ) -> Result<ExecutionOutcome<<E::Primitives as NodePrimitives>::Receipt>, StageError>
+    where
+        Provider: DBProvider + StatsReader,
+    {
+        let start_block = input.next_block();
+        let max_block = input.target();
+        let ExecutedBatch { outcome: mut state, blocks, .. } = batch;
+
         // Prepare the input for post execute commit hook, where an `ExExNotification` will be sent.
         //
         // Note: Since we only write to `blocks` if there are any ExExes, we don't need to perform
@@ -430,8 +471,6 @@ where
             }
         }
 
-        let time = Instant::now();
-
         if self.can_prune_changesets(provider, start_block, max_block)? {
             let prune_modes = provider.prune_modes_ref();
 
@@ -457,37 +496,139 @@ where
             }
         }
 
-        // write output
-        provider.write_state(&state, OriginalValuesKnown::Yes)?;
+        Ok(state)
+    }
 
-        let db_write_duration = time.elapsed();
-        debug!(
-            target: "sync::stages::execution",
-            block_fetch = ?fetch_block_duration,
-            execution = ?execution_duration,
-            write_preparation = ?write_preparation_duration,
-            write = ?db_write_duration,
-            "Execution time"
-        );
+    /// Executes the blocks up to the target of the input in batches, like calling
+    /// [`Stage::execute`] and committing until it's done, but writes and commits every batch on a
+    /// dedicated thread while the next batch executes.
+    ///
+    /// The next batch reads the state changed by the batch that is being committed from its
+    /// in-memory outcome, and everything else from a read-only transaction. At most one commit is
+    /// in flight: a batch is written only after the previous batch was committed, together with the
+    /// stage checkpoint, so after a crash the database is at the last committed batch like with
+    /// sequential commits.
+    ///
+    /// The node's pipeline doesn't run the stage this way: it passes every batch a transaction
+    /// that it commits itself after [`Stage::execute`] returns, so there's no commit to overlap
+    /// with. This is used by `reth stage run --pipeline-commits` and the execution benchmarks.
+    ///
+    /// Returns the checkpoint of the last committed batch.
+    pub fn execute_pipelined<F>(
+        &mut self,
+        factory: &F,
+        mut input: ExecInput,
+    ) -> Result<StageCheckpoint, StageError>
+    where
+        F: DatabaseProviderFactory + Clone + 'static,
+        F::Provider: BlockReader<
+                Block = <E::Primitives as NodePrimitives>::Block,
+                Header = <E::Primitives as NodePrimitives>::BlockHeader,
+            > + StaticFileProviderFactory<
+                Primitives: NodePrimitives<BlockHeader: reth_db_api::table::Value>,
+            > + StatsReader
+            + StageCheckpointReader
+            + StorageSettingsCache,
+        F::ProviderRW: StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>
+            + StageCheckpointWriter,
+    {
+        {
+            let provider = factory.database_provider_ro()?;
+            self.ensure_consistency(&provider, input.checkpoint().block_number, None)?;
+        }
 
-        let done = stage_progress == max_block;
-        Ok(ExecOutput {
-            checkpoint: StageCheckpoint::new(stage_progress)
-                .with_execution_stage_checkpoint(stage_checkpoint),
-            done,
-        })
+        let mut in_flight: Option<InFlightCommit<_>> = None;
+        while !input.target_reached() {
+            let provider = factory.database_provider_ro()?;
+            let db = StateProviderDatabase(LatestStateProviderRef::new(&provider));
+            let batch = match &in_flight {
+                Some(commit) => self.execute_batch(
+                    &provider,
+                    UncommittedBatchDatabase { db, outcome: &*commit.outcome },
+                    input,
+                ),
+                None => self.execute_batch(&provider, db, input),
+            };
+
+            // The previous batch has to be committed before this one is written, and before
+            // returning an error.
+            if let Some(commit) = in_flight.take() {
+                commit.wait()?;
+                self.notify_chain_committed();
+            }
+            let batch = batch?;
+
+            let checkpoint = batch.checkpoint;
+            let state = self.prepare_write(&provider, batch, input)?;
+            in_flight = Some(InFlightCommit::spawn(factory.clone(), state, checkpoint)?);
+            input.checkpoint = Some(checkpoint);
+        }
+
+        if let Some(commit) = in_flight {
+            commit.wait()?;
+            self.notify_chain_committed();
+        }
+
+        Ok(input.checkpoint())
     }
+}
 
-    fn post_execute_commit(&mut self) -> Result<(), StageError> {
-        let Some(chain) = self.post_execute_commit_input.take() else { return Ok(()) };
+impl<E, Provider> Stage<Provider> for ExecutionStage<E>
+where
+    E: ConfigureEvm,
+    Provider: DBProvider
+        + BlockReader<
+            Block = <E::Primitives as NodePrimitives>::Block,
+            Header = <E::Primitives as NodePrimitives>::BlockHeader,
+        > + StaticFileProviderFactory<
+            Primitives: NodePrimitives<BlockHeader: reth_db_api::table::Value>,
+        > + StatsReader
+        + BlockHashReader
+        + StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>
+        + StageCheckpointReader
+        + StorageSettingsCache,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::Execution
+    }
 
-        // NOTE: We can ignore the error here, since an error means that the channel is closed,
-        // which means the manager has died, which then in turn means the node is shutting down.
-        let _ = self.exex_manager_handle.send(
-            ExExNotificationSource::Pipeline,
-            ExExNotification::ChainCommitted { new: Arc::new(chain) },
-        );
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        _: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        ready!(self.exex_manager_handle.poll_ready(cx));
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Execute the stage
+    ///
+    /// The batch is written to the transaction of the pipeline, which commits it before the next
+    /// batch executes, see [`ExecutionStage::execute_pipelined`] to overlap them.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        self.ensure_consistency(provider, input.checkpoint().block_number, None)?;
+
+        let db = StateProviderDatabase(LatestStateProviderRef::new(provider));
+        let batch = self.execute_batch(provider, db, input)?;
+        let (checkpoint, done) = (batch.checkpoint, batch.done);
+        let state = self.prepare_write(provider, batch, input)?;
+
+        // write output
+        let time = Instant::now();
+        provider.write_state(&state, OriginalValuesKnown::Yes)?;
+        debug!(target: "sync::stages::execution", write = ?time.elapsed(), "Wrote execution outcome");
 
+        Ok(ExecOutput { checkpoint, done })
+    }
+
+    fn post_execute_commit(&mut self) -> Result<(), StageError> {
+        self.notify_chain_committed();
         Ok(())
     }
 
@@ -564,6 +705,107 @@ where
     }
 }
 
+/// A batch of blocks that was executed, but whose outcome wasn't written yet.
+struct ExecutedBatch<N: NodePrimitives> {
+    /// The outcome of executing the blocks.
+    outcome: ExecutionOutcome<N::Receipt>,
+    /// The executed blocks, only kept if there are `ExEx`es.
+    blocks: Vec<RecoveredBlock<N::Block>>,
+    /// The checkpoint after the last executed block.
+    checkpoint: StageCheckpoint,
+    /// Whether the target of the input was reached.
+    done: bool,
+}
+
+/// A batch whose outcome is written and committed on a dedicated thread, see
+/// [`ExecutionStage::execute_pipelined`].
+struct InFlightCommit<R> {
+    /// The outcome of the batch, which the next batch reads until it's committed.
+    outcome: Arc<ExecutionOutcome<R>>,
+    /// Handle to the thread that writes and commits the outcome.
+    handle: JoinHandle<ProviderResult<()>>,
+}
+
+impl<R: Send + Sync + 'static> InFlightCommit<R> {
+    /// Spawns the thread that writes the outcome and the stage checkpoint in a new transaction and
+    /// commits it.
+    fn spawn<F>(
+        factory: F,
+        outcome: ExecutionOutcome<R>,
+        checkpoint: StageCheckpoint,
+    ) -> Result<Self, StageError>
+    where
+        F: DatabaseProviderFactory + 'static,
+        F::ProviderRW: StateWriter<Receipt = R> + StageCheckpointWriter,
+    {
+        let outcome = Arc::new(outcome);
+        let handle = thread::Builder::new()
+            .name("execution-commit".to_string())
+            .spawn({
+                let outcome = Arc::clone(&outcome);
+                move || {
+                    let provider = factory.database_provider_rw()?;
+                    provider.write_state(&outcome, OriginalValuesKnown::Yes)?;
+                    provider.save_stage_checkpoint(StageId::Execution, checkpoint)?;
+                    provider.commit()?;
+                    Ok(())
+                }
+            })
+            .map_err(|err| StageError::Fatal(Box::new(err)))?;
+
+        Ok(Self { outcome, handle })
+    }
+
+    /// Waits until the batch is committed.
+    fn wait(self) -> Result<(), StageError> {
+        let time = Instant::now();
+        self.handle
+            .join()
+            .map_err(|_| StageError::Fatal("execution commit thread panicked".into()))??;
+        debug!(target: "sync::stages::execution", wait = ?time.elapsed(), "Waited for the commit of the previous batch");
+        Ok(())
+    }
+}
+
+/// A [`Database`] that reads the accounts, storage and bytecodes changed by a batch that isn't
+/// committed yet from its outcome, and everything else from the underlying database.
+struct UncommittedBatchDatabase<'a, DB, R> {
+    /// The state before the batch, or after it if the batch was committed already.
+    db: DB,
+    /// The outcome of the batch.
+    outcome: &'a ExecutionOutcome<R>,
+}
+
+impl<DB: Database, R> Database for UncommittedBatchDatabase<'_, DB, R> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        match self.outcome.account_state(&address) {
+            Some(account) => Ok(account.info.clone()),
+            None => self.db.basic(address),
+        }
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        match self.outcome.bundle.bytecode(&code_hash) {
+            Some(code) => Ok(code),
+            None => self.db.code_by_hash(code_hash),
+        }
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        // Also known if the account was destroyed in the batch, in which case it's zero.
+        match self.outcome.storage(&address, index) {
+            Some(value) => Ok(value),
+            None => self.db.storage(address, index),
+        }
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.db.block_hash(number)
+    }
+}
+
 fn execution_checkpoint<N>(
     provider: &StaticFileProvider<N>,
     start_block: BlockNumber,
@@ -671,11 +913,15 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{stages::MERKLE_STAGE_DEFAULT_REBUILD_THRESHOLD, test_utils::TestStageDB};
+    use crate::{
+        stages::MERKLE_STAGE_DEFAULT_REBUILD_THRESHOLD,
+        test_utils::{ExecutableChain, TestStageDB},
+    };
     use alloy_primitives::{address, hex_literal::hex, keccak256, Address, B256, U256};
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
     use reth_chainspec::ChainSpecBuilder;
+    use reth_consensus::noop::NoopConsensus;
     use reth_db_api::{
         models::{metadata::StorageSettings, AccountBeforeTx},
         transaction::{DbTx, DbTxMut},
@@ -686,7 +932,7 @@ mod tests {
     use reth_primitives_traits::{Account, Bytecode, SealedBlock, StorageEntry};
     use reth_provider::{
         test_utils::create_test_provider_factory, AccountReader, BlockWriter,
-        DatabaseProviderFactory, ReceiptProvider, StaticFileProviderFactory,
+        DatabaseProviderFactory, ReceiptProvider, StageCheckpointReader, StaticFileProviderFactory,
     };
     use reth_prune::PruneModes;
     use reth_prune_types::{PruneMode, ReceiptsLogPruneConfig};
@@ -1311,4 +1557,145 @@ mod tests {
             .ensure_consistency(&provider, 1, None)
             .expect("ensure_consistency should succeed when receipts are intentionally skipped");
     }
+
+    /// Executes the chain from genesis in batches of a few blocks, committing after every batch
+    /// either sequentially or pipelined.
+    fn execute_chain(chain: &ExecutableChain, pipelined: bool) -> TestStageDB {
+        let db = TestStageDB::default();
+        chain.insert(&db).unwrap();
+        execute_inserted_chain(&db, chain, pipelined);
+        db
+    }
+
+    /// Executes the chain that is already inserted into `db` from genesis, like
+    /// [`execute_chain`].
+    fn execute_inserted_chain(db: &TestStageDB, chain: &ExecutableChain, pipelined: bool) {
+        let mut stage = ExecutionStage::new(
+            EthEvmConfig::new(Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build())),
+            NoopConsensus::arc(),
+            ExecutionStageThresholds {
+                max_blocks: Some(3),
+                max_changes: None,
+                max_cumulative_gas: None,
+                max_duration: None,
+            },
+            MERKLE_STAGE_DEFAULT_REBUILD_THRESHOLD,
+            ExExManagerHandle::empty(),
+        );
+        let mut input = ExecInput { target: Some(chain.blocks.len() as u64 - 1), checkpoint: None };
+
+        if pipelined {
+            stage.execute_pipelined(&db.factory, input).unwrap();
+        } else {
+            loop {
+                let provider = db.factory.database_provider_rw().unwrap();
+                let ExecOutput { checkpoint, done } = stage.execute(&provider, input).unwrap();
+                provider.save_stage_checkpoint(StageId::Execution, checkpoint).unwrap();
+                provider.commit().unwrap();
+                input.checkpoint = Some(checkpoint);
+                if done {
+                    break
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pipelined_commits_match_sequential_commits() {
+        let num_blocks = 20;
+        let chain = ExecutableChain::generate(num_blocks, 4);
+
+        let sequential = execute_chain(&chain, false);
+        let pipelined = execute_chain(&chain, true);
+
+        // Every batch depends on the state of the previous one.
+        assert_eq!(
+            pipelined.table::<tables::PlainStorageState>().unwrap(),
+            vec![(chain.counter, StorageEntry::new(B256::ZERO, U256::from(num_blocks * 2)))]
+        );
+
+        assert_eq!(
+            pipelined.table::<tables::PlainAccountState>().unwrap(),
+            sequential.table::<tables::PlainAccountState>().unwrap()
+        );
+        assert_eq!(
+            pipelined.table::<tables::PlainStorageState>().unwrap(),
+            sequential.table::<tables::PlainStorageState>().unwrap()
+        );
+        assert_eq!(
+            pipelined.table::<tables::AccountChangeSets>().unwrap(),
+            sequential.table::<tables::AccountChangeSets>().unwrap()
+        );
+        assert_eq!(
+            pipelined.table::<tables::StorageChangeSets>().unwrap(),
+            sequential.table::<tables::StorageChangeSets>().unwrap()
+        );
+        assert_eq!(
+            pipelined.table::<tables::Bytecodes>().unwrap(),
+            sequential.table::<tables::Bytecodes>().unwrap()
+        );
+
+        let (pipelined, sequential) =
+            (pipelined.factory.provider().unwrap(), sequential.factory.provider().unwrap());
+        assert_eq!(
+            pipelined.receipts_by_block_range(1..=num_blocks).unwrap(),
+            sequential.receipts_by_block_range(1..=num_blocks).unwrap()
+        );
+        assert_eq!(
+            pipelined.get_stage_checkpoint(StageId::Execution).unwrap(),
+            sequential.get_stage_checkpoint(StageId::Execution).unwrap()
+        );
+        assert_eq!(
+            pipelined.get_stage_checkpoint(StageId::Execution).unwrap().unwrap().block_number,
+            num_blocks
+        );
+    }
+
+    #[test]
+    fn keeps_imported_receipts() {
+        let num_blocks = 6;
+        let imported = 4;
+        let chain = ExecutableChain::generate(num_blocks, 4);
+        let executed = execute_chain(&chain, false);
+        let executed = executed.factory.provider().unwrap();
+        let receipts = executed.receipts_by_block_range(1..=num_blocks).unwrap();
+
+        for pipelined in [false, true] {
+            let db = TestStageDB::default();
+            chain.insert(&db).unwrap();
+
+            // Import the receipts of the first blocks, like the era1 import does
+            let provider = db.factory.database_provider_rw().unwrap();
+            let static_file_provider = provider.static_file_provider();
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+            let mut tx_num = 0;
+            for (number, block_receipts) in (1..=imported).zip(&receipts) {
+                writer.increment_block(number).unwrap();
+                for receipt in block_receipts {
+                    writer.append_receipt(tx_num, receipt).unwrap();
+                    tx_num += 1;
+                }
+            }
+            writer.commit().unwrap();
+            drop(writer);
+            provider
+                .save_stage_checkpoint(ERA_RECEIPTS_STAGE_ID, StageCheckpoint::new(imported))
+                .unwrap();
+            provider.commit().unwrap();
+
+            execute_inserted_chain(&db, &chain, pipelined);
+
+            let provider = db.factory.provider().unwrap();
+            assert_eq!(provider.receipts_by_block_range(1..=num_blocks).unwrap(), receipts);
+            assert_eq!(
+                db.factory
+                    .static_file_provider()
+                    .get_highest_static_file_tx(StaticFileSegment::Receipts),
+                executed
+                    .static_file_provider()
+                    .get_highest_static_file_tx(StaticFileSegment::Receipts)
+            );
+        }
+    }
 }
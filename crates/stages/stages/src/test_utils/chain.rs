@@ -0,0 +1,131 @@
+use super::{StorageKind, TestStageDB};
+use alloy_consensus::{constants::EMPTY_OMMER_ROOT_HASH, Header, Transaction as _, TxLegacy};
+use alloy_primitives::{hex, keccak256, Address, Bytes, TxKind, B256, U256};
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_ethereum_primitives::{Block, BlockBody, Transaction};
+use reth_primitives_traits::{
+    crypto::secp256k1::public_key_to_address, proofs, Account, Block as _, Bytecode, SealedBlock,
+};
+use reth_provider::{providers::StaticFileWriter, StaticFileProviderFactory};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::ProviderResult;
+use reth_testing_utils::generators::{generate_keys, rng, sign_tx_with_key_pair};
+
+/// Code that increments the value of storage slot zero on every call.
+const COUNTER_CODE: [u8; 10] = hex!("60005460010160005500");
+
+/// Gas limit of the calls to the counter contract.
+const COUNTER_CALL_GAS: u64 = 50_000;
+
+/// Gas used by a plain transfer.
+const TRANSFER_GAS: u64 = 21_000;
+
+/// A chain of blocks whose transactions can be executed, where every block depends on the state
+/// left by the previous blocks.
+///
+/// Every block has one transaction per sender, which alternately either calls a contract that
+/// increments a storage slot, or transfers to an account that didn't exist before. The blocks
+/// aren't valid by consensus rules, so they're meant to be executed with
+/// [`NoopConsensus`](reth_consensus::noop::NoopConsensus).
+#[derive(Debug, Clone)]
+pub struct ExecutableChain {
+    /// The senders of the transactions, with their balance.
+    pub senders: Vec<(Address, Account)>,
+    /// The address of the counter contract.
+    pub counter: Address,
+    /// The blocks, starting with an empty genesis block.
+    pub blocks: Vec<SealedBlock<Block>>,
+}
+
+impl ExecutableChain {
+    /// Generates blocks `0..=num_blocks` with the given number of transactions each.
+    pub fn generate(num_blocks: u64, txs_per_block: usize) -> Self {
+        let mut rng = rng();
+        let keys = generate_keys(&mut rng, txs_per_block);
+        let senders = keys
+            .iter()
+            .map(|key| {
+                let account =
+                    Account { balance: U256::from(10).pow(U256::from(24)), ..Default::default() };
+                (public_key_to_address(key.public_key()), account)
+            })
+            .collect::<Vec<_>>();
+        let counter = Address::with_last_byte(0xc0);
+
+        let genesis = Block {
+            header: Header { gas_limit: 30_000_000, ..Default::default() },
+            body: Default::default(),
+        }
+        .seal_slow();
+        let mut blocks = vec![genesis];
+        for number in 1..=num_blocks {
+            let transactions = keys
+                .iter()
+                .enumerate()
+                .map(|(index, key)| {
+                    let (to, gas_limit, value) = if index % 2 == 0 {
+                        (counter, COUNTER_CALL_GAS, U256::ZERO)
+                    } else {
+                        let recipient = B256::from(U256::from(number << 32 | index as u64));
+                        (Address::from_word(recipient), TRANSFER_GAS, U256::from(1))
+                    };
+                    let tx = Transaction::Legacy(TxLegacy {
+                        chain_id: Some(1),
+                        nonce: number - 1,
+                        gas_price: 1,
+                        gas_limit,
+                        to: TxKind::Call(to),
+                        value,
+                        input: Bytes::default(),
+                    });
+                    sign_tx_with_key_pair(*key, tx)
+                })
+                .collect::<Vec<_>>();
+
+            let parent = blocks.last().expect("genesis");
+            let header = Header {
+                parent_hash: parent.hash(),
+                number,
+                timestamp: number * 12,
+                gas_limit: 30_000_000,
+                gas_used: transactions.iter().map(|tx| tx.gas_limit()).sum(),
+                transactions_root: proofs::calculate_transaction_root(&transactions),
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                ..Default::default()
+            };
+            let body = BlockBody { transactions, ..Default::default() };
+            blocks.push(Block { header, body }.seal_slow());
+        }
+
+        Self { senders, counter, blocks }
+    }
+
+    /// Inserts the blocks and the state before the first block, so that the execution stage can
+    /// run from genesis.
+    pub fn insert(&self, db: &TestStageDB) -> ProviderResult<()> {
+        db.insert_blocks(self.blocks.iter(), StorageKind::Static)?;
+
+        // The receipts of the genesis block are expected before executing from genesis.
+        let static_file_provider = db.factory.static_file_provider();
+        let mut receipts_writer =
+            static_file_provider.latest_writer(StaticFileSegment::Receipts)?;
+        receipts_writer.increment_block(0)?;
+        receipts_writer.commit()?;
+
+        let code_hash = keccak256(COUNTER_CODE);
+        db.commit(|tx| {
+            for (address, account) in &self.senders {
+                tx.put::<tables::PlainAccountState>(*address, *account)?;
+            }
+            tx.put::<tables::PlainAccountState>(
+                self.counter,
+                Account { bytecode_hash: Some(code_hash), ..Default::default() },
+            )?;
+            tx.put::<tables::Bytecodes>(
+                code_hash,
+                Bytecode::new_raw(Bytes::from_static(&COUNTER_CODE)),
+            )?;
+            Ok(())
+        })
+    }
+}
@@ -18,5 +18,8 @@ pub use test_db::{StorageKind, TestStageDB};
 mod set;
 pub use set::TestStages;
 
+mod chain;
+pub use chain::ExecutableChain;
+
 /// The test stage id
 pub const TEST_STAGE_ID: StageId = StageId::Other("TestStage");
@@ -0,0 +1,257 @@
+//! Consistency of the stage checkpoints, the static files and the canonical hashes.
+
+use alloy_primitives::BlockNumber;
+use reth_db_api::{cursor::DbCursorRO, tables, transaction::DbTx};
+use reth_provider::{DBProvider, ProviderResult, StageCheckpointReader, StaticFileProviderFactory};
+use reth_stages_types::StageId;
+use reth_static_file_types::StaticFileSegment;
+use std::fmt;
+use tracing::info;
+
+/// How to resolve inconsistencies that can't be healed on startup.
+pub const INCONSISTENCY_INSTRUCTIONS: &str = "Inspect the storage with `reth db \
+check-consistency`, then unwind the stages to a consistent block with `reth stage unwind \
+to-block <block>`, or resync the node.";
+
+/// The heights of the components of the storage of a node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageHeights {
+    /// The checkpoint of every stage in [`StageId::ALL`], zero if there is none.
+    pub stages: Vec<(StageId, BlockNumber)>,
+    /// The highest block of every static file segment, if there is any.
+    pub static_files: Vec<(StaticFileSegment, Option<BlockNumber>)>,
+    /// The last block in the [`tables::CanonicalHeaders`] table.
+    ///
+    /// Canonical hashes are stored in the headers static files, so the table is usually empty.
+    pub canonical_headers: Option<BlockNumber>,
+}
+
+impl StorageHeights {
+    /// Reads the heights of all components.
+    pub fn read<P>(provider: &P) -> ProviderResult<Self>
+    where
+        P: DBProvider + StageCheckpointReader + StaticFileProviderFactory,
+    {
+        let stages = StageId::ALL
+            .into_iter()
+            .map(|stage| {
+                let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default();
+                Ok((stage, checkpoint.block_number))
+            })
+            .collect::<ProviderResult<_>>()?;
+
+        let static_file_provider = provider.static_file_provider();
+        let static_files = StaticFileSegment::iter()
+            .map(|segment| (segment, static_file_provider.get_highest_static_file_block(segment)))
+            .collect();
+
+        let canonical_headers =
+            provider.tx_ref().cursor_read::<tables::CanonicalHeaders>()?.last()?.map(|(n, _)| n);
+
+        Ok(Self { stages, static_files, canonical_headers })
+    }
+
+    /// Returns the checkpoint of the stage.
+    pub fn stage(&self, stage: StageId) -> BlockNumber {
+        self.stages
+            .iter()
+            .find(|(id, _)| *id == stage)
+            .map(|(_, number)| *number)
+            .unwrap_or_default()
+    }
+
+    /// Returns the highest block of the static file segment.
+    pub fn static_file(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.static_files.iter().find(|(id, _)| *id == segment).and_then(|(_, number)| *number)
+    }
+
+    /// Returns the last block with a canonical hash, in the static files or the database.
+    pub fn canonical_tip(&self) -> Option<BlockNumber> {
+        self.static_file(StaticFileSegment::Headers).max(self.canonical_headers)
+    }
+
+    /// Returns the name and height of every component.
+    pub fn components(&self) -> Vec<(String, Option<BlockNumber>)> {
+        let stages =
+            self.stages.iter().map(|(stage, number)| (format!("Stage {stage}"), Some(*number)));
+        let static_files = self
+            .static_files
+            .iter()
+            .map(|(segment, number)| (format!("Static files {segment}"), *number));
+        stages
+            .chain(static_files)
+            .chain([("CanonicalHeaders table".to_string(), self.canonical_headers)])
+            .collect()
+    }
+
+    /// Returns the inconsistencies between the components.
+    pub fn inconsistencies(&self) -> Vec<Inconsistency> {
+        let mut inconsistencies = Vec::new();
+        let mut unwind_target: Option<BlockNumber> = None;
+
+        for (segment, highest) in &self.static_files {
+            // Receipts and senders can be written to the database instead of static files, so
+            // static files being behind the checkpoint is only an issue for headers and
+            // transactions.
+            let (stage, always_in_static_files) = match segment {
+                StaticFileSegment::Headers => (StageId::Headers, true),
+                StaticFileSegment::Transactions => (StageId::Bodies, true),
+                StaticFileSegment::Receipts => (StageId::Execution, false),
+                StaticFileSegment::TransactionSenders => (StageId::SenderRecovery, false),
+            };
+            let checkpoint = self.stage(stage);
+            match *highest {
+                Some(highest) if highest > checkpoint => inconsistencies.push(Inconsistency {
+                    healable: true,
+                    message: format!(
+                        "static files {segment} are ahead of the {stage} stage checkpoint \
+                         {checkpoint} at block {highest}, they are truncated to the checkpoint \
+                         on startup"
+                    ),
+                }),
+                Some(highest) if highest < checkpoint && always_in_static_files => {
+                    // The stages are unwound to the highest block, but not to the genesis block
+                    if highest > 0 {
+                        unwind_target = Some(unwind_target.map_or(highest, |t| t.min(highest)));
+                    }
+                    inconsistencies.push(Inconsistency {
+                        healable: highest > 0,
+                        message: format!(
+                            "static files {segment} are behind the {stage} stage checkpoint \
+                             {checkpoint} at block {highest}, the stages are unwound to block \
+                             {highest} on startup"
+                        ),
+                    })
+                }
+                None if checkpoint > 0 && always_in_static_files => {
+                    inconsistencies.push(Inconsistency {
+                        healable: false,
+                        message: format!(
+                            "static files {segment} are missing, but the {stage} stage checkpoint \
+                             is at block {checkpoint}"
+                        ),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        // The best block needs a canonical hash after unwinding, unless the database is empty
+        let best = self.stage(StageId::Finish).min(unwind_target.unwrap_or(BlockNumber::MAX));
+        if best > 0 && self.canonical_tip().is_none_or(|tip| tip < best) {
+            inconsistencies.push(Inconsistency {
+                healable: false,
+                message: format!(
+                    "the best block {best} is ahead of the last canonical hash at block {:?}",
+                    self.canonical_tip()
+                ),
+            });
+        }
+
+        inconsistencies
+    }
+
+    /// Logs the components whose height changed from `self` to `after`, after healing the
+    /// storage.
+    pub fn log_changes(&self, after: &Self) {
+        for ((component, before), (_, after)) in
+            self.components().into_iter().zip(after.components())
+        {
+            if before != after {
+                info!(
+                    target: "reth::cli",
+                    %component,
+                    ?before,
+                    ?after,
+                    "Healed storage inconsistency"
+                );
+            }
+        }
+    }
+}
+
+impl fmt::Display for StorageHeights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let components = self.components();
+        let width = components.iter().map(|(name, _)| name.len()).max().unwrap_or_default();
+        write!(f, "{:width$}  Height", "Component")?;
+        for (name, height) in components {
+            match height {
+                Some(height) => write!(f, "\n{name:width$}  {height}")?,
+                None => write!(f, "\n{name:width$}  -")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An inconsistency between the components of the storage, see
+/// [`StorageHeights::inconsistencies`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inconsistency {
+    /// Whether the inconsistency is healed on startup.
+    pub healable: bool,
+    /// Description of the inconsistency.
+    pub message: String,
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heights(headers: Option<BlockNumber>, checkpoint: BlockNumber) -> StorageHeights {
+        StorageHeights {
+            stages: StageId::ALL.into_iter().map(|stage| (stage, checkpoint)).collect(),
+            static_files: StaticFileSegment::iter()
+                .map(|segment| {
+                    let highest = match segment {
+                        StaticFileSegment::Headers => headers,
+                        StaticFileSegment::Transactions => Some(checkpoint),
+                        _ => None,
+                    };
+                    (segment, highest)
+                })
+                .collect(),
+            canonical_headers: None,
+        }
+    }
+
+    #[test]
+    fn classifies_inconsistencies() {
+        assert_eq!(heights(Some(10), 10).inconsistencies(), []);
+        assert_eq!(heights(Some(10), 10).canonical_tip(), Some(10));
+
+        // Partially written static files are truncated
+        let ahead = heights(Some(12), 10).inconsistencies();
+        assert_eq!(ahead.len(), 1);
+        assert!(ahead[0].healable);
+
+        // Static files behind the checkpoint are unwound
+        let behind = heights(Some(8), 10).inconsistencies();
+        assert_eq!(behind.len(), 1);
+        assert!(behind[0].healable);
+
+        // The best block needs a canonical hash
+        let mut without_hash = heights(Some(10), 10);
+        without_hash.stages.last_mut().unwrap().1 = 11;
+        let found = without_hash.inconsistencies();
+        assert_eq!(found.len(), 1);
+        assert!(!found[0].healable);
+        without_hash.canonical_headers = Some(11);
+        assert_eq!(without_hash.inconsistencies(), []);
+
+        let missing = heights(None, 10).inconsistencies();
+        assert!(missing.iter().all(|found| !found.healable));
+        assert_eq!(heights(None, 0).inconsistencies(), []);
+
+        let table = heights(Some(12), 10).to_string();
+        assert!(table.starts_with("Component"));
+        assert!(table.contains("\nStatic files Headers"));
+    }
+}
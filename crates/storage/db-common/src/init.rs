@@ -1,5 +1,6 @@
 //! Reth genesis initialization utility functions.
 
+use crate::migration::SCHEMA_VERSION;
 use alloy_consensus::BlockHeader;
 use alloy_genesis::GenesisAccount;
 use alloy_primitives::{keccak256, map::HashMap, Address, B256, U256};
@@ -191,22 +192,12 @@ where
     }
 
     // Static file segments start empty, so we need to initialize the genesis block.
-    let static_file_provider = provider_rw.static_file_provider();
-
-    // Static file segments start empty, so we need to initialize the genesis block.
-    // For genesis blocks with non-zero block numbers, we need to use get_writer() instead of
-    // latest_writer() to ensure the genesis block is stored in the correct static file range.
-    static_file_provider
-        .get_writer(genesis_block_number, StaticFileSegment::Receipts)?
-        .user_header_mut()
-        .set_block_range(genesis_block_number, genesis_block_number);
-    static_file_provider
-        .get_writer(genesis_block_number, StaticFileSegment::Transactions)?
-        .user_header_mut()
-        .set_block_range(genesis_block_number, genesis_block_number);
+    init_genesis_static_file_segments(&provider_rw, genesis_block_number)?;
 
     // Behaviour reserved only for new nodes should be set here.
     provider_rw.write_storage_settings(storage_settings)?;
+    // New databases don't need any migrations
+    provider_rw.write_schema_version(SCHEMA_VERSION)?;
 
     // `commit_unwind`` will first commit the DB and then the static file provider, which is
     // necessary on `init_genesis`.
@@ -216,6 +207,29 @@ where
     Ok(hash)
 }
 
+/// Initializes the block range of the static file segments that hold no data for the genesis
+/// block, so that the block after genesis can be appended to them.
+pub fn init_genesis_static_file_segments<Provider>(
+    provider: &Provider,
+    genesis_block_number: u64,
+) -> ProviderResult<()>
+where
+    Provider: StaticFileProviderFactory,
+{
+    let static_file_provider = provider.static_file_provider();
+
+    // For genesis blocks with non-zero block numbers, we need to use get_writer() instead of
+    // latest_writer() to ensure the genesis block is stored in the correct static file range.
+    for segment in [StaticFileSegment::Receipts, StaticFileSegment::Transactions] {
+        static_file_provider
+            .get_writer(genesis_block_number, segment)?
+            .user_header_mut()
+            .set_block_range(genesis_block_number, genesis_block_number);
+    }
+
+    Ok(())
+}
+
 /// Inserts the genesis state into the database.
 pub fn insert_genesis_state<'a, 'b, Provider>(
     provider: &Provider,
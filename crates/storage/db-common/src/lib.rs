@@ -8,7 +8,9 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+pub mod consistency;
 pub mod init;
+pub mod migration;
 
 mod db_tool;
 pub use db_tool::*;
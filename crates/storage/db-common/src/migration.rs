@@ -0,0 +1,225 @@
+//! Migrations of the tables of the database.
+//!
+//! The database records the version of the schema of its tables in the `Metadata` table. Every
+//! [`Migration`] upgrades the schema by one version, so a change to the encoding of a table can
+//! ship with a migration that rewrites the table instead of requiring a resync.
+//!
+//! A database that is initialized from genesis starts at [`SCHEMA_VERSION`], and a database that
+//! predates schema versions is at version `0`.
+//!
+//! Migrations that only add data are applied whenever the node starts, while the ones that rewrite
+//! existing data, see [`Migration::is_destructive`], have to be applied explicitly.
+
+use reth_provider::{
+    DBProvider, DatabaseProviderFactory, MetadataProvider, MetadataWriter, ProviderError,
+    ProviderResult, StorageSettings,
+};
+use std::{fmt, time::Instant};
+use tracing::info;
+
+/// The schema version that this binary expects, which is the version of the last migration.
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// A migration of the tables of the database.
+///
+/// Every migration runs in its own transaction, which also records its version, so a migration
+/// that fails leaves the database at the version before it.
+pub trait Migration<Provider>: fmt::Debug + Send + Sync {
+    /// The schema version that the migration upgrades the database to.
+    fn id(&self) -> u64;
+
+    /// A description of the migration, which is logged when it's applied.
+    fn description(&self) -> &'static str;
+
+    /// Whether the migration rewrites or deletes existing data, so it can't be undone by a
+    /// downgrade of the binary.
+    fn is_destructive(&self) -> bool;
+
+    /// Applies the migration with the read-write provider, without committing it.
+    fn up(&self, provider: &Provider) -> ProviderResult<()>;
+}
+
+/// Returns all migrations in the order they are applied.
+pub fn migrations<Provider>() -> Vec<Box<dyn Migration<Provider>>>
+where
+    Provider: MetadataProvider + MetadataWriter,
+{
+    vec![Box::new(PersistStorageSettings)]
+}
+
+/// Error of applying migrations.
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    /// The database was migrated by a newer binary.
+    #[error(
+        "database schema version {version} is newer than version {SCHEMA_VERSION} that this binary supports"
+    )]
+    UnsupportedVersion {
+        /// The schema version of the database.
+        version: u64,
+    },
+    /// A pending migration is destructive, and destructive migrations are not allowed.
+    #[error("migration {id} is destructive: {description}")]
+    Destructive {
+        /// The schema version that the migration upgrades the database to.
+        id: u64,
+        /// The description of the migration.
+        description: &'static str,
+    },
+    /// Provider error.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+/// Returns the schema version of the database.
+pub fn schema_version<Provider: MetadataProvider>(provider: &Provider) -> ProviderResult<u64> {
+    Ok(provider.schema_version()?.unwrap_or_default())
+}
+
+/// Returns the migrations that have not been applied to the database yet.
+pub fn pending_migrations<PF>(
+    factory: &PF,
+) -> Result<Vec<Box<dyn Migration<PF::ProviderRW>>>, MigrationError>
+where
+    PF: DatabaseProviderFactory<Provider: MetadataProvider>,
+    PF::ProviderRW: MetadataProvider + MetadataWriter,
+{
+    let version = schema_version(&factory.database_provider_ro()?)?;
+    if version > SCHEMA_VERSION {
+        return Err(MigrationError::UnsupportedVersion { version })
+    }
+    Ok(migrations().into_iter().filter(|migration| migration.id() > version).collect())
+}
+
+/// Applies the pending migrations in order, and returns how many were applied.
+///
+/// Unless `allow_destructive` is set, this stops with [`MigrationError::Destructive`] at the first
+/// destructive migration, after applying the ones before it.
+pub fn migrate<PF>(factory: &PF, allow_destructive: bool) -> Result<usize, MigrationError>
+where
+    PF: DatabaseProviderFactory<Provider: MetadataProvider>,
+    PF::ProviderRW: MetadataProvider + MetadataWriter,
+{
+    let pending = pending_migrations(factory)?;
+    let total = pending.len();
+    for (index, migration) in pending.into_iter().enumerate() {
+        if migration.is_destructive() && !allow_destructive {
+            return Err(MigrationError::Destructive {
+                id: migration.id(),
+                description: migration.description(),
+            })
+        }
+        info!(
+            target: "reth::storage",
+            id = migration.id(),
+            description = migration.description(),
+            progress = format_args!("{}/{total}", index + 1),
+            "Applying migration"
+        );
+        let start = Instant::now();
+
+        let provider_rw = factory.database_provider_rw()?;
+        migration.up(&provider_rw)?;
+        provider_rw.write_schema_version(migration.id())?;
+        provider_rw.commit()?;
+
+        info!(target: "reth::storage", id = migration.id(), elapsed = ?start.elapsed(), "Applied migration");
+    }
+    Ok(total)
+}
+
+/// Persists the legacy storage settings of databases that were created before storage settings
+/// were recorded, which are otherwise assumed whenever the database is opened.
+#[derive(Debug, Clone, Copy)]
+pub struct PersistStorageSettings;
+
+impl<Provider> Migration<Provider> for PersistStorageSettings
+where
+    Provider: MetadataProvider + MetadataWriter,
+{
+    fn id(&self) -> u64 {
+        1
+    }
+
+    fn description(&self) -> &'static str {
+        "Persist the legacy storage settings"
+    }
+
+    fn is_destructive(&self) -> bool {
+        false
+    }
+
+    fn up(&self, provider: &Provider) -> ProviderResult<()> {
+        if provider.storage_settings()?.is_none() {
+            provider.write_storage_settings(StorageSettings::legacy())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::init_genesis;
+    use reth_db_api::{tables, transaction::DbTxMut};
+    use reth_provider::{
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        ProviderFactory,
+    };
+
+    type ProviderRW = <ProviderFactory<MockNodeTypesWithDB> as DatabaseProviderFactory>::ProviderRW;
+
+    #[test]
+    fn versions_are_consecutive() {
+        let ids =
+            migrations::<ProviderRW>().iter().map(|migration| migration.id()).collect::<Vec<_>>();
+        assert_eq!(ids, (1..=SCHEMA_VERSION).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn fresh_database_is_at_latest_version() {
+        let factory = create_test_provider_factory();
+        init_genesis(&factory).unwrap();
+
+        assert_eq!(
+            schema_version(&factory.database_provider_ro().unwrap()).unwrap(),
+            SCHEMA_VERSION
+        );
+        assert!(pending_migrations(&factory).unwrap().is_empty());
+        assert_eq!(migrate(&factory, false).unwrap(), 0);
+    }
+
+    #[test]
+    fn old_database_is_upgraded() {
+        let factory = create_test_provider_factory();
+        init_genesis(&factory).unwrap();
+
+        // A database that predates schema versions and storage settings
+        let provider_rw = factory.provider_rw().unwrap();
+        for key in ["schema_version", "storage_settings"] {
+            provider_rw.tx_ref().delete::<tables::Metadata>(key.to_string(), None).unwrap();
+        }
+        provider_rw.commit().unwrap();
+        assert_eq!(schema_version(&factory.database_provider_ro().unwrap()).unwrap(), 0);
+        assert_eq!(pending_migrations(&factory).unwrap().len(), 1);
+
+        // Persisting the legacy settings only adds data, so it's applied without being allowed
+        assert_eq!(migrate(&factory, false).unwrap(), 1);
+        let provider = factory.database_provider_ro().unwrap();
+        assert_eq!(schema_version(&provider).unwrap(), SCHEMA_VERSION);
+        assert_eq!(provider.storage_settings().unwrap(), Some(StorageSettings::legacy()));
+    }
+
+    #[test]
+    fn newer_database_is_rejected() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.write_schema_version(SCHEMA_VERSION + 1).unwrap();
+        provider_rw.commit().unwrap();
+
+        assert!(matches!(
+            pending_migrations(&factory),
+            Err(MigrationError::UnsupportedVersion { version }) if version == SCHEMA_VERSION + 1
+        ));
+    }
+}
@@ -126,6 +126,10 @@ pub enum ProviderError {
     /// Static File Provider was initialized as read-only.
     #[error("cannot get a writer on a read-only environment.")]
     ReadOnlyStaticFileAccess,
+    /// The static files of a segment changed during every attempt to read a range that spans
+    /// static files and the database, or the read returned more rows than the range has.
+    #[error("{_0} static files changed while reading a range")]
+    StaticFileRangeChanged(StaticFileSegment),
     /// Consistent view error.
     #[error("failed to initialize consistent view: {_0}")]
     ConsistentView(Box<ConsistentViewError>),
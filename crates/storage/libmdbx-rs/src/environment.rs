@@ -227,6 +227,25 @@ impl Environment {
     }
 }
 
+/// Converts the path to the C string that mdbx expects, which may not contain the null character.
+pub(crate) fn path_to_cstring(path: &Path) -> Result<CString> {
+    #[cfg(unix)]
+    fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_ref().as_os_str().as_bytes().to_vec()
+    }
+
+    #[cfg(windows)]
+    fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+        // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+        // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+        // really help.
+        path.as_ref().to_string_lossy().to_string().into_bytes()
+    }
+
+    CString::new(path_to_bytes(path)).map_err(|_| Error::Invalid)
+}
+
 /// Container type for Environment internals.
 ///
 /// This holds the raw pointer to the MDBX environment and the transaction manager.
@@ -690,24 +709,7 @@ impl EnvironmentBuilder {
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
-                let path = match CString::new(path_to_bytes(path)) {
-                    Ok(path) => path,
-                    Err(_) => return Err(Error::Invalid),
-                };
+                let path = path_to_cstring(path)?;
                 mdbx_result(ffi::mdbx_env_open(
                     env,
                     path.as_ptr(),
@@ -1,8 +1,8 @@
 use crate::{
     database::Database,
-    environment::Environment,
+    environment::{path_to_cstring, Environment},
     error::{mdbx_result, Result},
-    flags::{DatabaseFlags, WriteFlags},
+    flags::{CopyFlags, DatabaseFlags, WriteFlags},
     txn_manager::{TxnManagerMessage, TxnPtr},
     Cursor, Error, Stat, TableObject,
 };
@@ -11,7 +11,8 @@ use parking_lot::{Mutex, MutexGuard};
 use std::{
     ffi::{c_uint, c_void},
     fmt::{self, Debug},
-    mem::size_of,
+    mem::{self, size_of},
+    path::Path,
     ptr, slice,
     sync::{atomic::AtomicBool, mpsc::sync_channel, Arc},
     time::Duration,
@@ -142,6 +143,17 @@ where
         self.txn_execute(|txn| unsafe { ffi::mdbx_txn_id(txn) })
     }
 
+    /// Returns the total size in bytes of the database pages dirtied by the transaction so far.
+    ///
+    /// For read-only transactions, this is always zero.
+    pub fn space_dirty(&self) -> Result<u64> {
+        unsafe {
+            let mut info: ffi::MDBX_txn_info = mem::zeroed();
+            self.txn_execute(|txn| mdbx_result(ffi::mdbx_txn_info(txn, &mut info, false)))??;
+            Ok(if K::IS_READ_ONLY { 0 } else { info.txn_space_dirty })
+        }
+    }
+
     /// Gets an item from a database.
     ///
     /// This function retrieves the data associated with the given key in the
@@ -237,6 +249,18 @@ where
         }
     }
 
+    /// Copies the snapshot of the environment that this transaction reads to a new file at the
+    /// path, which must not exist yet.
+    ///
+    /// The transaction is kept open for the whole copy.
+    pub fn copy_to_path(&self, path: &Path, flags: CopyFlags) -> Result<()> {
+        let path = path_to_cstring(path)?;
+        mdbx_result(self.txn_execute(|txn| unsafe {
+            ffi::mdbx_txn_copy2pathname(txn, path.as_ptr(), flags.bits())
+        })?)?;
+        Ok(())
+    }
+
     /// Open a new cursor on the given database.
     pub fn cursor(&self, db: &Database) -> Result<Cursor<K>> {
         Cursor::new(self.clone(), db.dbi())
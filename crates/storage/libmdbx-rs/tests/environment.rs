@@ -167,3 +167,40 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_compact() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    // Write values and delete half of them, which leaves pages on the freelist.
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    for i in 0..1024u64 {
+        tx.put(db.dbi(), i.to_be_bytes(), [0; 512], WriteFlags::default()).expect("tx.put");
+    }
+    tx.commit().expect("tx.commit");
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    for i in (0..1024u64).step_by(2) {
+        tx.del(db.dbi(), i.to_be_bytes(), None).expect("tx.del");
+    }
+    tx.commit().expect("tx.commit");
+    assert!(env.freelist().unwrap() > 0);
+
+    let copy_dir = tempdir().unwrap();
+    let tx = env.begin_ro_txn().expect("begin_ro_txn");
+    tx.copy_to_path(&copy_dir.path().join("mdbx.dat"), CopyFlags::COMPACT).expect("copy");
+    // The destination file must not exist.
+    assert!(tx.copy_to_path(&copy_dir.path().join("mdbx.dat"), CopyFlags::COMPACT).is_err());
+
+    let copy = Environment::builder().open(copy_dir.path()).unwrap();
+    assert_eq!(copy.freelist().unwrap(), 0);
+    assert!(copy.info().unwrap().last_pgno() < env.info().unwrap().last_pgno());
+
+    let tx = copy.begin_ro_txn().expect("begin_ro_txn");
+    let db = tx.open_db(None).unwrap();
+    assert_eq!(tx.db_stat(&db).unwrap().entries(), 512);
+    assert_eq!(tx.get::<[u8; 512]>(db.dbi(), &1u64.to_be_bytes()).unwrap(), Some([0; 512]));
+    assert_eq!(tx.get::<()>(db.dbi(), &2u64.to_be_bytes()).unwrap(), None);
+}
@@ -31,6 +31,23 @@ fn test_put_get_del() {
     assert_eq!(txn.get::<()>(db.dbi(), b"key1").unwrap(), None);
 }
 
+#[test]
+fn test_space_dirty() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_txn().unwrap();
+    let db = txn.open_db(None).unwrap();
+    let before = txn.space_dirty().unwrap();
+    for i in 0..1000u32 {
+        txn.put(db.dbi(), i.to_be_bytes(), [0; 100], WriteFlags::empty()).unwrap();
+    }
+    assert!(txn.space_dirty().unwrap() > before);
+    txn.commit().unwrap();
+
+    assert_eq!(env.begin_ro_txn().unwrap().space_dirty().unwrap(), 0);
+}
+
 #[test]
 fn test_put_get_del_multi() {
     let dir = tempdir().unwrap();
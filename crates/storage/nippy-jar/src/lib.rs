@@ -52,7 +52,7 @@ mod consistency;
 pub use consistency::NippyJarChecker;
 
 /// The version number of the Nippy Jar format.
-const NIPPY_JAR_VERSION: usize = 1;
+pub const NIPPY_JAR_VERSION: usize = 1;
 /// The file extension used for index files.
 const INDEX_FILE_EXTENSION: &str = "idx";
 /// The file extension used for offsets files.
@@ -6,6 +6,7 @@ use std::{
     fs::{File, OpenOptions},
     io::{BufWriter, Read, Seek, SeekFrom, Write},
     path::Path,
+    time::{Duration, Instant},
 };
 
 /// Size of one offset in bytes.
@@ -43,6 +44,8 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     column: usize,
     /// Whether the writer has changed data that needs to be committed.
     dirty: bool,
+    /// Time spent syncing the data and offsets files to disk by the last commit.
+    sync_duration: Duration,
 }
 
 impl<H: NippyJarHeader> NippyJarWriter<H> {
@@ -79,6 +82,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
             offsets: Vec::with_capacity(1_000_000),
             column: 0,
             dirty: false,
+            sync_duration: Duration::ZERO,
         };
 
         if !is_created {
@@ -103,6 +107,12 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         &mut self.jar.user_header
     }
 
+    /// Returns the time spent syncing the data and offsets files to disk by the last
+    /// [`Self::commit`].
+    pub const fn sync_duration(&self) -> Duration {
+        self.sync_duration
+    }
+
     /// Returns whether there are changes that need to be committed.
     pub const fn is_dirty(&self) -> bool {
         self.dirty
@@ -348,7 +358,9 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Commits configuration and offsets to disk. It drains the internal offset list.
     pub fn commit(&mut self) -> Result<(), NippyJarError> {
         self.data_file.flush()?;
+        let started = Instant::now();
         self.data_file.get_ref().sync_all()?;
+        self.sync_duration = started.elapsed();
 
         self.commit_offsets()?;
 
@@ -379,7 +391,9 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Flushes offsets to disk.
     pub(crate) fn commit_offsets(&mut self) -> Result<(), NippyJarError> {
         self.commit_offsets_inner()?;
+        let started = Instant::now();
         self.offsets_file.get_ref().sync_all()?;
+        self.sync_duration += started.elapsed();
 
         Ok(())
     }
@@ -0,0 +1,47 @@
+#![allow(missing_docs)]
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use reth_provider::test_utils::create_test_provider_factory;
+use reth_storage_api::{BlockWriter, DBProvider, HeaderProvider};
+use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = header_blooms
+}
+criterion_main!(benches);
+
+/// Number of blocks in the database.
+const BLOCKS: u64 = 10_000;
+
+// Compares loading the logs blooms of a range of blocks with loading the sealed headers, which
+// also reads the block hashes, and with loading the headers.
+fn header_blooms(c: &mut Criterion) {
+    let mut group = c.benchmark_group("HeaderBlooms");
+
+    let mut rng = generators::rng();
+    let factory = create_test_provider_factory();
+    let blocks = random_block_range(
+        &mut rng,
+        0..=BLOCKS - 1,
+        BlockRangeParams { tx_count: 0..1, ..Default::default() },
+    );
+
+    let provider_rw = factory.provider_rw().unwrap();
+    for block in blocks {
+        provider_rw.insert_block(block.try_recover().unwrap()).unwrap();
+    }
+    provider_rw.commit().unwrap();
+
+    let provider = factory.provider().unwrap();
+    group.bench_function("sealed headers", |b| {
+        b.iter(|| provider.sealed_headers_range(0..BLOCKS).unwrap());
+    });
+    group.bench_function("headers", |b| {
+        b.iter(|| provider.headers_range(0..BLOCKS).unwrap());
+    });
+    group.bench_function("blooms", |b| {
+        b.iter(|| provider.block_bloom_range(0..BLOCKS).unwrap());
+    });
+}
@@ -0,0 +1,67 @@
+#![allow(missing_docs)]
+
+use alloy_consensus::Header;
+use alloy_primitives::{Address, BloomInput, B256, U256};
+use alloy_rpc_types_eth::Filter;
+use criterion::{criterion_group, criterion_main, Criterion};
+use reth_provider::{
+    test_utils::MockEthProvider, BloomIndex, DatabaseProviderROFactory, ParallelLogScanner,
+    ProviderResult, BLOOM_INDEX_CHUNK_SIZE,
+};
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = log_scan
+}
+criterion_main!(benches);
+
+/// Number of indexed chunks of blocks.
+const CHUNKS: u64 = 8;
+
+struct MockFactory(MockEthProvider);
+
+impl DatabaseProviderROFactory for MockFactory {
+    type Provider = MockEthProvider;
+
+    fn database_provider_ro(&self) -> ProviderResult<Self::Provider> {
+        Ok(self.0.clone())
+    }
+}
+
+// Repeats a scan for an address that only appears in the blooms of the headers of the last
+// chunk. Without the bloom index every header is read to check its bloom, with the index only
+// the headers of the last chunk are.
+fn log_scan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("LogScan");
+
+    let address = Address::repeat_byte(1);
+    let mut bloom = alloy_primitives::Bloom::ZERO;
+    bloom.accrue(BloomInput::Raw(address.as_slice()));
+
+    let provider = MockEthProvider::default();
+    let end = CHUNKS * BLOOM_INDEX_CHUNK_SIZE - 1;
+    for number in 0..=end {
+        // The receipts are empty, so they are read but don't contain matching logs
+        let logs_bloom = if number == end { bloom } else { Default::default() };
+        provider.add_header(
+            B256::from(U256::from(number)),
+            Header { number, logs_bloom, ..Default::default() },
+        );
+        provider.add_receipts(number, Vec::new());
+    }
+
+    let mut index = BloomIndex::default();
+    index.update(&provider, end).unwrap();
+
+    let filter = Filter::new().address(address);
+    let scanner = ParallelLogScanner::new(MockFactory(provider.clone()));
+    group.bench_function("without index", |b| {
+        b.iter(|| scanner.scan(0..=end, &filter, |_| {}).unwrap());
+    });
+
+    let scanner = ParallelLogScanner::new(MockFactory(provider)).with_bloom_index(index);
+    group.bench_function("with index", |b| {
+        b.iter(|| scanner.scan(0..=end, &filter, |_| {}).unwrap());
+    });
+}
@@ -0,0 +1,56 @@
+#![allow(missing_docs)]
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_ethereum_primitives::Receipt;
+use reth_provider::test_utils::create_test_provider_factory;
+use reth_storage_api::{BlockWriter, DBProvider, ReceiptProvider};
+use reth_testing_utils::generators::{self, random_block_range, random_receipt, BlockRangeParams};
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = receipts_range
+}
+criterion_main!(benches);
+
+/// Number of blocks in the database.
+const BLOCKS: u64 = 10_000;
+
+// Compares loading the receipts of a range of blocks one block at a time with loading them with a
+// single range query. Every third block is empty on average.
+fn receipts_range(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ReceiptsByBlockRange");
+
+    let mut rng = generators::rng();
+    let factory = create_test_provider_factory();
+    let blocks = random_block_range(
+        &mut rng,
+        0..=BLOCKS - 1,
+        BlockRangeParams { tx_count: 0..3, ..Default::default() },
+    );
+
+    let provider_rw = factory.provider_rw().unwrap();
+    let mut tx_num = 0;
+    for block in blocks {
+        for transaction in &block.body().transactions {
+            let receipt = random_receipt(&mut rng, transaction, Some(1), Some(1));
+            provider_rw.tx_ref().put::<tables::Receipts<Receipt>>(tx_num, receipt).unwrap();
+            tx_num += 1;
+        }
+        provider_rw.insert_block(block.try_recover().unwrap()).unwrap();
+    }
+    provider_rw.commit().unwrap();
+
+    let provider = factory.provider().unwrap();
+    group.bench_function("per block", |b| {
+        b.iter(|| {
+            (0..BLOCKS)
+                .map(|number| provider.receipts_by_block(number.into()).unwrap().unwrap())
+                .collect::<Vec<_>>()
+        });
+    });
+    group.bench_function("range", |b| {
+        b.iter(|| provider.receipts_by_block_range(0..=BLOCKS - 1).unwrap());
+    });
+}
@@ -7,12 +7,16 @@ use crate::{
     providers::{NodeTypesForProvider, RocksDBProvider, StaticFileProvider},
     ProviderFactory,
 };
+use eyre::OptionExt;
+use itertools::Itertools;
+use reth_chainspec::EthChainSpec;
 use reth_db::{
     mdbx::{DatabaseArguments, MaxReadTransactionDuration},
     open_db_read_only, DatabaseEnv,
 };
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
 use reth_node_types::{NodeTypes, NodeTypesWithDBAdapter};
+use reth_storage_api::BlockHashReader;
 use reth_storage_errors::provider::ProviderResult;
 use std::{
     marker::PhantomData,
@@ -113,6 +117,62 @@ impl<N> ProviderFactoryBuilder<N> {
             .build_provider_factory()
             .map_err(Into::into)
     }
+
+    /// Opens the database like [`Self::open_read_only`], with the first of the `chainspecs` whose
+    /// genesis hash matches the genesis block in the database.
+    ///
+    /// Passing a single chainspec verifies that the database belongs to its chain. Fails if the
+    /// database has no genesis block, or if none of the chainspecs match it.
+    ///
+    /// ```no_run
+    /// use reth_chainspec::{HOLESKY, MAINNET, SEPOLIA};
+    /// use reth_provider::providers::{NodeTypesForProvider, ProviderFactoryBuilder};
+    ///
+    /// fn demo<N: NodeTypesForProvider<ChainSpec = reth_chainspec::ChainSpec>>() {
+    ///     let provider_factory = ProviderFactoryBuilder::<N>::default()
+    ///         .open_read_only_matching_genesis(
+    ///             [MAINNET.clone(), SEPOLIA.clone(), HOLESKY.clone()],
+    ///             "datadir",
+    ///         )
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn open_read_only_matching_genesis(
+        self,
+        chainspecs: impl IntoIterator<Item = Arc<N::ChainSpec>>,
+        config: impl Into<ReadOnlyConfig>,
+    ) -> eyre::Result<ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>>
+    where
+        N: NodeTypesForProvider,
+    {
+        let chainspecs = chainspecs.into_iter().collect::<Vec<_>>();
+        let Some(first) = chainspecs.first() else {
+            eyre::bail!("no chainspec to match the database against")
+        };
+
+        let factory = self.open_read_only(first.clone(), config)?;
+        let genesis_hash =
+            factory.provider()?.block_hash(0)?.ok_or_eyre("the database has no genesis block")?;
+
+        if let Some(chainspec) =
+            chainspecs.iter().find(|chainspec| chainspec.genesis_hash() == genesis_hash)
+        {
+            return Ok(factory.with_chain_spec(chainspec.clone()))
+        }
+
+        match chainspecs.as_slice() {
+            [chainspec] => eyre::bail!(
+                "genesis hash {genesis_hash} of the database doesn't match the genesis hash {} of \
+                 chain {}",
+                chainspec.genesis_hash(),
+                chainspec.chain()
+            ),
+            _ => eyre::bail!(
+                "genesis hash {genesis_hash} of the database doesn't match any of the chains {}",
+                chainspecs.iter().map(|chainspec| chainspec.chain().to_string()).format(", ")
+            ),
+        }
+    }
 }
 
 impl<N> Default for ProviderFactoryBuilder<N> {
@@ -373,3 +433,51 @@ where
         ProviderFactory::new(val_1, val_2, val_3, val_4)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockNodeTypes;
+    use alloy_primitives::B256;
+    use reth_chainspec::{ChainSpecProvider, MAINNET, SEPOLIA};
+    use reth_db::init_db;
+    use reth_db_api::{tables, transaction::DbTxMut};
+
+    /// Creates a datadir with the genesis hash of mainnet, if given.
+    fn init_datadir(genesis_hash: Option<B256>) -> tempfile::TempDir {
+        let datadir = tempfile::tempdir().unwrap();
+        let db = init_db(datadir.path().join("db"), Default::default()).unwrap();
+        StaticFileProvider::<reth_ethereum_primitives::EthPrimitives>::read_write(
+            datadir.path().join("static_files"),
+        )
+        .unwrap();
+        if let Some(genesis_hash) = genesis_hash {
+            let tx = db.tx_mut().unwrap();
+            tx.put::<tables::CanonicalHeaders>(0, genesis_hash).unwrap();
+            tx.commit().unwrap();
+        }
+        datadir
+    }
+
+    #[test]
+    fn open_read_only_matching_genesis() {
+        let datadir = init_datadir(Some(MAINNET.genesis_hash()));
+        let builder = ProviderFactoryBuilder::<MockNodeTypes>::default;
+
+        let factory = builder()
+            .open_read_only_matching_genesis([SEPOLIA.clone(), MAINNET.clone()], datadir.path())
+            .unwrap();
+        assert_eq!(factory.chain_spec().chain(), MAINNET.chain());
+        drop(factory);
+
+        let err = builder()
+            .open_read_only_matching_genesis([SEPOLIA.clone()], datadir.path())
+            .unwrap_err();
+        assert!(err.to_string().contains("of chain sepolia"), "{err}");
+
+        let empty = init_datadir(None);
+        let err =
+            builder().open_read_only_matching_genesis([MAINNET.clone()], empty.path()).unwrap_err();
+        assert_eq!(err.to_string(), "the database has no genesis block");
+    }
+}
@@ -127,6 +127,12 @@ impl<N: NodeTypesWithDB> ProviderFactory<N> {
         self
     }
 
+    /// Sets the chain spec for an existing [`ProviderFactory`].
+    pub fn with_chain_spec(mut self, chain_spec: Arc<N::ChainSpec>) -> Self {
+        self.chain_spec = chain_spec;
+        self
+    }
+
     /// Returns reference to the underlying database.
     pub const fn db_ref(&self) -> &N::DB {
         &self.db
@@ -531,7 +537,7 @@ impl<N: ProviderNodeTypes> ReceiptProvider for ProviderFactory<N> {
             to_range(range),
             |static_file, range, _| static_file.receipts_by_tx_range(range),
             |range, _| self.provider()?.receipts_by_tx_range(range),
-            |_| true,
+            || |_| true,
         )
     }
 
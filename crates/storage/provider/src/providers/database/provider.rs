@@ -647,12 +647,20 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         // have enough information to return the block anyways, so
         // we skip the block.
         let present_headers = self
-            .block_body_indices_range(range)?
+            .block_body_indices_range(range.clone())?
             .into_iter()
             .map(|b| b.tx_num_range())
             .zip(headers)
             .collect::<Vec<_>>();
 
+        // The headers and transactions are read from static files, while the body indices are
+        // read from the database, so make sure that they describe the same blocks
+        for (number, (_, header)) in range.zip(&present_headers) {
+            if header.as_ref().number() != number {
+                return Err(ProviderError::HeaderNotFound(number.into()))
+            }
+        }
+
         let mut inputs = Vec::new();
         for (tx_range, header) in &present_headers {
             let transactions = if tx_range.is_empty() {
@@ -660,6 +668,13 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
             } else {
                 self.transactions_by_tx_range(tx_range.clone())?
             };
+            ensure_range_len(
+                StaticFileSegment::Transactions,
+                tx_range.start,
+                transactions.len(),
+                tx_range.end - tx_range.start,
+                |tx| ProviderError::TransactionNotFound(tx.into()),
+            )?;
 
             inputs.push((header.as_ref(), transactions));
         }
@@ -1328,17 +1343,33 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
     ) -> ProviderResult<Vec<Vec<Self::Transaction>>> {
         let range = to_range(range);
 
-        self.block_body_indices_range(range.start..=range.end.saturating_sub(1))?
-            .into_iter()
-            .map(|body| {
-                let tx_num_range = body.tx_num_range();
-                if tx_num_range.is_empty() {
-                    Ok(Vec::new())
-                } else {
-                    self.transactions_by_tx_range(tx_num_range)
-                }
-            })
-            .collect()
+        let bodies = self.block_body_indices_range(range.start..=range.end.saturating_sub(1))?;
+        let (Some(first), Some(last)) = (bodies.first(), bodies.last()) else {
+            return Ok(Vec::new())
+        };
+
+        // Read the transactions of all blocks at once, so that they come from the same view of the
+        // static files, and make sure that none went missing, otherwise transactions would be
+        // assigned to the wrong blocks
+        let tx_range = first.first_tx_num()..last.next_tx_num();
+        let transactions = if tx_range.is_empty() {
+            Vec::new()
+        } else {
+            self.transactions_by_tx_range(tx_range.clone())?
+        };
+        ensure_range_len(
+            StaticFileSegment::Transactions,
+            tx_range.start,
+            transactions.len(),
+            tx_range.end - tx_range.start,
+            |tx| ProviderError::TransactionNotFound(tx.into()),
+        )?;
+
+        let mut transactions = transactions.into_iter();
+        Ok(bodies
+            .iter()
+            .map(|body| transactions.by_ref().take(body.tx_count() as usize).collect())
+            .collect())
     }
 
     fn transactions_by_tx_range(
@@ -1414,7 +1445,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
             to_range(range),
             |static_file, range, _| static_file.receipts_by_tx_range(range),
             |range, _| self.cursor_read_collect::<tables::Receipts<Self::Receipt>>(range),
-            |_| true,
+            || |_| true,
         )
     }
 
@@ -1426,16 +1457,18 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
             return Ok(Vec::new());
         }
 
-        // collect block body indices for each block in the range
+        // collect block body indices for each block in the range with a single cursor walk,
+        // instead of seeking for every block
+        let (start, end) = (*block_range.start(), *block_range.end());
         let mut block_body_indices = Vec::new();
-        for block_num in block_range {
-            if let Some(indices) = self.block_body_indices(block_num)? {
-                block_body_indices.push(indices);
-            } else {
-                // use default indices for missing blocks (empty block)
-                block_body_indices.push(StoredBlockBodyIndices::default());
-            }
+        for entry in self.tx.cursor_read::<tables::BlockBodyIndices>()?.walk_range(block_range)? {
+            let (block_num, indices) = entry?;
+            // use default indices for missing blocks (empty block)
+            block_body_indices
+                .resize((block_num - start) as usize, StoredBlockBodyIndices::default());
+            block_body_indices.push(indices);
         }
+        block_body_indices.resize((end - start) as usize + 1, StoredBlockBodyIndices::default());
 
         if block_body_indices.is_empty() {
             return Ok(Vec::new());
@@ -1456,6 +1489,19 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
 
         // fetch all receipts in the transaction range
         let all_receipts = self.receipts_by_tx_range(first_tx..=last_tx)?;
+
+        // the range may be stitched together from static files and the database, so make sure
+        // that no receipt went missing or was read twice, otherwise receipts would be assigned to
+        // the wrong blocks
+        let expected_receipts: u64 = non_empty_blocks.iter().map(|indices| indices.tx_count).sum();
+        ensure_range_len(
+            StaticFileSegment::Receipts,
+            first_tx,
+            all_receipts.len(),
+            expected_receipts,
+            |tx| ProviderError::ReceiptNotFound(tx.into()),
+        )?;
+
         let mut receipts_iter = all_receipts.into_iter();
 
         // distribute receipts to their respective blocks
@@ -1654,7 +1700,20 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             ));
         }
 
-        let mut receipts_writer = EitherWriter::new_receipts(self, first_block)?;
+        // Receipts that are in static files already, e.g. because they were imported from era1
+        // files, are not written again.
+        let first_receipts_block = if EitherWriter::receipts_destination(self).is_static_file() {
+            self.static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::Receipts)
+                .map_or(first_block, |highest| first_block.max(highest + 1))
+        } else {
+            first_block
+        };
+        if first_receipts_block > last_block {
+            return Ok(())
+        }
+
+        let mut receipts_writer = EitherWriter::new_receipts(self, first_receipts_block)?;
 
         let has_contract_log_filter = !self.prune_modes.receipts_log_filter.is_empty();
         let contract_log_pruner = self.prune_modes.receipts_log_filter.group_by_block(tip, None)?;
@@ -1682,6 +1741,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             execution_outcome.receipts.iter().zip(block_indices).enumerate()
         {
             let block_number = first_block + idx as u64;
+            if block_number < first_receipts_block {
+                continue
+            }
 
             // Increment block number for receipts static file writer
             receipts_writer.increment_block(block_number)?;
@@ -2091,7 +2153,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                         .map(|r| r.map_err(Into::into))
                         .collect()
                 },
-                |_| true,
+                || |_| true,
             )?
             .into_iter()
             .peekable();
@@ -3218,12 +3280,35 @@ impl<TX: Send + Sync, N: NodeTypes> StorageSettingsCache for DatabaseProvider<TX
     }
 }
 
+/// Makes sure that a read of the rows of a segment starting at `first`, which may be stitched
+/// together from static files and the database, returned exactly the `expected` number of rows
+/// given by the block body indices.
+///
+/// Otherwise, the rows would be assigned to the wrong blocks. A missing row is reported with
+/// `not_found` for its number, and surplus rows, e.g. a row that was read from both static files
+/// and the database, with [`ProviderError::StaticFileRangeChanged`].
+fn ensure_range_len(
+    segment: StaticFileSegment,
+    first: u64,
+    len: usize,
+    expected: u64,
+    not_found: impl FnOnce(u64) -> ProviderError,
+) -> ProviderResult<()> {
+    match (len as u64).cmp(&expected) {
+        Ordering::Less => Err(not_found(first + len as u64)),
+        Ordering::Greater => Err(ProviderError::StaticFileRangeChanged(segment)),
+        Ordering::Equal => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
-        BlockWriter,
+        test_utils::{
+            blocks::BlockchainTestData, create_test_provider_factory, MockNodeTypesWithDB,
+        },
+        BlockWriter, ProviderFactory,
     };
     use reth_ethereum_primitives::Receipt;
     use reth_testing_utils::generators::{self, random_block, BlockParams};
@@ -3436,6 +3521,261 @@ mod tests {
         assert_eq!(range_result, individual_results);
     }
 
+    /// Writes blocks 1-3 of [`BlockchainTestData`] with the receipts of block 1 in static files
+    /// and the receipts of blocks 2-3 in the database.
+    fn insert_blocks_with_receipts_across_static_file_boundary(
+        factory: &ProviderFactory<MockNodeTypesWithDB>,
+        data: &BlockchainTestData,
+    ) {
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(data.genesis.clone().try_recover().unwrap()).unwrap();
+        for i in 0..3 {
+            provider_rw.insert_block(data.blocks[i].0.clone()).unwrap();
+        }
+
+        let mut writer =
+            provider_rw.static_file_provider().latest_writer(StaticFileSegment::Receipts).unwrap();
+        writer.increment_block(0).unwrap();
+        writer.increment_block(1).unwrap();
+        writer.append_receipt(0, &data.blocks[0].1.receipts()[0][0]).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        for i in 1..3 {
+            provider_rw
+                .tx_ref()
+                .put::<tables::Receipts<Receipt>>(
+                    i as u64,
+                    data.blocks[i].1.receipts()[0][0].clone(),
+                )
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        assert_eq!(
+            factory.static_file_provider().get_highest_static_file_tx(StaticFileSegment::Receipts),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_receipts_by_block_range_across_static_file_boundary() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+        insert_blocks_with_receipts_across_static_file_boundary(&factory, &data);
+
+        let provider = factory.provider().unwrap();
+        let expected = (0..3).map(|i| data.blocks[i].1.receipts()[0].clone()).collect::<Vec<_>>();
+        assert_eq!(provider.receipts_by_block_range(1..=3).unwrap(), expected);
+        assert_eq!(
+            provider.receipts_by_tx_range(0..3).unwrap(),
+            expected.into_iter().flatten().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_receipts_by_block_range_missing_receipt_across_static_file_boundary() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+        insert_blocks_with_receipts_across_static_file_boundary(&factory, &data);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.tx_ref().delete::<tables::Receipts<Receipt>>(2, None).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert!(matches!(
+            provider.receipts_by_block_range(1..=3),
+            Err(ProviderError::ReceiptNotFound(_))
+        ));
+    }
+
+    /// Moves the receipt of block 2 from the database to static files, or back if `back` is set.
+    ///
+    /// The receipt stays in the database either way.
+    fn move_receipt_boundary(
+        factory: &ProviderFactory<MockNodeTypesWithDB>,
+        data: &BlockchainTestData,
+        back: bool,
+    ) {
+        let static_file_provider = factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+        if back {
+            writer.prune_receipts(1, 1).unwrap();
+        } else {
+            writer.increment_block(2).unwrap();
+            writer.append_receipt(1, &data.blocks[1].1.receipts()[0][0]).unwrap();
+        }
+        writer.commit().unwrap();
+    }
+
+    #[test]
+    fn test_receipts_range_with_static_file_boundary_moving_mid_read() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+        insert_blocks_with_receipts_across_static_file_boundary(&factory, &data);
+        let provider = factory.provider().unwrap();
+
+        let mut database_reads = 0;
+        let mut predicates = 0;
+        let receipts = factory
+            .static_file_provider()
+            .get_range_with_static_file_or_database(
+                StaticFileSegment::Receipts,
+                0..3,
+                |static_file, range, predicate| {
+                    let receipts = static_file.receipts_by_tx_range(range)?;
+                    Ok(receipts.into_iter().take_while(|receipt| predicate(receipt)).collect())
+                },
+                |range, predicate| {
+                    // The first read of the database misses the receipt of block 2, which has
+                    // been moved to static files in the meantime
+                    database_reads += 1;
+                    if database_reads == 1 {
+                        move_receipt_boundary(&factory, &data, false);
+                    }
+
+                    let mut receipts = Vec::new();
+                    let mut cursor =
+                        provider.tx_ref().cursor_read::<tables::Receipts<Receipt>>()?;
+                    for entry in cursor.walk_range(range)? {
+                        let (tx_num, receipt) = entry?;
+                        if database_reads == 1 && tx_num == 1 {
+                            continue
+                        }
+                        if !predicate(&receipt) {
+                            break
+                        }
+                        receipts.push(receipt);
+                    }
+                    Ok(receipts)
+                },
+                || {
+                    // Only takes three receipts, so that receipts of earlier attempts counting
+                    // towards it would cut the result short
+                    predicates += 1;
+                    let mut taken = 0;
+                    move |_: &Receipt| {
+                        taken += 1;
+                        taken <= 3
+                    }
+                },
+            )
+            .unwrap();
+
+        let expected =
+            (0..3).map(|i| data.blocks[i].1.receipts()[0][0].clone()).collect::<Vec<_>>();
+        assert_eq!(receipts, expected);
+        assert_eq!(database_reads, 2);
+        assert_eq!(predicates, 2);
+    }
+
+    #[test]
+    fn test_receipts_range_gives_up_if_static_file_boundary_keeps_moving() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+        insert_blocks_with_receipts_across_static_file_boundary(&factory, &data);
+        let provider = factory.provider().unwrap();
+
+        let mut database_reads = 0;
+        let result = factory.static_file_provider().get_range_with_static_file_or_database(
+            StaticFileSegment::Receipts,
+            0..3,
+            |static_file, range, _| static_file.receipts_by_tx_range(range),
+            |range, _| {
+                // Every read of the database moves the receipt of block 2 back and forth
+                database_reads += 1;
+                move_receipt_boundary(&factory, &data, database_reads % 2 == 0);
+                provider
+                    .tx_ref()
+                    .cursor_read::<tables::Receipts<Receipt>>()?
+                    .walk_range(range)?
+                    .map(|entry| entry.map(|(_, receipt)| receipt).map_err(Into::into))
+                    .collect()
+            },
+            || |_| true,
+        );
+
+        assert!(matches!(
+            result,
+            Err(ProviderError::StaticFileRangeChanged(StaticFileSegment::Receipts))
+        ));
+        assert_eq!(database_reads, 3);
+    }
+
+    #[test]
+    fn test_transactions_by_block_range_missing_transaction() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+        insert_blocks_with_receipts_across_static_file_boundary(&factory, &data);
+
+        // Remove the transaction of block 3 from static files, while its body indices remain in
+        // the database
+        let static_file_provider = factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::Transactions).unwrap();
+        writer.prune_transactions(1, 2).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        let provider = factory.provider().unwrap();
+        assert!(matches!(
+            provider.transactions_by_block_range(1..=3),
+            Err(ProviderError::TransactionNotFound(_))
+        ));
+        assert!(matches!(provider.block_range(1..=3), Err(ProviderError::TransactionNotFound(_))));
+        assert_eq!(provider.transactions_by_block_range(1..=2).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_range_len_rejects_duplicated_rows() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+        insert_blocks_with_receipts_across_static_file_boundary(&factory, &data);
+        let provider = factory.provider().unwrap();
+
+        // The receipt of the last transaction in static files is read from the database again, as
+        // if it was moved concurrently
+        let mut receipts = provider.receipts_by_tx_range(0..3).unwrap();
+        receipts.insert(1, receipts[1].clone());
+
+        let not_found = |tx: u64| ProviderError::ReceiptNotFound(tx.into());
+        assert!(matches!(
+            ensure_range_len(StaticFileSegment::Receipts, 0, receipts.len(), 3, not_found),
+            Err(ProviderError::StaticFileRangeChanged(StaticFileSegment::Receipts))
+        ));
+        receipts.truncate(2);
+        assert!(matches!(
+            ensure_range_len(StaticFileSegment::Receipts, 0, receipts.len(), 3, not_found),
+            Err(ProviderError::ReceiptNotFound(tx)) if tx == 2u64.into()
+        ));
+        receipts.truncate(1);
+        receipts.extend(provider.receipts_by_tx_range(1..3).unwrap());
+        assert!(
+            ensure_range_len(StaticFileSegment::Receipts, 0, receipts.len(), 3, not_found).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_headers_and_transactions_range_across_blocks() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+        insert_blocks_with_receipts_across_static_file_boundary(&factory, &data);
+
+        let provider = factory.provider().unwrap();
+        let headers = provider.headers_range(0..=3).unwrap();
+        assert_eq!(headers.len(), 4);
+        for (i, header) in headers.iter().skip(1).enumerate() {
+            assert_eq!(header, data.blocks[i].0.header());
+        }
+
+        let transactions = provider.transactions_by_block_range(1..=3).unwrap();
+        assert_eq!(transactions.len(), 3);
+        for (i, block_transactions) in transactions.iter().enumerate() {
+            assert_eq!(block_transactions, &data.blocks[i].0.body().transactions);
+        }
+    }
+
     #[test]
     fn test_write_trie_changesets() {
         use reth_db_api::models::BlockNumberHashedAddress;
@@ -13,8 +13,10 @@ use reth_db_api::{
     BlockNumberList,
 };
 use reth_primitives_traits::{Account, Bytecode};
+use reth_stages_types::StageId;
 use reth_storage_api::{
-    BlockNumReader, BytecodeReader, DBProvider, StateProofProvider, StorageRootProvider,
+    BlockNumReader, BytecodeReader, DBProvider, StageCheckpointReader, StateProofProvider,
+    StorageRootProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
@@ -243,6 +245,168 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provi
     }
 }
 
+impl<Provider> HistoricalStateProviderRef<'_, Provider>
+where
+    Provider: DBProvider + BlockNumReader + ChangeSetReader + StageCheckpointReader,
+{
+    /// Returns the account at `address` at the end of every block from the provider's block
+    /// through `end` in which it changed, in block order, or `None` for blocks after which it
+    /// doesn't exist, e.g. because it was destroyed.
+    ///
+    /// Together with [`AccountReader::basic_account`], which returns the account at the start of
+    /// the range, this is the history of the account over the range. The blocks are looked up in
+    /// the [`tables::AccountsHistory`] index, so `end` must not be past the last indexed block.
+    /// The account after a change is read from the [`tables::AccountChangeSets`] of the next
+    /// change, or from the plain state after the last one.
+    pub fn account_changes(
+        &self,
+        address: Address,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+        self.check_indexed(
+            StageId::IndexAccountHistory,
+            self.lowest_available_blocks.account_history_block_number,
+            end,
+        )?;
+
+        let mut cursor = self.tx().cursor_read::<tables::AccountsHistory>()?;
+        let (blocks, next_change) = self.changed_blocks(
+            &mut cursor,
+            ShardedKey::new(address, self.block_number),
+            |key| key.key == address,
+            end,
+        )?;
+
+        let mut changes = Vec::with_capacity(blocks.len());
+        for (index, &block) in blocks.iter().enumerate() {
+            let account = match blocks.get(index + 1).copied().or(next_change) {
+                Some(next) => {
+                    self.provider
+                        .get_account_before_block(next, address)?
+                        .ok_or(ProviderError::AccountChangesetNotFound {
+                            block_number: next,
+                            address,
+                        })?
+                        .info
+                }
+                None => self.tx().get_by_encoded_key::<tables::PlainAccountState>(&address)?,
+            };
+            changes.push((block, account));
+        }
+        Ok(changes)
+    }
+
+    /// Returns the value of each of the `storage_keys` of `address` at the end of every block
+    /// from the provider's block through `end` in which it changed, in block order.
+    ///
+    /// This is the storage counterpart of [`Self::account_changes`], with the value at the start
+    /// of the range returned by [`StateProvider::storage`]. The cursors are shared by all keys.
+    pub fn storage_changes(
+        &self,
+        address: Address,
+        storage_keys: &[StorageKey],
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<Vec<(BlockNumber, StorageValue)>>> {
+        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+        self.check_indexed(
+            StageId::IndexStorageHistory,
+            self.lowest_available_blocks.storage_history_block_number,
+            end,
+        )?;
+
+        let mut history = self.tx().cursor_read::<tables::StoragesHistory>()?;
+        let mut changesets = self.tx().cursor_dup_read::<tables::StorageChangeSets>()?;
+        let mut plain_state = self.tx().cursor_dup_read::<tables::PlainStorageState>()?;
+
+        let mut all_changes = Vec::with_capacity(storage_keys.len());
+        for &storage_key in storage_keys {
+            let (blocks, next_change) = self.changed_blocks(
+                &mut history,
+                StorageShardedKey::new(address, storage_key, self.block_number),
+                |key| key.address == address && key.sharded_key.key == storage_key,
+                end,
+            )?;
+
+            let mut changes = Vec::with_capacity(blocks.len());
+            for (index, &block) in blocks.iter().enumerate() {
+                let value = match blocks.get(index + 1).copied().or(next_change) {
+                    Some(next) => {
+                        changesets
+                            .seek_by_key_subkey((next, address).into(), storage_key)?
+                            .filter(|entry| entry.key == storage_key)
+                            .ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                                block_number: next,
+                                address,
+                                storage_key: Box::new(storage_key),
+                            })?
+                            .value
+                    }
+                    None => plain_state
+                        .seek_by_key_subkey(address, storage_key)?
+                        .filter(|entry| entry.key == storage_key)
+                        .map_or(StorageValue::ZERO, |entry| entry.value),
+                };
+                changes.push((block, value));
+            }
+            all_changes.push(changes);
+        }
+        Ok(all_changes)
+    }
+
+    /// Returns an error if the history index of `stage` doesn't reach `end`.
+    fn check_indexed(
+        &self,
+        stage: StageId,
+        lowest_available_block_number: Option<BlockNumber>,
+        end: BlockNumber,
+    ) -> ProviderResult<()> {
+        let indexed = self
+            .provider
+            .get_stage_checkpoint(stage)?
+            .map(|checkpoint| checkpoint.block_number)
+            .unwrap_or_default();
+        if end > indexed {
+            return Err(ProviderError::InsufficientChangesets {
+                requested: end,
+                available: lowest_available_block_number.unwrap_or_default()..=indexed,
+            })
+        }
+        Ok(())
+    }
+
+    /// Returns the blocks in the history index `T` from the provider's block through `end` in
+    /// which the key changed, and the first change after `end`, starting at the shard of `key`.
+    fn changed_blocks<T, K>(
+        &self,
+        cursor: &mut impl DbCursorRO<T>,
+        key: K,
+        key_filter: impl Fn(&K) -> bool,
+        end: BlockNumber,
+    ) -> ProviderResult<(Vec<BlockNumber>, Option<BlockNumber>)>
+    where
+        T: Table<Key = K, Value = BlockNumberList>,
+    {
+        let mut blocks = Vec::new();
+        // Shards of other keys follow the last shard of the key
+        let mut shard = cursor.seek(key)?;
+        while let Some((_, list)) = shard.filter(|(key, _)| key_filter(key)) {
+            for block in list.iter().skip_while(|block| *block < self.block_number) {
+                if block > end {
+                    return Ok((blocks, Some(block)))
+                }
+                blocks.push(block);
+            }
+            shard = cursor.next()?;
+        }
+        Ok((blocks, None))
+    }
+}
+
 impl<Provider: DBProvider + BlockNumReader + ChangeSetReader> AccountReader
     for HistoricalStateProviderRef<'_, Provider>
 {
@@ -493,6 +657,32 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProvider<Provider> {
     }
 }
 
+impl<Provider> HistoricalStateProvider<Provider>
+where
+    Provider: DBProvider + BlockNumReader + ChangeSetReader + StageCheckpointReader,
+{
+    /// Returns the changes of the account at `address` from the provider's block through `end`,
+    /// see [`HistoricalStateProviderRef::account_changes`].
+    pub fn account_changes(
+        &self,
+        address: Address,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        self.as_ref().account_changes(address, end)
+    }
+
+    /// Returns the changes of the `storage_keys` of `address` from the provider's block through
+    /// `end`, see [`HistoricalStateProviderRef::storage_changes`].
+    pub fn storage_changes(
+        &self,
+        address: Address,
+        storage_keys: &[StorageKey],
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<Vec<(BlockNumber, StorageValue)>>> {
+        self.as_ref().storage_changes(address, storage_keys, end)
+    }
+}
+
 // Delegates all provider impls to [HistoricalStateProviderRef]
 delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + ChangeSetReader]);
 
@@ -539,6 +729,7 @@ mod tests {
         BlockNumberList,
     };
     use reth_primitives_traits::{Account, StorageEntry};
+    use reth_stages_types::{StageCheckpoint, StageId};
     use reth_storage_api::{
         BlockHashReader, BlockNumReader, ChangeSetReader, DBProvider, DatabaseProviderFactory,
     };
@@ -670,6 +861,147 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn history_provider_account_changes() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: 7 },
+            BlockNumberList::new([1, 3, 7]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([10, 15]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: HIGHER_ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([4]).unwrap(),
+        )
+        .unwrap();
+
+        let account =
+            |nonce| Some(Account { nonce, balance: U256::from(nonce), bytecode_hash: None });
+        for (block, info) in
+            [(1, None), (3, account(3)), (7, account(7)), (10, None), (15, account(15))]
+        {
+            tx.put::<tables::AccountChangeSets>(block, AccountBeforeTx { address: ADDRESS, info })
+                .unwrap();
+        }
+        tx.put::<tables::AccountChangeSets>(
+            4,
+            AccountBeforeTx { address: HIGHER_ADDRESS, info: None },
+        )
+        .unwrap();
+        tx.put::<tables::PlainAccountState>(ADDRESS, account(100).unwrap()).unwrap();
+        tx.put::<tables::StageCheckpoints>(
+            StageId::IndexAccountHistory.to_string(),
+            StageCheckpoint::new(20),
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+
+        // The account is created in block 1, and destroyed in block 7
+        assert_eq!(
+            HistoricalStateProviderRef::new(&db, 0).account_changes(ADDRESS, 12).unwrap(),
+            vec![(1, account(3)), (3, account(7)), (7, None), (10, account(15))]
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&db, 8).account_changes(ADDRESS, 20).unwrap(),
+            vec![(10, account(15)), (15, account(100))]
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&db, 11).account_changes(ADDRESS, 14).unwrap(),
+            Vec::new()
+        );
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 0).account_changes(ADDRESS, 21),
+            Err(ProviderError::InsufficientChangesets { requested: 21, .. })
+        ));
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 1)
+                .with_lowest_available_account_history_block_number(5)
+                .account_changes(ADDRESS, 12),
+            Err(ProviderError::StateAtBlockPruned(1))
+        ));
+    }
+
+    #[test]
+    fn history_provider_storage_changes() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+        let other_storage = B256::with_last_byte(2);
+
+        for (storage_key, highest_block_number, blocks) in [
+            (STORAGE, 7, vec![3, 7]),
+            (STORAGE, u64::MAX, vec![10, 15]),
+            (other_storage, u64::MAX, vec![5]),
+        ] {
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey {
+                    address: ADDRESS,
+                    sharded_key: ShardedKey { key: storage_key, highest_block_number },
+                },
+                BlockNumberList::new(blocks).unwrap(),
+            )
+            .unwrap();
+        }
+        for (block, key, value) in [
+            (3, STORAGE, 0),
+            (5, other_storage, 0),
+            (7, STORAGE, 7),
+            (10, STORAGE, 10),
+            (15, STORAGE, 15),
+        ] {
+            tx.put::<tables::StorageChangeSets>(
+                (block, ADDRESS).into(),
+                StorageEntry { key, value: U256::from(value) },
+            )
+            .unwrap();
+        }
+        tx.put::<tables::PlainStorageState>(
+            ADDRESS,
+            StorageEntry { key: STORAGE, value: U256::from(100) },
+        )
+        .unwrap();
+        tx.put::<tables::PlainStorageState>(
+            ADDRESS,
+            StorageEntry { key: other_storage, value: U256::from(5) },
+        )
+        .unwrap();
+        tx.put::<tables::StageCheckpoints>(
+            StageId::IndexStorageHistory.to_string(),
+            StageCheckpoint::new(20),
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+
+        let keys = [STORAGE, other_storage, B256::with_last_byte(3)];
+        let changes = HistoricalStateProviderRef::new(&db, 4).storage_changes(ADDRESS, &keys, 12);
+        assert_eq!(
+            changes.unwrap(),
+            vec![
+                vec![(7, U256::from(10)), (10, U256::from(15))],
+                vec![(5, U256::from(5))],
+                Vec::new(),
+            ]
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&db, 12).storage_changes(ADDRESS, &keys, 20).unwrap(),
+            vec![vec![(15, U256::from(100))], Vec::new(), Vec::new()]
+        );
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 0).storage_changes(ADDRESS, &keys, 21),
+            Err(ProviderError::InsufficientChangesets { requested: 21, .. })
+        ));
+    }
+
     #[test]
     fn history_provider_get_storage() {
         let factory = create_test_provider_factory();
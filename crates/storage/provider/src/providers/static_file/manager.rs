@@ -48,6 +48,11 @@ use std::{
 };
 use tracing::{debug, info, trace, warn};
 
+/// How many times a range that spans static files and the database is read, before giving up
+/// because the static files keep changing, see
+/// [`StaticFileProvider::get_range_with_static_file_or_database`].
+const MAX_RANGE_READ_ATTEMPTS: usize = 3;
+
 /// Alias type for a map that can be queried for block or transaction ranges. It uses `u64` to
 /// represent either a block or a transaction number end of a static file range.
 type SegmentRanges = BTreeMap<u64, SegmentRangeInclusive>;
@@ -1599,50 +1604,69 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     /// Gets data within a specified range, potentially spanning different `static_files` and
     /// database.
     ///
+    /// The range is split at the highest block or transaction of the segment in static files: the
+    /// part up to it is read from static files and the remainder from the database. If the static
+    /// files changed while the data was read, e.g. because a concurrent static file producer
+    /// moved data out of the database, the read is retried so the result contains neither
+    /// duplicates nor gaps. If the static files changed during each of a few attempts,
+    /// [`ProviderError::StaticFileRangeChanged`] is returned.
+    ///
     /// # Arguments
     /// * `segment` - The segment of the static file to query.
     /// * `block_or_tx_range` - The range of data to fetch.
     /// * `fetch_from_static_file` - A function to fetch data from the `static_file`.
     /// * `fetch_from_database` - A function to fetch data from the database.
-    /// * `predicate` - A function used to evaluate each item in the fetched data. Fetching is
-    ///   terminated when this function returns false, thereby filtering the data based on the
-    ///   provided condition.
-    pub fn get_range_with_static_file_or_database<T, P, FS, FD>(
+    /// * `new_predicate` - A function that creates the predicate used to evaluate each item in the
+    ///   fetched data. Fetching is terminated when the predicate returns false, thereby filtering
+    ///   the data based on the provided condition. Every attempt creates a new predicate, so that
+    ///   the items of a discarded attempt don't count towards a stateful predicate.
+    pub fn get_range_with_static_file_or_database<T, P, PF, FS, FD>(
         &self,
         segment: StaticFileSegment,
-        mut block_or_tx_range: Range<u64>,
+        block_or_tx_range: Range<u64>,
         fetch_from_static_file: FS,
         mut fetch_from_database: FD,
-        mut predicate: P,
+        mut new_predicate: PF,
     ) -> ProviderResult<Vec<T>>
     where
         FS: Fn(&Self, Range<u64>, &mut P) -> ProviderResult<Vec<T>>,
-        FD: FnMut(Range<u64>, P) -> ProviderResult<Vec<T>>,
+        FD: FnMut(Range<u64>, &mut P) -> ProviderResult<Vec<T>>,
+        PF: FnMut() -> P,
         P: FnMut(&T) -> bool,
     {
-        let mut data = Vec::new();
+        // Check the maximum block or transaction number of the segment.
+        let static_file_upper_bound = || {
+            if segment.is_block_based() {
+                self.get_highest_static_file_block(segment)
+            } else {
+                self.get_highest_static_file_tx(segment)
+            }
+        };
 
-        // If there is, check the maximum block or transaction number of the segment.
-        if let Some(static_file_upper_bound) = if segment.is_block_based() {
-            self.get_highest_static_file_block(segment)
-        } else {
-            self.get_highest_static_file_tx(segment)
-        } && block_or_tx_range.start <= static_file_upper_bound
-        {
-            let end = block_or_tx_range.end.min(static_file_upper_bound + 1);
-            data.extend(fetch_from_static_file(
-                self,
-                block_or_tx_range.start..end,
-                &mut predicate,
-            )?);
-            block_or_tx_range.start = end;
-        }
+        for _ in 0..MAX_RANGE_READ_ATTEMPTS {
+            let upper_bound = static_file_upper_bound();
+            let mut range = block_or_tx_range.clone();
+            let mut predicate = new_predicate();
+            let mut data = Vec::new();
 
-        if block_or_tx_range.end > block_or_tx_range.start {
-            data.extend(fetch_from_database(block_or_tx_range, predicate)?)
+            if let Some(upper_bound) = upper_bound &&
+                range.start <= upper_bound
+            {
+                let end = range.end.min(upper_bound + 1);
+                data.extend(fetch_from_static_file(self, range.start..end, &mut predicate)?);
+                range.start = end;
+            }
+
+            if range.end > range.start {
+                data.extend(fetch_from_database(range, &mut predicate)?)
+            }
+
+            if static_file_upper_bound() == upper_bound {
+                return Ok(data)
+            }
         }
 
-        Ok(data)
+        Err(ProviderError::StaticFileRangeChanged(segment))
     }
 
     /// Returns static files directory
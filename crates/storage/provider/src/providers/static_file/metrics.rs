@@ -14,6 +14,7 @@ pub struct StaticFileProviderMetrics {
         (StaticFileSegment, StaticFileProviderOperation),
         StaticFileProviderOperationMetrics,
     >,
+    writers: HashMap<StaticFileSegment, StaticFileWriterMetrics>,
 }
 
 impl Default for StaticFileProviderMetrics {
@@ -39,6 +40,14 @@ impl Default for StaticFileProviderMetrics {
                     )
                 })
                 .collect(),
+            writers: StaticFileSegment::iter()
+                .map(|segment| {
+                    (
+                        segment,
+                        StaticFileWriterMetrics::new_with_labels(&[("segment", segment.as_str())]),
+                    )
+                })
+                .collect(),
         }
     }
 }
@@ -72,6 +81,9 @@ impl StaticFileProviderMetrics {
             .expect("segment operation metrics should exist");
 
         segment_operation.calls_total.increment(1);
+        if operation == StaticFileProviderOperation::Append {
+            self.record_rows_appended(segment, 1);
+        }
 
         if let Some(duration) = duration {
             segment_operation.write_duration_seconds.record(duration.as_secs_f64());
@@ -90,6 +102,9 @@ impl StaticFileProviderMetrics {
             .expect("segment operation metrics should exist")
             .calls_total
             .increment(count);
+        if operation == StaticFileProviderOperation::Append {
+            self.record_rows_appended(segment, count);
+        }
 
         if let Some(duration) = duration {
             self.segment_operations
@@ -99,6 +114,38 @@ impl StaticFileProviderMetrics {
                 .record(duration.as_secs_f64() / count as f64);
         }
     }
+
+    fn record_rows_appended(&self, segment: StaticFileSegment, rows: u64) {
+        self.writers
+            .get(&segment)
+            .expect("writer metrics should exist")
+            .rows_appended
+            .increment(rows);
+    }
+
+    /// Records `bytes` uncompressed bytes appended by a writer of the segment.
+    pub(crate) fn record_bytes_written(&self, segment: StaticFileSegment, bytes: usize) {
+        self.writers
+            .get(&segment)
+            .expect("writer metrics should exist")
+            .bytes_written
+            .increment(bytes as u64);
+    }
+
+    /// Records a commit of a writer of the segment, and the time spent syncing its files to disk
+    /// if they were synced.
+    pub(crate) fn record_commit(
+        &self,
+        segment: StaticFileSegment,
+        duration: Duration,
+        fsync_duration: Option<Duration>,
+    ) {
+        let writer = self.writers.get(&segment).expect("writer metrics should exist");
+        writer.commit_duration_seconds.record(duration.as_secs_f64());
+        if let Some(fsync_duration) = fsync_duration {
+            writer.fsync_duration_seconds.record(fsync_duration.as_secs_f64());
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
@@ -144,3 +191,17 @@ pub(crate) struct StaticFileProviderOperationMetrics {
     /// The time it took to execute the static file jar provider operation that writes data.
     write_duration_seconds: Histogram,
 }
+
+/// Metrics for the writers of a specific static file segment.
+#[derive(Metrics)]
+#[metrics(scope = "static_files.writer")]
+pub(crate) struct StaticFileWriterMetrics {
+    /// The number of uncompressed bytes appended to the static files of a segment
+    bytes_written: Counter,
+    /// The number of rows appended to the static files of a segment
+    rows_appended: Counter,
+    /// The time it took to commit a static file writer, including syncing its files to disk
+    commit_duration_seconds: Histogram,
+    /// The time it took to sync the data and offsets files of a static file writer to disk
+    fsync_duration_seconds: Histogram,
+}
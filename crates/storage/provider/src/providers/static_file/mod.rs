@@ -659,4 +659,36 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_writer_metrics() {
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+
+        // Metric handles are registered with the recorder when the provider is built
+        metrics::with_local_recorder(&recorder, || {
+            let (_static_dir_guard, static_dir) = create_test_static_files_dir();
+            let sf_rw = StaticFileProviderBuilder::<EthPrimitives>::read_write(&static_dir)
+                .unwrap()
+                .with_metrics()
+                .build()
+                .unwrap();
+
+            let mut writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+            let header = Header::default();
+            writer.append_header(&header, &header.hash_slow()).unwrap();
+            writer.commit().unwrap();
+        });
+
+        let rendered = handle.render();
+        for name in [
+            "static_files_writer_bytes_written",
+            "static_files_writer_rows_appended",
+            "static_files_writer_commit_duration_seconds",
+            "static_files_writer_fsync_duration_seconds",
+        ] {
+            assert!(rendered.contains(&format!("{name}{{segment=\"headers\"")), "{name} missing");
+        }
+        assert!(rendered.contains("static_files_writer_rows_appended{segment=\"headers\"} 1"));
+    }
 }
@@ -288,11 +288,13 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             self.writer.commit().map_err(ProviderError::other)?;
 
             if let Some(metrics) = &self.metrics {
+                let segment = self.writer.user_header().segment();
                 metrics.record_segment_operation(
-                    self.writer.user_header().segment(),
+                    segment,
                     StaticFileProviderOperation::CommitWriter,
                     Some(start.elapsed()),
                 );
+                metrics.record_commit(segment, start.elapsed(), Some(self.writer.sync_duration()));
             }
 
             debug!(
@@ -326,11 +328,13 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         self.writer.commit_without_sync_all().map_err(ProviderError::other)?;
 
         if let Some(metrics) = &self.metrics {
+            let segment = self.writer.user_header().segment();
             metrics.record_segment_operation(
-                self.writer.user_header().segment(),
+                segment,
                 StaticFileProviderOperation::CommitWriter,
                 Some(start.elapsed()),
             );
+            metrics.record_commit(segment, start.elapsed(), None);
         }
 
         debug!(
@@ -572,6 +576,9 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         column.to_compact(&mut self.buf);
 
         self.writer.append_column(Some(Ok(&self.buf))).map_err(ProviderError::other)?;
+        if let Some(metrics) = &self.metrics {
+            metrics.record_bytes_written(self.writer.user_header().segment(), self.buf.len());
+        }
         Ok(())
     }
 
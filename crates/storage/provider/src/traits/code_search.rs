@@ -0,0 +1,326 @@
+use alloy_primitives::{Address, FixedBytes, B256};
+use reth_db_api::{cursor::DbCursorRO, tables, transaction::DbTx};
+use reth_storage_api::{DBProvider, DatabaseProviderROFactory};
+use reth_storage_errors::provider::ProviderResult;
+use std::{
+    collections::HashMap,
+    ops::Bound,
+    sync::mpsc::{self, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+/// Default number of shards of the key space that the [`CodeSearch`] scans in parallel.
+pub const DEFAULT_CODE_SEARCH_SHARDS: usize = 256;
+
+/// The `PUSH4` opcode, which dispatchers use to compare the function selector of the call.
+const PUSH4: u8 = 0x63;
+
+/// What a [`CodeSearch`] looks for in deployed bytecode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodePattern {
+    /// The code contains the bytes anywhere, including push data and metadata.
+    Bytes(Vec<u8>),
+    /// The code pushes one of the function selectors with `PUSH4`, like the function dispatchers
+    /// of Solidity and Vyper contracts do.
+    ///
+    /// The opcodes are decoded, so selectors that only appear in push data of other instructions
+    /// or in metadata don't match.
+    Selectors(Vec<[u8; 4]>),
+}
+
+impl CodePattern {
+    /// Returns whether `code` matches the pattern.
+    pub fn matches(&self, code: &[u8]) -> bool {
+        match self {
+            Self::Bytes(pattern) => {
+                pattern.is_empty() || code.windows(pattern.len()).any(|window| window == pattern)
+            }
+            Self::Selectors(selectors) => {
+                let mut pc = 0;
+                while let Some(&opcode) = code.get(pc) {
+                    if opcode == PUSH4 &&
+                        let Some(value) = code.get(pc + 1..pc + 5) &&
+                        selectors.iter().any(|selector| selector == value)
+                    {
+                        return true
+                    }
+                    // `PUSH1` to `PUSH32` are followed by their data
+                    let data_len = if (0x60..=0x7f).contains(&opcode) { opcode - 0x5f } else { 0 };
+                    pc += 1 + data_len as usize;
+                }
+                false
+            }
+        }
+    }
+}
+
+/// An account whose code matched the pattern of a [`CodeSearch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeMatch {
+    /// Address of the account.
+    pub address: Address,
+    /// Hash of the code of the account.
+    pub code_hash: B256,
+    /// Length of the code in bytes.
+    pub code_len: usize,
+}
+
+/// Searches the deployed bytecode of all accounts for a [`CodePattern`].
+///
+/// The search reads the [`tables::Bytecodes`] table once to find the matching code, and then the
+/// [`tables::PlainAccountState`] table to find the accounts with that code. Both tables are split
+/// into [`Self::with_shards`] shards of their key space, which are scanned in parallel by rayon
+/// tasks that open their own read-only providers. Progress is logged while scanning.
+#[derive(Debug)]
+pub struct CodeSearch<F> {
+    factory: F,
+    shards: usize,
+    progress_interval: Duration,
+}
+
+impl<F> CodeSearch<F>
+where
+    F: DatabaseProviderROFactory<Provider: DBProvider> + Sync,
+{
+    /// Creates a new search that opens read-only providers with the given factory.
+    pub const fn new(factory: F) -> Self {
+        Self {
+            factory,
+            shards: DEFAULT_CODE_SEARCH_SHARDS,
+            progress_interval: super::DEFAULT_SCAN_PROGRESS_INTERVAL,
+        }
+    }
+
+    /// Sets the number of shards of each table, at most 65536.
+    pub const fn with_shards(mut self, shards: usize) -> Self {
+        self.shards = if shards == 0 {
+            1
+        } else if shards > 1 << 16 {
+            1 << 16
+        } else {
+            shards
+        };
+        self
+    }
+
+    /// Sets the interval between progress reports.
+    pub const fn with_progress_interval(mut self, progress_interval: Duration) -> Self {
+        self.progress_interval = progress_interval;
+        self
+    }
+
+    /// Calls `sink` for every account whose code matches `pattern`, ordered by address.
+    ///
+    /// Returns the number of distinct codes that matched.
+    ///
+    /// This blocks until the search is finished, so it must not be called from within the rayon
+    /// thread pool.
+    pub fn search(
+        &self,
+        pattern: &CodePattern,
+        mut sink: impl FnMut(CodeMatch),
+    ) -> ProviderResult<usize> {
+        let codes = self.matching_codes(pattern)?;
+        if !codes.is_empty() {
+            self.accounts_with_code(&codes, |matches| matches.into_iter().for_each(&mut sink))?;
+        }
+        Ok(codes.len())
+    }
+
+    /// Returns the lengths of the codes that match `pattern`, by code hash.
+    pub fn matching_codes(&self, pattern: &CodePattern) -> ProviderResult<HashMap<B256, usize>> {
+        let mut codes = HashMap::new();
+        self.scan_shards::<32, _>(
+            "Scanning bytecodes",
+            |provider, range| {
+                let mut matches = Vec::new();
+                let mut cursor = provider.tx_ref().cursor_read::<tables::Bytecodes>()?;
+                for entry in cursor.walk_range(range)? {
+                    let (code_hash, code) = entry?;
+                    let code = code.original_byte_slice();
+                    if pattern.matches(code) {
+                        matches.push((code_hash, code.len()));
+                    }
+                }
+                Ok(matches)
+            },
+            |matches| codes.extend(matches),
+        )?;
+        Ok(codes)
+    }
+
+    /// Passes the accounts with one of the `codes` to `sink`, ordered by address, in one batch
+    /// per shard.
+    pub fn accounts_with_code(
+        &self,
+        codes: &HashMap<B256, usize>,
+        sink: impl FnMut(Vec<CodeMatch>),
+    ) -> ProviderResult<()> {
+        self.scan_shards::<20, _>(
+            "Scanning accounts",
+            |provider, (start, end)| {
+                let mut matches = Vec::new();
+                let mut cursor = provider.tx_ref().cursor_read::<tables::PlainAccountState>()?;
+                for entry in
+                    cursor.walk_range((start.map(Address::from), end.map(Address::from)))?
+                {
+                    let (address, account) = entry?;
+                    if let Some(code_hash) = account.bytecode_hash &&
+                        let Some(&code_len) = codes.get(&code_hash)
+                    {
+                        matches.push(CodeMatch { address, code_hash, code_len });
+                    }
+                }
+                Ok(matches)
+            },
+            sink,
+        )
+    }
+
+    /// Runs `scan` for every shard of the key space in parallel, and passes the results to `sink`
+    /// in shard order on the calling thread.
+    fn scan_shards<const N: usize, T: Send>(
+        &self,
+        message: &'static str,
+        scan: impl Fn(&F::Provider, ShardRange<N>) -> ProviderResult<T> + Sync,
+        mut sink: impl FnMut(T),
+    ) -> ProviderResult<()> {
+        rayon::in_place_scope(|scope| {
+            let scan = &scan;
+            let receivers = (0..self.shards)
+                .map(|shard| {
+                    let (tx, rx) = mpsc::sync_channel(1);
+                    scope.spawn(move |_| {
+                        let result = self
+                            .factory
+                            .database_provider_ro()
+                            .and_then(|provider| scan(&provider, shard_range(shard, self.shards)));
+                        let _ = tx.send(result);
+                    });
+                    rx
+                })
+                .collect::<Vec<_>>();
+
+            let mut last_report = Instant::now();
+            for (shards_done, rx) in receivers.into_iter().enumerate() {
+                let result = loop {
+                    let timeout = self.progress_interval.saturating_sub(last_report.elapsed());
+                    match rx.recv_timeout(timeout) {
+                        Ok(result) => break result?,
+                        Err(RecvTimeoutError::Timeout) => {
+                            info!(
+                                target: "providers::code_search",
+                                shards_done,
+                                shards_total = self.shards,
+                                "{message}"
+                            );
+                            last_report = Instant::now();
+                        }
+                        // The task panicked, the panic is resumed at the end of the scope
+                        Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                    }
+                };
+                sink(result);
+            }
+            Ok(())
+        })
+    }
+}
+
+/// The keys of a shard of the key space.
+type ShardRange<const N: usize> = (Bound<FixedBytes<N>>, Bound<FixedBytes<N>>);
+
+/// Returns the keys of `shard` of `shards` shards, split by their first two bytes.
+fn shard_range<const N: usize>(shard: usize, shards: usize) -> ShardRange<N> {
+    let start = |shard: usize| {
+        let mut key = FixedBytes::<N>::ZERO;
+        let prefix = (shard << 16) / shards;
+        key[..2].copy_from_slice(&(prefix as u16).to_be_bytes());
+        key
+    };
+    let end =
+        if shard + 1 == shards { Bound::Unbounded } else { Bound::Excluded(start(shard + 1)) };
+    (Bound::Included(start(shard)), end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use alloy_primitives::{keccak256, Bytes};
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives_traits::{Account, Bytecode};
+    use reth_storage_api::DatabaseProviderFactory;
+
+    #[test]
+    fn matches_patterns() {
+        // PUSH4 0x12345678, PUSH1 0x63, PUSH5 with a selector in its data
+        let code = [0x63, 0x12, 0x34, 0x56, 0x78, 0x60, 0x63, 0x64, 0xaa, 0xbb, 0xcc, 0xdd, 0xee];
+
+        assert!(CodePattern::Bytes(vec![0x56, 0x78]).matches(&code));
+        assert!(CodePattern::Bytes(Vec::new()).matches(&code));
+        assert!(!CodePattern::Bytes(vec![0x78, 0x56]).matches(&code));
+
+        let selector = [0x12, 0x34, 0x56, 0x78];
+        assert!(CodePattern::Selectors(vec![[0; 4], selector]).matches(&code));
+        assert!(!CodePattern::Selectors(vec![[0xaa, 0xbb, 0xcc, 0xdd]]).matches(&code));
+        // Truncated push data
+        assert!(!CodePattern::Selectors(vec![selector]).matches(&code[..4]));
+    }
+
+    #[test]
+    fn splits_key_space() {
+        assert_eq!(shard_range::<20>(0, 1), (Bound::Included(FixedBytes::ZERO), Bound::Unbounded));
+        let prefixed = |prefix: u16| {
+            let mut key = B256::ZERO;
+            key[..2].copy_from_slice(&prefix.to_be_bytes());
+            key
+        };
+        assert_eq!(
+            shard_range::<32>(1, 3),
+            (Bound::Included(prefixed(0x5555)), Bound::Excluded(prefixed(0xaaaa)))
+        );
+    }
+
+    #[test]
+    fn searches_accounts() {
+        let factory = create_test_provider_factory();
+        let provider = factory.database_provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        let matching = Bytecode::new_raw(Bytes::from_static(&[0x63, 1, 2, 3, 4, 0x14, 0x57]));
+        let other = Bytecode::new_raw(Bytes::from_static(&[0x60, 1, 0x60, 2]));
+        let (matching_hash, other_hash) = (keccak256(matching.original_byte_slice()), B256::ZERO);
+        tx.put::<tables::Bytecodes>(matching_hash, matching).unwrap();
+        tx.put::<tables::Bytecodes>(other_hash, other).unwrap();
+
+        let accounts = [
+            (Address::repeat_byte(0xf0), Some(matching_hash)),
+            (Address::repeat_byte(0x01), Some(matching_hash)),
+            (Address::repeat_byte(0x80), Some(other_hash)),
+            (Address::repeat_byte(0x40), None),
+        ];
+        for (address, bytecode_hash) in accounts {
+            tx.put::<tables::PlainAccountState>(
+                address,
+                Account { bytecode_hash, ..Default::default() },
+            )
+            .unwrap();
+        }
+        provider.commit().unwrap();
+
+        let mut matches = Vec::new();
+        let search = CodeSearch::new(factory).with_shards(7);
+        let pattern = CodePattern::Selectors(vec![[1, 2, 3, 4]]);
+        assert_eq!(search.search(&pattern, |found| matches.push(found)).unwrap(), 1);
+        assert_eq!(
+            matches,
+            [0x01, 0xf0].map(|byte| CodeMatch {
+                address: Address::repeat_byte(byte),
+                code_hash: matching_hash,
+                code_len: 7,
+            })
+        );
+    }
+}
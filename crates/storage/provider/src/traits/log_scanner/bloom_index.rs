@@ -0,0 +1,227 @@
+use alloy_primitives::{BlockHash, BlockNumber, Bloom, B256, BLOOM_SIZE_BYTES};
+use alloy_rpc_types_eth::Filter;
+use reth_fs_util::{self as fs, FsPathError};
+use reth_storage_api::HeaderProvider;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{io::Write, path::Path};
+
+/// Number of blocks whose logs blooms are aggregated into a single bloom of the [`BloomIndex`].
+pub const BLOOM_INDEX_CHUNK_SIZE: u64 = 8192;
+
+/// Name of the index file in the index directory.
+const FILE_NAME: &str = "blooms.bin";
+
+/// Identifies the index file and its version.
+const MAGIC: &[u8; 8] = b"rethblm1";
+
+/// Length of the file header: the magic, the number of chunks and the tip hash.
+const HEADER_LEN: usize = MAGIC.len() + 8 + 32;
+
+/// Errors that can occur while loading or saving a [`BloomIndex`].
+#[derive(Debug, thiserror::Error)]
+pub enum BloomIndexError {
+    /// The index file could not be read or written.
+    #[error(transparent)]
+    Fs(#[from] FsPathError),
+    /// The index file is not a bloom index, or it is truncated.
+    #[error("invalid bloom index file {0}")]
+    InvalidFile(String),
+}
+
+/// The logs blooms of all blocks in each complete chunk of [`BLOOM_INDEX_CHUNK_SIZE`] blocks,
+/// OR-ed together.
+///
+/// Scans consult the chunk blooms first, so whole chunks that can't contain a matching log are
+/// skipped without reading their headers. The index records the hash of the last indexed block,
+/// which no longer matches the chain after a reorg of any indexed block, see [`Self::is_valid`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BloomIndex {
+    /// The bloom of chunk `n`, which contains blocks `n * BLOOM_INDEX_CHUNK_SIZE` up to the next
+    /// chunk.
+    blooms: Vec<Bloom>,
+    /// Hash of the last indexed block.
+    tip_hash: BlockHash,
+}
+
+impl BloomIndex {
+    /// Returns the number of the last indexed block, or `None` if no chunk is indexed yet.
+    pub fn tip(&self) -> Option<BlockNumber> {
+        (self.blooms.len() as u64 * BLOOM_INDEX_CHUNK_SIZE).checked_sub(1)
+    }
+
+    /// Returns the number of indexed chunks.
+    pub const fn len(&self) -> usize {
+        self.blooms.len()
+    }
+
+    /// Returns `true` if no chunk is indexed.
+    pub const fn is_empty(&self) -> bool {
+        self.blooms.is_empty()
+    }
+
+    /// Returns `false` if none of the blocks in `chunk` can contain a log that matches `filter`.
+    ///
+    /// Chunks that are not indexed may always match.
+    pub fn may_match(&self, chunk: u64, filter: &Filter) -> bool {
+        self.blooms.get(chunk as usize).is_none_or(|bloom| filter.matches_bloom(*bloom))
+    }
+
+    /// Returns whether the last indexed block is still canonical, and therefore all indexed
+    /// blocks.
+    pub fn is_valid<P: HeaderProvider>(&self, provider: &P) -> ProviderResult<bool> {
+        let Some(tip) = self.tip() else { return Ok(true) };
+        Ok(provider.sealed_header(tip)?.is_some_and(|header| header.hash() == self.tip_hash))
+    }
+
+    /// Indexes the complete chunks that end at or before `tip` and aren't indexed yet.
+    ///
+    /// The index must be valid, see [`Self::is_valid`].
+    pub fn update<P: HeaderProvider>(
+        &mut self,
+        provider: &P,
+        tip: BlockNumber,
+    ) -> ProviderResult<()> {
+        let chunks = tip.saturating_add(1) / BLOOM_INDEX_CHUNK_SIZE;
+        for chunk in self.blooms.len() as u64..chunks {
+            let start = chunk * BLOOM_INDEX_CHUNK_SIZE;
+            let blooms = provider.block_bloom_range(start..start + BLOOM_INDEX_CHUNK_SIZE)?;
+            if blooms.len() as u64 != BLOOM_INDEX_CHUNK_SIZE {
+                let missing = blooms
+                    .iter()
+                    .zip(start..)
+                    .find(|((number, _), expected)| number != expected)
+                    .map_or(start + blooms.len() as u64, |(_, number)| number);
+                return Err(ProviderError::HeaderNotFound(missing.into()))
+            }
+
+            let mut bloom = Bloom::ZERO;
+            for (_, block_bloom) in &blooms {
+                bloom.accrue_bloom(block_bloom);
+            }
+            self.blooms.push(bloom);
+        }
+
+        if let Some(tip) = self.tip() {
+            self.tip_hash = provider
+                .sealed_header(tip)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(tip.into()))?
+                .hash();
+        }
+        Ok(())
+    }
+
+    /// Loads the index from `dir`, or returns `None` if there is none.
+    pub fn load(dir: &Path) -> Result<Option<Self>, BloomIndexError> {
+        let path = dir.join(FILE_NAME);
+        if !path.exists() {
+            return Ok(None)
+        }
+
+        let invalid = || BloomIndexError::InvalidFile(path.display().to_string());
+        let bytes = fs::read(&path)?;
+        let (header, blooms) = bytes.split_at_checked(HEADER_LEN).ok_or_else(invalid)?;
+        let (magic, header) = header.split_at(MAGIC.len());
+        let (len, tip_hash) = header.split_at(8);
+        let len = u64::from_le_bytes(len.try_into().expect("8 bytes"));
+        if magic != MAGIC || blooms.len() as u64 != len * BLOOM_SIZE_BYTES as u64 {
+            return Err(invalid())
+        }
+
+        Ok(Some(Self {
+            blooms: blooms.chunks_exact(BLOOM_SIZE_BYTES).map(Bloom::from_slice).collect(),
+            tip_hash: B256::from_slice(tip_hash),
+        }))
+    }
+
+    /// Writes the index to `dir`, replacing the existing one.
+    pub fn save(&self, dir: &Path) -> Result<(), BloomIndexError> {
+        fs::create_dir_all(dir)?;
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.blooms.len() * BLOOM_SIZE_BYTES);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&(self.blooms.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(self.tip_hash.as_slice());
+        for bloom in &self.blooms {
+            bytes.extend_from_slice(bloom.as_slice());
+        }
+
+        fs::atomic_write_file(&dir.join(FILE_NAME), |file| file.write_all(&bytes))?;
+        Ok(())
+    }
+}
+
+/// Returns the index chunk that contains `block`, and the last block of that chunk.
+pub(super) const fn index_chunk(block: BlockNumber) -> (u64, BlockNumber) {
+    let chunk = block / BLOOM_INDEX_CHUNK_SIZE;
+    (chunk, (chunk * BLOOM_INDEX_CHUNK_SIZE).saturating_add(BLOOM_INDEX_CHUNK_SIZE - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockEthProvider;
+    use alloy_consensus::Header;
+    use alloy_primitives::{Address, BloomInput, U256};
+    use assert_matches::assert_matches;
+
+    fn add_headers(provider: &MockEthProvider, blocks: std::ops::Range<u64>, bloom: Bloom) {
+        for number in blocks {
+            let header = Header { number, logs_bloom: bloom, ..Default::default() };
+            provider.add_header(B256::from(U256::from(number)), header);
+        }
+    }
+
+    #[test]
+    fn index_chunks() {
+        let provider = MockEthProvider::default();
+        let address = Address::repeat_byte(1);
+        let mut bloom = Bloom::ZERO;
+        bloom.accrue(BloomInput::Raw(address.as_slice()));
+
+        add_headers(&provider, 0..BLOOM_INDEX_CHUNK_SIZE * 3 + 10, Bloom::ZERO);
+        add_headers(&provider, BLOOM_INDEX_CHUNK_SIZE + 5..BLOOM_INDEX_CHUNK_SIZE + 6, bloom);
+
+        let mut index = BloomIndex::default();
+        index.update(&provider, BLOOM_INDEX_CHUNK_SIZE * 2 - 2).unwrap();
+        assert_eq!((index.len(), index.tip()), (1, Some(BLOOM_INDEX_CHUNK_SIZE - 1)));
+
+        // Only complete chunks are indexed
+        index.update(&provider, BLOOM_INDEX_CHUNK_SIZE * 3 + 9).unwrap();
+        assert_eq!(index.len(), 3);
+        assert!(index.is_valid(&provider).unwrap());
+
+        let filter = Filter::new().address(address);
+        assert_eq!(
+            (0..4).map(|chunk| index.may_match(chunk, &filter)).collect::<Vec<_>>(),
+            [false, true, false, true]
+        );
+
+        // Headers past the tip are missing
+        assert_matches!(
+            index.clone().update(&provider, BLOOM_INDEX_CHUNK_SIZE * 5),
+            Err(ProviderError::HeaderNotFound(_))
+        );
+
+        // Replacing the tip invalidates the index
+        let tip = index.tip().unwrap();
+        let header = Header { number: tip, extra_data: vec![1].into(), ..Default::default() };
+        provider.headers.lock().retain(|_, header| header.number != tip);
+        provider.add_header(B256::repeat_byte(1), header);
+        assert!(!index.is_valid(&provider).unwrap());
+    }
+
+    #[test]
+    fn save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(BloomIndex::load(dir.path()).unwrap(), None);
+
+        let mut bloom = Bloom::ZERO;
+        bloom.accrue(BloomInput::Raw(b"log"));
+        let index = BloomIndex { blooms: vec![Bloom::ZERO, bloom], tip_hash: B256::repeat_byte(2) };
+        index.save(dir.path()).unwrap();
+        assert_eq!(BloomIndex::load(dir.path()).unwrap(), Some(index));
+
+        std::fs::write(dir.path().join(FILE_NAME), b"rethblm1").unwrap();
+        assert_matches!(BloomIndex::load(dir.path()), Err(BloomIndexError::InvalidFile(_)));
+    }
+}
@@ -0,0 +1,1206 @@
+use alloy_consensus::{transaction::TxHashRef, BlockHeader, TxReceipt};
+use alloy_primitives::{BlockHash, BlockNumber, Bloom, Log, TxHash, TxIndex};
+use alloy_rpc_types_eth::Filter;
+use parking_lot::Mutex;
+use reth_primitives_traits::SealedHeader;
+use reth_storage_api::{
+    BlockBodyIndicesProvider, DatabaseProviderROFactory, HeaderProvider, ReceiptProvider,
+    TransactionsProvider,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use schnellru::{ByLength, LruMap};
+use std::{
+    collections::VecDeque,
+    fmt,
+    ops::RangeInclusive,
+    sync::mpsc::{self, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+mod bloom_index;
+pub use bloom_index::{BloomIndex, BloomIndexError, BLOOM_INDEX_CHUNK_SIZE};
+
+mod query;
+pub use query::{Continuation, LogQuery};
+
+/// How many headers are loaded at once while scanning.
+const HEADERS_BATCH_SIZE: u64 = 1_000;
+
+/// How many blocks the [`ParallelLogScanner`] keeps cached.
+const HEADER_CACHE_SIZE: u32 = 4 * HEADERS_BATCH_SIZE as u32;
+
+/// Default number of blocks scanned by a single task of the [`ParallelLogScanner`].
+pub const DEFAULT_SCAN_CHUNK_SIZE: u64 = 10_000;
+
+/// Default interval between the progress reports of the [`ParallelLogScanner`].
+pub const DEFAULT_SCAN_PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Logs, skipped blocks and failed blocks found in a chunk of blocks.
+type ChunkResult = (Vec<ScannedLog>, MissingReceipts, Vec<FailedBlock>);
+
+/// A log found by [`LogScanner::scan_logs`], with its position in the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannedLog {
+    /// Number of the block that contains the log.
+    pub block_number: BlockNumber,
+    /// Hash of the block that contains the log.
+    pub block_hash: BlockHash,
+    /// Hash of the transaction that emitted the log.
+    pub tx_hash: TxHash,
+    /// Index of the transaction in the block.
+    pub tx_index: TxIndex,
+    /// Index of the log in the block.
+    pub log_index: u64,
+    /// Timestamp of the block.
+    pub block_timestamp: u64,
+    /// Base fee per gas of the block, if it's after London.
+    pub base_fee_per_gas: Option<u64>,
+    /// Gas used by all transactions of the block.
+    pub gas_used: u64,
+    /// The log.
+    pub log: Log,
+}
+
+/// A block that couldn't be scanned by a [`ParallelLogScanner`] that continues on errors, see
+/// [`ParallelLogScanner::with_continue_on_error`].
+#[derive(Debug)]
+pub struct FailedBlock {
+    /// Number of the block.
+    pub block: BlockNumber,
+    /// Why the block couldn't be scanned.
+    pub error: LogScanError,
+}
+
+/// The blocks that were skipped or failed during a scan of a [`ParallelLogScanner`].
+#[derive(Debug, Default)]
+pub struct ScanSummary {
+    /// The contiguous ranges of blocks that were skipped because of missing receipts, see
+    /// [`ParallelLogScanner::with_skip_missing`].
+    pub missing: Vec<RangeInclusive<BlockNumber>>,
+    /// The blocks that couldn't be scanned, in block order, see
+    /// [`ParallelLogScanner::with_continue_on_error`].
+    pub failed: Vec<FailedBlock>,
+}
+
+/// Errors that can occur while scanning logs with [`LogScanner::scan_logs`].
+#[derive(Debug, thiserror::Error)]
+pub enum LogScanError {
+    /// The receipts of a block whose logs bloom matches the filter are neither in the database
+    /// nor in static files, because they have been pruned.
+    #[error("receipts of block #{block} are missing, they may have been pruned")]
+    MissingReceipts {
+        /// The first block with missing receipts.
+        block: BlockNumber,
+    },
+    /// The transactions of a block with matching logs are not available.
+    #[error("transactions of block #{0} are not available")]
+    TransactionsNotFound(BlockNumber),
+    /// Error while reading headers or receipts.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+/// Scans the logs of a range of blocks for the ones that match a [`Filter`].
+///
+/// This is implemented for every provider that can read headers, block bodies, transactions and
+/// receipts with [`alloy_primitives::Log`]s.
+pub trait LogScanner:
+    HeaderProvider
+    + BlockBodyIndicesProvider
+    + TransactionsProvider
+    + ReceiptProvider<Receipt: TxReceipt<Log = Log>>
+{
+    /// Calls `sink` for every log in `range` that matches the address and topics of `filter`, in
+    /// order.
+    ///
+    /// Logs are matched like in `eth_getLogs`: a log matches if its address is one of the filter
+    /// addresses and each of its topics is one of the values for that position, where an empty set
+    /// matches anything. The block range of the `filter` is ignored. The receipts of a block are
+    /// only loaded if its logs bloom contains one of the addresses and one value for every topic
+    /// position.
+    ///
+    /// Receipts are read from the database or from static files, wherever the provider stores
+    /// them. The transactions of a block are only loaded if it contains a matching log, to look up
+    /// the transaction hashes.
+    ///
+    /// Returns [`LogScanError::MissingReceipts`] or [`LogScanError::TransactionsNotFound`] if the
+    /// receipts or transactions of such a block are missing, and [`ProviderError::HeaderNotFound`]
+    /// if a header in `range` is missing.
+    fn scan_logs(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        filter: &Filter,
+        sink: impl FnMut(ScannedLog),
+    ) -> Result<(), LogScanError> {
+        scan_blocks(self, range, filter, ScanOptions::default(), None, sink).map(drop)
+    }
+
+    /// Like [`Self::scan_logs`], but skips blocks with missing receipts instead of returning an
+    /// error.
+    ///
+    /// Returns the contiguous ranges of skipped blocks. A range may contain blocks that were never
+    /// checked because their bloom didn't match, but it starts and ends with a skipped block, and
+    /// none of its blocks has receipts that were found.
+    fn scan_logs_skip_missing(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        filter: &Filter,
+        sink: impl FnMut(ScannedLog),
+    ) -> Result<Vec<RangeInclusive<BlockNumber>>, LogScanError> {
+        let options = ScanOptions { skip_missing: true, ..Default::default() };
+        scan_blocks(self, range, filter, options, None, sink).map(|missing| missing.ranges)
+    }
+
+    /// Returns the page of `query` that starts at its continuation, and the continuation of the
+    /// next page, or `None` if this is the last one.
+    ///
+    /// The logs are converted to RPC logs like `eth_getLogs` does. A page may be empty even if
+    /// it isn't the last one, e.g. if none of its blocks contain matching logs.
+    fn query_logs(
+        &self,
+        query: &LogQuery,
+    ) -> Result<(Vec<alloy_rpc_types_eth::Log>, Option<Continuation>), LogScanError> {
+        query::query_logs(self, query)
+    }
+}
+
+impl<T> LogScanner for T where
+    T: HeaderProvider
+        + BlockBodyIndicesProvider
+        + TransactionsProvider
+        + ReceiptProvider<Receipt: TxReceipt<Log = Log>>
+{
+}
+
+/// The header fields that are added to the logs of a block.
+#[derive(Debug, Clone)]
+struct ScannedHeader {
+    hash: BlockHash,
+    timestamp: u64,
+    base_fee_per_gas: Option<u64>,
+    gas_used: u64,
+}
+
+impl<H: BlockHeader> From<SealedHeader<H>> for ScannedHeader {
+    fn from(header: SealedHeader<H>) -> Self {
+        Self {
+            hash: header.hash(),
+            timestamp: header.timestamp(),
+            base_fee_per_gas: header.base_fee_per_gas(),
+            gas_used: header.gas_used(),
+        }
+    }
+}
+
+/// A block in the [`HeaderCache`].
+#[derive(Debug, Clone)]
+struct CachedBlock {
+    logs_bloom: Bloom,
+    /// The header is only loaded once the bloom matched a filter.
+    header: Option<ScannedHeader>,
+}
+
+/// Recently loaded blooms and headers by block number, so they aren't loaded again by later
+/// scans.
+struct HeaderCache(Mutex<LruMap<BlockNumber, CachedBlock>>);
+
+impl HeaderCache {
+    fn new(max_length: u32) -> Self {
+        Self(Mutex::new(LruMap::new(ByLength::new(max_length))))
+    }
+}
+
+impl fmt::Debug for HeaderCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HeaderCache").field("len", &self.0.lock().len()).finish()
+    }
+}
+
+/// Options of [`scan_blocks`].
+#[derive(Debug, Default, Clone, Copy)]
+struct ScanOptions<'a> {
+    /// Chunks of blocks that can't match according to the index are skipped.
+    index: Option<&'a BloomIndex>,
+    /// Blocks with missing receipts are recorded instead of failing the scan.
+    skip_missing: bool,
+    /// Blooms and headers are taken from and added to the cache.
+    header_cache: Option<&'a HeaderCache>,
+}
+
+/// Scans the logs in `range`, see [`LogScanner::scan_logs`].
+///
+/// If `failed` is given, blocks that can't be scanned are pushed to it and the scan continues
+/// with the next block, and for missing headers with the next header that exists.
+fn scan_blocks<P: LogScanner + ?Sized>(
+    provider: &P,
+    range: RangeInclusive<BlockNumber>,
+    filter: &Filter,
+    options: ScanOptions<'_>,
+    mut failed: Option<&mut Vec<FailedBlock>>,
+    mut sink: impl FnMut(ScannedLog),
+) -> Result<MissingReceipts, LogScanError> {
+    let (start, end) = range.into_inner();
+    let mut batch_start = start;
+    let mut missing = MissingReceipts::default();
+
+    while batch_start <= end {
+        let mut batch_end = batch_start.saturating_add(HEADERS_BATCH_SIZE - 1).min(end);
+        if let Some(index) = options.index {
+            let (chunk, chunk_end) = bloom_index::index_chunk(batch_start);
+            if !index.may_match(chunk, filter) {
+                if chunk_end >= end {
+                    break
+                }
+                batch_start = chunk_end + 1;
+                continue
+            }
+            batch_end = batch_end.min(chunk_end);
+        }
+        let mut expected = batch_start;
+
+        let blooms = match load_blooms(provider, batch_start..=batch_end, options.header_cache) {
+            Ok(blooms) => blooms,
+            Err(err) => {
+                record_failure(&mut failed, batch_start, err.into())?;
+                // The headers of the batch are unknown, so none of them can be scanned
+                expected = batch_end + 1;
+                Vec::new()
+            }
+        };
+        for (number, logs_bloom) in blooms {
+            if number != expected {
+                record_failure(
+                    &mut failed,
+                    expected,
+                    ProviderError::HeaderNotFound(expected.into()).into(),
+                )?;
+            }
+            expected = number + 1;
+
+            // Only the headers of blocks that may contain a matching log are loaded
+            if filter.matches_bloom(logs_bloom) &&
+                let Err(err) = load_header(provider, number, options.header_cache)
+                    .map_err(LogScanError::from)
+                    .and_then(|header| {
+                        scan_block(
+                            provider,
+                            number,
+                            &header,
+                            filter,
+                            options.skip_missing,
+                            &mut missing,
+                            &mut sink,
+                        )
+                    })
+            {
+                record_failure(&mut failed, number, err)?;
+            }
+        }
+
+        if expected <= batch_end {
+            record_failure(
+                &mut failed,
+                expected,
+                ProviderError::HeaderNotFound(expected.into()).into(),
+            )?;
+        }
+        if batch_end == end {
+            break
+        }
+        batch_start = batch_end + 1;
+    }
+
+    Ok(missing)
+}
+
+/// Scans the logs of a block whose logs bloom matches the filter.
+fn scan_block<P: LogScanner + ?Sized>(
+    provider: &P,
+    number: BlockNumber,
+    header: &ScannedHeader,
+    filter: &Filter,
+    skip_missing: bool,
+    missing: &mut MissingReceipts,
+    sink: &mut impl FnMut(ScannedLog),
+) -> Result<(), LogScanError> {
+    let tx_count = provider
+        .block_body_indices(number)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?
+        .tx_count;
+    // Pruned receipts are left out of the returned list, whether they were in the database or in
+    // static files
+    let receipts = provider
+        .receipts_by_block(number.into())?
+        .filter(|receipts| receipts.len() as u64 == tx_count);
+    let Some(receipts) = receipts else {
+        if !skip_missing {
+            return Err(LogScanError::MissingReceipts { block: number })
+        }
+        missing.missing(number);
+        return Ok(())
+    };
+    missing.found();
+
+    let mut tx_hashes = None;
+    let mut log_index = 0;
+    for (tx_index, receipt) in receipts.into_iter().enumerate() {
+        for log in receipt.into_logs() {
+            if filter.matches(&log) {
+                if tx_hashes.is_none() {
+                    let transactions = provider
+                        .transactions_by_block(number.into())?
+                        .filter(|transactions| transactions.len() as u64 == tx_count)
+                        .ok_or(LogScanError::TransactionsNotFound(number))?;
+                    tx_hashes =
+                        Some(transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>());
+                }
+                // The number of transactions was checked, so no logs of a failing block are passed
+                // on
+                let tx_hash = tx_hashes.as_deref().expect("loaded above")[tx_index];
+
+                sink(ScannedLog {
+                    block_number: number,
+                    block_hash: header.hash,
+                    tx_hash,
+                    tx_index: tx_index as TxIndex,
+                    log_index,
+                    block_timestamp: header.timestamp,
+                    base_fee_per_gas: header.base_fee_per_gas,
+                    gas_used: header.gas_used,
+                    log,
+                });
+            }
+            log_index += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads the logs blooms of the blocks in `range`, from the `cache` if all of them are in it.
+///
+/// Otherwise all blooms are loaded from the provider and added to the cache.
+fn load_blooms<P: HeaderProvider + ?Sized>(
+    provider: &P,
+    range: RangeInclusive<BlockNumber>,
+    cache: Option<&HeaderCache>,
+) -> ProviderResult<Vec<(BlockNumber, Bloom)>> {
+    let Some(cache) = cache else { return provider.block_bloom_range(range) };
+
+    let cached = {
+        let mut cache = cache.0.lock();
+        range
+            .clone()
+            .map(|number| cache.get(&number).map(|block| (number, block.logs_bloom)))
+            .collect::<Option<Vec<_>>>()
+    };
+    if let Some(blooms) = cached {
+        return Ok(blooms)
+    }
+
+    let blooms = provider.block_bloom_range(range)?;
+    let mut cache = cache.0.lock();
+    for &(number, logs_bloom) in &blooms {
+        // Keep the headers of blocks that are already cached
+        cache.get_or_insert(number, || CachedBlock { logs_bloom, header: None });
+    }
+    Ok(blooms)
+}
+
+/// Loads the header of block `number`, from the `cache` if it's in it.
+///
+/// Otherwise the header is loaded from the provider and added to the cached block.
+fn load_header<P: HeaderProvider + ?Sized>(
+    provider: &P,
+    number: BlockNumber,
+    cache: Option<&HeaderCache>,
+) -> ProviderResult<ScannedHeader> {
+    if let Some(cache) = cache &&
+        let Some(header) = cache.0.lock().get(&number).and_then(|block| block.header.clone())
+    {
+        return Ok(header)
+    }
+
+    let header: ScannedHeader = provider
+        .sealed_header(number)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?
+        .into();
+    if let Some(cache) = cache &&
+        let Some(block) = cache.0.lock().get(&number)
+    {
+        block.header = Some(header.clone());
+    }
+    Ok(header)
+}
+
+/// Records that `block` failed if failures are collected, or returns the error otherwise.
+fn record_failure(
+    failed: &mut Option<&mut Vec<FailedBlock>>,
+    block: BlockNumber,
+    error: LogScanError,
+) -> Result<(), LogScanError> {
+    match failed {
+        Some(failed) => {
+            failed.push(FailedBlock { block, error });
+            Ok(())
+        }
+        None => Err(error),
+    }
+}
+
+/// Contiguous ranges of blocks with missing receipts, in the order they were scanned.
+#[derive(Debug, Default)]
+struct MissingReceipts {
+    ranges: Vec<RangeInclusive<BlockNumber>>,
+    /// Whether no receipts were found after the start of the last range.
+    open: bool,
+    /// Whether the first block that was checked had missing receipts.
+    starts_missing: bool,
+    /// Whether receipts were found for any block.
+    any_found: bool,
+}
+
+impl MissingReceipts {
+    /// Records that the receipts of `block` are missing.
+    fn missing(&mut self, block: BlockNumber) {
+        if self.open &&
+            let Some(last) = self.ranges.last_mut()
+        {
+            *last = *last.start()..=block;
+        } else {
+            self.starts_missing |= !self.any_found && self.ranges.is_empty();
+            self.ranges.push(block..=block);
+            self.open = true;
+        }
+    }
+
+    /// Records that the receipts of a block were found.
+    const fn found(&mut self) {
+        self.open = false;
+        self.any_found = true;
+    }
+
+    /// Appends the ranges of the following blocks, joining the ranges at the boundary if no
+    /// receipts were found in between.
+    fn append(&mut self, other: Self) {
+        let checked_any = other.any_found || !other.ranges.is_empty();
+        let mut ranges = other.ranges.into_iter();
+        if self.open &&
+            other.starts_missing &&
+            let (Some(last), Some(first)) = (self.ranges.last_mut(), ranges.next())
+        {
+            *last = *last.start()..=*first.end();
+        }
+        self.ranges.extend(ranges);
+
+        // Chunks without any checked block don't close the last range
+        if checked_any {
+            self.open = other.open;
+            self.any_found |= other.any_found;
+        }
+    }
+}
+
+/// Scans a range of blocks for logs on the rayon thread pool.
+///
+/// The range is split into chunks of [`Self::with_chunk_size`] blocks, and each chunk is scanned
+/// with [`LogScanner::scan_logs`] by a rayon task that opens its own read-only provider. The logs
+/// are passed on in block order as soon as all previous chunks are done, and only a limited number
+/// of chunks is scanned ahead, so memory use doesn't grow with the size of the range.
+///
+/// The logs blooms and headers of the most recently scanned blocks are cached, so scanning the
+/// same blocks again, e.g. with another filter, doesn't load them again. Headers are only loaded
+/// for blocks whose bloom matches. The scanned blocks must therefore not be
+/// reorged while the scanner is in use.
+#[derive(Debug)]
+pub struct ParallelLogScanner<F> {
+    factory: F,
+    chunk_size: u64,
+    progress_interval: Duration,
+    skip_missing: bool,
+    continue_on_error: bool,
+    bloom_index: Option<BloomIndex>,
+    header_cache: HeaderCache,
+}
+
+impl<F> ParallelLogScanner<F>
+where
+    F: DatabaseProviderROFactory<Provider: LogScanner> + Sync,
+{
+    /// Creates a new scanner that opens read-only providers with the given factory.
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory,
+            chunk_size: DEFAULT_SCAN_CHUNK_SIZE,
+            progress_interval: DEFAULT_SCAN_PROGRESS_INTERVAL,
+            skip_missing: false,
+            continue_on_error: false,
+            bloom_index: None,
+            header_cache: HeaderCache::new(HEADER_CACHE_SIZE),
+        }
+    }
+
+    /// Sets the number of blocks scanned by a single task.
+    pub const fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = if chunk_size == 0 { 1 } else { chunk_size };
+        self
+    }
+
+    /// Sets the interval between progress reports.
+    pub const fn with_progress_interval(mut self, progress_interval: Duration) -> Self {
+        self.progress_interval = progress_interval;
+        self
+    }
+
+    /// Sets whether blocks with missing receipts are skipped instead of failing the scan, see
+    /// [`LogScanner::scan_logs_skip_missing`].
+    pub const fn with_skip_missing(mut self, skip_missing: bool) -> Self {
+        self.skip_missing = skip_missing;
+        self
+    }
+
+    /// Sets whether blocks that can't be scanned are recorded in [`ScanSummary::failed`] instead
+    /// of failing the scan.
+    ///
+    /// The scan then continues with the next block, and no logs of a failed block are passed on.
+    /// Errors that affect the whole chunk, like failing to open a provider, still fail the scan.
+    pub const fn with_continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Sets the [`BloomIndex`] that is consulted to skip chunks of blocks without matching logs.
+    ///
+    /// The index must be valid for the chain of the factory, see [`BloomIndex::is_valid`].
+    pub fn with_bloom_index(mut self, bloom_index: BloomIndex) -> Self {
+        self.bloom_index = Some(bloom_index);
+        self
+    }
+
+    /// Calls `sink` for every log in `range` that matches `filter`, see
+    /// [`LogScanner::scan_logs`].
+    ///
+    /// The logs are passed to `sink` on the calling thread, ordered by block and log index. Unless
+    /// [`Self::with_continue_on_error`] is set, the scan stops on the first error, and logs of the
+    /// chunks after the failing one are discarded.
+    ///
+    /// Returns the contiguous ranges of blocks that were skipped because of missing receipts, which
+    /// are also logged once per range as soon as they are complete, and the blocks that failed.
+    ///
+    /// This blocks until the scan is finished, so it must not be called from within the rayon
+    /// thread pool.
+    pub fn scan(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        filter: &Filter,
+        mut sink: impl FnMut(ScannedLog),
+    ) -> Result<ScanSummary, LogScanError> {
+        self.scan_chunks(range, filter, |_, logs| logs.into_iter().for_each(&mut sink))
+    }
+
+    /// Like [`Self::scan`], but passes the logs of each chunk to `sink` at once, together with
+    /// the range of the chunk.
+    ///
+    /// `sink` is called for every chunk in order, including chunks without matching logs.
+    pub fn scan_chunks(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        filter: &Filter,
+        mut sink: impl FnMut(RangeInclusive<BlockNumber>, Vec<ScannedLog>),
+    ) -> Result<ScanSummary, LogScanError> {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return Ok(ScanSummary::default())
+        }
+
+        let mut chunks = chunks(start, end, self.chunk_size);
+        let total_chunks = chunks.len();
+        // Keep every thread busy, with one more chunk queued for each of them
+        let max_in_flight = rayon::current_num_threads() * 2;
+
+        rayon::in_place_scope(|scope| {
+            let mut spawn = |chunk: RangeInclusive<BlockNumber>| {
+                let (tx, rx) = mpsc::sync_channel(1);
+                let range = chunk.clone();
+                scope.spawn(move |_| {
+                    let _ = tx.send(self.scan_chunk(range, filter));
+                });
+                (chunk, rx)
+            };
+
+            let mut pending = VecDeque::with_capacity(max_in_flight);
+            pending.extend(chunks.by_ref().take(max_in_flight).map(&mut spawn));
+
+            let started_at = Instant::now();
+            let mut last_report = started_at;
+            let mut blocks_scanned = 0u64;
+            let mut chunks_done = 0;
+            let mut missing = MissingReceipts::default();
+            let mut missing_reported = 0;
+            let mut failed = Vec::new();
+
+            while let Some((chunk, rx)) = pending.pop_front() {
+                let (logs, chunk_missing, chunk_failed) = loop {
+                    let timeout = self.progress_interval.saturating_sub(last_report.elapsed());
+                    match rx.recv_timeout(timeout) {
+                        Ok(result) => break result?,
+                        Err(RecvTimeoutError::Timeout) => {
+                            let elapsed = started_at.elapsed().as_secs_f64();
+                            info!(
+                                target: "providers::log_scanner",
+                                blocks_scanned,
+                                blocks_per_second = (blocks_scanned as f64 / elapsed) as u64,
+                                chunks_remaining = total_chunks - chunks_done,
+                                "Scanning logs"
+                            );
+                            last_report = Instant::now();
+                        }
+                        // The task panicked, the panic is resumed at the end of the scope
+                        Err(RecvTimeoutError::Disconnected) => return Ok(ScanSummary::default()),
+                    }
+                };
+
+                blocks_scanned += chunk.end() - chunk.start() + 1;
+                sink(chunk, logs);
+
+                // Only the last range can still grow with the next chunks
+                missing.append(chunk_missing);
+                let complete = missing.ranges.len() - missing.open as usize;
+                for range in &missing.ranges[missing_reported.min(complete)..complete] {
+                    warn!(target: "providers::log_scanner", ?range, "Skipped blocks with missing receipts");
+                }
+                missing_reported = missing_reported.max(complete);
+                for FailedBlock { block, error } in &chunk_failed {
+                    warn!(target: "providers::log_scanner", block, %error, "Failed to scan block");
+                }
+                failed.extend(chunk_failed);
+                chunks_done += 1;
+
+                pending.extend(chunks.next().map(&mut spawn));
+            }
+
+            for range in &missing.ranges[missing_reported..] {
+                warn!(target: "providers::log_scanner", ?range, "Skipped blocks with missing receipts");
+            }
+
+            Ok(ScanSummary { missing: missing.ranges, failed })
+        })
+    }
+
+    /// Scans a single chunk with a new read-only provider.
+    fn scan_chunk(
+        &self,
+        chunk: RangeInclusive<BlockNumber>,
+        filter: &Filter,
+    ) -> Result<ChunkResult, LogScanError> {
+        let provider = self.factory.database_provider_ro()?;
+        let mut logs = Vec::new();
+        let mut failed = Vec::new();
+        let options = ScanOptions {
+            index: self.bloom_index.as_ref(),
+            skip_missing: self.skip_missing,
+            header_cache: Some(&self.header_cache),
+        };
+        let missing = scan_blocks(
+            &provider,
+            chunk,
+            filter,
+            options,
+            self.continue_on_error.then_some(&mut failed),
+            |log| logs.push(log),
+        )?;
+        Ok((logs, missing, failed))
+    }
+}
+
+/// Splits `start..=end` into consecutive ranges of at most `chunk_size` blocks.
+fn chunks(
+    start: BlockNumber,
+    end: BlockNumber,
+    chunk_size: u64,
+) -> impl ExactSizeIterator<Item = RangeInclusive<BlockNumber>> {
+    let count = (end - start) / chunk_size + 1;
+    (0..count as usize).map(move |index| {
+        let chunk_start = start + index as u64 * chunk_size;
+        chunk_start..=chunk_start.saturating_add(chunk_size - 1).min(end)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockEthProvider;
+    use alloy_consensus::{BlockBody, Header, TxLegacy};
+    use alloy_primitives::{logs_bloom, Address, Bloom, Bytes, Signature, B256, U256};
+    use assert_matches::assert_matches;
+    use reth_db_api::models::StoredBlockBodyIndices;
+    use reth_ethereum_primitives::{Block, Receipt, TransactionSigned};
+    use reth_storage_errors::provider::ProviderResult;
+
+    fn log(address: Address, topic: B256) -> Log {
+        Log::new_unchecked(address, vec![topic], Bytes::new())
+    }
+
+    fn receipt(logs: Vec<Log>) -> Receipt {
+        Receipt { logs, success: true, ..Default::default() }
+    }
+
+    /// Adds a block with a transaction for each of the given receipts and the logs bloom to the
+    /// provider, and returns the block.
+    fn add_block(
+        provider: &MockEthProvider,
+        number: BlockNumber,
+        receipts: Vec<Receipt>,
+        bloom: Bloom,
+    ) -> Block {
+        let header = Header {
+            number,
+            logs_bloom: bloom,
+            timestamp: number * 12,
+            base_fee_per_gas: Some(7),
+            ..Default::default()
+        };
+        let indices =
+            StoredBlockBodyIndices { first_tx_num: number * 100, tx_count: receipts.len() as u64 };
+        provider.add_block_body_indices(number, indices);
+        let transactions = (0..receipts.len())
+            .map(|index| {
+                let tx = TxLegacy { nonce: number * 100 + index as u64, ..Default::default() };
+                TransactionSigned::new_unhashed(tx.into(), Signature::test_signature())
+            })
+            .collect();
+        let block = Block { header, body: BlockBody { transactions, ..Default::default() } };
+        provider.add_block(B256::from(U256::from(number)), block.clone());
+        provider.add_receipts(number, receipts);
+        block
+    }
+
+    fn scan(
+        provider: &MockEthProvider,
+        range: RangeInclusive<BlockNumber>,
+        filter: &Filter,
+    ) -> Result<Vec<(BlockNumber, TxIndex, Log)>, LogScanError> {
+        let mut logs = Vec::new();
+        provider
+            .scan_logs(range, filter, |log| logs.push((log.block_number, log.tx_index, log.log)))?;
+        Ok(logs)
+    }
+
+    #[test]
+    fn scans_matching_logs() {
+        let provider = MockEthProvider::default();
+        let (address, other) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let topic = B256::repeat_byte(3);
+
+        let matching = log(address, topic);
+        let unrelated = log(other, topic);
+        let receipts =
+            vec![receipt(vec![unrelated.clone()]), receipt(vec![unrelated, matching.clone()])];
+        let bloom = logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs));
+        add_block(&provider, 0, Vec::new(), Bloom::ZERO);
+        add_block(&provider, 1, receipts.clone(), bloom);
+        add_block(&provider, 2, receipts, bloom);
+
+        let filter = Filter::new().address(address).event_signature(topic);
+        assert_eq!(
+            scan(&provider, 0..=2, &filter).unwrap(),
+            vec![(1, 1, matching.clone()), (2, 1, matching)]
+        );
+        assert!(scan(&provider, 0..=2, &Filter::new().address(Address::repeat_byte(4)))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn scans_log_positions() {
+        let provider = MockEthProvider::default();
+        let address = Address::repeat_byte(1);
+
+        let (matching, other) = (log(address, B256::ZERO), log(Address::ZERO, B256::ZERO));
+        let receipts = vec![
+            receipt(vec![other.clone(), matching.clone()]),
+            receipt(Vec::new()),
+            receipt(vec![other, matching.clone(), matching.clone()]),
+        ];
+        let bloom = logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs));
+        let block = add_block(&provider, 5, receipts, bloom);
+        let block_hash = block.header.hash_slow();
+        let tx_hash = |index: usize| *block.body.transactions[index].tx_hash();
+
+        let mut logs = Vec::new();
+        provider.scan_logs(5..=5, &Filter::new().address(address), |log| logs.push(log)).unwrap();
+        assert_eq!(
+            logs,
+            [(0, 1), (2, 3), (2, 4)].map(|(tx_index, log_index)| ScannedLog {
+                block_number: 5,
+                block_hash,
+                tx_hash: tx_hash(tx_index),
+                tx_index: tx_index as TxIndex,
+                log_index,
+                block_timestamp: 60,
+                base_fee_per_gas: Some(7),
+                gas_used: 0,
+                log: matching.clone(),
+            })
+        );
+
+        // Transactions are needed for the hashes of matching logs
+        provider.blocks.lock().clear();
+        assert_matches!(
+            provider.scan_logs(5..=5, &Filter::new().address(address), |_| {}),
+            Err(LogScanError::TransactionsNotFound(5))
+        );
+    }
+
+    #[test]
+    fn query_pages() {
+        let provider = MockEthProvider::default();
+        let address = Address::repeat_byte(1);
+        let matching = log(address, B256::ZERO);
+        let receipts = vec![receipt(vec![matching.clone(), matching.clone()]), receipt(Vec::new())];
+        let bloom = logs_bloom(&receipts[0].logs);
+        let blocks = (0..10)
+            .map(|number| match number % 3 {
+                0 => add_block(&provider, number, receipts.clone(), bloom),
+                _ => add_block(&provider, number, Vec::new(), Bloom::ZERO),
+            })
+            .collect::<Vec<_>>();
+        let block = &blocks[9];
+
+        let query = LogQuery::new(0..=9, Filter::new().address(address));
+        let (all, next) = provider.query_logs(&query).unwrap();
+        assert_eq!(next, None);
+        assert_eq!(all.len(), 8);
+        assert_eq!(
+            all[7],
+            alloy_rpc_types_eth::Log {
+                inner: matching,
+                block_hash: Some(block.header.hash_slow()),
+                block_number: Some(9),
+                block_timestamp: Some(108),
+                transaction_hash: Some(*block.body.transactions[0].tx_hash()),
+                transaction_index: Some(0),
+                log_index: Some(1),
+                removed: false,
+            }
+        );
+
+        // Pages end within blocks and after the block range limit, and together return all logs
+        let query = query.with_max_logs(3).with_max_block_range(4);
+        let (mut pages, mut logs, mut next) = (Vec::new(), Vec::new(), None);
+        loop {
+            let query =
+                next.map_or_else(|| query.clone(), |next| query.clone().with_continuation(next));
+            let (page, continuation) = provider.query_logs(&query).unwrap();
+            pages.push((page.len(), continuation));
+            logs.extend(page);
+            if continuation.is_none() {
+                break
+            }
+            next = continuation;
+        }
+        assert_eq!(
+            pages,
+            vec![
+                (3, Some(Continuation { block: 3, log_index: 1 })),
+                (3, Some(Continuation { block: 8, log_index: 0 })),
+                (2, None),
+            ]
+        );
+        assert_eq!(logs, all);
+    }
+
+    #[test]
+    fn scans_with_address_and_topic_sets() {
+        let provider = MockEthProvider::default();
+        let [a, b, c] = [1, 2, 3].map(Address::repeat_byte);
+        let [x, y, z, w] = [1, 2, 3, 4].map(B256::repeat_byte);
+        let topics = |topics: &[B256]| topics.to_vec();
+
+        let blocks = [
+            vec![
+                Log::new_unchecked(a, topics(&[x, y, z]), Bytes::new()),
+                Log::new_unchecked(b, topics(&[y]), Bytes::new()),
+                Log::new_unchecked(c, topics(&[x, w, z]), Bytes::new()),
+            ],
+            vec![
+                Log::new_unchecked(b, topics(&[w, x, z, y]), Bytes::new()),
+                Log::new_unchecked(a, topics(&[]), Bytes::new()),
+            ],
+            // `c` and `w` are both in the bloom, but not in the same log
+            vec![
+                Log::new_unchecked(a, topics(&[w]), Bytes::new()),
+                Log::new_unchecked(c, topics(&[x, y, z]), Bytes::new()),
+            ],
+        ];
+        for (number, logs) in blocks.iter().enumerate() {
+            let receipts = logs.iter().map(|log| receipt(vec![log.clone()])).collect::<Vec<_>>();
+            add_block(&provider, number as u64, receipts, logs_bloom(logs));
+        }
+
+        let filters = [
+            Filter::new().address(vec![a, b]),
+            Filter::new().event_signature(vec![x, y]),
+            Filter::new().address(vec![a, b]).event_signature(vec![x, y]),
+            Filter::new().topic2(z),
+            Filter::new().event_signature(vec![x, w]).topic2(z),
+            Filter::new().address(vec![a, c]).topic1(vec![y, w]).topic2(z),
+            Filter::new().topic3(y),
+            Filter::new().address(c).topic0(w),
+        ];
+        for filter in &filters {
+            // The logs that `eth_getLogs` returns for the filter
+            let expected = blocks
+                .iter()
+                .enumerate()
+                .flat_map(|(number, logs)| {
+                    logs.iter().enumerate().map(move |(index, log)| (number as u64, index, log))
+                })
+                .filter(|(_, _, log)| filter.matches(log))
+                .map(|(number, index, log)| (number, index as TxIndex, log.clone()))
+                .collect::<Vec<_>>();
+            assert_eq!(scan(&provider, 0..=2, filter).unwrap(), expected, "{filter:?}");
+        }
+
+        // Blocks are pruned if the bloom rules out any position, even if the addresses match
+        provider.receipts.lock().clear();
+        let filter = Filter::new().address(vec![a, b]).topic1(B256::repeat_byte(5));
+        assert!(scan(&provider, 0..=2, &filter).unwrap().is_empty());
+    }
+
+    #[test]
+    fn skips_blocks_by_bloom() {
+        let provider = MockEthProvider::default();
+        let address = Address::repeat_byte(1);
+
+        // The receipts contain a matching log, but the bloom rules it out.
+        add_block(&provider, 1, vec![receipt(vec![log(address, B256::ZERO)])], Bloom::ZERO);
+
+        assert!(scan(&provider, 1..=1, &Filter::new().address(address)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn missing_receipts() {
+        let provider = MockEthProvider::default();
+        let address = Address::repeat_byte(1);
+        let receipts = vec![receipt(vec![log(address, B256::ZERO)])];
+        add_block(&provider, 1, receipts.clone(), logs_bloom(&receipts[0].logs));
+        provider.receipts.lock().remove(&1);
+
+        assert_matches!(
+            scan(&provider, 1..=1, &Filter::new().address(address)),
+            Err(LogScanError::MissingReceipts { block: 1 })
+        );
+
+        // Pruned receipts are left out of the receipts of a block
+        provider.add_receipts(1, Vec::new());
+        assert_matches!(
+            scan(&provider, 1..=1, &Filter::new().address(address)),
+            Err(LogScanError::MissingReceipts { block: 1 })
+        );
+    }
+
+    #[test]
+    fn skip_missing_receipts() {
+        let provider = MockEthProvider::default();
+        let address = Address::repeat_byte(1);
+        let receipts = vec![receipt(vec![log(address, B256::ZERO)])];
+        let bloom = logs_bloom(&receipts[0].logs);
+        for number in 0..10 {
+            add_block(&provider, number, receipts.clone(), bloom);
+        }
+        // Blocks 4 and 9 don't match, so their receipts are never checked
+        add_block(&provider, 4, Vec::new(), Bloom::ZERO);
+        add_block(&provider, 9, Vec::new(), Bloom::ZERO);
+        for number in [1, 2, 3, 5, 7, 8] {
+            provider.receipts.lock().remove(&number);
+        }
+
+        let filter = Filter::new().address(address);
+        let mut blocks = Vec::new();
+        let missing = provider
+            .scan_logs_skip_missing(0..=9, &filter, |log| blocks.push(log.block_number))
+            .unwrap();
+        assert_eq!(blocks, vec![0, 6]);
+        assert_eq!(missing, vec![1..=5, 7..=8]);
+
+        // The ranges are joined across chunks
+        for chunk_size in 1..=10 {
+            let scanner = ParallelLogScanner::new(MockFactory(provider.clone()))
+                .with_chunk_size(chunk_size)
+                .with_skip_missing(true);
+            let mut blocks = Vec::new();
+            let missing = scanner.scan(0..=9, &filter, |log| blocks.push(log.block_number));
+            assert_eq!(blocks, vec![0, 6], "chunk size {chunk_size}");
+            assert_eq!(missing.unwrap().missing, vec![1..=5, 7..=8], "chunk size {chunk_size}");
+        }
+
+        assert_matches!(
+            ParallelLogScanner::new(MockFactory(provider)).with_chunk_size(3).scan(
+                0..=9,
+                &filter,
+                |_| {}
+            ),
+            Err(LogScanError::MissingReceipts { block: 1 })
+        );
+    }
+
+    /// Opens a clone of the mock provider for every chunk.
+    struct MockFactory(MockEthProvider);
+
+    impl DatabaseProviderROFactory for MockFactory {
+        type Provider = MockEthProvider;
+
+        fn database_provider_ro(&self) -> ProviderResult<Self::Provider> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn parallel_scan_is_ordered() {
+        let provider = MockEthProvider::default();
+        let address = Address::repeat_byte(1);
+        let mut expected = Vec::new();
+        for number in 0..100 {
+            let logs = (0..3).map(|index| log(address, B256::with_last_byte(index))).collect();
+            let receipts = vec![receipt(Vec::new()), receipt(logs)];
+            let bloom = logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs));
+            expected.extend(receipts[1].logs.iter().map(|log| (number, 1, log.clone())));
+            add_block(&provider, number, receipts, bloom);
+        }
+
+        let scanner = ParallelLogScanner::new(MockFactory(provider)).with_chunk_size(7);
+        let mut logs = Vec::new();
+        scanner
+            .scan(0..=99, &Filter::new().address(address), |log| {
+                logs.push((log.block_number, log.tx_index, log.log))
+            })
+            .unwrap();
+        assert_eq!(logs, expected);
+
+        assert_matches!(
+            scanner.scan(50..=100, &Filter::new(), |_| {}).unwrap_err(),
+            LogScanError::Provider(ProviderError::HeaderNotFound(number)) if number == 100.into()
+        );
+    }
+
+    #[test]
+    fn parallel_scan_with_bloom_index() {
+        let provider = MockEthProvider::default();
+        let address = Address::repeat_byte(1);
+        let receipts = vec![receipt(vec![log(address, B256::ZERO)])];
+        let bloom = logs_bloom(&receipts[0].logs);
+
+        let end = BLOOM_INDEX_CHUNK_SIZE * 2 + 10;
+        for number in 0..=end {
+            let header = Header { number, ..Default::default() };
+            provider.add_header(B256::from(U256::from(number)), header);
+        }
+        let matching = BLOOM_INDEX_CHUNK_SIZE + 1;
+        add_block(&provider, matching, receipts.clone(), bloom);
+        add_block(&provider, end, receipts, bloom);
+
+        let mut index = BloomIndex::default();
+        index.update(&provider, end).unwrap();
+        assert_eq!(index.len(), 2);
+
+        // A block of the first chunk now matches the bloom but has no receipts, which is only
+        // noticed without the index
+        let header = Header { number: 5, logs_bloom: bloom, ..Default::default() };
+        provider.add_header(B256::from(U256::from(5)), header);
+
+        let filter = Filter::new().address(address);
+        let scanner = ParallelLogScanner::new(MockFactory(provider)).with_chunk_size(1000);
+        assert_matches!(
+            scanner.scan(0..=end, &filter, |_| {}),
+            Err(LogScanError::MissingReceipts { block: 5 })
+        );
+
+        let scanner = scanner.with_bloom_index(index);
+        let mut blocks = Vec::new();
+        scanner.scan(0..=end, &filter, |log| blocks.push(log.block_number)).unwrap();
+        assert_eq!(blocks, vec![matching, end]);
+    }
+
+    #[test]
+    fn parallel_scan_continues_on_error() {
+        let provider = MockEthProvider::default();
+        let address = Address::repeat_byte(1);
+        let receipts = vec![receipt(vec![log(address, B256::ZERO)])];
+        let bloom = logs_bloom(&receipts[0].logs);
+        for number in (0..10).filter(|number| *number != 4) {
+            add_block(&provider, number, receipts.clone(), bloom);
+        }
+        provider.receipts.lock().remove(&7);
+
+        let filter = Filter::new().address(address);
+        for chunk_size in [1, 3, 10] {
+            let scanner = ParallelLogScanner::new(MockFactory(provider.clone()))
+                .with_chunk_size(chunk_size)
+                .with_continue_on_error(true);
+            let mut blocks = Vec::new();
+            let summary =
+                scanner.scan(0..=10, &filter, |log| blocks.push(log.block_number)).unwrap();
+            assert_eq!(blocks, vec![0, 1, 2, 3, 5, 6, 8, 9], "chunk size {chunk_size}");
+
+            let failed = summary.failed.iter().map(|failed| failed.block).collect::<Vec<_>>();
+            assert_eq!(failed, vec![4, 7, 10], "chunk size {chunk_size}");
+            assert_matches!(summary.failed[1].error, LogScanError::MissingReceipts { block: 7 });
+        }
+    }
+
+    #[test]
+    fn parallel_scan_caches_headers() {
+        let provider = MockEthProvider::default();
+        let (address, other) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let receipts = vec![receipt(vec![log(address, B256::ZERO)])];
+        let bloom = logs_bloom(&receipts[0].logs);
+        for number in 0..20 {
+            add_block(&provider, number, receipts.clone(), bloom);
+        }
+
+        let scanner = ParallelLogScanner::new(MockFactory(provider.clone())).with_chunk_size(5);
+        scanner.scan(0..=19, &Filter::new().address(other), |_| {}).unwrap();
+        let mut logs = Vec::new();
+        scanner.scan(0..=19, &Filter::new().address(address), |log| logs.push(log)).unwrap();
+
+        // The blooms and headers of the previous scans are used
+        provider.headers.lock().clear();
+        scanner.scan(0..=19, &Filter::new().address(other), |_| {}).unwrap();
+        logs.clear();
+        scanner.scan(0..=19, &Filter::new().address(address), |log| logs.push(log)).unwrap();
+        assert_eq!(logs.len(), 20);
+        assert_eq!((logs[3].block_number, logs[3].block_timestamp), (3, 36));
+        assert_matches!(
+            scanner.scan(0..=20, &Filter::new(), |_| {}),
+            Err(LogScanError::Provider(ProviderError::HeaderNotFound(_)))
+        );
+    }
+
+    #[test]
+    fn split_into_chunks() {
+        assert_eq!(chunks(0, 0, 10).collect::<Vec<_>>(), vec![0..=0]);
+        assert_eq!(chunks(5, 25, 10).collect::<Vec<_>>(), vec![5..=14, 15..=24, 25..=25]);
+        assert_eq!(
+            chunks(u64::MAX - 10, u64::MAX, 10).collect::<Vec<_>>(),
+            vec![u64::MAX - 10..=u64::MAX - 1, u64::MAX..=u64::MAX]
+        );
+    }
+
+    #[test]
+    fn missing_header() {
+        let provider = MockEthProvider::default();
+        add_block(&provider, 1, Vec::new(), Bloom::ZERO);
+        add_block(&provider, 3, Vec::new(), Bloom::ZERO);
+
+        assert_matches!(
+            scan(&provider, 1..=3, &Filter::new()),
+            Err(LogScanError::Provider(ProviderError::HeaderNotFound(number))) if number == 2.into()
+        );
+        assert_matches!(
+            scan(&provider, 1..=4, &Filter::new().address(Address::ZERO)),
+            Err(LogScanError::Provider(ProviderError::HeaderNotFound(_)))
+        );
+    }
+}
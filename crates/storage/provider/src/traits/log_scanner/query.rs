@@ -0,0 +1,125 @@
+use super::{LogScanError, LogScanner, ScannedLog, HEADERS_BATCH_SIZE};
+use alloy_primitives::BlockNumber;
+use alloy_rpc_types_eth::{Filter, Log as RpcLog};
+use std::ops::RangeInclusive;
+
+/// Where the next page of a [`LogQuery`] starts: the first log that wasn't returned yet is at or
+/// after `log_index` in block `block`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Continuation {
+    /// Number of the block of the next log.
+    pub block: BlockNumber,
+    /// Index of the next log in its block.
+    pub log_index: u64,
+}
+
+/// An `eth_getLogs` query over a range of blocks that is returned in pages, see
+/// [`LogScanner::query_logs`].
+///
+/// Logs are matched like in `eth_getLogs`, and the pages together contain exactly the logs that
+/// `eth_getLogs` returns for the whole range without limits. The limits bound the size of a page
+/// like the limits that RPC providers enforce bound a response:
+/// - a page spans at most [`Self::with_max_block_range`] blocks after its first block, like
+///   `--rpc.max-blocks-per-filter`,
+/// - a page contains at most [`Self::with_max_logs`] logs. Unlike `--rpc.max-logs-per-response`, an
+///   exceeded limit doesn't fail the query, but ends the page, which may end within a block.
+#[derive(Debug, Clone)]
+pub struct LogQuery {
+    range: RangeInclusive<BlockNumber>,
+    filter: Filter,
+    max_logs: Option<usize>,
+    max_block_range: Option<u64>,
+    continuation: Option<Continuation>,
+}
+
+impl LogQuery {
+    /// Creates an unlimited query for the logs in `range` that match the address and topics of
+    /// `filter`. The block range of the `filter` is ignored.
+    pub const fn new(range: RangeInclusive<BlockNumber>, filter: Filter) -> Self {
+        Self { range, filter, max_logs: None, max_block_range: None, continuation: None }
+    }
+
+    /// Sets the maximum number of logs in a page.
+    pub const fn with_max_logs(mut self, max_logs: usize) -> Self {
+        self.max_logs = Some(if max_logs == 0 { 1 } else { max_logs });
+        self
+    }
+
+    /// Sets the maximum number of blocks in a page after its first block.
+    pub const fn with_max_block_range(mut self, max_block_range: u64) -> Self {
+        self.max_block_range = Some(max_block_range);
+        self
+    }
+
+    /// Sets where the page starts, as returned by [`LogScanner::query_logs`] for the previous
+    /// page.
+    pub const fn with_continuation(mut self, continuation: Continuation) -> Self {
+        self.continuation = Some(continuation);
+        self
+    }
+}
+
+/// Returns the page of `query`, see [`LogScanner::query_logs`].
+pub(super) fn query_logs<P: LogScanner + ?Sized>(
+    provider: &P,
+    query: &LogQuery,
+) -> Result<(Vec<RpcLog>, Option<Continuation>), LogScanError> {
+    let (start, end) = query.range.clone().into_inner();
+    let Continuation { block: first, log_index: skip } =
+        query.continuation.unwrap_or(Continuation { block: start, log_index: 0 });
+    let first = first.max(start);
+    if first > end {
+        return Ok((Vec::new(), None))
+    }
+    let page_end = query.max_block_range.map_or(end, |max| first.saturating_add(max).min(end));
+    let max_logs = query.max_logs.unwrap_or(usize::MAX);
+
+    // The page is scanned in batches, so that a page that is full doesn't scan its remaining
+    // blocks
+    let mut logs = Vec::new();
+    let mut batch_start = first;
+    loop {
+        let batch_end = batch_start.saturating_add(HEADERS_BATCH_SIZE - 1).min(page_end);
+        let mut next = None;
+        provider.scan_logs(batch_start..=batch_end, &query.filter, |log| {
+            if next.is_some() || (log.block_number == first && log.log_index < skip) {
+                return
+            }
+            if logs.len() == max_logs {
+                next = Some(Continuation { block: log.block_number, log_index: log.log_index });
+                return
+            }
+            logs.push(log.into());
+        })?;
+
+        if next.is_some() {
+            return Ok((logs, next))
+        }
+        if batch_end == page_end {
+            break
+        }
+        if logs.len() == max_logs {
+            return Ok((logs, Some(Continuation { block: batch_end + 1, log_index: 0 })))
+        }
+        batch_start = batch_end + 1;
+    }
+
+    let next = (page_end < end).then(|| Continuation { block: page_end + 1, log_index: 0 });
+    Ok((logs, next))
+}
+
+impl From<ScannedLog> for RpcLog {
+    /// Converts the log like the RPC does for `eth_getLogs`.
+    fn from(log: ScannedLog) -> Self {
+        Self {
+            inner: log.log,
+            block_hash: Some(log.block_hash),
+            block_number: Some(log.block_number),
+            block_timestamp: Some(log.block_timestamp),
+            transaction_hash: Some(log.tx_hash),
+            transaction_index: Some(log.tx_index),
+            log_index: Some(log.log_index),
+            removed: false,
+        }
+    }
+}
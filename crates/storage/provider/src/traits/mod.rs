@@ -13,3 +13,19 @@ pub use rocksdb_provider::RocksDBProviderFactory;
 
 mod full;
 pub use full::FullProvider;
+
+mod code_search;
+pub use code_search::{CodeMatch, CodePattern, CodeSearch, DEFAULT_CODE_SEARCH_SHARDS};
+
+mod log_scanner;
+pub use log_scanner::{
+    BloomIndex, BloomIndexError, Continuation, FailedBlock, LogQuery, LogScanError, LogScanner,
+    ParallelLogScanner, ScanSummary, ScannedLog, BLOOM_INDEX_CHUNK_SIZE, DEFAULT_SCAN_CHUNK_SIZE,
+    DEFAULT_SCAN_PROGRESS_INTERVAL,
+};
+
+mod tx_context;
+pub use tx_context::{
+    par_transactions_with_context, TxContext, TxContextIter, TxContextReader,
+    DEFAULT_TX_CONTEXT_CHUNK_SIZE,
+};
@@ -0,0 +1,251 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, BlockNumber, TxIndex};
+use rayon::prelude::*;
+use reth_primitives_traits::SignedTransaction;
+use reth_storage_api::{
+    BlockBodyIndicesProvider, DatabaseProviderROFactory, HeaderProvider, TransactionsProvider,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{collections::VecDeque, ops::RangeInclusive};
+
+/// How many blocks are loaded at once by the [`TxContextIter`].
+const TX_CONTEXT_BATCH_SIZE: u64 = 1_000;
+
+/// Default number of blocks read by a single task of [`par_transactions_with_context`].
+pub const DEFAULT_TX_CONTEXT_CHUNK_SIZE: u64 = 10_000;
+
+/// A transaction returned by [`TxContextReader::transactions_with_context`], with its sender and
+/// its position in the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxContext<T> {
+    /// Number of the block that contains the transaction.
+    pub block_number: BlockNumber,
+    /// Timestamp of the block.
+    pub block_timestamp: u64,
+    /// Index of the transaction in the block.
+    pub tx_index: TxIndex,
+    /// Sender of the transaction.
+    pub sender: Address,
+    /// The transaction.
+    pub transaction: T,
+}
+
+/// Reads the transactions of a range of blocks together with their senders and blocks.
+///
+/// This is implemented for every provider that can read headers, block bodies and transactions.
+pub trait TxContextReader:
+    HeaderProvider + BlockBodyIndicesProvider + TransactionsProvider<Transaction: SignedTransaction>
+{
+    /// Returns an iterator over the transactions in `range`, in order.
+    ///
+    /// The headers, block bodies and transactions are read in batches of blocks, which reads them
+    /// sequentially from the static files. Senders are read from the provider where they are
+    /// stored, and recovered from the signatures of the transactions of a batch if some of them
+    /// are missing, e.g. because they have been pruned.
+    ///
+    /// Yields [`ProviderError::HeaderNotFound`], [`ProviderError::BlockBodyIndicesNotFound`] or
+    /// [`ProviderError::TransactionNotFound`] if the data of a block in `range` is missing, and
+    /// [`ProviderError::SenderRecoveryError`] if a sender can't be recovered. The iterator ends
+    /// after the first error.
+    fn transactions_with_context(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> TxContextIter<'_, Self> {
+        let (start, end) = range.into_inner();
+        TxContextIter {
+            provider: self,
+            next_block: start,
+            end,
+            done: start > end,
+            batch: Default::default(),
+        }
+    }
+}
+
+impl<T> TxContextReader for T where
+    T: HeaderProvider
+        + BlockBodyIndicesProvider
+        + TransactionsProvider<Transaction: SignedTransaction>
+{
+}
+
+/// Iterator over the transactions of a range of blocks, see
+/// [`TxContextReader::transactions_with_context`].
+#[derive(Debug)]
+pub struct TxContextIter<'a, P: TransactionsProvider + ?Sized> {
+    provider: &'a P,
+    /// The first block that wasn't loaded yet.
+    next_block: BlockNumber,
+    end: BlockNumber,
+    /// Whether all blocks were loaded, or loading failed.
+    done: bool,
+    /// The transactions that were loaded but not returned yet.
+    batch: VecDeque<TxContext<P::Transaction>>,
+}
+
+impl<P: TxContextReader + ?Sized> TxContextIter<'_, P> {
+    /// Loads the transactions of the next batch of blocks.
+    fn load_batch(&mut self) -> ProviderResult<()> {
+        let start = self.next_block;
+        let end = start.saturating_add(TX_CONTEXT_BATCH_SIZE - 1).min(self.end);
+        let blocks = (end - start + 1) as usize;
+
+        let headers = self.provider.headers_range(start..=end)?;
+        if headers.len() < blocks {
+            let number = start + headers.len() as u64;
+            return Err(ProviderError::HeaderNotFound(number.into()))
+        }
+        let bodies = self.provider.block_body_indices_range(start..=end)?;
+        if bodies.len() < blocks {
+            return Err(ProviderError::BlockBodyIndicesNotFound(start + bodies.len() as u64))
+        }
+
+        let tx_range = bodies[0].first_tx_num()..bodies[blocks - 1].next_tx_num();
+        let transactions = self.provider.transactions_by_tx_range(tx_range.clone())?;
+        if transactions.len() as u64 != tx_range.end - tx_range.start {
+            let tx_num = tx_range.start + transactions.len() as u64;
+            return Err(ProviderError::TransactionNotFound(tx_num.into()))
+        }
+        let mut senders = self.provider.senders_by_tx_range(tx_range)?;
+        if senders.len() != transactions.len() {
+            senders = transactions
+                .iter()
+                .map(|tx| tx.recover_signer().map_err(|_| ProviderError::SenderRecoveryError))
+                .collect::<ProviderResult<_>>()?;
+        }
+
+        let mut transactions = transactions.into_iter().zip(senders);
+        for (number, (header, body)) in (start..).zip(headers.iter().zip(&bodies)) {
+            for (tx_index, (transaction, sender)) in
+                transactions.by_ref().take(body.tx_count() as usize).enumerate()
+            {
+                self.batch.push_back(TxContext {
+                    block_number: number,
+                    block_timestamp: header.timestamp(),
+                    tx_index: tx_index as TxIndex,
+                    sender,
+                    transaction,
+                });
+            }
+        }
+
+        self.done = end == self.end;
+        self.next_block = end.saturating_add(1);
+        Ok(())
+    }
+}
+
+impl<P: TxContextReader + ?Sized> Iterator for TxContextIter<'_, P> {
+    type Item = ProviderResult<TxContext<P::Transaction>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Batches without transactions are skipped
+        while self.batch.is_empty() {
+            if self.done {
+                return None
+            }
+            if let Err(err) = self.load_batch() {
+                self.done = true;
+                return Some(Err(err))
+            }
+        }
+        self.batch.pop_front().map(Ok)
+    }
+}
+
+/// Returns a rayon iterator over the transactions in `range`, read in chunks of `chunk_size`
+/// blocks with [`TxContextReader::transactions_with_context`] by tasks that open their own
+/// read-only providers.
+///
+/// Each item holds the transactions of one chunk. The iterator is indexed, so collecting it keeps
+/// the chunks in order.
+pub fn par_transactions_with_context<F>(
+    factory: &F,
+    range: RangeInclusive<BlockNumber>,
+    chunk_size: u64,
+) -> impl IndexedParallelIterator<
+    Item = ProviderResult<Vec<TxContext<<F::Provider as TransactionsProvider>::Transaction>>>,
+> + '_
+where
+    F: DatabaseProviderROFactory<Provider: TxContextReader> + Sync,
+{
+    let chunk_size = chunk_size.max(1);
+    let (start, end) = range.into_inner();
+    let chunks = if start > end { 0 } else { (end - start) / chunk_size + 1 };
+    (0..chunks as usize).into_par_iter().map(move |chunk| {
+        let chunk_start = start + chunk as u64 * chunk_size;
+        let chunk_end = chunk_start.saturating_add(chunk_size - 1).min(end);
+        factory.database_provider_ro()?.transactions_with_context(chunk_start..=chunk_end).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_provider_factory, BlockWriter};
+    use alloy_primitives::B256;
+    use assert_matches::assert_matches;
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_prune_types::{PruneMode, PruneModes};
+    use reth_storage_api::DatabaseProviderFactory;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[test]
+    fn reads_transactions_with_context() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=5,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..3, ..Default::default() },
+        );
+        let expected = blocks
+            .iter()
+            .flat_map(|block| {
+                block.body().transactions.iter().enumerate().map(|(tx_index, tx)| TxContext {
+                    block_number: block.number,
+                    block_timestamp: block.timestamp,
+                    tx_index: tx_index as TxIndex,
+                    sender: tx.recover_signer().unwrap(),
+                    transaction: tx.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // Senders are recovered if they were pruned
+        let pruned = PruneModes { sender_recovery: Some(PruneMode::Full), ..Default::default() };
+        for prune_modes in [PruneModes::default(), pruned] {
+            let factory = create_test_provider_factory().with_prune_modes(prune_modes);
+            let provider = factory.database_provider_rw().unwrap();
+            for block in &blocks {
+                provider.insert_block(block.clone().try_recover().unwrap()).unwrap();
+            }
+            provider.commit().unwrap();
+
+            let provider = factory.database_provider_ro().unwrap();
+            let read =
+                provider.transactions_with_context(0..=5).collect::<ProviderResult<Vec<_>>>();
+            assert_eq!(read.unwrap(), expected);
+            let read =
+                provider.transactions_with_context(2..=3).collect::<ProviderResult<Vec<_>>>();
+            assert_eq!(
+                read.unwrap(),
+                expected
+                    .iter()
+                    .filter(|tx| (2..=3).contains(&tx.block_number))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            );
+
+            let chunks = par_transactions_with_context(&factory, 0..=5, 4)
+                .collect::<ProviderResult<Vec<_>>>()
+                .unwrap();
+            assert_eq!(chunks.len(), 2);
+            assert_eq!(chunks.concat(), expected);
+
+            // The iterator ends after the batch with a missing block
+            let mut read = provider.transactions_with_context(0..=6);
+            assert_matches!(read.next(), Some(Err(ProviderError::HeaderNotFound(_))));
+            assert_matches!(read.next(), None);
+        }
+    }
+}
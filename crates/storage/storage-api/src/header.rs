@@ -1,6 +1,6 @@
 use alloc::vec::Vec;
 use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::{BlockHash, BlockNumber};
+use alloy_primitives::{BlockHash, BlockNumber, Bloom};
 use core::ops::RangeBounds;
 use reth_primitives_traits::{BlockHeader, SealedHeader};
 use reth_storage_errors::provider::ProviderResult;
@@ -64,6 +64,22 @@ pub trait HeaderProvider: Send + Sync {
         self.sealed_headers_while(range, |_| true)
     }
 
+    /// Get the logs blooms of the headers in range of block numbers, with their block numbers.
+    ///
+    /// This is cheaper than [`Self::sealed_headers_range`] if only the blooms are needed, e.g. to
+    /// find the blocks that may contain logs that match a filter, since no block hashes are loaded
+    /// and no headers are kept.
+    fn block_bloom_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Bloom)>> {
+        Ok(self
+            .headers_range(range)?
+            .iter()
+            .map(|header| (header.number(), header.logs_bloom()))
+            .collect())
+    }
+
     /// Get sealed headers while `predicate` returns `true` or the range is exhausted.
     fn sealed_headers_while(
         &self,
@@ -7,6 +7,8 @@ use reth_storage_errors::provider::{ProviderError, ProviderResult};
 pub mod keys {
     /// Storage configuration settings for this node.
     pub const STORAGE_SETTINGS: &str = "storage_settings";
+    /// The version of the schema of the tables, bumped by every migration.
+    pub const SCHEMA_VERSION: &str = "schema_version";
 }
 
 /// Client trait for reading node metadata from the database.
@@ -21,6 +23,13 @@ pub trait MetadataProvider: Send + Sync {
             .map(|bytes| serde_json::from_slice(&bytes).map_err(ProviderError::other))
             .transpose()
     }
+
+    /// Get the schema version of the tables, `None` if the database predates schema versions.
+    fn schema_version(&self) -> ProviderResult<Option<u64>> {
+        self.get_metadata(keys::SCHEMA_VERSION)?
+            .map(|bytes| serde_json::from_slice(&bytes).map_err(ProviderError::other))
+            .transpose()
+    }
 }
 
 /// Client trait for writing node metadata to the database.
@@ -38,6 +47,14 @@ pub trait MetadataWriter: Send + Sync {
             serde_json::to_vec(&settings).map_err(ProviderError::other)?,
         )
     }
+
+    /// Write the schema version of the tables
+    fn write_schema_version(&self, version: u64) -> ProviderResult<()> {
+        self.write_metadata(
+            keys::SCHEMA_VERSION,
+            serde_json::to_vec(&version).map_err(ProviderError::other)?,
+        )
+    }
 }
 
 /// Trait for caching storage settings on a provider factory.
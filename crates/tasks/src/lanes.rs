@@ -0,0 +1,97 @@
+//! Lanes of blocking tasks that are limited independently of each other.
+
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Default maximum number of concurrently running blocking tasks of the
+/// [`BlockingTaskLane::Sync`] lane.
+pub const DEFAULT_MAX_SYNC_BLOCKING_TASKS: usize = 64;
+
+/// Default maximum number of concurrently running blocking tasks of the
+/// [`BlockingTaskLane::Rpc`] lane.
+pub const DEFAULT_MAX_RPC_BLOCKING_TASKS: usize = 256;
+
+/// Default maximum number of concurrently running blocking tasks of the
+/// [`BlockingTaskLane::Maintenance`] lane.
+pub const DEFAULT_MAX_MAINTENANCE_BLOCKING_TASKS: usize = 16;
+
+/// The lane of blocking tasks that a [`TaskExecutor`](crate::TaskExecutor) spawns its blocking
+/// tasks onto, see [`TaskExecutor::with_blocking_lane`](crate::TaskExecutor::with_blocking_lane).
+///
+/// All lanes share the blocking thread pool of the tokio runtime, but each lane limits how many
+/// of its tasks run at once. Tasks above the limit wait for a slot of their lane instead of
+/// occupying a thread, so that one lane can't starve the others as long as the sum of the limits
+/// is below the maximum number of blocking threads of the runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockingTaskLane {
+    /// Blocking tasks that the pipeline and the engine spawn through the executor.
+    ///
+    /// The pipeline itself runs as a critical blocking task, and its stages run on its thread and
+    /// on dedicated threads and thread pools, so they aren't limited by the lane.
+    Sync,
+    /// Blocking work of RPC requests, e.g. `eth_call` and `eth_estimateGas`.
+    Rpc,
+    /// Blocking background maintenance, e.g. of the transaction pool.
+    Maintenance,
+}
+
+/// Maximum number of concurrently running blocking tasks of each [`BlockingTaskLane`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockingTaskLaneLimits {
+    /// Limit of the [`BlockingTaskLane::Sync`] lane.
+    pub sync: usize,
+    /// Limit of the [`BlockingTaskLane::Rpc`] lane.
+    pub rpc: usize,
+    /// Limit of the [`BlockingTaskLane::Maintenance`] lane.
+    pub maintenance: usize,
+}
+
+impl Default for BlockingTaskLaneLimits {
+    fn default() -> Self {
+        Self {
+            sync: DEFAULT_MAX_SYNC_BLOCKING_TASKS,
+            rpc: DEFAULT_MAX_RPC_BLOCKING_TASKS,
+            maintenance: DEFAULT_MAX_MAINTENANCE_BLOCKING_TASKS,
+        }
+    }
+}
+
+/// The slots of the [`BlockingTaskLane`]s, shared by all clones of a
+/// [`TaskExecutor`](crate::TaskExecutor).
+#[derive(Debug, Clone)]
+pub struct BlockingTaskLanes {
+    sync: Arc<Semaphore>,
+    rpc: Arc<Semaphore>,
+    maintenance: Arc<Semaphore>,
+}
+
+impl BlockingTaskLanes {
+    /// Creates the lanes with the given limits.
+    pub fn new(limits: BlockingTaskLaneLimits) -> Self {
+        Self {
+            sync: Arc::new(Semaphore::new(limits.sync)),
+            rpc: Arc::new(Semaphore::new(limits.rpc)),
+            maintenance: Arc::new(Semaphore::new(limits.maintenance)),
+        }
+    }
+
+    /// Returns the number of tasks that can be started on the lane without waiting.
+    pub fn available(&self, lane: BlockingTaskLane) -> usize {
+        self.semaphore(lane).available_permits()
+    }
+
+    /// Returns the semaphore that limits the tasks of the lane.
+    pub(crate) const fn semaphore(&self, lane: BlockingTaskLane) -> &Arc<Semaphore> {
+        match lane {
+            BlockingTaskLane::Sync => &self.sync,
+            BlockingTaskLane::Rpc => &self.rpc,
+            BlockingTaskLane::Maintenance => &self.maintenance,
+        }
+    }
+}
+
+impl Default for BlockingTaskLanes {
+    fn default() -> Self {
+        Self::new(BlockingTaskLaneLimits::default())
+    }
+}
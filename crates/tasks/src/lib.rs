@@ -13,8 +13,12 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 use crate::{
+    lanes::{BlockingTaskLane, BlockingTaskLanes},
     metrics::{IncCounterOnDrop, TaskExecutorMetrics},
-    shutdown::{signal, GracefulShutdown, GracefulShutdownGuard, Shutdown, Signal},
+    shutdown::{
+        signal, GracefulShutdown, GracefulShutdownGuard, GracefulTasks, Shutdown, Signal,
+        UNNAMED_TASK,
+    },
 };
 use dyn_clone::DynClone;
 use futures_util::{
@@ -25,20 +29,21 @@ use std::{
     any::Any,
     fmt::{Display, Formatter},
     pin::{pin, Pin},
-    sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc, OnceLock,
-    },
+    sync::{Arc, OnceLock},
     task::{ready, Context, Poll},
 };
 use tokio::{
     runtime::Handle,
-    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    sync::{
+        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        OwnedSemaphorePermit,
+    },
     task::JoinHandle,
 };
 use tracing::{debug, error};
 use tracing_futures::Instrument;
 
+pub mod lanes;
 pub mod metrics;
 pub mod shutdown;
 
@@ -172,8 +177,10 @@ pub struct TaskManager {
     signal: Option<Signal>,
     /// Receiver of the shutdown signal.
     on_shutdown: Shutdown,
-    /// How many [`GracefulShutdown`] tasks are currently active
-    graceful_tasks: Arc<AtomicUsize>,
+    /// The [`GracefulShutdown`] tasks that are currently active
+    graceful_tasks: Arc<GracefulTasks>,
+    /// The lanes of blocking tasks of the executors
+    blocking_lanes: BlockingTaskLanes,
 }
 
 // === impl TaskManager ===
@@ -205,7 +212,8 @@ impl TaskManager {
             task_events_rx,
             signal: Some(signal),
             on_shutdown,
-            graceful_tasks: Arc::new(AtomicUsize::new(0)),
+            graceful_tasks: Default::default(),
+            blocking_lanes: Default::default(),
         };
 
         let _ = GLOBAL_EXECUTOR
@@ -224,6 +232,8 @@ impl TaskManager {
             task_events_tx: self.task_events_tx.clone(),
             metrics: Default::default(),
             graceful_tasks: Arc::clone(&self.graceful_tasks),
+            blocking_lanes: self.blocking_lanes.clone(),
+            blocking_lane: None,
         }
     }
 
@@ -242,7 +252,7 @@ impl TaskManager {
     fn do_graceful_shutdown(self, timeout: Option<std::time::Duration>) -> bool {
         drop(self.signal);
         let when = timeout.map(|t| std::time::Instant::now() + t);
-        while self.graceful_tasks.load(Ordering::Relaxed) > 0 {
+        while self.graceful_tasks.count() > 0 {
             if when.map(|when| std::time::Instant::now() > when).unwrap_or(false) {
                 debug!("graceful shutdown timed out");
                 return false
@@ -328,8 +338,12 @@ pub struct TaskExecutor {
     task_events_tx: UnboundedSender<TaskEvent>,
     /// Task Executor Metrics
     metrics: TaskExecutorMetrics,
-    /// How many [`GracefulShutdown`] tasks are currently active
-    graceful_tasks: Arc<AtomicUsize>,
+    /// The [`GracefulShutdown`] tasks that are currently active
+    graceful_tasks: Arc<GracefulTasks>,
+    /// The lanes of blocking tasks
+    blocking_lanes: BlockingTaskLanes,
+    /// The lane that regular blocking tasks are spawned onto, if any
+    blocking_lane: Option<BlockingTaskLane>,
 }
 
 // === impl TaskExecutor ===
@@ -362,6 +376,27 @@ impl TaskExecutor {
         &self.on_shutdown
     }
 
+    /// Returns the executor with the given lanes of blocking tasks, which are shared by all clones
+    /// of the returned executor.
+    pub fn with_blocking_task_lanes(mut self, blocking_lanes: BlockingTaskLanes) -> Self {
+        self.blocking_lanes = blocking_lanes;
+        self
+    }
+
+    /// Returns the lanes of blocking tasks.
+    pub const fn blocking_task_lanes(&self) -> &BlockingTaskLanes {
+        &self.blocking_lanes
+    }
+
+    /// Returns the executor that spawns regular blocking tasks onto the given lane.
+    ///
+    /// A task of the lane only occupies a thread of the blocking pool once the lane has a free
+    /// slot. Critical blocking tasks are long running and are not limited by the lane.
+    pub fn with_blocking_lane(mut self, lane: BlockingTaskLane) -> Self {
+        self.blocking_lane = Some(lane);
+        self
+    }
+
     /// Spawns a future on the tokio runtime depending on the [`TaskKind`]
     fn spawn_on_rt<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
@@ -400,6 +435,29 @@ impl TaskExecutor {
         }
         .in_current_span();
 
+        if let (TaskKind::Blocking, Some(lane)) = (&task_kind, self.blocking_lane) {
+            // The permit is moved into the blocking task, so the slot of the lane is only freed
+            // once the task is done, even if the returned handle is aborted or dropped
+            let semaphore = Arc::clone(self.blocking_lanes.semaphore(lane));
+            let handle = self.handle.clone();
+            let spawn_blocking = move |permit: OwnedSemaphorePermit| {
+                let blocking_handle = handle.clone();
+                handle.spawn_blocking(move || {
+                    let _permit = permit;
+                    blocking_handle.block_on(task)
+                })
+            };
+
+            if let Ok(permit) = Arc::clone(&semaphore).try_acquire_owned() {
+                return spawn_blocking(permit)
+            }
+            return self.handle.spawn(async move {
+                // Wait for a slot of the lane before occupying a thread of the blocking pool
+                let Ok(permit) = semaphore.acquire_owned().await else { return };
+                let _ = spawn_blocking(permit).await;
+            })
+        }
+
         self.spawn_on_rt(task, task_kind)
     }
 
@@ -559,7 +617,7 @@ impl TaskExecutor {
         let panicked_tasks_tx = self.task_events_tx.clone();
         let on_shutdown = GracefulShutdown::new(
             self.on_shutdown.clone(),
-            GracefulShutdownGuard::new(Arc::clone(&self.graceful_tasks)),
+            GracefulShutdownGuard::new(Arc::clone(&self.graceful_tasks), name),
         );
         let fut = f(on_shutdown);
 
@@ -605,13 +663,22 @@ impl TaskExecutor {
     {
         let on_shutdown = GracefulShutdown::new(
             self.on_shutdown.clone(),
-            GracefulShutdownGuard::new(Arc::clone(&self.graceful_tasks)),
+            GracefulShutdownGuard::new(Arc::clone(&self.graceful_tasks), UNNAMED_TASK),
         );
         let fut = f(on_shutdown);
 
         self.handle.spawn(fut)
     }
 
+    /// Returns the names of the tasks that hold a [`GracefulShutdownGuard`] and have not completed
+    /// yet, with how many guards each of them holds.
+    ///
+    /// These are the tasks a graceful shutdown waits for. Tasks spawned without a name are
+    /// reported as `unnamed`.
+    pub fn pending_graceful_tasks(&self) -> Vec<(&'static str, usize)> {
+        self.graceful_tasks.pending()
+    }
+
     /// Sends a request to the `TaskManager` to initiate a graceful shutdown.
     ///
     /// Caution: This will terminate the entire program.
@@ -627,7 +694,7 @@ impl TaskExecutor {
 
         Ok(GracefulShutdown::new(
             self.on_shutdown.clone(),
-            GracefulShutdownGuard::new(Arc::clone(&self.graceful_tasks)),
+            GracefulShutdownGuard::new(Arc::clone(&self.graceful_tasks), UNNAMED_TASK),
         ))
     }
 }
@@ -724,7 +791,11 @@ pub struct NoCurrentTaskExecutorError;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::{sync::atomic::AtomicBool, time::Duration};
+    use crate::lanes::BlockingTaskLaneLimits;
+    use std::{
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+        time::Duration,
+    };
 
     #[test]
     fn test_cloneable() {
@@ -864,6 +935,31 @@ mod tests {
         assert!(!val.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn test_pending_graceful_tasks() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::new(handle);
+        let executor = manager.executor();
+
+        let (signal, shutdown) = signal();
+        executor.spawn_critical_with_graceful_shutdown_signal(
+            "grace",
+            move |graceful| async move {
+                let _graceful = graceful;
+                shutdown.await;
+            },
+        );
+        assert_eq!(executor.pending_graceful_tasks(), vec![("grace", 1)]);
+
+        drop(signal);
+        let start = std::time::Instant::now();
+        while !executor.pending_graceful_tasks().is_empty() {
+            assert!(start.elapsed() < Duration::from_secs(5), "task did not complete");
+            std::thread::yield_now();
+        }
+    }
+
     #[test]
     fn can_access_global() {
         let runtime = tokio::runtime::Runtime::new().unwrap();
@@ -908,4 +1004,67 @@ mod tests {
             "Task should have received the shutdown signal and set the flag"
         );
     }
+
+    #[test]
+    fn test_saturated_blocking_lane_does_not_delay_other_lanes() {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .max_blocking_threads(4)
+            .enable_time()
+            .build()
+            .unwrap();
+        let manager = TaskManager::new(runtime.handle().clone());
+        let lanes =
+            BlockingTaskLanes::new(BlockingTaskLaneLimits { sync: 2, rpc: 2, maintenance: 0 });
+        let executor = manager.executor().with_blocking_task_lanes(lanes.clone());
+
+        // More sync tasks than blocking threads of the runtime
+        let sync_executor = executor.clone().with_blocking_lane(BlockingTaskLane::Sync);
+        for _ in 0..8 {
+            sync_executor.spawn_blocking(async { std::thread::sleep(Duration::from_millis(500)) });
+        }
+
+        runtime.block_on(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert_eq!(lanes.available(BlockingTaskLane::Sync), 0);
+
+            let rpc = executor.with_blocking_lane(BlockingTaskLane::Rpc).spawn_blocking(async {});
+            tokio::time::timeout(Duration::from_millis(250), rpc)
+                .await
+                .expect("rpc task should not wait for the sync lane")
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn test_aborted_blocking_lane_task_keeps_its_slot() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let manager = TaskManager::new(runtime.handle().clone());
+        let lanes =
+            BlockingTaskLanes::new(BlockingTaskLaneLimits { sync: 1, rpc: 1, maintenance: 0 });
+        let executor = manager
+            .executor()
+            .with_blocking_task_lanes(lanes.clone())
+            .with_blocking_lane(BlockingTaskLane::Sync);
+
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let task = executor.spawn_blocking(async move {
+            started_tx.send(()).unwrap();
+            let _ = release_rx.recv();
+        });
+        started_rx.recv().unwrap();
+
+        // The blocking work can't be interrupted, so it still occupies the lane
+        task.abort();
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(lanes.available(BlockingTaskLane::Sync), 0);
+
+        release_tx.send(()).unwrap();
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while lanes.available(BlockingTaskLane::Sync) == 0 {
+            assert!(std::time::Instant::now() < deadline, "slot should be freed by the task");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
 }
@@ -5,9 +5,13 @@ use futures_util::{
     FutureExt,
 };
 use std::{
+    collections::HashMap,
     future::Future,
     pin::Pin,
-    sync::{atomic::AtomicUsize, Arc},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
     task::{ready, Context, Poll},
 };
 use tokio::sync::oneshot;
@@ -45,27 +49,88 @@ impl Clone for GracefulShutdown {
     fn clone(&self) -> Self {
         Self {
             shutdown: self.shutdown.clone(),
-            guard: self.guard.as_ref().map(|g| GracefulShutdownGuard::new(Arc::clone(&g.0))),
+            guard: self
+                .guard
+                .as_ref()
+                .map(|g| GracefulShutdownGuard::new(Arc::clone(&g.tasks), g.name)),
         }
     }
 }
 
+/// Name reported for tasks that were spawned without a name.
+pub(crate) const UNNAMED_TASK: &str = "unnamed";
+
+/// Keeps track of the tasks that hold a [`GracefulShutdownGuard`].
+#[derive(Debug, Default)]
+pub(crate) struct GracefulTasks {
+    /// How many guards are currently active
+    count: AtomicUsize,
+    /// How many guards are currently active per task name
+    names: Mutex<HashMap<&'static str, usize>>,
+}
+
+impl GracefulTasks {
+    /// Returns how many guards are currently active.
+    pub(crate) fn count(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the names of the tasks that currently hold a guard, with how many guards each of
+    /// them holds, sorted by name.
+    pub(crate) fn pending(&self) -> Vec<(&'static str, usize)> {
+        let mut pending = self
+            .names
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .map(|(name, count)| (*name, *count))
+            .collect::<Vec<_>>();
+        pending.sort_unstable();
+        pending
+    }
+
+    fn add(&self, name: &'static str) {
+        *self
+            .names
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(name)
+            .or_default() += 1;
+        self.count.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn remove(&self, name: &'static str) {
+        let mut names = self.names.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(count) = names.get_mut(name) {
+            *count -= 1;
+            if *count == 0 {
+                names.remove(name);
+            }
+        }
+        drop(names);
+        self.count.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 /// A guard that fires once dropped to signal the [`TaskManager`](crate::TaskManager) that the
 /// [`GracefulShutdown`] has completed.
 #[derive(Debug)]
 #[must_use = "if unused the task will not be gracefully shutdown"]
-pub struct GracefulShutdownGuard(Arc<AtomicUsize>);
+pub struct GracefulShutdownGuard {
+    tasks: Arc<GracefulTasks>,
+    name: &'static str,
+}
 
 impl GracefulShutdownGuard {
-    pub(crate) fn new(counter: Arc<AtomicUsize>) -> Self {
-        counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        Self(counter)
+    pub(crate) fn new(tasks: Arc<GracefulTasks>, name: &'static str) -> Self {
+        tasks.add(name);
+        Self { tasks, name }
     }
 }
 
 impl Drop for GracefulShutdownGuard {
     fn drop(&mut self) {
-        self.0.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+        self.tasks.remove(self.name);
     }
 }
 
@@ -144,6 +209,25 @@ mod tests {
         join_all(tasks).await;
     }
 
+    #[test]
+    fn test_pending_graceful_tasks() {
+        let tasks = Arc::new(GracefulTasks::default());
+        let first = GracefulShutdownGuard::new(Arc::clone(&tasks), "engine");
+        let second = GracefulShutdownGuard::new(Arc::clone(&tasks), "engine");
+        let third = GracefulShutdownGuard::new(Arc::clone(&tasks), "blockchain-tree");
+        assert_eq!(tasks.count(), 3);
+        assert_eq!(tasks.pending(), vec![("blockchain-tree", 1), ("engine", 2)]);
+
+        drop(first);
+        drop(third);
+        assert_eq!(tasks.count(), 1);
+        assert_eq!(tasks.pending(), vec![("engine", 1)]);
+
+        drop(second);
+        assert_eq!(tasks.count(), 0);
+        assert!(tasks.pending().is_empty());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_drop_signal_from_thread() {
         let (signal, shutdown) = signal();
@@ -16,6 +16,7 @@ use opentelemetry_sdk::{
     Resource,
 };
 use opentelemetry_semantic_conventions::{attribute::SERVICE_VERSION, SCHEMA_URL};
+use std::sync::{Mutex, PoisonError};
 use tracing::Subscriber;
 use tracing_opentelemetry::OpenTelemetryLayer;
 use tracing_subscriber::registry::LookupSpan;
@@ -25,6 +26,9 @@ use url::Url;
 // See also <https://opentelemetry.io/docs/languages/sdk-configuration/otlp-exporter/#otel_exporter_otlp_traces_endpoint>.
 const HTTP_TRACE_ENDPOINT: &str = "/v1/traces";
 
+/// The tracer provider installed by [`span_layer`], shut down by [`OtlpGuard`].
+static TRACER_PROVIDER: Mutex<Option<SdkTracerProvider>> = Mutex::new(None);
+
 /// Creates a tracing [`OpenTelemetryLayer`] that exports spans to an OTLP endpoint.
 ///
 /// This layer can be added to a [`tracing_subscriber::Registry`] to enable `OpenTelemetry` tracing
@@ -57,11 +61,41 @@ where
         .build();
 
     global::set_tracer_provider(tracer_provider.clone());
+    *TRACER_PROVIDER.lock().unwrap_or_else(PoisonError::into_inner) = Some(tracer_provider.clone());
 
     let tracer = tracer_provider.tracer(otlp_config.service_name);
     Ok(tracing_opentelemetry::layer().with_tracer(tracer))
 }
 
+/// Guard that shuts down the tracer provider installed by [`span_layer`] when dropped.
+///
+/// The spans are exported in batches, so the guard should be held until the process exits to
+/// make sure that buffered spans are flushed to the collector. Dropping it is a no-op if no tracer
+/// provider was installed.
+#[derive(Debug, Default)]
+#[must_use = "buffered spans are flushed when the guard is dropped"]
+pub struct OtlpGuard {
+    _private: (),
+}
+
+impl OtlpGuard {
+    /// Creates a new guard.
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl Drop for OtlpGuard {
+    fn drop(&mut self) {
+        let provider = TRACER_PROVIDER.lock().unwrap_or_else(PoisonError::into_inner).take();
+        if let Some(provider) = provider &&
+            let Err(err) = provider.shutdown()
+        {
+            tracing::warn!(target: "reth::tracing", %err, "Failed to shut down OTLP tracer provider");
+        }
+    }
+}
+
 /// Configuration for OTLP trace export.
 #[derive(Debug, Clone)]
 pub struct OtlpConfig {
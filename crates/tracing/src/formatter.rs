@@ -1,6 +1,6 @@
 use crate::layers::BoxedLayer;
 use clap::ValueEnum;
-use std::{fmt, fmt::Display};
+use std::{fmt, fmt::Display, io::IsTerminal};
 use tracing_appender::non_blocking::NonBlocking;
 use tracing_subscriber::{EnvFilter, Layer, Registry};
 
@@ -18,6 +18,7 @@ pub enum LogFormat {
     /// Represents logfmt (key=value) formatting for logs.
     /// This format is concise and human-readable,
     /// typically used in command-line applications.
+    #[value(alias = "logfmt")]
     LogFmt,
 
     /// Represents terminal-friendly formatting for logs.
@@ -32,7 +33,9 @@ impl LogFormat {
     ///
     /// # Arguments
     /// * `filter` - An `EnvFilter` used to determine which log records to output.
-    /// * `color` - An optional string that enables or disables ANSI color codes in the logs.
+    /// * `color` - An optional string that enables or disables ANSI color codes in the logs. Only
+    ///   applies to the terminal format, and `auto` enables them only if `NO_COLOR` is not set and
+    ///   stdout is a terminal.
     /// * `file_writer` - An optional `NonBlocking` writer for directing logs to a file.
     ///
     /// # Returns
@@ -43,8 +46,10 @@ impl LogFormat {
         color: Option<String>,
         file_writer: Option<NonBlocking>,
     ) -> BoxedLayer<Registry> {
-        let ansi = if let Some(color) = color {
-            std::env::var("RUST_LOG_STYLE").map(|val| val != "never").unwrap_or(color != "never")
+        // Structured formats are meant to be parsed, so they never contain escape codes.
+        let ansi = if let Some(color) = color.filter(|_| *self == Self::Terminal) {
+            let style = std::env::var("RUST_LOG_STYLE").unwrap_or(color);
+            use_ansi(&style, std::env::var_os("NO_COLOR"), || std::io::stdout().is_terminal())
         } else {
             false
         };
@@ -67,7 +72,15 @@ impl LogFormat {
                     layer.with_filter(filter).boxed()
                 }
             }
-            Self::LogFmt => tracing_logfmt::layer().with_filter(filter).boxed(),
+            Self::LogFmt => {
+                let layer = tracing_logfmt::layer().with_ansi(false);
+
+                if let Some(writer) = file_writer {
+                    layer.with_writer(writer).with_filter(filter).boxed()
+                } else {
+                    layer.with_filter(filter).boxed()
+                }
+            }
             Self::Terminal => {
                 let layer = tracing_subscriber::fmt::layer().with_ansi(ansi).with_target(target);
 
@@ -81,6 +94,23 @@ impl LogFormat {
     }
 }
 
+/// Returns whether ANSI escape codes should be used for the given color mode.
+///
+/// `always` and `never` take precedence. In `auto` mode, colors are disabled if `NO_COLOR` is set
+/// to a non-empty value, see <https://no-color.org>, and otherwise only enabled if stdout is a
+/// terminal.
+fn use_ansi(
+    color: &str,
+    no_color: Option<std::ffi::OsString>,
+    is_terminal: impl FnOnce() -> bool,
+) -> bool {
+    match color {
+        "never" => false,
+        "auto" => no_color.is_none_or(|value| value.is_empty()) && is_terminal(),
+        _ => true,
+    }
+}
+
 impl Display for LogFormat {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -90,3 +120,25 @@ impl Display for LogFormat {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ansi_precedence() {
+        let no_color = || Some("1".into());
+
+        // An explicit color mode beats `NO_COLOR`.
+        assert!(use_ansi("always", no_color(), || false));
+        assert!(!use_ansi("never", None, || true));
+
+        // `NO_COLOR` beats terminal detection.
+        assert!(!use_ansi("auto", no_color(), || true));
+        assert!(use_ansi("auto", Some("".into()), || true));
+
+        // Otherwise colors are only used for terminals.
+        assert!(use_ansi("auto", None, || true));
+        assert!(!use_ansi("auto", None, || false));
+    }
+}
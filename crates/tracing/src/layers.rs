@@ -123,7 +123,7 @@ impl Layers {
         filter: &str,
         file_info: FileInfo,
     ) -> eyre::Result<FileWorkerGuard> {
-        let (writer, guard) = file_info.create_log_writer();
+        let (writer, guard) = file_info.create_log_writer()?;
         let file_filter = build_env_filter(None, filter)?;
         let layer = format.apply(file_filter, None, Some(writer));
         self.add_layer(layer);
@@ -175,29 +175,35 @@ impl FileInfo {
     ///
     /// # Returns
     /// A reference to the path of the log directory.
-    fn create_log_dir(&self) -> &Path {
+    fn create_log_dir(&self) -> std::io::Result<&Path> {
         let log_dir: &Path = self.dir.as_ref();
         if !log_dir.exists() {
-            std::fs::create_dir_all(log_dir).expect("Could not create log directory");
+            std::fs::create_dir_all(log_dir)?;
         }
-        log_dir
+        Ok(log_dir)
     }
 
     /// Creates a non-blocking writer for the log file.
     ///
+    /// The file is rotated once it exceeds `max_size_bytes`, keeping at most `max_files` rotated
+    /// files. Each event is handed to the writer as a single write, so lines are never split
+    /// across files, and dropping the returned guard flushes all pending events.
+    ///
     /// # Returns
     /// A tuple containing the non-blocking writer and its associated worker guard.
-    fn create_log_writer(&self) -> (tracing_appender::non_blocking::NonBlocking, WorkerGuard) {
-        let log_dir = self.create_log_dir();
-        let (writer, guard) = tracing_appender::non_blocking(
-            RollingFileAppender::new(
-                log_dir.join(&self.file_name),
-                RollingConditionBasic::new().max_size(self.max_size_bytes),
-                self.max_files,
-            )
-            .expect("Could not initialize file logging"),
-        );
-        (writer, guard)
+    fn create_log_writer(
+        &self,
+    ) -> eyre::Result<(tracing_appender::non_blocking::NonBlocking, WorkerGuard)> {
+        let log_dir = self.create_log_dir().map_err(|err| {
+            eyre::eyre!("Could not create log directory {}: {err}", self.dir.display())
+        })?;
+        let appender = RollingFileAppender::new(
+            log_dir.join(&self.file_name),
+            RollingConditionBasic::new().max_size(self.max_size_bytes),
+            self.max_files,
+        )
+        .map_err(|err| eyre::eyre!("Could not initialize file logging: {err}"))?;
+        Ok(tracing_appender::non_blocking(appender))
     }
 }
 
@@ -228,3 +234,35 @@ fn build_env_filter(
             Ok(env_filter.add_directive(directive.parse()?))
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn file_rotation() {
+        let dir = std::env::temp_dir().join(format!("reth-log-rotation-{}", std::process::id()));
+        let info = FileInfo::new(dir.clone(), "reth.log".to_string(), 1024, 2);
+
+        let (mut writer, guard) = info.create_log_writer().unwrap();
+        for i in 0..400 {
+            writer.write_all(format!("line {i:03}\n").as_bytes()).unwrap();
+        }
+        // Flushes all pending lines.
+        drop(guard);
+
+        let path = |suffix: &str| dir.join(format!("reth.log{suffix}"));
+        assert!(path("").exists());
+        assert!(path(".1").exists());
+        assert!(path(".2").exists());
+        assert!(!path(".3").exists());
+        for suffix in ["", ".1", ".2"] {
+            let contents = std::fs::read_to_string(path(suffix)).unwrap();
+            assert!(contents.lines().all(|line| line.len() == 8 && line.starts_with("line ")));
+            assert!(contents.ends_with('\n'));
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -52,15 +52,29 @@ pub use tracing_subscriber;
 pub use formatter::LogFormat;
 pub use layers::{FileInfo, FileWorkerGuard, Layers};
 pub use test_tracer::TestTracer;
+pub use timing::{
+    log_timing_stats, LogTimingSnapshot, LogTimingStats, TimingLayer, LOG_TIMING_BUCKETS,
+};
 
 mod formatter;
 mod layers;
 mod test_tracer;
+mod timing;
 
+use std::sync::OnceLock;
 use tracing::level_filters::LevelFilter;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+/// The format of the stdout layer of the global subscriber installed by [`RethTracer`].
+static STDOUT_FORMAT: OnceLock<LogFormat> = OnceLock::new();
+
+/// Returns the format of the logs written to stdout, if the global subscriber was installed by
+/// [`RethTracer`].
+pub fn stdout_log_format() -> Option<LogFormat> {
+    STDOUT_FORMAT.get().copied()
+}
+
 ///  Tracer for application logging.
 ///
 ///  Manages the configuration and initialization of logging layers,
@@ -70,6 +84,7 @@ pub struct RethTracer {
     stdout: LayerInfo,
     journald: Option<String>,
     file: Option<(LayerInfo, FileInfo)>,
+    timing: bool,
 }
 
 impl RethTracer {
@@ -78,7 +93,7 @@ impl RethTracer {
     ///  Initializes with default stdout layer configuration.
     ///  Journald and file layers are not set by default.
     pub fn new() -> Self {
-        Self { stdout: LayerInfo::default(), journald: None, file: None }
+        Self { stdout: LayerInfo::default(), journald: None, file: None, timing: false }
     }
 
     ///  Sets a custom configuration for the stdout layer.
@@ -108,6 +123,15 @@ impl RethTracer {
         self.file = Some((config, file_info));
         self
     }
+
+    ///  Sets whether the time spent logging is measured.
+    ///
+    ///  If enabled, all layers are wrapped in a [`TimingLayer`], whose statistics are returned by
+    ///  [`log_timing_stats`].
+    pub const fn with_timing(mut self, timing: bool) -> Self {
+        self.timing = timing;
+        self
+    }
 }
 
 impl Default for RethTracer {
@@ -207,6 +231,7 @@ impl Tracer for RethTracer {
     ///  An `eyre::Result` which is `Ok` with an optional `WorkerGuard` if a file layer is used,
     ///  or an `Err` in case of an error during initialization.
     fn init_with_layers(self, mut layers: Layers) -> eyre::Result<Option<WorkerGuard>> {
+        let stdout_format = self.stdout.format;
         layers.stdout(
             self.stdout.format,
             self.stdout.default_directive.parse()?,
@@ -226,7 +251,20 @@ impl Tracer for RethTracer {
 
         // The error is returned if the global default subscriber is already set,
         // so it's safe to ignore it
-        let _ = tracing_subscriber::registry().with(layers.into_inner()).try_init();
+        let initialized = if self.timing {
+            let layer = TimingLayer::new(layers.into_inner());
+            let stats = layer.stats();
+            let initialized = tracing_subscriber::registry().with(layer).try_init().is_ok();
+            if initialized {
+                timing::set_log_timing_stats(stats);
+            }
+            initialized
+        } else {
+            tracing_subscriber::registry().with(layers.into_inner()).try_init().is_ok()
+        };
+        if initialized {
+            let _ = STDOUT_FORMAT.set(stdout_format);
+        }
         Ok(file_guard)
     }
 }
@@ -241,3 +279,39 @@ impl Tracer for RethTracer {
 pub fn init_test_tracing() {
     let _ = TestTracer::default().init();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use layers::BoxedLayer;
+    use tracing_subscriber::Registry;
+
+    fn has_timing_layer() -> bool {
+        tracing::dispatcher::get_default(|dispatch| {
+            dispatch.is::<TimingLayer<Vec<BoxedLayer<Registry>>>>()
+        })
+    }
+
+    #[test]
+    fn init_sets_global_subscriber_once() {
+        let dir = std::env::temp_dir().join(format!("reth-tracing-test-{}", std::process::id()));
+        let file = || FileInfo::new(dir.clone(), "reth.log".to_string(), 1024 * 1024, 1);
+
+        let _guard = RethTracer::new()
+            .with_file(LayerInfo::default(), file())
+            .with_timing(true)
+            .init()
+            .unwrap();
+        assert!(tracing::dispatcher::has_been_set());
+        assert!(has_timing_layer());
+
+        // Initializing again must neither panic nor replace the global subscriber.
+        let _guard = RethTracer::new().with_file(LayerInfo::default(), file()).init().unwrap();
+        if tracing_journald::layer().is_ok() {
+            RethTracer::new().with_journald("error".to_string()).with_timing(true).init().unwrap();
+        }
+        assert!(has_timing_layer());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
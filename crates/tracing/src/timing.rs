@@ -0,0 +1,284 @@
+//! A layer wrapper measuring how much time is spent logging.
+
+use std::{
+    any::TypeId,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+use tracing::{
+    level_filters::LevelFilter, span, subscriber::Interest, Event, Metadata, Subscriber,
+};
+use tracing_subscriber::{layer::Context, Layer};
+
+/// The upper bounds of the buckets of the histogram of the time spent handling an event.
+///
+/// The histogram has another bucket for the events that took longer than the last bound.
+pub const LOG_TIMING_BUCKETS: [Duration; 5] = [
+    Duration::from_micros(1),
+    Duration::from_micros(10),
+    Duration::from_micros(100),
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+];
+
+/// The statistics of the [`TimingLayer`] that the global subscriber was initialized with.
+static GLOBAL_STATS: OnceLock<Arc<LogTimingStats>> = OnceLock::new();
+
+/// Returns the statistics of the time spent logging, if the global subscriber measures it.
+pub fn log_timing_stats() -> Option<&'static LogTimingStats> {
+    GLOBAL_STATS.get().map(|stats| &**stats)
+}
+
+/// Sets the statistics that [`log_timing_stats`] returns, once the global subscriber is
+/// initialized with a [`TimingLayer`].
+pub(crate) fn set_log_timing_stats(stats: Arc<LogTimingStats>) {
+    let _ = GLOBAL_STATS.set(stats);
+}
+
+/// Aggregated statistics about the time spent handling events, which are only ever added to so
+/// that they can be read as metrics.
+#[derive(Debug, Default)]
+pub struct LogTimingStats {
+    /// The number of handled events.
+    events: AtomicU64,
+    /// The cumulative time spent handling events, in nanoseconds.
+    total_nanos: AtomicU64,
+    /// The longest time spent handling a single event, in nanoseconds.
+    max_nanos: AtomicU64,
+    /// The number of events by the bucket of [`LOG_TIMING_BUCKETS`] that the time spent handling
+    /// them falls into.
+    buckets: [AtomicU64; LOG_TIMING_BUCKETS.len() + 1],
+}
+
+impl LogTimingStats {
+    /// Records the time spent handling a single event.
+    pub fn record(&self, elapsed: Duration) {
+        let nanos = elapsed.as_nanos().try_into().unwrap_or(u64::MAX);
+        let bucket = LOG_TIMING_BUCKETS.partition_point(|bound| *bound < elapsed);
+        self.events.fetch_add(1, Ordering::Relaxed);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the statistics recorded so far.
+    pub fn snapshot(&self) -> LogTimingSnapshot {
+        LogTimingSnapshot {
+            events: self.events.load(Ordering::Relaxed),
+            total: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            max: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed)),
+            buckets: self.buckets.each_ref().map(|bucket| bucket.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// A snapshot of [`LogTimingStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LogTimingSnapshot {
+    /// The number of handled events.
+    pub events: u64,
+    /// The cumulative time spent handling events.
+    pub total: Duration,
+    /// The longest time spent handling a single event.
+    pub max: Duration,
+    /// The number of events by the bucket of [`LOG_TIMING_BUCKETS`] that the time spent handling
+    /// them falls into, the last one counts the events that took longer than all bounds.
+    pub buckets: [u64; LOG_TIMING_BUCKETS.len() + 1],
+}
+
+/// A [`Layer`] that measures the time the wrapped layer spends handling events.
+///
+/// The measurements are aggregated into [`LogTimingStats`] instead of being reported per event,
+/// since reporting them from within the layer could recurse into it, or deadlock on the writer.
+#[derive(Debug)]
+pub struct TimingLayer<L> {
+    inner: L,
+    stats: Arc<LogTimingStats>,
+}
+
+impl<L> TimingLayer<L> {
+    /// Wraps the given layer.
+    pub fn new(inner: L) -> Self {
+        Self { inner, stats: Arc::default() }
+    }
+
+    /// Returns the statistics of the time spent handling events.
+    pub fn stats(&self) -> Arc<LogTimingStats> {
+        self.stats.clone()
+    }
+}
+
+impl<S, L> Layer<S> for TimingLayer<L>
+where
+    S: Subscriber,
+    L: Layer<S>,
+{
+    fn on_register_dispatch(&self, subscriber: &tracing::Dispatch) {
+        self.inner.on_register_dispatch(subscriber)
+    }
+
+    fn on_layer(&mut self, subscriber: &mut S) {
+        self.inner.on_layer(subscriber)
+    }
+
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        self.inner.register_callsite(metadata)
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        self.inner.enabled(metadata, ctx)
+    }
+
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        self.inner.on_new_span(attrs, id, ctx)
+    }
+
+    fn on_record(&self, span: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        self.inner.on_record(span, values, ctx)
+    }
+
+    fn on_follows_from(&self, span: &span::Id, follows: &span::Id, ctx: Context<'_, S>) {
+        self.inner.on_follows_from(span, follows, ctx)
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, ctx: Context<'_, S>) -> bool {
+        self.inner.event_enabled(event, ctx)
+    }
+
+    // Forwarded so that the callsites the wrapped layer doesn't handle are still filtered out
+    // statically, and don't skew the measured time.
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        self.inner.max_level_hint()
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let start = Instant::now();
+        self.inner.on_event(event, ctx);
+        self.stats.record(start.elapsed());
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        self.inner.on_enter(id, ctx)
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        self.inner.on_exit(id, ctx)
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        self.inner.on_close(id, ctx)
+    }
+
+    fn on_id_change(&self, old: &span::Id, new: &span::Id, ctx: Context<'_, S>) {
+        self.inner.on_id_change(old, new, ctx)
+    }
+
+    // Forwarded so that per-layer filters of the wrapped layer keep working.
+    #[doc(hidden)]
+    unsafe fn downcast_raw(&self, id: TypeId) -> Option<*const ()> {
+        if id == TypeId::of::<Self>() {
+            return Some(self as *const Self as *const ())
+        }
+        // SAFETY: the caller upholds the contract of `downcast_raw`, which is the same for the
+        // wrapped layer.
+        unsafe { self.inner.downcast_raw(id) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::{layer::SubscriberExt, Registry};
+
+    #[test]
+    fn aggregates_events() {
+        let layer = TimingLayer::new(tracing_subscriber::fmt::layer().with_writer(std::io::sink));
+        let stats = layer.stats();
+        let subscriber = Registry::default().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..3 {
+                tracing::info!("event");
+            }
+        });
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.events, 3);
+        assert_eq!(snapshot.buckets.iter().sum::<u64>(), 3);
+        assert!(snapshot.max <= snapshot.total);
+    }
+
+    #[test]
+    fn forwards_max_level_hint() {
+        let layer = || {
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::sink)
+                .with_filter(LevelFilter::INFO)
+        };
+        let unwrapped = Registry::default().with(layer());
+        let wrapped = Registry::default().with(TimingLayer::new(layer()));
+
+        assert_eq!(wrapped.max_level_hint(), unwrapped.max_level_hint());
+        assert_eq!(wrapped.max_level_hint(), Some(LevelFilter::INFO));
+    }
+
+    #[test]
+    fn histogram_buckets() {
+        let stats = LogTimingStats::default();
+        stats.record(Duration::from_nanos(500));
+        stats.record(Duration::from_micros(1));
+        stats.record(Duration::from_micros(50));
+        stats.record(Duration::from_secs(1));
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.buckets, [2, 0, 1, 0, 0, 1]);
+        assert_eq!(snapshot.max, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn stacks_with_stdout_and_file_layers() {
+        let dir = std::env::temp_dir().join(format!("reth-timing-test-{}", std::process::id()));
+        let layers = |file_name: &str| {
+            let mut layers = crate::Layers::new();
+            layers
+                .stdout(crate::LogFormat::Terminal, tracing::Level::INFO.into(), "", None)
+                .unwrap();
+            let guard = layers
+                .file(
+                    crate::LogFormat::Json,
+                    "debug",
+                    crate::FileInfo::new(dir.clone(), file_name.to_string(), 1024 * 1024, 1),
+                )
+                .unwrap();
+            (layers.into_inner(), guard)
+        };
+
+        // Layers that look up spans, like the formatting layers, compose with the wrapper
+        let (inner, _guard) = layers("reth.log");
+        let layer = TimingLayer::new(inner);
+        let stats = layer.stats();
+        let subscriber = Registry::default()
+            .with(layer)
+            .with(tracing_subscriber::fmt::layer().with_writer(std::io::sink));
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("span");
+            let _entered = span.enter();
+            tracing::info!("event");
+        });
+        assert_eq!(stats.snapshot().events, 1);
+
+        // The most verbose level of the wrapped layers is kept, so that the callsites above it are
+        // still filtered out statically
+        let (inner, _wrapped_guard) = layers("wrapped.log");
+        let wrapped = Registry::default().with(TimingLayer::new(inner));
+        let (inner, _unwrapped_guard) = layers("unwrapped.log");
+        let unwrapped = Registry::default().with(inner);
+        assert_eq!(wrapped.max_level_hint(), unwrapped.max_level_hint());
+        assert_eq!(wrapped.max_level_hint(), Some(LevelFilter::DEBUG));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
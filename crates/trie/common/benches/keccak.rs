@@ -0,0 +1,54 @@
+#![allow(missing_docs, unreachable_pub)]
+use alloy_primitives::{keccak256, B256};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+use reth_trie_common::{KeccakKeyHasher, KeyHasher};
+use std::hint::black_box;
+
+/// Number of keys hashed per iteration, can be overridden with `KECCAK_BENCH_KEYS`.
+const DEFAULT_NUM_KEYS: usize = 10_000_000;
+
+pub fn keccak_keys(c: &mut Criterion) {
+    let num_keys = std::env::var("KECCAK_BENCH_KEYS")
+        .ok()
+        .and_then(|keys| keys.parse().ok())
+        .unwrap_or(DEFAULT_NUM_KEYS);
+
+    let mut runner = TestRunner::deterministic();
+    let keys = proptest::collection::vec(any::<[u8; 32]>(), num_keys)
+        .new_tree(&mut runner)
+        .unwrap()
+        .current();
+
+    let mut group = c.benchmark_group(format!("Keccak {num_keys} keys"));
+    group.sample_size(10);
+
+    group.bench_function("single", |b| {
+        b.iter_batched(
+            || Vec::<B256>::with_capacity(keys.len()),
+            |mut out| {
+                for key in &keys {
+                    out.push(keccak256(key));
+                }
+                black_box(out)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("batched", |b| {
+        b.iter_batched(
+            || Vec::<B256>::with_capacity(keys.len()),
+            |mut out| {
+                KeccakKeyHasher::hash_keys_into(&keys, &mut out);
+                black_box(out)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(keccak, keccak_keys);
+criterion_main!(keccak);
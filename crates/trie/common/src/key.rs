@@ -1,12 +1,26 @@
+use alloc::vec::Vec;
 use alloy_primitives::{keccak256, B256};
 
 /// Trait for hashing keys in state.
 pub trait KeyHasher: Default + Clone + Send + Sync + 'static {
     /// Hashes the given bytes into a 256-bit hash.
     fn hash_key<T: AsRef<[u8]>>(bytes: T) -> B256;
+
+    /// Hashes all `inputs` at once, writing the hashes into `out` in input order.
+    ///
+    /// `out` is cleared before hashing, so the same buffer can be reused across batches to avoid
+    /// reallocating. The output is identical to calling [`KeyHasher::hash_key`] on each input,
+    /// which is what the default implementation does.
+    fn hash_keys_into<T: AsRef<[u8]>>(inputs: &[T], out: &mut Vec<B256>) {
+        out.clear();
+        out.reserve(inputs.len());
+        out.extend(inputs.iter().map(Self::hash_key));
+    }
 }
 
 /// A key hasher that uses the Keccak-256 hash function.
+///
+/// The accelerated assembly implementation is used when the `asm-keccak` feature is enabled.
 #[derive(Clone, Debug, Default)]
 pub struct KeccakKeyHasher;
 
@@ -16,3 +30,33 @@ impl KeyHasher for KeccakKeyHasher {
         keccak256(bytes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn batched_keccak_matches_single(inputs in proptest::collection::vec(any::<Vec<u8>>(), 0..256)) {
+            let mut out = vec![B256::repeat_byte(0xff); 3];
+            KeccakKeyHasher::hash_keys_into(&inputs, &mut out);
+
+            prop_assert_eq!(out.len(), inputs.len());
+            for (hash, input) in out.iter().zip(&inputs) {
+                prop_assert_eq!(*hash, keccak256(input));
+            }
+        }
+
+        #[test]
+        fn batched_keccak_addresses(addresses in proptest::collection::vec(any::<[u8; 20]>(), 0..256)) {
+            let addresses = addresses.into_iter().map(Address::from).collect::<Vec<_>>();
+            let mut out = Vec::new();
+            KeccakKeyHasher::hash_keys_into(&addresses, &mut out);
+
+            let expected = addresses.iter().map(KeccakKeyHasher::hash_key).collect::<Vec<_>>();
+            prop_assert_eq!(out, expected);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     hashed_cursor::{HashedCursor, HashedCursorFactory},
     progress::{IntermediateStateRootState, StateRootProgress},
-    trie::StateRoot,
+    trie::{StateRoot, StorageRoot},
     trie_cursor::{
         depth_first::{self, DepthFirstTrieIterator},
         noop::NoopTrieCursorFactory,
@@ -13,9 +13,12 @@ use alloy_primitives::B256;
 use alloy_trie::BranchNodeCompact;
 use reth_execution_errors::StateRootError;
 use reth_storage_errors::db::DatabaseError;
-use std::cmp::Ordering;
+use std::{cmp::Ordering, fmt};
 use tracing::trace;
 
+#[cfg(feature = "metrics")]
+use crate::{metrics::TrieRootMetrics, TrieType};
+
 /// Used by [`StateRootBranchNodesIter`] to iterate over branch nodes in a state root.
 #[derive(Debug)]
 enum BranchNode {
@@ -185,6 +188,26 @@ pub enum Output {
     Progress(Nibbles),
 }
 
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AccountExtra(path, _) => write!(f, "extra account trie node at {path:?}"),
+            Self::StorageExtra(account, path, _) => {
+                write!(f, "extra storage trie node of account {account} at {path:?}")
+            }
+            Self::AccountWrong { path, .. } => write!(f, "wrong account trie node at {path:?}"),
+            Self::StorageWrong { account, path, .. } => {
+                write!(f, "wrong storage trie node of account {account} at {path:?}")
+            }
+            Self::AccountMissing(path, _) => write!(f, "missing account trie node at {path:?}"),
+            Self::StorageMissing(account, path, _) => {
+                write!(f, "missing storage trie node of account {account} at {path:?}")
+            }
+            Self::Progress(path) => write!(f, "verified up to account trie path {path:?}"),
+        }
+    }
+}
+
 /// Verifies the contents of a trie table against some other data source which is able to produce
 /// stored trie nodes.
 #[derive(Debug)]
@@ -467,6 +490,35 @@ impl<'a, T: TrieCursorFactory, H: HashedCursorFactory + Clone> Iterator for Veri
     }
 }
 
+/// Checks the storage trie of a single account against its hashed storage, which is the source of
+/// truth like for the [`Verifier`], and returns the inconsistencies in depth-first order.
+pub fn verify_storage_trie<T: TrieCursorFactory, H: HashedCursorFactory>(
+    trie_cursor_factory: &T,
+    hashed_cursor_factory: H,
+    hashed_address: B256,
+) -> Result<Vec<Output>, StateRootError> {
+    let (_, _, updates) = StorageRoot::new_hashed(
+        NoopTrieCursorFactory,
+        hashed_cursor_factory,
+        hashed_address,
+        Default::default(),
+        #[cfg(feature = "metrics")]
+        TrieRootMetrics::new(TrieType::Storage),
+    )
+    .root_with_updates()?;
+    let mut nodes = updates.storage_nodes.into_iter().collect::<Vec<_>>();
+    nodes.sort_unstable_by(|a, b| depth_first::cmp(&a.0, &b.0));
+
+    let trie_cursor = trie_cursor_factory.storage_trie_cursor(hashed_address)?;
+    let mut verifier = SingleVerifier::new(Some(hashed_address), trie_cursor)?;
+    let mut outputs = Vec::new();
+    for (path, node) in nodes {
+        verifier.next(&mut outputs, path, node)?;
+    }
+    verifier.finalize(&mut outputs)?;
+    Ok(outputs)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1008,4 +1060,43 @@ mod tests {
         }
         assert!(outputs.is_empty());
     }
+
+    #[test]
+    fn test_verify_storage_trie() {
+        let account = keccak256(address!("0000000000000000000000000000000000000001"));
+        let storage = (0..64u64)
+            .map(|slot| (keccak256(B256::from(U256::from(slot))), U256::from(slot + 1)))
+            .collect::<BTreeMap<_, _>>();
+        let hashed_cursor_factory = MockHashedCursorFactory::new(
+            BTreeMap::from([(account, Account::default())]),
+            B256Map::from_iter([(account, storage)]),
+        );
+        let verify = |nodes: BTreeMap<Nibbles, BranchNodeCompact>| {
+            let trie_cursor_factory =
+                MockTrieCursorFactory::new(BTreeMap::new(), B256Map::from_iter([(account, nodes)]));
+            verify_storage_trie(&trie_cursor_factory, hashed_cursor_factory.clone(), account)
+                .unwrap()
+        };
+
+        // Every node computed from the hashed storage is missing from an empty trie
+        let nodes = verify(BTreeMap::new())
+            .into_iter()
+            .map(|output| match output {
+                Output::StorageMissing(hashed_address, path, node) => {
+                    assert_eq!(hashed_address, account);
+                    (path, node)
+                }
+                output => panic!("unexpected output: {output}"),
+            })
+            .collect::<BTreeMap<_, _>>();
+        assert!(!nodes.is_empty());
+        assert!(verify(nodes.clone()).is_empty());
+
+        // A node that no hashed storage is below is extra
+        let path = Nibbles::from_nibbles([0xf; 10]);
+        let extra = test_branch_node(0b0011, 0, 0b0011, vec![]);
+        let mut with_extra = nodes;
+        with_extra.insert(path, extra.clone());
+        assert_eq!(verify(with_extra), vec![Output::StorageExtra(account, path, extra)]);
+    }
 }
@@ -65,6 +65,10 @@ export const opRethCliSidebar: SidebarItem = {
                         }
                     ]
                 },
+                {
+                    text: "op-reth db export-segment",
+                    link: "/cli/op-reth/db/export-segment"
+                },
                 {
                     text: "op-reth db drop",
                     link: "/cli/op-reth/db/drop"
@@ -84,10 +88,18 @@ export const opRethCliSidebar: SidebarItem = {
                         }
                     ]
                 },
+                {
+                    text: "op-reth db compact",
+                    link: "/cli/op-reth/db/compact"
+                },
                 {
                     text: "op-reth db repair-trie",
                     link: "/cli/op-reth/db/repair-trie"
                 },
+                {
+                    text: "op-reth db verify-trie",
+                    link: "/cli/op-reth/db/verify-trie"
+                },
                 {
                     text: "op-reth db static-file-header",
                     link: "/cli/op-reth/db/static-file-header",
@@ -103,10 +115,18 @@ export const opRethCliSidebar: SidebarItem = {
                         }
                     ]
                 },
+                {
+                    text: "op-reth db static-files",
+                    link: "/cli/op-reth/db/static-files"
+                },
                 {
                     text: "op-reth db version",
                     link: "/cli/op-reth/db/version"
                 },
+                {
+                    text: "op-reth db migrate",
+                    link: "/cli/op-reth/db/migrate"
+                },
                 {
                     text: "op-reth db path",
                     link: "/cli/op-reth/db/path"
@@ -140,6 +160,14 @@ export const opRethCliSidebar: SidebarItem = {
                 {
                     text: "op-reth db account-storage",
                     link: "/cli/op-reth/db/account-storage"
+                },
+                {
+                    text: "op-reth db verify-chain",
+                    link: "/cli/op-reth/db/verify-chain"
+                },
+                {
+                    text: "op-reth db check-consistency",
+                    link: "/cli/op-reth/db/check-consistency"
                 }
             ]
         },
@@ -193,6 +221,10 @@ export const opRethCliSidebar: SidebarItem = {
                             link: "/cli/op-reth/stage/unwind/num-blocks"
                         }
                     ]
+                },
+                {
+                    text: "op-reth stage bench",
+                    link: "/cli/op-reth/stage/bench"
                 }
             ]
         },
@@ -223,6 +255,22 @@ export const opRethCliSidebar: SidebarItem = {
                 {
                     text: "op-reth p2p bootnode",
                     link: "/cli/op-reth/p2p/bootnode"
+                },
+                {
+                    text: "op-reth p2p probe",
+                    link: "/cli/op-reth/p2p/probe"
+                },
+                {
+                    text: "op-reth p2p crawl",
+                    link: "/cli/op-reth/p2p/crawl"
+                },
+                {
+                    text: "op-reth p2p export-peers",
+                    link: "/cli/op-reth/p2p/export-peers"
+                },
+                {
+                    text: "op-reth p2p import-peers",
+                    link: "/cli/op-reth/p2p/import-peers"
                 }
             ]
         },
@@ -237,6 +285,10 @@ export const opRethCliSidebar: SidebarItem = {
         {
             text: "op-reth re-execute",
             link: "/cli/op-reth/re-execute"
+        },
+        {
+            text: "op-reth version",
+            link: "/cli/op-reth/version"
         }
     ]
 };
@@ -21,6 +21,10 @@ export const rethCliSidebar: SidebarItem = {
             text: "reth import",
             link: "/cli/reth/import"
         },
+        {
+            text: "reth export",
+            link: "/cli/reth/export"
+        },
         {
             text: "reth import-era",
             link: "/cli/reth/import-era"
@@ -69,6 +73,10 @@ export const rethCliSidebar: SidebarItem = {
                         }
                     ]
                 },
+                {
+                    text: "reth db export-segment",
+                    link: "/cli/reth/db/export-segment"
+                },
                 {
                     text: "reth db drop",
                     link: "/cli/reth/db/drop"
@@ -88,10 +96,18 @@ export const rethCliSidebar: SidebarItem = {
                         }
                     ]
                 },
+                {
+                    text: "reth db compact",
+                    link: "/cli/reth/db/compact"
+                },
                 {
                     text: "reth db repair-trie",
                     link: "/cli/reth/db/repair-trie"
                 },
+                {
+                    text: "reth db verify-trie",
+                    link: "/cli/reth/db/verify-trie"
+                },
                 {
                     text: "reth db static-file-header",
                     link: "/cli/reth/db/static-file-header",
@@ -107,10 +123,18 @@ export const rethCliSidebar: SidebarItem = {
                         }
                     ]
                 },
+                {
+                    text: "reth db static-files",
+                    link: "/cli/reth/db/static-files"
+                },
                 {
                     text: "reth db version",
                     link: "/cli/reth/db/version"
                 },
+                {
+                    text: "reth db migrate",
+                    link: "/cli/reth/db/migrate"
+                },
                 {
                     text: "reth db path",
                     link: "/cli/reth/db/path"
@@ -144,6 +168,14 @@ export const rethCliSidebar: SidebarItem = {
                 {
                     text: "reth db account-storage",
                     link: "/cli/reth/db/account-storage"
+                },
+                {
+                    text: "reth db verify-chain",
+                    link: "/cli/reth/db/verify-chain"
+                },
+                {
+                    text: "reth db check-consistency",
+                    link: "/cli/reth/db/check-consistency"
                 }
             ]
         },
@@ -201,6 +233,10 @@ export const rethCliSidebar: SidebarItem = {
                             link: "/cli/reth/stage/unwind/num-blocks"
                         }
                     ]
+                },
+                {
+                    text: "reth stage bench",
+                    link: "/cli/reth/stage/bench"
                 }
             ]
         },
@@ -231,6 +267,22 @@ export const rethCliSidebar: SidebarItem = {
                 {
                     text: "reth p2p bootnode",
                     link: "/cli/reth/p2p/bootnode"
+                },
+                {
+                    text: "reth p2p probe",
+                    link: "/cli/reth/p2p/probe"
+                },
+                {
+                    text: "reth p2p crawl",
+                    link: "/cli/reth/p2p/crawl"
+                },
+                {
+                    text: "reth p2p export-peers",
+                    link: "/cli/reth/p2p/export-peers"
+                },
+                {
+                    text: "reth p2p import-peers",
+                    link: "/cli/reth/p2p/import-peers"
                 }
             ]
         },
@@ -245,6 +297,48 @@ export const rethCliSidebar: SidebarItem = {
         {
             text: "reth re-execute",
             link: "/cli/reth/re-execute"
+        },
+        {
+            text: "reth recover",
+            link: "/cli/reth/recover",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth recover storage-tries",
+                    link: "/cli/reth/recover/storage-tries"
+                }
+            ]
+        },
+        {
+            text: "reth debug",
+            link: "/cli/reth/debug",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth debug build-block",
+                    link: "/cli/reth/debug/build-block"
+                },
+                {
+                    text: "reth debug replay-block",
+                    link: "/cli/reth/debug/replay-block"
+                },
+                {
+                    text: "reth debug execution-range",
+                    link: "/cli/reth/debug/execution-range"
+                },
+                {
+                    text: "reth debug unwind-to",
+                    link: "/cli/reth/debug/unwind-to"
+                }
+            ]
+        },
+        {
+            text: "reth completions",
+            link: "/cli/reth/completions"
+        },
+        {
+            text: "reth version",
+            link: "/cli/reth/version"
         }
     ]
 };
@@ -0,0 +1,183 @@
+//! Example of tracing the ETH balance of an address over a range of blocks in the reth database,
+//! from the account changesets instead of re-executing the blocks.
+//!
+//! Run with
+//!
+//! ```sh
+//! RETH_DATADIR=~/.local/share/reth/mainnet cargo run --release -p example-balance-history -- \
+//!     --address 0xd8da6bf26964af9d7eed9e03e53415d37aa96045 --range 18000000:18100000
+//! ```
+//!
+//! The balance at the end of the first block of the range is written as CSV, followed by a row for
+//! every block after which the balance changed. Accounts that don't exist have a balance of zero,
+//! so the account may be created or destroyed within the range. The range can't extend past the
+//! last block of the account history index.
+//!
+//! With `--checkpoint-file`, the progress is recorded after every chunk of blocks, and a trace that
+//! was interrupted continues from the last recorded chunk when it's run again, appending to the
+//! `--output` file.
+
+#![warn(unused_crate_dependencies)]
+
+use alloy_primitives::{Address, U256};
+use clap::Parser;
+use example_scan_checkpoint::{chunks, parse_range, ScanCheckpoint};
+use eyre::bail;
+use reth_ethereum::{
+    chainspec::{ChainSpec, ChainSpecProvider, EthChainSpec},
+    cli::chainspec::{chain_value_parser, SUPPORTED_CHAINS},
+    node::EthereumNode,
+    primitives::Account,
+    provider::{
+        providers::ReadOnlyConfig, AccountReader, HistoricalStateProviderRef, ProviderError,
+    },
+    storage::PruneCheckpointReader,
+};
+use reth_prune_types::PruneSegment;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::RangeInclusive,
+    path::PathBuf,
+    sync::Arc,
+};
+
+/// Number of blocks whose changes are read between checkpoints.
+const CHUNK_SIZE: u64 = 100_000;
+
+/// Writes the balance history of an address.
+#[derive(Debug, Parser)]
+struct Args {
+    /// The chain of the database, which is checked against its genesis block.
+    ///
+    /// Detected from the genesis block if not given.
+    #[arg(long, value_name = "CHAIN", value_parser = chain_value_parser)]
+    chain: Option<Arc<ChainSpec>>,
+
+    /// The address to trace.
+    #[arg(long)]
+    address: Address,
+
+    /// The blocks to trace, as `<from>:<to>` (inclusive).
+    #[arg(long, value_parser = parse_range)]
+    range: RangeInclusive<u64>,
+
+    /// The file to write the rows to, instead of stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// A file to record the progress of the trace in after every chunk of blocks, so that an
+    /// interrupted trace continues where it stopped when it's run again with the same file.
+    ///
+    /// The trace must be resumed with the same address and range, and the output file is
+    /// continued instead of overwritten.
+    #[arg(long, value_name = "PATH", requires = "output")]
+    checkpoint_file: Option<PathBuf>,
+}
+
+fn main() -> eyre::Result<()> {
+    let args = Args::parse();
+
+    // The path to data directory, e.g. "~/.local/reth/share/mainnet"
+    let datadir = std::env::var("RETH_DATADIR")?;
+
+    // The chain is detected from the genesis block of the database, unless it's given
+    let chains = match args.chain {
+        Some(chain) => vec![chain],
+        None => SUPPORTED_CHAINS
+            .iter()
+            .map(|chain| chain_value_parser(chain))
+            .collect::<eyre::Result<_>>()?,
+    };
+    let factory = EthereumNode::provider_factory_builder()
+        .open_read_only_matching_genesis(chains, ReadOnlyConfig::from_datadir(datadir))?;
+    let provider = factory.provider()?;
+
+    let mut checkpoint = args
+        .checkpoint_file
+        .as_deref()
+        .map(|path| {
+            ScanCheckpoint::open(
+                path,
+                factory.chain_spec().genesis_hash(),
+                args.range.clone(),
+                &args.address,
+            )
+        })
+        .transpose()?;
+
+    let out: Box<dyn Write> = match (&args.output, &mut checkpoint) {
+        (Some(path), Some(checkpoint)) => Box::new(checkpoint.open_output(path)?),
+        (Some(path), None) => Box::new(File::create(path)?),
+        (None, _) => Box::new(io::stdout().lock()),
+    };
+    let mut out = BufWriter::new(out);
+    if !checkpoint.as_ref().is_some_and(ScanCheckpoint::is_resumed) {
+        writeln!(out, "block,balance")?;
+    }
+
+    let (start, end) = args.range.clone().into_inner();
+    let pruned = provider
+        .get_prune_checkpoint(PruneSegment::AccountHistory)?
+        .and_then(|checkpoint| checkpoint.block_number);
+
+    // A resumed trace continues after the checkpointed blocks, and may already be done
+    let range = match &checkpoint {
+        Some(checkpoint) => checkpoint.remaining(),
+        None => Some(args.range),
+    };
+
+    let balance = |account: Option<Account>| account.map_or(U256::ZERO, |account| account.balance);
+    for chunk in range.into_iter().flat_map(|range| chunks(range, CHUNK_SIZE)) {
+        // The state at the start of the chunk, which is the end of the block before it
+        let (chunk_start, chunk_end) = chunk.into_inner();
+        let mut state = HistoricalStateProviderRef::new(&provider, chunk_start);
+        if let Some(pruned) = pruned {
+            state = state.with_lowest_available_account_history_block_number(pruned + 1);
+        }
+
+        let history = state
+            .basic_account(&args.address)
+            .and_then(|initial| Ok((initial, state.account_changes(args.address, chunk_end)?)));
+        let (initial, changes) = match history {
+            Ok(history) => history,
+            Err(ProviderError::InsufficientChangesets { available, .. }) => bail!(
+                "the range ends at block {end}, but the account history is only indexed up to \
+                 block {}",
+                available.end()
+            ),
+            Err(ProviderError::StateAtBlockPruned(_)) => {
+                bail!("the account history at block {chunk_start} has been pruned")
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        // The balance at the start of a later chunk was already written by an earlier one
+        let mut last = balance(initial);
+        let mut changes = changes.into_iter().peekable();
+        if chunk_start == start {
+            // A change in the first block is part of the first row
+            if let Some((_, account)) = changes.next_if(|(block, _)| *block == start) {
+                last = balance(account);
+            }
+            writeln!(out, "{start},{last}")?;
+        }
+
+        for (block, account) in changes {
+            // The account may have changed without a change of its balance, e.g. its nonce
+            let current = balance(account);
+            if current != last {
+                writeln!(out, "{block},{current}")?;
+                last = current;
+            }
+        }
+
+        // The checkpoint only advances past a chunk once its rows have been written
+        out.flush()?;
+        if let Some(checkpoint) = &mut checkpoint {
+            checkpoint.advance(chunk_end + 1)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! Example of searching the deployed bytecode of all accounts in the reth database for a byte
+//! pattern or for function selectors.
+//!
+//! Run with
+//!
+//! ```sh
+//! RETH_DATADIR=~/.local/share/reth/mainnet cargo run --release -p example-code-search -- \
+//!     --selector 'transfer(address,uint256)',0x095ea7b3
+//! ```
+//!
+//! The bytecodes are scanned once for the pattern, and then the accounts for the matching code
+//! hashes. Both tables are scanned in shards of their key space in parallel. The accounts are
+//! written as CSV with their code hash and code length, to stdout or the file given with
+//! `--output`.
+
+#![warn(unused_crate_dependencies)]
+
+use alloy_primitives::{keccak256, Bytes, Selector};
+use clap::Parser;
+use reth_ethereum::{
+    chainspec::ChainSpec,
+    cli::chainspec::{chain_value_parser, SUPPORTED_CHAINS},
+    node::EthereumNode,
+    provider::{
+        providers::ReadOnlyConfig, CodeMatch, CodePattern, CodeSearch, DEFAULT_CODE_SEARCH_SHARDS,
+    },
+};
+use reth_tracing::{tracing::info, RethTracer, Tracer};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Searches the code of all accounts.
+#[derive(Debug, Parser)]
+struct Args {
+    /// The chain of the database, which is checked against its genesis block.
+    ///
+    /// Detected from the genesis block if not given.
+    #[arg(long, value_name = "CHAIN", value_parser = chain_value_parser)]
+    chain: Option<Arc<ChainSpec>>,
+
+    /// Match code that contains these bytes, in hex.
+    #[arg(long, required_unless_present = "selector", conflicts_with = "selector")]
+    pattern: Option<Bytes>,
+
+    /// Match code that dispatches one of these function selectors, given in hex or as function
+    /// signatures like `transfer(address,uint256)`. Can be repeated.
+    #[arg(long, value_delimiter = ',', value_parser = parse_selector)]
+    selector: Vec<Selector>,
+
+    /// Number of shards of the key space that are scanned in parallel.
+    #[arg(long, default_value_t = DEFAULT_CODE_SEARCH_SHARDS)]
+    shards: usize,
+
+    /// Interval between progress reports.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "5s")]
+    progress_interval: Duration,
+
+    /// The file to write the accounts to, instead of stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> eyre::Result<()> {
+    let _guard = RethTracer::new().init()?;
+    let args = Args::parse();
+
+    // The path to data directory, e.g. "~/.local/reth/share/mainnet"
+    let datadir = std::env::var("RETH_DATADIR")?;
+
+    // The chain is detected from the genesis block of the database, unless it's given
+    let chains = match args.chain {
+        Some(chain) => vec![chain],
+        None => SUPPORTED_CHAINS
+            .iter()
+            .map(|chain| chain_value_parser(chain))
+            .collect::<eyre::Result<_>>()?,
+    };
+    let factory = EthereumNode::provider_factory_builder()
+        .open_read_only_matching_genesis(chains, ReadOnlyConfig::from_datadir(datadir))?;
+
+    let pattern = match args.pattern {
+        Some(pattern) => CodePattern::Bytes(pattern.to_vec()),
+        None => CodePattern::Selectors(args.selector.into_iter().map(Into::into).collect()),
+    };
+
+    let out: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut out = BufWriter::new(out);
+    writeln!(out, "address,codehash,code_len")?;
+
+    // Accounts are written as they are found, the first write error is returned after the search
+    let started = Instant::now();
+    let mut result = Ok(());
+    let mut accounts = 0;
+    let codes = CodeSearch::new(factory)
+        .with_shards(args.shards)
+        .with_progress_interval(args.progress_interval)
+        .search(&pattern, |CodeMatch { address, code_hash, code_len }| {
+            accounts += 1;
+            if result.is_ok() {
+                result = writeln!(out, "{address},{code_hash},{code_len}");
+            }
+        })?;
+    result?;
+    out.flush()?;
+
+    info!(codes, accounts, elapsed = ?started.elapsed(), "Search finished");
+
+    Ok(())
+}
+
+/// Parses a function selector given in hex or as a function signature.
+fn parse_selector(value: &str) -> Result<Selector, String> {
+    if value.starts_with("0x") {
+        value.parse().map_err(|err| format!("invalid selector {value}: {err}"))
+    } else if value.contains('(') {
+        Ok(Selector::from_slice(&keccak256(value)[..4]))
+    } else {
+        Err(format!("expected a selector in hex or a function signature, got {value}"))
+    }
+}
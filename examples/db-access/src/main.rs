@@ -7,7 +7,8 @@ use reth_ethereum::{
     primitives::{AlloyBlockHeader, SealedBlock, SealedHeader},
     provider::{
         providers::ReadOnlyConfig, AccountReader, BlockNumReader, BlockReader, BlockSource,
-        HeaderProvider, ReceiptProvider, StateProvider, TransactionVariant, TransactionsProvider,
+        HeaderProvider, LogScanner, ReceiptProvider, StateProvider, TransactionVariant,
+        TransactionsProvider,
     },
     rpc::eth::primitives::Filter,
     TransactionSigned,
@@ -158,7 +159,7 @@ fn receipts_provider_example<
     let receipt = provider.receipt(txid)?.ok_or(eyre::eyre!("tx receipt not found"))?;
 
     // Can query receipt by txhash too
-    let tx = provider.transaction_by_id(txid)?.unwrap();
+    let tx = provider.transaction_by_id(txid)?.ok_or(eyre::eyre!("tx not found"))?;
     let receipt_by_hash = provider
         .receipt_by_hash(*tx.tx_hash())?
         .ok_or(eyre::eyre!("tx receipt by hash not found"))?;
@@ -169,13 +170,7 @@ fn receipts_provider_example<
         .receipts_by_block(100.into())?
         .ok_or(eyre::eyre!("no receipts found for block"))?;
 
-    // Can check if an address/topic filter is present in a header, if it is we query the block and
-    // receipts and do something with the data
-    // 1. get the bloom from the header
-    let header = provider.header_by_number(header_num)?.unwrap();
-    let bloom = header.logs_bloom();
-
-    // 2. Construct the address/topics filters. topic0 always refers to the event signature, so
+    // 1. Construct the address/topics filters. topic0 always refers to the event signature, so
     // filter it with event_signature() (or use the .event() helper). The remaining helpers map to
     // the indexed parameters in declaration order (topic1 -> first indexed param, etc).
     let contract_addr = Address::random();
@@ -191,18 +186,12 @@ fn receipts_provider_example<
         .topic1(indexed_from)
         .topic2(indexed_to);
 
-    // 3. If the address & topics filters match do something. We use the outer check against the
-    // bloom filter stored in the header to avoid having to query the receipts table when there
-    // is no instance of any event that matches the filter in the header.
-    if filter.matches_bloom(bloom) {
-        let receipts = provider.receipt(header_num)?.ok_or(eyre::eyre!("receipt not found"))?;
-        for log in &receipts.logs {
-            if filter.matches(log) {
-                // Do something with the log e.g. decode it.
-                println!("Matching log found! {log:?}")
-            }
-        }
-    }
+    // 2. Scan the logs of a range of blocks. The bloom filter stored in each header is checked
+    // first, so the receipts are only loaded for blocks that may contain a matching event.
+    provider.scan_logs(header_num - 10..=header_num, &filter, |log| {
+        // Do something with the log e.g. decode it.
+        println!("Matching log found in transaction {}! {:?}", log.tx_hash, log.log)
+    })?;
 
     Ok(())
 }
@@ -0,0 +1,353 @@
+//! Example of exporting the gas and fee fields of the headers of a range of blocks in the reth
+//! database as a time series, e.g. for fee analytics.
+//!
+//! Run with
+//!
+//! ```sh
+//! RETH_DATADIR=~/.local/share/reth/mainnet cargo run --release -p example-header-fees -- \
+//!     --range 15537394:19000000 --bucket day --output fees.csv
+//! ```
+//!
+//! The headers are read sequentially from the headers static files in batches, instead of looking
+//! up every block on its own, which is what makes exporting the whole chain take minutes rather
+//! than hours. Without `--bucket`, a CSV row of
+//! `block,timestamp,gas_used,gas_limit,base_fee,blob_gas_used,excess_blob_gas` is written for every
+//! block, where fields that the block doesn't have yet are empty. With `--bucket epoch` or
+//! `--bucket day`, the blocks are aggregated into buckets of 32 blocks or UTC days, with the
+//! minimum, maximum and mean of every field. The throughput is logged while exporting and at the
+//! end.
+//!
+//! With `--checkpoint-file`, the progress is recorded after every batch of headers, and an export
+//! that was interrupted continues from the last recorded batch when it's run again, appending to
+//! the `--output` file. With buckets, the checkpoint is kept at the start of the bucket that isn't
+//! finished yet, so a resumed export reads that bucket again.
+
+#![warn(unused_crate_dependencies)]
+
+use clap::{Parser, ValueEnum};
+use example_scan_checkpoint::{parse_range, ScanCheckpoint};
+use eyre::ensure;
+use reth_ethereum::{
+    chainspec::{ChainSpec, ChainSpecProvider, EthChainSpec},
+    cli::chainspec::{chain_value_parser, SUPPORTED_CHAINS},
+    node::EthereumNode,
+    primitives::AlloyBlockHeader,
+    provider::{
+        providers::ReadOnlyConfig, HeaderProvider, StaticFileProviderFactory, StaticFileSegment,
+    },
+};
+use reth_tracing::{tracing::info, RethTracer, Tracer};
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::RangeInclusive,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Number of headers that are read at once.
+const BATCH_SIZE: u64 = 10_000;
+
+/// Number of blocks in a bucket of [`Bucket::Epoch`].
+const EPOCH_BLOCKS: u64 = 32;
+
+/// Number of seconds in a bucket of [`Bucket::Day`].
+const DAY_SECONDS: u64 = 24 * 60 * 60;
+
+/// The names of the fields that are aggregated in buckets.
+const FIELDS: [&str; 5] = ["gas_used", "gas_limit", "base_fee", "blob_gas_used", "excess_blob_gas"];
+
+/// Exports the gas and fee fields of headers.
+#[derive(Debug, Parser)]
+struct Args {
+    /// The chain of the database, which is checked against its genesis block.
+    ///
+    /// Detected from the genesis block if not given.
+    #[arg(long, value_name = "CHAIN", value_parser = chain_value_parser)]
+    chain: Option<Arc<ChainSpec>>,
+
+    /// The blocks to export, as `<from>:<to>` (inclusive).
+    ///
+    /// Defaults to all blocks in the headers static files.
+    #[arg(long, value_parser = parse_range)]
+    range: Option<RangeInclusive<u64>>,
+
+    /// Aggregate the blocks into buckets instead of writing a row for every block.
+    #[arg(long, value_enum)]
+    bucket: Option<Bucket>,
+
+    /// Interval between progress reports.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "5s")]
+    progress_interval: Duration,
+
+    /// The file to write the rows to, instead of stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// A file to record the progress of the export in after every batch of headers, so that an
+    /// interrupted export continues where it stopped when it's run again with the same file.
+    ///
+    /// The export must be resumed with the same range and bucket, and the output file is
+    /// continued instead of overwritten.
+    #[arg(long, value_name = "PATH", requires = "output")]
+    checkpoint_file: Option<PathBuf>,
+}
+
+/// How blocks are grouped into buckets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+enum Bucket {
+    /// Buckets of 32 blocks, identified by the block number divided by 32.
+    Epoch,
+    /// Buckets of UTC days, identified by the timestamp of the midnight that starts the day.
+    Day,
+}
+
+impl Bucket {
+    /// Returns the bucket of a block.
+    const fn of(self, number: u64, timestamp: u64) -> u64 {
+        match self {
+            Self::Epoch => number / EPOCH_BLOCKS,
+            Self::Day => timestamp - timestamp % DAY_SECONDS,
+        }
+    }
+}
+
+/// The fields of a header that are exported.
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    number: u64,
+    timestamp: u64,
+    /// The values of [`FIELDS`].
+    fields: [Option<u64>; 5],
+}
+
+impl Row {
+    fn new(header: &impl AlloyBlockHeader) -> Self {
+        Self {
+            number: header.number(),
+            timestamp: header.timestamp(),
+            fields: [
+                Some(header.gas_used()),
+                Some(header.gas_limit()),
+                header.base_fee_per_gas(),
+                header.blob_gas_used(),
+                header.excess_blob_gas(),
+            ],
+        }
+    }
+}
+
+/// The minimum, maximum and sum of the values of a field in a bucket.
+#[derive(Debug, Clone, Copy, Default)]
+struct Stats {
+    min: u64,
+    max: u64,
+    sum: u128,
+    count: u64,
+}
+
+impl Stats {
+    fn add(&mut self, value: Option<u64>) {
+        let Some(value) = value else { return };
+        if self.count == 0 {
+            (self.min, self.max) = (value, value);
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.sum += u128::from(value);
+        self.count += 1;
+    }
+
+    /// Writes the `min,max,mean` columns, which are empty if no block had the field.
+    fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        if self.count == 0 {
+            return write!(out, ",,,")
+        }
+        write!(out, ",{},{},{:.2}", self.min, self.max, self.sum as f64 / self.count as f64)
+    }
+}
+
+/// The blocks of a bucket that were read so far.
+#[derive(Debug)]
+struct Aggregate {
+    bucket: u64,
+    first_block: u64,
+    last_block: u64,
+    stats: [Stats; 5],
+}
+
+impl Aggregate {
+    fn new(bucket: u64, row: &Row) -> Self {
+        let mut aggregate = Self {
+            bucket,
+            first_block: row.number,
+            last_block: row.number,
+            stats: Default::default(),
+        };
+        aggregate.add(row);
+        aggregate
+    }
+
+    fn add(&mut self, row: &Row) {
+        self.last_block = row.number;
+        for (stats, value) in self.stats.iter_mut().zip(row.fields) {
+            stats.add(value);
+        }
+    }
+
+    fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        let blocks = self.last_block - self.first_block + 1;
+        write!(out, "{},{},{},{blocks}", self.bucket, self.first_block, self.last_block)?;
+        for stats in &self.stats {
+            stats.write(out)?;
+        }
+        writeln!(out)
+    }
+}
+
+fn main() -> eyre::Result<()> {
+    let _guard = RethTracer::new().init()?;
+    let args = Args::parse();
+
+    // The path to data directory, e.g. "~/.local/reth/share/mainnet"
+    let datadir = std::env::var("RETH_DATADIR")?;
+
+    // The chain is detected from the genesis block of the database, unless it's given
+    let chains = match args.chain {
+        Some(chain) => vec![chain],
+        None => SUPPORTED_CHAINS
+            .iter()
+            .map(|chain| chain_value_parser(chain))
+            .collect::<eyre::Result<_>>()?,
+    };
+    let factory = EthereumNode::provider_factory_builder()
+        .open_read_only_matching_genesis(chains, ReadOnlyConfig::from_datadir(datadir))?;
+
+    // Headers are only read from the static files, which hold all headers up to the tip
+    let static_files = factory.static_file_provider();
+    let highest = static_files.get_highest_static_file_block(StaticFileSegment::Headers);
+    let Some(highest) = highest else { eyre::bail!("there are no headers in the static files") };
+    let (start, end) = args.range.map_or((0, highest), RangeInclusive::into_inner);
+    ensure!(end <= highest, "the headers static files end at block {highest}, before {end}");
+
+    let mut checkpoint = args
+        .checkpoint_file
+        .as_deref()
+        .map(|path| {
+            ScanCheckpoint::open(
+                path,
+                factory.chain_spec().genesis_hash(),
+                start..=end,
+                &args.bucket,
+            )
+        })
+        .transpose()?;
+
+    let out: Box<dyn Write> = match (&args.output, &mut checkpoint) {
+        (Some(path), Some(checkpoint)) => Box::new(checkpoint.open_output(path)?),
+        (Some(path), None) => Box::new(File::create(path)?),
+        (None, _) => Box::new(io::stdout().lock()),
+    };
+    let mut out = BufWriter::new(out);
+    // The header was already written by the run that started a resumed export
+    if !checkpoint.as_ref().is_some_and(ScanCheckpoint::is_resumed) {
+        if args.bucket.is_some() {
+            write!(out, "bucket,first_block,last_block,blocks")?;
+            for field in FIELDS {
+                write!(out, ",{field}_min,{field}_max,{field}_mean")?;
+            }
+            writeln!(out)?;
+        } else {
+            writeln!(out, "block,timestamp,{}", FIELDS.join(","))?;
+        }
+    }
+
+    // A resumed export continues after the checkpointed blocks, and may already be done
+    let range = match &checkpoint {
+        Some(checkpoint) => checkpoint.remaining(),
+        None => Some(start..=end),
+    };
+    let Some(range) = range else {
+        info!(blocks = end - start + 1, "Exported headers already");
+        return Ok(())
+    };
+    let start = *range.start();
+
+    let started = Instant::now();
+    let mut last_report = started;
+    let mut aggregate: Option<Aggregate> = None;
+    let mut batch_start = start;
+    while batch_start <= end {
+        let batch_end = batch_start.saturating_add(BATCH_SIZE - 1).min(end);
+        let headers = static_files.headers_range(batch_start..=batch_end)?;
+        ensure!(
+            headers.len() as u64 == batch_end - batch_start + 1,
+            "the headers of blocks {batch_start}..={batch_end} are missing from the static files"
+        );
+
+        for row in headers.iter().map(Row::new) {
+            let Some(bucket) = args.bucket else {
+                write!(out, "{},{}", row.number, row.timestamp)?;
+                for value in row.fields {
+                    match value {
+                        Some(value) => write!(out, ",{value}")?,
+                        None => write!(out, ",")?,
+                    }
+                }
+                writeln!(out)?;
+                continue
+            };
+
+            let key = bucket.of(row.number, row.timestamp);
+            match &mut aggregate {
+                Some(aggregate) if aggregate.bucket == key => aggregate.add(&row),
+                _ => {
+                    if let Some(finished) = aggregate.replace(Aggregate::new(key, &row)) {
+                        finished.write(&mut out)?;
+                    }
+                }
+            }
+        }
+
+        if last_report.elapsed() >= args.progress_interval {
+            let blocks = batch_end - start + 1;
+            info!(
+                block = batch_end,
+                progress = format!("{:.2}%", blocks as f64 / (end - start + 1) as f64 * 100.0),
+                blocks_per_second = (blocks as f64 / started.elapsed().as_secs_f64()) as u64,
+                "Exporting headers"
+            );
+            last_report = Instant::now();
+        }
+
+        // The checkpoint only advances past blocks once their rows have been written, so not
+        // past the bucket that is still being aggregated
+        if let Some(checkpoint) = &mut checkpoint {
+            out.flush()?;
+            checkpoint.advance(
+                aggregate.as_ref().map_or(batch_end + 1, |aggregate| aggregate.first_block),
+            )?;
+        }
+        batch_start = batch_end + 1;
+    }
+    if let Some(aggregate) = aggregate {
+        aggregate.write(&mut out)?;
+    }
+    out.flush()?;
+    if let Some(checkpoint) = &mut checkpoint {
+        checkpoint.advance(end + 1)?;
+    }
+
+    let elapsed = started.elapsed();
+    info!(
+        blocks = end - start + 1,
+        ?elapsed,
+        blocks_per_second = ((end - start + 1) as f64 / elapsed.as_secs_f64()) as u64,
+        "Exported headers"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+//! Decoding logs with a contract ABI.
+
+use alloy_dyn_abi::{DynSolValue, EventExt};
+use alloy_json_abi::{Event, JsonAbi};
+use alloy_primitives::{hex, LogData, B256};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::{collections::HashMap, path::Path};
+
+/// A log decoded with an event of the ABI.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct DecodedLog {
+    /// Name of the event.
+    pub(crate) name: String,
+    /// The decoded parameters by name, or by position for unnamed ones.
+    pub(crate) params: Map<String, Value>,
+}
+
+/// Decodes logs with the events of a contract ABI.
+#[derive(Debug, Default)]
+pub(crate) struct EventDecoder {
+    /// Events by selector. Events with the same signature may differ in which parameters are
+    /// indexed, so all of them are tried.
+    events: HashMap<B256, Vec<Event>>,
+    /// Anonymous events, which have no selector in their topics.
+    anonymous: Vec<Event>,
+}
+
+impl EventDecoder {
+    /// Loads the events of the JSON ABI at `path`.
+    pub(crate) fn load(path: &Path) -> eyre::Result<Self> {
+        let abi: JsonAbi = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        Ok(Self::new(&abi))
+    }
+
+    /// Creates a decoder for the events of `abi`.
+    pub(crate) fn new(abi: &JsonAbi) -> Self {
+        let mut decoder = Self::default();
+        for event in abi.events() {
+            if event.anonymous {
+                decoder.anonymous.push(event.clone());
+            } else {
+                decoder.events.entry(event.selector()).or_default().push(event.clone());
+            }
+        }
+        decoder
+    }
+
+    /// Decodes the log with the first event that fits it.
+    ///
+    /// Returns `None` if the log isn't an event of the ABI, and an error if its selector matches
+    /// an event but the topics or data can't be decoded.
+    pub(crate) fn decode(&self, log: &LogData) -> Option<Result<DecodedLog, String>> {
+        if let Some(events) = log.topics().first().and_then(|topic0| self.events.get(topic0)) {
+            let mut error = None;
+            for event in events {
+                match decode(event, log) {
+                    Ok(decoded) => return Some(Ok(decoded)),
+                    Err(err) => error = Some(format!("failed to decode {}: {err}", event.name)),
+                }
+            }
+            return error.map(Err)
+        }
+
+        // Anonymous events can only be told apart by whether the log decodes
+        self.anonymous.iter().find_map(|event| decode(event, log).ok()).map(Ok)
+    }
+}
+
+/// Decodes the log with `event`.
+fn decode(event: &Event, log: &LogData) -> Result<DecodedLog, alloy_dyn_abi::Error> {
+    let decoded = event.decode_log(log)?;
+    let (mut indexed, mut body) = (decoded.indexed.iter(), decoded.body.iter());
+
+    let mut params = Map::new();
+    for (position, input) in event.inputs.iter().enumerate() {
+        let name = if input.name.is_empty() { position.to_string() } else { input.name.clone() };
+        let value = if input.indexed {
+            let Some(value) = indexed.next() else { break };
+            // Only the hash of indexed strings, bytes, arrays and structs is stored in the topics
+            if is_dynamic(&input.ty) {
+                let mut hash = Map::new();
+                hash.insert("hash".to_string(), to_json(value));
+                Value::Object(hash)
+            } else {
+                to_json(value)
+            }
+        } else {
+            let Some(value) = body.next() else { break };
+            to_json(value)
+        };
+        params.insert(name, value);
+    }
+
+    Ok(DecodedLog { name: event.name.clone(), params })
+}
+
+/// Returns whether the type is stored as a hash when indexed.
+fn is_dynamic(ty: &str) -> bool {
+    ty == "string" || ty == "bytes" || ty.ends_with(']') || ty.starts_with("tuple")
+}
+
+/// Converts a decoded value to JSON. Integers are written as decimal strings, since they don't
+/// fit into JSON numbers, and bytes as hex.
+fn to_json(value: &DynSolValue) -> Value {
+    if let Some(value) = value.as_bool() {
+        Value::Bool(value)
+    } else if let Some((value, _)) = value.as_int() {
+        Value::String(value.to_string())
+    } else if let Some((value, _)) = value.as_uint() {
+        Value::String(value.to_string())
+    } else if let Some(address) = value.as_address() {
+        Value::String(address.to_checksum(None))
+    } else if let Some(value) = value.as_str() {
+        Value::String(value.to_string())
+    } else if let Some((bytes, _)) = value.as_fixed_bytes() {
+        Value::String(hex::encode_prefixed(bytes))
+    } else if let Some(bytes) = value.as_bytes() {
+        Value::String(hex::encode_prefixed(bytes))
+    } else if let Some(function) = value.as_function() {
+        Value::String(function.to_string())
+    } else if let Some(values) =
+        value.as_array().or_else(|| value.as_fixed_array()).or_else(|| value.as_tuple())
+    {
+        Value::Array(values.iter().map(to_json).collect())
+    } else {
+        Value::String(format!("{value:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, Address, U256};
+    use serde_json::json;
+
+    const ABI: &str = r#"[
+        {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+            {"name": "from", "type": "address", "indexed": true},
+            {"name": "to", "type": "address", "indexed": true},
+            {"name": "value", "type": "uint256", "indexed": false}
+        ]},
+        {"type": "event", "name": "Named", "anonymous": false, "inputs": [
+            {"name": "label", "type": "string", "indexed": true},
+            {"name": "", "type": "string", "indexed": false},
+            {"name": "ids", "type": "uint64[]", "indexed": false}
+        ]},
+        {"type": "event", "name": "Raw", "anonymous": true, "inputs": [
+            {"name": "data", "type": "bytes", "indexed": false}
+        ]}
+    ]"#;
+
+    fn decoder() -> EventDecoder {
+        EventDecoder::new(&serde_json::from_str(ABI).unwrap())
+    }
+
+    #[test]
+    fn decodes_indexed_and_body_params() {
+        let (from, to) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let log = LogData::new_unchecked(
+            vec![keccak256("Transfer(address,address,uint256)"), from.into_word(), to.into_word()],
+            U256::from(1000).to_be_bytes_vec().into(),
+        );
+
+        let decoded = decoder().decode(&log).unwrap().unwrap();
+        assert_eq!(decoded.name, "Transfer");
+        assert_eq!(
+            Value::Object(decoded.params),
+            json!({"from": from.to_checksum(None), "to": to.to_checksum(None), "value": "1000"})
+        );
+    }
+
+    #[test]
+    fn decodes_dynamic_params() {
+        let body = DynSolValue::Tuple(vec![
+            DynSolValue::String("hello".to_string()),
+            DynSolValue::Array(vec![DynSolValue::Uint(U256::from(7), 64)]),
+        ]);
+        let label = keccak256("label");
+        let log = LogData::new_unchecked(
+            vec![keccak256("Named(string,string,uint64[])"), label],
+            body.abi_encode_params().into(),
+        );
+
+        let decoded = decoder().decode(&log).unwrap().unwrap();
+        assert_eq!(
+            Value::Object(decoded.params),
+            json!({"label": {"hash": label.to_string()}, "1": "hello", "ids": ["7"]})
+        );
+    }
+
+    #[test]
+    fn anonymous_unknown_and_invalid_logs() {
+        let data = DynSolValue::Tuple(vec![DynSolValue::Bytes(vec![1, 2, 3])]).abi_encode_params();
+        let anonymous = LogData::new_unchecked(Vec::new(), data.into());
+        let decoded = decoder().decode(&anonymous).unwrap().unwrap();
+        assert_eq!((decoded.name.as_str(), &decoded.params["data"]), ("Raw", &json!("0x010203")));
+
+        let unknown = LogData::new_unchecked(vec![B256::ZERO], Default::default());
+        assert_eq!(decoder().decode(&unknown), None);
+
+        // A transfer without the indexed addresses
+        let truncated = LogData::new_unchecked(
+            vec![keccak256("Transfer(address,address,uint256)")],
+            U256::ZERO.to_be_bytes_vec().into(),
+        );
+        assert!(decoder().decode(&truncated).unwrap().is_err());
+    }
+}
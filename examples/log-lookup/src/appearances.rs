@@ -0,0 +1,211 @@
+//! Reading address appearances, the transactions an address appears in, from an appearance file.
+
+use alloy_primitives::Address;
+use clap::ValueEnum;
+use eyre::{bail, ensure, WrapErr};
+use std::{collections::BTreeMap, path::Path};
+
+/// The format of an appearance file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum AppearancesFormat {
+    /// Rows of `address,blockNumber,transactionIndex`, optionally with a header row, like the CSV
+    /// output of `chifra list`.
+    Csv,
+    /// A binary chunk of the TrueBlocks Unchained Index.
+    Unchained,
+}
+
+/// Magic number at the start of an Unchained Index chunk.
+const UNCHAINED_MAGIC: u32 = 0xdeadbeef;
+
+/// Length of the chunk header: the magic, the version hash and the record counts.
+const UNCHAINED_HEADER_LEN: usize = 4 + 32 + 4 + 4;
+
+/// Length of an address record: the address, and the offset and count of its appearances.
+const UNCHAINED_ADDRESS_LEN: usize = 20 + 4 + 4;
+
+/// Length of an appearance record: the block number and transaction index.
+const UNCHAINED_APPEARANCE_LEN: usize = 4 + 4;
+
+/// An appearance of an address in a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Appearance {
+    pub(crate) address: Address,
+    pub(crate) block: u64,
+    pub(crate) tx_index: u64,
+}
+
+/// The transaction indices of the appearances in each block, sorted and without duplicates.
+pub(crate) type Appearances = BTreeMap<u64, Vec<u64>>;
+
+/// Groups appearances by block. Several of the addresses may appear in the same transaction.
+pub(crate) fn by_block(appearances: &[Appearance]) -> Appearances {
+    let mut blocks = Appearances::new();
+    for appearance in appearances {
+        blocks.entry(appearance.block).or_default().push(appearance.tx_index);
+    }
+    for tx_indices in blocks.values_mut() {
+        tx_indices.sort_unstable();
+        tx_indices.dedup();
+    }
+    blocks
+}
+
+/// Reads the appearances of `addresses` from the file at `path` in file order, or of all
+/// addresses if empty.
+pub(crate) fn read(
+    path: &Path,
+    format: AppearancesFormat,
+    addresses: &[Address],
+) -> eyre::Result<Vec<Appearance>> {
+    let bytes = std::fs::read(path)?;
+    let appearances = match format {
+        AppearancesFormat::Csv => parse_csv(&String::from_utf8(bytes)?, addresses),
+        AppearancesFormat::Unchained => parse_unchained(&bytes, addresses),
+    };
+    appearances.wrap_err_with(|| format!("invalid appearance file {}", path.display()))
+}
+
+/// Parses appearances in the [`AppearancesFormat::Csv`] format.
+fn parse_csv(csv: &str, addresses: &[Address]) -> eyre::Result<Vec<Appearance>> {
+    let mut appearances = Vec::new();
+    for (line, row) in csv.lines().enumerate() {
+        let row = row.trim();
+        if row.is_empty() || (line == 0 && row.starts_with("address")) {
+            continue
+        }
+
+        let parse = || -> eyre::Result<_> {
+            let mut columns = row.split(',').map(str::trim);
+            let mut column = || columns.next().ok_or_else(|| eyre::eyre!("missing column"));
+            let address = column()?.parse::<Address>()?;
+            Ok((address, column()?.parse::<u64>()?, column()?.parse::<u64>()?))
+        };
+        let (address, block, tx_index) = parse().wrap_err_with(|| format!("line {}", line + 1))?;
+        if addresses.is_empty() || addresses.contains(&address) {
+            appearances.push(Appearance { address, block, tx_index });
+        }
+    }
+    Ok(appearances)
+}
+
+/// Parses appearances in the [`AppearancesFormat::Unchained`] format.
+///
+/// A chunk starts with the header, followed by the address records sorted by address, and the
+/// appearance records. All integers are little endian.
+fn parse_unchained(chunk: &[u8], addresses: &[Address]) -> eyre::Result<Vec<Appearance>> {
+    let u32_at =
+        |offset: usize| u32::from_le_bytes(chunk[offset..offset + 4].try_into().expect("4 bytes"));
+    let usize_at = |offset: usize| u32_at(offset) as usize;
+
+    ensure!(
+        chunk.len() >= UNCHAINED_HEADER_LEN,
+        "truncated chunk: {} bytes is shorter than the {UNCHAINED_HEADER_LEN} byte header",
+        chunk.len()
+    );
+    let magic = u32_at(0);
+    ensure!(magic == UNCHAINED_MAGIC, "not an Unchained Index chunk: magic is {magic:#x}");
+
+    let (address_count, appearance_count) = (usize_at(36), usize_at(40));
+    let appearances_start = UNCHAINED_HEADER_LEN + address_count * UNCHAINED_ADDRESS_LEN;
+    let expected_len = appearances_start + appearance_count * UNCHAINED_APPEARANCE_LEN;
+    if chunk.len() != expected_len {
+        bail!(
+            "{} chunk: {address_count} addresses and {appearance_count} appearances need \
+             {expected_len} bytes, but it has {}",
+            if chunk.len() < expected_len { "truncated" } else { "invalid" },
+            chunk.len()
+        )
+    }
+
+    let mut appearances = Vec::new();
+    for record in 0..address_count {
+        let start = UNCHAINED_HEADER_LEN + record * UNCHAINED_ADDRESS_LEN;
+        let address = Address::from_slice(&chunk[start..start + 20]);
+        if !addresses.is_empty() && !addresses.contains(&address) {
+            continue
+        }
+
+        let (offset, count) = (usize_at(start + 20), usize_at(start + 24));
+        ensure!(
+            offset + count <= appearance_count,
+            "appearances {offset}..{} of address {address} are out of bounds",
+            offset + count
+        );
+        for appearance in offset..offset + count {
+            let start = appearances_start + appearance * UNCHAINED_APPEARANCE_LEN;
+            let (block, tx_index) = (u32_at(start) as u64, u32_at(start + 4) as u64);
+            appearances.push(Appearance { address, block, tx_index });
+        }
+    }
+    Ok(appearances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(addresses: &[(Address, u32, u32)], appearances: &[(u32, u32)]) -> Vec<u8> {
+        let mut chunk = UNCHAINED_MAGIC.to_le_bytes().to_vec();
+        chunk.extend_from_slice(&[0; 32]);
+        chunk.extend_from_slice(&(addresses.len() as u32).to_le_bytes());
+        chunk.extend_from_slice(&(appearances.len() as u32).to_le_bytes());
+        for (address, offset, count) in addresses {
+            chunk.extend_from_slice(address.as_slice());
+            chunk.extend_from_slice(&offset.to_le_bytes());
+            chunk.extend_from_slice(&count.to_le_bytes());
+        }
+        for (block, tx_index) in appearances {
+            chunk.extend_from_slice(&block.to_le_bytes());
+            chunk.extend_from_slice(&tx_index.to_le_bytes());
+        }
+        chunk
+    }
+
+    #[test]
+    fn parses_unchained_chunk() {
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let chunk = chunk(&[(a, 0, 2), (b, 2, 2)], &[(10, 1), (12, 0), (10, 1), (11, 3)]);
+
+        let appearances = parse_unchained(&chunk, &[a]).unwrap();
+        assert_eq!(
+            appearances,
+            vec![
+                Appearance { address: a, block: 10, tx_index: 1 },
+                Appearance { address: a, block: 12, tx_index: 0 },
+            ]
+        );
+
+        let expected = Appearances::from([(10, vec![1]), (11, vec![3]), (12, vec![0])]);
+        assert_eq!(by_block(&parse_unchained(&chunk, &[]).unwrap()), expected);
+    }
+
+    #[test]
+    fn rejects_invalid_unchained_chunks() {
+        let a = Address::repeat_byte(1);
+        let valid = chunk(&[(a, 0, 2)], &[(10, 1), (12, 0)]);
+
+        let err = parse_unchained(&valid[..valid.len() - 1], &[a]).unwrap_err();
+        assert!(err.to_string().starts_with("truncated chunk"), "{err}");
+        assert!(parse_unchained(&valid[..10], &[a]).is_err());
+
+        let mut magic = valid.clone();
+        magic[0] = 0;
+        assert!(parse_unchained(&magic, &[a]).is_err());
+
+        let out_of_bounds = chunk(&[(a, 1, 2)], &[(10, 1), (12, 0)]);
+        assert!(parse_unchained(&out_of_bounds, &[a]).is_err());
+    }
+
+    #[test]
+    fn parses_csv() {
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let csv = format!("address,blockNumber,transactionIndex\n{a},10,2\n{b},10,1\n{a},9,0\n");
+
+        let expected = Appearances::from([(9, vec![0]), (10, vec![2])]);
+        assert_eq!(by_block(&parse_csv(&csv, &[a]).unwrap()), expected);
+
+        let err = parse_csv(&format!("{a},10\n"), &[]).unwrap_err();
+        assert_eq!(err.to_string(), "line 1");
+    }
+}
@@ -0,0 +1,393 @@
+//! Example of scanning a range of blocks in the reth database for logs that match a filter.
+//!
+//! Run with
+//!
+//! ```sh
+//! RETH_DATADIR=~/.local/share/reth/mainnet cargo run --release -p example-log-lookup -- \
+//!     --range 18000000:18100000 --address 0xdac17f958d2ee523a2206206994597c13d831ec7 \
+//!     --topic0 0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef
+//! ```
+//!
+//! The chain of the database is detected from its genesis block, or checked against `--chain`.
+//! The range is split into chunks that are scanned in parallel, each with its own read-only
+//! transaction. Matching logs are written in block order while the scan is running, as JSON lines
+//! or CSV, to stdout or the file given with `--output`. With `--abi`, logs of the events of a
+//! contract ABI are decoded into named parameters in the JSON-lines output. `--include` adds header
+//! fields of the block, e.g. `--include timestamp,basefee,gasused`, from the headers that the scan
+//! loads anyway for the bloom check.
+//!
+//! With `--index-dir`, the OR-ed logs blooms of chunks of 8192 blocks are kept in the given
+//! directory, so repeated scans skip whole chunks without matching logs. The index is extended
+//! on every run and rebuilt when the tip of the indexed blocks is no longer canonical.
+//!
+//! With `--appearances`, only the transactions listed in an appearance file are scanned, e.g. from
+//! the TrueBlocks Unchained Index. `--appearances-format` selects between CSV rows of
+//! `address,blockNumber,transactionIndex` and binary Unchained Index chunks, which are filtered to
+//! the `--address` values.
+//!
+//! `--verify` checks each appearance against the logs, sender and recipient of its transaction
+//! instead, to validate appearance lists. The appearances are written as CSV with a verdict column.
+//!
+//! With `--checkpoint-file`, the progress of the scan is recorded after every chunk, and a scan
+//! that was interrupted continues from the last recorded chunk when it's run again, appending to
+//! the `--output` file.
+//!
+//! Blocks that can't be scanned, e.g. because their header is missing or an appearance points past
+//! their transactions, don't stop the scan. They are listed when it's done, and the exit code is
+//! nonzero unless `--best-effort` is passed.
+
+#![warn(unused_crate_dependencies)]
+
+use abi::EventDecoder;
+use alloy_primitives::{Address, B256};
+use appearances::AppearancesFormat;
+use clap::Parser;
+use example_scan_checkpoint::{parse_range, ScanCheckpoint};
+use output::{Field, Format, LogWriter};
+use reth_ethereum::{
+    chainspec::{ChainSpec, ChainSpecProvider, EthChainSpec},
+    cli::chainspec::{chain_value_parser, SUPPORTED_CHAINS},
+    node::EthereumNode,
+    provider::{
+        providers::ReadOnlyConfig, BlockBodyIndicesProvider, BlockNumReader, BloomIndex,
+        HeaderProvider, LogScanner, ParallelLogScanner, ScanSummary, ScannedLog,
+        DEFAULT_SCAN_CHUNK_SIZE,
+    },
+    rpc::eth::primitives::Filter,
+};
+use reth_tracing::{tracing::info, RethTracer, Tracer};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+mod abi;
+mod appearances;
+mod output;
+mod verify;
+
+/// Scans a block range for logs.
+#[derive(Debug, Parser)]
+struct Args {
+    /// The chain of the database, which is checked against its genesis block.
+    ///
+    /// Detected from the genesis block if not given.
+    #[arg(long, value_name = "CHAIN", value_parser = chain_value_parser)]
+    chain: Option<Arc<ChainSpec>>,
+
+    /// The blocks to scan, as `<from>:<to>` (inclusive).
+    #[arg(long, value_parser = parse_range)]
+    range: RangeInclusive<u64>,
+
+    /// Only return logs emitted by one of these addresses.
+    #[arg(long, value_delimiter = ',')]
+    address: Vec<Address>,
+
+    /// Only return logs with one of these event signatures as their first topic.
+    #[arg(long, value_delimiter = ',')]
+    topic0: Vec<B256>,
+
+    /// Only return logs with one of these values as their second topic.
+    #[arg(long, value_delimiter = ',')]
+    topic1: Vec<B256>,
+
+    /// Only return logs with one of these values as their third topic.
+    #[arg(long, value_delimiter = ',')]
+    topic2: Vec<B256>,
+
+    /// Only return logs with one of these values as their fourth topic.
+    #[arg(long, value_delimiter = ',')]
+    topic3: Vec<B256>,
+
+    /// Number of blocks scanned by a single task.
+    #[arg(long, default_value_t = DEFAULT_SCAN_CHUNK_SIZE)]
+    chunk_size: u64,
+
+    /// Interval between progress reports.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "5s")]
+    progress_interval: Duration,
+
+    /// The file to write the logs to, instead of stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// The format of the written logs.
+    #[arg(long, value_enum, default_value_t = Format::Jsonl)]
+    format: Format,
+
+    /// Header fields of the block to add to every log.
+    #[arg(long, value_enum, value_delimiter = ',')]
+    include: Vec<Field>,
+
+    /// A contract ABI in JSON, to decode the logs of its events in the JSON-lines output.
+    ///
+    /// Logs of other events are written without decoding.
+    #[arg(long, value_name = "PATH")]
+    abi: Option<PathBuf>,
+
+    /// Continue past blocks whose receipts have been pruned, instead of stopping at the first
+    /// one. Each range of skipped blocks is reported once.
+    #[arg(long)]
+    skip_missing: bool,
+
+    /// A directory for an index of the logs blooms of chunks of blocks, which lets scans skip
+    /// chunks without matching logs.
+    ///
+    /// The index is created or extended to the current tip before scanning, and rebuilt if the
+    /// indexed blocks were reorged.
+    #[arg(long, value_name = "DIR")]
+    index_dir: Option<PathBuf>,
+
+    /// A file with the appearances of the addresses, to only scan the transactions they appear
+    /// in instead of the whole range.
+    #[arg(long, value_name = "PATH")]
+    appearances: Option<PathBuf>,
+
+    /// The format of the appearance file.
+    #[arg(long, value_enum, default_value_t = AppearancesFormat::Csv)]
+    appearances_format: AppearancesFormat,
+
+    /// Exit successfully even if some blocks couldn't be scanned.
+    ///
+    /// Failed blocks are listed after the scan either way.
+    #[arg(long)]
+    best_effort: bool,
+
+    /// Instead of writing logs, check every appearance against its transaction and write the
+    /// appearances as CSV with a verdict column.
+    ///
+    /// The verdict is MATCH if the transaction emitted a log of the address that matches the topic
+    /// filters, TX_MATCH if the address is only its sender or recipient, BLOOM_ONLY if only the
+    /// logs bloom of the block matches, and MISS otherwise. The number of appearances per verdict
+    /// is printed at the end.
+    #[arg(long, requires = "appearances")]
+    verify: bool,
+
+    /// A file to record the progress of the scan in after every chunk, so that an interrupted
+    /// scan continues where it stopped when it's run again with the same file.
+    ///
+    /// The scan must be resumed with the same range, filters and output options, and the output
+    /// file is continued instead of overwritten. Blocks that failed are only listed by the run
+    /// that scanned them.
+    #[arg(long, value_name = "PATH", requires = "output", conflicts_with = "appearances")]
+    checkpoint_file: Option<PathBuf>,
+}
+
+fn main() -> eyre::Result<()> {
+    let _guard = RethTracer::new().init()?;
+    let args = Args::parse();
+
+    // The path to data directory, e.g. "~/.local/reth/share/mainnet"
+    let datadir = std::env::var("RETH_DATADIR")?;
+
+    // The chain is detected from the genesis block of the database, unless it's given
+    let chains = match args.chain.clone() {
+        Some(chain) => vec![chain],
+        None => SUPPORTED_CHAINS
+            .iter()
+            .map(|chain| chain_value_parser(chain))
+            .collect::<eyre::Result<_>>()?,
+    };
+    let factory = EthereumNode::provider_factory_builder()
+        .open_read_only_matching_genesis(chains, ReadOnlyConfig::from_datadir(datadir))?;
+
+    let appearances = args
+        .appearances
+        .as_deref()
+        .map(|path| appearances::read(path, args.appearances_format, &args.address))
+        .transpose()?;
+
+    // Each flag can be repeated to match any of the given values, and positions without a value
+    // match any topic, like in `eth_getLogs`.
+    let filter = Filter::new()
+        .address(args.address)
+        .topic0(args.topic0)
+        .topic1(args.topic1)
+        .topic2(args.topic2)
+        .topic3(args.topic3);
+
+    let mut checkpoint = args
+        .checkpoint_file
+        .as_deref()
+        .map(|path| {
+            // Everything that changes the written logs must be the same when resuming
+            let query = (&filter, args.format, &args.include, &args.abi, args.skip_missing);
+            ScanCheckpoint::open(
+                path,
+                factory.chain_spec().genesis_hash(),
+                args.range.clone(),
+                &query,
+            )
+        })
+        .transpose()?;
+
+    let out: Box<dyn Write> = match (&args.output, &mut checkpoint) {
+        (Some(path), Some(checkpoint)) => Box::new(checkpoint.open_output(path)?),
+        (Some(path), None) => Box::new(File::create(path)?),
+        (None, _) => Box::new(io::stdout().lock()),
+    };
+
+    if args.verify {
+        let appearances = appearances.into_iter().flatten();
+        let mut failed = Vec::new();
+        verify::write_verdicts(
+            &factory.provider()?,
+            appearances.filter(|appearance| args.range.contains(&appearance.block)),
+            &filter,
+            BufWriter::new(out),
+            &mut failed,
+        )?;
+        return report_failures(&failed, args.best_effort)
+    }
+
+    let decoder = args.abi.as_deref().map(EventDecoder::load).transpose()?;
+    let mut writer = LogWriter::new(BufWriter::new(out), args.format, decoder, args.include);
+    if !checkpoint.as_ref().is_some_and(ScanCheckpoint::is_resumed) {
+        writer.write_header()?;
+    }
+
+    let mut result = Ok(());
+    // Blocks that can't be scanned are reported after the scan instead of aborting it
+    let mut failed = Vec::new();
+    let skipped = if let Some(appearances) = appearances.as_deref().map(appearances::by_block) {
+        // Only the blocks of the appearances are scanned, and only the logs of their transactions
+        // are written
+        let provider = factory.provider()?;
+        let mut skipped = Vec::new();
+        for (&block, tx_indices) in appearances.range(args.range) {
+            // The appearance file may be stale relative to the local chain
+            let tx_count = match provider.block_body_indices(block) {
+                Ok(Some(indices)) => indices.tx_count,
+                Ok(None) => {
+                    failed.push((block, "block body not found".to_string()));
+                    continue
+                }
+                Err(err) => {
+                    failed.push((block, err.to_string()));
+                    continue
+                }
+            };
+            if let Some(tx_index) = tx_indices.iter().find(|tx_index| **tx_index >= tx_count) {
+                failed.push((
+                    block,
+                    format!(
+                        "transaction index {tx_index} is out of bounds of {tx_count} transactions"
+                    ),
+                ));
+            }
+
+            let sink = |log: ScannedLog| {
+                if result.is_ok() && tx_indices.binary_search(&log.tx_index).is_ok() {
+                    result = writer.write(&log);
+                }
+            };
+            let scanned = if args.skip_missing {
+                provider
+                    .scan_logs_skip_missing(block..=block, &filter, sink)
+                    .map(|missing| skipped.extend(missing))
+            } else {
+                provider.scan_logs(block..=block, &filter, sink)
+            };
+            if let Err(err) = scanned {
+                failed.push((block, err.to_string()));
+            }
+        }
+        result = result.and_then(|_| writer.flush());
+        skipped
+    } else {
+        let index = args
+            .index_dir
+            .as_deref()
+            .map(|dir| load_index(&factory.provider()?, dir))
+            .transpose()?;
+
+        let mut scanner = ParallelLogScanner::new(factory)
+            .with_chunk_size(args.chunk_size)
+            .with_progress_interval(args.progress_interval)
+            .with_skip_missing(args.skip_missing)
+            .with_continue_on_error(true);
+        if let Some(index) = index {
+            scanner = scanner.with_bloom_index(index);
+        }
+
+        // A resumed scan continues after the checkpointed blocks, and may already be done
+        let range = match &checkpoint {
+            Some(checkpoint) => checkpoint.remaining(),
+            None => Some(args.range),
+        };
+
+        // Logs are written chunk by chunk as they arrive instead of being collected, so scanning
+        // a large range doesn't buffer all matches in memory. The checkpoint only advances past a
+        // chunk once its logs have been written.
+        let summary = match range {
+            Some(range) => scanner.scan_chunks(range, &filter, |chunk, logs| {
+                if result.is_ok() {
+                    result = logs
+                        .iter()
+                        .try_for_each(|log| writer.write(log))
+                        .and_then(|_| writer.flush())
+                        .and_then(|_| match &mut checkpoint {
+                            Some(checkpoint) => checkpoint.advance(chunk.end() + 1),
+                            None => Ok(()),
+                        });
+                }
+            })?,
+            None => ScanSummary::default(),
+        };
+        failed.extend(
+            summary.failed.into_iter().map(|failed| (failed.block, failed.error.to_string())),
+        );
+        summary.missing
+    };
+    result?;
+
+    if !skipped.is_empty() {
+        let blocks: u64 = skipped.iter().map(|range| range.end() - range.start() + 1).sum();
+        eprintln!(
+            "Skipped {} ranges of up to {blocks} blocks with missing receipts",
+            skipped.len()
+        );
+    }
+
+    report_failures(&failed, args.best_effort)
+}
+
+/// Lists the blocks that couldn't be scanned, and fails unless `best_effort` is set.
+fn report_failures(failed: &[(u64, String)], best_effort: bool) -> eyre::Result<()> {
+    if !failed.is_empty() {
+        eprintln!("Failed to scan {} blocks:", failed.len());
+        for (block, reason) in failed {
+            eprintln!("  #{block}: {reason}");
+        }
+        if !best_effort {
+            eyre::bail!("failed to scan {} blocks", failed.len())
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads the bloom index from `dir`, brings it up to date with the chain and saves it.
+fn load_index<P: HeaderProvider + BlockNumReader>(
+    provider: &P,
+    dir: &Path,
+) -> eyre::Result<BloomIndex> {
+    let mut index = BloomIndex::load(dir)?.unwrap_or_default();
+    if !index.is_valid(provider)? {
+        info!(tip = ?index.tip(), "Indexed blocks were reorged, rebuilding the bloom index");
+        index = BloomIndex::default();
+    }
+
+    let chunks = index.len();
+    index.update(provider, provider.best_block_number()?)?;
+    if index.len() != chunks {
+        info!(chunks = index.len() - chunks, tip = ?index.tip(), "Indexed new chunks");
+        index.save(dir)?;
+    }
+
+    Ok(index)
+}
@@ -0,0 +1,188 @@
+//! Writing matching logs in machine readable formats.
+
+use crate::abi::{DecodedLog, EventDecoder};
+use alloy_primitives::{Address, BlockHash, Bytes, TxHash, B256};
+use clap::ValueEnum;
+use reth_ethereum::provider::ScannedLog;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::io::{self, Write};
+
+/// The format of the written logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub(crate) enum Format {
+    /// One JSON object per line.
+    Jsonl,
+    /// Comma separated values with a header row, and a column for each topic.
+    Csv,
+}
+
+/// A header field of the block of a log that can be included in the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+pub(crate) enum Field {
+    /// The timestamp of the block.
+    Timestamp,
+    /// The base fee per gas of the block, empty before London.
+    #[value(name = "basefee")]
+    BaseFee,
+    /// The gas used by all transactions of the block.
+    #[value(name = "gasused")]
+    GasUsed,
+}
+
+impl Field {
+    /// The name of the field in the JSON-lines format.
+    const fn json_name(self) -> &'static str {
+        match self {
+            Self::Timestamp => "blockTimestamp",
+            Self::BaseFee => "baseFeePerGas",
+            Self::GasUsed => "gasUsed",
+        }
+    }
+
+    /// The name of the column in the CSV format.
+    const fn csv_name(self) -> &'static str {
+        match self {
+            Self::Timestamp => "block_timestamp",
+            Self::BaseFee => "base_fee_per_gas",
+            Self::GasUsed => "gas_used",
+        }
+    }
+
+    /// Returns the value of the field for the block of `log`.
+    const fn value(self, log: &ScannedLog) -> Option<u64> {
+        match self {
+            Self::Timestamp => Some(log.block_timestamp),
+            Self::BaseFee => log.base_fee_per_gas,
+            Self::GasUsed => Some(log.gas_used),
+        }
+    }
+}
+
+/// A log as written in the JSON-lines format.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonLog<'a> {
+    block_number: u64,
+    block_hash: BlockHash,
+    transaction_hash: TxHash,
+    transaction_index: u64,
+    log_index: u64,
+    address: Address,
+    topics: &'a [B256],
+    data: &'a Bytes,
+    /// The included header fields.
+    #[serde(flatten)]
+    fields: Map<String, Value>,
+    /// The log decoded with the ABI, if it's one of its events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event: Option<DecodedLog>,
+    /// Why the log couldn't be decoded, although its selector matches an event of the ABI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decode_error: Option<String>,
+}
+
+/// Writes logs in a [`Format`].
+#[derive(Debug)]
+pub(crate) struct LogWriter<W> {
+    out: W,
+    format: Format,
+    decoder: Option<EventDecoder>,
+    include: Vec<Field>,
+}
+
+impl<W: Write> LogWriter<W> {
+    /// Creates a new writer.
+    ///
+    /// If a `decoder` is given, logs of its events are decoded in the JSON-lines format. The
+    /// header fields in `include` are written after the log, in the given order.
+    pub(crate) const fn new(
+        out: W,
+        format: Format,
+        decoder: Option<EventDecoder>,
+        include: Vec<Field>,
+    ) -> Self {
+        Self { out, format, decoder, include }
+    }
+
+    /// Writes the CSV header if needed, before the first log.
+    pub(crate) fn write_header(&mut self) -> io::Result<()> {
+        if self.format == Format::Csv {
+            write!(
+                self.out,
+                "block_number,block_hash,transaction_hash,transaction_index,log_index,address,\
+                 topic0,topic1,topic2,topic3,data"
+            )?;
+            for field in &self.include {
+                write!(self.out, ",{}", field.csv_name())?;
+            }
+            writeln!(self.out)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a single log.
+    pub(crate) fn write(&mut self, log: &ScannedLog) -> io::Result<()> {
+        match self.format {
+            Format::Jsonl => {
+                let (event, decode_error) =
+                    match self.decoder.as_ref().and_then(|decoder| decoder.decode(&log.log.data)) {
+                        Some(Ok(event)) => (Some(event), None),
+                        Some(Err(err)) => (None, Some(err)),
+                        None => (None, None),
+                    };
+                let row = JsonLog {
+                    block_number: log.block_number,
+                    block_hash: log.block_hash,
+                    transaction_hash: log.tx_hash,
+                    transaction_index: log.tx_index,
+                    log_index: log.log_index,
+                    address: log.log.address,
+                    topics: log.log.topics(),
+                    data: &log.log.data.data,
+                    fields: self
+                        .include
+                        .iter()
+                        .map(|field| (field.json_name().to_string(), field.value(log).into()))
+                        .collect(),
+                    event,
+                    decode_error,
+                };
+                serde_json::to_writer(&mut self.out, &row)?;
+                writeln!(self.out)
+            }
+            Format::Csv => {
+                write!(
+                    self.out,
+                    "{},{},{},{},{},{}",
+                    log.block_number,
+                    log.block_hash,
+                    log.tx_hash,
+                    log.tx_index,
+                    log.log_index,
+                    log.log.address
+                )?;
+                // Logs have at most four topics, missing ones are left empty
+                for index in 0..4 {
+                    match log.log.topics().get(index) {
+                        Some(topic) => write!(self.out, ",{topic}")?,
+                        None => write!(self.out, ",")?,
+                    }
+                }
+                write!(self.out, ",{}", log.log.data.data)?;
+                for field in &self.include {
+                    match field.value(log) {
+                        Some(value) => write!(self.out, ",{value}")?,
+                        None => write!(self.out, ",")?,
+                    }
+                }
+                writeln!(self.out)
+            }
+        }
+    }
+
+    /// Flushes the buffered logs.
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
@@ -0,0 +1,137 @@
+//! Verifying appearances against the logs and transactions in the database.
+
+use crate::appearances::Appearance;
+use alloy_consensus::{transaction::SignerRecoverable, BlockHeader, Transaction, TxReceipt};
+use alloy_primitives::Address;
+use eyre::{ensure, OptionExt};
+use reth_ethereum::{
+    provider::{LogScanError, LogScanner},
+    rpc::eth::primitives::Filter,
+};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, Write},
+};
+
+/// How an appearance relates to the transaction it points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Verdict {
+    /// The transaction emitted a log of the address that matches the filter.
+    Match,
+    /// The transaction has no such log, but the address is its sender or recipient.
+    TxMatch,
+    /// The logs bloom of the block matches, but no log of the transaction does, so either the
+    /// bloom or the appearance is a false positive.
+    BloomOnly,
+    /// Not even the logs bloom of the block matches.
+    Miss,
+    /// The appearance couldn't be verified, e.g. because it points past the transactions of the
+    /// block.
+    Error,
+}
+
+impl Verdict {
+    const ALL: [Self; 5] = [Self::Match, Self::TxMatch, Self::BloomOnly, Self::Miss, Self::Error];
+
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Match => "MATCH",
+            Self::TxMatch => "TX_MATCH",
+            Self::BloomOnly => "BLOOM_ONLY",
+            Self::Miss => "MISS",
+            Self::Error => "ERROR",
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Writes the appearances as CSV with a verdict column, and prints the number of appearances per
+/// verdict.
+///
+/// Appearances that can't be verified get [`Verdict::Error`], and are pushed to `failed` with the
+/// reason.
+pub(crate) fn write_verdicts<P: LogScanner>(
+    provider: &P,
+    appearances: impl IntoIterator<Item = Appearance>,
+    filter: &Filter,
+    mut out: impl Write,
+    failed: &mut Vec<(u64, String)>,
+) -> io::Result<()> {
+    let mut counts = BTreeMap::<Verdict, usize>::new();
+    writeln!(out, "address,blockNumber,transactionIndex,verdict")?;
+    for appearance in appearances {
+        let verdict = verify(provider, &appearance, filter).unwrap_or_else(|err| {
+            failed.push((appearance.block, format!("{}: {err}", appearance.address)));
+            Verdict::Error
+        });
+        *counts.entry(verdict).or_default() += 1;
+
+        let Appearance { address, block, tx_index } = appearance;
+        writeln!(out, "{address},{block},{tx_index},{verdict}")?;
+    }
+    out.flush()?;
+
+    for verdict in Verdict::ALL {
+        eprintln!("{verdict}: {}", counts.get(&verdict).copied().unwrap_or_default());
+    }
+    Ok(())
+}
+
+/// Checks whether the transaction of the appearance emitted a log of its address that matches the
+/// topics of `filter`, or was sent by or to the address.
+///
+/// The receipt is only read if the logs bloom of the block matches.
+fn verify<P: LogScanner>(
+    provider: &P,
+    appearance: &Appearance,
+    filter: &Filter,
+) -> eyre::Result<Verdict> {
+    let Appearance { address, block, tx_index } = *appearance;
+    let header = provider.header_by_number(block)?.ok_or_eyre("header not found")?;
+    let indices = provider.block_body_indices(block)?.ok_or_eyre("block body not found")?;
+    ensure!(
+        tx_index < indices.tx_count,
+        "transaction index {tx_index} is out of bounds of {} transactions",
+        indices.tx_count
+    );
+    let tx_num = indices.first_tx_num + tx_index;
+
+    let filter = filter.clone().address(address);
+    let bloom_matches = filter.matches_bloom(header.logs_bloom());
+    if bloom_matches {
+        let receipt = provider.receipt(tx_num)?.ok_or(LogScanError::MissingReceipts { block })?;
+        if receipt.logs().iter().any(|log| filter.matches(log)) {
+            return Ok(Verdict::Match)
+        }
+    }
+
+    if is_sender_or_recipient(provider, tx_num, address)? {
+        return Ok(Verdict::TxMatch)
+    }
+    Ok(if bloom_matches { Verdict::BloomOnly } else { Verdict::Miss })
+}
+
+/// Returns whether `address` sent the transaction or is its recipient.
+fn is_sender_or_recipient<P: LogScanner>(
+    provider: &P,
+    tx_num: u64,
+    address: Address,
+) -> eyre::Result<bool> {
+    let transaction = provider.transaction_by_id(tx_num)?.ok_or_eyre("transaction not found")?;
+    if transaction.to() == Some(address) {
+        return Ok(true)
+    }
+
+    // Senders are recovered from the signature if they have been pruned
+    let sender = match provider.transaction_sender(tx_num)? {
+        Some(sender) => sender,
+        None => transaction.recover_signer()?,
+    };
+    Ok(sender == address)
+}
@@ -0,0 +1,240 @@
+//! Checkpoints for example tools that scan a range of blocks in chunks, so that a scan that was
+//! interrupted can be resumed instead of started over, and the helpers these tools share to parse
+//! and split their ranges.
+//!
+//! A [`ScanCheckpoint`] records the next block to scan and the length of the output file once all
+//! earlier blocks have been written. It's only advanced after the output of a chunk has been
+//! flushed and synced to disk, and a resumed scan truncates the output file to the recorded length
+//! before continuing at the recorded block, so rows are neither dropped nor duplicated.
+//!
+//! The checkpoint is bound to the genesis hash of the chain and a hash of the range and the query
+//! parameters, so it can't be resumed against another database or with different filters. The file
+//! is kept when the scan is done, so running the same scan again doesn't scan anything.
+
+#![warn(unused_crate_dependencies)]
+
+use alloy_primitives::{keccak256, B256};
+use eyre::{ensure, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Seek, SeekFrom, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Parses a block range formatted as `<from>:<to>` (inclusive).
+pub fn parse_range(value: &str) -> Result<RangeInclusive<u64>, String> {
+    let (from, to) =
+        value.split_once(':').ok_or_else(|| format!("expected <from>:<to>, got {value}"))?;
+    let from = from.parse::<u64>().map_err(|err| format!("invalid start block: {err}"))?;
+    let to = to.parse::<u64>().map_err(|err| format!("invalid end block: {err}"))?;
+    if from > to {
+        return Err(format!("start block {from} is after end block {to}"))
+    }
+    Ok(from..=to)
+}
+
+/// Splits `range` into consecutive chunks of up to `size` blocks.
+pub fn chunks(range: RangeInclusive<u64>, size: u64) -> impl Iterator<Item = RangeInclusive<u64>> {
+    let (start, end) = range.into_inner();
+    let mut next = Some(start).filter(|start| *start <= end);
+    std::iter::from_fn(move || {
+        let chunk_start = next?;
+        let chunk_end = chunk_start.saturating_add(size.max(1) - 1).min(end);
+        next = chunk_end.checked_add(1).filter(|next| *next <= end);
+        Some(chunk_start..=chunk_end)
+    })
+}
+
+/// The contents of a checkpoint file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct State {
+    /// Hash of the genesis block of the chain.
+    genesis_hash: B256,
+    /// Hash of the range and the query parameters.
+    query_hash: B256,
+    /// The next block to scan, past the end of the range if the scan is done.
+    next_block: u64,
+    /// Length of the output file once all blocks before `next_block` have been written.
+    output_len: u64,
+}
+
+/// The progress of a scan, persisted to a file after every chunk.
+#[derive(Debug)]
+pub struct ScanCheckpoint {
+    path: PathBuf,
+    end: u64,
+    state: State,
+    resumed: bool,
+    output: Option<Arc<File>>,
+}
+
+impl ScanCheckpoint {
+    /// Opens the checkpoint file at `path` for a scan of `range` with the given query parameters,
+    /// or starts a new scan if the file doesn't exist.
+    ///
+    /// Fails if the checkpoint in the file is for another chain, range or query.
+    pub fn open(
+        path: impl Into<PathBuf>,
+        genesis_hash: B256,
+        range: RangeInclusive<u64>,
+        query: &impl Serialize,
+    ) -> eyre::Result<Self> {
+        let path = path.into();
+        let query_hash = keccak256(serde_json::to_vec(&(&range, query))?);
+        let (start, end) = range.into_inner();
+
+        let (state, resumed) = match fs::read(&path) {
+            Ok(contents) => {
+                let state: State = serde_json::from_slice(&contents)
+                    .wrap_err_with(|| format!("invalid checkpoint file {}", path.display()))?;
+                ensure!(
+                    state.genesis_hash == genesis_hash,
+                    "checkpoint {} is for the chain with genesis {}, not {genesis_hash}",
+                    path.display(),
+                    state.genesis_hash
+                );
+                ensure!(
+                    state.query_hash == query_hash,
+                    "checkpoint {} is for another range or query, remove it to start over",
+                    path.display()
+                );
+                (state, true)
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                (State { genesis_hash, query_hash, next_block: start, output_len: 0 }, false)
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self { path, end, state, resumed, output: None })
+    }
+
+    /// Returns whether the scan is resumed from an existing checkpoint.
+    pub const fn is_resumed(&self) -> bool {
+        self.resumed
+    }
+
+    /// Returns the blocks that are left to scan, or `None` if the scan is done.
+    pub fn remaining(&self) -> Option<RangeInclusive<u64>> {
+        (self.state.next_block <= self.end).then_some(self.state.next_block..=self.end)
+    }
+
+    /// Opens the output file of the scan at `path`.
+    ///
+    /// A resumed scan continues the file after the output of the blocks that were checkpointed,
+    /// and discards anything written after the checkpoint. Otherwise the file is created or
+    /// truncated.
+    pub fn open_output(&mut self, path: &Path) -> eyre::Result<CheckpointOutput> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(!self.resumed)
+            .truncate(!self.resumed)
+            .open(path)
+            .wrap_err_with(|| format!("failed to open output file {}", path.display()))?;
+        if self.resumed {
+            let len = file.metadata()?.len();
+            ensure!(
+                len >= self.state.output_len,
+                "output file {} has {len} bytes, but the checkpoint expects at least {}",
+                path.display(),
+                self.state.output_len
+            );
+            file.set_len(self.state.output_len)?;
+            file.seek(SeekFrom::End(0))?;
+        }
+
+        let file = Arc::new(file);
+        self.output = Some(file.clone());
+        Ok(CheckpointOutput(file))
+    }
+
+    /// Records that all blocks before `next_block` have been scanned, and that their output has
+    /// been written to the [`CheckpointOutput`].
+    ///
+    /// Buffered writers of the output must be flushed before, since the output is synced to disk
+    /// and its length is recorded before the checkpoint file is replaced.
+    pub fn advance(&mut self, next_block: u64) -> io::Result<()> {
+        if let Some(output) = &self.output {
+            output.sync_data()?;
+            self.state.output_len = output.metadata()?.len();
+        }
+        self.state.next_block = next_block;
+
+        // The checkpoint is replaced atomically, so an interruption leaves either the old or the
+        // new one
+        let tmp = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec(&self.state)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// The output file of a scan with a [`ScanCheckpoint`], see [`ScanCheckpoint::open_output`].
+#[derive(Debug, Clone)]
+pub struct CheckpointOutput(Arc<File>);
+
+impl Write for CheckpointOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self.0).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self.0).flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_splits_range() {
+        assert_eq!(parse_range("10:20"), Ok(10..=20));
+        assert!(parse_range("20:10").is_err());
+        assert!(parse_range("10").is_err());
+
+        assert_eq!(chunks(10..=20, 5).collect::<Vec<_>>(), [10..=14, 15..=19, 20..=20]);
+        assert_eq!(chunks(10..=10, 5).collect::<Vec<_>>(), [10..=10]);
+        assert_eq!(
+            chunks(u64::MAX - 1..=u64::MAX, 5).collect::<Vec<_>>(),
+            [u64::MAX - 1..=u64::MAX]
+        );
+    }
+
+    #[test]
+    fn resumes_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let (path, output) = (dir.path().join("checkpoint.json"), dir.path().join("out.csv"));
+        let genesis = B256::repeat_byte(1);
+
+        let mut checkpoint = ScanCheckpoint::open(&path, genesis, 0..=99, &"filter").unwrap();
+        assert!(!checkpoint.is_resumed());
+        assert_eq!(checkpoint.remaining(), Some(0..=99));
+        let mut out = checkpoint.open_output(&output).unwrap();
+        out.write_all(b"header\n0-49\n").unwrap();
+        checkpoint.advance(50).unwrap();
+        // Interrupted before the next checkpoint
+        out.write_all(b"50-").unwrap();
+        drop(checkpoint);
+
+        let mut checkpoint = ScanCheckpoint::open(&path, genesis, 0..=99, &"filter").unwrap();
+        assert!(checkpoint.is_resumed());
+        assert_eq!(checkpoint.remaining(), Some(50..=99));
+        let mut out = checkpoint.open_output(&output).unwrap();
+        out.write_all(b"50-99\n").unwrap();
+        checkpoint.advance(100).unwrap();
+        assert_eq!(checkpoint.remaining(), None);
+        assert_eq!(fs::read(&output).unwrap(), b"header\n0-49\n50-99\n");
+
+        // Other chains and queries are rejected
+        assert!(ScanCheckpoint::open(&path, B256::ZERO, 0..=99, &"filter").is_err());
+        assert!(ScanCheckpoint::open(&path, genesis, 0..=99, &"other").is_err());
+        assert!(ScanCheckpoint::open(&path, genesis, 0..=98, &"filter").is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Example of listing the transactions of a sender over a range of blocks in the reth database,
+//! from the recovered senders instead of re-executing the blocks.
+//!
+//! Run with
+//!
+//! ```sh
+//! RETH_DATADIR=~/.local/share/reth/mainnet cargo run --release -p example-sender-transactions -- \
+//!     --address 0xd8da6bf26964af9d7eed9e03e53415d37aa96045 --range 18000000:18100000
+//! ```
+//!
+//! The transactions are written as CSV in the order they were included. Since all transactions of
+//! the sender in the range are listed, their nonces must increase by one from each transaction to
+//! the next, so this checks the senders that the sender recovery stage stored. Gaps and repeated
+//! nonces are reported after the list. A gap can also be caused by an EIP-7702 authorization
+//! signed by the sender, which increases its nonce without a transaction.
+//!
+//! Where the senders have been pruned, they are recovered from the signatures of all transactions
+//! in those blocks, which is much slower.
+//!
+//! With `--checkpoint-file`, the progress is recorded after every chunk of blocks, and a search
+//! that was interrupted continues from the last recorded chunk when it's run again, appending to
+//! the `--output` file.
+
+#![warn(unused_crate_dependencies)]
+
+use alloy_consensus::Transaction;
+use alloy_primitives::Address;
+use clap::Parser;
+use example_scan_checkpoint::{chunks, parse_range, ScanCheckpoint};
+use reth_ethereum::{
+    chainspec::{ChainSpec, ChainSpecProvider, EthChainSpec},
+    cli::chainspec::{chain_value_parser, SUPPORTED_CHAINS},
+    node::EthereumNode,
+    primitives::transaction::TxHashRef,
+    provider::{providers::ReadOnlyConfig, TxContext, TxContextReader},
+    storage::PruneCheckpointReader,
+};
+use reth_prune_types::PruneSegment;
+use reth_tracing::{tracing::warn, RethTracer, Tracer};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::RangeInclusive,
+    path::PathBuf,
+    sync::Arc,
+};
+
+/// Number of blocks whose transactions are read between checkpoints.
+const CHUNK_SIZE: u64 = 10_000;
+
+/// Lists the transactions of a sender.
+#[derive(Debug, Parser)]
+struct Args {
+    /// The chain of the database, which is checked against its genesis block.
+    ///
+    /// Detected from the genesis block if not given.
+    #[arg(long, value_name = "CHAIN", value_parser = chain_value_parser)]
+    chain: Option<Arc<ChainSpec>>,
+
+    /// The sender of the transactions.
+    #[arg(long)]
+    address: Address,
+
+    /// The blocks to search, as `<from>:<to>` (inclusive).
+    #[arg(long, value_parser = parse_range)]
+    range: RangeInclusive<u64>,
+
+    /// The file to write the transactions to, instead of stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// A file to record the progress of the search in after every chunk of blocks, so that an
+    /// interrupted search continues where it stopped when it's run again with the same file.
+    ///
+    /// The search must be resumed with the same address and range, and the output file is
+    /// continued instead of overwritten. The transactions are only counted and their nonces only
+    /// checked by the run that found them.
+    #[arg(long, value_name = "PATH", requires = "output")]
+    checkpoint_file: Option<PathBuf>,
+}
+
+fn main() -> eyre::Result<()> {
+    let _guard = RethTracer::new().init()?;
+    let args = Args::parse();
+
+    // The path to data directory, e.g. "~/.local/reth/share/mainnet"
+    let datadir = std::env::var("RETH_DATADIR")?;
+
+    // The chain is detected from the genesis block of the database, unless it's given
+    let chains = match args.chain {
+        Some(chain) => vec![chain],
+        None => SUPPORTED_CHAINS
+            .iter()
+            .map(|chain| chain_value_parser(chain))
+            .collect::<eyre::Result<_>>()?,
+    };
+    let factory = EthereumNode::provider_factory_builder()
+        .open_read_only_matching_genesis(chains, ReadOnlyConfig::from_datadir(datadir))?;
+    let provider = factory.provider()?;
+
+    let (start, end) = args.range.clone().into_inner();
+    let pruned = provider
+        .get_prune_checkpoint(PruneSegment::SenderRecovery)?
+        .and_then(|checkpoint| checkpoint.block_number)
+        .filter(|pruned| *pruned >= start);
+    if let Some(pruned) = pruned {
+        warn!(
+            pruned = pruned.min(end),
+            "Senders have been pruned, recovering them from the signatures of all transactions \
+             up to this block, which is much slower"
+        );
+    }
+
+    let mut checkpoint = args
+        .checkpoint_file
+        .as_deref()
+        .map(|path| {
+            ScanCheckpoint::open(
+                path,
+                factory.chain_spec().genesis_hash(),
+                args.range.clone(),
+                &args.address,
+            )
+        })
+        .transpose()?;
+
+    let out: Box<dyn Write> = match (&args.output, &mut checkpoint) {
+        (Some(path), Some(checkpoint)) => Box::new(checkpoint.open_output(path)?),
+        (Some(path), None) => Box::new(File::create(path)?),
+        (None, _) => Box::new(io::stdout().lock()),
+    };
+    let mut out = BufWriter::new(out);
+    if !checkpoint.as_ref().is_some_and(ScanCheckpoint::is_resumed) {
+        writeln!(out, "block,index,hash,nonce")?;
+    }
+
+    // A resumed search continues after the checkpointed blocks, and may already be done
+    let range = match &checkpoint {
+        Some(checkpoint) => checkpoint.remaining(),
+        None => Some(args.range),
+    };
+
+    let mut transactions = 0;
+    let mut last: Option<(u64, u64)> = None;
+    let mut issues = Vec::new();
+    for chunk in range.into_iter().flat_map(|range| chunks(range, CHUNK_SIZE)) {
+        let chunk_end = *chunk.end();
+        for tx in provider.transactions_with_context(chunk) {
+            let TxContext { block_number: block, tx_index, sender, transaction, .. } = tx?;
+            if sender != args.address {
+                continue
+            }
+
+            let nonce = transaction.nonce();
+            writeln!(out, "{block},{tx_index},{},{nonce}", transaction.tx_hash())?;
+            if let Some((last_block, last_nonce)) = last &&
+                nonce != last_nonce + 1
+            {
+                issues.push(if nonce > last_nonce {
+                    format!(
+                        "nonces {}..{nonce} are missing between blocks {last_block} and {block}",
+                        last_nonce + 1
+                    )
+                } else {
+                    format!("nonce {nonce} in block {block} follows nonce {last_nonce}")
+                });
+            }
+            last = Some((block, nonce));
+            transactions += 1;
+        }
+
+        // The checkpoint only advances past a chunk once its transactions have been written
+        out.flush()?;
+        if let Some(checkpoint) = &mut checkpoint {
+            checkpoint.advance(chunk_end + 1)?;
+        }
+    }
+    out.flush()?;
+
+    eprintln!("Found {transactions} transactions");
+    if !issues.is_empty() {
+        eprintln!("Found {} nonce gaps or repeated nonces:", issues.len());
+        for issue in &issues {
+            eprintln!("  {issue}");
+        }
+    }
+
+    Ok(())
+}
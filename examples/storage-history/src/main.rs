@@ -0,0 +1,217 @@
+//! Example of exporting the history of storage slots of a contract over a range of blocks in the
+//! reth database, from the storage changesets instead of re-executing the blocks.
+//!
+//! Run with
+//!
+//! ```sh
+//! RETH_DATADIR=~/.local/share/reth/mainnet cargo run --release -p example-storage-history -- \
+//!     --address 0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48 --range 18000000:18100000 \
+//!     --slot 0x10d6a54a4754c8869d6886b5f5d7fbfa5b4522237ea5c60d11bc4e7a1ff9390b
+//! ```
+//!
+//! For every slot, the value at the end of the first block of the range is written as CSV,
+//! followed by a row for every block after which the value changed. The rows are ordered by block,
+//! and in the order of the given slots within a block. This is e.g. how the implementation slot of
+//! a proxy can be audited. If the range ends at the tip, the last value is checked against the
+//! current state. The range can't extend past the last block of the storage history index.
+//!
+//! With `--checkpoint-file`, the progress is recorded after every chunk of blocks, and an export
+//! that was interrupted continues from the last recorded chunk when it's run again, appending to
+//! the `--output` file.
+
+#![warn(unused_crate_dependencies)]
+
+use alloy_primitives::{Address, B256};
+use clap::Parser;
+use example_scan_checkpoint::{chunks, parse_range, ScanCheckpoint};
+use eyre::{bail, ensure};
+use reth_ethereum::{
+    chainspec::{ChainSpec, ChainSpecProvider, EthChainSpec},
+    cli::chainspec::{chain_value_parser, SUPPORTED_CHAINS},
+    node::EthereumNode,
+    provider::{
+        providers::ReadOnlyConfig, BlockNumReader, HistoricalStateProviderRef,
+        LatestStateProviderRef, ProviderError, StateProvider,
+    },
+    storage::PruneCheckpointReader,
+};
+use reth_prune_types::PruneSegment;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::RangeInclusive,
+    path::PathBuf,
+    sync::Arc,
+};
+
+/// Number of blocks whose changes are read between checkpoints.
+const CHUNK_SIZE: u64 = 100_000;
+
+/// Writes the history of storage slots of an address.
+#[derive(Debug, Parser)]
+struct Args {
+    /// The chain of the database, which is checked against its genesis block.
+    ///
+    /// Detected from the genesis block if not given.
+    #[arg(long, value_name = "CHAIN", value_parser = chain_value_parser)]
+    chain: Option<Arc<ChainSpec>>,
+
+    /// The address of the contract.
+    #[arg(long)]
+    address: Address,
+
+    /// The storage slots to export. Can be repeated.
+    #[arg(long, value_delimiter = ',', required = true)]
+    slot: Vec<B256>,
+
+    /// The blocks to export, as `<from>:<to>` (inclusive).
+    #[arg(long, value_parser = parse_range)]
+    range: RangeInclusive<u64>,
+
+    /// The file to write the rows to, instead of stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// A file to record the progress of the export in after every chunk of blocks, so that an
+    /// interrupted export continues where it stopped when it's run again with the same file.
+    ///
+    /// The export must be resumed with the same address, slots and range, and the output file is
+    /// continued instead of overwritten.
+    #[arg(long, value_name = "PATH", requires = "output")]
+    checkpoint_file: Option<PathBuf>,
+}
+
+fn main() -> eyre::Result<()> {
+    let args = Args::parse();
+
+    // The path to data directory, e.g. "~/.local/reth/share/mainnet"
+    let datadir = std::env::var("RETH_DATADIR")?;
+
+    // The chain is detected from the genesis block of the database, unless it's given
+    let chains = match args.chain {
+        Some(chain) => vec![chain],
+        None => SUPPORTED_CHAINS
+            .iter()
+            .map(|chain| chain_value_parser(chain))
+            .collect::<eyre::Result<_>>()?,
+    };
+    let factory = EthereumNode::provider_factory_builder()
+        .open_read_only_matching_genesis(chains, ReadOnlyConfig::from_datadir(datadir))?;
+    let provider = factory.provider()?;
+
+    let mut checkpoint = args
+        .checkpoint_file
+        .as_deref()
+        .map(|path| {
+            ScanCheckpoint::open(
+                path,
+                factory.chain_spec().genesis_hash(),
+                args.range.clone(),
+                &(args.address, &args.slot),
+            )
+        })
+        .transpose()?;
+
+    let out: Box<dyn Write> = match (&args.output, &mut checkpoint) {
+        (Some(path), Some(checkpoint)) => Box::new(checkpoint.open_output(path)?),
+        (Some(path), None) => Box::new(File::create(path)?),
+        (None, _) => Box::new(io::stdout().lock()),
+    };
+    let mut out = BufWriter::new(out);
+    if !checkpoint.as_ref().is_some_and(ScanCheckpoint::is_resumed) {
+        writeln!(out, "slot,block,value")?;
+    }
+
+    let (start, end) = args.range.clone().into_inner();
+    let pruned = provider
+        .get_prune_checkpoint(PruneSegment::StorageHistory)?
+        .and_then(|checkpoint| checkpoint.block_number);
+
+    // The last values are only known to be current if no later block changed them
+    let latest =
+        (end == provider.best_block_number()?).then(|| LatestStateProviderRef::new(&provider));
+
+    // A resumed export continues after the checkpointed blocks, and may already be done
+    let range = match &checkpoint {
+        Some(checkpoint) => checkpoint.remaining(),
+        None => Some(args.range),
+    };
+
+    for chunk in range.into_iter().flat_map(|range| chunks(range, CHUNK_SIZE)) {
+        // The state at the start of the chunk, which is the end of the block before it
+        let (chunk_start, chunk_end) = chunk.into_inner();
+        let mut state = HistoricalStateProviderRef::new(&provider, chunk_start);
+        if let Some(pruned) = pruned {
+            state = state.with_lowest_available_storage_history_block_number(pruned + 1);
+        }
+
+        let history = args
+            .slot
+            .iter()
+            .map(|slot| Ok(state.storage(args.address, *slot)?.unwrap_or_default()))
+            .collect::<Result<Vec<_>, ProviderError>>()
+            .and_then(|initial| {
+                Ok((initial, state.storage_changes(args.address, &args.slot, chunk_end)?))
+            });
+        let (initial, changes) = match history {
+            Ok(history) => history,
+            Err(ProviderError::InsufficientChangesets { available, .. }) => bail!(
+                "the range ends at block {end}, but the storage history is only indexed up to \
+                 block {}",
+                available.end()
+            ),
+            Err(ProviderError::StateAtBlockPruned(_)) => {
+                bail!("the storage history at block {chunk_start} has been pruned")
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        // The values at the start of a later chunk were already written by an earlier one
+        let mut rows = Vec::new();
+        let mut values = initial;
+        for (index, (last, changes)) in values.iter_mut().zip(changes).enumerate() {
+            let mut changes = changes.into_iter().peekable();
+            if chunk_start == start {
+                // A change in the first block is part of the first row
+                if let Some((_, value)) = changes.next_if(|(block, _)| *block == start) {
+                    *last = value;
+                }
+                rows.push((start, index, *last));
+            }
+
+            for (block, value) in changes {
+                // A slot may be written with the value it already had
+                if value != *last {
+                    rows.push((block, index, value));
+                    *last = value;
+                }
+            }
+        }
+
+        rows.sort_by_key(|(block, index, _)| (*block, *index));
+        for (block, index, value) in rows {
+            writeln!(out, "{},{block},{value}", args.slot[index])?;
+        }
+
+        if chunk_end == end &&
+            let Some(latest) = &latest
+        {
+            for (slot, last) in args.slot.iter().zip(&values) {
+                let current = latest.storage(args.address, *slot)?.unwrap_or_default();
+                ensure!(
+                    current == *last,
+                    "reconstructed value {last} of slot {slot} doesn't match the current value \
+                     {current}"
+                );
+            }
+        }
+
+        // The checkpoint only advances past a chunk once its rows have been written
+        out.flush()?;
+        if let Some(checkpoint) = &mut checkpoint {
+            checkpoint.advance(chunk_end + 1)?;
+        }
+    }
+
+    Ok(())
+}
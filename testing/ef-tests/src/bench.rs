@@ -0,0 +1,189 @@
+//! Benchmark mode, using heavy test cases as an EVM performance benchmark.
+//!
+//! Enabled by setting [`BENCH_ENV`] to the path the JSON report should be written to. In this
+//! mode, only cases using at least [`BENCH_GAS_THRESHOLD_ENV`] gas are run. Each of them is
+//! validated once and then executed [`BENCH_ITERATIONS_ENV`] more times, and only the latter are
+//! timed.
+//!
+//! Cases run one after the other in this mode, so that their timings aren't skewed by other cases
+//! running at the same time. The test suites are run from separate test threads, so the tests
+//! should be run with `--test-threads=1` as well.
+
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    sync::{Mutex, PoisonError},
+    time::Duration,
+};
+
+/// Environment variable that enables the benchmark mode, writing the report to the given path.
+pub const BENCH_ENV: &str = "EF_TESTS_BENCH";
+
+/// Environment variable that sets the minimum total gas used by a case for it to be benchmarked.
+pub const BENCH_GAS_THRESHOLD_ENV: &str = "EF_TESTS_BENCH_GAS_THRESHOLD";
+
+/// Environment variable that sets how many timed iterations are run per case.
+pub const BENCH_ITERATIONS_ENV: &str = "EF_TESTS_BENCH_ITERATIONS";
+
+/// Default for [`BENCH_GAS_THRESHOLD_ENV`].
+pub const DEFAULT_BENCH_GAS_THRESHOLD: u64 = 10_000_000;
+
+/// Default for [`BENCH_ITERATIONS_ENV`].
+pub const DEFAULT_BENCH_ITERATIONS: usize = 5;
+
+/// The results of all cases benchmarked in this process.
+static RESULTS: Mutex<Vec<CaseBench>> = Mutex::new(Vec::new());
+
+/// Configuration of the benchmark mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// The path the report is written to.
+    pub output: PathBuf,
+    /// The minimum total gas used by a case for it to be benchmarked.
+    pub gas_threshold: u64,
+    /// The number of timed iterations per case.
+    pub iterations: usize,
+}
+
+impl BenchConfig {
+    /// Returns the benchmark configuration from the environment, or `None` if the benchmark mode
+    /// is disabled.
+    pub fn from_env() -> Option<Self> {
+        let output = std::env::var_os(BENCH_ENV)?.into();
+        let parse = |var| std::env::var(var).ok().and_then(|value| value.parse().ok());
+        Some(Self {
+            output,
+            gas_threshold: parse(BENCH_GAS_THRESHOLD_ENV).unwrap_or(DEFAULT_BENCH_GAS_THRESHOLD),
+            iterations: parse(BENCH_ITERATIONS_ENV).unwrap_or(DEFAULT_BENCH_ITERATIONS).max(1),
+        })
+    }
+}
+
+/// The benchmark results of a single case.
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseBench {
+    /// The name of the case.
+    pub name: String,
+    /// The fork the case runs on.
+    pub fork: String,
+    /// The total gas used by all blocks of the case.
+    pub gas: u64,
+    /// The number of timed iterations.
+    pub iterations: usize,
+    /// The mean execution time in nanoseconds.
+    pub mean_ns: u128,
+    /// The median execution time in nanoseconds.
+    pub median_ns: u128,
+    /// Throughput in million gas per second, based on the median execution time.
+    pub mgas_per_sec: f64,
+}
+
+impl CaseBench {
+    /// Summarizes the execution times of a case.
+    pub fn new(name: String, fork: String, gas: u64, mut timings: Vec<Duration>) -> Self {
+        timings.sort_unstable();
+        let iterations = timings.len();
+        let mean_ns = timings.iter().map(Duration::as_nanos).sum::<u128>() / iterations as u128;
+        let median_ns = timings[iterations / 2].as_nanos();
+        Self {
+            name,
+            fork,
+            gas,
+            iterations,
+            mean_ns,
+            median_ns,
+            mgas_per_sec: mgas_per_sec(gas, median_ns),
+        }
+    }
+}
+
+/// The aggregated benchmark results of all cases of a fork.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ForkBench {
+    /// The number of benchmarked cases.
+    pub cases: usize,
+    /// The total gas used by all cases.
+    pub gas: u64,
+    /// The sum of the median execution times of all cases in nanoseconds.
+    pub median_ns: u128,
+    /// Throughput in million gas per second, based on the median execution times.
+    pub mgas_per_sec: f64,
+}
+
+/// The benchmark report.
+#[derive(Debug, Serialize)]
+struct Report<'a> {
+    cases: &'a [CaseBench],
+    forks: BTreeMap<&'a str, ForkBench>,
+}
+
+/// Returns `true` if the benchmark mode is enabled.
+pub(crate) fn enabled() -> bool {
+    std::env::var_os(BENCH_ENV).is_some()
+}
+
+/// Records the benchmark results of a case.
+pub(crate) fn record(case: CaseBench) {
+    RESULTS.lock().unwrap_or_else(PoisonError::into_inner).push(case);
+}
+
+/// Writes all benchmark results recorded so far to the report, if the benchmark mode is enabled.
+pub(crate) fn write_report() {
+    let Some(config) = BenchConfig::from_env() else { return };
+
+    let mut cases = RESULTS.lock().unwrap_or_else(PoisonError::into_inner).clone();
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+    let report = Report { forks: fork_summary(&cases), cases: &cases };
+
+    let result = serde_json::to_vec_pretty(&report)
+        .map_err(std::io::Error::from)
+        .and_then(|json| fs::write(&config.output, json));
+    if let Err(err) = result {
+        eprintln!("Failed to write benchmark report to {}: {err}", config.output.display());
+    }
+}
+
+/// Aggregates the results of the given cases per fork.
+fn fork_summary(cases: &[CaseBench]) -> BTreeMap<&str, ForkBench> {
+    let mut forks = BTreeMap::<_, ForkBench>::new();
+    for case in cases {
+        let fork = forks.entry(case.fork.as_str()).or_default();
+        fork.cases += 1;
+        fork.gas += case.gas;
+        fork.median_ns += case.median_ns;
+    }
+    for fork in forks.values_mut() {
+        fork.mgas_per_sec = mgas_per_sec(fork.gas, fork.median_ns);
+    }
+    forks
+}
+
+/// Returns the throughput in million gas per second.
+fn mgas_per_sec(gas: u64, ns: u128) -> f64 {
+    if ns == 0 {
+        return 0.0
+    }
+    gas as f64 / 1_000_000.0 / (ns as f64 / 1_000_000_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarize() {
+        let timings = [3, 1, 2].map(Duration::from_millis).to_vec();
+        let case = CaseBench::new("a".to_string(), "Cancun".to_string(), 10_000_000, timings);
+        assert_eq!(case.mean_ns, 2_000_000);
+        assert_eq!(case.median_ns, 2_000_000);
+        assert_eq!(case.mgas_per_sec, 5_000.0);
+
+        let forks = fork_summary(&[case.clone(), case]);
+        assert_eq!(
+            forks["Cancun"],
+            ForkBench { cases: 2, gas: 20_000_000, median_ns: 4_000_000, mgas_per_sec: 5_000.0 }
+        );
+    }
+}
@@ -1,10 +1,14 @@
 //! Test case definitions
 
-use crate::result::{CaseResult, Error};
+use crate::{
+    bench,
+    result::{CaseResult, Error, SkipReason},
+};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::{
     fmt::Debug,
     path::{Path, PathBuf},
+    time::Instant,
 };
 
 /// A single test case, capable of loading a JSON description of itself and running it.
@@ -16,13 +20,41 @@ pub trait Case: Debug + Sync + Sized {
         "no description".to_string()
     }
 
+    /// Sets up the state that is shared by all tests, like caches, before the first test is
+    /// loaded.
+    ///
+    /// This is called before every suite and every test that runs on its own, so it is expected
+    /// to do the setup only once, e.g. with a [`OnceLock`](std::sync::OnceLock).
+    fn before_all() {}
+
     /// Load the test from the given file path.
     ///
     /// The file can be assumed to be a valid EF test case as described on <https://ethereum-tests.readthedocs.io/>.
     fn load(path: &Path) -> Result<Self, Error>;
 
+    /// Why the test at the given path is skipped regardless of its content, if it's skipped.
+    ///
+    /// This is known without loading the test, so that the test can be reported as ignored
+    /// before it runs.
+    fn skip_reason(_path: &Path) -> Option<SkipReason> {
+        None
+    }
+
     /// Run the test.
     fn run(&self) -> Result<(), Error>;
+
+    /// The inner tests of this test that are skipped while the rest of it runs, and why.
+    fn skipped_cases(&self) -> Vec<(String, SkipReason)> {
+        Vec::new()
+    }
+
+    /// The names of the inner tests of this test that run, which are reported one by one.
+    ///
+    /// The inner tests that fail are expected to be named by an [`Error::Failures`] returned from
+    /// [`Self::run`].
+    fn inner_cases(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 /// A container for multiple test cases.
@@ -34,10 +66,19 @@ pub struct Cases<T> {
 
 impl<T: Case> Cases<T> {
     /// Run the contained test cases.
+    ///
+    /// The test cases run in parallel, unless they're benchmarked.
     pub fn run(&self) -> Vec<CaseResult> {
-        self.test_cases
-            .par_iter()
-            .map(|(path, case)| CaseResult::new(path, case, case.run()))
-            .collect()
+        if bench::enabled() {
+            return self.test_cases.iter().map(|(path, case)| run_timed(path, case)).collect()
+        }
+        self.test_cases.par_iter().map(|(path, case)| run_timed(path, case)).collect()
     }
 }
+
+/// Runs a single test case, measuring how long it takes.
+pub(crate) fn run_timed<T: Case>(path: &Path, case: &T) -> CaseResult {
+    let start = Instant::now();
+    let result = case.run();
+    CaseResult::new(path, case, result, start.elapsed())
+}
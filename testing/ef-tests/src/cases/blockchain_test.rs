@@ -1,23 +1,34 @@
 //! Test runners for `BlockchainTests` in <https://github.com/ethereum/tests>
 
 use crate::{
-    models::{BlockchainTest, ForkSpec},
-    Case, Error, Suite,
+    bench::{self, BenchConfig, CaseBench},
+    difficulty::{DifficultyValidator, VALIDATE_DIFFICULTY_ENV},
+    models::{BlockchainTest, EngineNewPayload, EnginePayloadStatus, ForkSpec},
+    result::fail_fast,
+    Case, Error, SkipReason, Suite,
 };
 use alloy_rlp::{Decodable, Encodable};
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use reth_chainspec::ChainSpec;
 use reth_consensus::{Consensus, HeaderValidator};
-use reth_db_common::init::{insert_genesis_hashes, insert_genesis_history, insert_genesis_state};
+use reth_db_common::init::{
+    init_genesis_static_file_segments, insert_genesis_hashes, insert_genesis_history,
+    insert_genesis_state,
+};
 use reth_ethereum_consensus::{validate_block_post_execution, EthBeaconConsensus};
+use reth_ethereum_payload_builder::validator::ensure_well_formed_payload;
 use reth_ethereum_primitives::{Block, TransactionSigned};
-use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_evm::{
+    execute::{BlockExecutionOutput, BlockExecutor, Executor},
+    ConfigureEvm,
+};
 use reth_evm_ethereum::EthEvmConfig;
 use reth_primitives_traits::{Block as BlockTrait, RecoveredBlock, SealedBlock};
 use reth_provider::{
-    test_utils::create_test_provider_factory_with_chain_spec, BlockWriter, DatabaseProviderFactory,
-    ExecutionOutcome, HeaderProvider, HistoryWriter, OriginalValuesKnown, StateProofProvider,
-    StateWriter, StaticFileProviderFactory, StaticFileSegment, StaticFileWriter,
+    test_utils::{create_test_provider_factory_with_chain_spec, MockNodeTypesWithDB},
+    BlockWriter, DatabaseProviderFactory, ExecutionOutcome, HeaderProvider, HistoryWriter,
+    OriginalValuesKnown, ProviderFactory, ReceiptProvider, StateProofProvider, StateWriter,
+    StaticFileProviderFactory, StaticFileSegment, StaticFileWriter,
 };
 use reth_revm::{database::StateProviderDatabase, witness::ExecutionWitnessRecord, State};
 use reth_stateless::{
@@ -26,13 +37,27 @@ use reth_stateless::{
 };
 use reth_trie::{HashedPostState, KeccakKeyHasher, StateRoot};
 use reth_trie_db::DatabaseStateRoot;
+use revm::{database::states::bundle_state::BundleRetention, inspector::inspectors::TracerEip3155};
 use std::{
     collections::BTreeMap,
-    fs,
+    fs, io, mem,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Mutex},
+    time::Instant,
 };
 
+/// Environment variable that disables verifying the genesis state root against the pre-state.
+///
+/// The check requires a full trie computation per test, so it can be turned off to speed up runs.
+pub const SKIP_GENESIS_STATE_ROOT_CHECK_ENV: &str = "EF_TESTS_SKIP_GENESIS_STATE_ROOT_CHECK";
+
+/// Environment variable that enables writing EIP-3155 traces of failing tests to the given
+/// directory.
+///
+/// Failing tests are re-executed with a tracer attached, writing one JSON lines file per
+/// transaction.
+pub const TRACE_DIR_ENV: &str = "EF_TESTS_TRACE_DIR";
+
 /// A handler for the blockchain test suite.
 #[derive(Debug)]
 pub struct BlockchainTests {
@@ -59,11 +84,51 @@ impl Suite for BlockchainTests {
 pub struct BlockchainTestCase {
     /// The tests within this test case.
     pub tests: BTreeMap<String, BlockchainTest>,
-    /// Whether to skip this test case.
-    pub skip: bool,
+    /// Why this test case should be skipped, if it should be skipped.
+    pub skip: Option<SkipReason>,
+    /// The tests within this test case that should be skipped, and why.
+    pub skipped_tests: BTreeMap<String, SkipReason>,
 }
 
 impl BlockchainTestCase {
+    /// Marks the tests matching a [`CASE_SKIPS`] entry for the file at the given path, and the
+    /// tests of unsupported forks, as skipped.
+    ///
+    /// In benchmark mode, the tests that don't use enough gas to be benchmarked are skipped as
+    /// well.
+    fn with_skipped_tests(mut self, path: &Path) -> Self {
+        let bench = BenchConfig::from_env();
+        self.skipped_tests = self
+            .tests
+            .iter()
+            .filter_map(|(name, case)| {
+                let reason = should_skip_case(path, name)
+                    .or_else(|| {
+                        Self::excluded_fork(case.network).then_some(SkipReason::UnsupportedFork)
+                    })
+                    .or_else(|| {
+                        let bench = bench.as_ref()?;
+                        (!Self::is_benchmark(case, bench)).then_some(SkipReason::FilteredByUser)
+                    })?;
+                Some((name.clone(), reason))
+            })
+            .collect();
+        self
+    }
+
+    /// Returns `true` if the test is valid and uses enough gas to be benchmarked.
+    fn is_benchmark(case: &BlockchainTest, config: &BenchConfig) -> bool {
+        let gas = case
+            .blocks
+            .iter()
+            .filter_map(|block| block.block_header.as_ref())
+            .map(|header| header.gas_used.saturating_to::<u64>())
+            .sum::<u64>();
+        case.engine_new_payloads.is_none() &&
+            Self::expected_failure(case).is_none() &&
+            gas >= config.gas_threshold
+    }
+
     /// Returns `true` if the fork is not supported.
     const fn excluded_fork(network: ForkSpec) -> bool {
         matches!(
@@ -73,7 +138,8 @@ impl BlockchainTestCase {
                 ForkSpec::ConstantinopleFix |
                 ForkSpec::MergeEOF |
                 ForkSpec::MergeMeterInitCode |
-                ForkSpec::MergePush0
+                ForkSpec::MergePush0 |
+                ForkSpec::Unknown
         )
     }
 
@@ -162,8 +228,35 @@ impl Case for BlockchainTestCase {
                 serde_json::from_str(&s)
                     .map_err(|error| Error::CouldNotDeserialize { path: path.into(), error })?
             },
-            skip: should_skip(path),
-        })
+            skip: Self::skip_reason(path),
+            skipped_tests: BTreeMap::new(),
+        }
+        .with_skipped_tests(path))
+    }
+
+    /// Builds the chain specs of all forks, which are shared by the tests.
+    fn before_all() {
+        ForkSpec::chain_specs();
+    }
+
+    /// In benchmark mode, slow tests are not skipped.
+    fn skip_reason(path: &Path) -> Option<SkipReason> {
+        should_skip(path)
+            .filter(|reason| *reason != SkipReason::SlowTest || BenchConfig::from_env().is_none())
+    }
+
+    fn skipped_cases(&self) -> Vec<(String, SkipReason)> {
+        if self.skip.is_some() {
+            return Vec::new()
+        }
+        self.skipped_tests.iter().map(|(name, reason)| (name.clone(), *reason)).collect()
+    }
+
+    fn inner_cases(&self) -> Vec<String> {
+        if self.skip.is_some() {
+            return Vec::new()
+        }
+        self.tests.keys().filter(|name| !self.skipped_tests.contains_key(*name)).cloned().collect()
     }
 
     /// Runs the test cases for the Ethereum Forks test suite.
@@ -172,18 +265,73 @@ impl Case for BlockchainTestCase {
     /// Returns an error if the test is flagged for skipping or encounters issues during execution.
     fn run(&self) -> Result<(), Error> {
         // If the test is marked for skipping, return a Skipped error immediately.
-        if self.skip {
-            return Err(Error::Skipped);
+        if let Some(reason) = self.skip {
+            return Err(Error::Skipped(reason));
         }
 
-        // Iterate through test cases, filtering by the network type to exclude specific forks.
-        self.tests
-            .iter()
-            .filter(|(_, case)| !Self::excluded_fork(case.network))
-            .par_bridge()
-            .try_for_each(|(name, case)| Self::run_single_case(name, case).map(|_| ()))?;
+        // If every test is skipped individually, e.g. because none of them target a supported
+        // fork, record the whole case as skipped so it still shows up in the totals.
+        let mut skips = self.tests.keys().map(|name| self.skipped_tests.get(name));
+        if let Some(Some(reason)) = skips.next() &&
+            skips.all(|skip| skip.is_some())
+        {
+            return Err(Error::Skipped(*reason));
+        }
 
-        Ok(())
+        // Iterate through test cases, filtering out the tests that are skipped individually, which
+        // includes the tests of unsupported forks.
+        let mut tests =
+            self.tests.iter().filter(|(name, _)| !self.skipped_tests.contains_key(*name));
+        let run_test = |(name, case): (&String, &BlockchainTest)| {
+            let result = match &case.engine_new_payloads {
+                Some(payloads) => run_engine_case(name, case, payloads),
+                None => Self::run_single_case(name, case).map(|_| ()),
+            };
+
+            // Only benchmark tests that passed validation.
+            let result = match BenchConfig::from_env() {
+                Some(config) if result.is_ok() => run_bench_case(name, case, &config),
+                _ => result,
+            };
+
+            // Only trace failing tests, so passing tests aren't slowed down.
+            if result.is_err() &&
+                let Some(dir) = std::env::var_os(TRACE_DIR_ENV) &&
+                let Err(err) = trace_case(name, case, Path::new(&dir))
+            {
+                eprintln!("Failed to trace test case {name}: {err}");
+            }
+
+            result
+        };
+
+        // Unless failing fast, all tests run, so that all failures are reported. Failures are
+        // named, so that they can be reported per test. Benchmarked tests run one after the
+        // other, so that their timings aren't skewed by each other.
+        let sequential = bench::enabled();
+        if fail_fast() {
+            let run_test = |test: (&String, &BlockchainTest)| {
+                run_test(test).map_err(|err| Error::Failures(vec![(test.0.clone(), err)]))
+            };
+            return if sequential {
+                tests.try_for_each(run_test)
+            } else {
+                tests.par_bridge().try_for_each(run_test)
+            }
+        }
+        let failed = |test: (&String, &BlockchainTest)| {
+            run_test(test).err().map(|err| (test.0.clone(), err))
+        };
+        let mut failures: Vec<_> = if sequential {
+            tests.filter_map(failed).collect()
+        } else {
+            tests.par_bridge().filter_map(failed).collect()
+        };
+        if failures.is_empty() {
+            return Ok(())
+        }
+        failures.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        Err(Error::Failures(failures))
     }
 }
 
@@ -204,44 +352,18 @@ impl Case for BlockchainTestCase {
 fn run_case(
     case: &BlockchainTest,
 ) -> Result<Vec<(RecoveredBlock<Block>, ExecutionWitness)>, Error> {
-    // Create a new test database and initialize a provider for the test case.
-    let chain_spec: Arc<ChainSpec> = Arc::new(case.network.into());
-    let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
-    let provider = factory.database_provider_rw().unwrap();
-
-    // Insert initial test state into the provider.
-    let genesis_block = SealedBlock::<Block>::from_sealed_parts(
-        case.genesis_block_header.clone().into(),
-        Default::default(),
-    )
-    .try_recover()
-    .unwrap();
-
-    provider
-        .insert_block(genesis_block.clone())
-        .map_err(|err| Error::block_failed(0, Default::default(), err))?;
-
-    // Increment block number for receipts static file
-    provider
-        .static_file_provider()
-        .latest_writer(StaticFileSegment::Receipts)
-        .and_then(|mut writer| writer.increment_block(0))
-        .map_err(|err| Error::block_failed(0, Default::default(), err))?;
-
-    let genesis_state = case.pre.clone().into_genesis_state();
-    insert_genesis_state(&provider, genesis_state.iter())
-        .map_err(|err| Error::block_failed(0, Default::default(), err))?;
-    insert_genesis_hashes(&provider, genesis_state.iter())
-        .map_err(|err| Error::block_failed(0, Default::default(), err))?;
-    insert_genesis_history(&provider, genesis_state.iter())
-        .map_err(|err| Error::block_failed(0, Default::default(), err))?;
+    let chain_spec = case.chain_spec()?;
+    let setup = GenesisSetup::new(case, chain_spec.clone())?;
+    let provider = &setup.provider;
 
     // Decode blocks
     let blocks = decode_blocks(&case.blocks)?;
 
     let executor_provider = EthEvmConfig::ethereum(chain_spec.clone());
-    let mut parent = genesis_block;
+    let mut parent = setup.genesis_block.clone();
     let mut program_inputs = Vec::new();
+    let mut difficulty_validator = std::env::var_os(VALIDATE_DIFFICULTY_ENV)
+        .map(|_| DifficultyValidator::new(chain_spec.clone(), case.network, parent.header()));
 
     for (block_index, block) in blocks.iter().enumerate() {
         // Note: same as the comment on `decode_blocks` as to why we cannot use block.number
@@ -257,6 +379,13 @@ fn run_case(
             .commit()
             .map_err(|err| Error::block_failed(block_number, Default::default(), err))?;
 
+        if let Some(validator) = &mut difficulty_validator {
+            validator.validate(parent.header(), block.header()).map_err(|err| {
+                program_inputs.push((block.clone(), execution_witness_with_parent(&parent)));
+                Error::block_failed(block_number, program_inputs.clone(), err)
+            })?;
+        }
+
         // Consensus checks before block execution
         pre_execution_checks(chain_spec.clone(), &parent, block).map_err(|err| {
             program_inputs.push((block.clone(), execution_witness_with_parent(&parent)));
@@ -339,6 +468,163 @@ fn run_case(
         parent = block.clone()
     }
 
+    assert_receipts_segment(provider, &blocks)?;
+    assert_post_state(case, provider)?;
+
+    // Now validate using the stateless client if everything else passes
+    validate_stateless(&program_inputs, &chain_spec)?;
+
+    Ok(program_inputs)
+}
+
+/// Validates the executed blocks with the stateless client, using the witnesses recorded while
+/// executing them.
+///
+/// A block that fails fails with its number and the program inputs up to and including it.
+fn validate_stateless(
+    program_inputs: &[(RecoveredBlock<Block>, ExecutionWitness)],
+    chain_spec: &Arc<ChainSpec>,
+) -> Result<(), Error> {
+    for (block_index, (recovered_block, execution_witness)) in program_inputs.iter().enumerate() {
+        // Note: same as the comment on `decode_blocks` as to why we cannot use block.number
+        let block_number = (block_index + 1) as u64;
+        let block_failed = |err: Error| {
+            Error::block_failed(block_number, program_inputs[..=block_index].to_vec(), err)
+        };
+        let block = recovered_block.clone().into_block();
+
+        // Recover the actual public keys from the transaction signatures
+        let public_keys = recover_signers(block.body().transactions()).map_err(|err| {
+            block_failed(Error::Assertion(format!(
+                "failed to recover public keys for stateless validation: {err}"
+            )))
+        })?;
+
+        stateless_validation_with_trie::<StatelessSparseTrie, _, _>(
+            block,
+            public_keys,
+            execution_witness.clone(),
+            chain_spec.clone(),
+            EthEvmConfig::new(chain_spec.clone()),
+        )
+        .map_err(|err| {
+            block_failed(Error::Assertion(format!("stateless validation failed: {err}")))
+        })?;
+    }
+
+    Ok(())
+}
+
+/// The read-write provider used to run a single test.
+type TestProviderRW = <ProviderFactory<MockNodeTypesWithDB> as DatabaseProviderFactory>::ProviderRW;
+
+/// A fresh test database initialized with the genesis block and pre-state of a test.
+struct GenesisSetup {
+    /// The provider the test is run against.
+    provider: TestProviderRW,
+    /// The recovered genesis block.
+    genesis_block: RecoveredBlock<Block>,
+    /// Keeps the temporary database alive for as long as the provider is in use.
+    _factory: ProviderFactory<MockNodeTypesWithDB>,
+}
+
+impl GenesisSetup {
+    /// Creates a new test database and inserts the genesis block and pre-state of the test.
+    fn new(case: &BlockchainTest, chain_spec: Arc<ChainSpec>) -> Result<Self, Error> {
+        // Create a new test database and initialize a provider for the test case.
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
+        let provider = factory.database_provider_rw().unwrap();
+
+        // Insert initial test state into the provider.
+        let genesis_block = case
+            .genesis_block()?
+            .try_recover()
+            .map_err(|err| Error::block_failed(0, Default::default(), err))?;
+
+        provider
+            .insert_block(genesis_block.clone())
+            .map_err(|err| Error::block_failed(0, Default::default(), err))?;
+
+        // Initialize the static file segments the same way the node does on genesis
+        init_genesis_static_file_segments(&provider, 0)
+            .map_err(|err| Error::block_failed(0, Default::default(), err))?;
+
+        let genesis_state = case.pre.clone().into_genesis_state();
+        insert_genesis_state(&provider, genesis_state.iter())
+            .map_err(|err| Error::block_failed(0, Default::default(), err))?;
+        insert_genesis_hashes(&provider, genesis_state.iter())
+            .map_err(|err| Error::block_failed(0, Default::default(), err))?;
+        insert_genesis_history(&provider, genesis_state.iter())
+            .map_err(|err| Error::block_failed(0, Default::default(), err))?;
+
+        // Make sure the pre-state actually hashes to the genesis state root, otherwise any failure
+        // further down would be caused by a broken pre-state rather than by block execution.
+        if std::env::var_os(SKIP_GENESIS_STATE_ROOT_CHECK_ENV).is_none() {
+            let computed_state_root = StateRoot::from_tx(provider.tx_ref())
+                .root()
+                .map_err(|err| Error::block_failed(0, Default::default(), err))?;
+            if computed_state_root != case.genesis_block_header.state_root {
+                return Err(Error::GenesisStateRootMismatch {
+                    expected: case.genesis_block_header.state_root,
+                    got: computed_state_root,
+                });
+            }
+        }
+
+        Ok(Self { provider, genesis_block, _factory: factory })
+    }
+}
+
+/// Checks that the receipts static file segment covers exactly the genesis block and the given
+/// executed blocks, with one receipt per transaction of each block.
+///
+/// This surfaces off-by-one errors in the way receipts are written to static files, which would
+/// otherwise only show up on a real sync.
+fn assert_receipts_segment(
+    provider: &TestProviderRW,
+    blocks: &[RecoveredBlock<Block>],
+) -> Result<(), Error> {
+    let static_file_provider = provider.static_file_provider();
+    let segment = StaticFileSegment::Receipts;
+
+    // The receipts of the last block have not been committed yet
+    static_file_provider.commit()?;
+
+    let expected_block = blocks.len() as u64;
+    let expected_tx = blocks
+        .iter()
+        .map(|block| block.body().transactions.len() as u64)
+        .sum::<u64>()
+        .checked_sub(1);
+    let (highest_block, highest_tx) = (
+        static_file_provider.get_highest_static_file_block(segment),
+        static_file_provider.get_highest_static_file_tx(segment),
+    );
+    if highest_block != Some(expected_block) || highest_tx != expected_tx {
+        return Err(Error::Assertion(format!(
+            "Receipts static file segment covers blocks 0..={highest_block:?} and transactions \
+             0..={highest_tx:?}, expected blocks 0..={expected_block} and transactions \
+             0..={expected_tx:?}"
+        )))
+    }
+
+    for block in blocks {
+        let receipts = provider.receipts_by_block(block.number.into())?.unwrap_or_default();
+        let expected = block.body().transactions.len();
+        if receipts.len() != expected {
+            return Err(Error::Assertion(format!(
+                "Block {} has {} receipts in static files, expected {expected}",
+                block.number,
+                receipts.len()
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates the post-state of the test case against the database, if the test has one.
+fn assert_post_state(case: &BlockchainTest, provider: &TestProviderRW) -> Result<(), Error> {
     match &case.post_state {
         Some(expected_post_state) => {
             // Validate the post-state for the test case.
@@ -360,25 +646,253 @@ fn run_case(
         }
     }
 
-    // Now validate using the stateless client if everything else passes
-    for (recovered_block, execution_witness) in &program_inputs {
-        let block = recovered_block.clone().into_block();
+    Ok(())
+}
 
-        // Recover the actual public keys from the transaction signatures
-        let public_keys = recover_signers(block.body().transactions())
-            .expect("Failed to recover public keys from transaction signatures");
+/// Executes the `engineNewPayloads` section of a `BlockchainTest`, delivering every payload the
+/// way it would arrive through `engine_newPayload` and checking the status it is answered with.
+///
+/// Payloads are validated and executed on top of the canonical tip, valid payloads become the new
+/// tip. Once all payloads are processed, the post-state is validated like for [`run_case`].
+fn run_engine_case(
+    name: &str,
+    case: &BlockchainTest,
+    payloads: &[EngineNewPayload],
+) -> Result<(), Error> {
+    let chain_spec = case.chain_spec()?;
+    let setup = GenesisSetup::new(case, chain_spec.clone())?;
+    let provider = &setup.provider;
 
-        stateless_validation_with_trie::<StatelessSparseTrie, _, _>(
-            block,
-            public_keys,
-            execution_witness.clone(),
-            chain_spec.clone(),
-            EthEvmConfig::new(chain_spec.clone()),
-        )
-        .expect("stateless validation failed");
+    let executor_provider = EthEvmConfig::ethereum(chain_spec.clone());
+    let mut parent = setup.genesis_block.clone();
+
+    for (index, payload) in payloads.iter().enumerate() {
+        let expected = payload.expected_status();
+        let status = new_payload(provider, &chain_spec, &executor_provider, &mut parent, payload)
+            .map_err(|err| {
+            Error::Assertion(format!("{name}: payload {index} failed: {err}"))
+        })?;
+
+        if status != expected {
+            return Err(Error::Assertion(format!(
+                "{name}: payload {index} expected status {expected:?}, got {status:?}"
+            )));
+        }
     }
 
-    Ok(program_inputs)
+    assert_post_state(case, provider)
+}
+
+/// Processes a single engine payload on top of `parent`, returning the status it is answered with.
+///
+/// If the payload is valid, it is written to the database and becomes the new `parent`.
+fn new_payload(
+    provider: &TestProviderRW,
+    chain_spec: &Arc<ChainSpec>,
+    executor_provider: &EthEvmConfig,
+    parent: &mut RecoveredBlock<Block>,
+    payload: &EngineNewPayload,
+) -> Result<EnginePayloadStatus, Error> {
+    let execution_data =
+        payload.execution_data().map_err(|err| Error::Assertion(err.to_string()))?;
+
+    let Ok(block) =
+        ensure_well_formed_payload::<_, TransactionSigned>(chain_spec.as_ref(), execution_data)
+    else {
+        return Ok(EnginePayloadStatus::Invalid);
+    };
+
+    // Only payloads building on the canonical tip can be executed, a payload with an unknown
+    // parent can't be validated yet.
+    if block.parent_hash != parent.hash() {
+        if provider.header(block.parent_hash)?.is_none() {
+            return Ok(EnginePayloadStatus::Syncing);
+        }
+        return Err(Error::Assertion(
+            "payloads building on a non-canonical block are not supported".to_string(),
+        ));
+    }
+
+    let Ok(block) = block.try_recover() else { return Ok(EnginePayloadStatus::Invalid) };
+
+    if pre_execution_checks(chain_spec.clone(), parent, &block).is_err() {
+        return Ok(EnginePayloadStatus::Invalid);
+    }
+
+    let state_provider = provider.latest();
+    let Ok(output) =
+        executor_provider.batch_executor(StateProviderDatabase(&state_provider)).execute(&block)
+    else {
+        return Ok(EnginePayloadStatus::Invalid);
+    };
+
+    if validate_block_post_execution(&block, chain_spec, &output.receipts, &output.requests)
+        .is_err()
+    {
+        return Ok(EnginePayloadStatus::Invalid);
+    }
+
+    let hashed_state = HashedPostState::from_bundle_state::<KeccakKeyHasher>(output.state.state());
+    let (computed_state_root, _) =
+        StateRoot::overlay_root_with_updates(provider.tx_ref(), &hashed_state.clone_into_sorted())
+            .map_err(|err| Error::block_failed(block.number, Default::default(), err))?;
+    if computed_state_root != block.state_root {
+        return Ok(EnginePayloadStatus::Invalid);
+    }
+
+    // The payload is valid, make it the new canonical tip.
+    provider.insert_block(block.clone())?;
+    provider.static_file_provider().commit()?;
+    provider
+        .write_state(&ExecutionOutcome::single(block.number, output), OriginalValuesKnown::Yes)?;
+    provider.write_hashed_state(&hashed_state.into_sorted())?;
+    provider.update_history_indices(block.number..=block.number)?;
+
+    *parent = block;
+
+    Ok(EnginePayloadStatus::Valid)
+}
+
+/// Executes the blocks of a `BlockchainTest` the configured number of times, recording how long
+/// the execution took.
+///
+/// Setting up the database and validating the results is not part of the timings, the test is
+/// expected to have been validated by [`BlockchainTestCase::run_single_case`] before.
+fn run_bench_case(name: &str, case: &BlockchainTest, config: &BenchConfig) -> Result<(), Error> {
+    let chain_spec = case.chain_spec()?;
+    let evm_config = EthEvmConfig::ethereum(chain_spec.clone());
+    let blocks = decode_blocks(&case.blocks)?;
+
+    let mut timings = Vec::with_capacity(config.iterations);
+    for _ in 0..config.iterations {
+        let setup = GenesisSetup::new(case, chain_spec.clone())?;
+        let provider = &setup.provider;
+
+        // Insert the blocks up front, so their headers are available to `BLOCKHASH`.
+        for block in &blocks {
+            provider.insert_block(block.clone())?;
+        }
+        provider.static_file_provider().commit()?;
+
+        // State changes of earlier blocks are kept in memory by the executor.
+        let state_provider = provider.latest();
+        let mut executor = evm_config.batch_executor(StateProviderDatabase(&state_provider));
+
+        let start = Instant::now();
+        for block in &blocks {
+            executor
+                .execute_one(block)
+                .map_err(|err| Error::block_failed(block.number, Default::default(), err))?;
+        }
+        timings.push(start.elapsed());
+    }
+
+    let gas = blocks.iter().map(|block| block.gas_used).sum();
+    bench::record(CaseBench::new(name.to_string(), format!("{:?}", case.network), gas, timings));
+
+    Ok(())
+}
+
+/// A [`Write`](io::Write) implementation appending to a shared buffer, so the trace of a
+/// transaction can be taken out while the tracer is owned by the EVM.
+#[derive(Debug, Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    /// Takes the contents of the buffer, leaving it empty.
+    fn take(&self) -> Vec<u8> {
+        mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Re-executes the blocks of a `BlockchainTest` with an EIP-3155 tracer attached, writing the
+/// trace of every transaction to `<dir>/<test name>/block_<number>_tx_<index>.jsonl`.
+///
+/// Execution stops at the first block that can't be decoded or executed, since the state of any
+/// later block would be meaningless.
+fn trace_case(name: &str, case: &BlockchainTest, dir: &Path) -> Result<(), Error> {
+    let chain_spec = case.chain_spec()?;
+    let setup = GenesisSetup::new(case, chain_spec.clone())?;
+    let provider = &setup.provider;
+    let evm_config = EthEvmConfig::ethereum(chain_spec);
+
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || "-_.".contains(c) { c } else { '_' })
+        .collect();
+    let dir = dir.join(name);
+    fs::create_dir_all(&dir).map_err(|error| Error::Io { path: dir.clone(), error })?;
+
+    for (block_index, block) in case.blocks.iter().enumerate() {
+        let block_number = (block_index + 1) as u64;
+        let Some(block) = SealedBlock::<Block>::decode(&mut block.rlp.as_ref())
+            .ok()
+            .and_then(|block| block.try_recover().ok())
+        else {
+            break
+        };
+
+        provider.insert_block(block.clone())?;
+        provider.static_file_provider().commit()?;
+
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(provider.latest()))
+            .with_bundle_update()
+            .build();
+        let buffer = SharedBuffer::default();
+        let evm_env = evm_config
+            .evm_env(block.header())
+            .map_err(|err| Error::block_failed(block_number, Default::default(), err))?;
+        let evm = evm_config.evm_with_env_and_inspector(
+            &mut db,
+            evm_env,
+            TracerEip3155::new(Box::new(buffer.clone())),
+        );
+        let ctx = evm_config
+            .context_for_block(block.sealed_block())
+            .map_err(|err| Error::block_failed(block_number, Default::default(), err))?;
+        let mut executor = evm_config.create_executor(evm, ctx);
+
+        let mut result = executor.apply_pre_execution_changes();
+        // System calls are not part of any transaction trace.
+        buffer.take();
+
+        for (tx_index, tx) in block.transactions_recovered().enumerate() {
+            if result.is_err() {
+                break
+            }
+            result = executor.execute_transaction(tx).map(drop);
+
+            let path = dir.join(format!("block_{block_number}_tx_{tx_index}.jsonl"));
+            fs::write(&path, buffer.take()).map_err(|error| Error::Io { path, error })?;
+        }
+
+        let Ok((_, result)) = result.and_then(|()| executor.finish()) else { break };
+        db.merge_transitions(BundleRetention::Reverts);
+        let output = BlockExecutionOutput { result, state: db.take_bundle() };
+
+        let hashed_state =
+            HashedPostState::from_bundle_state::<KeccakKeyHasher>(output.state.state());
+        provider.write_state(
+            &ExecutionOutcome::single(block.number, output),
+            OriginalValuesKnown::Yes,
+        )?;
+        provider.write_hashed_state(&hashed_state.into_sorted())?;
+        provider.update_history_indices(block.number..=block.number)?;
+    }
+
+    Ok(())
 }
 
 fn decode_blocks(
@@ -426,7 +940,7 @@ fn pre_execution_checks(
 }
 
 /// Recover public keys from transaction signatures.
-fn recover_signers<'a, I>(txs: I) -> Result<Vec<UncompressedPublicKey>, Box<dyn std::error::Error>>
+fn recover_signers<'a, I>(txs: I) -> Result<Vec<UncompressedPublicKey>, String>
 where
     I: IntoIterator<Item = &'a TransactionSigned>,
 {
@@ -440,70 +954,97 @@ where
                         keys.to_encoded_point(false).as_bytes().try_into().unwrap(),
                     )
                 })
-                .map_err(|e| format!("failed to recover signature for tx #{i}: {e}").into())
+                .map_err(|e| format!("failed to recover signature for tx #{i}: {e}"))
         })
         .collect::<Result<Vec<UncompressedPublicKey>, _>>()
 }
 
-/// Returns whether the test at the given path should be skipped.
+/// Returns the reason the test at the given path should be skipped, if any.
 ///
 /// Some tests are edge cases that cannot happen on mainnet, while others are skipped for
 /// convenience (e.g. they take a long time to run) or are temporarily disabled.
 ///
 /// The reason should be documented in a comment above the file name(s).
-pub fn should_skip(path: &Path) -> bool {
+pub fn should_skip(path: &Path) -> Option<SkipReason> {
     let path_str = path.to_str().expect("Path is not valid UTF-8");
     let name = path.file_name().unwrap().to_str().unwrap();
-    matches!(
-        name,
-        // funky test with `bigint 0x00` value in json :) not possible to happen on mainnet and require
-        // custom json parser. https://github.com/ethereum/tests/issues/971
-        | "ValueOverflow.json"
-        | "ValueOverflowParis.json"
+
+    // Ignore outdated EOF tests that haven't been updated for Cancun yet.
+    if path_contains(path_str, &["EIPTests", "stEOF"]) {
+        return Some(SkipReason::UnsupportedFork)
+    }
+
+    match name {
+        // funky test with `bigint 0x00` value in json :) not possible to happen on mainnet and
+        // require custom json parser. https://github.com/ethereum/tests/issues/971
+        "ValueOverflow.json" | "ValueOverflowParis.json" => Some(SkipReason::UpstreamBug),
 
         // txbyte is of type 02 and we don't parse tx bytes for this test to fail.
-        | "typeTwoBerlin.json"
+        "typeTwoBerlin.json" => Some(SkipReason::UpstreamBug),
 
         // Test checks if nonce overflows. We are handling this correctly but we are not parsing
         // exception in testsuite. There are more nonce overflow tests that are internal
         // call/create, and those tests are passing and are enabled.
-        | "CreateTransactionHighNonce.json"
+        "CreateTransactionHighNonce.json" => Some(SkipReason::UpstreamBug),
 
-        // Test check if gas price overflows, we handle this correctly but does not match tests specific
-        // exception.
-        | "HighGasPrice.json"
-        | "HighGasPriceParis.json"
+        // Test check if gas price overflows, we handle this correctly but does not match tests
+        // specific exception.
+        "HighGasPrice.json" | "HighGasPriceParis.json" => Some(SkipReason::UpstreamBug),
 
         // Skip test where basefee/accesslist/difficulty is present but it shouldn't be supported in
         // London/Berlin/TheMerge. https://github.com/ethereum/tests/blob/5b7e1ab3ffaf026d99d20b17bb30f533a2c80c8b/GeneralStateTests/stExample/eip1559.json#L130
         // It is expected to not execute these tests.
-        | "accessListExample.json"
-        | "basefeeExample.json"
-        | "eip1559.json"
-        | "mergeTest.json"
-
-        // These tests are passing, but they take a lot of time to execute so we are going to skip them.
-        | "loopExp.json"
-        | "Call50000_sha256.json"
-        | "static_Call50000_sha256.json"
-        | "loopMul.json"
-        | "CALLBlake2f_MaxRounds.json"
-        | "shiftCombinations.json"
+        "accessListExample.json" | "basefeeExample.json" | "eip1559.json" | "mergeTest.json" => {
+            Some(SkipReason::UnsupportedFork)
+        }
+
+        // These tests are passing, but they take a lot of time to execute so we are going to skip
+        // them.
+        "loopExp.json" |
+        "Call50000_sha256.json" |
+        "static_Call50000_sha256.json" |
+        "loopMul.json" |
+        "CALLBlake2f_MaxRounds.json" |
+        "shiftCombinations.json" => Some(SkipReason::SlowTest),
 
         // Skipped by revm as well: <https://github.com/bluealloy/revm/blob/be92e1db21f1c47b34c5a58cfbf019f6b97d7e4b/bins/revme/src/cmd/statetest/runner.rs#L115-L125>
-        | "RevertInCreateInInit_Paris.json"
-        | "RevertInCreateInInit.json"
-        | "dynamicAccountOverwriteEmpty.json"
-        | "dynamicAccountOverwriteEmpty_Paris.json"
-        | "RevertInCreateInInitCreate2Paris.json"
-        | "create2collisionStorage.json"
-        | "RevertInCreateInInitCreate2.json"
-        | "create2collisionStorageParis.json"
-        | "InitCollision.json"
-        | "InitCollisionParis.json"
-    )
-    // Ignore outdated EOF tests that haven't been updated for Cancun yet.
-    || path_contains(path_str, &["EIPTests", "stEOF"])
+        "RevertInCreateInInit_Paris.json" |
+        "RevertInCreateInInit.json" |
+        "dynamicAccountOverwriteEmpty.json" |
+        "dynamicAccountOverwriteEmpty_Paris.json" |
+        "RevertInCreateInInitCreate2Paris.json" |
+        "create2collisionStorage.json" |
+        "RevertInCreateInInitCreate2.json" |
+        "create2collisionStorageParis.json" |
+        "InitCollision.json" |
+        "InitCollisionParis.json" => Some(SkipReason::UpstreamBug),
+
+        _ => None,
+    }
+}
+
+/// Skip entries for individual tests within a file, in the form `file.json::TestNameSubstring`.
+///
+/// Unlike the file level skips in [`should_skip`], these only skip the tests of the file whose
+/// name contains the given substring, e.g. a single fork variant. The reason should be documented
+/// in a comment above the entry.
+pub const CASE_SKIPS: &[(&str, SkipReason)] = &[];
+
+/// Returns the reason the test with the given name in the file at the given path should be
+/// skipped, if any.
+///
+/// See [`CASE_SKIPS`].
+pub fn should_skip_case(path: &Path, name: &str) -> Option<SkipReason> {
+    let file_name = path.file_name()?.to_str()?;
+    find_case_skip(CASE_SKIPS, file_name, name)
+}
+
+/// Returns the reason of the first entry in `skips` matching the given file and test name.
+fn find_case_skip(skips: &[(&str, SkipReason)], file_name: &str, name: &str) -> Option<SkipReason> {
+    skips.iter().find_map(|(entry, reason)| {
+        let (file, case) = entry.split_once("::")?;
+        (file == file_name && name.contains(case)).then_some(*reason)
+    })
 }
 
 /// `str::contains` but for a path. Takes into account the OS path separator (`/` or `\`).
@@ -517,3 +1058,215 @@ fn execution_witness_with_parent(parent: &RecoveredBlock<Block>) -> ExecutionWit
     parent.header().encode(&mut serialized_header);
     ExecutionWitness { headers: vec![serialized_header.into()], ..Default::default() }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, Signed, TxLegacy, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
+    use alloy_eips::eip1559::BaseFeeParams;
+    use alloy_primitives::{Signature, TxKind, B256, U256};
+    use alloy_rpc_types_engine::ExecutionPayloadV1;
+    use reth_ethereum_primitives::BlockBody;
+    use reth_primitives_traits::SealedHeader;
+
+    /// Returns a block with a single legacy transaction with the given signature.
+    fn signed_block(signature: Signature) -> Block {
+        let tx = TxLegacy {
+            gas_limit: 21_000,
+            to: TxKind::Call(Default::default()),
+            ..Default::default()
+        };
+        let tx = TransactionSigned::from(Signed::new_unhashed(tx, signature));
+        Block {
+            header: Header::default(),
+            body: BlockBody { transactions: vec![tx], ..Default::default() },
+        }
+    }
+
+    /// Returns the RLP encoding of a block with a single legacy transaction with the given
+    /// signature.
+    fn encode_block(signature: Signature) -> Vec<u8> {
+        alloy_rlp::encode(signed_block(signature))
+    }
+
+    fn fixture_block(rlp: Vec<u8>) -> crate::models::Block {
+        crate::models::Block { rlp: rlp.into(), ..Default::default() }
+    }
+
+    /// Returns the genesis header of a Paris test without any pre-state.
+    fn genesis_header() -> crate::models::Header {
+        let mut genesis = crate::models::Header {
+            gas_limit: U256::from(30_000_000),
+            base_fee_per_gas: Some(U256::from(1_000_000_000)),
+            state_root: EMPTY_ROOT_HASH,
+            receipt_trie: EMPTY_ROOT_HASH,
+            transactions_trie: EMPTY_ROOT_HASH,
+            uncle_hash: EMPTY_OMMER_ROOT_HASH,
+            ..Default::default()
+        };
+        genesis.hash = SealedHeader::from(genesis.clone()).header().hash_slow();
+        genesis
+    }
+
+    /// Returns an empty block on top of `parent` with the given state root, and the
+    /// `engine_newPayloadV1` call delivering it.
+    ///
+    /// The payload is expected to be invalid if `validation_error` is set.
+    fn empty_payload(
+        parent: &Header,
+        state_root: B256,
+        validation_error: Option<&str>,
+    ) -> (Header, EngineNewPayload) {
+        let header = Header {
+            parent_hash: parent.hash_slow(),
+            number: parent.number + 1,
+            timestamp: parent.timestamp + 12,
+            gas_limit: parent.gas_limit,
+            base_fee_per_gas: parent.next_block_base_fee(BaseFeeParams::ethereum()),
+            state_root,
+            receipts_root: EMPTY_ROOT_HASH,
+            transactions_root: EMPTY_ROOT_HASH,
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            ..Default::default()
+        };
+        let block = Block { header: header.clone(), body: Default::default() };
+        let params = serde_json::to_value(ExecutionPayloadV1::from_block_slow(&block)).unwrap();
+        let payload = EngineNewPayload {
+            params: vec![params],
+            new_payload_version: "1".to_string(),
+            forkchoice_updated_version: None,
+            validation_error: validation_error.map(str::to_string),
+            expected_status: None,
+        };
+        (header, payload)
+    }
+
+    /// Returns an engine test with a valid payload, followed by a payload with a wrong state root
+    /// that's expected to be valid if `mislabeled` is set.
+    fn engine_test(mislabeled: bool) -> BlockchainTest {
+        let genesis = genesis_header();
+        let parent = SealedHeader::from(genesis.clone()).into_header();
+        let (valid, valid_payload) = empty_payload(&parent, EMPTY_ROOT_HASH, None);
+        let validation_error = (!mislabeled).then_some("state root mismatch");
+        let (_, invalid_payload) = empty_payload(&valid, B256::with_last_byte(1), validation_error);
+
+        BlockchainTest {
+            genesis_block_header: genesis,
+            genesis_rlp: None,
+            blocks: Vec::new(),
+            post_state: None,
+            pre: Default::default(),
+            lastblockhash: valid.hash_slow(),
+            network: ForkSpec::Merge,
+            seal_engine: Default::default(),
+            engine_new_payloads: Some(vec![valid_payload, invalid_payload]),
+            config: None,
+        }
+    }
+
+    #[test]
+    fn engine_payloads() {
+        let test = engine_test(false);
+        run_engine_case("engine", &test, test.engine_new_payloads.as_ref().unwrap()).unwrap();
+
+        let test = engine_test(true);
+        let Err(Error::Assertion(message)) =
+            run_engine_case("engine", &test, test.engine_new_payloads.as_ref().unwrap())
+        else {
+            panic!("mislabeled payload passed")
+        };
+        assert_eq!(message, "engine: payload 1 expected status Valid, got Invalid");
+    }
+
+    #[test]
+    fn unsupported_forks_are_skipped() {
+        let mut unsupported = engine_test(false);
+        unsupported.network = ForkSpec::Constantinople;
+        let tests = BTreeMap::from([
+            ("supported".to_string(), engine_test(false)),
+            ("unsupported".to_string(), unsupported),
+        ]);
+        let case = BlockchainTestCase { tests, skip: None, skipped_tests: BTreeMap::new() }
+            .with_skipped_tests(Path::new("case.json"));
+        assert_eq!(
+            case.skipped_cases(),
+            vec![("unsupported".to_string(), SkipReason::UnsupportedFork)]
+        );
+        assert_eq!(case.inner_cases(), vec!["supported".to_string()]);
+
+        let mut tests = case.tests;
+        tests.remove("supported");
+        let case = BlockchainTestCase { tests, skip: None, skipped_tests: BTreeMap::new() }
+            .with_skipped_tests(Path::new("case.json"));
+        assert!(matches!(case.run(), Err(Error::Skipped(SkipReason::UnsupportedFork))));
+    }
+
+    #[test]
+    fn case_skips() {
+        let skips = [
+            ("InitCollision.json::fork_Paris", SkipReason::UpstreamBug),
+            ("InitCollision.json", SkipReason::SlowTest),
+        ];
+        let name = "tests/InitCollision.json::InitCollision[fork_Paris-blockchain_test]";
+
+        assert_eq!(
+            find_case_skip(&skips, "InitCollision.json", name),
+            Some(SkipReason::UpstreamBug)
+        );
+        assert_eq!(
+            find_case_skip(&skips, "InitCollision.json", "InitCollision[fork_Cancun]"),
+            None
+        );
+        assert_eq!(find_case_skip(&skips, "InitCollisionParis.json", name), None);
+    }
+
+    #[test]
+    fn malformed_v_fails_block() {
+        let r = U256::MAX >> 1;
+        let mut rlp = encode_block(Signature::new(r, r, false));
+
+        // Replace `v = 27` with `v = 5`, which is neither a legacy nor an EIP-155 value.
+        let v = rlp.windows(2).position(|w| w == [27, 0xa0]).unwrap();
+        rlp[v] = 5;
+
+        let blocks = [fixture_block(rlp)];
+        assert!(matches!(
+            decode_blocks(&blocks),
+            Err(Error::BlockProcessingFailed { block_number: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn unrecoverable_sender_fails_block() {
+        let blocks = [fixture_block(encode_block(Signature::new(U256::ZERO, U256::ZERO, false)))];
+        assert!(matches!(
+            decode_blocks(&blocks),
+            Err(Error::BlockProcessingFailed { block_number: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn stateless_validation_fails_block() {
+        let chain_spec = ForkSpec::Merge.chain_spec().unwrap();
+
+        // The sender can't be recovered from the signature.
+        let block = signed_block(Signature::new(U256::ZERO, U256::ZERO, false));
+        let inputs =
+            [(RecoveredBlock::new_unhashed(block, vec![Default::default()]), Default::default())];
+        let Err(Error::BlockProcessingFailed { block_number: 1, partial_program_inputs, err }) =
+            validate_stateless(&inputs, &chain_spec)
+        else {
+            panic!("unrecoverable sender passed stateless validation")
+        };
+        assert_eq!(partial_program_inputs.len(), 1);
+        assert!(err.to_string().contains("tx #0"));
+
+        // The witness is missing the parent header.
+        let block = Block { header: Header::default(), body: Default::default() };
+        let inputs = [(RecoveredBlock::new_unhashed(block, Vec::new()), Default::default())];
+        assert!(matches!(
+            validate_stateless(&inputs, &chain_spec),
+            Err(Error::BlockProcessingFailed { block_number: 1, .. })
+        ));
+    }
+}
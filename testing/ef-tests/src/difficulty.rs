@@ -0,0 +1,220 @@
+//! Validation of the proof of work difficulty of pre-merge headers.
+//!
+//! Enabled by setting [`VALIDATE_DIFFICULTY_ENV`]. The difficulty of every block is recomputed
+//! from its parent with the rules of the active fork and compared to the header, which catches
+//! regressions that header validation doesn't, since it doesn't check the difficulty formula.
+
+use crate::models::ForkSpec;
+use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
+use alloy_primitives::U256;
+use reth_chainspec::{ChainSpec, EthereumHardfork, ForkCondition, Hardforks};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Environment variable that enables validating the difficulty of the blocks of the tests.
+pub const VALIDATE_DIFFICULTY_ENV: &str = "EF_TESTS_VALIDATE_DIFFICULTY";
+
+/// The lowest difficulty of a proof of work block.
+const MINIMUM_DIFFICULTY: U256 = U256::from_limbs([131_072, 0, 0, 0]);
+
+/// The divisor of the parent difficulty that the difficulty is adjusted by.
+const DIFFICULTY_BOUND_DIVISOR: U256 = U256::from_limbs([2048, 0, 0, 0]);
+
+/// The number of blocks after which the difficulty bomb doubles.
+const EXP_DIFF_PERIOD: u64 = 100_000;
+
+/// The latest fork first, with the number of blocks that the difficulty bomb is delayed by.
+const BOMB_DELAYS: [(EthereumHardfork, u64); 6] = [
+    (EthereumHardfork::GrayGlacier, 11_400_000),
+    (EthereumHardfork::ArrowGlacier, 10_700_000),
+    (EthereumHardfork::London, 9_700_000),
+    (EthereumHardfork::MuirGlacier, 9_000_000),
+    (EthereumHardfork::Constantinople, 5_000_000),
+    (EthereumHardfork::Byzantium, 3_000_000),
+];
+
+/// The difficulty of a block doesn't match the difficulty computed from its parent.
+#[derive(Debug, Error)]
+#[error("{fork:?} block {block_number} has difficulty {got}, expected {expected}")]
+pub struct DifficultyMismatch {
+    /// The network of the test.
+    pub fork: ForkSpec,
+    /// The number of the block.
+    pub block_number: u64,
+    /// The difficulty computed from the parent.
+    pub expected: U256,
+    /// The difficulty of the block.
+    pub got: U256,
+}
+
+/// Validates the difficulty of consecutive blocks, see [`VALIDATE_DIFFICULTY_ENV`].
+///
+/// The total difficulty is accumulated, so that networks that transition to proof of stake at a
+/// terminal total difficulty are validated too: the blocks before the transition must have the
+/// computed difficulty, and the blocks after must have a difficulty of zero.
+#[derive(Debug)]
+pub struct DifficultyValidator {
+    chain_spec: Arc<ChainSpec>,
+    fork: ForkSpec,
+    total_difficulty: U256,
+}
+
+impl DifficultyValidator {
+    /// Creates a new validator of the chain that starts at the genesis header.
+    pub const fn new(chain_spec: Arc<ChainSpec>, fork: ForkSpec, genesis: &Header) -> Self {
+        Self { chain_spec, fork, total_difficulty: genesis.difficulty }
+    }
+
+    /// Validates the difficulty of the header, which has to be the child of the last validated
+    /// header, or of the genesis header.
+    pub fn validate(&mut self, parent: &Header, header: &Header) -> Result<(), DifficultyMismatch> {
+        let merged = match self.chain_spec.fork(EthereumHardfork::Paris) {
+            ForkCondition::TTD { total_difficulty, .. } => {
+                self.total_difficulty >= total_difficulty
+            }
+            condition => condition.active_at_block(header.number),
+        };
+        let expected =
+            if merged { U256::ZERO } else { expected_difficulty(&self.chain_spec, parent, header) };
+
+        if header.difficulty != expected {
+            return Err(DifficultyMismatch {
+                fork: self.fork,
+                block_number: header.number,
+                expected,
+                got: header.difficulty,
+            })
+        }
+        self.total_difficulty += header.difficulty;
+        Ok(())
+    }
+}
+
+/// Computes the proof of work difficulty of the header from its parent, with the rules of the
+/// fork that is active at the header.
+pub fn expected_difficulty(chain_spec: &ChainSpec, parent: &Header, header: &Header) -> U256 {
+    let number = header.number;
+    let time_delta = header.timestamp.saturating_sub(parent.timestamp);
+
+    // How many times the parent difficulty is adjusted by the bound, positive if the block came
+    // quickly, for EIP-100, EIP-2 and Frontier respectively
+    let steps = if chain_spec.is_fork_active_at_block(EthereumHardfork::Byzantium, number) {
+        let uncles = if parent.ommers_hash == EMPTY_OMMER_ROOT_HASH { 1 } else { 2 };
+        (uncles - (time_delta / 9).min(101) as i64).max(-99)
+    } else if chain_spec.is_fork_active_at_block(EthereumHardfork::Homestead, number) {
+        (1 - (time_delta / 10).min(100) as i64).max(-99)
+    } else if time_delta < 13 {
+        1
+    } else {
+        -1
+    };
+    let adjustment =
+        parent.difficulty / DIFFICULTY_BOUND_DIVISOR * U256::from(steps.unsigned_abs());
+    let difficulty = if steps >= 0 {
+        parent.difficulty.saturating_add(adjustment)
+    } else {
+        parent.difficulty.saturating_sub(adjustment)
+    };
+    let difficulty = difficulty.max(MINIMUM_DIFFICULTY);
+
+    let delay = BOMB_DELAYS
+        .iter()
+        .find(|(fork, _)| chain_spec.is_fork_active_at_block(*fork, number))
+        .map_or(0, |(_, delay)| *delay);
+    let period = number.saturating_sub(delay) / EXP_DIFF_PERIOD;
+    if period > 1 {
+        difficulty.saturating_add(U256::from(1) << (period - 2) as usize)
+    } else {
+        difficulty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_chainspec::ChainSpecBuilder;
+
+    fn header(number: u64, timestamp: u64, difficulty: u64) -> Header {
+        Header { number, timestamp, difficulty: U256::from(difficulty), ..Default::default() }
+    }
+
+    #[test]
+    fn difficulty_by_fork() {
+        let parent = header(0, 0, 1_000_000);
+
+        // Frontier only tells fast and slow blocks apart
+        let frontier = ForkSpec::Frontier.chain_spec().unwrap();
+        let expected = |header| expected_difficulty(&frontier, &parent, &header);
+        assert_eq!(expected(header(1, 12, 0)), U256::from(1_000_488));
+        assert_eq!(expected(header(1, 13, 0)), U256::from(999_512));
+
+        let homestead = ForkSpec::Homestead.chain_spec().unwrap();
+        let expected = |header| expected_difficulty(&homestead, &parent, &header);
+        assert_eq!(expected(header(1, 9, 0)), U256::from(1_000_488));
+        assert_eq!(expected(header(1, 25, 0)), U256::from(999_512));
+        assert_eq!(expected(header(1, 10_000, 0)), U256::from(1_000_000 - 99 * 488));
+
+        // Byzantium targets a shorter block time, and more difficulty if the parent has uncles
+        let byzantium = ForkSpec::Byzantium.chain_spec().unwrap();
+        let expected = |parent: &Header, header| expected_difficulty(&byzantium, parent, &header);
+        assert_eq!(expected(&parent, header(1, 9, 0)), U256::from(1_000_000));
+        let uncles = Header { ommers_hash: B256::with_last_byte(1), ..parent.clone() };
+        assert_eq!(expected(&uncles, header(1, 9, 0)), U256::from(1_000_488));
+
+        // The difficulty never drops below the minimum
+        let parent = header(0, 0, 131_072);
+        assert_eq!(expected(&parent, header(1, 100, 0)), MINIMUM_DIFFICULTY);
+    }
+
+    #[test]
+    fn difficulty_bomb() {
+        let parent = header(299_999, 0, 1_000_000);
+        let frontier = ForkSpec::Frontier.chain_spec().unwrap();
+        assert_eq!(
+            expected_difficulty(&frontier, &parent, &header(300_000, 1, 0)),
+            U256::from(1_000_488 + 2)
+        );
+
+        // Byzantium delays the bomb by three million blocks
+        let parent = header(3_299_999, 0, 1_000_000);
+        let byzantium = ForkSpec::Byzantium.chain_spec().unwrap();
+        assert_eq!(
+            expected_difficulty(&byzantium, &parent, &header(3_300_000, 1, 0)),
+            U256::from(1_000_488 + 2)
+        );
+        let constantinople = ForkSpec::Constantinople.chain_spec().unwrap();
+        assert_eq!(
+            expected_difficulty(&constantinople, &parent, &header(3_300_000, 1, 0)),
+            U256::from(1_000_488)
+        );
+    }
+
+    #[test]
+    fn terminal_total_difficulty() {
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .reset()
+            .london_activated()
+            .with_fork(
+                EthereumHardfork::Paris,
+                ForkCondition::TTD {
+                    activation_block_number: 2,
+                    fork_block: None,
+                    total_difficulty: U256::from(0x40000),
+                },
+            )
+            .build();
+        let genesis = header(0, 0, 0x20000);
+        let mut validator =
+            DifficultyValidator::new(Arc::new(chain_spec), ForkSpec::Unknown, &genesis);
+
+        let block = header(1, 12, 0x20000);
+        validator.validate(&genesis, &block).unwrap();
+
+        // The total difficulty reached the terminal total difficulty, so the block is proof of
+        // stake
+        let err = validator.validate(&block, &header(2, 24, 0x20000)).unwrap_err();
+        assert_eq!((err.block_number, err.expected), (2, U256::ZERO));
+        validator.validate(&block, &header(2, 24, 0)).unwrap();
+    }
+}
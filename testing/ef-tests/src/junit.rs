@@ -0,0 +1,261 @@
+//! JUnit XML reports for test results.
+
+use crate::result::{CaseResult, Error};
+use std::{
+    ffi::OsString,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
+
+/// Environment variable that enables writing a JUnit XML report to the given path.
+///
+/// Every process writes its own report, with the process id appended to the file stem, e.g.
+/// `junit-1234.xml` for `EF_TESTS_JUNIT=junit.xml`. This way, test runners that run every test in
+/// a process of its own don't overwrite the reports of each other.
+pub const JUNIT_ENV: &str = "EF_TESTS_JUNIT";
+
+/// The rendered `<testsuite>` elements of every suite that finished in this process.
+///
+/// Suites are run from separate test threads, so the report is rewritten with all suites seen so
+/// far every time one of them finishes.
+static SUITES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Adds the results of a suite to the JUnit report of this process, if [`JUNIT_ENV`] is set.
+pub(crate) fn write_report(suite_name: &str, path: &Path, results: &[CaseResult]) {
+    let Some(report_path) = std::env::var_os(JUNIT_ENV) else { return };
+    let report_path = process_report_path(Path::new(&report_path), std::process::id());
+
+    let mut suites = SUITES.lock().unwrap_or_else(PoisonError::into_inner);
+    suites.push(render_suite(suite_name, path, results));
+
+    let mut report = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+    for suite in suites.iter() {
+        report.push_str(suite);
+    }
+    report.push_str("</testsuites>\n");
+
+    if let Err(err) = fs::write(&report_path, report) {
+        eprintln!("Failed to write JUnit report to {}: {err}", report_path.display());
+    }
+}
+
+/// Returns the path of the report of the process with the given id, see [`JUNIT_ENV`].
+fn process_report_path(path: &Path, process_id: u32) -> PathBuf {
+    let mut file_name = path.file_stem().map(OsString::from).unwrap_or_default();
+    file_name.push(format!("-{process_id}"));
+    if let Some(extension) = path.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+    path.with_file_name(file_name)
+}
+
+/// The outcome of a single `<testcase>`.
+enum Outcome<'a> {
+    /// The test passed.
+    Passed,
+    /// The test was skipped, for the given reason.
+    Skipped(String),
+    /// The test failed with the given error.
+    Failed(&'a Error),
+}
+
+/// A single `<testcase>` of a suite.
+struct TestCase<'a> {
+    /// The name of the inner test, or of the test file if it has no inner tests.
+    name: String,
+    /// The test file relative to the suite, or the suite if the test case is a test file.
+    classname: String,
+    /// How long the test case took, which is only known for whole test files.
+    time: Option<f64>,
+    /// Whether the test case passed, was skipped or failed.
+    outcome: Outcome<'a>,
+}
+
+/// Returns the test cases of a test file, which are its inner tests if it ran any.
+///
+/// Inner tests fail if they're named by an [`Error::Failures`] of the test file, or with the error
+/// of the test file if it failed as a whole.
+fn test_cases<'a>(suite_name: &str, path: &Path, case: &'a CaseResult) -> Vec<TestCase<'a>> {
+    let file = case.path.strip_prefix(path).unwrap_or(&case.path).display().to_string();
+    if case.inner_cases.is_empty() && case.skipped_cases.is_empty() {
+        let outcome = match &case.result {
+            Ok(()) => Outcome::Passed,
+            Err(Error::Skipped(reason)) => Outcome::Skipped(reason.to_string()),
+            Err(err) => Outcome::Failed(err),
+        };
+        return vec![TestCase {
+            name: file,
+            classname: suite_name.to_string(),
+            time: Some(case.duration.as_secs_f64()),
+            outcome,
+        }]
+    }
+
+    let inner = case.inner_cases.iter().map(|name| {
+        let outcome = match &case.result {
+            Ok(()) => Outcome::Passed,
+            Err(Error::Skipped(reason)) => Outcome::Skipped(reason.to_string()),
+            Err(Error::Failures(failures)) => failures
+                .iter()
+                .find(|(failed, _)| failed == name)
+                .map_or(Outcome::Passed, |(_, err)| Outcome::Failed(err)),
+            Err(err) => Outcome::Failed(err),
+        };
+        (name.clone(), outcome)
+    });
+    let skipped = case
+        .skipped_cases
+        .iter()
+        .map(|(name, reason)| (name.clone(), Outcome::Skipped(reason.to_string())));
+    inner
+        .chain(skipped)
+        .map(|(name, outcome)| TestCase { name, classname: file.clone(), time: None, outcome })
+        .collect()
+}
+
+/// Renders the results of a suite as a `<testsuite>` element with one `<testcase>` per inner test
+/// of the test files.
+fn render_suite(suite_name: &str, path: &Path, results: &[CaseResult]) -> String {
+    let cases =
+        results.iter().flat_map(|case| test_cases(suite_name, path, case)).collect::<Vec<_>>();
+    let failures = cases.iter().filter(|case| matches!(case.outcome, Outcome::Failed(_))).count();
+    let skipped = cases.iter().filter(|case| matches!(case.outcome, Outcome::Skipped(_))).count();
+    let time: f64 = results.iter().map(|case| case.duration.as_secs_f64()).sum();
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"  <testsuite name="{}" tests="{}" failures="{failures}" errors="0" skipped="{skipped}" time="{time:.3}">"#,
+        escape(suite_name),
+        cases.len(),
+    );
+
+    for case in cases {
+        let _ = write!(
+            out,
+            r#"    <testcase name="{}" classname="{}""#,
+            escape(&case.name),
+            escape(&case.classname),
+        );
+        if let Some(time) = case.time {
+            let _ = write!(out, r#" time="{time:.3}""#);
+        }
+        match case.outcome {
+            Outcome::Passed => out.push_str("/>\n"),
+            Outcome::Skipped(reason) => {
+                let _ = writeln!(
+                    out,
+                    ">\n      <skipped message=\"{}\"/>\n    </testcase>",
+                    escape(&reason)
+                );
+            }
+            Outcome::Failed(err) => {
+                let message = err.to_string();
+                let _ = writeln!(
+                    out,
+                    ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                    escape(message.lines().next().unwrap_or_default()),
+                    escape(&message)
+                );
+            }
+        }
+    }
+
+    out.push_str("  </testsuite>\n");
+    out
+}
+
+/// Escapes the characters that are not allowed verbatim in XML text and attribute values, and
+/// drops the characters that are not allowed in XML at all, like most control characters.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c < ' ' || c == '\u{fffe}' || c == '\u{ffff}' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SkipReason;
+    use std::time::Duration;
+
+    fn case(path: &str, result: Result<(), Error>) -> CaseResult {
+        CaseResult {
+            desc: "no description".to_string(),
+            path: PathBuf::from("suite").join(path),
+            result,
+            duration: Duration::from_millis(1500),
+            skipped_cases: Vec::new(),
+            inner_cases: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn render() {
+        let results = vec![
+            case("a[fork_Cancun]>b.json", Ok(())),
+            case("skipped.json", Err(Error::Skipped(SkipReason::SlowTest))),
+            case("failed.json", Err(Error::Assertion("x < y".to_string()))),
+        ];
+
+        let suite = render_suite("dir", Path::new("suite"), &results);
+        assert!(suite.starts_with(
+            r#"  <testsuite name="dir" tests="3" failures="1" errors="0" skipped="1" time="4.500">"#
+        ));
+        assert!(suite.contains(
+            r#"<testcase name="a[fork_Cancun]&gt;b.json" classname="dir" time="1.500"/>"#
+        ));
+        assert!(suite.contains(&format!(r#"<skipped message="{}"/>"#, SkipReason::SlowTest)));
+        assert!(suite.contains(r#"<failure message="test failed: x &lt; y">"#));
+    }
+
+    #[test]
+    fn render_inner_cases() {
+        let failures = vec![("b[fork_Prague]".to_string(), Error::Assertion("x".to_string()))];
+        let mut result = case("file.json", Err(Error::Failures(failures)));
+        result.inner_cases = vec!["a[fork_Prague]".to_string(), "b[fork_Prague]".to_string()];
+        result.skipped_cases = vec![("c[fork_Prague]".to_string(), SkipReason::SlowTest)];
+
+        let suite = render_suite("dir", Path::new("suite"), &[result]);
+        assert!(suite.starts_with(
+            r#"  <testsuite name="dir" tests="3" failures="1" errors="0" skipped="1" time="1.500">"#
+        ));
+        assert!(suite.contains(r#"<testcase name="a[fork_Prague]" classname="file.json"/>"#));
+        assert!(suite.contains(
+            "<testcase name=\"b[fork_Prague]\" classname=\"file.json\">\n      \
+             <failure message=\"test failed: x\">"
+        ));
+        assert!(suite.contains(
+            "<testcase name=\"c[fork_Prague]\" classname=\"file.json\">\n      <skipped"
+        ));
+    }
+
+    #[test]
+    fn escape_strips_invalid_characters() {
+        assert_eq!(escape("a\u{0}b\u{1b}[0m\tc\nd\u{ffff}"), "ab[0m\tc\nd");
+        assert_eq!(escape("<&>\"'"), "&lt;&amp;&gt;&quot;&apos;");
+    }
+
+    #[test]
+    fn report_path_per_process() {
+        assert_eq!(
+            process_report_path(Path::new("out/junit.xml"), 42),
+            PathBuf::from("out/junit-42.xml")
+        );
+        assert_eq!(process_report_path(Path::new("junit"), 42), PathBuf::from("junit-42"));
+    }
+}
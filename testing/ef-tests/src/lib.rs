@@ -10,7 +10,10 @@
 use reth_revm as _;
 use revm as _;
 
+pub mod bench;
 pub mod case;
+pub mod difficulty;
+pub mod junit;
 pub mod result;
 pub mod suite;
 
@@ -19,5 +22,5 @@ pub mod cases;
 pub mod models;
 
 pub use case::{Case, Cases};
-pub use result::{CaseResult, Error};
-pub use suite::Suite;
+pub use result::{CaseResult, Error, SkipReason};
+pub use suite::{DiscoveredCase, Suite};
@@ -2,14 +2,32 @@
 
 use crate::{assert::assert_equal, Error};
 use alloy_consensus::Header as RethHeader;
-use alloy_eips::eip4895::Withdrawals;
+use alloy_eips::{
+    eip4895::Withdrawals,
+    eip7685::{Requests, RequestsOrHash},
+    eip7840::BlobParams,
+};
 use alloy_genesis::GenesisAccount;
-use alloy_primitives::{keccak256, Address, Bloom, Bytes, B256, B64, U256};
-use reth_chainspec::{ChainSpec, ChainSpecBuilder, EthereumHardfork, ForkCondition};
-use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
-use reth_primitives_traits::SealedHeader;
+use alloy_primitives::{keccak256, Address, Bloom, Bytes, B256, B64, U128, U256, U64};
+use alloy_rlp::Decodable;
+use alloy_rpc_types_engine::{
+    CancunPayloadFields, ExecutionData, ExecutionPayload, ExecutionPayloadSidecar,
+    PraguePayloadFields,
+};
+use reth_chainspec::{Chain, ChainSpec, ChainSpecBuilder, EthereumHardfork, ForkCondition};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    tables,
+    transaction::DbTx,
+};
+use reth_ethereum_primitives::Block as EthBlock;
+use reth_primitives_traits::{SealedBlock, SealedHeader};
 use serde::Deserialize;
-use std::{collections::BTreeMap, ops::Deref};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Deref,
+    sync::{Arc, OnceLock},
+};
 
 /// The definition of a blockchain test.
 #[derive(Debug, PartialEq, Eq, Deserialize)]
@@ -21,9 +39,10 @@ pub struct BlockchainTest {
     #[serde(rename = "genesisRLP")]
     pub genesis_rlp: Option<Bytes>,
     /// Block data.
+    #[serde(default)]
     pub blocks: Vec<Block>,
     /// The expected post state.
-    pub post_state: Option<BTreeMap<Address, Account>>,
+    pub post_state: Option<BTreeMap<Address, PostAccount>>,
     /// The test pre-state.
     pub pre: State,
     /// Hash of the best block.
@@ -33,6 +52,193 @@ pub struct BlockchainTest {
     #[serde(default)]
     /// Engine spec.
     pub seal_engine: SealEngine,
+    /// Payloads delivered through the Engine API, as emitted by newer fillers.
+    #[serde(default)]
+    pub engine_new_payloads: Option<Vec<EngineNewPayload>>,
+    /// Chain configuration, as emitted by newer fillers.
+    #[serde(default)]
+    pub config: Option<TestConfig>,
+}
+
+impl BlockchainTest {
+    /// Returns the genesis block of the test.
+    ///
+    /// The genesis block is decoded from `genesisRLP` if present, so that any body fields of the
+    /// genesis block are preserved. Otherwise it is built from the genesis header with an empty
+    /// body.
+    pub fn genesis_block(&self) -> Result<SealedBlock<EthBlock>, Error> {
+        let Some(rlp) = &self.genesis_rlp else {
+            return Ok(SealedBlock::from_sealed_parts(
+                self.genesis_block_header.clone().into(),
+                Default::default(),
+            ))
+        };
+
+        let block = SealedBlock::<EthBlock>::decode(&mut rlp.as_ref())?;
+        assert_equal(
+            self.genesis_block_header.hash,
+            block.hash(),
+            "Genesis block hash does not match",
+        )?;
+        Ok(block)
+    }
+
+    /// Returns the chain spec of the test, which is the shared chain spec of its fork unless the
+    /// `config` section overrides the chain id or the blob schedule.
+    ///
+    /// # Errors
+    ///
+    /// If the `config` section specifies something that the chain spec can't represent.
+    pub fn chain_spec(&self) -> Result<Arc<ChainSpec>, Error> {
+        let chain_spec = self.network.chain_spec()?;
+        let Some(config) = &self.config else { return Ok(chain_spec) };
+        if config.network != self.network {
+            return Err(Error::UnsupportedConfig(format!(
+                "network {:?} doesn't match the network {:?} of the test",
+                config.network, self.network
+            )))
+        }
+
+        let mut overridden = (*chain_spec).clone();
+        if let Some(chain_id) = config.chain_id {
+            overridden.chain = Chain::from_id(chain_id.to());
+            overridden.genesis.config.chain_id = chain_id.to();
+        }
+        for (fork, schedule) in &config.blob_schedule {
+            let params = match fork.to_lowercase().as_str() {
+                "cancun" => &mut overridden.blob_params.cancun,
+                "prague" => &mut overridden.blob_params.prague,
+                "osaka" => &mut overridden.blob_params.osaka,
+                _ => {
+                    return Err(Error::UnsupportedConfig(format!(
+                        "blob schedule of unsupported fork {fork}"
+                    )))
+                }
+            };
+            *params = schedule.apply(*params);
+        }
+
+        if overridden == *chain_spec {
+            Ok(chain_spec)
+        } else {
+            Ok(Arc::new(overridden))
+        }
+    }
+}
+
+/// Chain configuration of an Ethereum blockchain test.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestConfig {
+    /// Network spec, which has to match the network of the test.
+    pub network: ForkSpec,
+    /// Chain id, mainnet if absent.
+    #[serde(rename = "chainid")]
+    pub chain_id: Option<U64>,
+    /// Blob parameters by fork name, the defaults of the fork if absent.
+    #[serde(default)]
+    pub blob_schedule: BTreeMap<String, BlobSchedule>,
+}
+
+/// Blob parameters of a fork in the [`TestConfig`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobSchedule {
+    /// Target number of blobs per block.
+    pub target: U64,
+    /// Maximum number of blobs per block.
+    pub max: U64,
+    /// Denominator of the blob base fee update.
+    pub base_fee_update_fraction: U128,
+}
+
+impl BlobSchedule {
+    /// Returns the parameters with the target, maximum and update fraction of the schedule.
+    fn apply(&self, params: BlobParams) -> BlobParams {
+        let max_blob_count = self.max.to();
+        // Until a fork caps it, a transaction can use all blobs of a block
+        let max_blobs_per_tx = if params.max_blobs_per_tx == params.max_blob_count {
+            max_blob_count
+        } else {
+            params.max_blobs_per_tx.min(max_blob_count)
+        };
+        BlobParams {
+            target_blob_count: self.target.to(),
+            max_blob_count,
+            update_fraction: self.base_fee_update_fraction.to(),
+            max_blobs_per_tx,
+            ..params
+        }
+    }
+}
+
+/// A single `engine_newPayload` call in an Ethereum blockchain test.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineNewPayload {
+    /// The raw parameters of the `engine_newPayloadVX` call.
+    pub params: Vec<serde_json::Value>,
+    /// The version of `engine_newPayload` to use.
+    pub new_payload_version: String,
+    /// The version of `engine_forkchoiceUpdated` to use.
+    pub forkchoice_updated_version: Option<String>,
+    /// If the payload should be rejected, `validation_error` is `Some`.
+    pub validation_error: Option<String>,
+    /// The expected status of the payload, derived from `validation_error` if not set.
+    pub expected_status: Option<EnginePayloadStatus>,
+}
+
+impl EngineNewPayload {
+    /// Returns the status the payload is expected to be answered with.
+    pub fn expected_status(&self) -> EnginePayloadStatus {
+        self.expected_status.unwrap_or(if self.validation_error.is_some() {
+            EnginePayloadStatus::Invalid
+        } else {
+            EnginePayloadStatus::Valid
+        })
+    }
+
+    /// Converts the parameters into [`ExecutionData`] according to the `engine_newPayload`
+    /// version.
+    pub fn execution_data(&self) -> Result<ExecutionData, serde_json::Error> {
+        let param = |index: usize| self.params.get(index).cloned().unwrap_or_default();
+
+        let payload: ExecutionPayload = serde_json::from_value(param(0))?;
+        let sidecar = match self.new_payload_version.as_str() {
+            "1" | "2" => ExecutionPayloadSidecar::none(),
+            version => {
+                let cancun = CancunPayloadFields {
+                    versioned_hashes: serde_json::from_value(param(1))?,
+                    parent_beacon_block_root: serde_json::from_value(param(2))?,
+                };
+                if version == "3" {
+                    ExecutionPayloadSidecar::v3(cancun)
+                } else {
+                    let requests: Vec<Bytes> = serde_json::from_value(param(3))?;
+                    ExecutionPayloadSidecar::v4(
+                        cancun,
+                        PraguePayloadFields {
+                            requests: RequestsOrHash::Requests(Requests::new(requests)),
+                        },
+                    )
+                }
+            }
+        };
+
+        Ok(ExecutionData { payload, sidecar })
+    }
+}
+
+/// The status an `engine_newPayload` call is answered with.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum EnginePayloadStatus {
+    /// The payload is valid and was executed.
+    Valid,
+    /// The payload is invalid.
+    Invalid,
+    /// The parent of the payload is unknown.
+    Syncing,
 }
 
 /// A block header in an Ethereum blockchain test.
@@ -252,10 +458,74 @@ impl Account {
             }
         }
 
+        // The expected storage is complete, so any other slot must have been wiped, e.g. when
+        // the account was self-destructed and recreated.
+        for entry in storage_cursor.walk_dup(Some(address), None)? {
+            let (_, entry) = entry?;
+            let slot = U256::from_be_bytes(entry.key.0);
+            if !self.storage.contains_key(&slot) {
+                return Err(Error::Assertion(format!(
+                    "Unexpected slot {slot:?} in the database with value {:?}",
+                    entry.value
+                )))
+            }
+        }
+
         Ok(())
     }
 }
 
+/// The expected state of an account after all blocks of a test have been executed.
+#[derive(Debug, PartialEq, Eq, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum PostAccount {
+    /// The account must not exist, e.g. because it was self-destructed.
+    Absent(AbsentAccount),
+    /// The account must exist and match the expected values.
+    Present(Account),
+}
+
+impl PostAccount {
+    /// Check that the expected post state of the account matches what is in the database.
+    ///
+    /// In case of a mismatch, `Err(Error::Assertion)` is returned.
+    pub fn assert_db(&self, address: Address, tx: &impl DbTx) -> Result<(), Error> {
+        match self {
+            Self::Absent(_) => assert_absent_db(address, tx),
+            Self::Present(account) => account.assert_db(address, tx),
+        }
+    }
+}
+
+/// Marker for an account that must not exist, carried by fixtures as `"shouldnotexist"`.
+#[derive(Debug, PartialEq, Eq, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AbsentAccount {
+    /// The value of the marker, which is not meaningful.
+    #[serde(rename = "shouldnotexist")]
+    pub should_not_exist: serde_json::Value,
+}
+
+/// Check that neither the account nor any of its storage is present in the database.
+///
+/// In case anything remains, `Err(Error::Assertion)` is returned.
+fn assert_absent_db(address: Address, tx: &impl DbTx) -> Result<(), Error> {
+    if let Some(account) = tx.get_by_encoded_key::<tables::PlainAccountState>(&address)? {
+        return Err(Error::Assertion(format!(
+            "Account ({address}) should not exist, but found in DB: {account:?}"
+        )))
+    }
+
+    let mut storage_cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    if let Some((_, entry)) = storage_cursor.seek_exact(address)? {
+        return Err(Error::Assertion(format!(
+            "Account ({address}) should not exist, but found storage in DB: {entry:?}"
+        )))
+    }
+
+    Ok(())
+}
+
 /// Fork specification.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Hash, Ord, Clone, Copy, Deserialize)]
 pub enum ForkSpec {
@@ -319,13 +589,76 @@ pub enum ForkSpec {
     Prague,
     /// Osaka
     Osaka,
+    /// A fork that is not known to the test runner.
+    #[serde(other)]
+    Unknown,
+}
+
+impl ForkSpec {
+    /// All forks that are known to the test runner.
+    pub const KNOWN: [Self; 28] = [
+        Self::Frontier,
+        Self::FrontierToHomesteadAt5,
+        Self::Homestead,
+        Self::HomesteadToDaoAt5,
+        Self::HomesteadToEIP150At5,
+        Self::EIP150,
+        Self::EIP158,
+        Self::EIP158ToByzantiumAt5,
+        Self::Byzantium,
+        Self::ByzantiumToConstantinopleAt5,
+        Self::ByzantiumToConstantinopleFixAt5,
+        Self::Constantinople,
+        Self::ConstantinopleFix,
+        Self::Istanbul,
+        Self::Berlin,
+        Self::BerlinToLondonAt5,
+        Self::London,
+        Self::Merge,
+        Self::ParisToShanghaiAtTime15k,
+        Self::Shanghai,
+        Self::ShanghaiToCancunAtTime15k,
+        Self::MergeEOF,
+        Self::MergeMeterInitCode,
+        Self::MergePush0,
+        Self::Cancun,
+        Self::CancunToPragueAtTime15k,
+        Self::Prague,
+        Self::Osaka,
+    ];
+
+    /// Returns the chain specs of the [known](Self::KNOWN) forks, which are built the first time
+    /// this is called and shared by all tests afterwards.
+    pub fn chain_specs() -> &'static HashMap<Self, Arc<ChainSpec>> {
+        static CHAIN_SPECS: OnceLock<HashMap<ForkSpec, Arc<ChainSpec>>> = OnceLock::new();
+        CHAIN_SPECS.get_or_init(|| {
+            Self::KNOWN
+                .into_iter()
+                .filter_map(|fork| Some((fork, Arc::new(ChainSpec::try_from(fork).ok()?))))
+                .collect()
+        })
+    }
+
+    /// Returns the shared chain spec of the fork, see [`Self::chain_specs`].
+    ///
+    /// # Errors
+    ///
+    /// If the fork is [unknown](Self::Unknown).
+    pub fn chain_spec(self) -> Result<Arc<ChainSpec>, Error> {
+        match Self::chain_specs().get(&self) {
+            Some(chain_spec) => Ok(chain_spec.clone()),
+            None => Ok(Arc::new(self.try_into()?)),
+        }
+    }
 }
 
-impl From<ForkSpec> for ChainSpec {
-    fn from(fork_spec: ForkSpec) -> Self {
+impl TryFrom<ForkSpec> for ChainSpec {
+    type Error = Error;
+
+    fn try_from(fork_spec: ForkSpec) -> Result<Self, Self::Error> {
         let spec_builder = ChainSpecBuilder::mainnet().reset();
 
-        match fork_spec {
+        let spec_builder = match fork_spec {
             ForkSpec::Frontier => spec_builder.frontier_activated(),
             ForkSpec::FrontierToHomesteadAt5 => spec_builder
                 .frontier_activated()
@@ -374,8 +707,11 @@ impl From<ForkSpec> for ChainSpec {
                 .with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(15_000)),
             ForkSpec::Prague => spec_builder.prague_activated(),
             ForkSpec::Osaka => spec_builder.osaka_activated(),
-        }
-        .build()
+            ForkSpec::Unknown => {
+                return Err(Error::UnsupportedConfig("chain spec of an unknown fork".to_string()))
+            }
+        };
+        Ok(spec_builder.build())
     }
 }
 
@@ -489,4 +825,258 @@ mod tests {
         let res = serde_json::from_str::<Vec<Transaction>>(test);
         assert!(res.is_ok(), "Failed to deserialize transaction with error: {res:?}");
     }
+
+    #[test]
+    fn genesis_block_with_withdrawals() {
+        use alloy_eips::eip4895::Withdrawal;
+        use reth_ethereum_primitives::BlockBody;
+
+        let withdrawals = Withdrawals::new(vec![Withdrawal {
+            index: 1,
+            validator_index: 2,
+            address: Address::with_last_byte(3),
+            amount: 4,
+        }]);
+        let block = EthBlock {
+            header: RethHeader {
+                withdrawals_root: Some(alloy_consensus::proofs::calculate_withdrawals_root(
+                    &withdrawals,
+                )),
+                ..Default::default()
+            },
+            body: BlockBody { withdrawals: Some(withdrawals.clone()), ..Default::default() },
+        };
+        let hash = block.header.hash_slow();
+
+        let mut test = BlockchainTest {
+            genesis_block_header: Header {
+                hash,
+                withdrawals_root: block.header.withdrawals_root,
+                ..Default::default()
+            },
+            genesis_rlp: Some(alloy_rlp::encode(&block).into()),
+            blocks: Vec::new(),
+            post_state: None,
+            pre: State::default(),
+            lastblockhash: hash,
+            network: ForkSpec::Shanghai,
+            seal_engine: SealEngine::default(),
+            engine_new_payloads: None,
+            config: None,
+        };
+
+        let genesis = test.genesis_block().unwrap();
+        assert_eq!(genesis.hash(), hash);
+        assert_eq!(genesis.body().withdrawals, Some(withdrawals));
+
+        // Without `genesisRLP`, the body can't be recovered.
+        test.genesis_rlp = None;
+        assert_eq!(test.genesis_block().unwrap().body().withdrawals, None);
+    }
+
+    #[test]
+    fn post_account_deserialize() {
+        let test = r#"{
+            "0x095e7baea6a6c7c4c2dfeb977efac326af552d87" : {
+                "shouldnotexist" : "1"
+            },
+            "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b" : {
+                "balance" : "0x0de0b6b3a7640000",
+                "code" : "0x",
+                "nonce" : "0x00",
+                "storage" : {
+                }
+            }
+        }"#;
+
+        let res = serde_json::from_str::<BTreeMap<Address, PostAccount>>(test).unwrap();
+        let mut accounts = res.values();
+        assert!(matches!(accounts.next(), Some(PostAccount::Absent(_))));
+        assert!(matches!(accounts.next(), Some(PostAccount::Present(_))));
+    }
+
+    /// The post-state assertions that self-destructed accounts are checked with.
+    mod post_state_assertions {
+        use super::*;
+        use alloy_primitives::{address, bytes};
+        use reth_db_api::transaction::DbTxMut;
+        use reth_primitives_traits::StorageEntry;
+        use reth_provider::test_utils::create_test_provider_factory;
+
+        const ADDRESS: Address = address!("0x095e7baea6a6c7c4c2dfeb977efac326af552d87");
+        const CODE: Bytes = bytes!("0x6000ff");
+
+        fn absent() -> PostAccount {
+            PostAccount::Absent(AbsentAccount { should_not_exist: "1".into() })
+        }
+
+        fn present(storage: BTreeMap<U256, U256>) -> PostAccount {
+            PostAccount::Present(Account {
+                balance: U256::ZERO,
+                code: CODE,
+                nonce: U256::from(1),
+                storage,
+            })
+        }
+
+        fn put_account(tx: &impl DbTxMut, bytecode_hash: Option<B256>) {
+            let account =
+                reth_primitives_traits::Account { nonce: 1, balance: U256::ZERO, bytecode_hash };
+            tx.put::<tables::PlainAccountState>(ADDRESS, account).unwrap();
+        }
+
+        fn put_slot(tx: &impl DbTxMut, slot: U256, value: U256) {
+            let entry = StorageEntry { key: B256::new(slot.to_be_bytes()), value };
+            tx.put::<tables::PlainStorageState>(ADDRESS, entry).unwrap();
+        }
+
+        /// An account that must not exist fails the assertion if either the account or any of its
+        /// storage is left in the database.
+        #[test]
+        fn absent_account_rejects_leftover_account_and_storage() {
+            let factory = create_test_provider_factory();
+            let provider = factory.provider_rw().unwrap();
+            let tx = provider.tx_ref();
+
+            absent().assert_db(ADDRESS, tx).unwrap();
+
+            put_slot(tx, U256::from(1), U256::from(1));
+            assert!(matches!(absent().assert_db(ADDRESS, tx), Err(Error::Assertion(_))));
+
+            put_account(tx, Some(keccak256(&CODE)));
+            assert!(matches!(absent().assert_db(ADDRESS, tx), Err(Error::Assertion(_))));
+        }
+
+        /// An account that must exist fails the assertion if its code or its storage is missing
+        /// from the database.
+        #[test]
+        fn present_account_requires_code_and_storage() {
+            let factory = create_test_provider_factory();
+            let provider = factory.provider_rw().unwrap();
+            let tx = provider.tx_ref();
+            let expected = present(BTreeMap::from([(U256::from(1), U256::from(1))]));
+
+            // Code was wiped.
+            put_account(tx, None);
+            put_slot(tx, U256::from(1), U256::from(1));
+            assert!(matches!(expected.assert_db(ADDRESS, tx), Err(Error::Assertion(_))));
+
+            put_account(tx, Some(keccak256(&CODE)));
+            expected.assert_db(ADDRESS, tx).unwrap();
+
+            // Storage was wiped.
+            tx.delete::<tables::PlainStorageState>(ADDRESS, None).unwrap();
+            assert!(matches!(expected.assert_db(ADDRESS, tx), Err(Error::Assertion(_))));
+        }
+
+        /// An account that must exist fails the assertion if the database has storage beyond the
+        /// expected storage, like storage left over from before the account was recreated.
+        #[test]
+        fn present_account_rejects_extra_storage() {
+            let factory = create_test_provider_factory();
+            let provider = factory.provider_rw().unwrap();
+            let tx = provider.tx_ref();
+            let expected = present(BTreeMap::from([(U256::from(2), U256::from(2))]));
+
+            put_account(tx, Some(keccak256(&CODE)));
+            put_slot(tx, U256::from(2), U256::from(2));
+            expected.assert_db(ADDRESS, tx).unwrap();
+
+            put_slot(tx, U256::from(1), U256::from(1));
+            assert!(matches!(expected.assert_db(ADDRESS, tx), Err(Error::Assertion(_))));
+        }
+    }
+
+    #[test]
+    fn chain_specs_are_shared() {
+        for fork in ForkSpec::KNOWN {
+            let chain_spec = fork.chain_spec().unwrap();
+            assert!(Arc::ptr_eq(&chain_spec, &fork.chain_spec().unwrap()));
+            assert_eq!(*chain_spec, ChainSpec::try_from(fork).unwrap());
+        }
+    }
+
+    #[test]
+    fn unknown_fork_has_no_chain_spec() {
+        assert!(matches!(ForkSpec::Unknown.chain_spec(), Err(Error::UnsupportedConfig(_))));
+        assert!(matches!(ChainSpec::try_from(ForkSpec::Unknown), Err(Error::UnsupportedConfig(_))));
+    }
+
+    /// Returns a test of the fork with the chain configuration.
+    fn test_with_config(network: ForkSpec, config: &str) -> BlockchainTest {
+        BlockchainTest {
+            genesis_block_header: Header::default(),
+            genesis_rlp: None,
+            blocks: Vec::new(),
+            post_state: None,
+            pre: State::default(),
+            lastblockhash: B256::ZERO,
+            network,
+            seal_engine: SealEngine::default(),
+            engine_new_payloads: None,
+            config: Some(serde_json::from_str(config).unwrap()),
+        }
+    }
+
+    #[test]
+    fn custom_blob_schedule() {
+        // The config of the blob throughput tests of the Prague suite, with a custom schedule
+        let test = test_with_config(
+            ForkSpec::Prague,
+            r#"{
+                "network": "Prague",
+                "chainid": "0x01",
+                "blobSchedule": {
+                    "Cancun": {
+                        "target": "0x03",
+                        "max": "0x06",
+                        "baseFeeUpdateFraction": "0x32f0ed"
+                    },
+                    "Prague": {
+                        "target": "0x0a",
+                        "max": "0x0f",
+                        "baseFeeUpdateFraction": "0x4c6964"
+                    }
+                }
+            }"#,
+        );
+        let chain_spec = test.chain_spec().unwrap();
+        assert_eq!(chain_spec.chain, Chain::mainnet());
+        assert_eq!(chain_spec.blob_params.cancun, BlobParams::cancun());
+        assert_eq!(
+            chain_spec.blob_params.prague,
+            BlobParams {
+                target_blob_count: 10,
+                max_blob_count: 15,
+                max_blobs_per_tx: 15,
+                ..BlobParams::prague()
+            }
+        );
+
+        // Without overrides, the chain spec of the fork is shared
+        let test =
+            test_with_config(ForkSpec::Prague, r#"{ "network": "Prague", "chainid": "0x01" }"#);
+        assert!(Arc::ptr_eq(&test.chain_spec().unwrap(), &ForkSpec::Prague.chain_spec().unwrap()));
+
+        let test =
+            test_with_config(ForkSpec::Prague, r#"{ "network": "Prague", "chainid": "0x0a" }"#);
+        assert_eq!(test.chain_spec().unwrap().chain, Chain::from_id(10));
+    }
+
+    #[test]
+    fn unsupported_config() {
+        let test = test_with_config(ForkSpec::Prague, r#"{ "network": "Cancun" }"#);
+        assert!(matches!(test.chain_spec(), Err(Error::UnsupportedConfig(_))));
+
+        let test = test_with_config(
+            ForkSpec::Osaka,
+            r#"{
+                "network": "Osaka",
+                "blobSchedule": {
+                    "BPO1": { "target": "0x0a", "max": "0x0f", "baseFeeUpdateFraction": "0x7f5a51" }
+                }
+            }"#,
+        );
+        assert!(matches!(test.chain_spec(), Err(Error::UnsupportedConfig(_))));
+    }
 }
@@ -1,14 +1,53 @@
 //! Test results and errors
 
 use crate::Case;
+use alloy_primitives::B256;
 use reth_db::DatabaseError;
 use reth_ethereum_primitives::Block;
 use reth_primitives_traits::RecoveredBlock;
 use reth_provider::ProviderError;
 use reth_stateless::ExecutionWitness;
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 use thiserror::Error;
 
+/// Environment variable that stops running the remaining tests of a case, and the remaining
+/// sub-folders of a suite, after the first failure.
+pub const FAIL_FAST_ENV: &str = "EF_TESTS_FAIL_FAST";
+
+/// Returns whether [`FAIL_FAST_ENV`] is set.
+pub fn fail_fast() -> bool {
+    std::env::var_os(FAIL_FAST_ENV).is_some()
+}
+
+/// The reason a test was skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SkipReason {
+    /// The test passes, but takes too long to run.
+    SlowTest,
+    /// The test is broken or cannot be supported due to an issue in the upstream test suite.
+    UpstreamBug,
+    /// The test targets a fork that is not supported.
+    UnsupportedFork,
+    /// The test was excluded by a user-provided filter.
+    FilteredByUser,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SlowTest => f.write_str("slow test"),
+            Self::UpstreamBug => f.write_str("upstream bug"),
+            Self::UnsupportedFork => f.write_str("unsupported fork"),
+            Self::FilteredByUser => f.write_str("filtered by user"),
+        }
+    }
+}
+
 /// Test errors
 ///
 /// # Note
@@ -18,8 +57,8 @@ use thiserror::Error;
 #[non_exhaustive]
 pub enum Error {
     /// The test was skipped
-    #[error("test was skipped")]
-    Skipped,
+    #[error("test was skipped: {0}")]
+    Skipped(SkipReason),
     /// Block processing failed
     /// Note: This includes but is not limited to execution.
     /// For example, the header number could be incorrect.
@@ -67,6 +106,25 @@ pub enum Error {
     /// A consensus error occurred.
     #[error("an error occurred during consensus checks: {0}")]
     ConsensusError(#[from] reth_consensus::ConsensusError),
+    /// The `config` section of the test specifies something that can't be represented.
+    #[error("unsupported test config: {0}")]
+    UnsupportedConfig(String),
+    /// Tests of a case failed, with the name of each test and why it failed.
+    #[error("{} of the tests failed:{}", .0.len(), render_failures(.0))]
+    Failures(Vec<(String, Error)>),
+    /// The state root computed from the pre-state does not match the genesis header.
+    #[error("genesis state root mismatch: expected {expected}, got {got}")]
+    GenesisStateRootMismatch {
+        /// The state root of the fixture genesis header.
+        expected: B256,
+        /// The state root computed from the pre-state.
+        got: B256,
+    },
+}
+
+/// Renders the failures of [`Error::Failures`], one per line.
+fn render_failures(failures: &[(String, Error)]) -> String {
+    failures.iter().map(|(name, err)| format!("\n  {name}: {err}")).collect()
 }
 
 impl Error {
@@ -89,22 +147,131 @@ pub struct CaseResult {
     pub path: PathBuf,
     /// The result of the test.
     pub result: Result<(), Error>,
+    /// How long it took to run the test.
+    pub duration: Duration,
+    /// The inner tests that were skipped while the rest of the test ran, and why.
+    pub skipped_cases: Vec<(String, SkipReason)>,
+    /// The names of the inner tests that ran, see [`Case::inner_cases`].
+    pub inner_cases: Vec<String>,
 }
 
 impl CaseResult {
     /// Create a new test result.
-    pub fn new(path: &Path, case: &impl Case, result: Result<(), Error>) -> Self {
-        Self { desc: case.description(), path: path.into(), result }
+    pub fn new(
+        path: &Path,
+        case: &impl Case,
+        result: Result<(), Error>,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            desc: case.description(),
+            path: path.into(),
+            result,
+            duration,
+            skipped_cases: case.skipped_cases(),
+            inner_cases: case.inner_cases(),
+        }
+    }
+
+    /// Creates the result of a test that failed before it could run, e.g. because it couldn't be
+    /// loaded.
+    pub fn failed(path: &Path, error: Error) -> Self {
+        Self {
+            desc: "failed before running".to_string(),
+            path: path.into(),
+            result: Err(error),
+            duration: Duration::ZERO,
+            skipped_cases: Vec::new(),
+            inner_cases: Vec::new(),
+        }
+    }
+}
+
+/// Counts of the outcomes of all cases of a run, which are printed as a single line once the run
+/// finished, whether any case failed or not.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RunSummary {
+    /// The number of cases that were found.
+    pub discovered: usize,
+    /// The number of cases that passed.
+    pub passed: usize,
+    /// The number of cases that failed, and of other checks of the run that failed.
+    pub failed: usize,
+    /// The number of cases that were skipped, by reason.
+    pub skipped: BTreeMap<SkipReason, usize>,
+    /// How long the run took.
+    pub duration: Duration,
+}
+
+impl RunSummary {
+    /// Counts the outcome of a case.
+    pub fn record(&mut self, result: &CaseResult) {
+        match &result.result {
+            Ok(()) => self.passed += 1,
+            Err(Error::Skipped(reason)) => *self.skipped.entry(*reason).or_default() += 1,
+            Err(_) => self.failed += 1,
+        }
+        self.discovered += 1;
+    }
+
+    /// Counts a case that is skipped without running it.
+    pub fn record_skip(&mut self, reason: SkipReason) {
+        *self.skipped.entry(reason).or_default() += 1;
+        self.discovered += 1;
+    }
+
+    /// Counts a failed check of the run that isn't a case.
+    pub const fn record_failure(&mut self) {
+        self.failed += 1;
+    }
+
+    /// Returns the exit code of the run, which only depends on whether anything failed.
+    pub const fn exit_code(&self) -> i32 {
+        if self.failed == 0 {
+            0
+        } else {
+            101
+        }
     }
 }
 
-/// Assert that all the given tests passed and print the results to stdout.
-pub(crate) fn assert_tests_pass(suite_name: &str, path: &Path, results: &[CaseResult]) {
+impl fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Summary: {} cases, {} passed, {} failed, {} skipped",
+            self.discovered,
+            self.passed,
+            self.failed,
+            self.skipped.values().sum::<usize>()
+        )?;
+        if !self.skipped.is_empty() {
+            let reasons = self
+                .skipped
+                .iter()
+                .map(|(reason, count)| format!("{reason}: {count}"))
+                .collect::<Vec<_>>();
+            write!(f, " ({})", reasons.join(", "))?;
+        }
+        write!(f, " in {:.2?}", self.duration)
+    }
+}
+
+/// Prints the results of a suite to stdout, and adds them to the reports.
+pub(crate) fn report_results(suite_name: &str, path: &Path, results: &[CaseResult]) {
     let (passed, failed, skipped) = categorize_results(results);
 
+    write_reports(suite_name, path, results);
     print_results(suite_name, path, &passed, &failed, &skipped);
+}
 
-    assert!(failed.is_empty(), "Some tests failed (see above)");
+/// Adds the results of a suite to the JUnit report and writes the benchmark report, if they are
+/// enabled.
+///
+/// See [`JUNIT_ENV`](crate::junit::JUNIT_ENV) and [`BENCH_ENV`](crate::bench::BENCH_ENV).
+pub fn write_reports(suite_name: &str, path: &Path, results: &[CaseResult]) {
+    crate::junit::write_report(suite_name, path, results);
+    crate::bench::write_report();
 }
 
 /// Categorize test results into `(passed, failed, skipped)`.
@@ -117,7 +284,7 @@ pub(crate) fn categorize_results(
 
     for case in results {
         match case.result.as_ref().err() {
-            Some(Error::Skipped) => skipped.push(case),
+            Some(Error::Skipped(_)) => skipped.push(case),
             Some(_) => failed.push(case),
             None => passed.push(case),
         }
@@ -151,4 +318,76 @@ pub(crate) fn print_results(
         let error = case.result.as_ref().unwrap_err();
         println!("[!] Case {} failed (description: {}): {}", case.path.display(), case.desc, error);
     }
+
+    let skip_reasons = skip_reason_counts(skipped);
+    if !skip_reasons.is_empty() {
+        println!("Skipped by reason:");
+        for (reason, count) in skip_reasons {
+            println!("  {reason}: {count}");
+        }
+    }
+
+    let skipped_cases = passed
+        .iter()
+        .chain(failed)
+        .flat_map(|case| case.skipped_cases.iter().map(move |skip| (case, skip)))
+        .collect::<Vec<_>>();
+    if !skipped_cases.is_empty() {
+        println!("Skipped {} tests within cases:", skipped_cases.len());
+        for (case, (name, reason)) in skipped_cases {
+            println!("[S] Test {name} in case {} skipped: {reason}", case.path.display());
+        }
+    }
+}
+
+/// Count the given skipped test results by their [`SkipReason`].
+pub(crate) fn skip_reason_counts(skipped: &[&CaseResult]) -> BTreeMap<SkipReason, usize> {
+    let mut counts = BTreeMap::new();
+    for case in skipped {
+        if let Err(Error::Skipped(reason)) = &case.result {
+            *counts.entry(*reason).or_default() += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(result: Result<(), Error>) -> CaseResult {
+        CaseResult {
+            desc: String::new(),
+            path: "case.json".into(),
+            result,
+            duration: Duration::ZERO,
+            skipped_cases: Vec::new(),
+            inner_cases: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn run_summary() {
+        let mut summary = RunSummary::default();
+        assert_eq!(
+            summary.to_string(),
+            "Summary: 0 cases, 0 passed, 0 failed, 0 skipped in 0.00ns"
+        );
+        assert_eq!(summary.exit_code(), 0);
+
+        summary.record(&case(Ok(())));
+        summary.record(&case(Err(Error::Skipped(SkipReason::UnsupportedFork))));
+        summary.record_skip(SkipReason::SlowTest);
+        summary.record_skip(SkipReason::SlowTest);
+        assert_eq!(summary.exit_code(), 0);
+
+        summary.record(&case(Err(Error::Assertion("mismatch".to_string()))));
+        summary.duration = Duration::from_millis(1500);
+        assert_eq!(
+            summary.to_string(),
+            "Summary: 5 cases, 1 passed, 1 failed, 3 skipped (slow test: 2, unsupported fork: 1) \
+             in 1.50s"
+        );
+        assert_eq!(summary.exit_code(), 101);
+    }
 }
@@ -1,12 +1,26 @@
 //! Abstractions for groups of tests.
 
 use crate::{
-    case::{Case, Cases},
-    result::assert_tests_pass,
+    case::{run_timed, Case, Cases},
+    result::{fail_fast, report_results, CaseResult, Error, RunSummary, SkipReason},
+};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::Instant,
 };
-use std::path::{Path, PathBuf};
 use walkdir::{DirEntry, WalkDir};
 
+/// A test file of a suite, found without loading it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredCase {
+    /// The path to the test file.
+    pub path: PathBuf,
+    /// Why the test is skipped regardless of its content, if it's skipped, see
+    /// [`Case::skip_reason`].
+    pub skip: Option<SkipReason>,
+}
+
 /// A collection of tests.
 pub trait Suite {
     /// The type of test cases in this suite.
@@ -17,12 +31,41 @@ pub trait Suite {
 
     /// Run all test cases in the suite.
     fn run(&self) {
-        let suite_path = self.suite_path();
-        for entry in WalkDir::new(suite_path).min_depth(1).max_depth(1) {
-            let entry = entry.expect("Failed to read directory");
-            if entry.file_type().is_dir() {
-                self.run_only(entry.file_name().to_string_lossy().as_ref());
-            }
+        self.run_folders(&self.sub_folders());
+    }
+
+    /// Returns the names of the sub-folders of the suite, which [`Self::run`] runs.
+    fn sub_folders(&self) -> Vec<String> {
+        WalkDir::new(self.suite_path())
+            .min_depth(1)
+            .max_depth(1)
+            .into_iter()
+            .map(|entry| entry.expect("Failed to read directory"))
+            .filter(|entry| entry.file_type().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// Finds the test files of the provided sub-folder without loading them, so that each of
+    /// them can be run on its own with [`Self::run_case`].
+    ///
+    /// These are the same files that [`Self::run_only`] runs.
+    fn discover(&self, name: &str) -> Vec<DiscoveredCase> {
+        let suite_path = self.suite_path().join(name);
+        assert!(suite_path.exists(), "Test suite path does not exist: {suite_path:?}");
+
+        find_all_files_with_extension(&suite_path, ".json")
+            .into_iter()
+            .map(|path| DiscoveredCase { skip: Self::Case::skip_reason(&path), path })
+            .collect()
+    }
+
+    /// Loads and runs a single test file, which fails if it can't be loaded.
+    fn run_case(path: &Path) -> CaseResult {
+        Self::Case::before_all();
+        match Self::Case::load(path) {
+            Ok(case) => run_timed(path, &case),
+            Err(err) => CaseResult::failed(path, err),
         }
     }
 
@@ -32,26 +75,67 @@ pub trait Suite {
     ///
     /// This recursively finds every test description in the resulting path.
     fn run_only(&self, name: &str) {
+        self.run_folders(&[name.to_string()]);
+    }
+
+    /// Runs the test cases of the sub-folders and prints a [`RunSummary`] of all of them.
+    ///
+    /// # Panics
+    ///
+    /// Once all sub-folders ran, if any test case failed. With
+    /// [`FAIL_FAST_ENV`](crate::result::FAIL_FAST_ENV), the remaining sub-folders are not run
+    /// after the first one with a failure.
+    fn run_folders(&self, names: &[String]) {
+        let start = Instant::now();
+        let mut summary = RunSummary::default();
+        for name in names {
+            for result in self.run_folder(name) {
+                summary.record(&result);
+            }
+            if summary.failed > 0 && fail_fast() {
+                break
+            }
+        }
+
+        summary.duration = start.elapsed();
+        println!("{summary}");
+        assert_eq!(summary.exit_code(), 0, "Some tests failed (see above)");
+    }
+
+    /// Loads and runs the test cases of the sub-folder, and reports their results.
+    ///
+    /// A sub-folder that doesn't exist, and test cases that can't be loaded, are reported as
+    /// failed test cases.
+    fn run_folder(&self, name: &str) -> Vec<CaseResult> {
         // Build the path to the test suite directory
         let suite_path = self.suite_path().join(name);
 
         // Verify that the path exists
-        assert!(suite_path.exists(), "Test suite path does not exist: {suite_path:?}");
+        let mut results = Vec::new();
+        if !suite_path.exists() {
+            let error = io::Error::new(io::ErrorKind::NotFound, "test suite path does not exist");
+            results.push(CaseResult::failed(
+                &suite_path,
+                Error::Io { path: suite_path.clone(), error },
+            ));
+        }
+
+        Self::Case::before_all();
 
         // Find all files with the ".json" extension in the test suite directory
-        let test_cases = find_all_files_with_extension(&suite_path, ".json")
-            .into_iter()
-            .map(|test_case_path| {
-                let case = Self::Case::load(&test_case_path).expect("test case should load");
-                (test_case_path, case)
-            })
-            .collect();
+        let mut test_cases = Vec::new();
+        for test_case_path in find_all_files_with_extension(&suite_path, ".json") {
+            match Self::Case::load(&test_case_path) {
+                Ok(case) => test_cases.push((test_case_path, case)),
+                Err(err) => results.push(CaseResult::failed(&test_case_path, err)),
+            }
+        }
 
         // Run the test cases and collect the results
-        let results = Cases { test_cases }.run();
+        results.extend(Cases { test_cases }.run());
 
-        // Assert that all tests in the suite pass
-        assert_tests_pass(name, &suite_path, &results);
+        report_results(name, &suite_path, &results);
+        results
     }
 }
 
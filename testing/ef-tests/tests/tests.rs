@@ -1,112 +1,242 @@
 #![allow(missing_docs)]
-#![cfg(feature = "ef-tests")]
-
-use ef_tests::{cases::blockchain_test::BlockchainTests, suite::Suite};
-use std::path::PathBuf;
-
-macro_rules! general_state_test {
-    ($test_name:ident, $dir:ident) => {
-        #[test]
-        fn $test_name() {
-            reth_tracing::init_test_tracing();
-            let suite_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-                .join("ethereum-tests")
-                .join("BlockchainTests");
-
-            BlockchainTests::new(suite_path)
-                .run_only(&format!("GeneralStateTests/{}", stringify!($dir)));
-        }
-    };
+
+//! Registers every test file of the EF test suites as a test of its own, so that test runners
+//! like `cargo nextest` can retry, partition and time them individually.
+
+use ef_tests::{
+    cases::blockchain_test::BlockchainTests,
+    result::{write_reports, RunSummary},
+    CaseResult, Error, Suite,
+};
+use libtest_mimic::{Arguments, Failed, Trial};
+use std::{
+    collections::BTreeMap,
+    panic,
+    path::PathBuf,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Instant,
+};
+
+/// The folders of `BlockchainTests/GeneralStateTests`, and the name of the group of their tests.
+const GENERAL_STATE_TESTS: &[(&str, &str)] = &[
+    ("shanghai", "Shanghai"),
+    ("st_args_zero_one_balance", "stArgsZeroOneBalance"),
+    ("st_attack", "stAttackTest"),
+    ("st_bad_opcode", "stBadOpcode"),
+    ("st_bugs", "stBugs"),
+    ("st_call_codes", "stCallCodes"),
+    ("st_call_create_call_code", "stCallCreateCallCodeTest"),
+    ("st_call_delegate_codes_call_code_homestead", "stCallDelegateCodesCallCodeHomestead"),
+    ("st_call_delegate_codes_homestead", "stCallDelegateCodesHomestead"),
+    ("st_chain_id", "stChainId"),
+    ("st_code_copy_test", "stCodeCopyTest"),
+    ("st_code_size_limit", "stCodeSizeLimit"),
+    ("st_create2", "stCreate2"),
+    ("st_create", "stCreateTest"),
+    ("st_delegate_call_test_homestead", "stDelegatecallTestHomestead"),
+    ("st_eip150_gas_prices", "stEIP150singleCodeGasPrices"),
+    ("st_eip150", "stEIP150Specific"),
+    ("st_eip158", "stEIP158Specific"),
+    ("st_eip1559", "stEIP1559"),
+    ("st_eip2930", "stEIP2930"),
+    ("st_eip3607", "stEIP3607"),
+    ("st_example", "stExample"),
+    ("st_ext_codehash", "stExtCodeHash"),
+    ("st_homestead", "stHomesteadSpecific"),
+    ("st_init_code", "stInitCodeTest"),
+    ("st_log", "stLogTests"),
+    ("st_mem_expanding_eip150_calls", "stMemExpandingEIP150Calls"),
+    ("st_memory_stress", "stMemoryStressTest"),
+    ("st_memory", "stMemoryTest"),
+    ("st_non_zero_calls", "stNonZeroCallsTest"),
+    ("st_precompiles", "stPreCompiledContracts"),
+    ("st_precompiles2", "stPreCompiledContracts2"),
+    ("st_quadratic_complexity", "stQuadraticComplexityTest"),
+    ("st_random", "stRandom"),
+    ("st_random2", "stRandom2"),
+    ("st_recursive_create", "stRecursiveCreate"),
+    ("st_refund", "stRefundTest"),
+    ("st_return", "stReturnDataTest"),
+    ("st_revert", "stRevertTest"),
+    ("st_self_balance", "stSelfBalance"),
+    ("st_shift", "stShift"),
+    ("st_sload", "stSLoadTest"),
+    ("st_solidity", "stSolidityTest"),
+    ("st_special", "stSpecialTest"),
+    ("st_sstore", "stSStoreTest"),
+    ("st_stack", "stStackTests"),
+    ("st_static_call", "stStaticCall"),
+    ("st_static_flag", "stStaticFlagEnabled"),
+    ("st_system_operations", "stSystemOperationsTest"),
+    ("st_time_consuming", "stTimeConsuming"),
+    ("st_transaction", "stTransactionTest"),
+    ("st_wallet", "stWalletTest"),
+    ("st_zero_calls_revert", "stZeroCallsRevert"),
+    ("st_zero_calls", "stZeroCallsTest"),
+    ("st_zero_knowledge", "stZeroKnowledge"),
+    ("st_zero_knowledge2", "stZeroKnowledge2"),
+    ("vm_tests", "VMTests"),
+];
+
+/// The folders of `BlockchainTests`, and the name of the group of their tests.
+const BLOCKCHAIN_TESTS: &[(&str, &str)] =
+    &[("valid_blocks", "ValidBlocks"), ("invalid_blocks", "InvalidBlocks")];
+
+/// The name of the group of the tests of the execution spec tests.
+const EEST_GROUP: &str = "eest_fixtures";
+
+/// The outcomes of the tests, which the tests share.
+#[derive(Debug, Default)]
+struct Run {
+    /// The results of the tests by the folder they're in, which are reported as a suite.
+    results: BTreeMap<String, (PathBuf, Vec<CaseResult>)>,
+    /// The counts of the outcomes of all tests.
+    summary: RunSummary,
 }
 
-mod general_state_tests {
-    use super::*;
-
-    general_state_test!(shanghai, Shanghai);
-    general_state_test!(st_args_zero_one_balance, stArgsZeroOneBalance);
-    general_state_test!(st_attack, stAttackTest);
-    general_state_test!(st_bad_opcode, stBadOpcode);
-    general_state_test!(st_bugs, stBugs);
-    general_state_test!(st_call_codes, stCallCodes);
-    general_state_test!(st_call_create_call_code, stCallCreateCallCodeTest);
-    general_state_test!(
-        st_call_delegate_codes_call_code_homestead,
-        stCallDelegateCodesCallCodeHomestead
-    );
-    general_state_test!(st_call_delegate_codes_homestead, stCallDelegateCodesHomestead);
-    general_state_test!(st_chain_id, stChainId);
-    general_state_test!(st_code_copy_test, stCodeCopyTest);
-    general_state_test!(st_code_size_limit, stCodeSizeLimit);
-    general_state_test!(st_create2, stCreate2);
-    general_state_test!(st_create, stCreateTest);
-    general_state_test!(st_delegate_call_test_homestead, stDelegatecallTestHomestead);
-    general_state_test!(st_eip150_gas_prices, stEIP150singleCodeGasPrices);
-    general_state_test!(st_eip150, stEIP150Specific);
-    general_state_test!(st_eip158, stEIP158Specific);
-    general_state_test!(st_eip1559, stEIP1559);
-    general_state_test!(st_eip2930, stEIP2930);
-    general_state_test!(st_eip3607, stEIP3607);
-    general_state_test!(st_example, stExample);
-    general_state_test!(st_ext_codehash, stExtCodeHash);
-    general_state_test!(st_homestead, stHomesteadSpecific);
-    general_state_test!(st_init_code, stInitCodeTest);
-    general_state_test!(st_log, stLogTests);
-    general_state_test!(st_mem_expanding_eip150_calls, stMemExpandingEIP150Calls);
-    general_state_test!(st_memory_stress, stMemoryStressTest);
-    general_state_test!(st_memory, stMemoryTest);
-    general_state_test!(st_non_zero_calls, stNonZeroCallsTest);
-    general_state_test!(st_precompiles, stPreCompiledContracts);
-    general_state_test!(st_precompiles2, stPreCompiledContracts2);
-    general_state_test!(st_quadratic_complexity, stQuadraticComplexityTest);
-    general_state_test!(st_random, stRandom);
-    general_state_test!(st_random2, stRandom2);
-    general_state_test!(st_recursive_create, stRecursiveCreate);
-    general_state_test!(st_refund, stRefundTest);
-    general_state_test!(st_return, stReturnDataTest);
-    general_state_test!(st_revert, stRevertTest);
-    general_state_test!(st_self_balance, stSelfBalance);
-    general_state_test!(st_shift, stShift);
-    general_state_test!(st_sload, stSLoadTest);
-    general_state_test!(st_solidity, stSolidityTest);
-    general_state_test!(st_special, stSpecialTest);
-    general_state_test!(st_sstore, stSStoreTest);
-    general_state_test!(st_stack, stStackTests);
-    general_state_test!(st_static_call, stStaticCall);
-    general_state_test!(st_static_flag, stStaticFlagEnabled);
-    general_state_test!(st_system_operations, stSystemOperationsTest);
-    general_state_test!(st_time_consuming, stTimeConsuming);
-    general_state_test!(st_transaction, stTransactionTest);
-    general_state_test!(st_wallet, stWalletTest);
-    general_state_test!(st_zero_calls_revert, stZeroCallsRevert);
-    general_state_test!(st_zero_calls, stZeroCallsTest);
-    general_state_test!(st_zero_knowledge, stZeroKnowledge);
-    general_state_test!(st_zero_knowledge2, stZeroKnowledge2);
-    general_state_test!(vm_tests, VMTests);
+type SharedRun = Arc<Mutex<Run>>;
+
+fn lock(run: &SharedRun) -> MutexGuard<'_, Run> {
+    run.lock().unwrap_or_else(PoisonError::into_inner)
 }
 
-macro_rules! blockchain_test {
-    ($test_name:ident, $dir:ident) => {
-        #[test]
-        fn $test_name() {
-            reth_tracing::init_test_tracing();
-            let suite_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-                .join("ethereum-tests")
-                .join("BlockchainTests");
+/// A folder of test files, whose tests are named after the group.
+#[derive(Debug)]
+struct Folder {
+    group: String,
+    suite_path: PathBuf,
+    name: String,
+}
 
-            BlockchainTests::new(suite_path).run_only(stringify!($dir));
-        }
+impl Folder {
+    fn path(&self) -> PathBuf {
+        self.suite_path.join(&self.name)
+    }
+}
+
+fn main() {
+    let args = Arguments::from_args();
+    reth_tracing::init_test_tracing();
+
+    let start = Instant::now();
+    let run = SharedRun::default();
+    let trials = if cfg!(feature = "ef-tests") { trials(&args, &run) } else { Vec::new() };
+    let _ = libtest_mimic::run(&args, trials);
+    if args.list {
+        return
+    }
+
+    // The exit code only depends on the summary, which counts panicked tests as failed too
+    let mut state = lock(&run);
+    for (name, (path, results)) in &state.results {
+        write_reports(name, path, results);
+    }
+    state.summary.duration = start.elapsed();
+    println!("{}", state.summary);
+    std::process::exit(state.summary.exit_code())
+}
+
+/// Returns whether the filters of the arguments select the test, the way libtest does.
+fn is_selected(args: &Arguments, name: &str) -> bool {
+    let matches = |filter: &str| if args.exact { name == filter } else { name.contains(filter) };
+    args.filter.as_deref().is_none_or(matches) && !args.skip.iter().any(|skip| matches(skip))
+}
+
+/// Returns the folders of the suites.
+///
+/// If the execution spec tests are missing, their group is a single folder that doesn't exist.
+fn folders() -> Vec<Folder> {
+    let ethereum_tests =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("ethereum-tests").join("BlockchainTests");
+    let execution_spec_tests =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("execution-spec-tests");
+    let eest = execution_spec_tests.join("blockchain_tests");
+
+    let general_state_tests = GENERAL_STATE_TESTS.iter().map(|(group, name)| Folder {
+        group: format!("general_state_tests::{group}"),
+        suite_path: ethereum_tests.clone(),
+        name: format!("GeneralStateTests/{name}"),
+    });
+    let blockchain_tests = BLOCKCHAIN_TESTS.iter().map(|(group, name)| Folder {
+        group: group.to_string(),
+        suite_path: ethereum_tests.clone(),
+        name: name.to_string(),
+    });
+    let eest_fixtures = if eest.exists() {
+        BlockchainTests::new(eest.clone())
+            .sub_folders()
+            .into_iter()
+            .map(|name| Folder { group: EEST_GROUP.to_string(), suite_path: eest.clone(), name })
+            .collect()
+    } else {
+        vec![Folder {
+            group: EEST_GROUP.to_string(),
+            suite_path: execution_spec_tests,
+            name: "blockchain_tests".to_string(),
+        }]
     };
+    general_state_tests.chain(blockchain_tests).chain(eest_fixtures).collect()
 }
 
-blockchain_test!(valid_blocks, ValidBlocks);
-blockchain_test!(invalid_blocks, InvalidBlocks);
+/// Returns a test for every test file of the suites, and a failing test for every folder that
+/// doesn't exist.
+fn trials(args: &Arguments, run: &SharedRun) -> Vec<Trial> {
+    let mut trials = Vec::new();
+    for folder in folders() {
+        let path = folder.path();
+        if !path.exists() {
+            let run = run.clone();
+            trials.push(Trial::test(folder.group, move || {
+                lock(&run).summary.record_failure();
+                Err(format!("Test suite path does not exist: {path:?}").into())
+            }));
+            continue
+        }
 
-#[test]
-fn eest_fixtures() {
-    reth_tracing::init_test_tracing();
-    let suite_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("execution-spec-tests")
-        .join("blockchain_tests");
+        let suite = BlockchainTests::new(folder.suite_path.clone());
+        for case in suite.discover(&folder.name) {
+            let relative = case.path.strip_prefix(&path).unwrap_or(&case.path);
+            let name = format!("{}::{}", folder.group, relative.display());
+            // Ignored tests that are selected are reported as skipped, unless they are run
+            if let Some(reason) = case.skip &&
+                !args.list &&
+                !args.ignored &&
+                !args.include_ignored &&
+                is_selected(args, &name)
+            {
+                lock(run).summary.record_skip(reason);
+            }
 
-    BlockchainTests::new(suite_path).run();
+            let test_path = case.path.clone();
+            let suite_name = folder.name.clone();
+            let suite_path = path.clone();
+            let run = run.clone();
+            let trial = Trial::test(name, move || {
+                let result = panic::catch_unwind(|| BlockchainTests::run_case(&test_path))
+                    .unwrap_or_else(|_| {
+                        let error = Error::Assertion("test case panicked".to_string());
+                        CaseResult::failed(&test_path, error)
+                    });
+                let outcome = match &result.result {
+                    Ok(()) | Err(Error::Skipped(_)) => Ok(()),
+                    Err(err) => Err(Failed::from(format!(
+                        "Case {} failed (description: {}): {err}",
+                        test_path.display(),
+                        result.desc
+                    ))),
+                };
+                let mut state = lock(&run);
+                state.summary.record(&result);
+                state
+                    .results
+                    .entry(suite_name)
+                    .or_insert_with(|| (suite_path, Vec::new()))
+                    .1
+                    .push(result);
+                outcome
+            });
+            trials.push(trial.with_ignored_flag(case.skip.is_some()));
+        }
+    }
+    trials
 }